@@ -8,7 +8,7 @@ pragma solidity >=0.8.0;
 // These types represent the output of Sigstore bundle verification.
 //
 // DigestAlgorithm: Hash algorithm identifier
-//   0 = Unknown, 1 = SHA256, 2 = SHA384
+//   0 = Unknown, 1 = SHA256, 2 = SHA384, 3 = Git commit (SHA-1), 4 = SHA512
 //
 // TimestampProofType: Type of timestamp proof used
 //   0 = None, 1 = RFC3161 (TSA), 2 = Rekor (transparency log)
@@ -24,13 +24,16 @@ pragma solidity >=0.8.0;
 error InvalidDataLength();
 error InvalidCertificateHashesLength();
 error InvalidTimestampProofType();
+error UnsupportedOutputVersion(uint16 version);
 
 /// @notice Hash algorithm identifier
-/// @dev 0 = Unknown, 1 = SHA256, 2 = SHA384
+/// @dev 0 = Unknown, 1 = SHA256, 2 = SHA384, 3 = Git commit (SHA-1), 4 = SHA512
 enum DigestAlgorithm {
     Unknown,
     Sha256,
-    Sha384
+    Sha384,
+    GitCommit,
+    Sha512
 }
 
 /// @notice Timestamp proof type identifier
@@ -63,7 +66,12 @@ struct VerificationResult {
 }
 
 library VerificationResultParser {
+    /// @notice Newest output layout version this parser understands
+    uint16 internal constant OUTPUT_VERSION = 1;
+
     function parseVerificationResultBytes(bytes memory data) internal pure returns (VerificationResult memory result) {
+        data = _stripVersionHeader(data);
+
         // Validate minimum data length (8 bytes timestamp + 1 byte proof type + 32 byte tuple offset + ABI data)
         if (data.length < 73) revert InvalidDataLength();
 
@@ -76,6 +84,20 @@ library VerificationResultParser {
         if (result.certificateHashes.length < 2) revert InvalidCertificateHashesLength();
     }
 
+    /// @dev Versioned outputs start with a big-endian uint16 version below 256, i.e. 0x00 and a
+    /// nonzero byte. Unversioned (version 0) outputs start with the signing time, whose two
+    /// high bytes are zero, and are returned unchanged.
+    function _stripVersionHeader(bytes memory data) private pure returns (bytes memory body) {
+        if (data.length < 2 || data[0] != 0 || data[1] == 0) return data;
+        uint16 version = uint8(data[1]);
+        if (version > OUTPUT_VERSION) revert UnsupportedOutputVersion(version);
+        assembly ("memory-safe") {
+            // Reuse the header bytes as the length word of the body
+            body := add(data, 2)
+            mstore(body, sub(mload(data), 2))
+        }
+    }
+
     function _parseHeader(bytes memory data) private pure returns (uint64 signingTime, TimestampProofType proofType) {
         uint8 proofTypeRaw;
         assembly ("memory-safe") {
@@ -171,6 +193,8 @@ library VerificationResultParser {
     function _toDigestAlgorithm(uint8 value) private pure returns (DigestAlgorithm) {
         if (value == 1) return DigestAlgorithm.Sha256;
         if (value == 2) return DigestAlgorithm.Sha384;
+        if (value == 3) return DigestAlgorithm.GitCommit;
+        if (value == 4) return DigestAlgorithm.Sha512;
         return DigestAlgorithm.Unknown;
     }
 }
@@ -4,6 +4,11 @@ pragma solidity ^0.8.0;
 import {Test} from "forge-std/Test.sol";
 
 import {SigstoreAttestationVerifier} from "../src/SigstoreAttestationVerifier.sol";
+import {
+    VerificationResult,
+    VerificationResultParser,
+    UnsupportedOutputVersion
+} from "../src/Types.sol";
 import {ZkCoProcessorType} from "../src/interfaces/ISigstoreAttestationVerifier.sol";
 import {RiscZeroGroth16Verifier} from "risc0/groth16/RiscZeroGroth16Verifier.sol";
 import {ControlID} from "risc0/groth16/ControlID.sol";
@@ -53,6 +58,44 @@ contract SigstoreTest is Test {
         sigstoreVerifier.verifyAndAttestWithZKProof(output, ZkCoProcessorType.Succinct, proof);
     }
 
+    function testEvmCalldataFixtures() public {
+        _checkEvmCalldata("sp1-github", ZkCoProcessorType.Succinct);
+        _checkEvmCalldata("boundless-public", ZkCoProcessorType.RiscZero);
+    }
+
+    function testVersionedOutputParsing() public view {
+        string memory path = string.concat(vm.projectRoot(), "/", "test", "/", "fixtures", "/", "sp1-github.json");
+
+        (bytes memory output,) = _readFixture(path);
+        VerificationResult memory unversioned = this.parse(output);
+        VerificationResult memory versioned = this.parse(bytes.concat(hex"0001", output));
+        assertEq(abi.encode(versioned), abi.encode(unversioned));
+    }
+
+    function testUnsupportedOutputVersion() public {
+        string memory path = string.concat(vm.projectRoot(), "/", "test", "/", "fixtures", "/", "sp1-github.json");
+
+        (bytes memory output,) = _readFixture(path);
+        vm.expectRevert(abi.encodeWithSelector(UnsupportedOutputVersion.selector, uint16(2)));
+        this.parse(bytes.concat(hex"0002", output));
+    }
+
+    function parse(bytes memory output) external pure returns (VerificationResult memory) {
+        return VerificationResultParser.parseVerificationResultBytes(output);
+    }
+
+    /// @dev `<name>.evm.json` is `ProofArtifact::to_verify_and_attest_calldata(Address::ZERO)` of `<name>.json`
+    function _checkEvmCalldata(string memory name, ZkCoProcessorType zkCoProcessor) private {
+        string memory fixtures = string.concat(vm.projectRoot(), "/", "test", "/", "fixtures", "/");
+        (bytes memory output, bytes memory proof) = _readFixture(string.concat(fixtures, name, ".json"));
+        bytes memory data =
+            abi.decode(vm.parseJson(vm.readFile(string.concat(fixtures, name, ".evm.json")), ".data"), (bytes));
+
+        assertEq(data, abi.encodeCall(sigstoreVerifier.verifyAndAttestWithZKProof, (output, zkCoProcessor, proof)));
+        (bool success,) = address(sigstoreVerifier).call(data);
+        assertTrue(success);
+    }
+
     function _readFixture(string memory path) private view returns (bytes memory output, bytes memory proof) {
         string memory json = vm.readFile(path);
         output = abi.decode(vm.parseJson(json, ".journal"), (bytes));
@@ -35,7 +35,7 @@ impl ZkVmProver for PicoProver {
         // Serialize input to bytes
         let input_bytes = input
             .encode_input()
-            .map_err(|e| ZkVmError::InvalidInput(format!("Failed to encode ProverInput: {}", e)))?;
+            .map_err(|e| ZkVmError::InputEncodingError(format!("Failed to encode ProverInput: {}", e)))?;
 
         // Log program identifier
         println!("Program ID: {}", self.program_identifier()?);
@@ -3,6 +3,9 @@
 //! Defines all CLI commands, subcommands, and arguments using clap.
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use sigstore_zkvm_traits::secret::SecretKey;
+use sigstore_verifier::report::ReportFormat;
+use sigstore_verifier::types::environment::SigstoreEnvironment;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -26,6 +29,9 @@ pub enum Commands {
 
     /// Generate a proof of attestation verification
     Prove(ProveArgs),
+
+    /// Verify bundles natively (no proof) and write a report for CI
+    Verify(VerifyArgs),
 }
 
 #[derive(Args, Debug)]
@@ -38,15 +44,87 @@ pub struct ProveArgs {
     #[arg(long = "trust-roots", value_name = "PATH", required = true)]
     pub trust_roots_path: PathBuf,
 
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Unix time to measure the attestation's signing age against; the guest
+    /// commits it and the age so contracts can enforce a maximum age
+    #[arg(long = "reference-time", value_name = "UNIX_SECONDS")]
+    pub reference_time: Option<u64>,
+
+    /// DCAP quote of the TEE runner that built the artifact; the guest verifies it
+    /// and commits a combined TEE + SLSA claim (requires --tee-root)
+    #[arg(long = "tee-quote", value_name = "PATH", requires = "tee_root_path")]
+    pub tee_quote_path: Option<PathBuf>,
+
+    /// Intel SGX root CA certificate (PEM) the quote's PCK chain must end in
+    #[arg(long = "tee-root", value_name = "PATH", requires = "tee_quote_path")]
+    pub tee_root_path: Option<PathBuf>,
+
+    /// Bundle of an independent builder for the same artifact; the guest verifies
+    /// both and commits a reproduced-build claim
+    #[arg(long = "reproduced-bundle", value_name = "PATH", conflicts_with = "tee_quote_path")]
+    pub reproduced_bundle_path: Option<PathBuf>,
+
+    /// Operator key (hex P-256 private key) to sign the artifact metadata with, so relayers can authenticate this prover
+    #[arg(long = "operator-key", env = "PROVER_OPERATOR_KEY", value_name = "KEY", hide_env_values = true)]
+    pub operator_key: Option<SecretKey>,
+
     /// Path to write the proof artifact JSON file
     #[arg(long = "output", value_name = "PATH")]
     pub output_path: Option<PathBuf>,
 
+    /// Compress the guest input with zstd before upload (smaller upload, more guest cycles)
+    #[arg(long = "compress-input")]
+    pub compress_input: bool,
+
+    /// URL to POST proof metadata (program id, bundle digest, status) to when a proof completes or fails
+    #[arg(long = "notify-url", value_name = "URL")]
+    pub notify_url: Option<String>,
+
     /// Proving strategy
     #[command(subcommand)]
     pub strategy: ProveStrategy,
 }
 
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Path to a Sigstore attestation bundle JSON file, or a `gh attestation download`
+    /// .jsonl file with one bundle per line (repeat for several files)
+    #[arg(long = "bundle", value_name = "PATH", required = true)]
+    pub bundle_paths: Vec<PathBuf>,
+
+    /// Path to the trusted root JSONL file
+    #[arg(long = "trust-roots", value_name = "PATH", required = true)]
+    pub trust_roots_path: PathBuf,
+
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Report format: text, json, sarif (GitHub code scanning) or vsa (in-toto VSA)
+    #[arg(long = "report-format", value_name = "FORMAT", default_value = "text")]
+    pub report_format: ReportFormat,
+
+    /// Path to write the report (printed to stdout if omitted)
+    #[arg(long = "report-output", value_name = "PATH")]
+    pub report_output: Option<PathBuf>,
+
+    /// Append verified, failure_reason, subject_digest, repository, workflow_ref
+    /// and signing_time to $GITHUB_OUTPUT
+    #[arg(long = "github-output")]
+    pub github_output: bool,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ProveStrategy {
     /// Prove locally (not yet supported)
@@ -73,7 +151,7 @@ pub struct BoundlessArgs {
         value_name = "WALLET_KEY",
         hide_env_values = true
     )]
-    pub private_key: String,
+    pub private_key: SecretKey,
 
     /// Program URL (optional, uses embedded ELF if not provided)
     #[arg(
@@ -3,6 +3,8 @@
 //! Defines configuration structures for different proving strategies.
 
 use crate::cli::{BoundlessArgs, BoundlessProofType, ProveArgs, ProveStrategy};
+use sigstore_zkvm_traits::secret::SecretKey;
+use sigstore_zkvm_traits::types::InputEncoding;
 
 /// Proving strategy enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,13 +20,14 @@ pub enum ProvingStrategy {
 pub struct Risc0Config {
     pub proving_strategy: ProvingStrategy,
     pub boundless: Option<BoundlessConfig>,
+    pub input_encoding: InputEncoding,
 }
 
 /// Boundless network configuration
 #[derive(Debug, Clone)]
 pub struct BoundlessConfig {
     pub rpc_url: String,
-    pub private_key: String,
+    pub private_key: SecretKey,
     pub program_url: Option<String>,
     pub proof_type: BoundlessProofType,
     pub min_price: Option<u128>,
@@ -44,14 +47,19 @@ impl Risc0Config {
     ///
     /// Returns a Risc0Config with the appropriate strategy and parameters.
     pub fn from_cli_args(args: &ProveArgs) -> Self {
+        let input_encoding = InputEncoding {
+            compress: args.compress_input,
+        };
         match &args.strategy {
             ProveStrategy::Local => Risc0Config {
                 proving_strategy: ProvingStrategy::Local,
                 boundless: None,
+                input_encoding,
             },
             ProveStrategy::Boundless(boundless_args) => Risc0Config {
                 proving_strategy: ProvingStrategy::Boundless,
                 boundless: Some(BoundlessConfig::from_cli_args(boundless_args)),
+                input_encoding,
             },
         }
     }
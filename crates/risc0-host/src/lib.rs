@@ -0,0 +1,11 @@
+//! RISC0 zkVM host library for Sigstore attestation verification
+//!
+//! The `risc0-host` binary is a thin CLI over these modules; other hosts (such
+//! as `zk-host`) use the prover and configuration types directly.
+
+pub mod cli;
+pub mod config;
+pub mod prover;
+pub mod proving {
+    pub mod boundless;
+}
@@ -3,19 +3,20 @@
 //! This CLI tool generates zero-knowledge proofs of Sigstore attestation bundle
 //! verification using RISC0 zkVM.
 
-mod cli;
-mod config;
-mod prover;
-mod proving {
-    pub mod boundless;
-}
+use std::path::Path;
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use sigstore_verifier::types::result::{VerificationOptions, VerificationResult};
+use sigstore_verifier::report::exit_code;
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_zkvm_traits::guest::VerifiedOutput;
+use sigstore_zkvm_traits::notify::{notify_or_warn, ProofNotification, WebhookNotifier};
+use sigstore_zkvm_traits::operator::OperatorKey;
 use sigstore_zkvm_traits::traits::ZkVmProver;
-use sigstore_zkvm_traits::utils::{display_proof_result, display_verification_result, write_proof_artifact, ProofArtifact};
-use sigstore_zkvm_traits::workflow::prepare_guest_input_local;
+use sigstore_zkvm_traits::utils::{display_proof_result, display_verification_result, write_github_output, write_proof_artifact, write_report, sign_proof_artifact, ProofArtifact};
+use sigstore_zkvm_traits::workflow::{
+    load_tee_evidence, prepare_guest_input_for_environment, verify_local_file_for_environment,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -23,15 +24,18 @@ async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
     // Parse CLI arguments
-    let cli = crate::cli::Cli::parse();
+    let cli = risc0_host::cli::Cli::parse();
 
     match cli.command {
-        crate::cli::Commands::ImageId => {
+        risc0_host::cli::Commands::ImageId => {
             handle_image_id()?;
         }
-        crate::cli::Commands::Prove(args) => {
+        risc0_host::cli::Commands::Prove(args) => {
             handle_prove(args).await?;
         }
+        risc0_host::cli::Commands::Verify(args) => {
+            handle_verify(args)?;
+        }
     }
 
     Ok(())
@@ -42,13 +46,13 @@ async fn main() -> Result<()> {
 /// Displays the RISC0 ImageID of the guest program.
 fn handle_image_id() -> Result<()> {
     // Create prover to get image ID
-    let prover = crate::prover::Risc0Prover::new()
+    let prover = risc0_host::prover::Risc0Prover::new()
         .context("Failed to create RISC0 prover")?;
 
     let image_id = prover.program_identifier()
         .context("Failed to get program identifier")?;
 
-    let circuit_version = crate::prover::Risc0Prover::circuit_version();
+    let circuit_version = risc0_host::prover::Risc0Prover::circuit_version();
 
     println!("Image ID:        {}", image_id);
     println!("Circuit Version: {}", circuit_version);
@@ -56,10 +60,43 @@ fn handle_image_id() -> Result<()> {
     Ok(())
 }
 
+/// Handle the verify command
+///
+/// Verifies bundles natively, without generating a proof, and renders a
+/// report for CI systems. If any bundle fails, the process exits with the
+/// code of the first failure's class (see `FailureClass`).
+fn handle_verify(args: risc0_host::cli::VerifyArgs) -> Result<()> {
+    let options = load_verification_options(args.policy_path.as_deref())?;
+    let mut reports = Vec::with_capacity(args.bundle_paths.len());
+    for bundle_path in &args.bundle_paths {
+        let bundle_reports = verify_local_file_for_environment(
+            bundle_path,
+            &args.trust_roots_path,
+            &args.environment,
+            options.clone(),
+        )
+        .context(format!("Failed to verify bundle: {}", bundle_path.display()))?;
+        reports.extend(bundle_reports);
+    }
+
+    write_report(&reports, args.report_format, args.report_output.as_deref())?;
+    if args.github_output {
+        write_github_output(&reports)?;
+    }
+
+    let code = exit_code(&reports);
+    if code != 0 {
+        let failed = reports.iter().filter(|report| !report.verified()).count();
+        eprintln!("❌ {} of {} bundles failed verification", failed, reports.len());
+        std::process::exit(code.into());
+    }
+    Ok(())
+}
+
 /// Handle the prove command
 ///
 /// Generates a proof of Sigstore attestation verification.
-async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
+async fn handle_prove(args: risc0_host::cli::ProveArgs) -> Result<()> {
     println!("RISC0 Sigstore Proof Generation");
     println!("================================\n");
 
@@ -68,36 +105,89 @@ async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
     println!("   Bundle:       {}", args.bundle_path.display());
     println!("   Trusted Root: {}", args.trust_roots_path.display());
 
-    let verification_options = VerificationOptions {
-        expected_digest: None,
-        expected_issuer: None,
-        expected_subject: None,
-    };
+    let verification_options = load_verification_options(args.policy_path.as_deref())?;
+    let operator_key = args
+        .operator_key
+        .as_ref()
+        .map(OperatorKey::from_secret)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?;
 
-    let prover_input = prepare_guest_input_local(
+    let prover_input = prepare_guest_input_for_environment(
         &args.bundle_path,
         &args.trust_roots_path,
+        &args.environment,
         verification_options,
     )
     .context("Failed to prepare guest input")?;
+    let prover_input = match args.reference_time {
+        Some(reference_time) => prover_input.with_reference_time(reference_time),
+        None => prover_input,
+    };
+    let prover_input = match (&args.tee_quote_path, &args.tee_root_path) {
+        (Some(quote_path), Some(root_path)) => prover_input.with_tee_evidence(
+            load_tee_evidence(quote_path, root_path).context("Failed to load TEE evidence")?,
+        ),
+        _ => prover_input,
+    };
+    let prover_input = match &args.reproduced_bundle_path {
+        Some(path) => prover_input.with_reproduced_bundle(
+            std::fs::read(path).with_context(|| format!("Failed to read reproduced bundle {}", path.display()))?,
+        ),
+        None => prover_input,
+    };
 
     println!("✓ Guest input prepared\n");
 
     // Step 2: Create prover
     println!("🔧 Initializing RISC0 prover...");
-    let prover = crate::prover::Risc0Prover::new()
+    let prover = risc0_host::prover::Risc0Prover::new()
         .context("Failed to create RISC0 prover")?;
     println!("✓ Prover initialized\n");
 
     // Step 3: Build config
-    let config = crate::config::Risc0Config::from_cli_args(&args);
+    let config = risc0_host::config::Risc0Config::from_cli_args(&args);
 
     // Step 4: Generate proof
     println!("⚙️  Generating proof...");
-    let (journal, seal) = prover
-        .prove(&config, &prover_input)
-        .await
-        .context("Failed to generate proof")?;
+    let notifier = args
+        .notify_url
+        .as_deref()
+        .map(WebhookNotifier::new)
+        .transpose()
+        .context("Failed to create webhook notifier")?;
+    let program_id = format!("0x{}", prover.program_identifier()?);
+    let bundle_name = args.bundle_path.display().to_string();
+    let circuit_version = risc0_host::prover::Risc0Prover::circuit_version();
+    let (journal, seal) = match prover.prove(&config, &prover_input).await {
+        Ok(proved) => proved,
+        Err(e) => {
+            if let Some(ref notifier) = notifier {
+                let notification = ProofNotification::failed(
+                    "risc0",
+                    &program_id,
+                    &circuit_version,
+                    &bundle_name,
+                    &prover_input.bundle_json,
+                    &e.to_string(),
+                );
+                notify_or_warn(notifier, &notification).await;
+            }
+            return Err(e).context("Failed to generate proof");
+        }
+    };
+
+    if let Some(ref notifier) = notifier {
+        let notification = ProofNotification::proved(
+            "risc0",
+            &program_id,
+            &circuit_version,
+            &bundle_name,
+            &prover_input.bundle_json,
+            &journal,
+        );
+        notify_or_warn(notifier, &notification).await;
+    }
 
     println!("✓ Proof generated successfully\n");
 
@@ -106,22 +196,34 @@ async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
 
     // Step 6: Decode and display verification result
     println!("\n🔍 Decoding verification result...");
-    let verification_result = VerificationResult::from_slice(&journal)
-        .map_err(|e| anyhow::anyhow!("Failed to decode verification result from journal: {}", e))?;
+    let prover_output = VerifiedOutput::from_slice(&journal)
+        .map_err(|e| anyhow::anyhow!("Failed to decode prover output from journal: {}", e))?;
+    let verification_result = prover_output
+        .verification_result()
+        .map_err(|e| anyhow::anyhow!("Failed to decode verification result: {}", e))?;
 
     display_verification_result(&verification_result);
+    println!("Options hash:   0x{}", hex::encode(prover_output.options_hash));
+    if prover_output.reference_time != 0 {
+        println!("Reference time: {}", prover_output.reference_time);
+        println!("Signing age:    {}s", prover_output.signing_age);
+    }
 
     // Step 7: Write artifact if output path provided
     if let Some(ref output_path) = args.output_path {
         println!("\n💾 Writing proof artifact...");
     
-        let artifact = ProofArtifact {
+        let mut artifact = ProofArtifact {
             zkvm: "risc0".to_string(),
-            program_id: format!("0x{}", prover.program_identifier()?),
-            circuit_version: crate::prover::Risc0Prover::circuit_version(),
+            program_id,
+            circuit_version,
             journal: format!("0x{}", hex::encode(&journal)),
             proof: format!("0x{}", hex::encode(&seal)),
+            operator_signature: None,
         };
+        if let Some(ref operator_key) = operator_key {
+            sign_proof_artifact(&mut artifact, operator_key, &prover_input.bundle_json)?;
+        }
         
         write_proof_artifact(output_path, &artifact)
             .context("Failed to write proof artifact")?;
@@ -131,3 +233,12 @@ async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// Verification options from the `--policy` file, or the defaults if none was given
+fn load_verification_options(policy_path: Option<&Path>) -> Result<VerificationOptions> {
+    match policy_path {
+        Some(path) => VerificationOptions::from_policy_file(path)
+            .context(format!("Failed to load policy file: {}", path.display())),
+        None => Ok(VerificationOptions::default()),
+    }
+}
@@ -32,8 +32,9 @@ impl ZkVmProver for Risc0Prover {
         input: &ProverInput,
     ) -> Result<(Vec<u8>, Vec<u8>), ZkVmError> {
         // Serialize input to bytes
-        let input_bytes = input.encode_input()
-            .map_err(|e| ZkVmError::InvalidInput(format!("Failed to encode ProverInput: {}", e)))?;
+        let input_bytes = input
+            .encode_input_with(config.input_encoding)
+            .map_err(|e| ZkVmError::InputEncodingError(format!("Failed to encode ProverInput: {}", e)))?;
 
         // Log image ID
         let image_id = compute_image_id(self.elf)
@@ -49,7 +50,10 @@ impl ZkVmProver for Risc0Prover {
 
         let session_info = default_executor()
             .execute(env, self.elf)
-            .map_err(|e| ZkVmError::ProofGenerationError(format!("Failed to execute guest program: {}", e)))?;
+            .map_err(|e| ZkVmError::GuestExecutionError {
+                code: None,
+                message: format!("Failed to execute guest program: {}", e),
+            })?;
 
         let journal = session_info.journal.bytes.to_vec();
 
@@ -72,7 +76,7 @@ impl ZkVmProver for Risc0Prover {
 
                 prove_with_boundless(self.elf, &input_bytes, boundless_config)
                     .await
-                    .map_err(|e| ZkVmError::ProofGenerationError(format!("Boundless proving failed: {}", e)))?
+                    .map_err(|e| ZkVmError::from_network(format!("Boundless proving failed: {}", e)))?
             }
         };
 
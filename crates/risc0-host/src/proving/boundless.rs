@@ -70,8 +70,11 @@ pub async fn prove_with_boundless(
         )
     })?;
 
-    // Parse private key
-    let private_key_bytes = hex::decode(&config.private_key)
+    // Parse private key; the decoded bytes are zeroized on drop
+    let private_key_bytes = config
+        .private_key
+        .decode_hex()
+        .map_err(anyhow::Error::msg)
         .context("Failed to decode private key (must be hex-encoded)")?;
 
     let private_key = PrivateKeySigner::from_slice(&private_key_bytes)
@@ -5,31 +5,17 @@ use std::io::Read;
 use risc0_zkvm::guest::env;
 risc0_zkvm::guest::entry!(main);
 
-use sigstore_verifier::{
-    AttestationVerifier,
-    types::result::VerificationResult
-};
-use sigstore_zkvm_traits::types::ProverInput;
+use sigstore_zkvm_traits::guest::verify_input;
 
 fn main() {
     // read the values passed from host
     let mut input_bytes: Vec<u8> = vec![];
     env::stdin().read_to_end(&mut input_bytes).unwrap();
 
-    let input: ProverInput = ProverInput::parse_input(&input_bytes)
-        .expect("Failed to parse ProverInput");
+    // single bundle or batch, see sigstore_zkvm_traits::guest
+    let output = verify_input(&input_bytes);
 
-    let verifier = AttestationVerifier::new();
+    assert!(output.is_ok(), "Failed to verify bundle: {:?}", output.err());
 
-    let output = verifier.verify_bundle_bytes(
-        &input.bundle_json,
-        input.verification_options,
-        &input.trust_bundle,
-        input.tsa_cert_chain.as_ref(),
-    );
-
-    assert!(output.is_ok(), "Failed to verify bundle");
-
-    let verification_result: VerificationResult = output.unwrap();
-    env::commit_slice(&verification_result.as_slice());
-}
\ No newline at end of file
+    env::commit_slice(&output.unwrap());
+}
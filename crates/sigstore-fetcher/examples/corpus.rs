@@ -0,0 +1,75 @@
+// Manage the pinned sample bundles in samples/corpus.json.
+//
+//   corpus sync                                       fetch missing or changed samples
+//   corpus pin <name> github <owner/repo> <sha256:hex> pin a GitHub artifact attestation
+//   corpus pin <name> npm <package> <version>          pin an npm provenance attestation
+//   corpus pin <name> pypi <project> <version> <file>  pin a PyPI PEP 740 attestation
+//   corpus pin <name> url <url>                        pin a bundle served at a URL
+//
+// Set SAMPLES_DIR to use another samples directory, and GITHUB_TOKEN to
+// avoid GitHub's anonymous rate limit.
+
+use sigstore_fetcher::corpus::{CorpusEntry, CorpusManifest, CorpusSource};
+use std::env;
+use std::path::PathBuf;
+
+fn usage() -> ! {
+    eprintln!("Usage: corpus sync");
+    eprintln!("       corpus pin <name> github <owner/repo> <sha256:hex>");
+    eprintln!("       corpus pin <name> npm <package> <version>");
+    eprintln!("       corpus pin <name> pypi <project> <version> <file>");
+    eprintln!("       corpus pin <name> url <url>");
+    std::process::exit(1);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let samples_dir = env::var("SAMPLES_DIR").map(PathBuf::from).unwrap_or_else(|_| {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples");
+        path
+    });
+    let manifest_path = samples_dir.join("corpus.json");
+    let mut manifest = CorpusManifest::from_path(&manifest_path).expect("Failed to load corpus manifest");
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["sync"] => {
+            let fetched = manifest.materialize(&samples_dir).expect("Failed to materialize corpus");
+            println!("{} of {} samples fetched", fetched.len(), manifest.bundles.len());
+            for name in fetched {
+                println!("  {}", name);
+            }
+        }
+        ["pin", name, kind, rest @ ..] => {
+            let source = match (*kind, rest) {
+                ("github", [repository, subject_digest]) => CorpusSource::Github {
+                    repository: repository.to_string(),
+                    subject_digest: subject_digest.to_string(),
+                },
+                ("npm", [package, version]) => CorpusSource::Npm {
+                    package: package.to_string(),
+                    version: version.to_string(),
+                },
+                ("pypi", [project, version, file]) => CorpusSource::Pypi {
+                    project: project.to_string(),
+                    version: version.to_string(),
+                    file: file.to_string(),
+                },
+                ("url", [url]) => CorpusSource::Url { url: url.to_string() },
+                _ => usage(),
+            };
+            if manifest.bundles.iter().any(|entry| entry.name == *name) {
+                eprintln!("Error: {} is already pinned", name);
+                std::process::exit(1);
+            }
+            let entry = CorpusEntry::pin(name, source).expect("Failed to pin bundle");
+            println!("Pinned {} at {}", entry.name, entry.digest);
+            manifest.bundles.push(entry);
+            manifest.write(&manifest_path).expect("Failed to write corpus manifest");
+            manifest.materialize(&samples_dir).expect("Failed to materialize corpus");
+        }
+        _ => usage(),
+    }
+}
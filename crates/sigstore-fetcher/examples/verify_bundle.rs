@@ -4,12 +4,8 @@
 // For an alternative approach using local trust root bundles (JSONL format),
 // see the verify_bundle_with_trusted_root example.
 
-use sigstore_verifier::fetcher::trust_bundle::{
-    fetch_fulcio_trust_bundle, fetch_trust_bundle_from_url,
-};
-use sigstore_verifier::types::certificate::FulcioInstance;
-use sigstore_verifier::types::result::VerificationOptions;
-use sigstore_verifier::AttestationVerifier;
+use sigstore_fetcher::trust_bundle::{fetch_fulcio_trust_bundle, fetch_trust_bundle_from_url};
+use sigstore_verifier::prelude::*;
 use std::env;
 use std::path::PathBuf;
 
@@ -37,11 +33,7 @@ fn main() {
 
     let verifier = AttestationVerifier::new();
 
-    let options = VerificationOptions {
-        expected_digest: None,
-        expected_issuer: None,
-        expected_subject: None,
-    };
+    let options = VerificationOptions::default();
 
     let fulcio_issuer_chain =
         fetch_fulcio_trust_bundle(&fulcio_instance).expect("Failed to fetch Fulcio trust bundle");
@@ -66,15 +58,15 @@ fn main() {
             println!("✓ Verification SUCCESS\n");
 
             println!("Certificate Chain Hashes:");
-            println!("  Leaf:   {}", hex::encode(&result.certificate_hashes.leaf));
+            println!("  Leaf:   {}", hex::encode(result.certificate_hashes.leaf));
             for (i, hash) in result.certificate_hashes.intermediates.iter().enumerate() {
                 println!("  Int[{}]: {}", i, hex::encode(hash));
             }
-            println!("  Root:   {}", hex::encode(&result.certificate_hashes.root));
+            println!("  Root:   {}", hex::encode(result.certificate_hashes.root));
             println!();
 
             println!("Signing Time: {}", result.signing_time.to_rfc3339());
-            println!("Subject Digest: {}", hex::encode(&result.subject_digest));
+            println!("Subject Digest: {}", result.subject_digest);
 
             if let Some(ref identity) = result.oidc_identity {
                 println!("\nOIDC Identity:");
@@ -0,0 +1,337 @@
+//! Pinned corpus of real-world sample bundles
+//!
+//! The test suites verify bundles published by real projects rather than
+//! bundles we signed ourselves. `samples/corpus.json` lists each one with the
+//! place it was published (GitHub's attestation API, the npm registry, PyPI's
+//! integrity API or a plain URL) and the SHA-256 of its RFC 8785 canonical JSON, so re-fetching a
+//! bundle yields the same bytes to verify even when the service pretty-prints
+//! it differently, and a bundle that changed upstream is rejected instead of
+//! silently replacing the sample.
+//!
+//! [`CorpusManifest::materialize`] writes every bundle that is missing or no
+//! longer matches its pin into the samples directory, and
+//! [`CorpusEntry::pin`] fetches a new bundle and records its digest.
+//!
+//! PyPI serves PEP 740 attestations rather than Sigstore bundles; each one is
+//! converted to the bundle it was made from (see [`pypi_attestation_bundle`])
+//! before it is pinned.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sigstore_verifier::crypto::hash::sha256;
+use sigstore_verifier::crypto::jcs::to_canonical_vec;
+use sigstore_verifier::types::digest::Digest;
+
+use crate::error::CorpusError;
+
+/// Version of the manifest format
+pub const CORPUS_MANIFEST_VERSION: u32 = 1;
+
+/// GitHub REST API base URL
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// npm registry base URL
+pub const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org";
+
+/// PyPI base URL, serving the PEP 740 integrity API
+pub const PYPI_URL: &str = "https://pypi.org";
+
+/// Media type of the bundles PEP 740 attestations convert to
+const BUNDLE_V03_MEDIA_TYPE: &str = "application/vnd.dev.sigstore.bundle.v0.3+json";
+
+/// `samples/corpus.json`: the pinned sample bundles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusManifest {
+    pub version: u32,
+    pub bundles: Vec<CorpusEntry>,
+}
+
+/// One pinned bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    /// File name stem; the bundle is written to `<name>.sigstore.json`
+    pub name: String,
+    pub source: CorpusSource,
+    /// SHA-256 of the bundle's RFC 8785 canonical JSON
+    pub digest: Digest,
+}
+
+/// Where a bundle was published
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CorpusSource {
+    /// GitHub artifact attestations for a subject digest
+    Github {
+        /// `owner/repo`
+        repository: String,
+        /// `sha256:<hex>` digest of the attested artifact
+        subject_digest: String,
+    },
+    /// npm provenance and publish attestations of a package version
+    Npm { package: String, version: String },
+    /// PEP 740 attestations of one distribution file of a PyPI release
+    Pypi {
+        project: String,
+        version: String,
+        /// Distribution file name, e.g. `sampleproject-4.0.0.tar.gz`
+        file: String,
+    },
+    /// A URL serving the bundle JSON itself
+    Url { url: String },
+}
+
+impl CorpusSource {
+    /// Fetch every bundle the source publishes, in the order the service lists them
+    ///
+    /// GitHub requests are authenticated with `GITHUB_TOKEN` if it is set, to
+    /// avoid the anonymous rate limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response has no bundles.
+    pub fn fetch_bundles(&self) -> Result<Vec<Value>, CorpusError> {
+        let url = match self {
+            CorpusSource::Github {
+                repository,
+                subject_digest,
+            } => format!("{}/repos/{}/attestations/{}", GITHUB_API_URL, repository, subject_digest),
+            CorpusSource::Npm { package, version } => {
+                format!("{}/-/npm/v1/attestations/{}@{}", NPM_REGISTRY_URL, package, version)
+            }
+            CorpusSource::Pypi { project, version, file } => {
+                format!("{}/integrity/{}/{}/{}/provenance", PYPI_URL, project, version, file)
+            }
+            CorpusSource::Url { url } => url.clone(),
+        };
+
+        let mut request = reqwest::blocking::Client::new()
+            .get(&url)
+            .header("User-Agent", "sigstore-fetcher")
+            .header("Accept", "application/json");
+        if let (CorpusSource::Github { .. }, Ok(token)) = (self, std::env::var("GITHUB_TOKEN")) {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().map_err(|e| CorpusError::Fetch(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(CorpusError::Fetch(format!("{} returned HTTP {}", url, response.status())));
+        }
+        let body: Value = response.json().map_err(|e| CorpusError::Fetch(e.to_string()))?;
+
+        let bundles = match self {
+            // Both APIs answer {"attestations": [{"bundle": {...}, ...}]}
+            CorpusSource::Github { .. } | CorpusSource::Npm { .. } => body["attestations"]
+                .as_array()
+                .map(|attestations| attestations.iter().filter_map(|a| a.get("bundle").cloned()).collect())
+                .unwrap_or_default(),
+            // {"attestation_bundles": [{"publisher": {...}, "attestations": [...]}]}
+            CorpusSource::Pypi { .. } => body["attestation_bundles"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|bundle| bundle["attestations"].as_array())
+                .flatten()
+                .filter_map(pypi_attestation_bundle)
+                .collect(),
+            CorpusSource::Url { .. } => vec![body],
+        };
+        if bundles.is_empty() {
+            return Err(CorpusError::Fetch(format!("{} returned no bundles", url)));
+        }
+        Ok(bundles)
+    }
+}
+
+/// Convert a PEP 740 attestation to the Sigstore bundle it was made from
+///
+/// The attestation carries the signing certificate, the Rekor entries and
+/// the DSSE statement and signature of the bundle, under other names. PEP 740
+/// envelopes always hold an in-toto statement.
+///
+/// # Returns
+///
+/// The v0.3 bundle, or `None` if the attestation lacks one of those fields
+pub fn pypi_attestation_bundle(attestation: &Value) -> Option<Value> {
+    let material = &attestation["verification_material"];
+    let envelope = &attestation["envelope"];
+    Some(serde_json::json!({
+        "mediaType": BUNDLE_V03_MEDIA_TYPE,
+        "verificationMaterial": {
+            "certificate": {"rawBytes": material["certificate"].as_str()?},
+            "tlogEntries": material["transparency_entries"].as_array()?,
+        },
+        "dsseEnvelope": {
+            "payload": envelope["statement"].as_str()?,
+            "payloadType": "application/vnd.in-toto+json",
+            "signatures": [{"sig": envelope["signature"].as_str()?}],
+        },
+    }))
+}
+
+/// SHA-256 of the RFC 8785 canonical form of a bundle
+///
+/// # Errors
+///
+/// Returns an error if `bundle` is not valid JSON.
+pub fn bundle_digest(bundle: &[u8]) -> Result<Digest, CorpusError> {
+    let value: Value = serde_json::from_slice(bundle).map_err(|e| CorpusError::InvalidBundle(e.to_string()))?;
+    canonical_digest(&value)
+}
+
+fn canonical_digest(bundle: &Value) -> Result<Digest, CorpusError> {
+    let canonical = to_canonical_vec(bundle).map_err(|e| CorpusError::InvalidBundle(e.to_string()))?;
+    Ok(Digest::sha256(sha256(&canonical)))
+}
+
+impl CorpusEntry {
+    /// Fetch the first bundle `source` publishes and pin it
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let entry = CorpusEntry::pin("sigstore-js-3.0.0", CorpusSource::Npm {
+    ///     package: "sigstore".into(),
+    ///     version: "3.0.0".into(),
+    /// })?;
+    /// manifest.bundles.push(entry);
+    /// ```
+    pub fn pin(name: &str, source: CorpusSource) -> Result<Self, CorpusError> {
+        let bundles = source.fetch_bundles()?;
+        let digest = canonical_digest(&bundles[0])?;
+        Ok(CorpusEntry {
+            name: name.to_string(),
+            source,
+            digest,
+        })
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("{}.sigstore.json", self.name)
+    }
+
+    /// Fetch the pinned bundle, pretty-printed
+    ///
+    /// # Errors
+    ///
+    /// Returns `DigestMismatch` if the source no longer publishes a bundle
+    /// with the pinned digest.
+    pub fn fetch(&self) -> Result<Vec<u8>, CorpusError> {
+        for bundle in self.source.fetch_bundles()? {
+            if canonical_digest(&bundle)? == self.digest {
+                return serde_json::to_vec_pretty(&bundle).map_err(|e| CorpusError::InvalidBundle(e.to_string()));
+            }
+        }
+        Err(CorpusError::DigestMismatch {
+            name: self.name.clone(),
+            expected: self.digest.to_string(),
+        })
+    }
+
+    /// Whether `dir` holds this bundle with the pinned digest
+    pub fn is_materialized(&self, dir: &Path) -> bool {
+        fs::read(dir.join(self.file_name()))
+            .ok()
+            .and_then(|bundle| bundle_digest(&bundle).ok())
+            .is_some_and(|digest| digest == self.digest)
+    }
+}
+
+impl CorpusManifest {
+    pub fn from_path(path: &Path) -> Result<Self, CorpusError> {
+        let manifest: CorpusManifest = serde_json::from_slice(&fs::read(path)?)
+            .map_err(|e| CorpusError::InvalidManifest(e.to_string()))?;
+        if manifest.version != CORPUS_MANIFEST_VERSION {
+            return Err(CorpusError::InvalidManifest(format!(
+                "unsupported version {}, expected {}",
+                manifest.version, CORPUS_MANIFEST_VERSION
+            )));
+        }
+        Ok(manifest)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), CorpusError> {
+        let mut json = serde_json::to_vec_pretty(self).map_err(|e| CorpusError::InvalidManifest(e.to_string()))?;
+        json.push(b'\n');
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Write every bundle missing from `dir`, or not matching its pin, into `dir`
+    ///
+    /// # Returns
+    ///
+    /// Names of the bundles that were fetched
+    ///
+    /// # Errors
+    ///
+    /// Returns the first fetch error; bundles written before it are kept.
+    pub fn materialize(&self, dir: &Path) -> Result<Vec<String>, CorpusError> {
+        let mut fetched = Vec::new();
+        for entry in &self.bundles {
+            if entry.is_materialized(dir) {
+                continue;
+            }
+            fs::write(dir.join(entry.file_name()), entry.fetch()?)?;
+            fetched.push(entry.name.clone());
+        }
+        Ok(fetched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_bundle() -> Value {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        serde_json::from_slice(&fs::read(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_pypi_attestation_bundle() {
+        // The sample bundle as PyPI would serve it in a PEP 740 attestation
+        let mut bundle = sample_bundle();
+        let attestation = serde_json::json!({
+            "version": 1,
+            "verification_material": {
+                "certificate": bundle["verificationMaterial"]["certificate"]["rawBytes"],
+                "transparency_entries": bundle["verificationMaterial"]["tlogEntries"],
+            },
+            "envelope": {
+                "statement": bundle["dsseEnvelope"]["payload"],
+                "signature": bundle["dsseEnvelope"]["signatures"][0]["sig"],
+            },
+        });
+
+        // PEP 740 attestations have no RFC 3161 timestamps
+        bundle["verificationMaterial"]
+            .as_object_mut()
+            .unwrap()
+            .remove("timestampVerificationData");
+        assert_eq!(pypi_attestation_bundle(&attestation), Some(bundle));
+
+        let mut unsigned = attestation;
+        unsigned["envelope"].as_object_mut().unwrap().remove("signature");
+        assert_eq!(pypi_attestation_bundle(&unsigned), None);
+    }
+
+    #[test]
+    fn test_pypi_source_round_trip() {
+        let json = r#"{"type":"pypi","project":"sampleproject","version":"4.0.0","file":"sampleproject-4.0.0.tar.gz"}"#;
+        let source: CorpusSource = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            source,
+            CorpusSource::Pypi {
+                project: "sampleproject".to_string(),
+                version: "4.0.0".to_string(),
+                file: "sampleproject-4.0.0.tar.gz".to_string(),
+            }
+        );
+        assert_eq!(serde_json::to_string(&source).unwrap(), json);
+    }
+}
@@ -0,0 +1,69 @@
+use sigstore_verifier::error::CertificateError;
+use sigstore_verifier::parser::certificate::parse_der_certificate;
+use sigstore_verifier::types::certificate::CertificateChain;
+use sigstore_verifier::verifier::revocation::{crl_distribution_points, CrlSet};
+
+/// Fetch a CRL (PEM or DER) from a distribution point URL
+///
+/// # Arguments
+/// * `url` - CRL distribution point, e.g. from `crl_distribution_points`
+///
+/// # Returns
+/// * The response body
+pub fn fetch_crl(url: &str) -> Result<Vec<u8>, CertificateError> {
+    let response = reqwest::blocking::get(url).map_err(|e| CertificateError::CrlFetch(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(CertificateError::CrlFetch(format!(
+            "HTTP error {} for {}",
+            response.status(),
+            url
+        )));
+    }
+
+    response
+        .bytes()
+        .map(|body| body.to_vec())
+        .map_err(|e| CertificateError::CrlFetch(e.to_string()))
+}
+
+/// Fetch the CRLs of every distribution point named by a chain's certificates
+///
+/// Certificates without a CRL distribution points extension are skipped, so
+/// the result may be empty. Each URL is fetched once.
+///
+/// # Arguments
+/// * `chains` - Certificate chains, e.g. the Fulcio trust bundle and TSA chain
+///
+/// # Returns
+/// * `CrlSet` to pass to `AttestationVerifier::with_crls`
+///
+/// # Example
+/// ```ignore
+/// use sigstore_fetcher::crl::fetch_chain_crls;
+///
+/// let crls = fetch_chain_crls(&[&trust_bundle, &tsa_chain])?;
+/// let verifier = AttestationVerifier::new().with_crls(crls);
+/// ```
+pub fn fetch_chain_crls(chains: &[&CertificateChain]) -> Result<CrlSet, CertificateError> {
+    let mut urls: Vec<String> = Vec::new();
+    for chain in chains {
+        let ders = std::iter::once(&chain.leaf)
+            .chain(&chain.intermediates)
+            .filter(|der| !der.is_empty());
+        for der in ders {
+            let cert = parse_der_certificate(der)?;
+            for url in crl_distribution_points(&cert) {
+                if !urls.contains(&url) {
+                    urls.push(url);
+                }
+            }
+        }
+    }
+
+    let mut crls = CrlSet::new();
+    for url in &urls {
+        crls.add_bytes(&fetch_crl(url)?)?;
+    }
+    Ok(crls)
+}
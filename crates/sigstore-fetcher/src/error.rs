@@ -0,0 +1,57 @@
+use sigstore_verifier::error::VerificationError;
+use sigstore_verifier::fetcher::jsonl::diff::TrustRootDiff;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CorpusError {
+    #[error("Failed to fetch sample bundles: {0}")]
+    Fetch(String),
+
+    #[error("Invalid sample bundle: {0}")]
+    InvalidBundle(String),
+
+    #[error("Invalid corpus manifest: {0}")]
+    InvalidManifest(String),
+
+    #[error("No bundle published for {name} matches the pinned digest {expected}")]
+    DigestMismatch { name: String, expected: String },
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum ReferenceError {
+    #[error("Invalid package URL or image reference: {0}")]
+    InvalidReference(String),
+
+    #[error("Cannot resolve a digest for {0}")]
+    UnsupportedPackage(String),
+
+    #[error("Registry lookup failed: {0}")]
+    Registry(String),
+
+    #[error("Failed to fetch attestations: {0}")]
+    Attestations(String),
+
+    #[error("No attestations published in {repository} for {digest}")]
+    NoAttestations { repository: String, digest: String },
+
+    #[error(transparent)]
+    Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, Error)]
+pub enum TrustRootError {
+    #[error("Failed to fetch trusted roots: {0}")]
+    Fetch(String),
+
+    #[error("Invalid trusted roots: {0}")]
+    Parse(#[from] VerificationError),
+
+    #[error("Trusted root change rejected: {0}")]
+    Rejected(Box<TrustRootDiff>),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,37 @@
+//! Network clients for the Sigstore services around verification
+//!
+//! `sigstore-verifier` itself never touches the network: clients fetch trust
+//! bundles, timestamps and log entries and hand them to the verifier. This
+//! crate holds the blocking HTTP clients for doing so, and the keyless signer
+//! built on them, so that the verifier stays free of an HTTP stack for zkVM
+//! guests, WebAssembly and other offline builds.
+//!
+//! Every client can be given an HTTP client built from a
+//! [`network::NetworkConfig`], which pins hosts to addresses, resolves the
+//! rest over DNS-over-HTTPS and, with the `tls-pinning` feature, pins the
+//! keys of TLS endpoints.
+//!
+//! Offline trust material (the `trusted_root.jsonl` written by
+//! `gh attestation trusted-root`) is parsed by `sigstore_verifier::fetcher::jsonl`.
+
+pub mod corpus;
+#[cfg(feature = "revocation")]
+pub mod crl;
+pub mod error;
+pub mod network;
+pub mod reference;
+pub mod rekor;
+pub mod rekor_v2;
+pub mod signer;
+pub mod trust_bundle;
+pub mod trusted_root;
+pub mod tsa;
+
+pub use sigstore_verifier;
+
+use sigstore_verifier::error::VerificationError;
+
+/// Report a failed request, for clients whose errors are not service-specific
+pub(crate) fn http_error(error: reqwest::Error) -> VerificationError {
+    VerificationError::HttpError(error.to_string())
+}
@@ -0,0 +1,420 @@
+//! Name resolution and TLS pinning for the fetcher clients
+//!
+//! Locked-down build environments often restrict DNS, and fetching trust
+//! material should trust as little of the network as possible. A
+//! [`NetworkConfig`] builds the HTTP client that the Fulcio, Rekor, TSA and
+//! trust-root clients use, with:
+//!
+//! - static host pins, resolved without DNS (like `/etc/hosts`)
+//! - DNS-over-HTTPS for every other host, through a JSON resolver such as
+//!   `https://1.1.1.1/dns-query`
+//! - with the `tls-pinning` feature, SHA-256 pins of the SubjectPublicKeyInfo
+//!   a host's chain must contain, on top of the usual certificate validation
+//!
+//! # Example
+//!
+//! ```ignore
+//! use sigstore_fetcher::network::{spki_sha256, NetworkConfig};
+//! use sigstore_fetcher::rekor::{RekorClient, PUBLIC_GOOD_REKOR_URL};
+//!
+//! let client = NetworkConfig::new()
+//!     .with_host("rekor.sigstore.dev", &["10.0.0.5".parse()?])
+//!     .with_doh("https://1.1.1.1/dns-query")
+//!     .with_spki_pin("rekor.sigstore.dev", spki_sha256(&rekor_ca_der)?)
+//!     .client()?;
+//! let rekor = RekorClient::new(PUBLIC_GOOD_REKOR_URL).with_client(client);
+//! ```
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde_json::Value;
+use sigstore_verifier::crypto::hash::sha256;
+use sigstore_verifier::error::{CertificateError, VerificationError};
+use sigstore_verifier::parser::certificate::{extract_subject_public_key_info, parse_der_certificate};
+
+use crate::http_error;
+
+/// DNS record types requested from the DoH resolver: A and AAAA
+const DOH_RECORD_TYPES: [(&str, u64); 2] = [("A", 1), ("AAAA", 28)];
+
+/// How the fetcher clients reach the network
+///
+/// The default configuration resolves with the system resolver and validates
+/// TLS against the usual roots, like `reqwest::blocking::Client::new()`.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkConfig {
+    hosts: HashMap<String, Vec<SocketAddr>>,
+    doh_url: Option<String>,
+    #[cfg(feature = "tls-pinning")]
+    spki_pins: HashMap<String, Vec<[u8; 32]>>,
+}
+
+impl NetworkConfig {
+    /// Configuration with the system resolver and no pins
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `host` to `addresses` without asking any resolver
+    ///
+    /// The port of the request URL is kept. Pinning the DoH resolver's own
+    /// host avoids the one lookup DoH would otherwise leave to the system.
+    pub fn with_host(mut self, host: &str, addresses: &[IpAddr]) -> Self {
+        let addresses = addresses.iter().map(|ip| SocketAddr::new(*ip, 0)).collect();
+        self.hosts.insert(host.to_ascii_lowercase(), addresses);
+        self
+    }
+
+    /// Resolve hosts that are not pinned with DNS-over-HTTPS
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Resolver endpoint of the JSON API (`application/dns-json`),
+    ///   e.g. `https://1.1.1.1/dns-query` or `https://dns.google/resolve`
+    pub fn with_doh(mut self, url: &str) -> Self {
+        self.doh_url = Some(url.to_string());
+        self
+    }
+
+    /// Require `host`'s certificate chain to contain a key with SPKI hash `sha256`
+    ///
+    /// Several pins for one host are alternatives, e.g. the current and the
+    /// next key. The chain is still validated against the system roots;
+    /// pinning only narrows which chains are accepted.
+    #[cfg(feature = "tls-pinning")]
+    pub fn with_spki_pin(mut self, host: &str, sha256: [u8; 32]) -> Self {
+        self.spki_pins.entry(host.to_ascii_lowercase()).or_default().push(sha256);
+        self
+    }
+
+    /// Build a blocking HTTP client with this configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `HttpError` if the client or its TLS configuration cannot be built.
+    pub fn client(&self) -> Result<reqwest::blocking::Client, VerificationError> {
+        let mut builder = reqwest::blocking::Client::builder();
+        for (host, addresses) in &self.hosts {
+            builder = builder.resolve_to_addrs(host, addresses);
+        }
+        if let Some(url) = &self.doh_url {
+            builder = builder.dns_resolver(Arc::new(DohResolver::new(url, &self.hosts)?));
+        }
+        #[cfg(feature = "tls-pinning")]
+        if !self.spki_pins.is_empty() {
+            builder = builder.use_preconfigured_tls(pinning::tls_config(&self.spki_pins)?);
+        }
+        builder.build().map_err(http_error)
+    }
+}
+
+/// SHA-256 of a certificate's DER-encoded SubjectPublicKeyInfo, the value to pin
+///
+/// This is the `pin-sha256` of HPKP, so existing pins can be reused after
+/// base64 decoding.
+pub fn spki_sha256(certificate_der: &[u8]) -> Result<[u8; 32], CertificateError> {
+    let cert = parse_der_certificate(certificate_der)?;
+    Ok(sha256(extract_subject_public_key_info(&cert).raw))
+}
+
+/// Resolver querying a DNS-over-HTTPS JSON API
+struct DohResolver {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl DohResolver {
+    fn new(url: &str, hosts: &HashMap<String, Vec<SocketAddr>>) -> Result<Self, VerificationError> {
+        // The resolver's own host can only be reached through a pin or the system resolver
+        let mut builder = reqwest::Client::builder();
+        for (host, addresses) in hosts {
+            builder = builder.resolve_to_addrs(host, addresses);
+        }
+        Ok(DohResolver {
+            url: url.to_string(),
+            client: builder.build().map_err(http_error)?,
+        })
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let client = self.client.clone();
+        let url = self.url.clone();
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let mut addresses = Vec::new();
+            for (record_type, _) in DOH_RECORD_TYPES {
+                let response: Value = client
+                    .get(&url)
+                    .query(&[("name", host.as_str()), ("type", record_type)])
+                    .header(reqwest::header::ACCEPT, "application/dns-json")
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                addresses.extend(parse_doh_answer(&response)?);
+            }
+            if addresses.is_empty() {
+                return Err(format!("{} returned no addresses for {}", url, host).into());
+            }
+            let addrs: Addrs = Box::new(addresses.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+/// Addresses of the A and AAAA records of a DNS JSON API response
+///
+/// CNAME and other records in the answer are skipped; the resolver has
+/// already followed the CNAME chain.
+fn parse_doh_answer(response: &Value) -> Result<Vec<IpAddr>, String> {
+    match response["Status"].as_u64() {
+        Some(0) => {}
+        // NXDOMAIN and friends: report them like a failed system lookup
+        Some(status) => return Err(format!("DNS-over-HTTPS lookup failed with rcode {}", status)),
+        None => return Err("Invalid DNS-over-HTTPS response: no Status".to_string()),
+    }
+
+    let answers = response["Answer"].as_array().map(Vec::as_slice).unwrap_or_default();
+    answers
+        .iter()
+        .filter(|answer| {
+            let record_type = answer["type"].as_u64();
+            DOH_RECORD_TYPES.iter().any(|(_, code)| record_type == Some(*code))
+        })
+        .map(|answer| {
+            answer["data"]
+                .as_str()
+                .and_then(|data| data.parse().ok())
+                .ok_or_else(|| format!("Invalid address record in DNS-over-HTTPS response: {}", answer))
+        })
+        .collect()
+}
+
+#[cfg(feature = "tls-pinning")]
+mod pinning {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::client::{VerifierBuilderError, WebPkiServerVerifier};
+    use rustls::crypto::{CryptoProvider, WebPkiSupportedAlgorithms};
+    use rustls::pki_types::{CertificateDer, ServerName, TrustAnchor, UnixTime};
+    use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+    use sigstore_verifier::crypto::hash::sha256;
+    use sigstore_verifier::error::VerificationError;
+
+    use super::spki_sha256;
+
+    /// TLS configuration validating against the system roots and enforcing `pins`
+    pub(super) fn tls_config(pins: &HashMap<String, Vec<[u8; 32]>>) -> Result<ClientConfig, VerificationError> {
+        let tls_error = |e: &dyn std::fmt::Display| VerificationError::HttpError(format!("TLS configuration: {}", e));
+
+        let mut roots = RootCertStore::empty();
+        roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let verifier = SpkiPinVerifier::new(roots, provider.clone(), pins.clone()).map_err(|e| tls_error(&e))?;
+
+        let mut config = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| tls_error(&e))?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth();
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+
+    /// WebPKI validation, then a check of the validated chain against the host's SPKI pins
+    ///
+    /// Only the end entity and the certificates of the path WebPKI built to a
+    /// root count: a server could otherwise satisfy a pin by sending the
+    /// pinned certificate along as an unused intermediate.
+    #[derive(Debug)]
+    struct SpkiPinVerifier {
+        inner: Arc<WebPkiServerVerifier>,
+        roots: Arc<RootCertStore>,
+        algorithms: WebPkiSupportedAlgorithms,
+        pins: HashMap<String, Vec<[u8; 32]>>,
+    }
+
+    impl SpkiPinVerifier {
+        fn new(
+            roots: RootCertStore,
+            provider: Arc<CryptoProvider>,
+            pins: HashMap<String, Vec<[u8; 32]>>,
+        ) -> Result<Self, VerifierBuilderError> {
+            let roots = Arc::new(roots);
+            let inner = WebPkiServerVerifier::builder_with_provider(roots.clone(), provider.clone()).build()?;
+            Ok(SpkiPinVerifier {
+                inner,
+                roots,
+                algorithms: provider.signature_verification_algorithms,
+                pins,
+            })
+        }
+
+        /// SPKI digests of the end entity and of the path from it to a root
+        fn verified_chain_spkis(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            intermediates: &[CertificateDer<'_>],
+            now: UnixTime,
+        ) -> Result<Vec<[u8; 32]>, rustls::Error> {
+            let path_error = |e: webpki::Error| rustls::Error::General(format!("Failed to build certificate path: {}", e));
+            let cert = webpki::EndEntityCert::try_from(end_entity).map_err(path_error)?;
+            let path = cert
+                .verify_for_usage(
+                    self.algorithms.all,
+                    &self.roots.roots,
+                    intermediates,
+                    now,
+                    webpki::KeyUsage::server_auth(),
+                    None,
+                    None,
+                )
+                .map_err(path_error)?;
+
+            let mut spkis = Vec::new();
+            for cert in std::iter::once(end_entity.clone()).chain(path.intermediate_certificates().map(|cert| cert.der())) {
+                spkis.push(spki_sha256(&cert).map_err(|e| rustls::Error::General(e.to_string()))?);
+            }
+            spkis.push(sha256(&anchor_spki(path.anchor())));
+            Ok(spkis)
+        }
+    }
+
+    /// DER SubjectPublicKeyInfo of a trust anchor, which keeps only the SEQUENCE contents
+    fn anchor_spki(anchor: &TrustAnchor<'_>) -> Vec<u8> {
+        let contents: &[u8] = anchor.subject_public_key_info.as_ref();
+        let mut der = vec![0x30];
+        match contents.len() {
+            len @ 0..=0x7f => der.push(len as u8),
+            len @ 0x80..=0xff => der.extend([0x81, len as u8]),
+            len => der.extend([0x82, (len >> 8) as u8, len as u8]),
+        }
+        der.extend_from_slice(contents);
+        der
+    }
+
+    impl ServerCertVerifier for SpkiPinVerifier {
+        fn verify_server_cert(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            intermediates: &[CertificateDer<'_>],
+            server_name: &ServerName<'_>,
+            ocsp_response: &[u8],
+            now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            let verified = self
+                .inner
+                .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)?;
+
+            let host = server_name.to_str().to_ascii_lowercase();
+            if let Some(pins) = self.pins.get(&host) {
+                let chain = self.verified_chain_spkis(end_entity, intermediates, now)?;
+                if !chain.iter().any(|spki| pins.contains(spki)) {
+                    return Err(rustls::Error::General(format!(
+                        "No certificate in the chain of {} matches a pinned key",
+                        host
+                    )));
+                }
+            }
+            Ok(verified)
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            self.inner.verify_tls12_signature(message, cert, dss)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            self.inner.verify_tls13_signature(message, cert, dss)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.inner.supported_verify_schemes()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn certificate(name: &str) -> CertificateDer<'static> {
+            let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            path.pop();
+            path.pop();
+            path.push(format!("samples/certs/{}.pem", name));
+            CertificateDer::from(pem::parse(std::fs::read(path).unwrap()).unwrap().into_contents())
+        }
+
+        fn verify_pinned(pinned: &str, intermediates: &[CertificateDer<'_>]) -> Result<ServerCertVerified, rustls::Error> {
+            let mut roots = RootCertStore::empty();
+            roots.add(certificate("tls-root")).unwrap();
+            let pins = HashMap::from([(
+                "rekor.example.com".to_string(),
+                vec![spki_sha256(&certificate(pinned)).unwrap()],
+            )]);
+            let verifier = SpkiPinVerifier::new(roots, Arc::new(rustls::crypto::ring::default_provider()), pins).unwrap();
+            verifier.verify_server_cert(
+                &certificate("tls-leaf"),
+                intermediates,
+                &ServerName::try_from("rekor.example.com").unwrap(),
+                &[],
+                UnixTime::now(),
+            )
+        }
+
+        #[test]
+        fn test_pins_match_only_the_verified_chain() {
+            let intermediates = [certificate("tls-intermediate"), certificate("tls-unused")];
+            for pinned in ["tls-leaf", "tls-intermediate", "tls-root"] {
+                assert!(verify_pinned(pinned, &intermediates).is_ok(), "{} should match", pinned);
+            }
+
+            // Presented by the server, but not on the path to the root
+            assert!(verify_pinned("tls-unused", &intermediates).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_doh_answer() {
+        let response = json!({
+            "Status": 0,
+            "Answer": [
+                { "name": "rekor.sigstore.dev", "type": 5, "data": "rekor.l.sigstore.dev." },
+                { "name": "rekor.l.sigstore.dev", "type": 1, "data": "34.110.217.43" },
+                { "name": "rekor.l.sigstore.dev", "type": 28, "data": "2600:1901:0:5d00::" }
+            ]
+        });
+        assert_eq!(
+            parse_doh_answer(&response).unwrap(),
+            vec!["34.110.217.43".parse::<IpAddr>().unwrap(), "2600:1901:0:5d00::".parse().unwrap()]
+        );
+
+        assert!(parse_doh_answer(&json!({ "Status": 0 })).unwrap().is_empty());
+        assert!(parse_doh_answer(&json!({ "Status": 3 })).is_err());
+        assert!(parse_doh_answer(&json!({ "Status": 0, "Answer": [{ "type": 1, "data": "x" }] })).is_err());
+    }
+}
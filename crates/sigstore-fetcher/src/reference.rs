@@ -0,0 +1,610 @@
+//! Expected digests from package URLs and image references
+//!
+//! Users know what they want to verify by name (`ghcr.io/org/app:v1.2`,
+//! `pkg:npm/left-pad@1.3.0`), while the verifier needs the digest the
+//! attestation's subject must carry. [`resolve_expected_digest`] looks the
+//! digest up: image tags are resolved with a manifest `HEAD` against the
+//! registry (with an anonymous pull token where the registry asks for one),
+//! npm versions through the registry's `dist.integrity`, and references
+//! that already pin a digest (`@sha256:...`, or a purl `checksum` qualifier)
+//! are not looked up at all.
+//!
+//! [`verify_reference`] goes from a reference to a result in one call: it
+//! resolves the digest, fetches the GitHub artifact attestations published
+//! for it and verifies them against the digest.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use base64::prelude::*;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{HeaderMap, ACCEPT, WWW_AUTHENTICATE};
+use reqwest::StatusCode;
+use serde_json::Value;
+use sigstore_verifier::build_tree::{BuildReference, BundleSource};
+use sigstore_verifier::error::VerificationError;
+use sigstore_verifier::types::certificate::CertificateChain;
+use sigstore_verifier::types::digest::{Digest, DigestAlgorithm};
+use sigstore_verifier::types::result::{VerificationOptions, VerificationResult};
+use sigstore_verifier::AttestationVerifier;
+
+use crate::corpus::{GITHUB_API_URL, NPM_REGISTRY_URL};
+use crate::error::ReferenceError;
+
+/// Registry of image references without a registry host
+pub const DEFAULT_REGISTRY: &str = "docker.io";
+
+/// Host serving the Docker Hub registry API
+const DOCKER_HUB_REGISTRY_HOST: &str = "registry-1.docker.io";
+
+/// Manifest media types a registry may resolve a tag to
+const MANIFEST_MEDIA_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+
+const USER_AGENT: &str = "sigstore-fetcher";
+
+/// OCI image reference: `[registry/]repository[:tag][@digest]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageReference {
+    /// Registry host, `docker.io` if the reference has none
+    pub registry: String,
+    /// Repository path, with `library/` prepended for official Docker Hub images
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<Digest>,
+}
+
+impl FromStr for ImageReference {
+    type Err = ReferenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ReferenceError::InvalidReference(s.to_string());
+        let (name, digest) = match s.split_once('@') {
+            Some((name, digest)) => (name, Some(digest.parse::<Digest>().map_err(|_| invalid())?)),
+            None => (s, None),
+        };
+        // A colon after the last slash separates the tag; one before it is a registry port
+        let (name, tag) = match name.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, Some(tag.to_string())),
+            _ => (name, None),
+        };
+
+        // The first component is a registry host only if it looks like one
+        let (registry, repository) = match name.split_once('/') {
+            Some((host, path)) if host.contains(['.', ':']) || host == "localhost" => {
+                (host.to_string(), path.to_string())
+            }
+            _ => (DEFAULT_REGISTRY.to_string(), name.to_string()),
+        };
+        let repository = if registry == DEFAULT_REGISTRY && !repository.contains('/') {
+            format!("library/{}", repository)
+        } else {
+            repository
+        };
+
+        let valid_component = |c: &str| {
+            !c.is_empty()
+                && c.chars().all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || "._-".contains(ch))
+        };
+        if !repository.split('/').all(valid_component) || tag.as_deref().is_some_and(str::is_empty) {
+            return Err(invalid());
+        }
+
+        Ok(ImageReference {
+            registry,
+            repository,
+            tag,
+            digest,
+        })
+    }
+}
+
+impl fmt::Display for ImageReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.registry, self.repository)?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{}", tag)?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+impl ImageReference {
+    /// Digest of the manifest the reference points to
+    ///
+    /// A pinned digest is returned as is; otherwise the tag (`latest` if
+    /// none) is resolved with a `HEAD` request for the manifest, which for a
+    /// multi-platform image yields the digest of its index.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Registry` if the registry cannot be reached, refuses the
+    /// request or does not report a digest.
+    pub fn resolve_digest(&self) -> Result<Digest, ReferenceError> {
+        if let Some(digest) = &self.digest {
+            return Ok(digest.clone());
+        }
+
+        let host = if self.registry == DEFAULT_REGISTRY {
+            DOCKER_HUB_REGISTRY_HOST
+        } else {
+            self.registry.as_str()
+        };
+        let url = format!(
+            "https://{}/v2/{}/manifests/{}",
+            host,
+            self.repository,
+            self.tag.as_deref().unwrap_or("latest")
+        );
+        let client = Client::new();
+        let request = || client.head(&url).header(ACCEPT, MANIFEST_MEDIA_TYPES);
+
+        let mut response = send(request())?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let token = pull_token(&client, response.headers())?;
+            response = send(request().bearer_auth(token))?;
+        }
+        if !response.status().is_success() {
+            return Err(ReferenceError::Registry(format!("{} returned HTTP {}", url, response.status())));
+        }
+
+        response
+            .headers()
+            .get("Docker-Content-Digest")
+            .and_then(|digest| digest.to_str().ok())
+            .ok_or_else(|| ReferenceError::Registry(format!("{} did not report a manifest digest", url)))?
+            .parse()
+            .map_err(|e| ReferenceError::Registry(format!("invalid manifest digest: {}", e)))
+    }
+}
+
+/// Fetch the anonymous pull token a registry's `Bearer` challenge points to
+fn pull_token(client: &Client, headers: &HeaderMap) -> Result<String, ReferenceError> {
+    let challenge = headers
+        .get(WWW_AUTHENTICATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| ReferenceError::Registry("registry requires unsupported authentication".to_string()))?;
+
+    let mut params = BTreeMap::new();
+    for param in challenge.split(',') {
+        if let Some((key, value)) = param.trim().split_once('=') {
+            params.insert(key, value.trim_matches('"'));
+        }
+    }
+    let realm = params
+        .remove("realm")
+        .ok_or_else(|| ReferenceError::Registry("authentication challenge has no realm".to_string()))?;
+
+    let response = send(client.get(realm).query(&params))?;
+    if !response.status().is_success() {
+        return Err(ReferenceError::Registry(format!("{} returned HTTP {}", realm, response.status())));
+    }
+    let body: Value = response.json().map_err(|e| ReferenceError::Registry(e.to_string()))?;
+    body["token"]
+        .as_str()
+        .or(body["access_token"].as_str())
+        .map(str::to_string)
+        .ok_or_else(|| ReferenceError::Registry(format!("{} returned no token", realm)))
+}
+
+fn send(request: RequestBuilder) -> Result<Response, ReferenceError> {
+    request
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .map_err(|e| ReferenceError::Registry(e.to_string()))
+}
+
+/// Package URL: `pkg:type/namespace/name@version?qualifiers#subpath`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageUrl {
+    /// Package type, lowercased, e.g. `npm` or `oci`
+    pub package_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    pub qualifiers: BTreeMap<String, String>,
+    pub subpath: Option<String>,
+}
+
+impl FromStr for PackageUrl {
+    type Err = ReferenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ReferenceError::InvalidReference(s.to_string());
+        let rest = s.strip_prefix("pkg:").ok_or_else(invalid)?.trim_start_matches('/');
+        let (rest, subpath) = match rest.split_once('#') {
+            Some((rest, subpath)) => (rest, Some(percent_decode(subpath.trim_matches('/')).ok_or_else(invalid)?)),
+            None => (rest, None),
+        };
+
+        let (rest, qualifiers) = match rest.split_once('?') {
+            Some((rest, query)) => {
+                let mut qualifiers = BTreeMap::new();
+                for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+                    let (key, value) = pair.split_once('=').ok_or_else(invalid)?;
+                    qualifiers.insert(key.to_ascii_lowercase(), percent_decode(value).ok_or_else(invalid)?);
+                }
+                (rest, qualifiers)
+            }
+            None => (rest, BTreeMap::new()),
+        };
+
+        let (rest, version) = match rest.rsplit_once('@') {
+            Some((rest, version)) => (rest, Some(percent_decode(version).ok_or_else(invalid)?)),
+            None => (rest, None),
+        };
+
+        let (package_type, path) = rest.split_once('/').ok_or_else(invalid)?;
+        let mut segments: Vec<String> = path
+            .trim_matches('/')
+            .split('/')
+            .map(|segment| percent_decode(segment).ok_or_else(invalid))
+            .collect::<Result<_, _>>()?;
+        let name = segments.pop().filter(|name| !name.is_empty()).ok_or_else(invalid)?;
+        if package_type.is_empty() || segments.iter().any(String::is_empty) {
+            return Err(invalid());
+        }
+
+        Ok(PackageUrl {
+            package_type: package_type.to_ascii_lowercase(),
+            namespace: (!segments.is_empty()).then(|| segments.join("/")),
+            name,
+            version,
+            qualifiers,
+            subpath,
+        })
+    }
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+impl PackageUrl {
+    /// Digest of the package the purl points to
+    ///
+    /// A `checksum` qualifier (`sha256:<hex>`, or a comma-separated list of
+    /// which the strongest is used) is returned as is. Otherwise:
+    ///
+    /// * `pkg:oci/...@<digest>` - the version is the manifest digest
+    /// * `pkg:oci` and `pkg:docker` with a tag - resolved against the image's
+    ///   registry (`repository_url` qualifier, Docker Hub by default)
+    /// * `pkg:npm` - the SHA-512 of the tarball, from the npm registry
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedPackage` for other package types, a purl without
+    /// a version, or an unparseable `checksum`.
+    pub fn resolve_digest(&self) -> Result<Digest, ReferenceError> {
+        if let Some(checksums) = self.qualifiers.get("checksum") {
+            return checksums
+                .split(',')
+                .map(str::parse::<Digest>)
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+                .and_then(|digests| digests.into_iter().max_by_key(|d| d.as_bytes().len()))
+                .ok_or_else(|| self.unsupported("invalid checksum qualifier"));
+        }
+
+        let version = self.version.as_deref().ok_or_else(|| self.unsupported("no version"))?;
+        match self.package_type.as_str() {
+            "oci" | "docker" => self.image_reference(version)?.resolve_digest(),
+            "npm" => self.npm_integrity(version),
+            _ => Err(self.unsupported("unsupported package type")),
+        }
+    }
+
+    /// Image reference of a `pkg:oci` or `pkg:docker` purl at `version`
+    fn image_reference(&self, version: &str) -> Result<ImageReference, ReferenceError> {
+        // oci purls name the image only; the repository URL carries the path
+        let repository = match (self.package_type.as_str(), self.qualifiers.get("repository_url")) {
+            ("oci", Some(url)) => url.clone(),
+            ("oci", None) => format!("{}/library/{}", DEFAULT_REGISTRY, self.name),
+            (_, url) => {
+                let path = match &self.namespace {
+                    Some(namespace) => format!("{}/{}", namespace, self.name),
+                    None => self.name.clone(),
+                };
+                match url {
+                    Some(url) => format!("{}/{}", url.trim_end_matches('/'), path),
+                    None => path,
+                }
+            }
+        };
+        let separator = if version.contains(':') { '@' } else { ':' };
+        format!("{}{}{}", repository, separator, version).parse()
+    }
+
+    /// SHA-512 of an npm package tarball, from its `dist.integrity`
+    fn npm_integrity(&self, version: &str) -> Result<Digest, ReferenceError> {
+        let package = match &self.namespace {
+            Some(scope) => format!("{}/{}", scope, self.name),
+            None => self.name.clone(),
+        };
+        let url = format!("{}/{}/{}", NPM_REGISTRY_URL, package, version);
+        let response = send(Client::new().get(&url))?;
+        if !response.status().is_success() {
+            return Err(ReferenceError::Registry(format!("{} returned HTTP {}", url, response.status())));
+        }
+        let body: Value = response.json().map_err(|e| ReferenceError::Registry(e.to_string()))?;
+
+        // Subresource Integrity: "sha512-<base64>", possibly among weaker hashes
+        body["dist"]["integrity"]
+            .as_str()
+            .and_then(|integrity| integrity.split_whitespace().find_map(|hash| hash.strip_prefix("sha512-")))
+            .and_then(|hash| BASE64_STANDARD.decode(hash).ok())
+            .and_then(|bytes| Digest::new(DigestAlgorithm::Sha512, bytes).ok())
+            .ok_or_else(|| ReferenceError::Registry(format!("{} has no sha512 integrity", url)))
+    }
+
+    fn unsupported(&self, reason: &str) -> ReferenceError {
+        ReferenceError::UnsupportedPackage(format!("{}: {}", self, reason))
+    }
+}
+
+impl fmt::Display for PackageUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pkg:{}/", self.package_type)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{}/", namespace)?;
+        }
+        write!(f, "{}", self.name)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{}", version)?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolve a purl (`pkg:...`) or image reference to the digest to verify against
+///
+/// # Example
+///
+/// ```ignore
+/// let digest = resolve_expected_digest("ghcr.io/org/app:v1.2")?;
+/// let mut options = VerificationOptions::default();
+/// options.expected_digest = Some(digest);
+/// ```
+pub fn resolve_expected_digest(reference: &str) -> Result<Digest, ReferenceError> {
+    if reference.starts_with("pkg:") {
+        reference.parse::<PackageUrl>()?.resolve_digest()
+    } else {
+        reference.parse::<ImageReference>()?.resolve_digest()
+    }
+}
+
+/// Fetch the GitHub artifact attestation bundles published for a subject digest
+///
+/// Authenticated with `GITHUB_TOKEN` if it is set.
+///
+/// # Arguments
+///
+/// * `repository` - `owner/repo` the attestations were published under
+/// * `digest` - Subject digest
+pub fn fetch_github_attestations(repository: &str, digest: &Digest) -> Result<Vec<Vec<u8>>, ReferenceError> {
+    let url = format!("{}/repos/{}/attestations/{}", GITHUB_API_URL, repository, digest);
+    let mut request = Client::new().get(&url).header(ACCEPT, "application/json");
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        request = request.bearer_auth(token);
+    }
+    let response = send(request).map_err(|e| ReferenceError::Attestations(e.to_string()))?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(ReferenceError::Attestations(format!("{} returned HTTP {}", url, response.status())));
+    }
+    let body: Value = response.json().map_err(|e| ReferenceError::Attestations(e.to_string()))?;
+
+    body["attestations"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|attestation| attestation.get("bundle"))
+        .map(|bundle| serde_json::to_vec(bundle).map_err(|e| ReferenceError::Attestations(e.to_string())))
+        .collect()
+}
+
+/// A [`BundleSource`] fetching GitHub artifact attestations by digest
+///
+/// Attestations are looked up under the repository of a `github.com` reference
+/// URI (e.g. `git+https://github.com/org/lib@refs/tags/v1`), and otherwise
+/// under a default repository. GitHub indexes attestations by SHA-256 subject
+/// digest, so references without one, such as builder IDs, are unattested.
+///
+/// # Example
+///
+/// ```ignore
+/// let source = GitHubAttestationSource::new("org/app");
+/// let tree = verifier.verify_build_tree(&bundle_json, options, &source, DEFAULT_MAX_BUILD_DEPTH, &trust_bundle, None)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GitHubAttestationSource {
+    repository: String,
+}
+
+impl GitHubAttestationSource {
+    /// # Arguments
+    ///
+    /// * `repository` - `owner/repo` searched for references outside `github.com`
+    pub fn new(repository: impl Into<String>) -> Self {
+        Self {
+            repository: repository.into(),
+        }
+    }
+
+    /// `owner/repo` the attestations of `uri` are published under
+    fn repository_of(&self, uri: &str) -> String {
+        let uri = uri.strip_prefix("git+").unwrap_or(uri);
+        if let Some(path) = uri.strip_prefix("https://github.com/") {
+            let mut segments = path.split(['/', '@']);
+            if let (Some(owner), Some(repo)) = (segments.next(), segments.next()) {
+                if !owner.is_empty() && !repo.is_empty() {
+                    return format!("{}/{}", owner, repo.trim_end_matches(".git"));
+                }
+            }
+        }
+        self.repository.clone()
+    }
+}
+
+impl BundleSource for GitHubAttestationSource {
+    fn bundles(&self, reference: &BuildReference) -> Result<Vec<Vec<u8>>, VerificationError> {
+        let Some(digest) = reference
+            .digests
+            .iter()
+            .find(|digest| digest.algorithm == DigestAlgorithm::Sha256)
+        else {
+            return Ok(Vec::new());
+        };
+        fetch_github_attestations(&self.repository_of(&reference.uri), digest)
+            .map_err(|e| VerificationError::HttpError(e.to_string()))
+    }
+}
+
+/// Verify the GitHub attestations of a purl or image reference
+///
+/// Resolves `reference` to its digest, fetches the attestations `repository`
+/// published for it and returns the result of the first bundle that verifies
+/// against the digest and `options` (whose `expected_digest` is replaced).
+///
+/// # Errors
+///
+/// Returns `NoAttestations` if none were published for the digest, and the
+/// last bundle's `Verification` error if none verifies.
+///
+/// # Example
+///
+/// ```ignore
+/// let trust_bundle = fetch_fulcio_trust_bundle(&FulcioInstance::GitHub)?;
+/// let result = verify_reference(
+///     "ghcr.io/org/app:v1.2",
+///     "org/app",
+///     VerificationOptions::default(),
+///     &trust_bundle,
+///     tsa_chain.as_ref(),
+/// )?;
+/// ```
+pub fn verify_reference(
+    reference: &str,
+    repository: &str,
+    options: VerificationOptions,
+    trust_bundle: &CertificateChain,
+    tsa_cert_chain: Option<&CertificateChain>,
+) -> Result<VerificationResult, ReferenceError> {
+    let digest = resolve_expected_digest(reference)?;
+    let bundles = fetch_github_attestations(repository, &digest)?;
+    let mut options = options;
+    options.expected_digest = Some(digest.clone());
+
+    let verifier = AttestationVerifier::new();
+    let mut last_error = None;
+    for bundle in &bundles {
+        match verifier.verify_bundle_bytes(bundle, options.clone(), trust_bundle, tsa_cert_chain) {
+            Ok(result) => return Ok(result),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(match last_error {
+        Some(e) => ReferenceError::Verification(e),
+        None => ReferenceError::NoAttestations {
+            repository: repository.to_string(),
+            digest: digest.to_string(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "sha256:0101010101010101010101010101010101010101010101010101010101010101";
+
+    #[test]
+    fn test_parse_image_reference() {
+        let image: ImageReference = "ghcr.io/org/app:v1.2".parse().unwrap();
+        assert_eq!(image.registry, "ghcr.io");
+        assert_eq!(image.repository, "org/app");
+        assert_eq!(image.tag.as_deref(), Some("v1.2"));
+        assert_eq!(image.digest, None);
+
+        let image: ImageReference = "alpine".parse().unwrap();
+        assert_eq!(image.to_string(), "docker.io/library/alpine");
+
+        let image: ImageReference = format!("localhost:5000/app:1@{}", DIGEST).parse().unwrap();
+        assert_eq!(image.registry, "localhost:5000");
+        assert_eq!(image.tag.as_deref(), Some("1"));
+        // A pinned digest resolves without a registry
+        assert_eq!(image.resolve_digest().unwrap().to_string(), DIGEST);
+
+        assert!("ghcr.io/Org/app".parse::<ImageReference>().is_err());
+        assert!("ghcr.io/org/app@sha256:00".parse::<ImageReference>().is_err());
+    }
+
+    #[test]
+    fn test_attestation_source_repository() {
+        let source = GitHubAttestationSource::new("org/app");
+        assert_eq!(source.repository_of("git+https://github.com/org/lib@refs/tags/v1"), "org/lib");
+        assert_eq!(source.repository_of("git+https://github.com/org/lib.git"), "org/lib");
+        assert_eq!(
+            source.repository_of("https://github.com/org/builder/.github/workflows/build.yml@refs/heads/main"),
+            "org/builder"
+        );
+        assert_eq!(source.repository_of("pkg:npm/left-pad@1.3.0"), "org/app");
+    }
+
+    #[test]
+    fn test_parse_package_url() {
+        let purl: PackageUrl = "pkg:npm/%40sigstore/bundle@3.0.0".parse().unwrap();
+        assert_eq!(purl.package_type, "npm");
+        assert_eq!(purl.namespace.as_deref(), Some("@sigstore"));
+        assert_eq!(purl.name, "bundle");
+        assert_eq!(purl.version.as_deref(), Some("3.0.0"));
+
+        let purl: PackageUrl = format!("pkg:oci/app@{}?repository_url=ghcr.io/org/app", DIGEST)
+            .replace("sha256:", "sha256%3A")
+            .parse()
+            .unwrap();
+        assert_eq!(purl.version.as_deref(), Some(DIGEST));
+        assert_eq!(purl.qualifiers["repository_url"], "ghcr.io/org/app");
+        assert_eq!(purl.resolve_digest().unwrap().to_string(), DIGEST);
+
+        assert!("npm/left-pad@1.3.0".parse::<PackageUrl>().is_err());
+        assert!("pkg:npm@1.3.0".parse::<PackageUrl>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_checksum_qualifier() {
+        let sha512 = format!("sha512:{}", "02".repeat(64));
+        let digest = resolve_expected_digest(&format!("pkg:generic/tool@1.0?checksum={},{}", DIGEST, sha512)).unwrap();
+        assert_eq!(digest.to_string(), sha512);
+
+        assert!(matches!(
+            resolve_expected_digest("pkg:generic/tool@1.0"),
+            Err(ReferenceError::UnsupportedPackage(_))
+        ));
+        assert!(matches!(
+            resolve_expected_digest("pkg:generic/tool@1.0?checksum=md5:00"),
+            Err(ReferenceError::UnsupportedPackage(_))
+        ));
+    }
+}
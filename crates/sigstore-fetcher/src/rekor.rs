@@ -0,0 +1,310 @@
+//! Rekor v1 submission client
+//!
+//! The verifier only reads bundles, but artifacts we sign ourselves need a
+//! transparency log entry before they can be verified (or proven in the zkVM).
+//! `RekorClient::submit_dsse` uploads a DSSE envelope and its signing
+//! certificate as a `dsse` 0.0.1 entry, waits until the log returns an
+//! inclusion proof for it, and converts the result into the bundle's
+//! `TransparencyLogEntry`. `build_bundle` then assembles a complete bundle.
+
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use base64::prelude::*;
+use serde::Deserialize;
+
+use sigstore_verifier::error::{TransparencyError, VerificationError};
+use sigstore_verifier::types::bundle::{
+    BundleVersion, Certificate, DsseEnvelope, InclusionPromise, InclusionProof, KindVersion, LogId, SigstoreBundle,
+    TransparencyLogEntry, VerificationMaterial,
+};
+
+/// Public-good Rekor instance
+pub const PUBLIC_GOOD_REKOR_URL: &str = "https://rekor.sigstore.dev";
+
+/// Media type of the bundles built by [`build_bundle`]
+pub const BUNDLE_MEDIA_TYPE: &str = BundleVersion::V0_3.media_type();
+
+/// Log entry as returned by the Rekor v1 API
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEntry {
+    body: String,
+    integrated_time: i64,
+    #[serde(rename = "logID")]
+    log_id: String,
+    log_index: u64,
+    verification: Option<LogEntryVerification>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEntryVerification {
+    inclusion_proof: Option<LogEntryInclusionProof>,
+    signed_entry_timestamp: Option<String>,
+}
+
+/// Inclusion proof with hex hashes, as returned by the Rekor v1 API
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEntryInclusionProof {
+    checkpoint: Option<String>,
+    hashes: Vec<String>,
+    log_index: u64,
+    root_hash: String,
+    tree_size: u64,
+}
+
+/// Client for the Rekor v1 write API
+///
+/// # Example
+///
+/// ```ignore
+/// use sigstore_fetcher::rekor::{build_bundle, RekorClient, PUBLIC_GOOD_REKOR_URL};
+///
+/// let client = RekorClient::new(PUBLIC_GOOD_REKOR_URL);
+/// let entry = client.submit_dsse(&envelope, &certificate_der)?;
+/// let bundle = build_bundle(envelope, &certificate_der, entry);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RekorClient {
+    base_url: String,
+    client: reqwest::blocking::Client,
+    poll_interval: Duration,
+    max_polls: u32,
+}
+
+impl RekorClient {
+    /// Create a client for the log at `base_url`
+    pub fn new(base_url: &str) -> Self {
+        RekorClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::blocking::Client::new(),
+            poll_interval: Duration::from_secs(1),
+            max_polls: 30,
+        }
+    }
+
+    /// Send requests with `client`, e.g. one built by `NetworkConfig::client`
+    pub fn with_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Poll for the inclusion proof every `interval`, at most `attempts` times
+    pub fn with_polling(mut self, interval: Duration, attempts: u32) -> Self {
+        self.poll_interval = interval;
+        self.max_polls = attempts;
+        self
+    }
+
+    /// Upload a DSSE envelope as a `dsse` 0.0.1 entry and wait for its inclusion proof
+    ///
+    /// # Arguments
+    ///
+    /// * `envelope` - Signed DSSE envelope
+    /// * `certificate_der` - DER-encoded certificate of the signing key
+    ///
+    /// # Returns
+    ///
+    /// The entry with its inclusion proof, checkpoint and Signed Entry Timestamp
+    ///
+    /// # Errors
+    ///
+    /// Returns `LogSubmit` if Rekor rejects the entry, and `LogFetch` if the
+    /// inclusion proof is not available after the configured polling.
+    pub fn submit_dsse(
+        &self,
+        envelope: &DsseEnvelope,
+        certificate_der: &[u8],
+    ) -> Result<TransparencyLogEntry, VerificationError> {
+        let certificate_pem = pem::encode(&pem::Pem::new("CERTIFICATE", certificate_der));
+        let request = serde_json::json!({
+            "apiVersion": "0.0.1",
+            "kind": "dsse",
+            "spec": {
+                "proposedContent": {
+                    "envelope": serde_json::to_string(envelope)?,
+                    "verifiers": [BASE64_STANDARD.encode(certificate_pem)],
+                },
+            },
+        });
+
+        let url = format!("{}/api/v1/log/entries", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .map_err(|e| TransparencyError::LogSubmit(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().unwrap_or_default();
+            return Err(TransparencyError::LogSubmit(format!("HTTP error {} for {}: {}", status, url, message)).into());
+        }
+        let (uuid, entry) = single_entry(
+            response
+                .json()
+                .map_err(|e| TransparencyError::LogSubmit(e.to_string()))?,
+        )?;
+
+        if has_inclusion_proof(&entry) {
+            return to_tlog_entry(entry);
+        }
+        self.wait_for_inclusion(&uuid)
+    }
+
+    /// Fetch an entry by UUID
+    pub fn fetch_entry(&self, uuid: &str) -> Result<TransparencyLogEntry, VerificationError> {
+        to_tlog_entry(self.get_entry(uuid)?)
+    }
+
+    fn get_entry(&self, uuid: &str) -> Result<LogEntry, VerificationError> {
+        let url = format!("{}/api/v1/log/entries/{}", self.base_url, uuid);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| TransparencyError::LogFetch(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(TransparencyError::LogFetch(format!("HTTP error {} for {}", response.status(), url)).into());
+        }
+        let entries = response
+            .json()
+            .map_err(|e| TransparencyError::LogFetch(e.to_string()))?;
+        single_entry(entries).map(|(_, entry)| entry)
+    }
+
+    fn wait_for_inclusion(&self, uuid: &str) -> Result<TransparencyLogEntry, VerificationError> {
+        for _ in 0..self.max_polls {
+            thread::sleep(self.poll_interval);
+            let entry = self.get_entry(uuid)?;
+            if has_inclusion_proof(&entry) {
+                return to_tlog_entry(entry);
+            }
+        }
+        Err(TransparencyError::LogFetch(format!("no inclusion proof for entry {} yet", uuid)).into())
+    }
+}
+
+/// Upload a DSSE envelope to the public-good Rekor instance
+///
+/// See [`RekorClient::submit_dsse`].
+pub fn submit_entry(
+    envelope: &DsseEnvelope,
+    certificate_der: &[u8],
+) -> Result<TransparencyLogEntry, VerificationError> {
+    RekorClient::new(PUBLIC_GOOD_REKOR_URL).submit_dsse(envelope, certificate_der)
+}
+
+/// Assemble a bundle from a signed envelope, its certificate and its log entry
+pub fn build_bundle(envelope: DsseEnvelope, certificate_der: &[u8], entry: TransparencyLogEntry) -> SigstoreBundle {
+    let material = VerificationMaterial::new(Certificate::from_der(certificate_der)).with_tlog_entries(vec![entry]);
+    SigstoreBundle::new(material, envelope)
+}
+
+fn single_entry(entries: HashMap<String, LogEntry>) -> Result<(String, LogEntry), VerificationError> {
+    if entries.len() != 1 {
+        return Err(TransparencyError::InvalidEntryBody(format!(
+            "expected one log entry in the response, got {}",
+            entries.len()
+        ))
+        .into());
+    }
+    Ok(entries.into_iter().next().unwrap())
+}
+
+fn has_inclusion_proof(entry: &LogEntry) -> bool {
+    entry
+        .verification
+        .as_ref()
+        .is_some_and(|verification| verification.inclusion_proof.is_some())
+}
+
+/// Convert a Rekor API entry (hex hashes and log ID) into a bundle entry (base64)
+fn to_tlog_entry(entry: LogEntry) -> Result<TransparencyLogEntry, VerificationError> {
+    let hex_to_base64 = |value: &str| {
+        hex::decode(value)
+            .map(|bytes| BASE64_STANDARD.encode(bytes))
+            .map_err(|e| TransparencyError::InvalidEntryBody(format!("invalid hex {}: {}", value, e)))
+    };
+    let verification = entry.verification.unwrap_or(LogEntryVerification {
+        inclusion_proof: None,
+        signed_entry_timestamp: None,
+    });
+    let inclusion_proof = verification
+        .inclusion_proof
+        .map(|proof| -> Result<InclusionProof, TransparencyError> {
+            let hashes = proof
+                .hashes
+                .iter()
+                .map(|hash| hex_to_base64(hash))
+                .collect::<Result<_, _>>()?;
+            let inclusion_proof =
+                InclusionProof::new(proof.log_index, hex_to_base64(&proof.root_hash)?, proof.tree_size, hashes);
+            Ok(match proof.checkpoint {
+                Some(envelope) => inclusion_proof.with_checkpoint(envelope),
+                None => inclusion_proof,
+            })
+        })
+        .transpose()?;
+
+    let mut tlog_entry = TransparencyLogEntry::new(entry.body);
+    tlog_entry.log_index = Some(entry.log_index.to_string());
+    tlog_entry.log_id = Some(LogId {
+        key_id: hex_to_base64(&entry.log_id)?,
+    });
+    tlog_entry.kind_version = Some(KindVersion {
+        kind: "dsse".to_string(),
+        version: "0.0.1".to_string(),
+    });
+    tlog_entry.integrated_time = entry.integrated_time.to_string();
+    tlog_entry.inclusion_promise = verification
+        .signed_entry_timestamp
+        .map(|signed_entry_timestamp| InclusionPromise { signed_entry_timestamp });
+    tlog_entry.inclusion_proof = inclusion_proof;
+    Ok(tlog_entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_tlog_entry() {
+        let response = serde_json::json!({
+            "24296fb24b8ad77a": {
+                "body": "eyJhcGlWZXJzaW9uIjoiMC4wLjEifQ==",
+                "integratedTime": 1700000000,
+                "logID": "c0d23d6ad406973f",
+                "logIndex": 123456789,
+                "verification": {
+                    "inclusionProof": {
+                        "checkpoint": "rekor.sigstore.dev - 1193050959916656506\n42\nAAAA\n",
+                        "hashes": ["00ff", "ff00"],
+                        "logIndex": 4567,
+                        "rootHash": "abcd",
+                        "treeSize": 5000
+                    },
+                    "signedEntryTimestamp": "MEUCIQ=="
+                }
+            }
+        });
+        let (uuid, entry) = single_entry(serde_json::from_value(response).unwrap()).unwrap();
+        assert_eq!(uuid, "24296fb24b8ad77a");
+        assert!(has_inclusion_proof(&entry));
+
+        let entry = to_tlog_entry(entry).unwrap();
+        assert_eq!(entry.log_index.as_deref(), Some("123456789"));
+        assert_eq!(entry.log_id.unwrap().key_id, BASE64_STANDARD.encode(hex::decode("c0d23d6ad406973f").unwrap()));
+        assert_eq!(entry.integrated_time, "1700000000");
+        assert_eq!(entry.inclusion_promise.unwrap().signed_entry_timestamp, "MEUCIQ==");
+        let proof = entry.inclusion_proof.unwrap();
+        assert_eq!(proof.log_index, "4567");
+        assert_eq!(proof.tree_size, "5000");
+        assert_eq!(proof.root_hash, BASE64_STANDARD.encode([0xab, 0xcd]));
+        assert_eq!(proof.hashes, vec!["AP8=", "/wA="]);
+        assert!(proof.checkpoint.unwrap().envelope.starts_with("rekor.sigstore.dev"));
+    }
+}
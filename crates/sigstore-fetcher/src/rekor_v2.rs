@@ -0,0 +1,164 @@
+//! Rekor v2 (tile-based) log client
+//!
+//! Rekor v2 serves its latest checkpoint, hash tiles and entry bundles as
+//! static files under `/api/v2/`. `RekorV2Client` fetches them and rebuilds
+//! the inclusion proof of an entry, so bundles whose proof is missing or
+//! anchored to an old checkpoint can be proven against the current tree.
+
+use sigstore_verifier::crypto::merkle::HASH_SIZE;
+use sigstore_verifier::crypto::tiles::{
+    entry_bundle_path, inclusion_proof_from_tiles, parse_entry_bundle, parse_tile, tile_path,
+    tile_width, TileSource, TILE_WIDTH,
+};
+use sigstore_verifier::error::{TransparencyError, VerificationError};
+use sigstore_verifier::parser::checkpoint::{parse_checkpoint, LogCheckpoint};
+use sigstore_verifier::types::bundle::InclusionProof;
+use sigstore_verifier::verifier::checkpoint::{verify_checkpoint_signature, LogVerifier};
+use base64::prelude::*;
+
+/// Path prefix of the Rekor v2 read API
+pub const API_PREFIX: &str = "api/v2";
+
+/// Client for the read API of a Rekor v2 log
+///
+/// # Example
+///
+/// ```ignore
+/// use sigstore_fetcher::rekor_v2::RekorV2Client;
+/// use sigstore_verifier::verifier::checkpoint::LogVerifier;
+///
+/// let client = RekorV2Client::new("https://log2025-1.rekor.sigstore.dev");
+/// let verifier = LogVerifier::from_base64("log2025-1.rekor.sigstore.dev", &log_public_key)?;
+/// let (checkpoint, envelope) = client.fetch_verified_checkpoint(&verifier)?;
+/// let proof = client.inclusion_proof(log_index, &checkpoint, &envelope)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RekorV2Client {
+    base_url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl RekorV2Client {
+    /// Create a client for the log at `base_url` (the TrustedRoot tlog `baseUrl`)
+    pub fn new(base_url: &str) -> Self {
+        RekorV2Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Send requests with `client`, e.g. one built by `NetworkConfig::client`
+    pub fn with_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    fn get(&self, path: &str) -> Result<Vec<u8>, TransparencyError> {
+        let url = format!("{}/{}/{}", self.base_url, API_PREFIX, path);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .map_err(|e| TransparencyError::LogFetch(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(TransparencyError::LogFetch(format!(
+                "HTTP error {} for {}",
+                response.status(),
+                url
+            )));
+        }
+
+        response
+            .bytes()
+            .map(|body| body.to_vec())
+            .map_err(|e| TransparencyError::LogFetch(e.to_string()))
+    }
+
+    /// Fetch the latest checkpoint envelope
+    pub fn fetch_checkpoint_envelope(&self) -> Result<String, TransparencyError> {
+        String::from_utf8(self.get("checkpoint")?)
+            .map_err(|_| TransparencyError::InvalidCheckpoint("not UTF-8".to_string()))
+    }
+
+    /// Fetch the latest checkpoint and verify its signature
+    ///
+    /// # Returns
+    ///
+    /// The parsed checkpoint and its envelope
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be fetched or parsed, or is not
+    /// signed by `verifier`.
+    pub fn fetch_verified_checkpoint(
+        &self,
+        verifier: &LogVerifier,
+    ) -> Result<(LogCheckpoint, String), VerificationError> {
+        let envelope = self.fetch_checkpoint_envelope()?;
+        let checkpoint = parse_checkpoint(&envelope)?;
+        verify_checkpoint_signature(&checkpoint, verifier)?;
+        Ok((checkpoint, envelope))
+    }
+
+    /// Fetch the entries of entry bundle `index` in a tree of `tree_size` leaves
+    pub fn fetch_entry_bundle(&self, index: u64, tree_size: u64) -> Result<Vec<Vec<u8>>, TransparencyError> {
+        let width = tile_width(0, index, tree_size);
+        if width == 0 {
+            return Err(TransparencyError::IndexOutOfRange {
+                index: index * TILE_WIDTH,
+                size: tree_size,
+            });
+        }
+        parse_entry_bundle(&self.get(&entry_bundle_path(index, width))?)
+    }
+
+    /// Fetch the canonicalized body of entry `log_index` in a tree of `tree_size` leaves
+    pub fn fetch_entry(&self, log_index: u64, tree_size: u64) -> Result<Vec<u8>, TransparencyError> {
+        if log_index >= tree_size {
+            return Err(TransparencyError::IndexOutOfRange {
+                index: log_index,
+                size: tree_size,
+            });
+        }
+        let mut entries = self.fetch_entry_bundle(log_index / TILE_WIDTH, tree_size)?;
+        let offset = (log_index % TILE_WIDTH) as usize;
+        if offset >= entries.len() {
+            return Err(TransparencyError::InvalidTile(format!(
+                "entry bundle has {} entries, expected at least {}",
+                entries.len(),
+                offset + 1
+            )));
+        }
+        Ok(entries.swap_remove(offset))
+    }
+
+    /// Build the bundle inclusion proof of entry `log_index` against a checkpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `log_index` - Index of the entry in the log
+    /// * `checkpoint` - Checkpoint to prove against, e.g. from [`Self::fetch_verified_checkpoint`]
+    /// * `envelope` - Signed note of `checkpoint`, embedded in the proof
+    pub fn inclusion_proof(
+        &self,
+        log_index: u64,
+        checkpoint: &LogCheckpoint,
+        envelope: &str,
+    ) -> Result<InclusionProof, TransparencyError> {
+        let hashes = inclusion_proof_from_tiles(self, log_index, checkpoint.tree_size)?;
+        Ok(InclusionProof::new(
+            log_index,
+            BASE64_STANDARD.encode(checkpoint.root_hash),
+            checkpoint.tree_size,
+            hashes.iter().map(|hash| BASE64_STANDARD.encode(hash)).collect(),
+        )
+        .with_checkpoint(envelope))
+    }
+}
+
+impl TileSource for RekorV2Client {
+    fn tile(&self, level: u64, index: u64, width: u64) -> Result<Vec<[u8; HASH_SIZE]>, TransparencyError> {
+        parse_tile(&self.get(&tile_path(level, index, width))?)
+    }
+}
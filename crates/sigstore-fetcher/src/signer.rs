@@ -0,0 +1,409 @@
+//! Keyless signing with ephemeral Fulcio certificates
+//!
+//! The counterpart of verification: `EphemeralSigner` generates a P-256 key,
+//! proves possession of it to Fulcio with an OIDC identity token, and signs
+//! DSSE envelopes with it. `sign_bundle` then records the envelope in Rekor
+//! (see `rekor`) and assembles a bundle that `AttestationVerifier`
+//! and the zkVM guests accept like any bundle produced by cosign or
+//! `actions/attest-build-provenance`.
+//!
+//! Obtaining the identity token is left to the caller, except for GitHub
+//! Actions, where `IdentityToken::from_github_actions` requests one from the
+//! runner. The key never leaves memory and is dropped with the signer.
+
+use base64::prelude::*;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature as EcdsaSignature, SigningKey};
+use p256::pkcs8::{EncodePublicKey, LineEnding};
+use serde::Deserialize;
+
+use sigstore_verifier::error::{SignerError, VerificationError};
+use sigstore_verifier::parser::payload::IN_TOTO_PAYLOAD_TYPE;
+use sigstore_verifier::redaction::REDACTABLE_PAYLOAD_TYPE;
+use sigstore_verifier::types::bundle::{DsseEnvelope, Signature, SigstoreBundle};
+use sigstore_verifier::types::dsse::Statement;
+use sigstore_verifier::verifier::signature::{build_pae, signed_message};
+
+use crate::http_error;
+use crate::rekor::{build_bundle, RekorClient};
+
+/// Public-good Fulcio instance
+pub const PUBLIC_GOOD_FULCIO_URL: &str = "https://fulcio.sigstore.dev";
+
+/// Audience Fulcio expects in identity tokens
+pub const SIGSTORE_AUDIENCE: &str = "sigstore";
+
+/// An OIDC identity token and the subject Fulcio will bind the certificate to
+#[derive(Debug, Clone)]
+pub struct IdentityToken {
+    token: String,
+    subject: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenClaims {
+    sub: Option<String>,
+    email: Option<String>,
+}
+
+impl IdentityToken {
+    /// Read the subject of a JWT without verifying it (Fulcio does)
+    ///
+    /// The subject is the `email` claim if present and `sub` otherwise, which is
+    /// the value Fulcio checks the proof of possession against.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SignerError::InvalidToken` if the token is not a JWT with a
+    /// subject.
+    pub fn new(token: &str) -> Result<Self, VerificationError> {
+        let claims = token
+            .split('.')
+            .nth(1)
+            .ok_or_else(|| SignerError::InvalidToken("not a JWT".to_string()))?;
+        let claims = BASE64_URL_SAFE_NO_PAD
+            .decode(claims.trim_end_matches('='))
+            .map_err(|e| SignerError::InvalidToken(e.to_string()))?;
+        let claims: TokenClaims =
+            serde_json::from_slice(&claims).map_err(|e| SignerError::InvalidToken(e.to_string()))?;
+        let subject = claims
+            .email
+            .or(claims.sub)
+            .ok_or_else(|| SignerError::InvalidToken("no email or sub claim".to_string()))?;
+
+        Ok(IdentityToken {
+            token: token.to_string(),
+            subject,
+        })
+    }
+
+    /// Request a token for the `sigstore` audience from the GitHub Actions runner
+    ///
+    /// The workflow needs the `id-token: write` permission.
+    pub fn from_github_actions() -> Result<Self, VerificationError> {
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            value: String,
+        }
+
+        let env = |name: &str| {
+            std::env::var(name).map_err(|_| SignerError::InvalidToken(format!("{} is not set", name)))
+        };
+        let url = env("ACTIONS_ID_TOKEN_REQUEST_URL")?;
+        let request_token = env("ACTIONS_ID_TOKEN_REQUEST_TOKEN")?;
+
+        let response = reqwest::blocking::Client::new()
+            .get(&url)
+            .query(&[("audience", SIGSTORE_AUDIENCE)])
+            .bearer_auth(request_token)
+            .send().map_err(http_error)?;
+        if !response.status().is_success() {
+            return Err(SignerError::InvalidToken(format!("HTTP error {} requesting the token", response.status())).into());
+        }
+        let response: TokenResponse = response.json().map_err(http_error)?;
+        Self::new(&response.value)
+    }
+
+    /// Email or `sub` claim of the token
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SigningCertificateResponse {
+    signed_certificate_embedded_sct: Option<SignedCertificate>,
+    signed_certificate_detached_sct: Option<SignedCertificate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignedCertificate {
+    chain: PemChain,
+}
+
+#[derive(Debug, Deserialize)]
+struct PemChain {
+    certificates: Vec<String>,
+}
+
+/// Client for the Fulcio v2 signing certificate API
+#[derive(Debug, Clone)]
+pub struct FulcioClient {
+    base_url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl FulcioClient {
+    /// Create a client for the Fulcio instance at `base_url`
+    pub fn new(base_url: &str) -> Self {
+        FulcioClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Send requests with `client`, e.g. one built by `NetworkConfig::client`
+    pub fn with_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Request a certificate for `key`
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key to certify; it signs the token subject as proof of possession
+    /// * `token` - Identity token for the `sigstore` audience
+    ///
+    /// # Returns
+    ///
+    /// The DER-encoded chain, leaf first
+    ///
+    /// # Errors
+    ///
+    /// Returns `SignerError::CertificateRequest` if Fulcio rejects the request
+    /// or returns no certificate.
+    pub fn request_certificate(&self, key: &SigningKey, token: &IdentityToken) -> Result<Vec<Vec<u8>>, VerificationError> {
+        let public_key = key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(|e| SignerError::Signing(e.to_string()))?;
+        let proof: EcdsaSignature = key.sign(token.subject.as_bytes());
+        let request = serde_json::json!({
+            "credentials": {
+                "oidcIdentityToken": token.token,
+            },
+            "publicKeyRequest": {
+                "publicKey": {
+                    "algorithm": "ECDSA",
+                    "content": public_key,
+                },
+                "proofOfPossession": BASE64_STANDARD.encode(proof.to_der().as_bytes()),
+            },
+        });
+
+        let url = format!("{}/api/v2/signingCert", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .map_err(|e| SignerError::CertificateRequest(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().unwrap_or_default();
+            return Err(SignerError::CertificateRequest(format!("HTTP error {} for {}: {}", status, url, message)).into());
+        }
+        let response: SigningCertificateResponse = response
+            .json()
+            .map_err(|e| SignerError::CertificateRequest(e.to_string()))?;
+        certificate_chain(response)
+    }
+}
+
+fn certificate_chain(response: SigningCertificateResponse) -> Result<Vec<Vec<u8>>, VerificationError> {
+    let certificates = response
+        .signed_certificate_embedded_sct
+        .or(response.signed_certificate_detached_sct)
+        .map(|signed| signed.chain.certificates)
+        .unwrap_or_default();
+    if certificates.is_empty() {
+        return Err(SignerError::CertificateRequest("no certificate in the response".to_string()).into());
+    }
+    certificates
+        .iter()
+        .map(|certificate| {
+            pem::parse(certificate)
+                .map(pem::Pem::into_contents)
+                .map_err(|e| SignerError::CertificateRequest(format!("invalid PEM certificate: {}", e)).into())
+        })
+        .collect()
+}
+
+/// A freshly generated key with its short-lived Fulcio certificate
+pub struct EphemeralSigner {
+    key: SigningKey,
+    certificate_chain: Vec<Vec<u8>>,
+}
+
+impl std::fmt::Debug for EphemeralSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EphemeralSigner")
+            .field("certificate_chain", &self.certificate_chain.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl EphemeralSigner {
+    /// Generate a key and obtain a certificate for it from Fulcio
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use sigstore_fetcher::signer::{EphemeralSigner, FulcioClient, IdentityToken, PUBLIC_GOOD_FULCIO_URL};
+    ///
+    /// let token = IdentityToken::from_github_actions()?;
+    /// let signer = EphemeralSigner::new(&FulcioClient::new(PUBLIC_GOOD_FULCIO_URL), &token)?;
+    /// let envelope = signer.sign_statement(&statement)?;
+    /// ```
+    pub fn new(fulcio: &FulcioClient, token: &IdentityToken) -> Result<Self, VerificationError> {
+        let key = SigningKey::random(&mut rand_core::OsRng);
+        let certificate_chain = fulcio.request_certificate(&key, token)?;
+        Ok(EphemeralSigner { key, certificate_chain })
+    }
+
+    /// DER-encoded leaf certificate
+    pub fn certificate(&self) -> &[u8] {
+        &self.certificate_chain[0]
+    }
+
+    /// DER-encoded chain returned by Fulcio, leaf first
+    pub fn certificate_chain(&self) -> &[Vec<u8>] {
+        &self.certificate_chain
+    }
+
+    /// Sign `payload` as a DSSE envelope of type `payload_type`
+    pub fn sign_dsse(&self, payload_type: &str, payload: &[u8]) -> DsseEnvelope {
+        self.envelope(payload_type, payload, &build_pae(payload_type, payload))
+    }
+
+    /// Sign an in-toto statement as a redactable statement
+    ///
+    /// The signature covers the statement's commitment, so predicate fields
+    /// can later be removed with `SigstoreBundle::redact` (see
+    /// `sigstore_verifier::redaction`). Rekor's `dsse` type checks signatures
+    /// over the payload itself, so timestamp these envelopes with a TSA.
+    pub fn sign_redactable_statement(&self, statement: &Statement) -> Result<DsseEnvelope, VerificationError> {
+        let payload = serde_json::to_vec(statement)?;
+        let message = signed_message(REDACTABLE_PAYLOAD_TYPE, &payload)?;
+        Ok(self.envelope(REDACTABLE_PAYLOAD_TYPE, &payload, &message))
+    }
+
+    fn envelope(&self, payload_type: &str, payload: &[u8], message: &[u8]) -> DsseEnvelope {
+        let signature: EcdsaSignature = self.key.sign(message);
+        DsseEnvelope::new(payload_type, payload, vec![Signature::from_bytes(signature.to_der().as_bytes())])
+    }
+
+    /// Sign an in-toto statement
+    pub fn sign_statement(&self, statement: &Statement) -> Result<DsseEnvelope, VerificationError> {
+        Ok(self.sign_dsse(IN_TOTO_PAYLOAD_TYPE, &serde_json::to_vec(statement)?))
+    }
+}
+
+/// Sign a payload, record it in Rekor and assemble the bundle
+///
+/// # Arguments
+///
+/// * `signer` - Signer holding the Fulcio certificate
+/// * `rekor` - Log to record the envelope in
+/// * `payload_type` - DSSE payload type, usually `application/vnd.in-toto+json`
+/// * `payload` - Payload to sign
+///
+/// # Returns
+///
+/// A bundle with the envelope, the leaf certificate and the log entry with its
+/// inclusion proof
+pub fn sign_bundle(
+    signer: &EphemeralSigner,
+    rekor: &RekorClient,
+    payload_type: &str,
+    payload: &[u8],
+) -> Result<SigstoreBundle, VerificationError> {
+    let envelope = signer.sign_dsse(payload_type, payload);
+    let entry = rekor.submit_dsse(&envelope, signer.certificate())?;
+    Ok(build_bundle(envelope, signer.certificate(), entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::signature::Verifier;
+
+    fn jwt(claims: &serde_json::Value) -> String {
+        format!(
+            "{}.{}.c2ln",
+            BASE64_URL_SAFE_NO_PAD.encode(br#"{"alg":"RS256"}"#),
+            BASE64_URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    #[test]
+    fn test_identity_token_subject() {
+        let token = IdentityToken::new(&jwt(&serde_json::json!({"sub": "repo:org/app:ref:refs/heads/main"}))).unwrap();
+        assert_eq!(token.subject(), "repo:org/app:ref:refs/heads/main");
+
+        let token = IdentityToken::new(&jwt(&serde_json::json!({"sub": "1234", "email": "dev@example.com"}))).unwrap();
+        assert_eq!(token.subject(), "dev@example.com");
+
+        assert!(IdentityToken::new("not-a-jwt").is_err());
+        assert!(IdentityToken::new(&jwt(&serde_json::json!({"iss": "x"}))).is_err());
+    }
+
+    #[test]
+    fn test_sign_dsse() {
+        let signer = EphemeralSigner {
+            key: SigningKey::random(&mut rand_core::OsRng),
+            certificate_chain: vec![Vec::new()],
+        };
+        let envelope = signer.sign_dsse(IN_TOTO_PAYLOAD_TYPE, b"{}");
+        assert_eq!(envelope.payload, "e30=");
+
+        let signature = BASE64_STANDARD.decode(&envelope.signatures[0].sig).unwrap();
+        let signature = EcdsaSignature::from_der(&signature).unwrap();
+        signer
+            .key
+            .verifying_key()
+            .verify(&build_pae(IN_TOTO_PAYLOAD_TYPE, b"{}"), &signature)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sign_redactable_statement() {
+        use sigstore_verifier::redaction::RedactionPolicy;
+        use sigstore_verifier::types::bundle::{Certificate, VerificationMaterial};
+        use sigstore_verifier::types::dsse::Subject;
+
+        let signer = EphemeralSigner {
+            key: SigningKey::random(&mut rand_core::OsRng),
+            certificate_chain: vec![Vec::new()],
+        };
+        let statement = Statement {
+            statement_type: "https://in-toto.io/Statement/v1".to_string(),
+            subject: vec![Subject {
+                name: "app".to_string(),
+                digest: [("sha256".to_string(), "ab".repeat(32))].into(),
+            }],
+            predicate_type: "https://slsa.dev/provenance/v1".to_string(),
+            predicate: serde_json::json!({"buildDefinition": {"internalParameters": {"token": "secret"}}}),
+        };
+        let envelope = signer.sign_redactable_statement(&statement).unwrap();
+        let bundle = SigstoreBundle::new(VerificationMaterial::new(Certificate::default()), envelope);
+
+        // The signature still verifies once the parameters are redacted
+        let redacted = bundle
+            .redact(&RedactionPolicy::new().with_field("/buildDefinition/internalParameters"))
+            .unwrap();
+        let payload = BASE64_STANDARD.decode(&redacted.dsse_envelope.payload).unwrap();
+        assert!(!String::from_utf8_lossy(&payload).contains("secret"));
+
+        let signature = BASE64_STANDARD.decode(&redacted.dsse_envelope.signatures[0].sig).unwrap();
+        let signature = EcdsaSignature::from_der(&signature).unwrap();
+        signer
+            .key
+            .verifying_key()
+            .verify(&signed_message(REDACTABLE_PAYLOAD_TYPE, &payload).unwrap(), &signature)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_certificate_chain_response() {
+        let der = vec![0x30, 0x03, 0x02, 0x01, 0x01];
+        let certificate = pem::encode(&pem::Pem::new("CERTIFICATE", der.clone()));
+        let response = serde_json::json!({
+            "signedCertificateDetachedSct": {"chain": {"certificates": [certificate]}}
+        });
+        assert_eq!(certificate_chain(serde_json::from_value(response).unwrap()).unwrap(), vec![der]);
+        assert!(certificate_chain(serde_json::from_value(serde_json::json!({})).unwrap()).is_err());
+    }
+}
@@ -1,6 +1,6 @@
-use crate::error::CertificateError;
-use crate::parser::certificate::parse_pem_certificate;
-use crate::types::certificate::{CertificateChain, FulcioInstance, TrustBundle};
+use sigstore_verifier::error::CertificateError;
+use sigstore_verifier::parser::certificate::parse_pem_certificate;
+use sigstore_verifier::types::certificate::{CertificateChain, FulcioInstance, TrustBundle};
 
 /// Fetch Fulcio trust bundle for a specific Fulcio instance
 ///
@@ -32,14 +32,24 @@ pub fn fetch_fulcio_trust_bundle(
 ///
 /// # Example
 /// ```ignore
-/// use sigstore_verifier::fetcher::trust_bundle::fetch_trust_bundle_from_url;
+/// use sigstore_fetcher::trust_bundle::fetch_trust_bundle_from_url;
 ///
 /// // Fetch TSA trust bundle (GitHub format - raw PEM)
 /// let tsa_url = "https://timestamp.githubapp.com/api/v1/timestamp/certchain";
 /// let tsa_chain = fetch_trust_bundle_from_url(tsa_url).unwrap();
 /// ```
 pub fn fetch_trust_bundle_from_url(url: &str) -> Result<CertificateChain, CertificateError> {
-    let response = reqwest::blocking::get(url)
+    fetch_trust_bundle_with(&reqwest::blocking::Client::new(), url)
+}
+
+/// [`fetch_trust_bundle_from_url`] through `client`, e.g. one built by `NetworkConfig::client`
+pub fn fetch_trust_bundle_with(
+    client: &reqwest::blocking::Client,
+    url: &str,
+) -> Result<CertificateChain, CertificateError> {
+    let response = client
+        .get(url)
+        .send()
         .map_err(|e| CertificateError::TrustBundleFetch(e.to_string()))?;
 
     if !response.status().is_success() {
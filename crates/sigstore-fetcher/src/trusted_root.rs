@@ -0,0 +1,148 @@
+//! Refreshing a cached `trusted_root.jsonl`
+//!
+//! Services that verify continuously keep their trusted roots on disk and
+//! refresh them from the source they were first fetched from (a TUF mirror,
+//! or a copy of `gh attestation trusted-root` output). [`TrustedRootCache::refresh`]
+//! compares what it fetched with what it holds, and hands every change to the
+//! handler installed with [`TrustedRootCache::with_change_handler`] before
+//! accepting it, so an unexpected CA, TSA or log change raises an alert (or
+//! is refused) instead of silently becoming trusted.
+
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use sigstore_verifier::fetcher::jsonl::diff::TrustRootDiff;
+use sigstore_verifier::fetcher::jsonl::types::TrustedRootSet;
+
+use crate::error::TrustRootError;
+
+/// Decides whether a change to the trusted roots is accepted
+pub type ChangeHandler = Arc<dyn Fn(&TrustRootDiff) -> bool + Send + Sync>;
+
+/// A `trusted_root.jsonl` cached on disk and refreshed from a URL
+///
+/// # Example
+///
+/// ```ignore
+/// let mut cache = TrustedRootCache::new(url, "/var/cache/trusted_root.jsonl")?
+///     .with_change_handler(|diff| {
+///         alert(&format!("trusted root changed: {}", diff));
+///         !diff.has_removals()
+///     });
+/// cache.refresh()?;
+/// let ca = select_certificate_authority_for(&cache.current().roots, &environment, signing_time)?;
+/// ```
+#[derive(Clone)]
+pub struct TrustedRootCache {
+    url: String,
+    path: PathBuf,
+    current: TrustedRootSet,
+    on_change: Option<ChangeHandler>,
+    client: reqwest::blocking::Client,
+}
+
+impl fmt::Debug for TrustedRootCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrustedRootCache")
+            .field("url", &self.url)
+            .field("path", &self.path)
+            .field("roots", &self.current.roots.len())
+            .finish()
+    }
+}
+
+impl TrustedRootCache {
+    /// Open the cache at `path`, loading its contents if the file exists
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Source of the trusted roots, serving JSONL or a single `trusted_root.json`
+    /// * `path` - File the accepted trusted roots are written to
+    pub fn new(url: &str, path: impl Into<PathBuf>) -> Result<Self, TrustRootError> {
+        let path = path.into();
+        let current = if path.exists() {
+            TrustedRootSet::from_jsonl(&fs::read_to_string(&path)?)?
+        } else {
+            TrustedRootSet::default()
+        };
+        Ok(Self {
+            url: url.to_string(),
+            path,
+            current,
+            on_change: None,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Call `handler` with every non-empty change before accepting it
+    ///
+    /// The change is accepted if `handler` returns `true`; otherwise the
+    /// cached roots are kept and `refresh` fails with `Rejected`. Without a
+    /// handler every change is accepted. The first refresh of an empty cache
+    /// reports every entry as added.
+    pub fn with_change_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&TrustRootDiff) -> bool + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(handler));
+        self
+    }
+
+    /// Fetch with `client`, e.g. one built by `NetworkConfig::client`
+    pub fn with_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// The trusted roots accepted so far
+    pub fn current(&self) -> &TrustedRootSet {
+        &self.current
+    }
+
+    /// Fetch the trusted roots and accept them if the change handler agrees
+    ///
+    /// # Returns
+    ///
+    /// The changes that were accepted (empty if nothing changed)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Rejected` with the diff if the handler refused the change, and
+    /// fetch, parse or IO errors otherwise; the cache is left unchanged on error.
+    pub fn refresh(&mut self) -> Result<TrustRootDiff, TrustRootError> {
+        let response = self.client.get(&self.url).send().map_err(|e| TrustRootError::Fetch(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(TrustRootError::Fetch(format!("{} returned HTTP {}", self.url, response.status())));
+        }
+        let content = response.text().map_err(|e| TrustRootError::Fetch(e.to_string()))?;
+        self.update(&content)
+    }
+
+    /// Accept `content` as the new trusted roots if the change handler agrees
+    ///
+    /// `refresh` without the fetch, for roots obtained another way.
+    pub fn update(&mut self, content: &str) -> Result<TrustRootDiff, TrustRootError> {
+        let fetched = TrustedRootSet::from_jsonl(content)?;
+        let diff = TrustedRootSet::diff(&self.current, &fetched);
+        if diff.is_empty() {
+            return Ok(diff);
+        }
+        if let Some(handler) = &self.on_change {
+            if !handler(&diff) {
+                return Err(TrustRootError::Rejected(Box::new(diff)));
+            }
+        }
+
+        let jsonl: Vec<String> = fetched
+            .roots
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<_, _>>()
+            .map_err(|e| TrustRootError::Fetch(e.to_string()))?;
+        fs::write(&self.path, jsonl.join("\n") + "\n")?;
+        self.current = fetched;
+        Ok(diff)
+    }
+}
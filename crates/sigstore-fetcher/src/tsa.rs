@@ -0,0 +1,300 @@
+//! RFC 3161 timestamp authority client
+//!
+//! Builds a TimeStampReq for a hash, posts it to a TSA and checks the
+//! TimeStampResp before handing the token back: the TSA must grant the request,
+//! the token must cover the requested hash and echo the request's nonce, and it
+//! must be signed by the given TSA chain. The token goes into a bundle's
+//! `verificationMaterial.timestampVerificationData.rfc3161Timestamps`.
+//!
+//! A [`TsaCache`] keeps the fetched TSA chains and every TimeStampResp in a
+//! directory, which `sigstore_verifier::verifier::timestamp::TsaMaterial::from_dir`
+//! loads to verify RFC 3161 timestamps without network access.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rand_core::{OsRng, RngCore};
+
+use sigstore_verifier::crypto::hash::{constant_time_eq, sha256};
+use sigstore_verifier::error::{CertificateError, TimestampError, VerificationError};
+use sigstore_verifier::parser::certificate::{certs_to_chain, parse_pem_certificates};
+use sigstore_verifier::parser::rfc3161::{extract_granted_timestamp_token, parse_rfc3161_timestamp, HashAlgorithm};
+use sigstore_verifier::types::certificate::CertificateChain;
+use sigstore_verifier::verifier::rfc3161::verify_pkcs7_signature;
+
+use crate::http_error;
+use crate::trust_bundle::fetch_trust_bundle_with;
+
+/// Public-good Sigstore timestamp authority
+pub const PUBLIC_GOOD_TSA_URL: &str = "https://timestamp.sigstore.dev/api/v1/timestamp";
+
+/// DER-encoded AlgorithmIdentifier for SHA-256 (with NULL parameters)
+const SHA256_ALGORITHM: &[u8] = &[
+    0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00,
+];
+
+/// DER-encoded AlgorithmIdentifier for SHA-384 (with NULL parameters)
+const SHA384_ALGORITHM: &[u8] = &[
+    0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00,
+];
+
+/// Request a timestamp for `hash` and validate the response
+///
+/// For a bundle, `hash` is the SHA-256 of the DSSE signature bytes.
+///
+/// # Arguments
+/// * `hash` - SHA-256 or SHA-384 digest to timestamp (the algorithm is taken from its length)
+/// * `tsa_url` - Timestamp endpoint, e.g. `PUBLIC_GOOD_TSA_URL`
+/// * `tsa_chain` - Chain the token must be signed by
+///
+/// # Returns
+/// The DER-encoded TimeStampToken
+pub fn request_timestamp(
+    hash: &[u8],
+    tsa_url: &str,
+    tsa_chain: &CertificateChain,
+) -> Result<Vec<u8>, VerificationError> {
+    request_timestamp_with(&reqwest::blocking::Client::new(), hash, tsa_url, tsa_chain)
+}
+
+/// [`request_timestamp`] through `client`, e.g. one built by `NetworkConfig::client`
+pub fn request_timestamp_with(
+    client: &reqwest::blocking::Client,
+    hash: &[u8],
+    tsa_url: &str,
+    tsa_chain: &CertificateChain,
+) -> Result<Vec<u8>, VerificationError> {
+    let (response, nonce) = post_timestamp_request(client, hash, tsa_url)?;
+    validate_timestamp_response(&response, hash, Some(&nonce), tsa_chain)
+}
+
+/// Post a TimeStampReq for `hash` with a fresh nonce
+///
+/// # Returns
+/// The DER-encoded TimeStampResp and the nonce it must echo
+fn post_timestamp_request(
+    client: &reqwest::blocking::Client,
+    hash: &[u8],
+    tsa_url: &str,
+) -> Result<(Vec<u8>, [u8; 8]), VerificationError> {
+    let mut nonce = [0u8; 8];
+    OsRng.fill_bytes(&mut nonce);
+    // Positive and without leading zero bytes, as the TSA will encode it
+    nonce[0] = (nonce[0] & 0x7f) | 0x40;
+
+    let request = encode_timestamp_request(hash, &nonce)?;
+    let response = client
+        .post(tsa_url)
+        .header(reqwest::header::CONTENT_TYPE, "application/timestamp-query")
+        .body(request)
+        .send().map_err(http_error)?;
+
+    if !response.status().is_success() {
+        return Err(TimestampError::Rfc3161Rejected {
+            status: u64::from(response.status().as_u16()),
+            message: format!("HTTP error for {}", tsa_url),
+        }
+        .into());
+    }
+
+    let response = response.bytes().map_err(http_error)?;
+    Ok((response.to_vec(), nonce))
+}
+
+/// TSA chains and timestamp responses kept in a directory for offline verification
+///
+/// Chains are stored as `chain-<URL hash>.pem` (leaf first) and responses as
+/// `<hex digest>.tsr`, the layout `TsaMaterial::from_dir` reads. A timestamp
+/// request for a digest with a stored response replays it instead of asking
+/// the TSA again, so a build can be re-timestamped and re-verified offline.
+///
+/// # Example
+///
+/// ```ignore
+/// let cache = TsaCache::new("tsa")?;
+/// let tsa_chain = cache.fetch_chain("https://timestamp.githubapp.com/api/v1/timestamp/certchain")?;
+/// let token = cache.request_timestamp(&signature_hash, PUBLIC_GOOD_TSA_URL, &tsa_chain)?;
+/// // later, offline
+/// let material = TsaMaterial::from_dir(Path::new("tsa"))?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TsaCache {
+    dir: PathBuf,
+    client: reqwest::blocking::Client,
+}
+
+impl TsaCache {
+    /// Open the cache in `dir`, creating the directory if needed
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, VerificationError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(TsaCache {
+            dir,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Send requests with `client`, e.g. one built by `NetworkConfig::client`
+    pub fn with_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// The cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The TSA chain served at `url`, fetched once and then read from the cache
+    ///
+    /// # Arguments
+    /// * `url` - Certificate chain endpoint serving PEM, leaf first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chain cannot be fetched or parsed, has no
+    /// signing certificate, or cannot be written to the cache.
+    pub fn fetch_chain(&self, url: &str) -> Result<CertificateChain, VerificationError> {
+        let path = self.dir.join(format!("chain-{}.pem", &hex::encode(sha256(url.as_bytes()))[..16]));
+        if path.exists() {
+            return Ok(certs_to_chain(parse_pem_certificates(&fs::read(&path)?)?)?);
+        }
+
+        let chain = fetch_trust_bundle_with(&self.client, url)?;
+        if chain.leaf.is_empty() {
+            return Err(CertificateError::TrustBundleFetch(format!("{} serves no TSA signing certificate", url)).into());
+        }
+        let certificates: Vec<pem::Pem> = std::iter::once(&chain.leaf)
+            .chain(&chain.intermediates)
+            .chain(std::iter::once(&chain.root))
+            .map(|der| pem::Pem::new("CERTIFICATE", der.clone()))
+            .collect();
+        fs::write(&path, pem::encode_many(&certificates))?;
+        Ok(chain)
+    }
+
+    /// [`request_timestamp`], replaying the stored response for `hash` if there is one
+    ///
+    /// A new response is validated, then stored as `<hex hash>.tsr`. A stored
+    /// response is validated against `tsa_chain` again, without the nonce check.
+    pub fn request_timestamp(
+        &self,
+        hash: &[u8],
+        tsa_url: &str,
+        tsa_chain: &CertificateChain,
+    ) -> Result<Vec<u8>, VerificationError> {
+        let path = self.dir.join(format!("{}.tsr", hex::encode(hash)));
+        if path.exists() {
+            return validate_timestamp_response(&fs::read(&path)?, hash, None, tsa_chain);
+        }
+
+        let (response, nonce) = post_timestamp_request(&self.client, hash, tsa_url)?;
+        let token = validate_timestamp_response(&response, hash, Some(&nonce), tsa_chain)?;
+        fs::write(&path, &response)?;
+        Ok(token)
+    }
+}
+
+/// Validate a TimeStampResp for a request of `hash` with `nonce`
+///
+/// # Arguments
+/// * `response` - DER-encoded TimeStampResp
+/// * `hash` - Digest that was sent in the request
+/// * `nonce` - Nonce that was sent in the request, if any
+/// * `tsa_chain` - Chain the token must be signed by
+///
+/// # Returns
+/// The DER-encoded TimeStampToken
+pub fn validate_timestamp_response(
+    response: &[u8],
+    hash: &[u8],
+    nonce: Option<&[u8]>,
+    tsa_chain: &CertificateChain,
+) -> Result<Vec<u8>, VerificationError> {
+    let token = extract_granted_timestamp_token(response)?;
+    let timestamp = parse_rfc3161_timestamp(&token)?;
+    let imprint = &timestamp.tst_info.message_imprint;
+
+    if imprint.hash_algorithm != hash_algorithm(hash)? || !constant_time_eq(&imprint.hashed_message, hash) {
+        return Err(TimestampError::MessageImprintMismatch {
+            expected: hex::encode(hash),
+            actual: hex::encode(&imprint.hashed_message),
+        }
+        .into());
+    }
+    if let Some(nonce) = nonce {
+        if timestamp.tst_info.nonce.as_deref() != Some(nonce) {
+            return Err(TimestampError::NonceMismatch.into());
+        }
+    }
+    verify_pkcs7_signature(&timestamp.signed_data, tsa_chain)?;
+
+    Ok(token)
+}
+
+fn hash_algorithm(hash: &[u8]) -> Result<HashAlgorithm, TimestampError> {
+    match hash.len() {
+        32 => Ok(HashAlgorithm::Sha256),
+        48 => Ok(HashAlgorithm::Sha384),
+        len => Err(TimestampError::UnsupportedHashAlgorithm(format!("{}-byte digest", len))),
+    }
+}
+
+/// Encode a TimeStampReq asking the TSA to include its certificate
+///
+/// TimeStampReq ::= SEQUENCE {
+///     version        INTEGER { v1(1) },
+///     messageImprint MessageImprint,
+///     reqPolicy      TSAPolicyId OPTIONAL,
+///     nonce          INTEGER OPTIONAL,
+///     certReq        BOOLEAN DEFAULT FALSE,
+///     extensions     [0] IMPLICIT Extensions OPTIONAL
+/// }
+fn encode_timestamp_request(hash: &[u8], nonce: &[u8]) -> Result<Vec<u8>, TimestampError> {
+    let algorithm = match hash_algorithm(hash)? {
+        HashAlgorithm::Sha256 => SHA256_ALGORITHM,
+        HashAlgorithm::Sha384 => SHA384_ALGORITHM,
+    };
+
+    let mut imprint = algorithm.to_vec();
+    imprint.extend(der_tlv(0x04, hash));
+
+    let mut request = der_tlv(0x02, &[0x01]);
+    request.extend(der_tlv(0x30, &imprint));
+    request.extend(der_tlv(0x02, nonce));
+    request.extend(der_tlv(0x01, &[0xff]));
+    Ok(der_tlv(0x30, &request))
+}
+
+fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    if content.len() < 0x80 {
+        out.push(content.len() as u8);
+    } else {
+        let len = content.len().to_be_bytes();
+        let len = &len[len.iter().position(|byte| *byte != 0).unwrap_or(len.len() - 1)..];
+        out.push(0x80 | len.len() as u8);
+        out.extend_from_slice(len);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_timestamp_request() {
+        let request = encode_timestamp_request(&[0xab; 32], &[0x42, 0x01]).unwrap();
+        let mut expected = vec![0x30, 0x3d, 0x02, 0x01, 0x01, 0x30, 0x31];
+        expected.extend_from_slice(SHA256_ALGORITHM);
+        expected.extend_from_slice(&[0x04, 0x20]);
+        expected.extend_from_slice(&[0xab; 32]);
+        expected.extend_from_slice(&[0x02, 0x02, 0x42, 0x01, 0x01, 0x01, 0xff]);
+        assert_eq!(request, expected);
+
+        assert!(encode_timestamp_request(&[0xab; 20], &[0x01]).is_err());
+        assert_eq!(der_tlv(0x04, &[0; 200])[..3], [0x04, 0x81, 200]);
+    }
+}
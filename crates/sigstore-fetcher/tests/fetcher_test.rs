@@ -0,0 +1,172 @@
+use sigstore_fetcher::corpus::{bundle_digest, CorpusManifest};
+use sigstore_fetcher::trust_bundle::fetch_fulcio_trust_bundle;
+use sigstore_fetcher::tsa::validate_timestamp_response;
+use sigstore_verifier::types::certificate::{CertificateChain, FulcioInstance};
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_verifier::AttestationVerifier;
+use std::path::PathBuf;
+
+#[test]
+fn test_verify_rekor_bundle() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+
+    // Auto-detect Fulcio instance from bundle
+    let bundle_json = std::fs::read_to_string(&path).expect("Failed to read bundle");
+    let instance =
+        FulcioInstance::from_bundle_json(&bundle_json).expect("Failed to detect Fulcio instance");
+
+    // Fetch trust bundle for detected instance
+    // In production, the client should fetch and cache this
+    let trust_bundle = fetch_fulcio_trust_bundle(&instance).expect("Failed to fetch trust bundle");
+
+    let verifier = AttestationVerifier::new();
+    let options = VerificationOptions::default();
+
+    let result = verifier.verify_bundle(&path, options, &trust_bundle, None);
+    assert!(result.is_ok(), "Verification failed: {:?}", result.err());
+
+    if let Ok(verification_result) = result {
+        println!("Verification succeeded!");
+        println!(
+            "Leaf hash: {}",
+            hex::encode(verification_result.certificate_hashes.leaf)
+        );
+        println!(
+            "Root hash: {}",
+            hex::encode(verification_result.certificate_hashes.root)
+        );
+        println!("Signing time: {}", verification_result.signing_time);
+    }
+}
+
+fn load_rfc3161_sample() -> (Vec<u8>, CertificateChain, CertificateChain) {
+    use sigstore_verifier::fetcher::jsonl::parser::{
+        load_trusted_root_from_jsonl, select_certificate_authority, select_timestamp_authority,
+    };
+    use sigstore_verifier::parser::bundle::{extract_bundle_timestamp, parse_bundle_from_bytes};
+
+    let mut samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    samples.pop();
+    samples.pop();
+    samples.push("samples");
+
+    let bundle_json =
+        std::fs::read(samples.join("actions-attest-build-provenance-attestation-13581567.sigstore.json"))
+            .expect("Failed to read bundle");
+    let trusted_root_content = std::fs::read_to_string(samples.join("trusted_root.jsonl"))
+        .expect("Failed to read trusted root file");
+    let trust_roots = load_trusted_root_from_jsonl(&trusted_root_content)
+        .expect("Failed to parse trusted root JSONL");
+
+    let fulcio_instance = FulcioInstance::from_bundle_json(std::str::from_utf8(&bundle_json).unwrap())
+        .expect("Failed to detect Fulcio instance");
+    let bundle = parse_bundle_from_bytes(&bundle_json).expect("Failed to parse bundle");
+    let timestamp = extract_bundle_timestamp(&bundle).expect("Failed to extract timestamp");
+    let fulcio_chain = select_certificate_authority(&trust_roots, &fulcio_instance, timestamp)
+        .expect("Failed to select certificate authority");
+    let tsa_chain = select_timestamp_authority(&trust_roots, &fulcio_instance, timestamp)
+        .expect("Failed to select timestamp authority");
+
+    (bundle_json, fulcio_chain, tsa_chain)
+}
+
+#[test]
+fn test_validate_timestamp_response() {
+    use sha2::{Digest, Sha256};
+    use sigstore_verifier::error::{TimestampError, VerificationError};
+    use sigstore_verifier::parser::bundle::{decode_base64, parse_bundle_from_bytes};
+
+    let (bundle_json, _, tsa_chain) = load_rfc3161_sample();
+    let bundle = parse_bundle_from_bytes(&bundle_json).expect("Failed to parse bundle");
+    let token = decode_base64(
+        &bundle.verification_material.timestamp_verification_data.unwrap().rfc3161_timestamps.unwrap()[0]
+            .signed_timestamp,
+    )
+    .unwrap();
+    let hash = Sha256::digest(decode_base64(&bundle.dsse_envelope.signatures[0].sig).unwrap()).to_vec();
+
+    // TimeStampResp { status: granted, timeStampToken }
+    let mut content = vec![0x30, 0x03, 0x02, 0x01, 0x00];
+    content.extend_from_slice(&token);
+    let mut response = vec![0x30, 0x82];
+    response.extend_from_slice(&(content.len() as u16).to_be_bytes());
+    response.extend_from_slice(&content);
+
+    assert_eq!(validate_timestamp_response(&response, &hash, None, &tsa_chain).unwrap(), token);
+    assert!(matches!(
+        validate_timestamp_response(&response, &[0; 32], None, &tsa_chain),
+        Err(VerificationError::Timestamp(TimestampError::MessageImprintMismatch { .. }))
+    ));
+    // The GitHub TSA token was requested without a nonce
+    assert!(matches!(
+        validate_timestamp_response(&response, &hash, Some(&[0x42]), &tsa_chain),
+        Err(VerificationError::Timestamp(TimestampError::NonceMismatch))
+    ));
+}
+
+#[test]
+fn test_samples_match_corpus() {
+    let mut samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    samples.pop();
+    samples.pop();
+    samples.push("samples");
+
+    // Every pinned bundle is present with its pinned digest (run the corpus example to fetch it)
+    let manifest = CorpusManifest::from_path(&samples.join("corpus.json")).expect("Failed to load corpus manifest");
+    for entry in &manifest.bundles {
+        assert!(entry.is_materialized(&samples), "{} is missing or does not match its pin", entry.file_name());
+    }
+
+    // and every sample bundle is pinned
+    for file in std::fs::read_dir(&samples).expect("Failed to read samples") {
+        let name = file.unwrap().file_name().into_string().unwrap();
+        if let Some(stem) = name.strip_suffix(".sigstore.json") {
+            assert!(
+                manifest.bundles.iter().any(|entry| entry.name == stem),
+                "{} is not pinned in corpus.json",
+                name
+            );
+        }
+    }
+
+    let bundle = std::fs::read(samples.join(manifest.bundles[0].file_name())).unwrap();
+    let mut reformatted = serde_json::to_vec(&serde_json::from_slice::<serde_json::Value>(&bundle).unwrap()).unwrap();
+    assert_eq!(bundle_digest(&reformatted).unwrap(), manifest.bundles[0].digest);
+    reformatted.splice(1..1, *b" ");
+    assert_eq!(bundle_digest(&reformatted).unwrap(), manifest.bundles[0].digest);
+}
+
+#[test]
+fn test_trusted_root_cache_update() {
+    use sigstore_fetcher::error::TrustRootError;
+    use sigstore_fetcher::trusted_root::TrustedRootCache;
+
+    let mut samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    samples.pop();
+    samples.pop();
+    samples.push("samples");
+    let content = std::fs::read_to_string(samples.join("trusted_root.jsonl")).expect("Failed to read trusted root file");
+    let path = std::env::temp_dir().join(format!("trusted_root_cache_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut cache = TrustedRootCache::new("https://example.invalid/trusted_root.jsonl", &path)
+        .unwrap()
+        .with_change_handler(|diff| !diff.has_removals());
+    let diff = cache.update(&content).expect("Initial roots should be accepted");
+    assert!(!diff.is_empty());
+    assert_eq!(cache.current().roots.len(), 2);
+    assert!(cache.update(&content).unwrap().is_empty());
+
+    // Dropping the GitHub roots removes entries: the handler refuses and the cache is kept
+    let first_line = content.lines().next().unwrap();
+    assert!(matches!(cache.update(first_line), Err(TrustRootError::Rejected(ref diff)) if diff.has_removals()));
+    assert_eq!(cache.current().roots.len(), 2);
+
+    // The accepted roots were persisted
+    let reopened = TrustedRootCache::new("https://example.invalid/trusted_root.jsonl", &path).unwrap();
+    assert_eq!(reopened.current().roots.len(), 2);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,23 @@
+use sigstore_verifier::error::VerificationError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(#[from] VerificationError),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    #[error("Stored record is malformed: {0}")]
+    MalformedRecord(String),
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(e: serde_json::Error) -> Self {
+        StoreError::Serialization(e.to_string())
+    }
+}
@@ -0,0 +1,35 @@
+//! # Sigstore Attestation Store
+//!
+//! Persists bundles, verification results, proof artifacts and policies in
+//! SQLite or PostgreSQL (through sqlx), keyed by subject digest, so that an
+//! attestation registry can answer queries such as "all verified attestations
+//! for digest X signed since date Y" without re-verifying.
+//!
+//! ## Features
+//!
+//! - `sqlite` (default): `sqlite://` URLs
+//! - `postgres`: `postgres://` URLs
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use sigstore_store::AttestationStore;
+//!
+//! let store = AttestationStore::connect("sqlite://attestations.db?mode=rwc").await?;
+//! let bundle_id = store.put_bundle(&bundle_json).await?;
+//! let policy_id = store.put_policy("release", &options).await?;
+//!
+//! let result = verifier.verify_bundle_bytes(&bundle_json, options, &trust_bundle, tsa_chain);
+//! store.record_verification(&bundle_id, Some(&policy_id), &result, Utc::now()).await?;
+//!
+//! let attestations = store.verified_attestations(&"sha256:<digest>".parse()?, since).await?;
+//! ```
+
+pub mod error;
+pub mod record;
+pub mod schema;
+pub mod store;
+
+pub use error::StoreError;
+pub use record::{StoredBundle, StoredProof, VerifiedAttestation};
+pub use store::AttestationStore;
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::types::result::VerificationResult;
+use sigstore_zkvm_traits::utils::ProofArtifact;
+
+/// A bundle as stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredBundle {
+    /// Hex SHA-256 of the bundle JSON
+    pub id: String,
+    pub predicate_type: String,
+    pub bundle_json: String,
+    pub stored_at: DateTime<Utc>,
+}
+
+/// A bundle with one of its successful verifications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifiedAttestation {
+    pub bundle: StoredBundle,
+    /// Policy the bundle was verified under, if it was stored
+    pub policy_id: Option<String>,
+    pub verified_at: DateTime<Utc>,
+    pub result: VerificationResult,
+}
+
+/// A proof artifact generated for a stored bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredProof {
+    pub bundle_id: String,
+    pub policy_id: Option<String>,
+    pub artifact: ProofArtifact,
+    pub stored_at: DateTime<Utc>,
+}
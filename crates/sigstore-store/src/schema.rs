@@ -0,0 +1,50 @@
+//! Database schema
+//!
+//! The statements only use types and syntax that SQLite and PostgreSQL share:
+//! `TEXT` for JSON documents and digests, `BIGINT` for Unix timestamps in
+//! seconds, and `$n` placeholders.
+
+/// Statements creating the tables and indexes, run by `AttestationStore::migrate`
+pub const MIGRATIONS: &[&str] = &[
+    // Bundles keyed by the SHA-256 of their JSON
+    "CREATE TABLE IF NOT EXISTS bundles (
+        id TEXT PRIMARY KEY,
+        predicate_type TEXT NOT NULL,
+        bundle_json TEXT NOT NULL,
+        stored_at BIGINT NOT NULL
+    )",
+    // Subject digests of each bundle's statement, as `<alg>:<hex>`
+    "CREATE TABLE IF NOT EXISTS subjects (
+        bundle_id TEXT NOT NULL REFERENCES bundles (id),
+        subject_digest TEXT NOT NULL,
+        PRIMARY KEY (bundle_id, subject_digest)
+    )",
+    "CREATE INDEX IF NOT EXISTS subjects_digest ON subjects (subject_digest)",
+    // Verification options keyed by their options hash
+    "CREATE TABLE IF NOT EXISTS policies (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        options_json TEXT NOT NULL,
+        stored_at BIGINT NOT NULL
+    )",
+    // One row per verification run; error_code is NULL when it succeeded
+    "CREATE TABLE IF NOT EXISTS verifications (
+        bundle_id TEXT NOT NULL REFERENCES bundles (id),
+        policy_id TEXT REFERENCES policies (id),
+        verified_at BIGINT NOT NULL,
+        signing_time BIGINT,
+        result_json TEXT,
+        error_code TEXT,
+        error_message TEXT
+    )",
+    "CREATE INDEX IF NOT EXISTS verifications_bundle ON verifications (bundle_id)",
+    "CREATE TABLE IF NOT EXISTS proofs (
+        bundle_id TEXT NOT NULL REFERENCES bundles (id),
+        policy_id TEXT REFERENCES policies (id),
+        zkvm TEXT NOT NULL,
+        program_id TEXT NOT NULL,
+        artifact_json TEXT NOT NULL,
+        stored_at BIGINT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS proofs_bundle ON proofs (bundle_id)",
+];
@@ -2,9 +2,7 @@ use sigstore_verifier::fetcher::jsonl::parser::{
     load_trusted_root_from_jsonl, select_certificate_authority, select_timestamp_authority,
 };
 use sigstore_verifier::parser::bundle::{extract_bundle_timestamp, parse_bundle_from_path};
-use sigstore_verifier::types::certificate::FulcioInstance;
-use sigstore_verifier::types::result::VerificationOptions;
-use sigstore_verifier::AttestationVerifier;
+use sigstore_verifier::prelude::{AttestationVerifier, FulcioInstance, VerificationOptions};
 use std::env;
 use std::path::PathBuf;
 
@@ -82,11 +80,7 @@ fn main() {
     // Verify the bundle
     let verifier = AttestationVerifier::new();
 
-    let options = VerificationOptions {
-        expected_digest: None,
-        expected_issuer: None,
-        expected_subject: None,
-    };
+    let options = VerificationOptions::default();
 
     match verifier.verify_bundle(&bundle_path, options, &fulcio_chain, Some(&tsa_chain)) {
         Ok(result) => {
@@ -101,7 +95,7 @@ fn main() {
             println!();
 
             println!("Signing Time: {}", result.signing_time.to_rfc3339());
-            println!("Subject Digest: {}", hex::encode(&result.subject_digest));
+            println!("Subject Digest: {}", result.subject_digest);
 
             if let Some(ref identity) = result.oidc_identity {
                 println!("\nOIDC Identity:");
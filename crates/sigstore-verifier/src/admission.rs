@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::certificate::{CertificateChain, OidcIdentity};
+use crate::types::digest::Digest;
+use crate::types::result::{VerificationOptions, VerificationResult};
+use crate::AttestationVerifier;
+
+/// Input to an admission decision: the image being admitted and the
+/// attestation bundles that were fetched for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdmissionRequest {
+    /// Image reference or bare digest, e.g. `ghcr.io/org/app@sha256:<hex>` or `sha256:<hex>`
+    pub image: String,
+
+    /// Raw JSON bytes of each sigstore bundle attached to the image
+    pub bundles: Vec<Vec<u8>>,
+}
+
+/// Outcome of an admission review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdmissionDecision {
+    pub allowed: bool,
+    pub reasons: Vec<AdmissionReason>,
+}
+
+/// Structured reason attached to an admission decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AdmissionReason {
+    /// The image reference does not carry a usable `sha256:` digest
+    InvalidImageDigest { image: String },
+
+    /// No attestation bundles were supplied for the image
+    NoAttestations,
+
+    /// The bundle at `index` verified against the image digest and policy
+    BundleVerified {
+        index: usize,
+        signing_time: i64,
+        identity: Option<OidcIdentity>,
+    },
+
+    /// The bundle at `index` failed verification or policy evaluation
+    BundleRejected { index: usize, error: String },
+}
+
+impl AdmissionDecision {
+    fn deny(reason: AdmissionReason) -> Self {
+        Self {
+            allowed: false,
+            reasons: vec![reason],
+        }
+    }
+
+    /// Human-readable summary suitable for an `AdmissionReview` status message
+    pub fn message(&self) -> String {
+        let parts: Vec<String> = self
+            .reasons
+            .iter()
+            .map(|reason| match reason {
+                AdmissionReason::InvalidImageDigest { image } => {
+                    format!("image '{}' is not pinned by sha256 digest", image)
+                }
+                AdmissionReason::NoAttestations => "no attestation bundles found".to_string(),
+                AdmissionReason::BundleVerified { index, .. } => {
+                    format!("bundle {} verified", index)
+                }
+                AdmissionReason::BundleRejected { index, error } => {
+                    format!("bundle {} rejected: {}", index, error)
+                }
+            })
+            .collect();
+        parts.join("; ")
+    }
+}
+
+/// Maps admission requests to bundle verification and policy evaluation
+///
+/// An image is admitted when at least one of its bundles verifies against
+/// the image digest, the trust material and the configured options.
+#[derive(Debug, Clone)]
+pub struct AdmissionReviewer {
+    verifier: AttestationVerifier,
+    options: VerificationOptions,
+    trust_bundle: CertificateChain,
+    tsa_cert_chain: Option<CertificateChain>,
+}
+
+impl AdmissionReviewer {
+    /// Create a reviewer
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Policy applied to every bundle; `expected_digest` is replaced by the image digest
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    pub fn new(
+        options: VerificationOptions,
+        trust_bundle: CertificateChain,
+        tsa_cert_chain: Option<CertificateChain>,
+    ) -> Self {
+        Self {
+            verifier: AttestationVerifier::new(),
+            options,
+            trust_bundle,
+            tsa_cert_chain,
+        }
+    }
+
+    /// Review an admission request
+    ///
+    /// # Returns
+    ///
+    /// An `AdmissionDecision` with one reason per bundle, or a single reason
+    /// when the request itself is unusable.
+    pub fn review(&self, request: &AdmissionRequest) -> AdmissionDecision {
+        let digest = match parse_image_digest(&request.image) {
+            Some(digest) => digest,
+            None => {
+                return AdmissionDecision::deny(AdmissionReason::InvalidImageDigest {
+                    image: request.image.clone(),
+                })
+            }
+        };
+
+        if request.bundles.is_empty() {
+            return AdmissionDecision::deny(AdmissionReason::NoAttestations);
+        }
+
+        let mut options = self.options.clone();
+        options.expected_digest = Some(digest);
+
+        let reasons: Vec<AdmissionReason> = request
+            .bundles
+            .iter()
+            .enumerate()
+            .map(|(index, bundle_json)| {
+                match self.verify_one(bundle_json, options.clone()) {
+                    Ok(result) => AdmissionReason::BundleVerified {
+                        index,
+                        signing_time: result.signing_time.timestamp(),
+                        identity: result.oidc_identity,
+                    },
+                    Err(error) => AdmissionReason::BundleRejected { index, error },
+                }
+            })
+            .collect();
+
+        let allowed = reasons
+            .iter()
+            .any(|reason| matches!(reason, AdmissionReason::BundleVerified { .. }));
+
+        AdmissionDecision { allowed, reasons }
+    }
+
+    fn verify_one(
+        &self,
+        bundle_json: &[u8],
+        options: VerificationOptions,
+    ) -> Result<VerificationResult, String> {
+        self.verifier
+            .verify_bundle_bytes(
+                bundle_json,
+                options,
+                &self.trust_bundle,
+                self.tsa_cert_chain.as_ref(),
+            )
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Extract the digest from an image reference
+///
+/// Accepts either a bare `<alg>:<hex>` digest or a full reference pinned with
+/// `@<alg>:<hex>`. Tag-only references return `None`.
+pub fn parse_image_digest(image: &str) -> Option<Digest> {
+    let digest = match image.rsplit_once('@') {
+        Some((_, digest)) => digest,
+        None => image,
+    };
+    digest.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn reviewer() -> AdmissionReviewer {
+        let empty_chain = CertificateChain {
+            leaf: vec![],
+            intermediates: vec![],
+            root: vec![],
+        };
+        AdmissionReviewer::new(VerificationOptions::default(), empty_chain, None)
+    }
+
+    #[test]
+    fn test_parse_image_digest() {
+        let bare = format!("sha256:{}", DIGEST);
+        let pinned = format!("ghcr.io/org/app@sha256:{}", DIGEST);
+        assert_eq!(parse_image_digest(&bare).unwrap().bytes.len(), 32);
+        assert_eq!(parse_image_digest(&pinned), parse_image_digest(&bare));
+        assert!(parse_image_digest("ghcr.io/org/app:latest").is_none());
+        assert!(parse_image_digest("sha256:abcd").is_none());
+    }
+
+    #[test]
+    fn test_review_denies_unpinned_image() {
+        let decision = reviewer().review(&AdmissionRequest {
+            image: "ghcr.io/org/app:latest".to_string(),
+            bundles: vec![b"{}".to_vec()],
+        });
+        assert!(!decision.allowed);
+        assert!(matches!(
+            decision.reasons[0],
+            AdmissionReason::InvalidImageDigest { .. }
+        ));
+    }
+
+    #[test]
+    fn test_review_denies_without_bundles() {
+        let decision = reviewer().review(&AdmissionRequest {
+            image: format!("sha256:{}", DIGEST),
+            bundles: vec![],
+        });
+        assert!(!decision.allowed);
+        assert_eq!(decision.reasons, vec![AdmissionReason::NoAttestations]);
+    }
+
+    #[test]
+    fn test_review_reports_rejected_bundles() {
+        let decision = reviewer().review(&AdmissionRequest {
+            image: format!("sha256:{}", DIGEST),
+            bundles: vec![b"not json".to_vec()],
+        });
+        assert!(!decision.allowed);
+        assert!(matches!(
+            decision.reasons[0],
+            AdmissionReason::BundleRejected { index: 0, .. }
+        ));
+        assert!(decision.message().starts_with("bundle 0 rejected"));
+    }
+}
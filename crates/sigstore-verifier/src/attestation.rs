@@ -0,0 +1,1207 @@
+//! The verification pipeline behind [`AttestationVerifier`]
+
+use std::io::{BufRead, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::{error, parser, policy, profiling, redaction, types};
+use crate::audit::{AuditEvent, AuditSink};
+use crate::build_tree::{BuildNode, BuildTreeWalker, BundleSource};
+use crate::crypto::hash::{constant_time_eq, hash_reader};
+use crate::error::{TransparencyError, VerificationError};
+use crate::parser::bundle::{
+    decode_base64, parse_bundle_from_bytes_with_limits, parse_bundle_from_path_with_limits, parse_dsse_payload,
+};
+use crate::parser::certificate::certs_to_chain;
+use crate::parser::identity::{extract_fulcio_extensions, extract_oidc_identity};
+use crate::parser::payload::{PayloadParser, PayloadParserRegistry};
+use crate::parser::rfc3161::parse_rfc3161_timestamp;
+use crate::parser::simple_signing::parse_simple_signing_payload;
+use crate::policy::dependency::resolved_dependencies;
+use crate::policy::hook::{PolicyHook, PolicyInput, TlogMetadata};
+use crate::policy::pattern::wildcard_match;
+use crate::profiling::CycleSpan;
+use crate::stream::BundleStream;
+use crate::types::bundle::{Certificate, DsseEnvelope, Rfc3161Timestamp, TimestampVerificationData, VerificationMaterial};
+use crate::types::certificate::{CertificateChain, ParsedCertificate};
+use crate::types::limits::InputLimits;
+use crate::types::simple_signing::SimpleSignature;
+use crate::types::result::{
+    CertificateChainHashes, Digest, DigestAlgorithm, TimestampProof, TimestampRequirement, VerificationOptions,
+    VerificationResult,
+};
+use crate::verifier::certificate::{verify_chain, verify_tsa_chain};
+use crate::verifier::context::VerificationContext;
+use crate::verifier::pool::CertificatePool;
+use crate::verifier::rfc3161::verify_rfc3161_token;
+use crate::verifier::root_store::RootStore;
+use crate::verifier::signature::{verify_dsse_signature_bytes, verify_message_signature};
+use crate::verifier::statement::validate_statement_json;
+use crate::verifier::step::{BuiltinStep, StepContext, VerificationStep};
+use crate::verifier::subject::verify_subject;
+use crate::verifier::timestamp::{get_integrated_time, verify_signing_time_in_validity, TsaMaterial};
+use crate::verifier::transparency::{
+    verify_entry_body, verify_entry_checkpoint, verify_entry_log_ids, verify_signed_entry_timestamp,
+    verify_tlog_entries_in, SignedEnvelope,
+};
+
+/// Main attestation verifier
+#[derive(Clone, Default)]
+pub struct AttestationVerifier {
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    policy_hook: Option<Arc<dyn PolicyHook>>,
+    steps: Vec<(BuiltinStep, Arc<dyn VerificationStep>)>,
+    payload_parsers: PayloadParserRegistry,
+    retain_certificate_chains: bool,
+    certificate_pool: Option<Arc<CertificatePool>>,
+    tsa_root_store: Option<Arc<RootStore>>,
+    tsa_material: Option<Arc<TsaMaterial>>,
+    pub(crate) input_limits: InputLimits,
+    context: VerificationContext,
+    #[cfg(feature = "revocation")]
+    crls: Option<Arc<crate::verifier::revocation::CrlSet>>,
+}
+
+impl std::fmt::Debug for AttestationVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("AttestationVerifier");
+        debug
+            .field("audit_sink", &self.audit_sink.is_some())
+            .field("policy_hook", &self.policy_hook.is_some())
+            .field("steps", &self.steps.iter().map(|(after, _)| after).collect::<Vec<_>>())
+            .field("payload_parsers", &self.payload_parsers)
+            .field("retain_certificate_chains", &self.retain_certificate_chains)
+            .field("certificate_pool", &self.certificate_pool.as_ref().map(|pool| pool.stats()))
+            .field("tsa_root_store", &self.tsa_root_store.as_ref().map(|store| store.len()))
+            .field("tsa_material", &self.tsa_material.as_ref().map(|material| material.token_count()))
+            .field("input_limits", &self.input_limits)
+            .field("context", &self.context);
+        #[cfg(feature = "revocation")]
+        debug.field("crls", &self.crls.as_ref().map_or(0, |crls| crls.len()));
+        debug.finish()
+    }
+}
+
+impl AttestationVerifier {
+    /// Create a new verifier instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record every verification decision to `sink`
+    ///
+    /// If a successful verification cannot be recorded, it is reported as
+    /// `VerificationError::Audit` instead. Failed verifications keep their
+    /// original error.
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    /// Evaluate `hook` after the built-in checks of every verification
+    ///
+    /// A denial is returned as `PolicyError::Denied` with the hook's messages.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let verifier = AttestationVerifier::new().with_policy_hook(Arc::new(|input: &PolicyInput| {
+    ///     Ok(match input.extensions.runner_environment.as_deref() {
+    ///         Some("github-hosted") => PolicyDecision::allow(),
+    ///         _ => PolicyDecision::deny(vec!["build must run on a GitHub-hosted runner".into()]),
+    ///     })
+    /// }));
+    /// ```
+    pub fn with_policy_hook(mut self, hook: Arc<dyn PolicyHook>) -> Self {
+        self.policy_hook = Some(hook);
+        self
+    }
+
+    /// Run `step` right after the built-in step `after`
+    ///
+    /// Steps attached after the same built-in step run in the order they were
+    /// added; all custom steps run before the policy hook. See `verifier::step`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let verifier = AttestationVerifier::new().with_step(
+    ///     BuiltinStep::CertificateChain,
+    ///     Arc::new(|context: &StepContext| {
+    ///         let leaf = ParsedCertificate::from_der(&context.certificate_chain.unwrap().leaf)?;
+    ///         check_org_extension(&leaf)
+    ///     }),
+    /// );
+    /// ```
+    pub fn with_step(mut self, after: BuiltinStep, step: Arc<dyn VerificationStep>) -> Self {
+        self.steps.push((after, step));
+        self
+    }
+
+    fn run_steps(&self, context: &StepContext<'_>) -> Result<(), VerificationError> {
+        self.context.check(context.after.name())?;
+        self.steps
+            .iter()
+            .filter(|(after, _)| *after == context.after)
+            .try_for_each(|(_, step)| step.check(context))
+    }
+
+    /// Parse DSSE payloads of type `payload_type` with `parser`
+    ///
+    /// The parser maps the payload to the `Statement` whose subject digest and
+    /// predicate type are checked; everything else is verified as for in-toto
+    /// payloads. Payload types without a parser are parsed as in-toto statements.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let verifier = AttestationVerifier::new().with_payload_parser(
+    ///     "application/vnd.example.compliance+json",
+    ///     Arc::new(|payload: &[u8]| compliance_to_statement(payload)),
+    /// );
+    /// ```
+    pub fn with_payload_parser(mut self, payload_type: impl Into<String>, parser: Arc<dyn PayloadParser>) -> Self {
+        self.payload_parsers.register(payload_type, parser);
+        self
+    }
+
+    /// Keep the verified Fulcio and TSA certificate chains in the result
+    ///
+    /// The chains are then available from `VerificationResult::certificate_chain`
+    /// and `tsa_certificate_chain`, e.g. to archive the signing certificate.
+    /// They are not part of the zkVM public output.
+    pub fn with_certificate_chains(mut self) -> Self {
+        self.retain_certificate_chains = true;
+        self
+    }
+
+    /// Parse trust bundle certificates once and reuse them from `pool`
+    ///
+    /// The pool can be shared by several verifiers and threads; see
+    /// `verifier::pool`. Its `stats` show how often it saved a parse.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let pool = Arc::new(CertificatePool::default());
+    /// let verifier = AttestationVerifier::new().with_certificate_pool(pool.clone());
+    /// ```
+    pub fn with_certificate_pool(mut self, pool: Arc<CertificatePool>) -> Self {
+        self.certificate_pool = Some(pool);
+        self
+    }
+
+    /// Accept RFC 3161 timestamps from TSAs whose chain ends in a root of `store`
+    ///
+    /// For TSAs on the public web PKI rather than Sigstore's: the certificates
+    /// embedded in the token (or the leaf and intermediates of the TSA chain
+    /// passed to `verify_*` if it embeds none) are completed with the store
+    /// root that issued them, and a token not chaining to the store is
+    /// rejected. The TSA chain argument is then optional. See
+    /// `verifier::root_store`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let verifier = AttestationVerifier::new().with_tsa_root_store(Arc::new(RootStore::webpki()));
+    /// let result = verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, None)?;
+    /// ```
+    pub fn with_tsa_root_store(mut self, store: Arc<RootStore>) -> Self {
+        self.tsa_root_store = Some(store);
+        self
+    }
+
+    /// Verify RFC 3161 timestamps offline with stored TSA chains and responses
+    ///
+    /// A bundle without a timestamp is verified with the stored response
+    /// covering its signature, and a timestamp without a TSA chain argument
+    /// with the stored chain that signed it. See `verifier::timestamp::TsaMaterial`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let material = TsaMaterial::from_dir(Path::new("tsa"))?;
+    /// let verifier = AttestationVerifier::new().with_tsa_material(Arc::new(material));
+    /// ```
+    pub fn with_tsa_material(mut self, material: Arc<TsaMaterial>) -> Self {
+        self.tsa_material = Some(material);
+        self
+    }
+
+    /// Bound the size of bundles, their base64 fields, log entries, inclusion
+    /// proofs and certificate chains
+    ///
+    /// Inputs over a limit fail with `VerificationError::LimitExceeded` before
+    /// they are decoded. Without this, `InputLimits::default()` applies.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let limits = InputLimits { max_bundle_size: 256 * 1024, ..InputLimits::default() };
+    /// let verifier = AttestationVerifier::new().with_input_limits(limits);
+    /// ```
+    pub fn with_input_limits(mut self, limits: InputLimits) -> Self {
+        self.input_limits = limits;
+        self
+    }
+
+    /// Stop verifying once the context's deadline passes or its token is cancelled
+    ///
+    /// The context is checked between built-in steps and while verifying
+    /// chain links and inclusion proofs (see `verifier::context`); an
+    /// interrupted verification fails with `VerificationError::Interrupted`.
+    /// A deadline is absolute, so attach a fresh context per request.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let token = CancellationToken::new();
+    /// let context = VerificationContext::new()
+    ///     .with_timeout(Duration::from_secs(5))
+    ///     .with_cancellation(token.clone());
+    /// let result = verifier.clone().with_context(context).verify_bundle_bytes(&json, options, &trust, tsa);
+    /// ```
+    pub fn with_context(mut self, context: VerificationContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Reject Fulcio and TSA chains with a certificate revoked by a CRL in `crls`
+    ///
+    /// Certificates whose issuer has no CRL in the set are not checked (see
+    /// `verifier::revocation`).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut crls = CrlSet::new();
+    /// crls.add_pem(&std::fs::read_to_string("tsa-intermediate.crl")?)?;
+    /// let verifier = AttestationVerifier::new().with_crls(crls);
+    /// ```
+    #[cfg(feature = "revocation")]
+    pub fn with_crls(mut self, crls: crate::verifier::revocation::CrlSet) -> Self {
+        self.crls = Some(Arc::new(crls));
+        self
+    }
+
+    #[cfg(feature = "revocation")]
+    fn check_revocation(&self, chain: &CertificateChain) -> Result<(), error::CertificateError> {
+        match &self.crls {
+            Some(crls) => crate::verifier::revocation::check_chain_revocation(chain, crls),
+            None => Ok(()),
+        }
+    }
+
+    /// Verify a sigstore bundle from a file path
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle_path` - Path to the sigstore bundle JSON file
+    /// * `options` - Verification options
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    ///
+    /// # Returns
+    ///
+    /// On success, returns `VerificationResult` containing:
+    /// - Certificate chain hashes (leaf, intermediates, root)
+    /// - Signing time
+    /// - Subject digest
+    /// - OIDC identity (if present)
+    pub fn verify_bundle(
+        &self,
+        bundle_path: &Path,
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        // Only read the raw bytes when they are needed for the audit record
+        let bundle_json = self
+            .audit_sink
+            .as_ref()
+            .and_then(|_| std::fs::read(bundle_path).ok());
+        self.audited(bundle_json.as_deref(), options, trust_bundle, |options| {
+            let bundle = parse_bundle_from_path_with_limits(bundle_path, &self.input_limits)?;
+            self.verify_bundle_internal(&bundle, options, trust_bundle, tsa_cert_chain)
+        })
+    }
+
+    /// Verify a sigstore bundle from raw JSON bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle_json` - Raw JSON bytes of the sigstore bundle
+    /// * `options` - Verification options
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    ///
+    /// # Returns
+    ///
+    /// On success, returns `VerificationResult` containing:
+    /// - Certificate chain hashes (leaf, intermediates, root)
+    /// - Signing time
+    /// - Subject digest
+    /// - OIDC identity (if present)
+    pub fn verify_bundle_bytes(
+        &self,
+        bundle_json: &[u8],
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        self.audited(Some(bundle_json), options, trust_bundle, |options| {
+            let bundle = parse_bundle_from_bytes_with_limits(bundle_json, &self.input_limits)?;
+            self.verify_bundle_internal(&bundle, options, trust_bundle, tsa_cert_chain)
+        })
+    }
+
+    /// Verify newline-delimited bundles as they are read
+    ///
+    /// Each non-empty line holds a bundle or a `gh attestation download`
+    /// wrapper and is verified as by [`verify_bundle_bytes`](Self::verify_bundle_bytes).
+    /// Only one line is held in memory at a time, so arbitrarily large
+    /// attestation dumps can be checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of JSONL, e.g. a `BufReader` over a file
+    /// * `options` - Verification options, applied to every bundle
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding the line number and result of every bundle
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let reader = BufReader::new(File::open("attestations.jsonl")?);
+    /// for verified in verifier.verify_bundle_stream(reader, options, &trust_bundle, Some(&tsa_chain)) {
+    ///     if let Err(e) = verified.result {
+    ///         eprintln!("line {}: {}", verified.line, e);
+    ///     }
+    /// }
+    /// ```
+    pub fn verify_bundle_stream<'a, R: BufRead>(
+        &'a self,
+        reader: R,
+        options: VerificationOptions,
+        trust_bundle: &'a CertificateChain,
+        tsa_cert_chain: Option<&'a CertificateChain>,
+    ) -> BundleStream<'a, R> {
+        BundleStream::new(self, reader, options, trust_bundle, tsa_cert_chain)
+    }
+
+    /// Verify a bundle and, recursively, the attested builds it came from
+    ///
+    /// The builder and dependencies its SLSA provenance references (see
+    /// [`crate::build_tree::build_references`]) are looked up in `source`, verified,
+    /// and followed in turn down to `max_depth` levels below the root. A
+    /// dependency's bundle must attest to the digest the provenance recorded
+    /// for it. Referenced bundles are verified with the trust settings of
+    /// `options` but without its subject, identity, predicate type and source
+    /// expectations, which only describe the root.
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle_json` - Raw JSON bytes of the root bundle
+    /// * `options` - Verification options of the root bundle
+    /// * `source` - Supplies the bundles of referenced artifacts
+    /// * `max_depth` - Levels followed below the root, e.g. `build_tree::DEFAULT_MAX_BUILD_DEPTH`
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    ///
+    /// # Errors
+    ///
+    /// Returns the root bundle's error if it fails verification. Failures
+    /// below the root are recorded in the tree instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut source = BundleMap::new();
+    /// source.insert_uri(builder_id, builder_bundle);
+    /// let tree = verifier.verify_build_tree(&bundle_json, options, &source, DEFAULT_MAX_BUILD_DEPTH, &trust_bundle, None)?;
+    /// assert!(tree.all_verified());
+    /// ```
+    pub fn verify_build_tree(
+        &self,
+        bundle_json: &[u8],
+        options: VerificationOptions,
+        source: &dyn BundleSource,
+        max_depth: usize,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<BuildNode, VerificationError> {
+        BuildTreeWalker {
+            verifier: self,
+            source,
+            options: &options,
+            max_depth,
+            trust_bundle,
+            tsa_cert_chain,
+        }
+        .verify_root(bundle_json)
+    }
+
+    /// Verify a bundle and that it attests to an artifact
+    ///
+    /// The artifact is hashed as it is read, with the strongest of SHA-512,
+    /// SHA-384 and SHA-256 that the bundle's first subject carries, and the
+    /// digest is enforced as `options.expected_digest` (overriding any set).
+    /// This saves computing and encoding the expected digest by hand.
+    ///
+    /// # Arguments
+    ///
+    /// * `artifact` - Reader over the artifact's contents
+    /// * `bundle_json` - Raw JSON bytes of the sigstore bundle
+    /// * `options` - Verification options
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    ///
+    /// # Errors
+    ///
+    /// Returns `SubjectDigestMismatch` if the bundle does not attest to the
+    /// artifact, `Io` if reading it fails, and any error of
+    /// [`verify_bundle_bytes`](Self::verify_bundle_bytes).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let artifact = File::open("release.tar.gz")?;
+    /// let result = verifier.verify_artifact(artifact, &bundle_json, options, &trust_bundle, Some(&tsa_chain))?;
+    /// ```
+    pub fn verify_artifact<R: Read>(
+        &self,
+        artifact: R,
+        bundle_json: &[u8],
+        mut options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        let bundle = parse_bundle_from_bytes_with_limits(bundle_json, &self.input_limits)?;
+        // Payloads that are not in-toto statements fall back to SHA-256
+        let statement = parse_dsse_payload(&bundle.dsse_envelope).ok();
+        let algorithm = [DigestAlgorithm::Sha512, DigestAlgorithm::Sha384, DigestAlgorithm::Sha256]
+            .into_iter()
+            .find(|algorithm| {
+                statement
+                    .as_ref()
+                    .is_some_and(|statement| statement.get_subject_digest(algorithm.name()).is_some())
+            })
+            .unwrap_or(DigestAlgorithm::Sha256);
+
+        options.expected_digest = Some(hash_reader(algorithm, artifact)?);
+        self.verify_bundle_bytes(bundle_json, options, trust_bundle, tsa_cert_chain)
+    }
+
+    /// Verify a bundle and that it attests to a local file
+    ///
+    /// See [`verify_artifact`](Self::verify_artifact).
+    pub fn verify_artifact_file(
+        &self,
+        artifact_path: &Path,
+        bundle_json: &[u8],
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        let artifact = std::fs::File::open(artifact_path)?;
+        self.verify_artifact(artifact, bundle_json, options, trust_bundle, tsa_cert_chain)
+    }
+
+    /// Verify a cosign "simple signing" signature of a container image
+    ///
+    /// The payload's `docker-manifest-digest` must equal `expected_manifest_digest`
+    /// (overriding `options.expected_digest`). The signing certificate, Rekor
+    /// entry or RFC 3161 timestamp and identity are then checked exactly as for
+    /// a bundle, with the payload viewed as a statement by
+    /// [`SimpleSigningPayload::to_statement`](types::simple_signing::SimpleSigningPayload::to_statement).
+    /// Audit records carry the SHA-256 of the payload as bundle digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature` - Signature layer, e.g. from `parse_cosign_signature_layer`
+    /// * `expected_manifest_digest` - Digest of the image manifest being admitted
+    /// * `options` - Verification options
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let signature = parse_cosign_signature_layer(layer_blob, &layer_annotations)?;
+    /// let result = verifier.verify_simple_signing(
+    ///     &signature,
+    ///     &"sha256:<manifest digest>".parse()?,
+    ///     VerificationOptions::default(),
+    ///     &trust_bundle,
+    ///     None,
+    /// )?;
+    /// ```
+    pub fn verify_simple_signing(
+        &self,
+        signature: &SimpleSignature,
+        expected_manifest_digest: &Digest,
+        mut options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        options.expected_digest = Some(expected_manifest_digest.clone());
+        self.audited(Some(&signature.payload), options, trust_bundle, |options| {
+            let material = signature.to_verification_material()?;
+            let fields = BundleBytes {
+                payload: &signature.payload,
+                signature: &signature.signature,
+                certificate: &signature.certificate,
+                rfc3161_timestamp: signature.rfc3161_timestamp.as_deref(),
+            };
+            self.verify_decoded(
+                &material,
+                SignedContent::SimpleSigning,
+                &fields,
+                options,
+                trust_bundle,
+                tsa_cert_chain,
+            )
+        })
+    }
+
+    /// Verify a DSSE envelope signed by a given certificate, without a bundle
+    ///
+    /// For toolchains that hand over an envelope and its signing certificate
+    /// rather than a Sigstore bundle. The envelope is checked exactly as the
+    /// envelope of a bundle whose only timestamp is `rfc3161_timestamp`: Fulcio
+    /// certificates are valid for minutes, so without a timestamp taken when
+    /// the envelope was signed verification fails with `NoTimestamp`. Audit
+    /// records carry the SHA-256 of the envelope's JSON as bundle digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `envelope` - DSSE envelope; its first signature is verified
+    /// * `leaf_cert_der` - DER-encoded signing certificate
+    /// * `rfc3161_timestamp` - DER-encoded RFC 3161 timestamp token over the signature
+    /// * `options` - Verification options
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - TSA certificate chain for `rfc3161_timestamp`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let envelope: DsseEnvelope = serde_json::from_slice(&envelope_json)?;
+    /// let result = verifier.verify_dsse_envelope(
+    ///     &envelope,
+    ///     &leaf_cert_der,
+    ///     Some(&timestamp_token),
+    ///     VerificationOptions::default(),
+    ///     &trust_bundle,
+    ///     Some(&tsa_chain),
+    /// )?;
+    /// ```
+    pub fn verify_dsse_envelope(
+        &self,
+        envelope: &DsseEnvelope,
+        leaf_cert_der: &[u8],
+        rfc3161_timestamp: Option<&[u8]>,
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        let envelope_json = serde_json::to_vec(envelope).ok();
+        self.audited(envelope_json.as_deref(), options, trust_bundle, |options| {
+            let payload = decode_base64(&envelope.payload)?;
+            let signature = envelope
+                .signatures
+                .first()
+                .ok_or_else(|| VerificationError::InvalidBundleFormat("Envelope has no signatures".to_string()))?;
+            let signature = decode_base64(&signature.sig)?;
+            let material = VerificationMaterial {
+                timestamp_verification_data: rfc3161_timestamp.map(|token| TimestampVerificationData {
+                    rfc3161_timestamps: Some(vec![Rfc3161Timestamp {
+                        signed_timestamp: BASE64.encode(token),
+                    }]),
+                }),
+                certificate: Certificate {
+                    raw_bytes: BASE64.encode(leaf_cert_der),
+                },
+                x509_certificate_chain: None,
+                tlog_entries: None,
+            };
+            let fields = BundleBytes {
+                payload: &payload,
+                signature: &signature,
+                certificate: leaf_cert_der,
+                rfc3161_timestamp,
+            };
+            self.verify_decoded(
+                &material,
+                SignedContent::Dsse {
+                    payload_type: &envelope.payload_type,
+                },
+                &fields,
+                options,
+                trust_bundle,
+                tsa_cert_chain,
+            )
+        })
+    }
+
+    /// Run `verify` and report the decision to the audit sink, if one is set
+    fn audited<F>(
+        &self,
+        bundle_json: Option<&[u8]>,
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        verify: F,
+    ) -> Result<VerificationResult, VerificationError>
+    where
+        F: FnOnce(VerificationOptions) -> Result<VerificationResult, VerificationError>,
+    {
+        let Some(sink) = &self.audit_sink else {
+            return verify(options);
+        };
+
+        let recorded_options = options.clone();
+        let result = verify(options);
+        let event = AuditEvent::new(bundle_json, recorded_options, trust_bundle, &result);
+        match (result, sink.record(&event)) {
+            (Ok(_), Err(e)) => Err(e.into()),
+            (result, _) => result,
+        }
+    }
+
+    fn verify_bundle_internal(
+        &self,
+        bundle: &types::bundle::SigstoreBundle,
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        // Decode each base64 field once; later steps work on the raw bytes
+        let texts = FieldTexts::from_bundle(bundle);
+        let dsse_payload = decode_base64(texts.dsse_payload)?;
+        let dsse_signature = decode_base64(texts.dsse_signature)?;
+        let certificate = decode_base64(texts.certificate)
+            .map_err(|e| error::CertificateError::ParseError(e.to_string()))?;
+        let rfc3161_timestamp = texts
+            .rfc3161_timestamp
+            .map(|text| {
+                BASE64.decode(text).map_err(|e| {
+                    error::TimestampError::Rfc3161Parse(format!(
+                        "Failed to decode timestamp base64: {}",
+                        e
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let fields = BundleBytes {
+            payload: &dsse_payload,
+            signature: &dsse_signature,
+            certificate: &certificate,
+            rfc3161_timestamp: rfc3161_timestamp.as_deref(),
+        };
+        self.verify_decoded(&bundle.verification_material, dsse_content(bundle), &fields, options, trust_bundle, tsa_cert_chain)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn verify_decoded(
+        &self,
+        material: &types::bundle::VerificationMaterial,
+        content: SignedContent<'_>,
+        fields: &BundleBytes<'_>,
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<VerificationResult, VerificationError> {
+        self.context.check("start")?;
+
+        // Step 1: Parse and verify subject digest
+        let statement = match content {
+            SignedContent::Dsse { payload_type } => {
+                if options.strict_statement && self.payload_parsers.get(payload_type).is_none() {
+                    validate_statement_json(fields.payload)?;
+                }
+                let statement = self.payload_parsers.parse(payload_type, fields.payload)?;
+                if payload_type == redaction::REDACTABLE_PAYLOAD_TYPE
+                    && !options.allow_redacted_predicate
+                    && redaction::contains_redactions(&statement.predicate)
+                {
+                    return Err(error::RedactionError::RedactedPredicate.into());
+                }
+                statement
+            }
+            SignedContent::SimpleSigning => parse_simple_signing_payload(fields.payload)?.to_statement()?,
+        };
+        let (subject_digest, subject_name) = verify_subject(
+            &statement,
+            options.expected_digest.as_ref(),
+            options.expected_subject_name.as_deref(),
+        )?;
+        if let Some(ref allowed) = options.allowed_predicate_types {
+            if !allowed
+                .iter()
+                .any(|pattern| wildcard_match(pattern, &statement.predicate_type))
+            {
+                return Err(error::PolicyError::PredicateTypeNotAllowed(statement.predicate_type).into());
+            }
+        }
+        if let Some(ref source_policy) = options.source_policy {
+            source_policy.evaluate(&statement)?;
+        }
+        let resolved_dependencies = resolved_dependencies(&statement);
+        if let Some(ref dependency_policy) = options.dependency_policy {
+            dependency_policy.evaluate(&resolved_dependencies)?;
+        }
+        let mut step_context = StepContext {
+            after: BuiltinStep::Subject,
+            options: &options,
+            statement: &statement,
+            subject_digest: &subject_digest,
+            signing_time: None,
+            certificate_chain: None,
+            leaf_certificate: None,
+            timestamp_proof: None,
+            identity: None,
+        };
+        self.run_steps(&step_context)?;
+
+        // Step 2: Validate exactly one timestamp mechanism and get signing time
+        let tlog_entries = material.tlog_entries.as_deref().unwrap_or_default();
+        let has_rfc3161 = material
+            .timestamp_verification_data
+            .as_ref()
+            .and_then(|td| td.rfc3161_timestamps.as_ref())
+            .map(|ts| !ts.is_empty())
+            .unwrap_or(false);
+
+        let has_tlog = !tlog_entries.is_empty();
+
+        // Rekor v2 entries carry no integrated time: the bundle is timestamped
+        // by RFC 3161 and the log only proves inclusion
+        let rekor_v2 = has_tlog && tlog_entries.iter().all(|entry| entry.is_rekor_v2());
+        let has_tlog = has_tlog && !rekor_v2;
+
+        // A bundle without a timestamp of its own can use a stored response over its signature
+        let rfc3161_timestamp = match &self.tsa_material {
+            Some(tsa_material) if !has_rfc3161 && !has_tlog => tsa_material.token_for(fields.signature),
+            _ => fields.rfc3161_timestamp,
+        };
+        let has_rfc3161 = has_rfc3161 || rfc3161_timestamp.is_some();
+
+        if let Some(required) = options.required_timestamp {
+            let actual = match (has_rfc3161, has_tlog) {
+                (true, false) => Some(TimestampRequirement::Rfc3161),
+                (false, true) => Some(TimestampRequirement::Rekor),
+                _ => None,
+            };
+            if let Some(actual) = actual.filter(|actual| *actual != required) {
+                return Err(error::PolicyError::TimestampMechanism {
+                    expected: required.name(),
+                    actual: actual.name(),
+                }
+                .into());
+            }
+        }
+
+        // Validate we have a TSA chain for RFC 3161 path
+        if has_rfc3161 && tsa_cert_chain.is_none() && self.tsa_root_store.is_none() && self.tsa_material.is_none() {
+            return Err(error::TimestampError::MissingTSAChain.into());
+        }
+
+        // Get signing time from appropriate mechanism
+        let mut parsed_timestamp = None;
+        let signing_time = match (has_rfc3161, has_tlog) {
+            (true, true) => return Err(error::TimestampError::BothTimestampMechanisms.into()),
+            (false, false) => return Err(error::TimestampError::NoTimestamp.into()),
+            (true, false) => {
+                // Safe: has_rfc3161 means the bundle has a first timestamp or one was replayed
+                let parsed = parse_rfc3161_timestamp(rfc3161_timestamp.unwrap())?;
+                let gen_time = parsed.tst_info.gen_time;
+                parsed_timestamp = Some(parsed);
+                gen_time
+            }
+            (false, true) => get_integrated_time(&tlog_entries[0])?,
+        };
+
+        // Step 2b: When verifying as of a given time, the bundle must already exist then
+        if let Some(verify_at) = options.verify_at {
+            if signing_time.timestamp() > verify_at.timestamp() {
+                return Err(error::TimestampError::SignedAfterVerificationTime {
+                    signing_time: signing_time.to_rfc3339(),
+                    verify_at: verify_at.to_rfc3339(),
+                }
+                .into());
+            }
+        }
+
+        // Step 3: Verify certificate chain and get hashes
+        let cert_chain_span = CycleSpan::enter(profiling::CERT_CHAIN);
+        self.input_limits.check_chain_length(trust_bundle.intermediates.len() + 2)?;
+        let chain_policy = options.chain_policy.clone().unwrap_or_default();
+        let (chain, certificate_hashes) = verify_chain(
+            fields.certificate.to_vec(),
+            trust_bundle,
+            options.signature_algorithms(),
+            &chain_policy,
+            self.certificate_pool.as_deref(),
+            &self.context,
+        )
+        .map_err(certificate_error)?;
+        #[cfg(feature = "revocation")]
+        self.check_revocation(&chain)?;
+        drop(cert_chain_span);
+
+        // Step 3a: Check the chain against the pinned roots (if specified)
+        if let Some(ref pinned) = options.pinned_root_digests {
+            chain_policy.check_pinned(&certificate_hashes, pinned)?;
+        }
+
+        // Step 3b: Verify signing time is within certificate validity period,
+        // widened to genTime ± accuracy if the TSA policy asks for it
+        let leaf_cert = ParsedCertificate::from_der(&chain.leaf)
+            .map_err(|e| VerificationError::InvalidBundleFormat(e.to_string()))?;
+        let honor_accuracy = options.tsa_policy.as_ref().is_some_and(|policy| policy.honor_accuracy);
+        match parsed_timestamp.as_ref() {
+            Some(parsed) if honor_accuracy => {
+                let (earliest, latest) = parsed.tst_info.time_window();
+                verify_signing_time_in_validity(&earliest, &leaf_cert)?;
+                verify_signing_time_in_validity(&latest, &leaf_cert)?;
+            }
+            _ => verify_signing_time_in_validity(&signing_time, &leaf_cert)?,
+        }
+        let leaf_metadata = leaf_cert.metadata()?;
+        step_context.after = BuiltinStep::CertificateChain;
+        step_context.signing_time = Some(signing_time);
+        step_context.certificate_chain = Some(&chain);
+        step_context.leaf_certificate = Some(&leaf_metadata);
+        self.run_steps(&step_context)?;
+
+        // Step 4: Verify the DSSE or simple signing signature (made with the
+        // leaf curve's standard digest)
+        let dsse_span = CycleSpan::enter(profiling::DSSE_SIGNATURE);
+        let dsse_algorithm = leaf_cert.public_key()?.default_algorithm();
+        if !options.signature_algorithms().contains(&dsse_algorithm) {
+            return Err(error::SignatureError::DisallowedAlgorithm(dsse_algorithm.name().to_string()).into());
+        }
+        match content {
+            SignedContent::Dsse { payload_type } => {
+                verify_dsse_signature_bytes(payload_type, fields.payload, fields.signature, &chain)?
+            }
+            SignedContent::SimpleSigning => verify_message_signature(fields.payload, fields.signature, &chain)?,
+        }
+        drop(dsse_span);
+        step_context.after = BuiltinStep::Signature;
+        self.run_steps(&step_context)?;
+
+        // The content a transparency log entry has to commit to
+        let envelope = SignedEnvelope {
+            payload_type: match content {
+                SignedContent::Dsse { payload_type } => Some(payload_type),
+                SignedContent::SimpleSigning => None,
+            },
+            payload: fields.payload,
+            signature: fields.signature,
+            certificate: fields.certificate,
+        };
+
+        // Step 5: Verify timestamp mechanism (RFC 3161 OR Rekor, mutually exclusive)
+        // and collect timestamp proof data
+        let mut verified_tsa_chain = None;
+        let timestamp_proof = if let Some(parsed_timestamp) = parsed_timestamp {
+            // RFC 3161 path: verify TSA chain and timestamp signature
+            let _span = CycleSpan::enter(profiling::RFC3161);
+
+            // Without a chain from the caller, use the stored chain that signed the token
+            let tsa_cert_chain = tsa_cert_chain.or_else(|| self.tsa_material.as_ref()?.chain_for(&parsed_timestamp));
+
+            // Try to extract embedded certificates (takes precedence)
+            let tsa_chain = if let Some(store) = &self.tsa_root_store {
+                // Only the root store is trusted: complete the certificates to one of its roots
+                let certificates = match (parsed_timestamp.certificates.clone(), tsa_cert_chain) {
+                    (Some(embedded_certs), _) if !embedded_certs.is_empty() => embedded_certs,
+                    (_, Some(chain)) => std::iter::once(chain.leaf.clone())
+                        .chain(chain.intermediates.iter().cloned())
+                        .collect(),
+                    _ => return Err(error::TimestampError::MissingTSAChain.into()),
+                };
+                store
+                    .complete_chain(certificates, options.signature_algorithms())
+                    .map_err(certificate_error)?
+            } else if let Some(embedded_certs) = parsed_timestamp.certificates.clone() {
+                if !embedded_certs.is_empty() {
+                    // Embedded certs found - use them
+                    certs_to_chain(embedded_certs).map_err(|e| {
+                        error::TimestampError::InvalidTSACertificate(format!(
+                            "Failed to parse embedded TSA certs: {}",
+                            e
+                        ))
+                    })?
+                } else {
+                    // Empty embedded cert list - fall back to user-provided
+                    tsa_cert_chain.cloned().ok_or(error::TimestampError::MissingTSAChain)?
+                }
+            } else {
+                // No embedded certs field at all - use user-provided
+                tsa_cert_chain.cloned().ok_or(error::TimestampError::MissingTSAChain)?
+            };
+
+            // Verify TSA certificate chain and EKU
+            self.input_limits.check_chain_length(tsa_chain.intermediates.len() + 2)?;
+            verify_tsa_chain(
+                &tsa_chain,
+                options.signature_algorithms(),
+                self.tsa_root_store.is_none(),
+                self.certificate_pool.as_deref(),
+                &self.context,
+            )
+            .map_err(certificate_error)?;
+            #[cfg(feature = "revocation")]
+            self.check_revocation(&tsa_chain)?;
+
+            // Verify RFC 3161 timestamp token (message imprint + PKCS7 signature)
+            verify_rfc3161_token(&parsed_timestamp, fields.signature, &tsa_chain)?;
+
+            // Check the token's policy OID, accuracy, ordering and nonce
+            if let Some(ref tsa_policy) = options.tsa_policy {
+                tsa_policy.evaluate(&parsed_timestamp.tst_info)?;
+            }
+
+            // Compute TSA chain hashes for the timestamp proof
+            use crate::crypto::hash::sha256;
+            let tsa_leaf_hash = sha256(&tsa_chain.leaf);
+            let tsa_intermediate_hashes: Vec<[u8; 32]> = tsa_chain
+                .intermediates
+                .iter()
+                .map(|der| sha256(der))
+                .collect();
+            let tsa_root_hash = sha256(&tsa_chain.root);
+            if self.retain_certificate_chains {
+                verified_tsa_chain = Some(tsa_chain);
+            }
+
+            // Extract message imprint algorithm
+            let message_imprint_algorithm = match parsed_timestamp.tst_info.message_imprint.hash_algorithm {
+                parser::rfc3161::HashAlgorithm::Sha256 => DigestAlgorithm::Sha256,
+                parser::rfc3161::HashAlgorithm::Sha384 => DigestAlgorithm::Sha384,
+            };
+
+            TimestampProof::Rfc3161 {
+                tsa_chain_hashes: CertificateChainHashes {
+                    leaf: tsa_leaf_hash,
+                    intermediates: tsa_intermediate_hashes,
+                    root: tsa_root_hash,
+                },
+                message_imprint_algorithm,
+                message_imprint: parsed_timestamp.tst_info.message_imprint.hashed_message,
+            }
+        } else {
+            // Rekor path: verify transparency log
+            let merkle_span = CycleSpan::enter(profiling::MERKLE);
+            verify_tlog_entries_in(tlog_entries, &self.input_limits, &self.context)?;
+            drop(merkle_span);
+
+            // The proven entry must commit to the envelope this bundle carries
+            verify_entry_body(&tlog_entries[0], &envelope)?;
+
+            // The integrated time is only the log's word if its SET verifies
+            if let Some(keys) = options.transparency_log_keys.as_deref() {
+                verify_signed_entry_timestamp(&tlog_entries[0], keys)?;
+            }
+
+            // Check the entries' logs against the allowed logs (if specified)
+            if let Some(ref allowed) = options.allowed_log_ids {
+                verify_entry_log_ids(tlog_entries, allowed)?;
+            }
+
+            // Extract log_id, log_index (tree), and entry_index from tlog entry
+            let tlog_entry = &tlog_entries[0];
+
+            let log_id: [u8; 32] = if let Some(ref log_id_struct) = tlog_entry.log_id {
+                let log_id_bytes = parser::bundle::decode_base64(&log_id_struct.key_id)
+                    .map_err(|e| VerificationError::InvalidBundleFormat(format!(
+                        "Failed to decode log_id: {}", e
+                    )))?;
+                log_id_bytes.try_into().map_err(|_| {
+                    VerificationError::InvalidBundleFormat("log_id is not 32 bytes".to_string())
+                })?
+            } else {
+                [0u8; 32]
+            };
+
+            // Tree leaf index (for Merkle proof verification against checkpoint)
+            let log_index: u64 = tlog_entry
+                .inclusion_proof
+                .as_ref()
+                .and_then(|proof| proof.log_index.parse().ok())
+                .unwrap_or(0);
+
+            // Entry index (for API queries to fetch the full entry)
+            let entry_index: u64 = tlog_entry
+                .log_index
+                .as_ref()
+                .and_then(|idx| idx.parse().ok())
+                .unwrap_or(0);
+
+            TimestampProof::Rekor { log_id, log_index, entry_index }
+        };
+
+        // Step 5a: Verify Rekor v2 inclusion, whose timestamp came from RFC 3161
+        if rekor_v2 {
+            let _span = CycleSpan::enter(profiling::MERKLE);
+            verify_tlog_entries_in(tlog_entries, &self.input_limits, &self.context)?;
+            // Without a promise, the checkpoint signature is what ties the
+            // proven tree to the log
+            let keys = options
+                .transparency_log_keys
+                .as_deref()
+                .ok_or(TransparencyError::MissingLogKeys)?;
+            verify_entry_checkpoint(&tlog_entries[0], keys)?;
+            verify_entry_body(&tlog_entries[0], &envelope)?;
+            if let Some(ref allowed) = options.allowed_log_ids {
+                verify_entry_log_ids(tlog_entries, allowed)?;
+            }
+        }
+        step_context.after = BuiltinStep::Timestamp;
+        step_context.timestamp_proof = Some(&timestamp_proof);
+        self.run_steps(&step_context)?;
+
+        // Step 6: Extract OIDC identity from certificate extensions
+        let oidc_identity = extract_oidc_identity(&leaf_cert).ok();
+
+        // Step 7: Verify OIDC identity against expected values (if specified)
+        if let Some(ref identity) = oidc_identity {
+            if let Some(ref expected_issuer) = options.expected_issuer {
+                let actual_issuer = identity
+                    .issuer
+                    .as_ref()
+                    .ok_or(error::PolicyError::MissingField("issuer"))?;
+                if !constant_time_eq(actual_issuer.as_bytes(), expected_issuer.as_bytes()) {
+                    return Err(error::PolicyError::Mismatch {
+                        field: "issuer",
+                        expected: expected_issuer.clone(),
+                        actual: actual_issuer.clone(),
+                    }
+                    .into());
+                }
+            }
+
+            if let Some(ref expected_subject) = options.expected_subject {
+                let actual_subject = identity
+                    .subject
+                    .as_ref()
+                    .ok_or(error::PolicyError::MissingField("subject"))?;
+                if !constant_time_eq(actual_subject.as_bytes(), expected_subject.as_bytes()) {
+                    return Err(error::PolicyError::Mismatch {
+                        field: "subject",
+                        expected: expected_subject.clone(),
+                        actual: actual_subject.clone(),
+                    }
+                    .into());
+                }
+            }
+        } else if options.expected_issuer.is_some()
+            || options.expected_subject.is_some()
+            || options.identity_policy.is_some()
+        {
+            return Err(error::PolicyError::MissingField("identity").into());
+        }
+
+        // Step 7b: Evaluate identity policy (if specified)
+        if let (Some(policy), Some(identity)) = (&options.identity_policy, &oidc_identity) {
+            policy.evaluate(identity)?;
+        }
+        step_context.after = BuiltinStep::Identity;
+        step_context.identity = oidc_identity.as_ref();
+        self.run_steps(&step_context)?;
+
+        // Step 8: Evaluate the custom policy hook (if attached)
+        if let Some(ref hook) = self.policy_hook {
+            let input = PolicyInput {
+                statement,
+                subject_digest: subject_digest.clone(),
+                signing_time,
+                identity: oidc_identity.clone(),
+                extensions: extract_fulcio_extensions(&leaf_cert)?,
+                tlog: tlog_entries.first().map(TlogMetadata::from_entry),
+            };
+            policy::hook::enforce(hook.as_ref(), &input)?;
+        }
+
+        Ok(VerificationResult {
+            certificate_hashes,
+            signing_time,
+            subject_digest,
+            subject_name: Some(subject_name),
+            oidc_identity,
+            timestamp_proof,
+            leaf_certificate: Some(leaf_metadata),
+            certificate_chain: self.retain_certificate_chains.then_some(chain),
+            tsa_certificate_chain: verified_tsa_chain,
+            resolved_dependencies,
+        })
+    }
+}
+
+/// Report an interrupted chain verification as such rather than as a certificate error
+fn certificate_error(error: error::CertificateError) -> VerificationError {
+    match error {
+        error::CertificateError::Interrupted(interrupted) => interrupted.into(),
+        error => error.into(),
+    }
+}
+
+fn dsse_content(bundle: &types::bundle::SigstoreBundle) -> SignedContent<'_> {
+    SignedContent::Dsse {
+        payload_type: &bundle.dsse_envelope.payload_type,
+    }
+}
+
+/// What the bundle's signature covers
+#[derive(Clone, Copy)]
+enum SignedContent<'a> {
+    /// A DSSE envelope holding an in-toto statement
+    Dsse { payload_type: &'a str },
+    /// A cosign simple signing payload, signed as is
+    SimpleSigning,
+}
+
+/// Base64 text of the bundle fields decoded by `verify_bundle_internal`
+struct FieldTexts<'a> {
+    dsse_payload: &'a str,
+    dsse_signature: &'a str,
+    certificate: &'a str,
+    rfc3161_timestamp: Option<&'a str>,
+}
+
+impl<'a> FieldTexts<'a> {
+    /// Select the fields of a validated bundle (which has at least one signature)
+    fn from_bundle(bundle: &'a types::bundle::SigstoreBundle) -> Self {
+        FieldTexts {
+            dsse_payload: &bundle.dsse_envelope.payload,
+            dsse_signature: &bundle.dsse_envelope.signatures[0].sig,
+            certificate: &bundle.verification_material.certificate.raw_bytes,
+            rfc3161_timestamp: bundle
+                .verification_material
+                .timestamp_verification_data
+                .as_ref()
+                .and_then(|td| td.rfc3161_timestamps.as_ref())
+                .and_then(|ts| ts.first())
+                .map(|ts| ts.signed_timestamp.as_str()),
+        }
+    }
+}
+
+/// Decoded base64 fields of a bundle, shared by all verification entry points
+struct BundleBytes<'a> {
+    /// DSSE or simple signing payload
+    payload: &'a [u8],
+    /// Signature over the payload (its DSSE PAE for envelopes)
+    signature: &'a [u8],
+    certificate: &'a [u8],
+    rfc3161_timestamp: Option<&'a [u8]>,
+}
@@ -0,0 +1,409 @@
+//! Audit log of verification decisions
+//!
+//! An [`AttestationVerifier`](crate::AttestationVerifier) configured with an
+//! [`AuditSink`] reports every decision it makes: digests of the bundle and trust
+//! material, the options used, and either the result or the error code. The
+//! [`JsonlAuditSink`] appends these as JSON lines and can hash-chain them, so
+//! that removing or editing a record breaks [`verify_chain`]. Other stores
+//! (e.g. a database table) only need to implement `AuditSink`.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::crypto::hash::{constant_time_eq, sha256};
+use crate::crypto::jcs::canonicalize;
+use crate::encoding::hex_encode;
+use crate::error::{AuditError, VerificationError};
+use crate::types::certificate::CertificateChain;
+use crate::types::digest::Digest;
+use crate::types::result::{VerificationOptions, VerificationResult};
+use crate::types::time::Timestamp;
+
+/// Receives verification decisions
+///
+/// Sinks are shared between threads by the verifier, so implementations handle
+/// their own locking.
+pub trait AuditSink: Send + Sync {
+    /// Record one decision
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the decision could not be persisted. The verifier then
+    /// reports a successful verification as failed rather than leave it unrecorded.
+    fn record(&self, event: &AuditEvent) -> Result<(), AuditError>;
+}
+
+/// A single verification decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// When the decision was made
+    pub timestamp: Timestamp,
+
+    /// SHA-256 of the bundle JSON, if the bundle could be read
+    pub bundle_digest: Option<Digest>,
+
+    /// SHA-256 of the trusted root certificate (DER)
+    pub trust_root_digest: Digest,
+
+    /// Options the bundle was verified against
+    pub options: VerificationOptions,
+
+    pub outcome: AuditOutcome,
+}
+
+/// Whether the bundle was accepted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Verified {
+        result: Box<VerificationResult>,
+    },
+    Rejected {
+        /// [`VerificationError::code`] of the failure
+        code: String,
+        message: String,
+    },
+}
+
+impl AuditEvent {
+    /// Describe a decision made now
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle_json` - Raw bundle bytes, if available
+    /// * `options` - Verification options
+    /// * `trust_bundle` - Certificate chain the bundle was verified against
+    /// * `result` - Outcome of the verification
+    pub fn new(
+        bundle_json: Option<&[u8]>,
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        result: &Result<VerificationResult, VerificationError>,
+    ) -> Self {
+        let outcome = match result {
+            Ok(result) => AuditOutcome::Verified {
+                result: Box::new(result.clone()),
+            },
+            Err(error) => AuditOutcome::Rejected {
+                code: error.code().to_string(),
+                message: error.to_string(),
+            },
+        };
+
+        AuditEvent {
+            timestamp: Timestamp::now(),
+            bundle_digest: bundle_json.map(|bytes| Digest::sha256(sha256(bytes))),
+            trust_root_digest: Digest::sha256(sha256(&trust_bundle.root)),
+            options,
+            outcome,
+        }
+    }
+
+    pub fn is_verified(&self) -> bool {
+        matches!(self.outcome, AuditOutcome::Verified { .. })
+    }
+}
+
+/// A line of a JSONL audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Position in the log, starting at 0
+    pub sequence: u64,
+
+    /// `record_hash` of the previous record (chained logs only)
+    pub prev_hash: Option<String>,
+
+    /// Hex SHA-256 of the RFC 8785 canonical form of this record without
+    /// `record_hash` (chained logs only)
+    pub record_hash: Option<String>,
+
+    pub event: AuditEvent,
+}
+
+/// Position reached after reading an audit log
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainHead {
+    /// Number of records in the log
+    pub records: u64,
+
+    /// `record_hash` of the last record
+    pub last_hash: Option<String>,
+}
+
+/// Appends decisions to a writer as JSON lines
+pub struct JsonlAuditSink<W> {
+    state: Mutex<JsonlState<W>>,
+}
+
+struct JsonlState<W> {
+    writer: W,
+    chained: bool,
+    head: ChainHead,
+}
+
+impl<W: Write + Send> JsonlAuditSink<W> {
+    /// Write unchained records to `writer`
+    pub fn new(writer: W) -> Self {
+        Self::with_head(writer, false, ChainHead::default())
+    }
+
+    /// Write hash-chained records to `writer`
+    pub fn hash_chained(writer: W) -> Self {
+        Self::with_head(writer, true, ChainHead::default())
+    }
+
+    fn with_head(writer: W, chained: bool, head: ChainHead) -> Self {
+        JsonlAuditSink {
+            state: Mutex::new(JsonlState {
+                writer,
+                chained,
+                head,
+            }),
+        }
+    }
+
+    /// Consume the sink and return the writer
+    pub fn into_inner(self) -> W {
+        let state = self.state.into_inner().unwrap_or_else(|e| e.into_inner());
+        state.writer
+    }
+}
+
+impl JsonlAuditSink<File> {
+    /// Open an audit log file for appending, creating it if needed
+    ///
+    /// Existing records are checked with [`verify_chain`] and new records
+    /// continue the sequence (and the hash chain, if `chained`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or the existing log fails verification.
+    pub fn open(path: &Path, chained: bool) -> Result<Self, AuditError> {
+        let head = match File::open(path) {
+            Ok(file) => verify_chain(BufReader::new(file))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ChainHead::default(),
+            Err(e) => return Err(e.into()),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::with_head(file, chained, head))
+    }
+}
+
+impl<W: Write + Send> AuditSink for JsonlAuditSink<W> {
+    fn record(&self, event: &AuditEvent) -> Result<(), AuditError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut record = AuditRecord {
+            sequence: state.head.records,
+            prev_hash: None,
+            record_hash: None,
+            event: event.clone(),
+        };
+        if state.chained {
+            record.prev_hash = state.head.last_hash.clone();
+            record.record_hash = Some(record_hash(&to_value(&record)?)?);
+        }
+
+        let mut line =
+            serde_json::to_vec(&record).map_err(|e| AuditError::Serialization(e.to_string()))?;
+        line.push(b'\n');
+        state.writer.write_all(&line)?;
+        state.writer.flush()?;
+
+        state.head.records += 1;
+        state.head.last_hash = record.record_hash;
+        Ok(())
+    }
+}
+
+/// Check the sequence numbers and hash chain of a JSONL audit log
+///
+/// Records without a `record_hash` are only checked for sequence. A record that
+/// has one must hash correctly and its `prev_hash` must match the previous
+/// record's `record_hash`.
+///
+/// # Returns
+///
+/// The number of records and the hash of the last one, used to continue the log.
+///
+/// # Errors
+///
+/// Returns `MalformedRecord` for unparsable lines or out-of-order sequence numbers
+/// and `ChainBroken` for a record whose hashes do not match.
+pub fn verify_chain<R: BufRead>(reader: R) -> Result<ChainHead, AuditError> {
+    let mut head = ChainHead::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let malformed = |reason: String| AuditError::MalformedRecord {
+            line: index + 1,
+            reason,
+        };
+
+        let value: Value = serde_json::from_str(&line).map_err(|e| malformed(e.to_string()))?;
+        let record: AuditRecord =
+            serde_json::from_value(value.clone()).map_err(|e| malformed(e.to_string()))?;
+        if record.sequence != head.records {
+            return Err(malformed(format!(
+                "expected sequence {}, got {}",
+                head.records, record.sequence
+            )));
+        }
+
+        if let Some(claimed) = &record.record_hash {
+            let prev_matches = match (&record.prev_hash, &head.last_hash) {
+                (Some(prev), Some(last)) => constant_time_eq(prev.as_bytes(), last.as_bytes()),
+                (None, None) => true,
+                _ => false,
+            };
+            let mut unhashed = value;
+            if let Value::Object(map) = &mut unhashed {
+                map.insert("record_hash".to_string(), Value::Null);
+            }
+            let actual = record_hash(&unhashed)?;
+            if !prev_matches || !constant_time_eq(claimed.as_bytes(), actual.as_bytes()) {
+                return Err(AuditError::ChainBroken {
+                    sequence: record.sequence,
+                });
+            }
+        } else if record.prev_hash.is_some() {
+            return Err(AuditError::ChainBroken {
+                sequence: record.sequence,
+            });
+        }
+
+        head.records += 1;
+        head.last_hash = record.record_hash;
+    }
+
+    Ok(head)
+}
+
+fn to_value(record: &AuditRecord) -> Result<Value, AuditError> {
+    serde_json::to_value(record).map_err(|e| AuditError::Serialization(e.to_string()))
+}
+
+/// Hash of a record whose `record_hash` is null
+fn record_hash(value: &Value) -> Result<String, AuditError> {
+    let canonical = canonicalize(value).map_err(|e| AuditError::Serialization(e.to_string()))?;
+    Ok(hex_encode(&sha256(canonical.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SignatureError;
+
+    fn rejected_event() -> AuditEvent {
+        let chain = CertificateChain {
+            leaf: vec![],
+            intermediates: vec![],
+            root: b"root".to_vec(),
+        };
+        let result = Err(SignatureError::InvalidSignature.into());
+        AuditEvent::new(Some(b"{}"), VerificationOptions::default(), &chain, &result)
+    }
+
+    fn write_log(chained: bool, count: usize) -> String {
+        let sink = if chained {
+            JsonlAuditSink::hash_chained(Vec::new())
+        } else {
+            JsonlAuditSink::new(Vec::new())
+        };
+        for _ in 0..count {
+            sink.record(&rejected_event()).unwrap();
+        }
+        String::from_utf8(sink.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_rejected_event() {
+        let event = rejected_event();
+        assert!(!event.is_verified());
+        assert!(matches!(
+            &event.outcome,
+            AuditOutcome::Rejected { code, .. } if code == "signature"
+        ));
+        assert_eq!(
+            event.bundle_digest.unwrap().to_string(),
+            "sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        );
+    }
+
+    #[test]
+    fn test_hash_chain_round_trip() {
+        let log = write_log(true, 3);
+        let head = verify_chain(log.as_bytes()).unwrap();
+        assert_eq!(head.records, 3);
+
+        let last: AuditRecord = serde_json::from_str(log.lines().last().unwrap()).unwrap();
+        assert_eq!(last.sequence, 2);
+        assert_eq!(head.last_hash, last.record_hash);
+        assert!(last.prev_hash.is_some());
+    }
+
+    #[test]
+    fn test_unchained_log() {
+        let log = write_log(false, 2);
+        let head = verify_chain(log.as_bytes()).unwrap();
+        assert_eq!(
+            head,
+            ChainHead {
+                records: 2,
+                last_hash: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_tampering_breaks_chain() {
+        let log = write_log(true, 3);
+        let lines: Vec<&str> = log.lines().collect();
+
+        // Edited record
+        let edited = log.replacen("\"signature\"", "\"policy\"", 1);
+        assert!(matches!(
+            verify_chain(edited.as_bytes()),
+            Err(AuditError::ChainBroken { sequence: 0 })
+        ));
+
+        // Removed record
+        let removed = [lines[0], lines[2]].join("\n");
+        assert!(matches!(
+            verify_chain(removed.as_bytes()),
+            Err(AuditError::MalformedRecord { line: 2, .. })
+        ));
+
+        // Reordered records
+        let reordered = [lines[1], lines[0]].join("\n");
+        assert!(verify_chain(reordered.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_open_continues_chain() {
+        let path = std::env::temp_dir().join(format!("audit-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        JsonlAuditSink::open(&path, true)
+            .unwrap()
+            .record(&rejected_event())
+            .unwrap();
+        JsonlAuditSink::open(&path, true)
+            .unwrap()
+            .record(&rejected_event())
+            .unwrap();
+
+        let head = verify_chain(BufReader::new(File::open(&path).unwrap())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(head.records, 2);
+        assert!(head.last_hash.is_some());
+    }
+}
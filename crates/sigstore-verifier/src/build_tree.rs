@@ -0,0 +1,426 @@
+//! Recursive verification of the builds behind an artifact
+//!
+//! A SLSA provenance names the builder that produced the artifact
+//! (`builder.id`) and the inputs it consumed (`resolvedDependencies`, or
+//! `materials` in v0.2). Those are artifacts in their own right and may be
+//! attested too. [`AttestationVerifier::verify_build_tree`] verifies a bundle,
+//! asks a [`BundleSource`] for the bundles of every artifact its provenance
+//! references, verifies those in turn and returns the resulting tree.
+//!
+//! Recursion stops at a configurable depth, and a reference back to an
+//! artifact already on the path from the root is recorded as a cycle rather
+//! than followed.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::parser::bundle::{parse_bundle_from_bytes_with_limits, parse_dsse_payload};
+use crate::policy::attestations::SLSA_PROVENANCE_V1;
+use crate::policy::dependency::resolved_dependencies;
+use crate::report::ReportError;
+use crate::slsa::SLSA_PROVENANCE_V0_2;
+use crate::types::certificate::CertificateChain;
+use crate::types::digest::{Digest, DigestAlgorithm};
+use crate::types::dsse::Statement;
+use crate::types::result::{VerificationOptions, VerificationResult};
+use crate::AttestationVerifier;
+
+/// Depth of the tree below the root followed by default
+pub const DEFAULT_MAX_BUILD_DEPTH: usize = 4;
+
+/// How a provenance refers to another artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// `builder.id`
+    Builder,
+    /// An entry of `resolvedDependencies` (v1) or `materials` (v0.2)
+    Dependency,
+}
+
+/// An artifact referenced by a provenance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildReference {
+    pub kind: ReferenceKind,
+    pub uri: String,
+    /// Digests of the artifact in a supported algorithm; empty for builders,
+    /// which SLSA names by URI only
+    pub digests: Vec<Digest>,
+}
+
+/// Extract the builder and dependencies a SLSA provenance references
+///
+/// Statements of other predicate types reference nothing. Dependencies
+/// without a `uri` are skipped.
+pub fn build_references(statement: &Statement) -> Vec<BuildReference> {
+    let builder = match statement.predicate_type.as_str() {
+        SLSA_PROVENANCE_V1 => &statement.predicate["runDetails"]["builder"]["id"],
+        SLSA_PROVENANCE_V0_2 => &statement.predicate["builder"]["id"],
+        _ => return Vec::new(),
+    };
+
+    let builder = builder.as_str().filter(|id| !id.is_empty()).map(|id| BuildReference {
+        kind: ReferenceKind::Builder,
+        uri: id.to_string(),
+        digests: Vec::new(),
+    });
+    let dependencies = resolved_dependencies(statement).into_iter().filter_map(|dependency| {
+        Some(BuildReference {
+            kind: ReferenceKind::Dependency,
+            uri: dependency.uri?,
+            digests: dependency.digests,
+        })
+    });
+    builder.into_iter().chain(dependencies).collect()
+}
+
+/// Supplies the bundles attesting to a referenced artifact
+///
+/// Implemented for closures, and by [`BundleMap`] for bundles at hand. A
+/// source that fetches bundles over the network is in `sigstore-fetcher`.
+pub trait BundleSource: Send + Sync {
+    /// Bundles that may attest to `reference`, in order of preference
+    ///
+    /// An empty list marks the artifact as unattested.
+    ///
+    /// # Errors
+    ///
+    /// Any error marks the reference as failed; the rest of the tree is still
+    /// verified.
+    fn bundles(&self, reference: &BuildReference) -> Result<Vec<Vec<u8>>, VerificationError>;
+}
+
+impl<F> BundleSource for F
+where
+    F: Fn(&BuildReference) -> Result<Vec<Vec<u8>>, VerificationError> + Send + Sync,
+{
+    fn bundles(&self, reference: &BuildReference) -> Result<Vec<Vec<u8>>, VerificationError> {
+        self(reference)
+    }
+}
+
+/// Bundles held in memory, looked up by artifact digest or URI
+#[derive(Debug, Clone, Default)]
+pub struct BundleMap {
+    by_digest: HashMap<Digest, Vec<Vec<u8>>>,
+    by_uri: HashMap<String, Vec<Vec<u8>>>,
+}
+
+impl BundleMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a bundle attesting to the artifact with `digest`
+    pub fn insert_digest(&mut self, digest: Digest, bundle_json: Vec<u8>) -> &mut Self {
+        self.by_digest.entry(digest).or_default().push(bundle_json);
+        self
+    }
+
+    /// Add a bundle for the artifact at `uri`, e.g. a builder ID
+    pub fn insert_uri(&mut self, uri: impl Into<String>, bundle_json: Vec<u8>) -> &mut Self {
+        self.by_uri.entry(uri.into()).or_default().push(bundle_json);
+        self
+    }
+}
+
+impl BundleSource for BundleMap {
+    fn bundles(&self, reference: &BuildReference) -> Result<Vec<Vec<u8>>, VerificationError> {
+        let bundles = reference
+            .digests
+            .iter()
+            .filter_map(|digest| self.by_digest.get(digest))
+            .chain(self.by_uri.get(&reference.uri))
+            .flatten()
+            .cloned()
+            .collect();
+        Ok(bundles)
+    }
+}
+
+/// Outcome of following one reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildStatus {
+    /// A bundle for the artifact verified
+    Verified(Box<VerificationResult>),
+    /// The source has no bundle for the artifact
+    Unattested,
+    /// The source failed, or none of its bundles verified (the last error is kept)
+    Failed(ReportError),
+    /// The artifact is already on the path from the root
+    Cycle,
+    /// Not followed: the tree reached its maximum depth
+    DepthLimit,
+}
+
+/// A node of the tree returned by [`AttestationVerifier::verify_build_tree`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildNode {
+    /// Reference that led here; `None` for the root
+    pub reference: Option<BuildReference>,
+    pub status: BuildStatus,
+    /// References of the artifact's own provenance, if it verified
+    pub children: Vec<BuildNode>,
+}
+
+impl BuildNode {
+    /// Result of this node, if it verified
+    pub fn result(&self) -> Option<&VerificationResult> {
+        match &self.status {
+            BuildStatus::Verified(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Whether every artifact in the tree was attested and verified
+    ///
+    /// Cycles count as verified, since they lead back to a verified node;
+    /// references cut off by the depth limit do not.
+    pub fn all_verified(&self) -> bool {
+        matches!(self.status, BuildStatus::Verified(_) | BuildStatus::Cycle)
+            && self.children.iter().all(BuildNode::all_verified)
+    }
+
+    /// Number of nodes in the tree, the root included
+    pub fn node_count(&self) -> usize {
+        1 + self.children.iter().map(BuildNode::node_count).sum::<usize>()
+    }
+}
+
+/// Artifacts on the path from the root to the node being verified
+#[derive(Default)]
+struct BuildPath {
+    digests: Vec<Digest>,
+    uris: Vec<String>,
+}
+
+impl BuildPath {
+    fn contains(&self, reference: &BuildReference) -> bool {
+        self.uris.contains(&reference.uri) || reference.digests.iter().any(|digest| self.digests.contains(digest))
+    }
+}
+
+pub(crate) struct BuildTreeWalker<'a> {
+    pub verifier: &'a AttestationVerifier,
+    pub source: &'a dyn BundleSource,
+    pub options: &'a VerificationOptions,
+    pub max_depth: usize,
+    pub trust_bundle: &'a CertificateChain,
+    pub tsa_cert_chain: Option<&'a CertificateChain>,
+}
+
+impl BuildTreeWalker<'_> {
+    pub fn verify_root(&self, bundle_json: &[u8]) -> Result<BuildNode, VerificationError> {
+        let result = self.verifier.verify_bundle_bytes(
+            bundle_json,
+            self.options.clone(),
+            self.trust_bundle,
+            self.tsa_cert_chain,
+        )?;
+        let mut path = BuildPath::default();
+        let children = self.follow(bundle_json, &result, &mut path, 0);
+        Ok(BuildNode {
+            reference: None,
+            status: BuildStatus::Verified(Box::new(result)),
+            children,
+        })
+    }
+
+    /// Verify the references of a verified bundle at `depth`
+    fn follow(
+        &self,
+        bundle_json: &[u8],
+        result: &VerificationResult,
+        path: &mut BuildPath,
+        depth: usize,
+    ) -> Vec<BuildNode> {
+        // The bundle already verified, so it parses
+        let references = parse_bundle_from_bytes_with_limits(bundle_json, &self.verifier.input_limits)
+            .ok()
+            .and_then(|bundle| parse_dsse_payload(&bundle.dsse_envelope).ok())
+            .map(|statement| build_references(&statement))
+            .unwrap_or_default();
+
+        path.digests.push(result.subject_digest.clone());
+        let children = references
+            .into_iter()
+            .map(|reference| self.verify_reference(reference, path, depth + 1))
+            .collect();
+        path.digests.pop();
+        children
+    }
+
+    fn verify_reference(&self, reference: BuildReference, path: &mut BuildPath, depth: usize) -> BuildNode {
+        let leaf = |reference, status| BuildNode {
+            reference: Some(reference),
+            status,
+            children: Vec::new(),
+        };
+        if path.contains(&reference) {
+            return leaf(reference, BuildStatus::Cycle);
+        }
+        if depth > self.max_depth {
+            return leaf(reference, BuildStatus::DepthLimit);
+        }
+        let bundles = match self.source.bundles(&reference) {
+            Ok(bundles) if bundles.is_empty() => return leaf(reference, BuildStatus::Unattested),
+            Ok(bundles) => bundles,
+            Err(e) => return leaf(reference, BuildStatus::Failed(report_error(&e))),
+        };
+
+        let options = dependency_options(self.options, preferred_digest(&reference.digests));
+        let mut last_error = None;
+        for bundle_json in &bundles {
+            match self
+                .verifier
+                .verify_bundle_bytes(bundle_json, options.clone(), self.trust_bundle, self.tsa_cert_chain)
+            {
+                Ok(result) => {
+                    // A builder has no digest to check up front, so its cycle shows once verified
+                    if path.digests.contains(&result.subject_digest) {
+                        return leaf(reference, BuildStatus::Cycle);
+                    }
+                    path.uris.push(reference.uri.clone());
+                    let children = self.follow(bundle_json, &result, path, depth);
+                    path.uris.pop();
+                    return BuildNode {
+                        reference: Some(reference),
+                        status: BuildStatus::Verified(Box::new(result)),
+                        children,
+                    };
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let error = last_error.map(|e| report_error(&e)).unwrap_or_else(|| ReportError {
+            code: "unattested".to_string(),
+            message: "no bundle verified".to_string(),
+        });
+        leaf(reference, BuildStatus::Failed(error))
+    }
+}
+
+/// Digest a referenced artifact's bundle must attest to, in the order subject digests are read
+fn preferred_digest(digests: &[Digest]) -> Option<Digest> {
+    [
+        DigestAlgorithm::Sha256,
+        DigestAlgorithm::Sha384,
+        DigestAlgorithm::Sha512,
+        DigestAlgorithm::GitCommit,
+    ]
+    .into_iter()
+    .find_map(|algorithm| digests.iter().find(|digest| digest.algorithm == algorithm))
+    .cloned()
+}
+
+/// Options for the bundle of a referenced artifact
+///
+/// Trust settings carry over from the root; expectations about the root's
+/// subject, signer and predicate do not, since another workflow built and
+/// signed the referenced artifact.
+fn dependency_options(options: &VerificationOptions, expected_digest: Option<Digest>) -> VerificationOptions {
+    VerificationOptions {
+        expected_digest,
+        expected_subject_name: None,
+        expected_issuer: None,
+        expected_subject: None,
+        identity_policy: None,
+        allowed_predicate_types: None,
+        source_policy: None,
+        ..options.clone()
+    }
+}
+
+fn report_error(error: &VerificationError) -> ReportError {
+    ReportError {
+        code: error.code().to_string(),
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    const SHA256: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+    fn provenance(predicate_type: &str, predicate: Value) -> Statement {
+        serde_json::from_value(json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "subject": [{ "name": "artifact", "digest": { "sha256": SHA256 } }],
+            "predicateType": predicate_type,
+            "predicate": predicate,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_references_v1() {
+        let statement = provenance(
+            SLSA_PROVENANCE_V1,
+            json!({
+                "buildDefinition": {
+                    "resolvedDependencies": [
+                        { "uri": "git+https://github.com/org/app@refs/heads/main", "digest": { "gitCommit": "0123456789abcdef0123456789abcdef01234567" } },
+                        { "uri": "pkg:npm/left-pad@1.3.0", "digest": { "sha256": SHA256, "md5": "00" } },
+                        { "name": "no uri", "digest": { "sha256": SHA256 } },
+                    ]
+                },
+                "runDetails": { "builder": { "id": "https://github.com/org/builder/.github/workflows/build.yml@refs/tags/v1" } },
+            }),
+        );
+        let references = build_references(&statement);
+        assert_eq!(references.len(), 3);
+        assert_eq!(references[0].kind, ReferenceKind::Builder);
+        assert!(references[0].digests.is_empty());
+        assert_eq!(references[1].kind, ReferenceKind::Dependency);
+        assert_eq!(references[1].digests[0].algorithm, DigestAlgorithm::GitCommit);
+        // md5 is not supported, and the entry without a uri is skipped
+        assert_eq!(references[2].digests, vec![Digest::from_hex(DigestAlgorithm::Sha256, SHA256).unwrap()]);
+    }
+
+    #[test]
+    fn test_build_references_v0_2() {
+        let statement = provenance(
+            SLSA_PROVENANCE_V0_2,
+            json!({
+                "builder": { "id": "https://cloudbuild.googleapis.com/GoogleHostedWorker" },
+                "materials": [{ "uri": "git+https://github.com/org/app", "digest": { "sha1": "0123456789abcdef0123456789abcdef01234567" } }],
+            }),
+        );
+        let references = build_references(&statement);
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[1].uri, "git+https://github.com/org/app");
+        // SHA-1 is only supported as gitCommit
+        assert!(references[1].digests.is_empty());
+
+        let sbom = provenance("https://spdx.dev/Document/v2.3", json!({}));
+        assert!(build_references(&sbom).is_empty());
+    }
+
+    #[test]
+    fn test_bundle_map_lookup() {
+        let digest = Digest::from_hex(DigestAlgorithm::Sha256, SHA256).unwrap();
+        let mut map = BundleMap::new();
+        map.insert_digest(digest.clone(), b"by digest".to_vec())
+            .insert_uri("https://builder", b"by uri".to_vec());
+
+        let reference = BuildReference {
+            kind: ReferenceKind::Dependency,
+            uri: "https://builder".to_string(),
+            digests: vec![digest],
+        };
+        assert_eq!(map.bundles(&reference).unwrap(), vec![b"by digest".to_vec(), b"by uri".to_vec()]);
+
+        let unknown = BuildReference {
+            kind: ReferenceKind::Builder,
+            uri: "https://other".to_string(),
+            digests: Vec::new(),
+        };
+        assert!(map.bundles(&unknown).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+//! CBOR and COSE encodings of verification results
+//!
+//! Policy agents on constrained devices would rather not carry a JSON parser
+//! to read a [`VerificationResult`]. [`VerificationResult::to_cbor`] encodes
+//! the result as CBOR (RFC 8949) with the same field names and values as its
+//! JSON form, and [`VerificationResult::to_cose_sign1`] wraps that encoding
+//! as the payload of a COSE_Sign1 message (RFC 9052) signed with an ES256 key,
+//! so an agent that trusts the verifier's key can act on a result it received
+//! over any channel.
+//!
+//! The COSE message is tagged (CBOR tag 18), carries the algorithm in its
+//! protected header and the optional key ID in its unprotected header, and
+//! uses no external AAD.
+
+use std::collections::BTreeMap;
+
+use p256::ecdsa::signature::{Signer, Verifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use serde_cbor::Value;
+
+use crate::error::CborError;
+use crate::types::result::VerificationResult;
+
+/// CBOR tag of a COSE_Sign1 message
+pub const COSE_SIGN1_TAG: u64 = 18;
+
+/// COSE algorithm identifier of ECDSA P-256 with SHA-256
+pub const COSE_ALG_ES256: i128 = -7;
+
+const HEADER_ALG: i128 = 1;
+const HEADER_KID: i128 = 4;
+
+/// Initial byte of a CBOR tag 18, the only tag the messages here carry
+const COSE_SIGN1_TAG_BYTE: u8 = 0xc0 | COSE_SIGN1_TAG as u8;
+
+impl VerificationResult {
+    /// Encode the result as CBOR, with the field names of its JSON form
+    pub fn to_cbor(&self) -> Result<Vec<u8>, CborError> {
+        serde_cbor::to_vec(self).map_err(|e| CborError::Encode(e.to_string()))
+    }
+
+    /// Decode a result encoded by [`to_cbor`](Self::to_cbor)
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, CborError> {
+        serde_cbor::from_slice(bytes).map_err(|e| CborError::Decode(e.to_string()))
+    }
+
+    /// Encode the result as a tagged COSE_Sign1 message signed with `key`
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - ES256 key of the verifier
+    /// * `kid` - Key ID to put in the unprotected header, for agents trusting several keys
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let result = verifier.verify_bundle(bundle_path, options, &trust_bundle, tsa_chain.as_ref())?;
+    /// let message = result.to_cose_sign1(&signing_key, Some(b"verifier-1"))?;
+    /// // On the device
+    /// let result = VerificationResult::from_cose_sign1(&message, &verifying_key)?;
+    /// ```
+    pub fn to_cose_sign1(&self, key: &SigningKey, kid: Option<&[u8]>) -> Result<Vec<u8>, CborError> {
+        let protected = protected_header()?;
+        let payload = self.to_cbor()?;
+        let signature: Signature = key.sign(&sig_structure(&protected, &payload)?);
+
+        let mut unprotected = BTreeMap::new();
+        if let Some(kid) = kid {
+            unprotected.insert(Value::Integer(HEADER_KID), Value::Bytes(kid.to_vec()));
+        }
+        let message = Value::Array(vec![
+            Value::Bytes(protected),
+            Value::Map(unprotected),
+            Value::Bytes(payload),
+            Value::Bytes(signature.to_bytes().to_vec()),
+        ]);
+
+        let mut bytes = vec![COSE_SIGN1_TAG_BYTE];
+        bytes.extend(serde_cbor::to_vec(&message).map_err(|e| CborError::Encode(e.to_string()))?);
+        Ok(bytes)
+    }
+
+    /// Check a COSE_Sign1 message against `key` and decode the result it carries
+    ///
+    /// Accepts the message with or without tag 18.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CoseSignature` if the message is not signed by `key` with
+    /// ES256, and `Decode` if it is not a COSE_Sign1 message carrying a result.
+    pub fn from_cose_sign1(message: &[u8], key: &VerifyingKey) -> Result<Self, CborError> {
+        let message = message.strip_prefix(&[COSE_SIGN1_TAG_BYTE]).unwrap_or(message);
+        let Value::Array(items) = serde_cbor::from_slice(message).map_err(|e| CborError::Decode(e.to_string()))?
+        else {
+            return Err(CborError::Decode("COSE_Sign1 message is not an array".to_string()));
+        };
+        let [Value::Bytes(protected), Value::Map(_), Value::Bytes(payload), Value::Bytes(signature)] = items.as_slice()
+        else {
+            return Err(CborError::Decode("Malformed COSE_Sign1 message".to_string()));
+        };
+
+        let header: BTreeMap<Value, Value> =
+            serde_cbor::from_slice(protected).map_err(|e| CborError::Decode(e.to_string()))?;
+        if header.get(&Value::Integer(HEADER_ALG)) != Some(&Value::Integer(COSE_ALG_ES256)) {
+            return Err(CborError::CoseSignature("algorithm is not ES256".to_string()));
+        }
+        let signature = Signature::from_slice(signature).map_err(|e| CborError::CoseSignature(e.to_string()))?;
+        key.verify(&sig_structure(protected, payload)?, &signature)
+            .map_err(|e| CborError::CoseSignature(e.to_string()))?;
+
+        Self::from_cbor(payload)
+    }
+}
+
+fn protected_header() -> Result<Vec<u8>, CborError> {
+    let header = BTreeMap::from([(Value::Integer(HEADER_ALG), Value::Integer(COSE_ALG_ES256))]);
+    serde_cbor::to_vec(&Value::Map(header)).map_err(|e| CborError::Encode(e.to_string()))
+}
+
+/// The `Sig_structure` a COSE_Sign1 signature is computed over
+fn sig_structure(protected: &[u8], payload: &[u8]) -> Result<Vec<u8>, CborError> {
+    let structure = Value::Array(vec![
+        Value::Text("Signature1".to_string()),
+        Value::Bytes(protected.to_vec()),
+        Value::Bytes(Vec::new()),
+        Value::Bytes(payload.to_vec()),
+    ]);
+    serde_cbor::to_vec(&structure).map_err(|e| CborError::Encode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::digest::Digest;
+    use crate::types::result::{CertificateChainHashes, TimestampProof};
+    use crate::types::time::Timestamp;
+
+    fn sample_result() -> VerificationResult {
+        VerificationResult {
+            certificate_hashes: CertificateChainHashes {
+                leaf: [1; 32],
+                intermediates: vec![[2; 32]],
+                root: [3; 32],
+            },
+            signing_time: Timestamp::from_unix(1700000000),
+            subject_digest: Digest::sha256([4; 32]),
+            oidc_identity: None,
+            timestamp_proof: TimestampProof::Rekor {
+                log_id: [5; 32],
+                log_index: 7,
+                entry_index: 8,
+            },
+            subject_name: None,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_cbor_roundtrip() {
+        let result = sample_result();
+        let cbor = result.to_cbor().unwrap();
+        assert!(cbor.len() < serde_json::to_vec(&result).unwrap().len());
+        let decoded = VerificationResult::from_cbor(&cbor).unwrap();
+        assert_eq!(decoded.as_slice(), result.as_slice());
+    }
+
+    #[test]
+    fn test_cose_sign1() {
+        let key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let message = sample_result().to_cose_sign1(&key, Some(b"verifier-1")).unwrap();
+        assert_eq!(message[0], 0xd2);
+
+        let decoded = VerificationResult::from_cose_sign1(&message, key.verifying_key()).unwrap();
+        assert_eq!(decoded.as_slice(), sample_result().as_slice());
+        // Untagged messages are accepted too
+        VerificationResult::from_cose_sign1(&message[1..], key.verifying_key()).unwrap();
+
+        let other = SigningKey::from_slice(&[8; 32]).unwrap();
+        assert!(matches!(
+            VerificationResult::from_cose_sign1(&message, other.verifying_key()),
+            Err(CborError::CoseSignature(_))
+        ));
+
+        let mut tampered = message.clone();
+        let last = tampered.len() - 70;
+        tampered[last] ^= 1;
+        assert!(VerificationResult::from_cose_sign1(&tampered, key.verifying_key()).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Signature algorithms a certificate may declare in its `signatureAlgorithm` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureAlgorithm {
+    /// ecdsa-with-SHA256 (1.2.840.10045.4.3.2)
+    EcdsaSha256,
+    /// ecdsa-with-SHA384 (1.2.840.10045.4.3.3)
+    EcdsaSha384,
+    /// ecdsa-with-SHA512 (1.2.840.10045.4.3.4)
+    EcdsaSha512,
+    /// sha256WithRSAEncryption (1.2.840.113549.1.1.11)
+    RsaSha256,
+    /// sha384WithRSAEncryption (1.2.840.113549.1.1.12)
+    RsaSha384,
+    /// sha512WithRSAEncryption (1.2.840.113549.1.1.13)
+    RsaSha512,
+}
+
+/// Algorithms accepted when `VerificationOptions::allowed_signature_algorithms` is `None`
+///
+/// Sigstore's CAs and TSAs only sign with ECDSA; the RSA algorithms, used by
+/// web PKI timestamp authorities, must be allowed explicitly.
+pub const DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS: &[SignatureAlgorithm] = &[
+    SignatureAlgorithm::EcdsaSha256,
+    SignatureAlgorithm::EcdsaSha384,
+    SignatureAlgorithm::EcdsaSha512,
+];
+
+/// Outcome of looking up a signature algorithm OID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgorithmLookup {
+    /// A supported algorithm
+    Supported(SignatureAlgorithm),
+    /// A known algorithm that must never be accepted (MD5 or SHA-1 based)
+    Weak(&'static str),
+    /// An algorithm this library does not implement
+    Unsupported,
+}
+
+impl SignatureAlgorithm {
+    /// Look up a signature algorithm by its dotted OID string
+    pub fn from_oid(oid: &str) -> AlgorithmLookup {
+        match oid {
+            "1.2.840.10045.4.3.2" => AlgorithmLookup::Supported(SignatureAlgorithm::EcdsaSha256),
+            "1.2.840.10045.4.3.3" => AlgorithmLookup::Supported(SignatureAlgorithm::EcdsaSha384),
+            "1.2.840.10045.4.3.4" => AlgorithmLookup::Supported(SignatureAlgorithm::EcdsaSha512),
+            "1.2.840.113549.1.1.11" => AlgorithmLookup::Supported(SignatureAlgorithm::RsaSha256),
+            "1.2.840.113549.1.1.12" => AlgorithmLookup::Supported(SignatureAlgorithm::RsaSha384),
+            "1.2.840.113549.1.1.13" => AlgorithmLookup::Supported(SignatureAlgorithm::RsaSha512),
+            "1.2.840.10045.4.1" => AlgorithmLookup::Weak("ecdsa-with-SHA1"),
+            "1.2.840.113549.1.1.4" => AlgorithmLookup::Weak("md5WithRSAEncryption"),
+            "1.2.840.113549.1.1.5" => AlgorithmLookup::Weak("sha1WithRSAEncryption"),
+            "1.2.840.10040.4.3" => AlgorithmLookup::Weak("dsa-with-sha1"),
+            _ => AlgorithmLookup::Unsupported,
+        }
+    }
+
+    /// Human-readable algorithm name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SignatureAlgorithm::EcdsaSha256 => "ecdsa-with-SHA256",
+            SignatureAlgorithm::EcdsaSha384 => "ecdsa-with-SHA384",
+            SignatureAlgorithm::EcdsaSha512 => "ecdsa-with-SHA512",
+            SignatureAlgorithm::RsaSha256 => "sha256WithRSAEncryption",
+            SignatureAlgorithm::RsaSha384 => "sha384WithRSAEncryption",
+            SignatureAlgorithm::RsaSha512 => "sha512WithRSAEncryption",
+        }
+    }
+
+    /// Hash `data` with the algorithm's digest
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            SignatureAlgorithm::EcdsaSha256 | SignatureAlgorithm::RsaSha256 => Sha256::digest(data).to_vec(),
+            SignatureAlgorithm::EcdsaSha384 | SignatureAlgorithm::RsaSha384 => Sha384::digest(data).to_vec(),
+            SignatureAlgorithm::EcdsaSha512 | SignatureAlgorithm::RsaSha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_oid() {
+        assert_eq!(
+            SignatureAlgorithm::from_oid("1.2.840.10045.4.3.3"),
+            AlgorithmLookup::Supported(SignatureAlgorithm::EcdsaSha384)
+        );
+        assert_eq!(
+            SignatureAlgorithm::from_oid("1.2.840.113549.1.1.5"),
+            AlgorithmLookup::Weak("sha1WithRSAEncryption")
+        );
+        assert_eq!(
+            SignatureAlgorithm::from_oid("1.2.840.113549.1.1.11"),
+            AlgorithmLookup::Supported(SignatureAlgorithm::RsaSha256)
+        );
+        assert!(!DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS.contains(&SignatureAlgorithm::RsaSha256));
+        assert_eq!(SignatureAlgorithm::from_oid("1.2.3.4"), AlgorithmLookup::Unsupported);
+    }
+
+    #[test]
+    fn test_digest_length() {
+        assert_eq!(SignatureAlgorithm::EcdsaSha256.digest(b"x").len(), 32);
+        assert_eq!(SignatureAlgorithm::EcdsaSha384.digest(b"x").len(), 48);
+        assert_eq!(SignatureAlgorithm::EcdsaSha512.digest(b"x").len(), 64);
+    }
+}
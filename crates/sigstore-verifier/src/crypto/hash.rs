@@ -1,4 +1,10 @@
-use sha2::{Digest, Sha256};
+use std::io::Read;
+
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use subtle::ConstantTimeEq;
+
+use crate::error::VerificationError;
+use crate::types::digest::{Digest as ArtifactDigest, DigestAlgorithm};
 
 pub fn sha256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Sha256::new();
@@ -6,12 +12,49 @@ pub fn sha256(data: &[u8]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
-pub fn hex_encode(bytes: &[u8]) -> String {
-    hex::encode(bytes)
+pub use crate::encoding::{hex_decode, hex_encode};
+
+/// Hash everything `reader` yields, a block at a time
+///
+/// # Errors
+///
+/// Returns `Io` if reading fails and `InvalidBundleFormat` for algorithms that
+/// cannot be computed over a byte stream (`gitCommit`, `Unknown`).
+pub fn hash_reader<R: Read>(algorithm: DigestAlgorithm, reader: R) -> Result<ArtifactDigest, VerificationError> {
+    let bytes = match algorithm {
+        DigestAlgorithm::Sha256 => hash_stream::<Sha256, R>(reader)?,
+        DigestAlgorithm::Sha384 => hash_stream::<Sha384, R>(reader)?,
+        DigestAlgorithm::Sha512 => hash_stream::<Sha512, R>(reader)?,
+        DigestAlgorithm::GitCommit | DigestAlgorithm::Unknown => {
+            return Err(VerificationError::InvalidBundleFormat(format!(
+                "Cannot hash an artifact with {}",
+                algorithm.name()
+            )))
+        }
+    };
+    Ok(ArtifactDigest { algorithm, bytes })
+}
+
+fn hash_stream<D: Digest, R: Read>(mut reader: R) -> std::io::Result<Vec<u8>> {
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(hasher.finalize().to_vec()),
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
 }
 
-pub fn hex_decode(s: &str) -> Result<Vec<u8>, hex::FromHexError> {
-    hex::decode(s)
+/// Compare two byte strings in constant time
+///
+/// The running time depends only on the lengths, not on where the inputs
+/// differ. Use this for digests, Merkle roots and identity claims that an
+/// attacker can probe byte by byte.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
 }
 
 #[cfg(test)]
@@ -26,6 +69,23 @@ mod tests {
         assert_eq!(hex_encode(&hash), expected);
     }
 
+    #[test]
+    fn test_hash_reader() {
+        let digest = hash_reader(DigestAlgorithm::Sha256, &b"hello world"[..]).unwrap();
+        assert_eq!(digest.to_hex(), hex_encode(&sha256(b"hello world")));
+        let digest = hash_reader(DigestAlgorithm::Sha512, &[0u8; 200_000][..]).unwrap();
+        assert_eq!(digest.bytes, Sha512::digest([0u8; 200_000]).to_vec());
+        assert!(hash_reader(DigestAlgorithm::GitCommit, &b""[..]).is_err());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"digest", b"digest"));
+        assert!(!constant_time_eq(b"digest", b"digesT"));
+        assert!(!constant_time_eq(b"digest", b"diges"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
     #[test]
     fn test_hex_roundtrip() {
         let original = b"test data";
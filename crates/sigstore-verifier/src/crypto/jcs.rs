@@ -0,0 +1,271 @@
+//! JSON Canonicalization Scheme (RFC 8785)
+//!
+//! Rekor signs its Signed Entry Timestamps over the canonical JSON form of the
+//! entry, so reconstructing those payloads needs a byte-exact canonicalization:
+//! object members sorted by UTF-16 code units, minimal string escaping, and
+//! numbers serialized like ECMAScript's `Number.prototype.toString`.
+
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JcsError {
+    #[error("Invalid JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error("Number cannot be represented in canonical JSON: {0}")]
+    InvalidNumber(String),
+}
+
+/// Canonicalize a JSON value
+///
+/// # Errors
+///
+/// Returns `InvalidNumber` for numbers that are not finite IEEE 754 doubles.
+pub fn canonicalize(value: &Value) -> Result<String, JcsError> {
+    let mut out = String::new();
+    write_value(&mut out, value)?;
+    Ok(out)
+}
+
+/// Parse JSON text and return its canonical form
+///
+/// Duplicate object keys are not rejected; as with `serde_json`, the last one wins.
+pub fn canonicalize_str(json: &str) -> Result<String, JcsError> {
+    let value: Value = serde_json::from_str(json)?;
+    canonicalize(&value)
+}
+
+/// Serialize any `Serialize` type to canonical JSON bytes
+pub fn to_canonical_vec<T: Serialize>(value: &T) -> Result<Vec<u8>, JcsError> {
+    let value = serde_json::to_value(value)?;
+    Ok(canonicalize(&value)?.into_bytes())
+}
+
+fn write_value(out: &mut String, value: &Value) -> Result<(), JcsError> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(true) => out.push_str("true"),
+        Value::Bool(false) => out.push_str("false"),
+        Value::Number(number) => write_number(out, number)?,
+        Value::String(string) => write_string(out, string),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => write_object(out, map)?,
+    }
+    Ok(())
+}
+
+fn write_object(out: &mut String, map: &Map<String, Value>) -> Result<(), JcsError> {
+    // RFC 8785 §3.2.3: sort by the UTF-16 code units of the keys
+    let mut entries: Vec<(Vec<u16>, &String, &Value)> = map
+        .iter()
+        .map(|(key, value)| (key.encode_utf16().collect(), key, value))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    out.push('{');
+    for (i, (_, key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_string(out, key);
+        out.push(':');
+        write_value(out, value)?;
+    }
+    out.push('}');
+    Ok(())
+}
+
+/// RFC 8785 §3.2.2.2: escape only `"`, `\` and control characters
+fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// RFC 8785 §3.2.2.3: serialize as an IEEE 754 double, formatted like ECMAScript
+fn write_number(out: &mut String, number: &Number) -> Result<(), JcsError> {
+    let value = number
+        .as_f64()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| JcsError::InvalidNumber(number.to_string()))?;
+
+    if value == 0.0 {
+        // Covers -0 as well
+        out.push('0');
+        return Ok(());
+    }
+    if value < 0.0 {
+        out.push('-');
+    }
+
+    // Rust's `{:e}` yields the shortest round-trip digits, e.g. "1.2345e-7"
+    let formatted = format!("{:e}", value.abs());
+    let (mantissa, exponent) = formatted
+        .split_once('e')
+        .ok_or_else(|| JcsError::InvalidNumber(formatted.clone()))?;
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent
+        .parse()
+        .map_err(|_| JcsError::InvalidNumber(formatted.clone()))?;
+
+    // ECMAScript: value = 0.d1d2...dk × 10^n
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', (n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', (-n) as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if n - 1 < 0 { '-' } else { '+' });
+        out.push_str(&(n - 1).abs().to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn number(v: f64) -> String {
+        canonicalize(&json!(v)).unwrap()
+    }
+
+    #[test]
+    fn test_key_ordering() {
+        let value = json!({"b": 1, "a": {"d": true, "c": null}, "A": []});
+        assert_eq!(
+            canonicalize(&value).unwrap(),
+            r#"{"A":[],"a":{"c":null,"d":true},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_key_ordering_uses_utf16_code_units() {
+        // RFC 8785 §3.2.3 example: U+1F600 (surrogates D83D DE00) sorts before U+FB33
+        let value = json!({
+            "\u{20ac}": "Euro Sign",
+            "\r": "Carriage Return",
+            "\u{fb33}": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\u{1f600}": "Emoji: Grinning Face",
+            "\u{0080}": "Control",
+            "\u{00f6}": "Latin Small Letter O With Diaeresis"
+        });
+        let canonical = canonicalize(&value).unwrap();
+        let order: Vec<&str> = [
+            "\\r",
+            "1",
+            "\u{0080}",
+            "\u{00f6}",
+            "\u{20ac}",
+            "\u{1f600}",
+            "\u{fb33}",
+        ]
+        .to_vec();
+        let mut last = 0;
+        for key in order {
+            let position = canonical.find(&format!("\"{}\":", key)).unwrap();
+            assert!(position >= last, "{} out of order in {}", key, canonical);
+            last = position;
+        }
+    }
+
+    #[test]
+    fn test_string_escaping() {
+        let value = json!("\u{0}\u{8}\t\n\u{b}\u{c}\r\u{1f}\"\\/\u{7f}\u{20ac}\u{1f600}");
+        assert_eq!(
+            canonicalize(&value).unwrap(),
+            "\"\\u0000\\b\\t\\n\\u000b\\f\\r\\u001f\\\"\\\\/\u{7f}\u{20ac}\u{1f600}\""
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        // RFC 8785 Appendix B
+        assert_eq!(number(0.0), "0");
+        assert_eq!(number(-0.0), "0");
+        assert_eq!(number(f64::from_bits(0x0000000000000001)), "5e-324");
+        assert_eq!(
+            number(f64::from_bits(0x7fefffffffffffff)),
+            "1.7976931348623157e+308"
+        );
+        assert_eq!(
+            number(f64::from_bits(0x4340000000000000)),
+            "9007199254740992"
+        );
+        assert_eq!(number(f64::from_bits(0x444b1ae4d6e2ef50)), "1e+21");
+        assert_eq!(number(1e20), "100000000000000000000");
+        assert_eq!(number(123456789012345680000.0), "123456789012345680000");
+        assert_eq!(number(f64::from_bits(0x3eb0c6f7a0b5ed8d)), "0.000001");
+        assert_eq!(
+            number(f64::from_bits(0x3eb0c6f7a0b5ed8c)),
+            "9.999999999999997e-7"
+        );
+        assert_eq!(
+            number(f64::from_bits(0x41b3de4355555553)),
+            "333333333.3333332"
+        );
+        assert_eq!(number(-1.5), "-1.5");
+        assert_eq!(number(1e-7), "1e-7");
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(canonicalize(&json!(1763454699u64)).unwrap(), "1763454699");
+        assert_eq!(canonicalize(&json!(-42i64)).unwrap(), "-42");
+        // Beyond 2^53 integers are rounded to the nearest double, as in ECMAScript
+        assert_eq!(
+            canonicalize(&json!(12345678901234567890u64)).unwrap(),
+            "12345678901234567000"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_str() {
+        let json = r#"{ "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                        "string": "€$\u000F\u000aA'B\"\\\\\"\/",
+                        "literals": [null, true, false] }"#;
+        assert_eq!(
+            canonicalize_str(json).unwrap(),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+}
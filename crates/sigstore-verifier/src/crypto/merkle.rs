@@ -1,6 +1,32 @@
-use crate::crypto::hash::sha256;
+use sha2::{Digest, Sha256};
+
+use crate::crypto::hash::{constant_time_eq, sha256};
 use crate::error::TransparencyError;
 
+/// Size in bytes of an RFC 6962 (SHA-256) Merkle tree hash
+pub const HASH_SIZE: usize = 32;
+
+/// Verify an RFC 6962 Merkle inclusion proof
+///
+/// Follows the inner/border decomposition used by transparency-dev: the first
+/// `inner` proof hashes are siblings on the path below the point where the
+/// leaf's path and the last leaf's path diverge, and the remaining `border`
+/// hashes are left siblings on the right border of the tree.
+///
+/// # Arguments
+///
+/// * `leaf_hash` - The RFC 6962 leaf hash of the entry
+/// * `log_index` - Index of the leaf in the tree
+/// * `tree_size` - Number of leaves in the tree
+/// * `proof_hashes` - Audit path, ordered from the leaf towards the root
+/// * `root_hash` - Expected tree root
+///
+/// # Errors
+///
+/// Returns `InvalidHashSize` if any hash is not 32 bytes, `InvalidProofLength`
+/// if the proof does not have exactly the number of hashes implied by
+/// `log_index` and `tree_size`, and `InclusionProofFailed` if the index is out
+/// of range or the computed root does not match.
 pub fn verify_inclusion_proof(
     leaf_hash: &[u8],
     log_index: u64,
@@ -12,47 +38,443 @@ pub fn verify_inclusion_proof(
         return Err(TransparencyError::InclusionProofFailed);
     }
 
-    let mut computed_hash = leaf_hash.to_vec();
-    let mut index = log_index;
-    let mut size = tree_size;
+    let leaf_hash = to_hash(leaf_hash)?;
+    let root_hash = to_hash(root_hash)?;
+    let proof = proof_hashes
+        .iter()
+        .map(|hash| to_hash(hash))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (inner, border) = decompose_inclusion_proof(log_index, tree_size);
+    if proof.len() != inner + border {
+        return Err(TransparencyError::InvalidProofLength {
+            expected: inner + border,
+            actual: proof.len(),
+        });
+    }
+
+    let computed = chain_inner(leaf_hash, &proof[..inner], log_index);
+    let computed = chain_border_right(computed, &proof[inner..]);
+
+    if constant_time_eq(&computed, &root_hash) {
+        Ok(())
+    } else {
+        Err(TransparencyError::InclusionProofFailed)
+    }
+}
+
+/// Split an inclusion proof into its inner and border lengths
+///
+/// `inner` is the height at which the paths to `index` and to the last leaf
+/// (`size - 1`) merge; `border` is the number of left siblings above that point.
+fn decompose_inclusion_proof(index: u64, size: u64) -> (usize, usize) {
+    let inner = (u64::BITS - (index ^ (size - 1)).leading_zeros()) as usize;
+    let border = (index >> inner).count_ones() as usize;
+    (inner, border)
+}
 
-    for proof_hash in proof_hashes {
-        if size <= 1 {
-            return Err(TransparencyError::InclusionProofFailed);
+fn chain_inner(seed: [u8; HASH_SIZE], proof: &[[u8; HASH_SIZE]], index: u64) -> [u8; HASH_SIZE] {
+    proof.iter().enumerate().fold(seed, |acc, (i, hash)| {
+        if (index >> i) & 1 == 0 {
+            hash_children(&acc, hash)
+        } else {
+            hash_children(hash, &acc)
         }
+    })
+}
 
-        let (left, right) = if index % 2 == 0 && index + 1 < size {
-            // Current node is left sibling
-            (&computed_hash[..], &proof_hash[..])
+/// Like `chain_inner`, but only folds in the left siblings (used for the old root)
+fn chain_inner_right(
+    seed: [u8; HASH_SIZE],
+    proof: &[[u8; HASH_SIZE]],
+    index: u64,
+) -> [u8; HASH_SIZE] {
+    proof.iter().enumerate().fold(seed, |acc, (i, hash)| {
+        if (index >> i) & 1 == 1 {
+            hash_children(hash, &acc)
         } else {
-            // Current node is right sibling or last node
-            (&proof_hash[..], &computed_hash[..])
+            acc
+        }
+    })
+}
+
+fn chain_border_right(seed: [u8; HASH_SIZE], proof: &[[u8; HASH_SIZE]]) -> [u8; HASH_SIZE] {
+    proof
+        .iter()
+        .fold(seed, |acc, hash| hash_children(hash, &acc))
+}
+
+/// RFC 6962 interior node hash: SHA256(0x01 || left || right)
+pub fn hash_children(left: &[u8; HASH_SIZE], right: &[u8; HASH_SIZE]) -> [u8; HASH_SIZE] {
+    NodeHasher::new().children(left, right)
+}
+
+/// RFC 6962 leaf and node hasher for batches
+///
+/// Hashing many leaves or interior nodes through one `NodeHasher` reuses a
+/// single SHA-256 state, reset after each node, instead of setting one up
+/// (and, for leaves, copying the data behind a prefix byte) per node. Use it
+/// when building trees or hashing whole levels; single proofs can use
+/// [`compute_leaf_hash`] and [`hash_children`].
+#[derive(Debug, Clone, Default)]
+pub struct NodeHasher {
+    hasher: Sha256,
+}
+
+impl NodeHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leaf hash: SHA256(0x00 || data)
+    pub fn leaf(&mut self, data: &[u8]) -> [u8; HASH_SIZE] {
+        self.hasher.update([0x00]);
+        self.hasher.update(data);
+        self.hasher.finalize_reset().into()
+    }
+
+    /// Interior node hash: SHA256(0x01 || left || right)
+    pub fn children(&mut self, left: &[u8; HASH_SIZE], right: &[u8; HASH_SIZE]) -> [u8; HASH_SIZE] {
+        self.hasher.update([0x01]);
+        self.hasher.update(left);
+        self.hasher.update(right);
+        self.hasher.finalize_reset().into()
+    }
+
+    /// Replace a level of nodes with the level above it
+    ///
+    /// Adjacent nodes are hashed pairwise; an odd last node moves up unchanged,
+    /// which is how RFC 6962 trees of any size are built bottom-up.
+    pub fn hash_level(&mut self, level: &mut Vec<[u8; HASH_SIZE]>) {
+        let parents = level.len().div_ceil(2);
+        for i in 0..parents {
+            let node = match level.get(2 * i + 1).copied() {
+                Some(right) => self.children(&level[2 * i], &right),
+                None => level[2 * i],
+            };
+            level[i] = node;
+        }
+        level.truncate(parents);
+    }
+
+    /// RFC 6962 §2.1 MTH of a run of leaf hashes
+    pub fn subtree_root(&mut self, leaves: &[[u8; HASH_SIZE]]) -> [u8; HASH_SIZE] {
+        match leaves.len() {
+            0 => sha256(&[]),
+            1 => leaves[0],
+            _ => {
+                let mut level = leaves.to_vec();
+                while level.len() > 1 {
+                    self.hash_level(&mut level);
+                }
+                level[0]
+            }
+        }
+    }
+}
+
+fn to_hash(bytes: &[u8]) -> Result<[u8; HASH_SIZE], TransparencyError> {
+    bytes
+        .try_into()
+        .map_err(|_| TransparencyError::InvalidHashSize(bytes.len()))
+}
+
+pub fn compute_leaf_hash(data: &[u8]) -> [u8; 32] {
+    // RFC 6962: leaf hash = SHA256(0x00 || data)
+    NodeHasher::new().leaf(data)
+}
+
+/// RFC 6962 leaf hashes of a batch of entries, in order
+pub fn compute_leaf_hashes<I, T>(leaves: I) -> Vec<[u8; HASH_SIZE]>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut hasher = NodeHasher::new();
+    leaves.into_iter().map(|leaf| hasher.leaf(leaf.as_ref())).collect()
+}
+
+/// Verify an RFC 6962 Merkle consistency proof between two tree sizes
+///
+/// # Arguments
+///
+/// * `old_size` - Size of the earlier tree
+/// * `new_size` - Size of the later tree
+/// * `proof_hashes` - Consistency proof as produced by [`MerkleTree::consistency_proof`]
+/// * `old_root` - Root of the earlier tree
+/// * `new_root` - Root of the later tree
+///
+/// # Errors
+///
+/// Returns `InvalidHashSize` if any hash is not 32 bytes, `InvalidProofLength`
+/// if the proof has the wrong number of hashes, and `ConsistencyProofFailed`
+/// if the later tree does not extend the earlier one.
+pub fn verify_consistency_proof(
+    old_size: u64,
+    new_size: u64,
+    proof_hashes: &[Vec<u8>],
+    old_root: &[u8],
+    new_root: &[u8],
+) -> Result<(), TransparencyError> {
+    let old_root = to_hash(old_root)?;
+    let new_root = to_hash(new_root)?;
+    let proof = proof_hashes
+        .iter()
+        .map(|hash| to_hash(hash))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if new_size < old_size {
+        return Err(TransparencyError::TreeSizeOutOfRange {
+            requested: old_size,
+            available: new_size,
+        });
+    }
+    if old_size == new_size || old_size == 0 {
+        if !proof.is_empty() {
+            return Err(TransparencyError::InvalidProofLength {
+                expected: 0,
+                actual: proof.len(),
+            });
+        }
+        return if old_size == 0 || old_root == new_root {
+            Ok(())
+        } else {
+            Err(TransparencyError::ConsistencyProofFailed)
         };
+    }
 
-        // Hash parent: SHA256(0x01 || left || right)
-        let mut parent_data = Vec::with_capacity(1 + left.len() + right.len());
-        parent_data.push(0x01);
-        parent_data.extend_from_slice(left);
-        parent_data.extend_from_slice(right);
-        computed_hash = sha256(&parent_data).to_vec();
+    // The proof is a suffix of the inclusion proof for leaf `old_size - 1`,
+    // starting at the level of the largest complete subtree ending there
+    let (inner, border) = decompose_inclusion_proof(old_size - 1, new_size);
+    let shift = old_size.trailing_zeros() as usize;
+    let inner = inner - shift;
 
-        index /= 2;
-        size = (size + 1) / 2;
+    // Unless the old tree is itself complete, its last subtree root leads the proof
+    let (seed, start) = if old_size == 1 << shift {
+        (old_root, 0)
+    } else {
+        match proof.first() {
+            Some(first) => (*first, 1),
+            None => {
+                return Err(TransparencyError::InvalidProofLength {
+                    expected: 1 + inner + border,
+                    actual: 0,
+                })
+            }
+        }
+    };
+    if proof.len() != start + inner + border {
+        return Err(TransparencyError::InvalidProofLength {
+            expected: start + inner + border,
+            actual: proof.len(),
+        });
     }
+    let proof = &proof[start..];
+    let mask = (old_size - 1) >> shift;
+
+    let computed_old = chain_inner_right(seed, &proof[..inner], mask);
+    let computed_old = chain_border_right(computed_old, &proof[inner..]);
+    let computed_new = chain_inner(seed, &proof[..inner], mask);
+    let computed_new = chain_border_right(computed_new, &proof[inner..]);
 
-    if computed_hash == root_hash {
+    // Evaluate both comparisons so neither short-circuits
+    let old_matches = constant_time_eq(&computed_old, &old_root);
+    let new_matches = constant_time_eq(&computed_new, &new_root);
+    if old_matches & new_matches {
         Ok(())
     } else {
-        Err(TransparencyError::InclusionProofFailed)
+        Err(TransparencyError::ConsistencyProofFailed)
     }
 }
 
-pub fn compute_leaf_hash(data: &[u8]) -> [u8; 32] {
-    // RFC 6962: leaf hash = SHA256(0x00 || data)
-    let mut leaf_data = Vec::with_capacity(1 + data.len());
-    leaf_data.push(0x00);
-    leaf_data.extend_from_slice(data);
-    sha256(&leaf_data)
+/// In-memory RFC 6962 Merkle tree
+///
+/// Holds the leaf hashes of an append-only log so that roots, inclusion
+/// proofs and consistency proofs can be produced for any earlier tree size.
+/// Proofs verify with [`verify_inclusion_proof`] and [`verify_consistency_proof`].
+///
+/// # Example
+///
+/// ```ignore
+/// let tree = MerkleTree::from_leaves([b"a".as_slice(), b"b", b"c"]);
+/// let proof = tree.inclusion_proof(1, tree.size())?;
+/// verify_inclusion_proof(&tree.leaf_hash(1).unwrap(), 1, 3, &proof, &tree.root())?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleTree {
+    leaves: Vec<[u8; HASH_SIZE]>,
+}
+
+impl MerkleTree {
+    /// Create an empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a tree from raw leaf data, hashing each entry as an RFC 6962 leaf
+    pub fn from_leaves<I, T>(leaves: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        MerkleTree {
+            leaves: compute_leaf_hashes(leaves),
+        }
+    }
+
+    /// Build a tree from precomputed leaf hashes
+    pub fn from_leaf_hashes(leaf_hashes: Vec<[u8; HASH_SIZE]>) -> Self {
+        MerkleTree {
+            leaves: leaf_hashes,
+        }
+    }
+
+    /// Append raw leaf data and return its index
+    pub fn push(&mut self, data: &[u8]) -> u64 {
+        self.push_hash(compute_leaf_hash(data))
+    }
+
+    /// Append a precomputed leaf hash and return its index
+    pub fn push_hash(&mut self, leaf_hash: [u8; HASH_SIZE]) -> u64 {
+        self.leaves.push(leaf_hash);
+        self.size() - 1
+    }
+
+    /// Number of leaves in the tree
+    pub fn size(&self) -> u64 {
+        self.leaves.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Leaf hash at `index`, if present
+    pub fn leaf_hash(&self, index: u64) -> Option<[u8; HASH_SIZE]> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.leaves.get(index))
+            .copied()
+    }
+
+    /// Root of the full tree (SHA-256 of the empty string when empty)
+    pub fn root(&self) -> [u8; HASH_SIZE] {
+        NodeHasher::new().subtree_root(&self.leaves)
+    }
+
+    /// Root of the tree formed by the first `size` leaves
+    ///
+    /// # Errors
+    ///
+    /// Returns `TreeSizeOutOfRange` if `size` exceeds the number of leaves.
+    pub fn root_at(&self, size: u64) -> Result<[u8; HASH_SIZE], TransparencyError> {
+        Ok(NodeHasher::new().subtree_root(self.prefix(size)?))
+    }
+
+    /// Inclusion proof for leaf `index` in the tree of the first `size` leaves
+    ///
+    /// # Errors
+    ///
+    /// Returns `TreeSizeOutOfRange` if `size` exceeds the number of leaves and
+    /// `IndexOutOfRange` if `index >= size`.
+    pub fn inclusion_proof(
+        &self,
+        index: u64,
+        size: u64,
+    ) -> Result<Vec<[u8; HASH_SIZE]>, TransparencyError> {
+        let leaves = self.prefix(size)?;
+        if index >= size {
+            return Err(TransparencyError::IndexOutOfRange { index, size });
+        }
+        let mut proof = Vec::new();
+        audit_path(&mut NodeHasher::new(), index as usize, leaves, &mut proof);
+        Ok(proof)
+    }
+
+    /// Consistency proof between the trees of the first `old_size` and `new_size` leaves
+    ///
+    /// # Errors
+    ///
+    /// Returns `TreeSizeOutOfRange` if `new_size` exceeds the number of leaves
+    /// or `old_size > new_size`.
+    pub fn consistency_proof(
+        &self,
+        old_size: u64,
+        new_size: u64,
+    ) -> Result<Vec<[u8; HASH_SIZE]>, TransparencyError> {
+        let leaves = self.prefix(new_size)?;
+        if old_size > new_size {
+            return Err(TransparencyError::TreeSizeOutOfRange {
+                requested: old_size,
+                available: new_size,
+            });
+        }
+        let mut proof = Vec::new();
+        if old_size > 0 && old_size < new_size {
+            subproof(&mut NodeHasher::new(), old_size as usize, leaves, true, &mut proof);
+        }
+        Ok(proof)
+    }
+
+    fn prefix(&self, size: u64) -> Result<&[[u8; HASH_SIZE]], TransparencyError> {
+        usize::try_from(size)
+            .ok()
+            .and_then(|size| self.leaves.get(..size))
+            .ok_or(TransparencyError::TreeSizeOutOfRange {
+                requested: size,
+                available: self.size(),
+            })
+    }
+}
+
+/// Largest power of two strictly less than `n` (for `n >= 2`)
+fn split_point(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+/// RFC 6962 §2.1.1 PATH, appended leaf-first
+fn audit_path(
+    hasher: &mut NodeHasher,
+    index: usize,
+    leaves: &[[u8; HASH_SIZE]],
+    proof: &mut Vec<[u8; HASH_SIZE]>,
+) {
+    if leaves.len() <= 1 {
+        return;
+    }
+    let k = split_point(leaves.len());
+    if index < k {
+        audit_path(hasher, index, &leaves[..k], proof);
+        proof.push(hasher.subtree_root(&leaves[k..]));
+    } else {
+        audit_path(hasher, index - k, &leaves[k..], proof);
+        proof.push(hasher.subtree_root(&leaves[..k]));
+    }
+}
+
+/// RFC 6962 §2.1.2 SUBPROOF
+fn subproof(
+    hasher: &mut NodeHasher,
+    old_size: usize,
+    leaves: &[[u8; HASH_SIZE]],
+    complete: bool,
+    proof: &mut Vec<[u8; HASH_SIZE]>,
+) {
+    let n = leaves.len();
+    if old_size == n {
+        if !complete {
+            proof.push(hasher.subtree_root(leaves));
+        }
+        return;
+    }
+    let k = split_point(n);
+    if old_size <= k {
+        subproof(hasher, old_size, &leaves[..k], complete, proof);
+        proof.push(hasher.subtree_root(&leaves[k..]));
+    } else {
+        subproof(hasher, old_size - k, &leaves[k..], false, proof);
+        proof.push(hasher.subtree_root(&leaves[..k]));
+    }
 }
 
 #[cfg(test)]
@@ -86,4 +508,289 @@ mod tests {
         let result = verify_inclusion_proof(&leaf, 5, 3, &proof, &root);
         assert!(result.is_err());
     }
+
+    // Reference tree from the RFC 6962 / transparency-dev test vectors
+    const LEAVES: [&[u8]; 8] = [
+        b"",
+        b"\x00",
+        b"\x10",
+        b"\x20\x21",
+        b"\x30\x31",
+        b"\x40\x41\x42\x43",
+        b"\x50\x51\x52\x53\x54\x55\x56\x57",
+        b"\x60\x61\x62\x63\x64\x65\x66\x67\x68\x69\x6a\x6b\x6c\x6d\x6e\x6f",
+    ];
+
+    const ROOTS: [&str; 8] = [
+        "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+        "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+        "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+        "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+        "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+        "76e67dadbcdf1e10e1b74ddc608abd2f98dfb16fbce75277b5232a127f2087ef",
+        "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+        "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+    ];
+
+    // (leaf index, tree size, audit path)
+    const PROOFS: [(u64, u64, &[&str]); 6] = [
+        (0, 1, &[]),
+        (
+            0,
+            8,
+            &[
+                "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7",
+                "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+                "6b47aaf29ee3c2af9af889bc1fb9254dabd31177f16232dd6aab035ca39bf6e4",
+            ],
+        ),
+        (
+            5,
+            8,
+            &[
+                "bc1a0643b12e4d2d7c77918f44e0f4f79a838b6cf9ec5b5c283e1f4d88599e6b",
+                "ca854ea128ed050b41b35ffc1b87b8eb2bde461e9e3b5596ece6b9d5975a0ae0",
+                "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+            ],
+        ),
+        (
+            2,
+            3,
+            &["fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125"],
+        ),
+        (
+            1,
+            5,
+            &[
+                "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+                "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+                "bc1a0643b12e4d2d7c77918f44e0f4f79a838b6cf9ec5b5c283e1f4d88599e6b",
+            ],
+        ),
+        (
+            6,
+            7,
+            &[
+                "0ebc5d3437fbe2db158b9f126a1d118e308181031d0a949f8dededebc558ef6a",
+                "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+            ],
+        ),
+    ];
+
+    fn decode_proof(proof: &[&str]) -> Vec<Vec<u8>> {
+        proof
+            .iter()
+            .map(|hash| hex::decode(hash).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_inclusion_proof_known_answers() {
+        for (index, size, proof) in PROOFS {
+            let leaf = compute_leaf_hash(LEAVES[index as usize]);
+            let root = hex::decode(ROOTS[size as usize - 1]).unwrap();
+            let proof = decode_proof(proof);
+            assert!(
+                verify_inclusion_proof(&leaf, index, size, &proof, &root).is_ok(),
+                "proof for leaf {} in tree of size {} should verify",
+                index,
+                size
+            );
+
+            // The same proof must not verify for a neighbouring leaf
+            let other = compute_leaf_hash(LEAVES[(index as usize + 1) % 8]);
+            assert!(verify_inclusion_proof(&other, index, size, &proof, &root).is_err());
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_wrong_length() {
+        let (index, size, proof) = PROOFS[2];
+        let leaf = compute_leaf_hash(LEAVES[index as usize]);
+        let root = hex::decode(ROOTS[size as usize - 1]).unwrap();
+
+        let mut extended = decode_proof(proof);
+        extended.push(vec![0u8; 32]);
+        assert!(matches!(
+            verify_inclusion_proof(&leaf, index, size, &extended, &root),
+            Err(TransparencyError::InvalidProofLength {
+                expected: 3,
+                actual: 4
+            })
+        ));
+
+        let truncated = &decode_proof(proof)[..2];
+        assert!(matches!(
+            verify_inclusion_proof(&leaf, index, size, truncated, &root),
+            Err(TransparencyError::InvalidProofLength {
+                expected: 3,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_bad_hash_size() {
+        let (index, size, proof) = PROOFS[1];
+        let leaf = compute_leaf_hash(LEAVES[index as usize]);
+        let root = hex::decode(ROOTS[size as usize - 1]).unwrap();
+
+        let mut proof = decode_proof(proof);
+        proof[1].push(0);
+        assert!(matches!(
+            verify_inclusion_proof(&leaf, index, size, &proof, &root),
+            Err(TransparencyError::InvalidHashSize(33))
+        ));
+        assert!(matches!(
+            verify_inclusion_proof(&leaf[..31], index, size, &decode_proof(PROOFS[1].2), &root),
+            Err(TransparencyError::InvalidHashSize(31))
+        ));
+    }
+
+    #[test]
+    fn test_decompose_inclusion_proof() {
+        assert_eq!(decompose_inclusion_proof(0, 1), (0, 0));
+        assert_eq!(decompose_inclusion_proof(0, 8), (3, 0));
+        assert_eq!(decompose_inclusion_proof(6, 7), (0, 2));
+        assert_eq!(decompose_inclusion_proof(3, 7), (3, 0));
+        assert_eq!(decompose_inclusion_proof(4, 5), (0, 1));
+        assert_eq!(decompose_inclusion_proof(5, 7), (2, 1));
+    }
+
+    fn reference_tree() -> MerkleTree {
+        MerkleTree::from_leaves(LEAVES)
+    }
+
+    fn to_vecs(proof: Vec<[u8; HASH_SIZE]>) -> Vec<Vec<u8>> {
+        proof.into_iter().map(|hash| hash.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_merkle_tree_roots() {
+        let tree = reference_tree();
+        for (size, root) in ROOTS.iter().enumerate() {
+            assert_eq!(hex::encode(tree.root_at(size as u64 + 1).unwrap()), *root);
+        }
+        assert_eq!(tree.root(), tree.root_at(8).unwrap());
+        assert_eq!(
+            hex::encode(MerkleTree::new().root()),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert!(matches!(
+            tree.root_at(9),
+            Err(TransparencyError::TreeSizeOutOfRange {
+                requested: 9,
+                available: 8
+            })
+        ));
+    }
+
+    #[test]
+    fn test_merkle_tree_inclusion_proofs() {
+        let tree = reference_tree();
+        for (index, size, proof) in PROOFS {
+            let generated = tree.inclusion_proof(index, size).unwrap();
+            assert_eq!(to_vecs(generated), decode_proof(proof));
+        }
+
+        // Every generated proof verifies
+        for size in 1..=tree.size() {
+            let root = tree.root_at(size).unwrap();
+            for index in 0..size {
+                let proof = to_vecs(tree.inclusion_proof(index, size).unwrap());
+                let leaf = tree.leaf_hash(index).unwrap();
+                verify_inclusion_proof(&leaf, index, size, &proof, &root).unwrap();
+            }
+        }
+
+        assert!(matches!(
+            tree.inclusion_proof(3, 3),
+            Err(TransparencyError::IndexOutOfRange { index: 3, size: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_merkle_tree_consistency_proofs() {
+        let tree = reference_tree();
+        let known: [(u64, u64, &[&str]); 3] = [
+            (
+                1,
+                8,
+                &[
+                    "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7",
+                    "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+                    "6b47aaf29ee3c2af9af889bc1fb9254dabd31177f16232dd6aab035ca39bf6e4",
+                ],
+            ),
+            (
+                6,
+                8,
+                &[
+                    "0ebc5d3437fbe2db158b9f126a1d118e308181031d0a949f8dededebc558ef6a",
+                    "ca854ea128ed050b41b35ffc1b87b8eb2bde461e9e3b5596ece6b9d5975a0ae0",
+                    "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+                ],
+            ),
+            (
+                2,
+                5,
+                &[
+                    "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+                    "bc1a0643b12e4d2d7c77918f44e0f4f79a838b6cf9ec5b5c283e1f4d88599e6b",
+                ],
+            ),
+        ];
+        for (old_size, new_size, proof) in known {
+            let generated = tree.consistency_proof(old_size, new_size).unwrap();
+            assert_eq!(to_vecs(generated), decode_proof(proof));
+        }
+
+        for new_size in 0..=tree.size() {
+            let new_root = tree.root_at(new_size).unwrap();
+            for old_size in 0..=new_size {
+                let old_root = tree.root_at(old_size).unwrap();
+                let proof = to_vecs(tree.consistency_proof(old_size, new_size).unwrap());
+                verify_consistency_proof(old_size, new_size, &proof, &old_root, &new_root)
+                    .unwrap_or_else(|e| panic!("{} -> {}: {}", old_size, new_size, e));
+
+                if old_size > 0 && old_size < new_size {
+                    let wrong_root = tree.root_at(old_size - 1).unwrap();
+                    assert!(verify_consistency_proof(
+                        old_size,
+                        new_size,
+                        &proof,
+                        &wrong_root,
+                        &new_root
+                    )
+                    .is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_node_hasher_matches_single_hashes() {
+        let mut hasher = NodeHasher::new();
+        let leaf_hashes = compute_leaf_hashes(LEAVES);
+        for (leaf, hash) in LEAVES.iter().zip(&leaf_hashes) {
+            assert_eq!(*hash, compute_leaf_hash(leaf));
+            assert_eq!(hasher.leaf(leaf), *hash);
+        }
+        assert_eq!(hasher.children(&leaf_hashes[0], &leaf_hashes[1]), hash_children(&leaf_hashes[0], &leaf_hashes[1]));
+
+        // Bottom-up roots match the reference roots for every size, odd ones included
+        for (size, root) in ROOTS.iter().enumerate() {
+            assert_eq!(hex::encode(hasher.subtree_root(&leaf_hashes[..size + 1])), *root);
+        }
+    }
+
+    #[test]
+    fn test_merkle_tree_push() {
+        let mut tree = MerkleTree::new();
+        assert!(tree.is_empty());
+        for (i, leaf) in LEAVES.iter().enumerate() {
+            assert_eq!(tree.push(leaf), i as u64);
+        }
+        assert_eq!(tree, reference_tree());
+    }
 }
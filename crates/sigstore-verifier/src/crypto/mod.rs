@@ -1,3 +1,12 @@
+pub mod algorithm;
 pub mod hash;
+#[cfg(feature = "full")]
+#[doc(hidden)]
+pub mod jcs;
+#[cfg(feature = "full")]
+#[doc(hidden)]
 pub mod merkle;
 pub mod signature;
+#[cfg(feature = "full")]
+#[doc(hidden)]
+pub mod tiles;
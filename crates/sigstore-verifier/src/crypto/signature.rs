@@ -1,68 +1,130 @@
+use ecdsa::signature::hazmat::PrehashVerifier;
 use ecdsa::signature::Verifier;
+use k256::ecdsa::{Signature as K256Signature, VerifyingKey as K256VerifyingKey};
 use p256::ecdsa::{Signature as P256Signature, VerifyingKey as P256VerifyingKey};
 use p384::ecdsa::{Signature as P384Signature, VerifyingKey as P384VerifyingKey};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1v15::{Signature as RsaSignature, VerifyingKey as RsaVerifyingKey};
+use rsa::RsaPublicKey;
+use sha2::{Sha256, Sha384, Sha512};
+#[cfg(feature = "full")]
 use x509_parser::prelude::*;
 
+use crate::crypto::algorithm::SignatureAlgorithm;
 use crate::error::SignatureError;
+use crate::parser::der::{self, DerReader};
+
+/// id-ecPublicKey (RFC 5480)
+pub const OID_EC_PUBLIC_KEY: &str = "1.2.840.10045.2.1";
+
+/// secp256r1 / P-256 named curve
+pub const OID_CURVE_P256: &str = "1.2.840.10045.3.1.7";
+
+/// secp384r1 / P-384 named curve
+pub const OID_CURVE_P384: &str = "1.3.132.0.34";
+
+/// secp256k1 named curve
+pub const OID_CURVE_SECP256K1: &str = "1.3.132.0.10";
+
+/// rsaEncryption (RFC 8017)
+pub const OID_RSA_ENCRYPTION: &str = "1.2.840.113549.1.1.1";
 
 #[derive(Debug, Clone)]
 pub enum PublicKey {
     P256(P256VerifyingKey),
     P384(P384VerifyingKey),
+    Secp256k1(K256VerifyingKey),
+    /// PKCS#1 v1.5 RSA key, found on web PKI TSA chains
+    Rsa(RsaPublicKey),
 }
 
 impl PublicKey {
+    #[cfg(feature = "full")]
     pub fn from_certificate(cert: &X509Certificate) -> Result<Self, SignatureError> {
-        let spki = cert.public_key();
-        let algorithm_oid = &spki.algorithm.algorithm;
-
-        // Check if this is an EC public key (1.2.840.10045.2.1)
-        if algorithm_oid.to_id_string() == "1.2.840.10045.2.1" {
-            // For EC keys, the curve is specified in the parameters
-            if let Some(params) = &spki.algorithm.parameters {
-                if let Ok(curve_oid) = params.as_oid() {
-                    match curve_oid.to_id_string().as_str() {
-                        "1.2.840.10045.3.1.7" => {
-                            // secp256r1 (P-256)
-                            let key_bytes = &spki.subject_public_key.data;
-                            let verifying_key = P256VerifyingKey::from_sec1_bytes(key_bytes)
-                                .map_err(|e| SignatureError::PublicKeyParse(e.to_string()))?;
-                            return Ok(PublicKey::P256(verifying_key));
-                        }
-                        "1.3.132.0.34" => {
-                            // secp384r1 (P-384)
-                            let key_bytes = &spki.subject_public_key.data;
-                            let verifying_key = P384VerifyingKey::from_sec1_bytes(key_bytes)
-                                .map_err(|e| SignatureError::PublicKeyParse(e.to_string()))?;
-                            return Ok(PublicKey::P384(verifying_key));
-                        }
-                        oid => return Err(SignatureError::UnsupportedAlgorithm(format!("EC curve: {}", oid))),
+        Self::from_spki(cert.public_key())
+    }
+
+    /// Build a verifying key from a DER SubjectPublicKeyInfo (`PUBLIC KEY` PEM body)
+    pub fn from_spki_der(spki_der: &[u8]) -> Result<Self, SignatureError> {
+        let (algorithm_oid, curve_oid, key_bytes) = read_spki(spki_der).map_err(SignatureError::PublicKeyParse)?;
+        Self::from_key_parts(&algorithm_oid, curve_oid.as_deref(), key_bytes)
+    }
+
+    /// Build a verifying key from a SubjectPublicKeyInfo, dispatching on its curve OID
+    #[cfg(feature = "full")]
+    pub fn from_spki(spki: &SubjectPublicKeyInfo) -> Result<Self, SignatureError> {
+        let curve_oid = spki
+            .algorithm
+            .parameters
+            .as_ref()
+            .and_then(|params| params.as_oid().ok())
+            .map(|oid| oid.to_id_string());
+        Self::from_key_parts(
+            &spki.algorithm.algorithm.to_id_string(),
+            curve_oid.as_deref(),
+            &spki.subject_public_key.data,
+        )
+    }
+
+    /// Build a verifying key from the algorithm OID, the curve OID of EC keys
+    /// and the key bits of a SubjectPublicKeyInfo
+    fn from_key_parts(
+        algorithm_oid: &str,
+        curve_oid: Option<&str>,
+        key_bytes: &[u8],
+    ) -> Result<Self, SignatureError> {
+        if algorithm_oid == OID_RSA_ENCRYPTION {
+            return RsaPublicKey::from_pkcs1_der(key_bytes)
+                .map(PublicKey::Rsa)
+                .map_err(|e| SignatureError::PublicKeyParse(e.to_string()));
+        }
+
+        // For EC keys (id-ecPublicKey), the curve is specified in the parameters
+        if algorithm_oid == OID_EC_PUBLIC_KEY {
+            let curve_oid = curve_oid.ok_or_else(|| {
+                SignatureError::UnsupportedAlgorithm("EC key without curve parameters".to_string())
+            })?;
+            return Self::from_curve(curve_oid, key_bytes)
+                .map_err(|e| match e {
+                    SignatureError::UnsupportedAlgorithm(oid) => {
+                        SignatureError::UnsupportedAlgorithm(format!("EC curve: {}", oid))
                     }
-                }
-            }
-            return Err(SignatureError::UnsupportedAlgorithm("EC key without curve parameters".to_string()));
+                    e => e,
+                });
         }
 
         // Legacy support: try matching the algorithm OID directly (for older formats)
-        match algorithm_oid.to_id_string().as_str() {
-            "1.2.840.10045.3.1.7" => {
-                // secp256r1 (P-256)
-                let key_bytes = &spki.subject_public_key.data;
-                let verifying_key = P256VerifyingKey::from_sec1_bytes(key_bytes)
-                    .map_err(|e| SignatureError::PublicKeyParse(e.to_string()))?;
-                Ok(PublicKey::P256(verifying_key))
-            }
-            "1.3.132.0.34" => {
-                // secp384r1 (P-384)
-                let key_bytes = &spki.subject_public_key.data;
-                let verifying_key = P384VerifyingKey::from_sec1_bytes(key_bytes)
-                    .map_err(|e| SignatureError::PublicKeyParse(e.to_string()))?;
-                Ok(PublicKey::P384(verifying_key))
-            }
+        Self::from_curve(algorithm_oid, key_bytes)
+    }
+
+    /// Parse SEC1-encoded key bytes for the named curve `curve_oid`
+    fn from_curve(curve_oid: &str, key_bytes: &[u8]) -> Result<Self, SignatureError> {
+        let parse_error = |e: ecdsa::Error| SignatureError::PublicKeyParse(e.to_string());
+        match curve_oid {
+            OID_CURVE_P256 => P256VerifyingKey::from_sec1_bytes(key_bytes)
+                .map(PublicKey::P256)
+                .map_err(parse_error),
+            OID_CURVE_P384 => P384VerifyingKey::from_sec1_bytes(key_bytes)
+                .map(PublicKey::P384)
+                .map_err(parse_error),
+            OID_CURVE_SECP256K1 => K256VerifyingKey::from_sec1_bytes(key_bytes)
+                .map(PublicKey::Secp256k1)
+                .map_err(parse_error),
             oid => Err(SignatureError::UnsupportedAlgorithm(oid.to_string())),
         }
     }
 
+    /// Algorithm used for signatures without a declared algorithm (e.g. DSSE envelopes)
+    ///
+    /// Such signatures are verified with the curve's standard digest.
+    pub fn default_algorithm(&self) -> SignatureAlgorithm {
+        match self {
+            PublicKey::P256(_) | PublicKey::Secp256k1(_) => SignatureAlgorithm::EcdsaSha256,
+            PublicKey::P384(_) => SignatureAlgorithm::EcdsaSha384,
+            PublicKey::Rsa(_) => SignatureAlgorithm::RsaSha256,
+        }
+    }
+
     pub fn verify_signature(&self, message: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
         match self {
             PublicKey::P256(key) => {
@@ -77,9 +139,93 @@ impl PublicKey {
                 key.verify(message, &sig)
                     .map_err(|_| SignatureError::InvalidSignature)?;
             }
+            PublicKey::Secp256k1(key) => {
+                // k256 rejects high-S signatures, which other ECDSA signers may produce
+                let sig = K256Signature::from_der(signature)
+                    .map_err(|e| SignatureError::InvalidFormat(e.to_string()))?;
+                let sig = sig.normalize_s().unwrap_or(sig);
+                key.verify(message, &sig)
+                    .map_err(|_| SignatureError::InvalidSignature)?;
+            }
+            PublicKey::Rsa(_) => {
+                return self.verify_prehash(&SignatureAlgorithm::RsaSha256.digest(message), signature);
+            }
         }
         Ok(())
     }
+
+    /// Verify a DER-encoded ECDSA signature over an already computed message digest
+    ///
+    /// Used where the digest algorithm is chosen by the signer (e.g. CMS
+    /// SignedData) rather than implied by the curve. RSA signatures are
+    /// PKCS#1 v1.5, with the hash told apart by the digest length.
+    pub fn verify_prehash(&self, digest: &[u8], signature: &[u8]) -> Result<(), SignatureError> {
+        match self {
+            PublicKey::P256(key) => {
+                let sig = P256Signature::from_der(signature)
+                    .map_err(|e| SignatureError::InvalidFormat(e.to_string()))?;
+                key.verify_prehash(digest, &sig)
+                    .map_err(|_| SignatureError::InvalidSignature)?;
+            }
+            PublicKey::P384(key) => {
+                let sig = P384Signature::from_der(signature)
+                    .map_err(|e| SignatureError::InvalidFormat(e.to_string()))?;
+                key.verify_prehash(digest, &sig)
+                    .map_err(|_| SignatureError::InvalidSignature)?;
+            }
+            PublicKey::Secp256k1(key) => {
+                let sig = K256Signature::from_der(signature)
+                    .map_err(|e| SignatureError::InvalidFormat(e.to_string()))?;
+                let sig = sig.normalize_s().unwrap_or(sig);
+                key.verify_prehash(digest, &sig)
+                    .map_err(|_| SignatureError::InvalidSignature)?;
+            }
+            PublicKey::Rsa(key) => {
+                let sig = RsaSignature::try_from(signature).map_err(|e| SignatureError::InvalidFormat(e.to_string()))?;
+                let result = match digest.len() {
+                    32 => RsaVerifyingKey::<Sha256>::new(key.clone()).verify_prehash(digest, &sig),
+                    48 => RsaVerifyingKey::<Sha384>::new(key.clone()).verify_prehash(digest, &sig),
+                    64 => RsaVerifyingKey::<Sha512>::new(key.clone()).verify_prehash(digest, &sig),
+                    len => {
+                        return Err(SignatureError::UnsupportedAlgorithm(format!(
+                            "RSA with a {}-byte digest",
+                            len
+                        )))
+                    }
+                };
+                result.map_err(|_| SignatureError::InvalidSignature)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Algorithm OID, curve OID (for EC keys) and key bits of a DER
+/// `SubjectPublicKeyInfo ::= SEQUENCE { algorithm AlgorithmIdentifier, subjectPublicKey BIT STRING }`
+fn read_spki(spki_der: &[u8]) -> Result<(String, Option<String>, &[u8]), String> {
+    let mut outer = DerReader::new(spki_der);
+    let mut fields = DerReader::new(outer.read(der::TAG_SEQUENCE)?.content);
+    outer.finish()?;
+
+    let mut algorithm = DerReader::new(fields.read(der::TAG_SEQUENCE)?.content);
+    let algorithm_oid = der::oid_to_string(algorithm.read(der::TAG_OID)?.content)?;
+    let curve_oid = match algorithm.read_optional(der::TAG_OID)? {
+        Some(curve) => Some(der::oid_to_string(curve.content)?),
+        // Other parameters, e.g. the NULL of RSA keys
+        None if !algorithm.is_empty() => {
+            algorithm.read_any()?;
+            None
+        }
+        None => None,
+    };
+    algorithm.finish()?;
+
+    let key_bits = fields.read(der::TAG_BIT_STRING)?;
+    fields.finish()?;
+    match key_bits.content.split_first() {
+        Some((unused, key_bytes)) if *unused < 8 => Ok((algorithm_oid, curve_oid, key_bytes)),
+        _ => Err("invalid BIT STRING".to_string()),
+    }
 }
 
 #[cfg(test)]
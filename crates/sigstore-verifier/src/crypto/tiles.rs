@@ -0,0 +1,335 @@
+//! Tiled transparency logs (C2SP `tlog-tiles`)
+//!
+//! Rekor v2 publishes its Merkle tree as static tiles instead of answering
+//! proof queries. A tile at level `L` holds up to 256 consecutive hashes of the
+//! tree nodes `8 * L` levels above the leaves, so level 0 tiles hold leaf
+//! hashes; entry bundles hold the log entries of the matching level 0 tile.
+//! This module maps tree coordinates to tile paths and rebuilds RFC 6962
+//! inclusion proofs from tiles, so an entry can be proven against a checkpoint
+//! with static fetches only.
+
+use std::collections::HashMap;
+
+use crate::crypto::merkle::{hash_children, NodeHasher, HASH_SIZE};
+use crate::error::TransparencyError;
+
+/// Number of tree levels covered by one tile
+pub const TILE_HEIGHT: u32 = 8;
+
+/// Maximum number of hashes (or entries) in a tile
+pub const TILE_WIDTH: u64 = 1 << TILE_HEIGHT;
+
+/// Source of tile hashes, e.g. an HTTP client or a local cache
+pub trait TileSource {
+    /// Hashes of tile `index` at `level`
+    ///
+    /// `width` is `TILE_WIDTH` for a full tile and the number of hashes for
+    /// the partial tile at the right edge of the tree.
+    fn tile(&self, level: u64, index: u64, width: u64) -> Result<Vec<[u8; HASH_SIZE]>, TransparencyError>;
+}
+
+/// Path of a hash tile, relative to the log's tile root
+///
+/// e.g. `tile/0/x001/x234/067.p/8` for the partial level 0 tile 1234067 of width 8
+pub fn tile_path(level: u64, index: u64, width: u64) -> String {
+    format!("tile/{}/{}", level, encode_index(index, width))
+}
+
+/// Path of an entry bundle, relative to the log's tile root
+pub fn entry_bundle_path(index: u64, width: u64) -> String {
+    format!("tile/entries/{}", encode_index(index, width))
+}
+
+/// Tile index as 3-digit path elements, all but the last prefixed with `x`
+fn encode_index(index: u64, width: u64) -> String {
+    let mut elements = vec![format!("{:03}", index % 1000)];
+    let mut rest = index / 1000;
+    while rest > 0 {
+        elements.push(format!("x{:03}", rest % 1000));
+        rest /= 1000;
+    }
+    elements.reverse();
+
+    let mut path = elements.join("/");
+    if width < TILE_WIDTH {
+        path.push_str(&format!(".p/{}", width));
+    }
+    path
+}
+
+/// Width of tile `index` at `level` in a tree of `tree_size` leaves
+///
+/// Returns 0 if the tile does not exist yet.
+pub fn tile_width(level: u64, index: u64, tree_size: u64) -> u64 {
+    let level_size = tree_size >> (TILE_HEIGHT as u64 * level);
+    level_size
+        .saturating_sub(index * TILE_WIDTH)
+        .min(TILE_WIDTH)
+}
+
+/// Parse a hash tile: concatenated 32-byte hashes
+///
+/// # Errors
+///
+/// Returns `InvalidTile` if the data is not a whole number of hashes.
+pub fn parse_tile(data: &[u8]) -> Result<Vec<[u8; HASH_SIZE]>, TransparencyError> {
+    if !data.len().is_multiple_of(HASH_SIZE) {
+        return Err(TransparencyError::InvalidTile(format!(
+            "{} bytes is not a multiple of {}",
+            data.len(),
+            HASH_SIZE
+        )));
+    }
+    Ok(data
+        .chunks_exact(HASH_SIZE)
+        .map(|hash| hash.try_into().unwrap())
+        .collect())
+}
+
+/// Parse an entry bundle: entries each prefixed with a big-endian u16 length
+///
+/// # Errors
+///
+/// Returns `InvalidTile` if an entry is truncated.
+pub fn parse_entry_bundle(data: &[u8]) -> Result<Vec<Vec<u8>>, TransparencyError> {
+    let mut entries = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        if rest.len() < 2 {
+            return Err(TransparencyError::InvalidTile("truncated entry length".to_string()));
+        }
+        let len = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+        let entry = rest
+            .get(2..2 + len)
+            .ok_or_else(|| TransparencyError::InvalidTile("truncated entry".to_string()))?;
+        entries.push(entry.to_vec());
+        rest = &rest[2 + len..];
+    }
+    Ok(entries)
+}
+
+/// Rebuild the RFC 6962 inclusion proof of leaf `index` from tiles
+///
+/// The proof verifies with `verify_inclusion_proof` against the root of the
+/// tree of `tree_size` leaves (e.g. from a checkpoint).
+///
+/// # Errors
+///
+/// Returns `IndexOutOfRange` if `index >= tree_size` and any error of the
+/// tile source, or `InvalidTile` if a tile is shorter than its width.
+pub fn inclusion_proof_from_tiles<S: TileSource + ?Sized>(
+    source: &S,
+    index: u64,
+    tree_size: u64,
+) -> Result<Vec<[u8; HASH_SIZE]>, TransparencyError> {
+    if index >= tree_size {
+        return Err(TransparencyError::IndexOutOfRange { index, size: tree_size });
+    }
+    let mut reader = TileReader::new(source, tree_size);
+    let mut proof = Vec::new();
+    reader.audit_path(index, 0, tree_size, &mut proof)?;
+    Ok(proof)
+}
+
+/// Root hash of the tree of `tree_size` leaves, computed from tiles
+///
+/// # Errors
+///
+/// Returns any error of the tile source, or `InvalidTile` if a tile is
+/// shorter than its width.
+pub fn root_from_tiles<S: TileSource + ?Sized>(
+    source: &S,
+    tree_size: u64,
+) -> Result<[u8; HASH_SIZE], TransparencyError> {
+    if tree_size == 0 {
+        return Ok(crate::crypto::hash::sha256(&[]));
+    }
+    TileReader::new(source, tree_size).range_hash(0, tree_size)
+}
+
+/// Tile fetches for one tree size, each tile fetched at most once
+struct TileReader<'a, S: ?Sized> {
+    source: &'a S,
+    tree_size: u64,
+    tiles: HashMap<(u64, u64), Vec<[u8; HASH_SIZE]>>,
+}
+
+impl<'a, S: TileSource + ?Sized> TileReader<'a, S> {
+    fn new(source: &'a S, tree_size: u64) -> Self {
+        TileReader {
+            source,
+            tree_size,
+            tiles: HashMap::new(),
+        }
+    }
+
+    /// RFC 6962 §2.1.1 PATH over leaves `[lo, hi)`, appended leaf-first
+    fn audit_path(
+        &mut self,
+        index: u64,
+        lo: u64,
+        hi: u64,
+        proof: &mut Vec<[u8; HASH_SIZE]>,
+    ) -> Result<(), TransparencyError> {
+        if hi - lo <= 1 {
+            return Ok(());
+        }
+        let k = split_point(hi - lo);
+        if index < lo + k {
+            self.audit_path(index, lo, lo + k, proof)?;
+            proof.push(self.range_hash(lo + k, hi)?);
+        } else {
+            self.audit_path(index, lo + k, hi, proof)?;
+            proof.push(self.range_hash(lo, lo + k)?);
+        }
+        Ok(())
+    }
+
+    /// RFC 6962 MTH of leaves `[lo, hi)`, where `lo` is aligned to the
+    /// largest power of two below `hi - lo`
+    fn range_hash(&mut self, lo: u64, hi: u64) -> Result<[u8; HASH_SIZE], TransparencyError> {
+        let n = hi - lo;
+        if n.is_power_of_two() {
+            let height = n.trailing_zeros();
+            return self.node_hash(height, lo >> height);
+        }
+        let k = split_point(n);
+        let left = self.range_hash(lo, lo + k)?;
+        let right = self.range_hash(lo + k, hi)?;
+        Ok(hash_children(&left, &right))
+    }
+
+    /// Hash of the complete subtree at `height` with index `index`
+    fn node_hash(&mut self, height: u32, index: u64) -> Result<[u8; HASH_SIZE], TransparencyError> {
+        let level = (height / TILE_HEIGHT) as u64;
+        let rest = height % TILE_HEIGHT;
+
+        // The node covers 2^rest consecutive hashes of its tile level
+        let first = index << rest;
+        let tile_index = first / TILE_WIDTH;
+        let offset = (first % TILE_WIDTH) as usize;
+        let count = 1usize << rest;
+
+        let tile = self.tile(level, tile_index)?;
+        let hashes = tile.get(offset..offset + count).ok_or_else(|| {
+            TransparencyError::InvalidTile(format!("tile {}/{} is missing node hashes", level, tile_index))
+        })?;
+        Ok(complete_subtree_root(hashes))
+    }
+
+    fn tile(&mut self, level: u64, index: u64) -> Result<&[[u8; HASH_SIZE]], TransparencyError> {
+        if !self.tiles.contains_key(&(level, index)) {
+            let width = tile_width(level, index, self.tree_size);
+            let hashes = self.source.tile(level, index, width)?;
+            if (hashes.len() as u64) < width {
+                return Err(TransparencyError::InvalidTile(format!(
+                    "tile {}/{} has {} hashes, expected {}",
+                    level,
+                    index,
+                    hashes.len(),
+                    width
+                )));
+            }
+            self.tiles.insert((level, index), hashes);
+        }
+        Ok(&self.tiles[&(level, index)])
+    }
+}
+
+/// Largest power of two strictly less than `n` (for `n >= 2`)
+fn split_point(n: u64) -> u64 {
+    1 << (u64::BITS - 1 - (n - 1).leading_zeros())
+}
+
+/// Root of a complete subtree from its 2^k bottom hashes
+fn complete_subtree_root(hashes: &[[u8; HASH_SIZE]]) -> [u8; HASH_SIZE] {
+    NodeHasher::new().subtree_root(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::merkle::{verify_inclusion_proof, MerkleTree};
+
+    /// Tiles of an in-memory tree, built level by level from complete subtrees
+    struct MemoryTiles {
+        levels: Vec<Vec<[u8; HASH_SIZE]>>,
+    }
+
+    impl MemoryTiles {
+        fn new(tree: &MerkleTree) -> Self {
+            let mut levels = vec![(0..tree.size()).map(|i| tree.leaf_hash(i).unwrap()).collect::<Vec<_>>()];
+            loop {
+                let below = levels.last().unwrap();
+                let above: Vec<_> = below
+                    .chunks_exact(TILE_WIDTH as usize)
+                    .map(complete_subtree_root)
+                    .collect();
+                if above.is_empty() {
+                    break;
+                }
+                levels.push(above);
+            }
+            MemoryTiles { levels }
+        }
+    }
+
+    impl TileSource for MemoryTiles {
+        fn tile(&self, level: u64, index: u64, width: u64) -> Result<Vec<[u8; HASH_SIZE]>, TransparencyError> {
+            let start = (index * TILE_WIDTH) as usize;
+            Ok(self.levels[level as usize][start..start + width as usize].to_vec())
+        }
+    }
+
+    #[test]
+    fn test_tile_paths() {
+        assert_eq!(tile_path(0, 0, TILE_WIDTH), "tile/0/000");
+        assert_eq!(tile_path(1, 1234067, TILE_WIDTH), "tile/1/x001/x234/067");
+        assert_eq!(tile_path(0, 1234067, 8), "tile/0/x001/x234/067.p/8");
+        assert_eq!(entry_bundle_path(1000, 3), "tile/entries/x001/000.p/3");
+    }
+
+    #[test]
+    fn test_tile_width() {
+        assert_eq!(tile_width(0, 0, 1000), 256);
+        assert_eq!(tile_width(0, 3, 1000), 232);
+        assert_eq!(tile_width(0, 4, 1000), 0);
+        assert_eq!(tile_width(1, 0, 1000), 3);
+        assert_eq!(tile_width(2, 0, 1000), 0);
+    }
+
+    #[test]
+    fn test_parse_entry_bundle() {
+        let data = [0, 2, b'a', b'b', 0, 0, 0, 1, b'c'];
+        assert_eq!(
+            parse_entry_bundle(&data).unwrap(),
+            vec![b"ab".to_vec(), vec![], b"c".to_vec()]
+        );
+        assert!(parse_entry_bundle(&[0, 3, b'a']).is_err());
+        assert!(parse_tile(&[0; 33]).is_err());
+    }
+
+    #[test]
+    fn test_inclusion_proof_from_tiles() {
+        let tree = MerkleTree::from_leaves((0..65_537u32).map(|i| i.to_be_bytes()));
+        let tiles = MemoryTiles::new(&tree);
+
+        for size in [1u64, 2, 255, 256, 257, 65_537] {
+            let root = tree.root_at(size).unwrap();
+            assert_eq!(root_from_tiles(&tiles, size).unwrap(), root);
+
+            for index in [0, size / 3, size - 1] {
+                let proof = inclusion_proof_from_tiles(&tiles, index, size).unwrap();
+                assert_eq!(proof, tree.inclusion_proof(index, size).unwrap());
+
+                let proof: Vec<Vec<u8>> = proof.iter().map(|hash| hash.to_vec()).collect();
+                verify_inclusion_proof(&tree.leaf_hash(index).unwrap(), index, size, &proof, &root).unwrap();
+            }
+        }
+
+        assert!(matches!(
+            inclusion_proof_from_tiles(&tiles, 5, 5),
+            Err(TransparencyError::IndexOutOfRange { .. })
+        ));
+    }
+}
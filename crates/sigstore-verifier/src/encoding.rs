@@ -0,0 +1,153 @@
+//! Hex and base64 helpers with a fixed output format
+//!
+//! Encoders always produce lowercase hex and padded standard base64 (RFC 4648 §4),
+//! so values written by this crate compare equal as strings across versions.
+//! Decoders accept either hex case but only padded standard base64, matching
+//! what Sigstore bundles contain.
+//!
+//! [`base64_decode_bounded`] is the decoder `parser::bundle` uses in zkVM
+//! guests (`target_os = "zkvm"`). It computes the exact output length from the
+//! input, allocates it once and decodes four symbols at a time with a lookup
+//! table, accepting and rejecting exactly the same inputs as `base64_decode`.
+//! No guest cycle counts have been measured for it, so it is not shown to be
+//! faster than the general-purpose engine in the guest.
+
+use base64::prelude::*;
+
+/// Encode bytes as lowercase hex
+pub fn hex_encode(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
+
+/// Decode hex in either case
+pub fn hex_decode(s: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    hex::decode(s)
+}
+
+/// Encode bytes as padded standard base64
+pub fn base64_encode(bytes: &[u8]) -> String {
+    BASE64_STANDARD.encode(bytes)
+}
+
+/// Decode padded standard base64
+pub fn base64_decode(s: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    BASE64_STANDARD.decode(s)
+}
+
+/// Marks bytes outside the standard alphabet in [`DECODE_TABLE`]
+const INVALID: u8 = 0xff;
+
+const DECODE_TABLE: [u8; 256] = {
+    let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Decode padded standard base64 with a single, exactly sized allocation
+///
+/// Accepts the same inputs as [`base64_decode`]: length a multiple of four,
+/// at most two `=` at the end and no set bits after the last encoded byte.
+///
+/// # Errors
+///
+/// Returns `InvalidByte` for a byte outside the alphabet (including misplaced
+/// padding), `InvalidPadding` for a length that is not a multiple of four and
+/// `InvalidLastSymbol` for non-canonical trailing bits.
+pub fn base64_decode_bounded(s: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    let input = s.as_bytes();
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !input.len().is_multiple_of(4) {
+        return Err(base64::DecodeError::InvalidPadding);
+    }
+
+    let padding = input.iter().rev().take(2).take_while(|&&byte| byte == b'=').count();
+    let mut output = Vec::with_capacity(input.len() / 4 * 3 - padding);
+    let (body, last) = input.split_at(input.len() - 4);
+    for (index, quad) in body.chunks_exact(4).enumerate() {
+        let bits = decode_symbols(quad, index * 4)?;
+        output.extend_from_slice(&[(bits >> 16) as u8, (bits >> 8) as u8, bits as u8]);
+    }
+
+    let offset = body.len();
+    let symbols = &last[..4 - padding];
+    // Pad with 'A' (zero bits) so the final quantum decodes like the others
+    let mut quad = [b'A'; 4];
+    quad[..symbols.len()].copy_from_slice(symbols);
+    let bits = decode_symbols(&quad, offset)?;
+    let (bytes, unused_bits) = match padding {
+        0 => (3, 0),
+        1 => (2, 0xff),
+        _ => (1, 0xffff),
+    };
+    if bits & unused_bits != 0 {
+        let last_symbol = symbols.len() - 1;
+        return Err(base64::DecodeError::InvalidLastSymbol(offset + last_symbol, symbols[last_symbol]));
+    }
+    output.extend_from_slice(&[(bits >> 16) as u8, (bits >> 8) as u8, bits as u8][..bytes]);
+    Ok(output)
+}
+
+/// Decode four symbols starting at `offset` in the input into 24 bits
+fn decode_symbols(quad: &[u8], offset: usize) -> Result<u32, base64::DecodeError> {
+    let mut bits = 0u32;
+    for (i, &symbol) in quad.iter().enumerate() {
+        let value = DECODE_TABLE[symbol as usize];
+        if value == INVALID {
+            return Err(base64::DecodeError::InvalidByte(offset + i, symbol));
+        }
+        bits = (bits << 6) | value as u32;
+    }
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_is_lowercase() {
+        assert_eq!(hex_encode(&[0xAB, 0x01]), "ab01");
+        assert_eq!(hex_decode("AB01").unwrap(), vec![0xAB, 0x01]);
+        assert!(hex_decode("abc").is_err());
+    }
+
+    #[test]
+    fn test_base64_is_padded_standard() {
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "+/8=");
+        assert_eq!(base64_decode("+/8=").unwrap(), vec![0xfb, 0xff]);
+        assert!(base64_decode("-_8=").is_err());
+        assert!(base64_decode("+/8").is_err());
+    }
+
+    #[test]
+    fn test_bounded_base64_matches_standard() {
+        // Every length mod 3, with all byte values
+        for len in 0..=64usize {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 97 + len) as u8).collect();
+            let encoded = base64_encode(&bytes);
+            let decoded = base64_decode_bounded(&encoded).unwrap();
+            assert_eq!(decoded, bytes);
+            assert_eq!(decoded.capacity(), len);
+        }
+
+        for input in [
+            "+/8", "+/8==", "+/9=", "+/8=AAAA", "AA=A", "A===", "====", "-_8=", "AAA\n", "AB==", "AAB=", "Zm9v YmFy",
+        ] {
+            assert_eq!(
+                base64_decode_bounded(input).is_ok(),
+                base64_decode(input).is_ok(),
+                "{:?}",
+                input
+            );
+        }
+        assert!(matches!(base64_decode_bounded("+/9="), Err(base64::DecodeError::InvalidLastSymbol(2, b'9'))));
+        assert!(matches!(base64_decode_bounded("AA=A"), Err(base64::DecodeError::InvalidByte(2, b'='))));
+    }
+}
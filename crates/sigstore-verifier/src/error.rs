@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum VerificationError {
     #[error("Bundle parsing error: {0}")]
     BundleParse(#[from] serde_json::Error),
@@ -17,24 +18,146 @@ pub enum VerificationError {
     #[error("Transparency log error: {0}")]
     Transparency(#[from] TransparencyError),
 
+    #[error("Policy error: {0}")]
+    Policy(#[from] PolicyError),
+
     #[error("Subject digest is zero")]
     ZeroSubjectDigest,
 
     #[error("Subject digest mismatch: expected {expected}, got {actual}")]
     SubjectDigestMismatch { expected: String, actual: String },
 
-    #[cfg(feature = "fetcher")]
+    #[error("No subject name matches {pattern} (subjects: {})", names.join(", "))]
+    SubjectNameMismatch { pattern: String, names: Vec<String> },
+
     #[error("HTTP request failed: {0}")]
-    HttpError(#[from] reqwest::Error),
+    HttpError(String),
 
     #[error("Base64 decode error: {0}")]
     Base64Decode(#[from] base64::DecodeError),
 
     #[error("Invalid bundle format: {0}")]
     InvalidBundleFormat(String),
+
+    #[error("Input exceeds the {limit} limit: {actual} > {max}")]
+    LimitExceeded { limit: &'static str, max: usize, actual: usize },
+
+    #[error("Verification interrupted: {0}")]
+    Interrupted(#[from] Interrupted),
+
+    #[error("Statement does not match the in-toto schema: {}", .0.join("; "))]
+    StatementSchema(Vec<String>),
+
+    #[error("Audit log error: {0}")]
+    Audit(#[from] AuditError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Signing error: {0}")]
+    Signer(#[from] SignerError),
+
+    #[error("Bundle conversion error: {0}")]
+    Conversion(#[from] ConversionError),
+
+    #[error("Redaction error: {0}")]
+    Redaction(#[from] RedactionError),
+}
+
+impl VerificationError {
+    /// Stable machine-readable code for the error category, e.g. for audit records
+    pub fn code(&self) -> &'static str {
+        match self {
+            VerificationError::BundleParse(_) => "bundle_parse",
+            VerificationError::Certificate(_) => "certificate",
+            VerificationError::Signature(_) => "signature",
+            VerificationError::Timestamp(_) => "timestamp",
+            VerificationError::Transparency(_) => "transparency",
+            VerificationError::Policy(_) => "policy",
+            VerificationError::ZeroSubjectDigest => "zero_subject_digest",
+            VerificationError::SubjectDigestMismatch { .. } => "subject_digest_mismatch",
+            VerificationError::SubjectNameMismatch { .. } => "subject_name_mismatch",
+            VerificationError::HttpError(_) => "http",
+            VerificationError::Base64Decode(_) => "base64_decode",
+            VerificationError::InvalidBundleFormat(_) => "invalid_bundle_format",
+            VerificationError::LimitExceeded { .. } => "limit_exceeded",
+            VerificationError::Interrupted(_) => "interrupted",
+            VerificationError::StatementSchema(_) => "statement_schema",
+            VerificationError::Audit(_) => "audit",
+            VerificationError::Io(_) => "io",
+            VerificationError::Signer(_) => "signer",
+            VerificationError::Conversion(_) => "conversion",
+            VerificationError::Redaction(_) => "redaction",
+        }
+    }
+}
+
+/// Why a `VerificationContext` stopped a verification, with the stage it stopped at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum Interrupted {
+    #[error("cancelled at {0}")]
+    Cancelled(&'static str),
+
+    #[error("deadline exceeded at {0}")]
+    DeadlineExceeded(&'static str),
+}
+
+/// Errors of the keyless signer in `sigstore-fetcher`
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SignerError {
+    #[error("Invalid identity token: {0}")]
+    InvalidToken(String),
+
+    #[error("Fulcio certificate request failed: {0}")]
+    CertificateRequest(String),
+
+    #[error("Signing failed: {0}")]
+    Signing(String),
+}
+
+/// Why a bundle cannot be converted to another bundle version without losing data
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ConversionError {
+    #[error("Unsupported bundle media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("Bundle has no certificate")]
+    MissingCertificate,
+
+    #[error("Transparency log entry {log_index} has no inclusion proof, which bundle {version} requires")]
+    MissingInclusionProof { log_index: String, version: &'static str },
+
+    #[error("Transparency log entry {log_index} has no checkpoint, which bundle {version} requires")]
+    MissingCheckpoint { log_index: String, version: &'static str },
+
+    #[error("Transparency log entry {log_index} has no inclusion promise, which bundle {version} requires")]
+    MissingInclusionPromise { log_index: String, version: &'static str },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum RedactionError {
+    #[error("Invalid JSON Pointer: {0}")]
+    InvalidPointer(String),
+
+    #[error("Predicate has no field at {0}")]
+    FieldNotFound(String),
+
+    #[error("Invalid redaction marker: {0}")]
+    InvalidMarker(String),
+
+    #[error("Invalid redactable payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Predicate has redacted fields, which the verification options do not allow")]
+    RedactedPredicate,
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum CertificateError {
     #[error("Failed to parse certificate: {0}")]
     ParseError(String),
@@ -63,9 +186,42 @@ pub enum CertificateError {
 
     #[error("Self-signed certificate verification failed")]
     SelfSignedVerificationFailed,
+
+    #[error("Certificate chain has {depth} certificates, policy allows at most {max}")]
+    ChainTooDeep { depth: usize, max: usize },
+
+    #[error("Certificate uses weak signature algorithm: {0}")]
+    WeakSignatureAlgorithm(String),
+
+    #[error("Certificate signature algorithm not allowed: {0}")]
+    DisallowedSignatureAlgorithm(String),
+
+    #[error("Unsupported certificate signature algorithm: {0}")]
+    UnsupportedSignatureAlgorithm(String),
+
+    #[error("Certificate signature algorithm {outer} does not match TBSCertificate algorithm {tbs}")]
+    SignatureAlgorithmMismatch { outer: String, tbs: String },
+
+    #[cfg(feature = "revocation")]
+    #[error("Certificate revoked: {0}")]
+    Revoked(String),
+
+    #[cfg(feature = "revocation")]
+    #[error("Invalid certificate revocation list: {0}")]
+    InvalidCrl(String),
+
+    #[cfg(feature = "revocation")]
+    #[error("Failed to fetch certificate revocation list: {0}")]
+    CrlFetch(String),
+
+    /// Chain verification stopped by a `VerificationContext`; the verifier
+    /// reports it as `VerificationError::Interrupted`
+    #[error("Verification interrupted: {0}")]
+    Interrupted(#[from] Interrupted),
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
+#[non_exhaustive]
 pub enum SignatureError {
     #[error("Unsupported signature algorithm: {0}")]
     UnsupportedAlgorithm(String),
@@ -76,6 +232,9 @@ pub enum SignatureError {
     #[error("Signature verification failed")]
     InvalidSignature,
 
+    #[error("Signature algorithm not allowed: {0}")]
+    DisallowedAlgorithm(String),
+
     #[error("Failed to parse public key: {0}")]
     PublicKeyParse(String),
 
@@ -84,6 +243,7 @@ pub enum SignatureError {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum TimestampError {
     #[error("No timestamp found (neither RFC3161 nor integrated time)")]
     NoTimestamp,
@@ -100,6 +260,12 @@ pub enum TimestampError {
     #[error("RFC3161 timestamp signature verification failed")]
     Rfc3161SignatureInvalid,
 
+    #[error("Timestamp request rejected by TSA (status {status}): {message}")]
+    Rfc3161Rejected { status: u64, message: String },
+
+    #[error("Timestamp nonce does not match the request")]
+    NonceMismatch,
+
     #[error("Message imprint mismatch: expected {expected}, got {actual}")]
     MessageImprintMismatch { expected: String, actual: String },
 
@@ -114,9 +280,13 @@ pub enum TimestampError {
 
     #[error("Invalid integrated time")]
     InvalidIntegratedTime,
+
+    #[error("Bundle was signed at {signing_time}, after the verification time {verify_at}")]
+    SignedAfterVerificationTime { signing_time: String, verify_at: String },
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum TransparencyError {
     #[error("No Rekor entry found in bundle")]
     NoRekorEntry,
@@ -127,6 +297,219 @@ pub enum TransparencyError {
     #[error("Merkle tree inclusion proof verification failed")]
     InclusionProofFailed,
 
+    #[error("Invalid Merkle hash size: expected 32 bytes, got {0}")]
+    InvalidHashSize(usize),
+
+    #[error("Invalid inclusion proof length: expected {expected} hashes, got {actual}")]
+    InvalidProofLength { expected: usize, actual: usize },
+
+    #[error("Merkle consistency proof verification failed")]
+    ConsistencyProofFailed,
+
+    #[error("Leaf index {index} out of range for tree of size {size}")]
+    IndexOutOfRange { index: u64, size: u64 },
+
+    #[error("Tree size {requested} out of range (tree has {available} leaves)")]
+    TreeSizeOutOfRange { requested: u64, available: u64 },
+
     #[error("Signed entry timestamp verification failed")]
     SignedEntryTimestampInvalid,
+
+    #[error("Unsupported Rekor entry kind: {kind} {version}")]
+    UnsupportedEntryKind { kind: String, version: String },
+
+    #[error("Entry kindVersion {declared} does not match its body ({body})")]
+    EntryKindMismatch { declared: String, body: String },
+
+    #[error("Invalid Rekor entry body: {0}")]
+    InvalidEntryBody(String),
+
+    #[error("Rekor entry commits to a different attestation than the bundle: {0}")]
+    EntryBodyMismatch(String),
+
+    #[error("Transparency log entry has no log ID")]
+    MissingLogId,
+
+    #[error("Invalid transparency log ID: {0}")]
+    InvalidLogId(String),
+
+    #[error("Transparency log {0} is not trusted")]
+    UnknownLog(String),
+
+    #[error("Rekor v2 entry has no inclusion proof")]
+    MissingInclusionProof,
+
+    #[error("Rekor v2 inclusion proof has no checkpoint")]
+    MissingCheckpoint,
+
+    #[error("Rekor entry has no inclusion promise")]
+    MissingInclusionPromise,
+
+    #[error("No transparency log keys to verify the entry against")]
+    MissingLogKeys,
+
+    #[error("Invalid checkpoint: {0}")]
+    InvalidCheckpoint(String),
+
+    #[error("Checkpoint does not match the inclusion proof: {0}")]
+    CheckpointMismatch(String),
+
+    #[error("Checkpoint has no signature from {0}")]
+    MissingCheckpointSignature(String),
+
+    #[error("Checkpoint signature verification failed")]
+    CheckpointSignatureInvalid,
+
+    #[error("Invalid tile: {0}")]
+    InvalidTile(String),
+
+    #[error("Failed to fetch from transparency log: {0}")]
+    LogFetch(String),
+
+    #[error("Transparency log rejected the entry: {0}")]
+    LogSubmit(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum TimeError {
+    #[error("Invalid RFC 3339 date-time: {0}")]
+    InvalidFormat(String),
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DigestError {
+    #[error("Digest '{0}' is missing an '<algorithm>:' prefix")]
+    MissingAlgorithm(String),
+
+    #[error("Unknown digest algorithm: {0}")]
+    UnknownAlgorithm(String),
+
+    #[error("Invalid digest hex: {0}")]
+    InvalidHex(String),
+
+    #[error("Invalid digest base64: {0}")]
+    InvalidBase64(String),
+
+    #[error("Cannot infer the algorithm of a {0}-byte digest")]
+    UnknownLength(usize),
+
+    #[error("Invalid {algorithm} digest length: expected {expected} bytes, got {actual}")]
+    InvalidLength {
+        algorithm: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl From<std::convert::Infallible> for DigestError {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PolicyError {
+    #[error("Identity field '{0}' required by policy is missing from certificate")]
+    MissingField(&'static str),
+
+    #[error("Identity field '{field}' mismatch: expected '{expected}', got '{actual}'")]
+    Mismatch {
+        field: &'static str,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Predicate type '{0}' is not allowed by policy")]
+    PredicateTypeNotAllowed(String),
+
+    #[error("Timestamp mechanism mismatch: policy requires {expected}, bundle uses {actual}")]
+    TimestampMechanism {
+        expected: &'static str,
+        actual: &'static str,
+    },
+
+    #[error("Fulcio root certificate {0} is not pinned by policy")]
+    UnpinnedRoot(String),
+
+    #[error("No CA certificate of the Fulcio chain (root {0}) is pinned by policy")]
+    UnpinnedChain(String),
+
+    #[error("TSA policy {0} is not allowed by policy")]
+    TsaPolicyNotAllowed(String),
+
+    #[error("Timestamp accuracy rejected by policy: {0}")]
+    TimestampAccuracy(String),
+
+    #[error("Timestamp ordering mismatch: policy requires ordering={expected}")]
+    TimestampOrdering { expected: bool },
+
+    #[error("Timestamp nonce rejected by policy: {0}")]
+    TimestampNonce(String),
+
+    #[error("Source provenance rejected by policy: {0}")]
+    SourcePolicy(String),
+
+    #[error("Build dependencies rejected by policy: {0}")]
+    DependencyPolicy(String),
+
+    #[error("Policy requires {required}, evidence supports {achieved}: {unmet}")]
+    SlsaLevel {
+        required: &'static str,
+        achieved: &'static str,
+        unmet: String,
+    },
+
+    #[error("Both bundles were signed by the same builder: {0}")]
+    SameBuilder(String),
+
+    #[error("Denied by policy: {0}")]
+    Denied(String),
+
+    #[error("Policy evaluation failed: {0}")]
+    Evaluation(String),
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PolicyFileError {
+    #[error("Failed to read policy file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid policy file: {0}")]
+    Parse(String),
+
+    #[error("Unsupported policy file version {found} (expected {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CborError {
+    #[error("Failed to encode CBOR: {0}")]
+    Encode(String),
+
+    #[error("Failed to decode CBOR: {0}")]
+    Decode(String),
+
+    #[error("COSE signature verification failed: {0}")]
+    CoseSignature(String),
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum AuditError {
+    #[error("Audit log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialize audit record: {0}")]
+    Serialization(String),
+
+    #[error("Malformed audit record at line {line}: {reason}")]
+    MalformedRecord { line: usize, reason: String },
+
+    #[error("Audit hash chain broken at sequence {sequence}")]
+    ChainBroken { sequence: u64 },
 }
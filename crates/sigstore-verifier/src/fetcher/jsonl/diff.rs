@@ -0,0 +1,228 @@
+//! Changes between two versions of the trusted roots
+//!
+//! A refreshed `trusted_root.jsonl` decides which CAs, TSAs and logs the
+//! verifier accepts from then on, so a change to it is worth a look before it
+//! is trusted. [`TrustedRootSet::diff`] lists the entries that appeared,
+//! disappeared or had their validity period changed, and flags a new
+//! certificate chain or key for a service that was already trusted as a
+//! rotation, the usual shape of a legitimate update (the old entry gains an
+//! `end`, a new one is added for the same URI).
+
+use std::collections::HashMap;
+use std::fmt;
+
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::hash::sha256;
+use crate::fetcher::jsonl::types::{CertChain, TrustedRootSet, ValidityPeriod};
+use crate::types::digest::Digest;
+
+/// How an entry of the trusted roots changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustRootChangeKind {
+    /// An entry for a URI that was not trusted before
+    Added,
+    /// An entry that is no longer present
+    Removed,
+    /// A new certificate chain or key for a URI that was already trusted
+    Rotated,
+    /// The same certificate chain or key with a different validity period
+    ValidityChanged,
+}
+
+/// One changed CA, TSA or log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustRootChange {
+    pub kind: TrustRootChangeKind,
+    /// CA or TSA URI, or log base URL
+    pub uri: String,
+    /// SHA-256 of the certificate chain DER, or of the log's public key
+    pub fingerprint: Digest,
+    /// Validity period before the change, `None` for added entries
+    pub old_validity: Option<ValidityPeriod>,
+    /// Validity period after the change, `None` for removed entries
+    pub new_validity: Option<ValidityPeriod>,
+}
+
+/// Changes between two trusted root sets, per kind of entry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustRootDiff {
+    pub certificate_authorities: Vec<TrustRootChange>,
+    pub timestamp_authorities: Vec<TrustRootChange>,
+    pub tlogs: Vec<TrustRootChange>,
+    pub ctlogs: Vec<TrustRootChange>,
+}
+
+impl TrustRootDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes().next().is_none()
+    }
+
+    /// Every change, with the kind of entry it concerns (`ca`, `tsa`, `tlog`, `ctlog`)
+    pub fn changes(&self) -> impl Iterator<Item = (&'static str, &TrustRootChange)> + '_ {
+        [
+            ("ca", &self.certificate_authorities),
+            ("tsa", &self.timestamp_authorities),
+            ("tlog", &self.tlogs),
+            ("ctlog", &self.ctlogs),
+        ]
+        .into_iter()
+        .flat_map(|(section, changes)| changes.iter().map(move |change| (section, change)))
+    }
+
+    /// Whether any entry was dropped outright, rather than retired by ending its validity
+    pub fn has_removals(&self) -> bool {
+        self.changes().any(|(_, change)| change.kind == TrustRootChangeKind::Removed)
+    }
+}
+
+impl fmt::Display for TrustRootDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+        let changes: Vec<String> = self
+            .changes()
+            .map(|(section, change)| {
+                let kind = match change.kind {
+                    TrustRootChangeKind::Added => "added",
+                    TrustRootChangeKind::Removed => "removed",
+                    TrustRootChangeKind::Rotated => "rotated",
+                    TrustRootChangeKind::ValidityChanged => "validity changed",
+                };
+                format!("{} {} {} ({})", section, change.uri, kind, change.fingerprint)
+            })
+            .collect();
+        write!(f, "{}", changes.join("; "))
+    }
+}
+
+/// An entry as compared across versions
+struct Entry<'a> {
+    uri: &'a str,
+    fingerprint: Digest,
+    validity: Option<&'a ValidityPeriod>,
+}
+
+fn chain_fingerprint(chain: &CertChain) -> Digest {
+    let der: Vec<u8> = chain
+        .certificates
+        .iter()
+        .flat_map(|certificate| {
+            BASE64_STANDARD
+                .decode(&certificate.raw_bytes)
+                .unwrap_or_else(|_| certificate.raw_bytes.as_bytes().to_vec())
+        })
+        .collect();
+    Digest::sha256(sha256(&der))
+}
+
+fn authority_entries(set: &TrustedRootSet, tsa: bool) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    for root in &set.roots {
+        if tsa {
+            entries.extend(root.timestamp_authorities.iter().map(|authority| Entry {
+                uri: &authority.uri,
+                fingerprint: chain_fingerprint(&authority.cert_chain),
+                validity: Some(&authority.valid_for),
+            }));
+        } else {
+            entries.extend(root.certificate_authorities.iter().map(|authority| Entry {
+                uri: &authority.uri,
+                fingerprint: chain_fingerprint(&authority.cert_chain),
+                validity: Some(&authority.valid_for),
+            }));
+        }
+    }
+    entries
+}
+
+fn log_entries(set: &TrustedRootSet, ctlogs: bool) -> Vec<Entry<'_>> {
+    set.roots
+        .iter()
+        .flat_map(|root| if ctlogs { &root.ctlogs } else { &root.tlogs })
+        .map(|log| {
+            let key = log.public_key.as_ref();
+            let key_bytes = key
+                .and_then(|key| key.raw_bytes.as_deref())
+                .map(|raw| BASE64_STANDARD.decode(raw).unwrap_or_else(|_| raw.as_bytes().to_vec()))
+                .unwrap_or_else(|| log.base_url.as_bytes().to_vec());
+            Entry {
+                uri: &log.base_url,
+                fingerprint: Digest::sha256(sha256(&key_bytes)),
+                validity: key.and_then(|key| key.valid_for.as_ref()),
+            }
+        })
+        .collect()
+}
+
+fn diff_entries(old: &[Entry<'_>], new: &[Entry<'_>]) -> Vec<TrustRootChange> {
+    let old_by_fingerprint: HashMap<&Digest, &Entry> = old.iter().map(|e| (&e.fingerprint, e)).collect();
+    let new_by_fingerprint: HashMap<&Digest, &Entry> = new.iter().map(|e| (&e.fingerprint, e)).collect();
+
+    let mut changes = Vec::new();
+    for entry in new {
+        match old_by_fingerprint.get(&entry.fingerprint) {
+            Some(previous) if previous.validity == entry.validity => {}
+            Some(previous) => changes.push(TrustRootChange {
+                kind: TrustRootChangeKind::ValidityChanged,
+                uri: entry.uri.to_string(),
+                fingerprint: entry.fingerprint.clone(),
+                old_validity: previous.validity.cloned(),
+                new_validity: entry.validity.cloned(),
+            }),
+            None => {
+                let known_uri = old.iter().any(|previous| previous.uri == entry.uri);
+                changes.push(TrustRootChange {
+                    kind: if known_uri {
+                        TrustRootChangeKind::Rotated
+                    } else {
+                        TrustRootChangeKind::Added
+                    },
+                    uri: entry.uri.to_string(),
+                    fingerprint: entry.fingerprint.clone(),
+                    old_validity: None,
+                    new_validity: entry.validity.cloned(),
+                });
+            }
+        }
+    }
+    for entry in old {
+        if !new_by_fingerprint.contains_key(&entry.fingerprint) {
+            changes.push(TrustRootChange {
+                kind: TrustRootChangeKind::Removed,
+                uri: entry.uri.to_string(),
+                fingerprint: entry.fingerprint.clone(),
+                old_validity: entry.validity.cloned(),
+                new_validity: None,
+            });
+        }
+    }
+    changes
+}
+
+impl TrustedRootSet {
+    /// Compare two versions of the trusted roots
+    ///
+    /// Entries are matched across versions by fingerprint, so moving an entry
+    /// between the TrustedRoots of the set is not a change.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let diff = TrustedRootSet::diff(&cached, &TrustedRootSet::from_jsonl(&fetched)?);
+    /// if diff.has_removals() {
+    ///     alert(&format!("trusted root changed: {}", diff));
+    /// }
+    /// ```
+    pub fn diff(old: &TrustedRootSet, new: &TrustedRootSet) -> TrustRootDiff {
+        TrustRootDiff {
+            certificate_authorities: diff_entries(&authority_entries(old, false), &authority_entries(new, false)),
+            timestamp_authorities: diff_entries(&authority_entries(old, true), &authority_entries(new, true)),
+            tlogs: diff_entries(&log_entries(old, false), &log_entries(new, false)),
+            ctlogs: diff_entries(&log_entries(old, true), &log_entries(new, true)),
+        }
+    }
+}
@@ -1,15 +1,20 @@
 use base64::prelude::*;
-use chrono::DateTime;
-use crate::fetcher::jsonl::types::{CertChain as JsonlCertChain, TrustedRoot};
+use crate::fetcher::jsonl::types::{CertChain as JsonlCertChain, TransparencyLogInstance, TrustedRoot, ValidityPeriod};
+use crate::error::{TransparencyError, VerificationError};
+use crate::types::bundle::SigstoreBundle;
 use crate::types::certificate::{CertificateChain, FulcioInstance};
-use crate::VerificationError;
+use crate::types::digest::{Digest, DigestAlgorithm};
+use crate::types::environment::SigningAuthority;
+use crate::types::result::TransparencyLogKey;
+use crate::types::time::Timestamp;
+use crate::verifier::transparency::verify_log_ids;
 
 /// Parse RFC3339 timestamp string to Unix timestamp in seconds.
 fn parse_rfc3339_timestamp(s: &str) -> Result<i64, VerificationError> {
-    let dt = DateTime::parse_from_rfc3339(s).map_err(|e| {
+    let time = Timestamp::parse_rfc3339(s).map_err(|e| {
         VerificationError::InvalidBundleFormat(format!("Invalid RFC3339 timestamp: {}", e))
     })?;
-    Ok(dt.timestamp())
+    Ok(time.timestamp())
 }
 
 /// Load and parse Sigstore TrustedRoot bundles from JSONL format.
@@ -67,43 +72,10 @@ pub fn select_certificate_authority(
     timestamp: i64,
 ) -> Result<CertificateChain, VerificationError> {
     let expected_uri = instance.trust_bundle_url();
-    let mut best_match: Option<(&JsonlCertChain, i64)> = None;
-
-    for root in roots {
-        for ca in &root.certificate_authorities {
-            // Match by URI (primary method)
-            if ca.uri.contains(expected_uri.trim_start_matches("https://").split('/').next().unwrap()) {
-                // Validate timestamp falls within validity period
-                if let Some(start_str) = &ca.valid_for.start {
-                    let start = parse_rfc3339_timestamp(start_str)?;
-                    if timestamp < start {
-                        continue; // Not yet valid
-                    }
+    let expected_domain = expected_uri.trim_start_matches("https://").split('/').next().unwrap();
 
-                    // Check end time if present
-                    if let Some(end_str) = &ca.valid_for.end {
-                        let end = parse_rfc3339_timestamp(end_str)?;
-                        if timestamp > end {
-                            continue; // Expired
-                        }
-                    }
-                    // No end time means ongoing/current certificate
-
-                    // Keep track of the best match (most recent start date)
-                    match best_match {
-                        None => best_match = Some((&ca.cert_chain, start)),
-                        Some((_, best_start)) if start > best_start => {
-                            best_match = Some((&ca.cert_chain, start));
-                        }
-                        _ => {} // Keep existing best match
-                    }
-                }
-            }
-        }
-    }
-
-    match best_match {
-        Some((cert_chain, _)) => extract_cert_chain_from_authority(cert_chain),
+    match select_latest_valid(certificate_authorities(roots), expected_domain, timestamp)? {
+        Some(cert_chain) => extract_cert_chain_from_authority(cert_chain),
         None => Err(VerificationError::InvalidBundleFormat(format!(
             "No valid certificate authority found for instance {:?} at timestamp {}",
             instance, timestamp
@@ -134,48 +106,243 @@ pub fn select_timestamp_authority(
         FulcioInstance::PublicGood => "timestamp.sigstore.dev",
     };
 
+    match select_latest_valid(timestamp_authorities(roots), expected_tsa_domain, timestamp)? {
+        Some(cert_chain) => extract_tsa_cert_chain_from_authority(cert_chain),
+        None => Err(VerificationError::InvalidBundleFormat(format!(
+            "No valid timestamp authority found for instance {:?} at timestamp {}",
+            instance, timestamp
+        ))),
+    }
+}
+
+/// Select the certificate authority of a signing authority from trust bundles.
+/// Matches CAs by the authority's Fulcio domain, otherwise selects like
+/// `select_certificate_authority`.
+///
+/// # Arguments
+/// * `roots` - Parsed trust root bundles
+/// * `authority` - Signing authority of the bundle's environment
+/// * `timestamp` - Signature timestamp in Unix seconds
+///
+/// # Returns
+/// Certificate chain for the matching authority
+pub fn select_certificate_authority_for(
+    roots: &[TrustedRoot],
+    authority: &SigningAuthority,
+    timestamp: i64,
+) -> Result<CertificateChain, VerificationError> {
+    match select_latest_valid(certificate_authorities(roots), authority.fulcio_domain(), timestamp)? {
+        Some(cert_chain) => extract_cert_chain_from_authority(cert_chain),
+        None => Err(VerificationError::InvalidBundleFormat(format!(
+            "No valid certificate authority found for {} at timestamp {}",
+            authority.fulcio_url, timestamp
+        ))),
+    }
+}
+
+/// Select the timestamp authority of a signing authority from trust bundles.
+/// Matches TSAs by the authority's TSA domain, otherwise selects like
+/// `select_timestamp_authority`.
+///
+/// # Arguments
+/// * `roots` - Parsed trust root bundles
+/// * `authority` - Signing authority of the bundle's environment
+/// * `timestamp` - Signature timestamp in Unix seconds
+///
+/// # Returns
+/// Certificate chain for the matching timestamp authority
+pub fn select_timestamp_authority_for(
+    roots: &[TrustedRoot],
+    authority: &SigningAuthority,
+    timestamp: i64,
+) -> Result<CertificateChain, VerificationError> {
+    let tsa_domain = authority.tsa_domain().ok_or_else(|| {
+        VerificationError::InvalidBundleFormat(format!("Signing authority {} has no TSA", authority.name))
+    })?;
+
+    match select_latest_valid(timestamp_authorities(roots), tsa_domain, timestamp)? {
+        Some(cert_chain) => extract_tsa_cert_chain_from_authority(cert_chain),
+        None => Err(VerificationError::InvalidBundleFormat(format!(
+            "No valid timestamp authority found for {} at timestamp {}",
+            tsa_domain, timestamp
+        ))),
+    }
+}
+
+type AuthorityEntry<'a> = (&'a str, &'a ValidityPeriod, &'a JsonlCertChain);
+
+fn certificate_authorities(roots: &[TrustedRoot]) -> impl Iterator<Item = AuthorityEntry<'_>> {
+    roots
+        .iter()
+        .flat_map(|root| &root.certificate_authorities)
+        .map(|ca| (ca.uri.as_str(), &ca.valid_for, &ca.cert_chain))
+}
+
+fn timestamp_authorities(roots: &[TrustedRoot]) -> impl Iterator<Item = AuthorityEntry<'_>> {
+    roots
+        .iter()
+        .flat_map(|root| &root.timestamp_authorities)
+        .map(|tsa| (tsa.uri.as_str(), &tsa.valid_for, &tsa.cert_chain))
+}
+
+/// Among the authorities whose URI contains `domain` and that were valid at
+/// `timestamp`, pick the one with the latest start date
+fn select_latest_valid<'a>(
+    authorities: impl Iterator<Item = AuthorityEntry<'a>>,
+    domain: &str,
+    timestamp: i64,
+) -> Result<Option<&'a JsonlCertChain>, VerificationError> {
     let mut best_match: Option<(&JsonlCertChain, i64)> = None;
 
+    for (uri, valid_for, cert_chain) in authorities {
+        // Match by URI
+        if !uri.contains(domain) {
+            continue;
+        }
+
+        // Validate timestamp falls within validity period
+        let Some(start_str) = &valid_for.start else {
+            continue;
+        };
+        let start = parse_rfc3339_timestamp(start_str)?;
+        if timestamp < start {
+            continue; // Not yet valid
+        }
+
+        // Check end time if present
+        if let Some(end_str) = &valid_for.end {
+            let end = parse_rfc3339_timestamp(end_str)?;
+            if timestamp > end {
+                continue; // Expired
+            }
+        }
+        // No end time means ongoing/current certificate
+
+        // Keep track of the best match (most recent start date)
+        match best_match {
+            None => best_match = Some((cert_chain, start)),
+            Some((_, best_start)) if start > best_start => {
+                best_match = Some((cert_chain, start));
+            }
+            _ => {} // Keep existing best match
+        }
+    }
+
+    Ok(best_match.map(|(cert_chain, _)| cert_chain))
+}
+
+/// Collect the log IDs of the Rekor transparency logs in the trust bundles whose
+/// public key was valid at the given timestamp.
+///
+/// # Arguments
+/// * `roots` - Parsed trust root bundles
+/// * `timestamp` - Signature timestamp in Unix seconds
+///
+/// # Returns
+/// Log IDs (SHA-256 of the log public key) of the matching logs
+pub fn select_transparency_logs(
+    roots: &[TrustedRoot],
+    timestamp: i64,
+) -> Result<Vec<Digest>, VerificationError> {
+    let mut log_ids = Vec::new();
+
+    for tlog in transparency_logs_at(roots, timestamp)? {
+        let Some(log_id) = &tlog.log_id else {
+            continue;
+        };
+
+        let key_id = BASE64_STANDARD
+            .decode(&log_id.key_id)
+            .map_err(|e| TransparencyError::InvalidLogId(e.to_string()))?;
+        let log_id = Digest::new(DigestAlgorithm::Sha256, key_id)
+            .map_err(|e| TransparencyError::InvalidLogId(e.to_string()))?;
+        if !log_ids.contains(&log_id) {
+            log_ids.push(log_id);
+        }
+    }
+
+    Ok(log_ids)
+}
+
+/// Collect the public keys of the Rekor transparency logs in the trust bundles
+/// whose public key was valid at the given timestamp, for
+/// `VerificationOptions::transparency_log_keys`.
+///
+/// A log's origin, the name its checkpoints are signed under, is its base URL
+/// without the scheme.
+///
+/// # Arguments
+/// * `roots` - Parsed trust root bundles
+/// * `timestamp` - Signature timestamp in Unix seconds
+///
+/// # Returns
+/// Keys of the matching logs that list their public key
+pub fn select_transparency_log_keys(
+    roots: &[TrustedRoot],
+    timestamp: i64,
+) -> Result<Vec<TransparencyLogKey>, VerificationError> {
+    let mut keys = Vec::new();
+
+    for tlog in transparency_logs_at(roots, timestamp)? {
+        let Some(raw_bytes) = tlog.public_key.as_ref().and_then(|key| key.raw_bytes.as_ref()) else {
+            continue;
+        };
+
+        let origin = tlog.base_url.split_once("://").map_or(tlog.base_url.as_str(), |(_, rest)| rest);
+        let key = TransparencyLogKey::new(origin.trim_end_matches('/'), raw_bytes.as_str());
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Transparency logs of the trust bundles whose public key was valid at the
+/// given timestamp
+fn transparency_logs_at(
+    roots: &[TrustedRoot],
+    timestamp: i64,
+) -> Result<Vec<&TransparencyLogInstance>, VerificationError> {
+    let mut tlogs = Vec::new();
+
     for root in roots {
-        for tsa in &root.timestamp_authorities {
-            // Match by URI
-            if tsa.uri.contains(expected_tsa_domain) {
-                // Validate timestamp falls within validity period
-                if let Some(start_str) = &tsa.valid_for.start {
-                    let start = parse_rfc3339_timestamp(start_str)?;
-                    if timestamp < start {
+        for tlog in &root.tlogs {
+            if let Some(valid_for) = tlog.public_key.as_ref().and_then(|key| key.valid_for.as_ref()) {
+                if let Some(start_str) = &valid_for.start {
+                    if timestamp < parse_rfc3339_timestamp(start_str)? {
                         continue; // Not yet valid
                     }
-
-                    // Check end time if present
-                    if let Some(end_str) = &tsa.valid_for.end {
-                        let end = parse_rfc3339_timestamp(end_str)?;
-                        if timestamp > end {
-                            continue; // Expired
-                        }
-                    }
-                    // No end time means ongoing/current certificate
-
-                    // Keep track of the best match (most recent start date)
-                    match best_match {
-                        None => best_match = Some((&tsa.cert_chain, start)),
-                        Some((_, best_start)) if start > best_start => {
-                            best_match = Some((&tsa.cert_chain, start));
-                        }
-                        _ => {} // Keep existing best match
+                }
+                if let Some(end_str) = &valid_for.end {
+                    if timestamp > parse_rfc3339_timestamp(end_str)? {
+                        continue; // Expired
                     }
                 }
             }
+            tlogs.push(tlog);
         }
     }
 
-    match best_match {
-        Some((cert_chain, _)) => extract_tsa_cert_chain_from_authority(cert_chain),
-        None => Err(VerificationError::InvalidBundleFormat(format!(
-            "No valid timestamp authority found for instance {:?} at timestamp {}",
-            instance, timestamp
-        ))),
-    }
+    Ok(tlogs)
+}
+
+/// Verify that every transparency log entry of a bundle comes from a Rekor log
+/// of the trust bundles that was valid at the given timestamp.
+///
+/// # Arguments
+/// * `roots` - Parsed trust root bundles
+/// * `bundle` - Bundle whose `tlogEntries` are checked
+/// * `timestamp` - Signature timestamp in Unix seconds
+///
+/// # Returns
+/// `Ok(())` if every entry's log is trusted, otherwise an `UnknownLog` error
+pub fn verify_bundle_transparency_logs(
+    roots: &[TrustedRoot],
+    bundle: &SigstoreBundle,
+    timestamp: i64,
+) -> Result<(), VerificationError> {
+    verify_log_ids(bundle, &select_transparency_logs(roots, timestamp)?)
 }
 
 /// Convert JSONL cert chain to verifier's CertificateChain format for Fulcio CAs.
@@ -302,4 +469,69 @@ mod tests {
         let result = load_trusted_root_from_jsonl("not a json");
         assert!(result.is_err());
     }
+
+    const TRUSTED_ROOT: &str = r#"{"mediaType":"application/vnd.dev.sigstore.trustedroot+json;version=0.1","tlogs":[{"baseUrl":"https://rekor.sigstore.dev","hashAlgorithm":"SHA2_256","publicKey":{"keyDetails":"PKIX_ECDSA_P256_SHA_256","validFor":{"start":"2021-01-12T11:53:27Z"}},"logId":{"keyId":"wNI9atQGlz+VWfO6LRygH4QUfY/8W4RFwiT5i5WRgB0="}},{"baseUrl":"https://log2025-1.rekor.sigstore.dev","hashAlgorithm":"SHA2_256","publicKey":{"keyDetails":"PKIX_ED25519","validFor":{"start":"2025-09-23T00:00:00Z"}},"logId":{"keyId":"zxGZFVvd0FEmjR8WrFwMdcAJ9vtaY/QXf44Y1wUeP6A="}}]}"#;
+
+    #[test]
+    fn test_select_transparency_logs_by_validity() {
+        let roots = load_trusted_root_from_jsonl(TRUSTED_ROOT).unwrap();
+
+        // 2024-01-01: only the original Rekor log was active
+        let log_ids = select_transparency_logs(&roots, 1704067200).unwrap();
+        assert_eq!(log_ids.len(), 1);
+        assert_eq!(
+            log_ids[0].to_hex(),
+            "c0d23d6ad406973f9559f3ba2d1ca01f84147d8ffc5b8445c224f98b9591801d"
+        );
+
+        // 2026-01-01: both logs
+        assert_eq!(select_transparency_logs(&roots, 1767225600).unwrap().len(), 2);
+
+        // 2020-01-01: none
+        assert!(select_transparency_logs(&roots, 1577836800).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_select_transparency_log_keys() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/trusted_root.jsonl");
+        let roots = load_trusted_root_from_jsonl(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        // 2026-01-01: both logs, named by their host and keyed by their log IDs
+        let keys = select_transparency_log_keys(&roots, 1767225600).unwrap();
+        let origins: Vec<_> = keys.iter().map(|key| key.origin.as_str()).collect();
+        assert_eq!(origins, ["rekor.sigstore.dev", "log2025-1.rekor.sigstore.dev"]);
+        let log_ids: Vec<_> = keys.iter().map(|key| key.log_id().unwrap()).collect();
+        assert_eq!(log_ids, select_transparency_logs(&roots, 1767225600).unwrap());
+
+        // 2024-01-01: only the original Rekor log was active
+        assert_eq!(select_transparency_log_keys(&roots, 1704067200).unwrap().len(), 1);
+
+        // Logs without a public key are skipped
+        let roots = load_trusted_root_from_jsonl(TRUSTED_ROOT).unwrap();
+        assert!(select_transparency_log_keys(&roots, 1767225600).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_verify_bundle_transparency_logs() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        let bundle = crate::parser::bundle::parse_bundle_from_path(&path).unwrap();
+        let timestamp = crate::parser::bundle::extract_bundle_timestamp(&bundle).unwrap();
+
+        let roots = load_trusted_root_from_jsonl(TRUSTED_ROOT).unwrap();
+        verify_bundle_transparency_logs(&roots, &bundle, timestamp).unwrap();
+
+        let mut untrusted = roots.clone();
+        untrusted[0].tlogs.remove(0);
+        let err = verify_bundle_transparency_logs(&untrusted, &bundle, timestamp).unwrap_err();
+        assert!(matches!(
+            err,
+            VerificationError::Transparency(TransparencyError::UnknownLog(_))
+        ));
+    }
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::fetcher::jsonl::parser::load_trusted_root_from_jsonl;
+use crate::error::VerificationError;
+
 /// Sigstore TrustedRoot bundle format
 /// Spec: https://github.com/sigstore/protobuf-specs/blob/main/protos/sigstore_trustroot.proto
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +19,26 @@ pub struct TrustedRoot {
     pub timestamp_authorities: Vec<TimestampAuthority>,
 }
 
+/// Every TrustedRoot of a `trusted_root.jsonl`, compared as one unit when the file is refreshed
+#[derive(Debug, Clone, Default)]
+pub struct TrustedRootSet {
+    pub roots: Vec<TrustedRoot>,
+}
+
+impl TrustedRootSet {
+    pub fn new(roots: Vec<TrustedRoot>) -> Self {
+        Self { roots }
+    }
+
+    /// Parse a `trusted_root.jsonl`, or a single `trusted_root.json` document
+    pub fn from_jsonl(content: &str) -> Result<Self, VerificationError> {
+        match serde_json::from_str::<TrustedRoot>(content) {
+            Ok(root) => Ok(Self::new(vec![root])),
+            Err(_) => load_trusted_root_from_jsonl(content).map(Self::new),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CertificateAuthority {
@@ -52,7 +75,7 @@ pub struct Certificate {
     pub raw_bytes: String, // base64-encoded DER
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidityPeriod {
     pub start: Option<String>, // RFC3339 timestamp
     pub end: Option<String>,   // RFC3339 timestamp
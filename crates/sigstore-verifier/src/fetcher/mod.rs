@@ -1,12 +1,10 @@
-//! Fetcher utilities for retrieving trust bundles
+//! Offline trust material
 //!
-//! This module provides helper functions for fetching Fulcio certificate chains
-//! from external sources. These are utility functions that clients can use to
-//! obtain the necessary trust bundles for verification.
+//! Parses the `trusted_root.jsonl` written by `gh attestation trusted-root`
+//! into the certificate chains the verifier takes.
 //!
-//! **Note**: The verification library itself does not fetch data. Clients are
-//! responsible for fetching and providing certificate chains to the verifier.
+//! **Note**: The verification library itself does not fetch data. Clients
+//! fetching trust bundles, timestamps and log entries over the network use the
+//! `sigstore-fetcher` crate.
 
 pub mod jsonl;
-#[cfg(feature = "fetcher")]
-pub mod trust_bundle;
@@ -0,0 +1,137 @@
+//! Minimal verification profile
+//!
+//! Checks that a DSSE envelope is signed by a pinned public key and that its
+//! in-toto statement covers an expected digest, and nothing else: no
+//! certificate chain, transparency log, timestamp or policy. Built with
+//! `default-features = false, features = ["minimal"]`, only this module,
+//! `parser::bundle`, `crypto::signature`, `verifier::subject` and the types
+//! they use are compiled, which leaves out x509-parser, the CMS and ASN.1
+//! stacks and the Solidity encoding.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let public_key = PublicKey::from_spki_der(&pinned_spki_der)?;
+//! let statement = minimal::verify_bundle(&bundle_json, &public_key, &expected_digest)?;
+//! ```
+
+use crate::crypto::signature::PublicKey;
+use crate::error::VerificationError;
+use crate::parser::bundle::{parse_bundle_from_bytes, parse_dsse_payload};
+use crate::types::bundle::DsseEnvelope;
+use crate::types::digest::Digest;
+use crate::types::dsse::{Statement, IN_TOTO_PAYLOAD_TYPE};
+use crate::verifier::signature::verify_dsse_signature_with_key;
+use crate::verifier::subject::verify_subject_digest;
+
+/// Verify a DSSE envelope against a pinned key and the digest it must attest
+///
+/// Only in-toto envelopes are accepted, so that another payload signed with
+/// the same key cannot be read as a statement.
+///
+/// # Returns
+///
+/// The envelope's in-toto statement
+///
+/// # Errors
+///
+/// Returns `InvalidBundleFormat` if the payload type is not
+/// `application/vnd.in-toto+json`, `Signature` if no signature of the envelope
+/// verifies with `public_key`, the statement parse errors, and
+/// `SubjectDigestMismatch` if the first subject's digest is not `expected_digest`.
+pub fn verify_envelope(
+    envelope: &DsseEnvelope,
+    public_key: &PublicKey,
+    expected_digest: &Digest,
+) -> Result<Statement, VerificationError> {
+    if envelope.payload_type != IN_TOTO_PAYLOAD_TYPE {
+        return Err(VerificationError::InvalidBundleFormat(format!(
+            "Unexpected DSSE payload type: {}",
+            envelope.payload_type
+        )));
+    }
+    verify_dsse_signature_with_key(envelope, public_key)?;
+    let statement = parse_dsse_payload(envelope)?;
+    verify_subject_digest(&statement, Some(expected_digest))?;
+    Ok(statement)
+}
+
+/// Verify the DSSE envelope of a Sigstore bundle as by [`verify_envelope`]
+///
+/// The bundle's certificate, transparency log entries and timestamps are
+/// ignored; the pinned key stands in for all of them.
+pub fn verify_bundle(
+    bundle_json: &[u8],
+    public_key: &PublicKey,
+    expected_digest: &Digest,
+) -> Result<Statement, VerificationError> {
+    let bundle = parse_bundle_from_bytes(bundle_json)?;
+    verify_envelope(&bundle.dsse_envelope, public_key, expected_digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SignatureError;
+    use crate::types::bundle::Signature;
+    use crate::verifier::signature::build_pae;
+    use p256::ecdsa::signature::Signer;
+    use p256::ecdsa::{Signature as P256Signature, SigningKey};
+    use p256::pkcs8::EncodePublicKey;
+
+    fn key(seed: u8) -> (SigningKey, PublicKey) {
+        let signing_key = SigningKey::from_bytes(&[seed; 32].into()).unwrap();
+        let spki = signing_key.verifying_key().to_public_key_der().unwrap();
+        (signing_key, PublicKey::from_spki_der(spki.as_bytes()).unwrap())
+    }
+
+    fn envelope(signing_key: &SigningKey, payload_type: &str, digest: &Digest) -> DsseEnvelope {
+        let payload = serde_json::json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "subject": [{"name": "artifact", "digest": {"sha256": hex::encode(&digest.bytes)}}],
+            "predicateType": "https://slsa.dev/provenance/v1",
+            "predicate": {},
+        })
+        .to_string();
+        let signature: P256Signature = signing_key.sign(&build_pae(payload_type, payload.as_bytes()));
+        DsseEnvelope::new(
+            payload_type,
+            payload.as_bytes(),
+            vec![Signature::from_bytes(signature.to_der().as_bytes())],
+        )
+    }
+
+    #[test]
+    fn test_verify_envelope() {
+        let (signing_key, public_key) = key(0x11);
+        let digest = Digest::sha256([0x42; 32]);
+        let envelope = envelope(&signing_key, IN_TOTO_PAYLOAD_TYPE, &digest);
+
+        let statement = verify_envelope(&envelope, &public_key, &digest).unwrap();
+        assert_eq!(statement.subject[0].name, "artifact");
+
+        assert!(matches!(
+            verify_envelope(&envelope, &public_key, &Digest::sha256([0x43; 32])),
+            Err(VerificationError::SubjectDigestMismatch { .. })
+        ));
+
+        let (_, other_key) = key(0x22);
+        assert!(matches!(
+            verify_envelope(&envelope, &other_key, &digest),
+            Err(VerificationError::Signature(SignatureError::InvalidSignature))
+        ));
+    }
+
+    #[test]
+    fn test_verify_envelope_rejects_other_payload_type() {
+        // Correctly signed by the pinned key, but not an in-toto envelope
+        let (signing_key, public_key) = key(0x11);
+        let digest = Digest::sha256([0x42; 32]);
+        let envelope = envelope(&signing_key, "application/vnd.example.compliance+json", &digest);
+
+        assert!(matches!(
+            verify_envelope(&envelope, &public_key, &digest),
+            Err(VerificationError::InvalidBundleFormat(_))
+        ));
+    }
+}
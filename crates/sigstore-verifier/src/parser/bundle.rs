@@ -2,26 +2,57 @@ use std::path::Path;
 
 use base64::prelude::*;
 use crate::error::VerificationError;
+#[cfg(feature = "full")]
 use crate::parser::rfc3161::parse_rfc3161_timestamp;
+#[cfg(feature = "full")]
 use crate::parser::timestamp::parse_integrated_time;
+use crate::profiling::{self, CycleSpan};
 use crate::types::bundle::{DsseEnvelope, SigstoreBundle};
 use crate::types::dsse::Statement;
+use crate::types::limits::InputLimits;
 
 pub fn parse_bundle_from_path(path: &Path) -> Result<SigstoreBundle, VerificationError> {
-    let contents = std::fs::read_to_string(path)
-        .map_err(|e| VerificationError::InvalidBundleFormat(e.to_string()))?;
-    parse_bundle_from_str(&contents)
+    parse_bundle_from_path_with_limits(path, &InputLimits::default())
 }
 
 pub fn parse_bundle_from_bytes(bytes: &[u8]) -> Result<SigstoreBundle, VerificationError> {
-    let bundle: SigstoreBundle = serde_json::from_slice(bytes)?;
-    validate_bundle(&bundle)?;
-    Ok(bundle)
+    parse_bundle_from_bytes_with_limits(bytes, &InputLimits::default())
 }
 
 pub fn parse_bundle_from_str(json: &str) -> Result<SigstoreBundle, VerificationError> {
-    let bundle: SigstoreBundle = serde_json::from_str(json)?;
+    parse_bundle_from_bytes(json.as_bytes())
+}
+
+/// Parse a bundle file, rejecting it before reading if it exceeds `limits`
+pub fn parse_bundle_from_path_with_limits(
+    path: &Path,
+    limits: &InputLimits,
+) -> Result<SigstoreBundle, VerificationError> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| VerificationError::InvalidBundleFormat(e.to_string()))?
+        .len();
+    limits.check_bundle_size(usize::try_from(size).unwrap_or(usize::MAX))?;
+    let contents = std::fs::read(path)
+        .map_err(|e| VerificationError::InvalidBundleFormat(e.to_string()))?;
+    parse_bundle_from_bytes_with_limits(&contents, limits)
+}
+
+/// Parse a bundle, checking its size before parsing and its counts and base64
+/// field lengths before anything is decoded
+///
+/// # Errors
+///
+/// Returns `LimitExceeded` if the bundle or one of its fields exceeds
+/// `limits`, and the parse errors of [`parse_bundle_from_bytes`].
+pub fn parse_bundle_from_bytes_with_limits(
+    bytes: &[u8],
+    limits: &InputLimits,
+) -> Result<SigstoreBundle, VerificationError> {
+    limits.check_bundle_size(bytes.len())?;
+    let _span = CycleSpan::enter(profiling::JSON_PARSE);
+    let bundle: SigstoreBundle = serde_json::from_slice(bytes)?;
     validate_bundle(&bundle)?;
+    limits.check_bundle(&bundle)?;
     Ok(bundle)
 }
 
@@ -36,6 +67,13 @@ fn validate_bundle(bundle: &SigstoreBundle) -> Result<(), VerificationError> {
         )));
     }
 
+    if bundle.verification_material.x509_certificate_chain.is_some() {
+        return Err(VerificationError::InvalidBundleFormat(format!(
+            "Bundle carries an x509CertificateChain ({}); convert it with SigstoreBundle::convert_to(BundleVersion::V0_3)",
+            bundle.media_type
+        )));
+    }
+
     if bundle.dsse_envelope.signatures.is_empty() {
         return Err(VerificationError::InvalidBundleFormat(
             "No signatures in DSSE envelope".to_string(),
@@ -46,13 +84,29 @@ fn validate_bundle(bundle: &SigstoreBundle) -> Result<(), VerificationError> {
 }
 
 pub fn parse_dsse_payload(envelope: &DsseEnvelope) -> Result<Statement, VerificationError> {
-    let payload_bytes = BASE64_STANDARD.decode(&envelope.payload)?;
-    let statement: Statement = serde_json::from_slice(&payload_bytes)?;
+    let payload_bytes = decode_base64(&envelope.payload)?;
+    parse_statement(&payload_bytes)
+}
+
+/// Parse an in-toto statement from a decoded DSSE payload
+pub fn parse_statement(payload_bytes: &[u8]) -> Result<Statement, VerificationError> {
+    let _span = CycleSpan::enter(profiling::JSON_PARSE);
+    let statement: Statement = serde_json::from_slice(payload_bytes)?;
     Ok(statement)
 }
 
+/// Decode a padded standard base64 bundle field
+///
+/// zkVM guests use the exactly sized decoder of
+/// [`base64_decode_bounded`](crate::encoding::base64_decode_bounded), which
+/// accepts the same inputs.
 pub fn decode_base64(input: &str) -> Result<Vec<u8>, VerificationError> {
-    BASE64_STANDARD.decode(input).map_err(|e| e.into())
+    let _span = CycleSpan::enter(profiling::BASE64);
+    #[cfg(target_os = "zkvm")]
+    let decoded = crate::encoding::base64_decode_bounded(input);
+    #[cfg(not(target_os = "zkvm"))]
+    let decoded = BASE64_STANDARD.decode(input);
+    decoded.map_err(|e| e.into())
 }
 
 /// Extract timestamp from a Sigstore bundle in Unix seconds.
@@ -63,6 +117,7 @@ pub fn decode_base64(input: &str) -> Result<Vec<u8>, VerificationError> {
 ///
 /// # Returns
 /// Unix timestamp in seconds
+#[cfg(feature = "full")]
 pub fn extract_bundle_timestamp(bundle: &SigstoreBundle) -> Result<i64, VerificationError> {
     if let Some(timestamp_data) = bundle
         .verification_material
@@ -103,7 +158,7 @@ mod tests {
 
     #[test]
     fn test_validate_bundle_invalid_media_type() {
-        use crate::types::bundle::{Certificate, Signature, VerificationMaterial};
+        use crate::types::bundle::{Certificate, Signature, VerificationMaterial, X509CertificateChain};
 
         let mut bundle = SigstoreBundle {
             media_type: "invalid".to_string(),
@@ -112,6 +167,7 @@ mod tests {
                 certificate: Certificate {
                     raw_bytes: String::new(),
                 },
+                x509_certificate_chain: None,
                 tlog_entries: None,
             },
             dsse_envelope: DsseEnvelope {
@@ -128,5 +184,43 @@ mod tests {
 
         bundle.media_type = "application/vnd.dev.sigstore.bundle.v0.3+json".to_string();
         assert!(validate_bundle(&bundle).is_ok());
+
+        bundle.verification_material.x509_certificate_chain = Some(X509CertificateChain { certificates: vec![] });
+        assert!(matches!(
+            validate_bundle(&bundle),
+            Err(VerificationError::InvalidBundleFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_bundle_limits() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        let json = std::fs::read(&path).unwrap();
+        parse_bundle_from_bytes_with_limits(&json, &InputLimits::default()).unwrap();
+
+        let limits = InputLimits { max_bundle_size: 1024, ..InputLimits::default() };
+        assert!(matches!(
+            parse_bundle_from_bytes_with_limits(&json, &limits),
+            Err(VerificationError::LimitExceeded { limit: "bundle size", max: 1024, .. })
+        ));
+        assert!(matches!(
+            parse_bundle_from_path_with_limits(&path, &limits),
+            Err(VerificationError::LimitExceeded { limit: "bundle size", .. })
+        ));
+
+        let limits = InputLimits { max_base64_field_size: 64, ..InputLimits::default() };
+        assert!(matches!(
+            parse_bundle_from_bytes_with_limits(&json, &limits),
+            Err(VerificationError::LimitExceeded { limit: "DSSE payload", max: 64, .. })
+        ));
+
+        let limits = InputLimits { max_tlog_entries: 0, ..InputLimits::default() };
+        assert!(matches!(
+            parse_bundle_from_bytes_with_limits(&json, &limits),
+            Err(VerificationError::LimitExceeded { limit: "tlog entries", max: 0, actual: 1 })
+        ));
     }
 }
@@ -1,7 +1,13 @@
 use x509_parser::prelude::*;
 
+use crate::crypto::algorithm::{AlgorithmLookup, SignatureAlgorithm};
+use crate::crypto::hash::sha256;
+use crate::crypto::signature::PublicKey;
 use crate::error::CertificateError;
-use crate::types::certificate::{CertificateChain, FulcioInstance};
+use crate::types::certificate::{
+    CertificateChain, CertificateExtension, CertificateMetadata, FulcioInstance, ParsedCertificate, SubjectAltName,
+};
+use crate::types::time::Timestamp;
 
 pub fn parse_der_certificate(der: &[u8]) -> Result<X509Certificate<'_>, CertificateError> {
     let (_, cert) = X509Certificate::from_der(der)
@@ -9,6 +15,73 @@ pub fn parse_der_certificate(der: &[u8]) -> Result<X509Certificate<'_>, Certific
     Ok(cert)
 }
 
+/// Parse a DER certificate into the owned [`ParsedCertificate`] model
+///
+/// Uses x509-parser, or `parser::x509` with the `minimal-x509` feature.
+///
+/// # Errors
+///
+/// Returns an error if the certificate or its validity times cannot be parsed.
+/// A public key of an unsupported type, or an issuer without a common name,
+/// is only an error when that field is used.
+pub fn parse_certificate(der: &[u8]) -> Result<ParsedCertificate, CertificateError> {
+    #[cfg(feature = "minimal-x509")]
+    return crate::parser::x509::parse_certificate(der);
+    #[cfg(not(feature = "minimal-x509"))]
+    return parse_certificate_with_x509_parser(der);
+}
+
+/// [`parse_certificate`] with x509-parser, whatever the features
+pub fn parse_certificate_with_x509_parser(der: &[u8]) -> Result<ParsedCertificate, CertificateError> {
+    let cert = parse_der_certificate(der)?;
+    let validity = cert.validity();
+
+    let mut subject_alt_names = Vec::new();
+    if let Some(san) = cert.subject_alternative_name().ok().flatten() {
+        for name in &san.value.general_names {
+            match name {
+                GeneralName::RFC822Name(email) => subject_alt_names.push(SubjectAltName::Email(email.to_string())),
+                GeneralName::URI(uri) => subject_alt_names.push(SubjectAltName::Uri(uri.to_string())),
+                _ => {}
+            }
+        }
+    }
+    let subject_key_id = cert.extensions().iter().find_map(|ext| match ext.parsed_extension() {
+        ParsedExtension::SubjectKeyIdentifier(key_id) => Some(hex::encode(key_id.0)),
+        _ => None,
+    });
+    let extensions = cert
+        .extensions()
+        .iter()
+        .map(|ext| CertificateExtension {
+            oid: ext.oid.to_id_string(),
+            critical: ext.critical,
+            value: ext.value.to_vec(),
+        })
+        .collect();
+
+    Ok(ParsedCertificate {
+        der: der.to_vec(),
+        fingerprint: sha256(der),
+        serial_number: hex::encode(cert.raw_serial()),
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        issuer_cn: extract_issuer_cn(&cert).ok(),
+        not_before: asn1_time_to_utc(&validity.not_before)?,
+        not_after: asn1_time_to_utc(&validity.not_after)?,
+        spki: cert.public_key().raw.to_vec(),
+        subject_key_id,
+        subject_alt_names,
+        extensions,
+        public_key: PublicKey::from_certificate(&cert),
+        tbs_certificate: cert.tbs_certificate.as_ref().to_vec(),
+        signature: cert.signature_value.data.to_vec(),
+        signature_algorithm: cert.signature_algorithm.algorithm.to_id_string(),
+        tbs_signature_algorithm: cert.tbs_certificate.signature.algorithm.to_id_string(),
+        algorithm_parameters_match: cert.signature_algorithm.parameters == cert.tbs_certificate.signature.parameters,
+    })
+}
+
 pub fn parse_pem_certificate(pem_str: &str) -> Result<Vec<u8>, CertificateError> {
     let parsed = ::pem::parse(pem_str.as_bytes())
         .map_err(|e| CertificateError::ParseError(e.to_string()))?;
@@ -23,6 +96,20 @@ pub fn parse_pem_certificate(pem_str: &str) -> Result<Vec<u8>, CertificateError>
     Ok(parsed.into_contents())
 }
 
+/// Parse every `CERTIFICATE` block of a PEM bundle, in order
+pub fn parse_pem_certificates(pem_bytes: &[u8]) -> Result<Vec<Vec<u8>>, CertificateError> {
+    let blocks = ::pem::parse_many(pem_bytes).map_err(|e| CertificateError::ParseError(e.to_string()))?;
+    let certs: Vec<Vec<u8>> = blocks
+        .into_iter()
+        .filter(|block| block.tag() == "CERTIFICATE")
+        .map(::pem::Pem::into_contents)
+        .collect();
+    if certs.is_empty() {
+        return Err(CertificateError::ParseError("No CERTIFICATE block in PEM".into()));
+    }
+    Ok(certs)
+}
+
 pub fn extract_issuer_cn(cert: &X509Certificate) -> Result<String, CertificateError> {
     let issuer = cert.issuer();
 
@@ -57,6 +144,51 @@ pub fn determine_fulcio_instance(cert: &X509Certificate) -> Result<FulcioInstanc
         .ok_or_else(|| CertificateError::UnknownIssuer(issuer_cn))
 }
 
+/// Extract the audit metadata of a certificate
+///
+/// # Returns
+///
+/// Serial number, subject key identifier, validity window, signature
+/// algorithm and issuer CN
+///
+/// # Errors
+///
+/// Returns an error if the issuer has no common name or the validity
+/// timestamps are out of range.
+pub fn extract_certificate_metadata(cert: &X509Certificate) -> Result<CertificateMetadata, CertificateError> {
+    let subject_key_id = cert.tbs_certificate.extensions().iter().find_map(|ext| {
+        match ext.parsed_extension() {
+            ParsedExtension::SubjectKeyIdentifier(key_id) => Some(hex::encode(key_id.0)),
+            _ => None,
+        }
+    });
+
+    let signature_algorithm = signature_algorithm_name(&cert.signature_algorithm.algorithm.to_id_string());
+
+    let validity = cert.validity();
+    Ok(CertificateMetadata {
+        serial_number: hex::encode(cert.raw_serial()),
+        subject_key_id,
+        not_before: asn1_time_to_utc(&validity.not_before)?,
+        not_after: asn1_time_to_utc(&validity.not_after)?,
+        signature_algorithm,
+        issuer_cn: extract_issuer_cn(cert)?,
+    })
+}
+
+/// Name of a signature algorithm OID (e.g. "ecdsa-with-SHA256"), or the OID if unsupported
+pub(crate) fn signature_algorithm_name(oid: &str) -> String {
+    match SignatureAlgorithm::from_oid(oid) {
+        AlgorithmLookup::Supported(algorithm) => algorithm.name().to_string(),
+        AlgorithmLookup::Weak(name) => name.to_string(),
+        AlgorithmLookup::Unsupported => oid.to_string(),
+    }
+}
+
+fn asn1_time_to_utc(time: &ASN1Time) -> Result<Timestamp, CertificateError> {
+    Ok(Timestamp::from_unix(time.timestamp()))
+}
+
 pub fn extract_subject_public_key_info<'a>(cert: &'a X509Certificate) -> &'a SubjectPublicKeyInfo<'a> {
     cert.public_key()
 }
@@ -130,4 +262,39 @@ mod tests {
         let result = parse_pem_certificate(pem);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_extract_certificate_metadata() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/certs/revocation-intermediate.pem");
+        let der = parse_pem_certificate(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let cert = parse_der_certificate(&der).unwrap();
+
+        let metadata = extract_certificate_metadata(&cert).unwrap();
+        assert_eq!(metadata.serial_number, "1000");
+        assert_eq!(metadata.issuer_cn, "revocation-ca");
+        assert_eq!(metadata.signature_algorithm, "ecdsa-with-SHA256");
+        assert_eq!(metadata.subject_key_id.map(|key_id| key_id.len()), Some(40));
+        assert!(metadata.not_before < metadata.not_after);
+    }
+
+    #[test]
+    fn test_parse_certificate() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/certs/email-leaf.pem");
+        let der = parse_pem_certificate(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        // Owned: outlives the DER it was parsed from and can move across threads
+        let parsed = std::thread::spawn(move || parse_certificate(&der).unwrap()).join().unwrap();
+        let cert = parse_der_certificate(&parsed.der).unwrap();
+        assert_eq!(parsed.metadata().unwrap(), extract_certificate_metadata(&cert).unwrap());
+        assert_eq!(parsed.issuer_cn().unwrap(), extract_issuer_cn(&cert).unwrap());
+        assert_eq!(parsed.subject_alt_names, vec![SubjectAltName::Email("alice@example.com".to_string())]);
+        assert!(parsed.extension("1.3.6.1.4.1.57264.1.8").is_some());
+        assert!(parsed.public_key().is_ok());
+    }
 }
@@ -0,0 +1,146 @@
+//! Transparency log checkpoints
+//!
+//! A checkpoint is a signed note (C2SP `signed-note`) committing to a tree size
+//! and root hash. Rekor v1 embeds one in every inclusion proof, and Rekor v2
+//! serves the latest one at `/api/v2/checkpoint`:
+//!
+//! ```text
+//! <origin>
+//! <tree size>
+//! <base64 root hash>
+//! [extension lines]
+//!
+//! — <signer name> <base64(key hint || signature)>
+//! ```
+
+use crate::crypto::merkle::HASH_SIZE;
+use crate::error::TransparencyError;
+use crate::parser::bundle::decode_base64;
+
+/// Prefix of a signature line (em dash and space)
+const SIGNATURE_PREFIX: &str = "\u{2014} ";
+
+/// Parsed checkpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogCheckpoint {
+    /// Log origin, e.g. `log2025-1.rekor.sigstore.dev`
+    pub origin: String,
+    pub tree_size: u64,
+    pub root_hash: [u8; HASH_SIZE],
+    /// Optional lines following the root hash
+    pub extensions: Vec<String>,
+    pub signatures: Vec<NoteSignature>,
+    /// Signed text: every line up to the blank separator, newline-terminated
+    pub body: String,
+}
+
+/// One signature line of a signed note
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteSignature {
+    /// Name of the signer, usually the log origin without its tree ID
+    pub name: String,
+    /// First four bytes of the signer's key ID
+    pub key_hint: [u8; 4],
+    pub signature: Vec<u8>,
+}
+
+/// Parse a checkpoint envelope
+///
+/// # Errors
+///
+/// Returns `InvalidCheckpoint` if the note has no signatures or a malformed
+/// body or signature line.
+pub fn parse_checkpoint(envelope: &str) -> Result<LogCheckpoint, TransparencyError> {
+    let invalid = |reason: &str| TransparencyError::InvalidCheckpoint(reason.to_string());
+
+    let split = envelope
+        .find("\n\n")
+        .ok_or_else(|| invalid("missing blank line before signatures"))?;
+    let body = &envelope[..split + 1];
+    let signature_lines = &envelope[split + 2..];
+
+    let mut lines = body.lines();
+    let origin = lines
+        .next()
+        .filter(|line| !line.is_empty())
+        .ok_or_else(|| invalid("missing origin"))?;
+    let tree_size = lines
+        .next()
+        .and_then(|line| line.parse::<u64>().ok())
+        .ok_or_else(|| invalid("missing or invalid tree size"))?;
+    let root_hash = lines
+        .next()
+        .and_then(|line| decode_base64(line).ok())
+        .and_then(|hash| <[u8; HASH_SIZE]>::try_from(hash).ok())
+        .ok_or_else(|| invalid("missing or invalid root hash"))?;
+    let extensions = lines.map(str::to_string).collect();
+
+    let signatures = signature_lines
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(parse_signature_line)
+        .collect::<Result<Vec<_>, _>>()?;
+    if signatures.is_empty() {
+        return Err(invalid("no signatures"));
+    }
+
+    Ok(LogCheckpoint {
+        origin: origin.to_string(),
+        tree_size,
+        root_hash,
+        extensions,
+        signatures,
+        body: body.to_string(),
+    })
+}
+
+fn parse_signature_line(line: &str) -> Result<NoteSignature, TransparencyError> {
+    let invalid = || TransparencyError::InvalidCheckpoint(format!("malformed signature line: {}", line));
+
+    let (name, signature) = line
+        .strip_prefix(SIGNATURE_PREFIX)
+        .and_then(|rest| rest.rsplit_once(' '))
+        .ok_or_else(invalid)?;
+    let bytes = decode_base64(signature).map_err(|_| invalid())?;
+    if bytes.len() <= 4 {
+        return Err(invalid());
+    }
+
+    Ok(NoteSignature {
+        name: name.to_string(),
+        key_hint: bytes[..4].try_into().unwrap(),
+        signature: bytes[4..].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REKOR_V1_CHECKPOINT: &str = "rekor.sigstore.dev - 1193050959916656506\n585383803\nr/bTFC+gN/oyGdCqEBRUuOvBsDWm4p86X6DohvgjbD4=\n\n\u{2014} rekor.sigstore.dev wNI9ajBGAiEA7OPgR1BW8KLlRclsnr7toj7o8woUDhtinWp1rOQ+4TICIQDBx1aYHc5/V6KavBrfVUg1IluJjYWhNr13MJd0Il59NA==\n";
+
+    #[test]
+    fn test_parse_checkpoint() {
+        let checkpoint = parse_checkpoint(REKOR_V1_CHECKPOINT).unwrap();
+        assert_eq!(checkpoint.origin, "rekor.sigstore.dev - 1193050959916656506");
+        assert_eq!(checkpoint.tree_size, 585383803);
+        assert_eq!(
+            hex::encode(checkpoint.root_hash),
+            "aff6d3142fa037fa3219d0aa101454b8ebc1b035a6e29f3a5fa0e886f8236c3e"
+        );
+        assert!(checkpoint.extensions.is_empty());
+        assert_eq!(checkpoint.signatures.len(), 1);
+        assert_eq!(checkpoint.signatures[0].name, "rekor.sigstore.dev");
+        assert_eq!(checkpoint.signatures[0].key_hint, [0xc0, 0xd2, 0x3d, 0x6a]);
+        assert!(checkpoint.body.ends_with("=\n"));
+    }
+
+    #[test]
+    fn test_parse_checkpoint_without_signatures() {
+        let envelope = REKOR_V1_CHECKPOINT.split("\n\n").next().unwrap().to_string() + "\n\n";
+        assert!(matches!(
+            parse_checkpoint(&envelope),
+            Err(TransparencyError::InvalidCheckpoint(_))
+        ));
+    }
+}
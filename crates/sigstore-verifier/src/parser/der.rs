@@ -0,0 +1,228 @@
+//! Minimal DER reader
+//!
+//! Just enough of X.690 for `parser::x509`: definite-length TLVs with
+//! single-byte tags, object identifiers, and UTCTime / GeneralizedTime in the
+//! `Z` form DER requires.
+
+use crate::types::time::Timestamp;
+
+pub const TAG_BOOLEAN: u8 = 0x01;
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_BIT_STRING: u8 = 0x03;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_OBJECT_DESCRIPTOR: u8 = 0x07;
+pub const TAG_UTF8_STRING: u8 = 0x0c;
+pub const TAG_NUMERIC_STRING: u8 = 0x12;
+pub const TAG_PRINTABLE_STRING: u8 = 0x13;
+pub const TAG_T61_STRING: u8 = 0x14;
+pub const TAG_VIDEOTEX_STRING: u8 = 0x15;
+pub const TAG_IA5_STRING: u8 = 0x16;
+pub const TAG_UTC_TIME: u8 = 0x17;
+pub const TAG_GENERALIZED_TIME: u8 = 0x18;
+pub const TAG_GRAPHIC_STRING: u8 = 0x19;
+pub const TAG_VISIBLE_STRING: u8 = 0x1a;
+pub const TAG_GENERAL_STRING: u8 = 0x1b;
+pub const TAG_BMP_STRING: u8 = 0x1e;
+pub const TAG_SEQUENCE: u8 = 0x30;
+pub const TAG_SET: u8 = 0x31;
+
+/// One tag-length-value element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tlv<'a> {
+    pub tag: u8,
+    /// Value bytes, without tag and length
+    pub content: &'a [u8],
+    /// The whole element, tag and length included
+    pub raw: &'a [u8],
+}
+
+/// Reads consecutive TLVs from a byte slice
+#[derive(Debug, Clone)]
+pub struct DerReader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> DerReader<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        DerReader { input }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Bytes not read yet
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// Tag of the next element, without reading it
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.input.first().copied()
+    }
+
+    /// Read the next element, whatever its tag
+    pub fn read_any(&mut self) -> Result<Tlv<'a>, String> {
+        let input = self.input;
+        let (&tag, rest) = input.split_first().ok_or("unexpected end of input")?;
+        if tag & 0x1f == 0x1f {
+            return Err(format!("multi-byte tag 0x{:02x} not supported", tag));
+        }
+        let (&first, mut rest) = rest.split_first().ok_or("missing length")?;
+        let length = if first < 0x80 {
+            usize::from(first)
+        } else {
+            let count = usize::from(first & 0x7f);
+            if count == 0 {
+                return Err("indefinite length not allowed in DER".to_string());
+            }
+            if count > 4 || rest.len() < count {
+                return Err("invalid length".to_string());
+            }
+            let (bytes, after) = rest.split_at(count);
+            rest = after;
+            bytes.iter().fold(0usize, |length, byte| (length << 8) | usize::from(*byte))
+        };
+        if rest.len() < length {
+            return Err(format!("element of {} bytes overruns input", length));
+        }
+        let header = input.len() - rest.len();
+        let (content, after) = rest.split_at(length);
+        self.input = after;
+        Ok(Tlv { tag, content, raw: &input[..header + length] })
+    }
+
+    /// Read the next element, which must have tag `tag`
+    pub fn read(&mut self, tag: u8) -> Result<Tlv<'a>, String> {
+        let tlv = self.read_any()?;
+        if tlv.tag != tag {
+            return Err(format!("expected tag 0x{:02x}, found 0x{:02x}", tag, tlv.tag));
+        }
+        Ok(tlv)
+    }
+
+    /// Read the next element if it has tag `tag`
+    pub fn read_optional(&mut self, tag: u8) -> Result<Option<Tlv<'a>>, String> {
+        if self.peek_tag() == Some(tag) {
+            self.read(tag).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Fail if anything is left
+    pub fn finish(&self) -> Result<(), String> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("{} trailing bytes", self.input.len()))
+        }
+    }
+}
+
+/// Dotted form of an OID's content bytes, e.g. "1.2.840.10045.2.1"
+///
+/// Arcs up to 128 bits are supported, which covers UUID-based `2.25` OIDs.
+pub fn oid_to_string(content: &[u8]) -> Result<String, String> {
+    if content.is_empty() || content.last().is_some_and(|byte| byte & 0x80 != 0) {
+        return Err("invalid OID encoding".to_string());
+    }
+    let mut arcs = Vec::new();
+    let mut value: u128 = 0;
+    for (index, byte) in content.iter().enumerate() {
+        if value == 0 && *byte == 0x80 && (index == 0 || content[index - 1] & 0x80 == 0) {
+            return Err("non-minimal OID arc".to_string());
+        }
+        if value >> 121 != 0 {
+            return Err("OID arc too large".to_string());
+        }
+        value = (value << 7) | u128::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            arcs.push(value);
+            value = 0;
+        }
+    }
+
+    let first = arcs[0];
+    let (root, second) = match first {
+        0..=39 => (0, first),
+        40..=79 => (1, first - 40),
+        _ => (2, first - 80),
+    };
+    let mut dotted = format!("{}.{}", root, second);
+    for arc in &arcs[1..] {
+        dotted.push('.');
+        dotted.push_str(&arc.to_string());
+    }
+    Ok(dotted)
+}
+
+/// Read a UTCTime (`YYMMDDHHMMSSZ`, years 1950 to 2049) or GeneralizedTime
+/// (`YYYYMMDDHHMMSSZ`)
+pub fn parse_time(tlv: &Tlv) -> Result<Timestamp, String> {
+    let text = std::str::from_utf8(tlv.content).map_err(|_| "time is not ASCII".to_string())?;
+    let digits = text.strip_suffix('Z').ok_or_else(|| format!("time without Z: {}", text))?;
+    let year_digits = match tlv.tag {
+        TAG_UTC_TIME => 2,
+        TAG_GENERALIZED_TIME => 4,
+        tag => return Err(format!("expected a time, found tag 0x{:02x}", tag)),
+    };
+    if digits.len() != year_digits + 10 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid time: {}", text));
+    }
+    let field = |start: usize| -> u32 { digits[start..start + 2].parse().unwrap_or(0) };
+
+    let mut year: i64 = digits[..year_digits].parse().unwrap_or(0);
+    if tlv.tag == TAG_UTC_TIME {
+        year += if year < 50 { 2000 } else { 1900 };
+    }
+    let rest = year_digits;
+    Timestamp::from_ymd_hms(year, field(rest), field(rest + 2), field(rest + 4), field(rest + 6), field(rest + 8))
+        .ok_or_else(|| format!("invalid time: {}", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lengths() {
+        let mut long = vec![TAG_OCTET_STRING, 0x81, 0x80];
+        long.extend([0xab; 0x80]);
+        long.extend([TAG_BOOLEAN, 0x01, 0xff]);
+        let mut reader = DerReader::new(&long);
+        let tlv = reader.read(TAG_OCTET_STRING).unwrap();
+        assert_eq!(tlv.content.len(), 0x80);
+        assert_eq!(tlv.raw.len(), 0x83);
+        assert_eq!(reader.read_optional(TAG_INTEGER).unwrap(), None);
+        assert_eq!(reader.read(TAG_BOOLEAN).unwrap().content, [0xff]);
+        assert!(reader.finish().is_ok());
+
+        // Indefinite length, overrun and high tag numbers
+        for bad in [&[0x30, 0x80, 0x00, 0x00][..], &[0x04, 0x05, 0x00], &[0x1f, 0x81, 0x00]] {
+            assert!(DerReader::new(bad).read_any().is_err(), "{:02x?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_oid_to_string() {
+        assert_eq!(oid_to_string(&[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01]).unwrap(), "1.2.840.10045.2.1");
+        assert_eq!(oid_to_string(&[0x55, 0x04, 0x03]).unwrap(), "2.5.4.3");
+        assert_eq!(oid_to_string(&[0x88, 0x37, 0x03]).unwrap(), "2.999.3");
+        assert!(oid_to_string(&[]).is_err());
+        assert!(oid_to_string(&[0x2a, 0x86]).is_err());
+        assert!(oid_to_string(&[0x2a, 0x80, 0x01]).is_err());
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let time = |tag, text: &str| parse_time(&Tlv { tag, content: text.as_bytes(), raw: &[] });
+        assert_eq!(time(TAG_UTC_TIME, "251118075834Z").unwrap().to_rfc3339(), "2025-11-18T07:58:34Z");
+        assert_eq!(time(TAG_UTC_TIME, "500101000000Z").unwrap().to_rfc3339(), "1950-01-01T00:00:00Z");
+        assert_eq!(time(TAG_GENERALIZED_TIME, "20501231235959Z").unwrap().to_rfc3339(), "2050-12-31T23:59:59Z");
+        assert!(time(TAG_UTC_TIME, "2511180758Z").is_err());
+        assert!(time(TAG_UTC_TIME, "251118075834+0100").is_err());
+        assert!(time(TAG_GENERALIZED_TIME, "20251318075834Z").is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Field-by-field comparison of two bundles
+//!
+//! `diff_bundles` flattens the parts of each bundle that matter when
+//! investigating a rebuild discrepancy or suspected tampering (the signing
+//! certificate, the identity it carries, the envelope, the statement's subjects
+//! and predicate, and the transparency log entries) into `path = value` pairs
+//! and reports every path whose value differs. Nothing is verified: run the
+//! verifier first if the bundles' authenticity matters.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::VerificationError;
+use crate::parser::bundle::{decode_base64, parse_statement};
+use crate::parser::identity::{extract_fulcio_extensions, extract_oidc_identity};
+use crate::types::bundle::SigstoreBundle;
+use crate::types::certificate::ParsedCertificate;
+
+/// Part of a bundle a difference was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffSection {
+    /// Leaf certificate: fingerprint, serial, validity, issuer
+    Certificate,
+    /// OIDC identity and Fulcio extensions of the leaf certificate
+    Identity,
+    /// DSSE payload type, payload digest and signatures
+    Envelope,
+    /// Statement subjects, keyed by name
+    Subject,
+    /// Predicate type and predicate fields
+    Predicate,
+    /// Transparency log entries, by position
+    TransparencyLog,
+}
+
+impl DiffSection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiffSection::Certificate => "certificate",
+            DiffSection::Identity => "identity",
+            DiffSection::Envelope => "envelope",
+            DiffSection::Subject => "subject",
+            DiffSection::Predicate => "predicate",
+            DiffSection::TransparencyLog => "transparency_log",
+        }
+    }
+}
+
+/// A field present in at least one bundle with different values
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BundleDifference {
+    pub section: DiffSection,
+    /// Dotted path of the field within its section, e.g. `runDetails.builder.id`
+    pub path: String,
+    /// Value in the first bundle, `None` if absent
+    pub left: Option<String>,
+    /// Value in the second bundle, `None` if absent
+    pub right: Option<String>,
+}
+
+/// Every difference between two bundles, ordered by section and path
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BundleDiff {
+    pub differences: Vec<BundleDifference>,
+}
+
+impl BundleDiff {
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// Differences within one section
+    pub fn section(&self, section: DiffSection) -> impl Iterator<Item = &BundleDifference> {
+        self.differences
+            .iter()
+            .filter(move |difference| difference.section == section)
+    }
+}
+
+impl fmt::Display for BundleDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "Bundles are identical");
+        }
+        for difference in &self.differences {
+            writeln!(f, "{} {}", difference.section.name(), difference.path)?;
+            writeln!(f, "  - {}", difference.left.as_deref().unwrap_or("<absent>"))?;
+            writeln!(f, "  + {}", difference.right.as_deref().unwrap_or("<absent>"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare two bundles field by field
+///
+/// # Arguments
+///
+/// * `left` - First bundle, e.g. the original attestation
+/// * `right` - Second bundle, e.g. the attestation of a rebuild
+///
+/// # Returns
+///
+/// The differences; an empty diff if the compared fields are identical
+///
+/// # Errors
+///
+/// Returns an error if either bundle's certificate or payload cannot be parsed.
+pub fn diff_bundles(left: &SigstoreBundle, right: &SigstoreBundle) -> Result<BundleDiff, VerificationError> {
+    let left = flatten_bundle(left)?;
+    let right = flatten_bundle(right)?;
+
+    let mut differences = Vec::new();
+    for (section, left_fields) in &left {
+        let empty = BTreeMap::new();
+        let right_fields = right.get(section).unwrap_or(&empty);
+        let paths = left_fields.keys().chain(right_fields.keys().filter(|path| !left_fields.contains_key(*path)));
+        for path in paths {
+            let (left_value, right_value) = (left_fields.get(path), right_fields.get(path));
+            if left_value != right_value {
+                differences.push(BundleDifference {
+                    section: *section,
+                    path: path.clone(),
+                    left: left_value.cloned(),
+                    right: right_value.cloned(),
+                });
+            }
+        }
+    }
+    differences.sort_by(|a, b| (a.section, &a.path).cmp(&(b.section, &b.path)));
+
+    Ok(BundleDiff { differences })
+}
+
+type Fields = BTreeMap<String, String>;
+
+fn flatten_bundle(bundle: &SigstoreBundle) -> Result<BTreeMap<DiffSection, Fields>, VerificationError> {
+    let mut sections = BTreeMap::new();
+
+    let cert_der = decode_base64(&bundle.verification_material.certificate.raw_bytes)?;
+    let cert = ParsedCertificate::from_der(&cert_der)?;
+    let mut certificate = Fields::new();
+    certificate.insert("fingerprint".to_string(), hex::encode(cert.fingerprint));
+    flatten("", &serde_json::to_value(cert.metadata()?)?, &mut certificate);
+    sections.insert(DiffSection::Certificate, certificate);
+
+    let mut identity = Fields::new();
+    flatten("", &serde_json::to_value(extract_oidc_identity(&cert)?)?, &mut identity);
+    flatten("extensions", &serde_json::to_value(extract_fulcio_extensions(&cert)?)?, &mut identity);
+    sections.insert(DiffSection::Identity, identity);
+
+    let envelope = &bundle.dsse_envelope;
+    let payload = decode_base64(&envelope.payload)?;
+    let mut envelope_fields = Fields::new();
+    envelope_fields.insert("payloadType".to_string(), envelope.payload_type.clone());
+    envelope_fields.insert("payload.sha256".to_string(), hex::encode(Sha256::digest(&payload)));
+    for (i, signature) in envelope.signatures.iter().enumerate() {
+        envelope_fields.insert(format!("signatures[{}]", i), signature.sig.clone());
+    }
+    sections.insert(DiffSection::Envelope, envelope_fields);
+
+    let statement = parse_statement(&payload)?;
+    let mut subjects = Fields::new();
+    for subject in &statement.subject {
+        for (algorithm, digest) in &subject.digest {
+            subjects.insert(format!("{}.{}", subject.name, algorithm), digest.clone());
+        }
+    }
+    sections.insert(DiffSection::Subject, subjects);
+
+    let mut predicate = Fields::new();
+    predicate.insert("predicateType".to_string(), statement.predicate_type.clone());
+    flatten("", &statement.predicate, &mut predicate);
+    sections.insert(DiffSection::Predicate, predicate);
+
+    let mut tlog = Fields::new();
+    for (i, entry) in bundle.verification_material.tlog_entries.iter().flatten().enumerate() {
+        let mut entry = serde_json::to_value(entry)?;
+        // The body is long and base64; its digest is enough to spot a change
+        if let Some(body) = entry.get_mut("canonicalizedBody") {
+            let digest = hex::encode(Sha256::digest(body.as_str().unwrap_or_default()));
+            *body = serde_json::json!({ "sha256": digest });
+        }
+        flatten(&format!("[{}]", i), &entry, &mut tlog);
+    }
+    sections.insert(DiffSection::TransparencyLog, tlog);
+
+    Ok(sections)
+}
+
+/// Insert every leaf of `value` under its dotted path
+fn flatten(prefix: &str, value: &Value, fields: &mut Fields) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else if key.starts_with('[') {
+            format!("{}{}", prefix, key)
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                flatten(&join(key), value, fields);
+            }
+        }
+        Value::Array(array) => {
+            for (i, value) in array.iter().enumerate() {
+                flatten(&join(&format!("[{}]", i)), value, fields);
+            }
+        }
+        Value::Null => {}
+        Value::String(string) => {
+            fields.insert(prefix.to_string(), string.clone());
+        }
+        other => {
+            fields.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::bundle::parse_bundle_from_path;
+    use base64::prelude::*;
+
+    fn sample_bundle(name: &str) -> SigstoreBundle {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples");
+        path.push(name);
+        parse_bundle_from_path(&path).unwrap()
+    }
+
+    #[test]
+    fn test_diff_bundles() {
+        let bundle = sample_bundle("actions-attest-build-provenance-attestation-13581567.sigstore.json");
+        assert!(diff_bundles(&bundle, &bundle).unwrap().is_empty());
+
+        let mut statement: Value =
+            serde_json::from_slice(&decode_base64(&bundle.dsse_envelope.payload).unwrap()).unwrap();
+        statement["subject"][0]["digest"]["sha256"] = Value::from("00");
+        statement["predicate"]["runDetails"]["builder"]["id"] = Value::from("https://example.com/builder");
+        let mut tampered = bundle.clone();
+        tampered.dsse_envelope.payload = BASE64_STANDARD.encode(statement.to_string());
+
+        let diff = diff_bundles(&bundle, &tampered).unwrap();
+        let subject_name = statement["subject"][0]["name"].as_str().unwrap();
+        let subject = diff.section(DiffSection::Subject).next().unwrap();
+        assert_eq!(subject.path, format!("{}.sha256", subject_name));
+        assert_eq!(subject.right.as_deref(), Some("00"));
+        let predicate: Vec<_> = diff.section(DiffSection::Predicate).collect();
+        assert_eq!(predicate.len(), 1);
+        assert_eq!(predicate[0].path, "runDetails.builder.id");
+        assert_eq!(diff.section(DiffSection::Envelope).next().unwrap().path, "payload.sha256");
+        assert_eq!(diff.section(DiffSection::Certificate).count(), 0);
+
+        // A different signer and log entry
+        let other = sample_bundle("actions-attest-build-provenance-attestation-13531551.sigstore.json");
+        let diff = diff_bundles(&bundle, &other).unwrap();
+        assert!(diff.section(DiffSection::Certificate).any(|d| d.path == "fingerprint"));
+        assert!(diff.to_string().starts_with("certificate "));
+    }
+}
@@ -0,0 +1,125 @@
+//! `gh attestation download` output
+//!
+//! `gh attestation download` and the GitHub attestations API return one
+//! attestation per line, either a bare bundle or the bundle wrapped in
+//! metadata (`{"bundle": {...}, "repository_id": 123, "bundle_url": ...}`).
+//! `parse_gh_attestations` accepts both forms, so the output of the gh CLI,
+//! including release and SBOM attestations, can be verified line by line.
+
+use serde_json::Value;
+
+use crate::error::VerificationError;
+use crate::parser::bundle::parse_bundle_from_bytes;
+use crate::types::bundle::SigstoreBundle;
+
+/// One attestation from a `gh attestation download` JSONL file
+#[derive(Debug, Clone)]
+pub struct GhAttestation {
+    /// 1-based line number in the input
+    pub line: usize,
+    /// The bundle JSON, to pass to `AttestationVerifier::verify_bundle_bytes`
+    pub bundle_json: Vec<u8>,
+    pub bundle: SigstoreBundle,
+    /// ID of the repository the attestation was stored in, if given
+    pub repository_id: Option<u64>,
+    /// URL the bundle was downloaded from, if given
+    pub bundle_url: Option<String>,
+}
+
+/// Parse every non-empty line of a `gh attestation download` file
+///
+/// # Arguments
+///
+/// * `jsonl` - Lines holding a bundle or a `{"bundle": ...}` wrapper
+///
+/// # Errors
+///
+/// Returns `InvalidBundleFormat` naming the first line that is not valid JSON
+/// or does not hold a bundle.
+///
+/// # Example
+///
+/// ```ignore
+/// for attestation in parse_gh_attestations(&std::fs::read_to_string("sha256:abc.jsonl")?)? {
+///     let result = verifier.verify_bundle_bytes(&attestation.bundle_json, options.clone(), &trust_bundle, tsa_chain)?;
+/// }
+/// ```
+pub fn parse_gh_attestations(jsonl: &str) -> Result<Vec<GhAttestation>, VerificationError> {
+    let mut attestations = Vec::new();
+    for (index, line) in jsonl.lines().enumerate() {
+        if let Some(attestation) = parse_gh_attestation_line(index + 1, line)? {
+            attestations.push(attestation);
+        }
+    }
+    Ok(attestations)
+}
+
+/// Parse one line of a `gh attestation download` file
+///
+/// # Arguments
+///
+/// * `line_number` - 1-based line number, used in errors
+/// * `line` - A bundle or a `{"bundle": ...}` wrapper
+///
+/// # Returns
+///
+/// `None` for an empty line
+pub fn parse_gh_attestation_line(line_number: usize, line: &str) -> Result<Option<GhAttestation>, VerificationError> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let invalid = |message: String| VerificationError::InvalidBundleFormat(format!("line {}: {}", line_number, message));
+
+    let mut value: Value = serde_json::from_str(line).map_err(|e| invalid(e.to_string()))?;
+    let (bundle, repository_id, bundle_url) = match value.get_mut("bundle") {
+        Some(bundle) => {
+            let bundle = bundle.take();
+            (bundle, value["repository_id"].as_u64(), value["bundle_url"].as_str().map(str::to_string))
+        }
+        None => (value, None, None),
+    };
+    let bundle_json = serde_json::to_vec(&bundle).map_err(|e| invalid(e.to_string()))?;
+    let bundle = parse_bundle_from_bytes(&bundle_json).map_err(|e| invalid(e.to_string()))?;
+
+    Ok(Some(GhAttestation {
+        line: line_number,
+        bundle_json,
+        bundle,
+        repository_id,
+        bundle_url,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gh_attestations() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13581567.sigstore.json");
+        let bundle: Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+
+        let wrapped = serde_json::json!({
+            "bundle": bundle,
+            "repository_id": 1234,
+            "bundle_url": "https://example.com/bundle",
+        });
+        let jsonl = format!("{}\n\n{}\n", wrapped, bundle);
+
+        let attestations = parse_gh_attestations(&jsonl).unwrap();
+        assert_eq!(attestations.len(), 2);
+        assert_eq!(attestations[0].line, 1);
+        assert_eq!(attestations[0].repository_id, Some(1234));
+        assert_eq!(attestations[0].bundle_url.as_deref(), Some("https://example.com/bundle"));
+        assert_eq!(attestations[1].line, 3);
+        assert_eq!(attestations[1].repository_id, None);
+        assert_eq!(attestations[0].bundle_json, attestations[1].bundle_json);
+
+        let error = parse_gh_attestations("{\"bundle\": {}}").unwrap_err();
+        assert!(error.to_string().contains("line 1"));
+    }
+}
@@ -1,21 +1,33 @@
-use x509_parser::prelude::*;
-use x509_parser::oid_registry::Oid;
-
 use crate::error::CertificateError;
-use crate::types::certificate::OidcIdentity;
+use crate::types::certificate::{CertificateExtension, FulcioExtensions, OidcIdentity, ParsedCertificate, SubjectAltName};
 
 // OIDC token claim OIDs (1.3.6.1.4.1.57264.1.x)
-const OID_ISSUER: [u64; 9] = [1, 3, 6, 1, 4, 1, 57264, 1, 8]; // Issuer (v2)
-const OID_SOURCE_REPOSITORY_URI: [u64; 9] = [1, 3, 6, 1, 4, 1, 57264, 1, 12];
-const OID_SOURCE_REPOSITORY_REF: [u64; 9] = [1, 3, 6, 1, 4, 1, 57264, 1, 14];
+const OID_ISSUER: &str = "1.3.6.1.4.1.57264.1.8"; // Issuer (v2)
+const OID_BUILD_SIGNER_URI: &str = "1.3.6.1.4.1.57264.1.9";
+const OID_BUILD_SIGNER_DIGEST: &str = "1.3.6.1.4.1.57264.1.10";
+const OID_RUNNER_ENVIRONMENT: &str = "1.3.6.1.4.1.57264.1.11";
+const OID_SOURCE_REPOSITORY_URI: &str = "1.3.6.1.4.1.57264.1.12";
+const OID_SOURCE_REPOSITORY_DIGEST: &str = "1.3.6.1.4.1.57264.1.13";
+const OID_SOURCE_REPOSITORY_REF: &str = "1.3.6.1.4.1.57264.1.14";
+const OID_SOURCE_REPOSITORY_IDENTIFIER: &str = "1.3.6.1.4.1.57264.1.15";
+const OID_SOURCE_REPOSITORY_OWNER_URI: &str = "1.3.6.1.4.1.57264.1.16";
+const OID_SOURCE_REPOSITORY_OWNER_IDENTIFIER: &str = "1.3.6.1.4.1.57264.1.17";
+const OID_BUILD_CONFIG_URI: &str = "1.3.6.1.4.1.57264.1.18";
+const OID_BUILD_CONFIG_DIGEST: &str = "1.3.6.1.4.1.57264.1.19";
+const OID_BUILD_TRIGGER: &str = "1.3.6.1.4.1.57264.1.20";
+const OID_RUN_INVOCATION_URI: &str = "1.3.6.1.4.1.57264.1.21";
+const OID_SOURCE_REPOSITORY_VISIBILITY: &str = "1.3.6.1.4.1.57264.1.22";
 
-// Legacy GitHub workflow OIDs (deprecated but still in use)
-const OID_GITHUB_WORKFLOW_TRIGGER: [u64; 9] = [1, 3, 6, 1, 4, 1, 57264, 1, 2];
-const OID_GITHUB_WORKFLOW_REPOSITORY: [u64; 9] = [1, 3, 6, 1, 4, 1, 57264, 1, 5];
-const OID_GITHUB_WORKFLOW_REF: [u64; 9] = [1, 3, 6, 1, 4, 1, 57264, 1, 6];
+// Legacy OIDs (deprecated but still in use)
+const OID_LEGACY_ISSUER: &str = "1.3.6.1.4.1.57264.1.1"; // Raw string, not DER
+const OID_GITHUB_WORKFLOW_TRIGGER: &str = "1.3.6.1.4.1.57264.1.2";
+const OID_GITHUB_WORKFLOW_SHA: &str = "1.3.6.1.4.1.57264.1.3";
+const OID_GITHUB_WORKFLOW_NAME: &str = "1.3.6.1.4.1.57264.1.4";
+const OID_GITHUB_WORKFLOW_REPOSITORY: &str = "1.3.6.1.4.1.57264.1.5";
+const OID_GITHUB_WORKFLOW_REF: &str = "1.3.6.1.4.1.57264.1.6";
 
 /// Extract OIDC identity from Fulcio certificate extensions
-pub fn extract_oidc_identity(cert: &X509Certificate) -> Result<OidcIdentity, CertificateError> {
+pub fn extract_oidc_identity(cert: &ParsedCertificate) -> Result<OidcIdentity, CertificateError> {
     let mut identity = OidcIdentity {
         issuer: None,
         subject: None,
@@ -25,57 +37,114 @@ pub fn extract_oidc_identity(cert: &X509Certificate) -> Result<OidcIdentity, Cer
     };
 
     // Extract subject from SAN (Subject Alternative Name)
-    if let Some(san_ext) = cert.subject_alternative_name().ok().and_then(|x| x) {
-        // The subject is typically in the URI field
-        for name in &san_ext.value.general_names {
-            if let x509_parser::extensions::GeneralName::RFC822Name(email) = name {
-                identity.subject = Some(email.to_string());
-            } else if let x509_parser::extensions::GeneralName::URI(uri) = name {
-                // For some OIDC providers, subject is in URI
+    for name in &cert.subject_alt_names {
+        match name {
+            SubjectAltName::Email(email) => identity.subject = Some(email.clone()),
+            // For some OIDC providers, subject is in URI
+            SubjectAltName::Uri(uri) => {
                 if identity.subject.is_none() {
-                    identity.subject = Some(uri.to_string());
+                    identity.subject = Some(uri.clone());
                 }
             }
         }
     }
 
     // Extract custom Fulcio extensions
-    for ext in cert.extensions() {
-        let oid = &ext.oid;
+    let mut legacy_issuer = None;
+    for ext in &cert.extensions {
+        let oid = ext.oid.as_str();
 
         // Match against known OIDs
-        if oid_equals(oid, &OID_ISSUER) {
+        if oid == OID_ISSUER {
             identity.issuer = extract_string_from_extension(ext)?;
-        } else if oid_equals(oid, &OID_SOURCE_REPOSITORY_URI) || oid_equals(oid, &OID_GITHUB_WORKFLOW_REPOSITORY) {
+        } else if oid == OID_LEGACY_ISSUER {
+            legacy_issuer = extract_string_from_extension(ext)?;
+        } else if oid == OID_SOURCE_REPOSITORY_URI || oid == OID_GITHUB_WORKFLOW_REPOSITORY {
             identity.repository = extract_string_from_extension(ext)?;
-        } else if oid_equals(oid, &OID_SOURCE_REPOSITORY_REF) || oid_equals(oid, &OID_GITHUB_WORKFLOW_REF) {
+        } else if oid == OID_SOURCE_REPOSITORY_REF || oid == OID_GITHUB_WORKFLOW_REF {
             identity.workflow_ref = extract_string_from_extension(ext)?;
-        } else if oid_equals(oid, &OID_GITHUB_WORKFLOW_TRIGGER) {
+        } else if oid == OID_GITHUB_WORKFLOW_TRIGGER {
             identity.event_name = extract_string_from_extension(ext)?;
         }
     }
 
+    // Certificates issued before the v2 issuer extension only carry 1.1
+    if identity.issuer.is_none() {
+        identity.issuer = legacy_issuer;
+    }
+
     Ok(identity)
 }
 
-fn oid_equals(oid: &Oid, expected: &[u64]) -> bool {
-    if let Some(mut iter) = oid.iter() {
-        for &expected_val in expected {
-            match iter.next() {
-                Some(val) if val == expected_val => continue,
-                _ => return false,
-            }
-        }
-        iter.next().is_none() // Ensure no extra components
-    } else {
-        false
+/// Extract all known Fulcio extensions from a certificate
+///
+/// Unlike `extract_oidc_identity`, this keeps every extension value separately so
+/// callers can apply provider-specific checks (GitHub, GitLab, Cloud Build).
+pub fn extract_fulcio_extensions(cert: &ParsedCertificate) -> Result<FulcioExtensions, CertificateError> {
+    let mut extensions = FulcioExtensions::default();
+    let mut legacy_issuer = None;
+
+    for ext in &cert.extensions {
+        let oid = ext.oid.as_str();
+        let slot = if oid == OID_ISSUER {
+            &mut extensions.issuer
+        } else if oid == OID_LEGACY_ISSUER {
+            &mut legacy_issuer
+        } else if oid == OID_BUILD_SIGNER_URI {
+            &mut extensions.build_signer_uri
+        } else if oid == OID_BUILD_SIGNER_DIGEST {
+            &mut extensions.build_signer_digest
+        } else if oid == OID_RUNNER_ENVIRONMENT {
+            &mut extensions.runner_environment
+        } else if oid == OID_SOURCE_REPOSITORY_URI {
+            &mut extensions.source_repository_uri
+        } else if oid == OID_SOURCE_REPOSITORY_DIGEST {
+            &mut extensions.source_repository_digest
+        } else if oid == OID_SOURCE_REPOSITORY_REF {
+            &mut extensions.source_repository_ref
+        } else if oid == OID_SOURCE_REPOSITORY_IDENTIFIER {
+            &mut extensions.source_repository_identifier
+        } else if oid == OID_SOURCE_REPOSITORY_OWNER_URI {
+            &mut extensions.source_repository_owner_uri
+        } else if oid == OID_SOURCE_REPOSITORY_OWNER_IDENTIFIER {
+            &mut extensions.source_repository_owner_identifier
+        } else if oid == OID_BUILD_CONFIG_URI {
+            &mut extensions.build_config_uri
+        } else if oid == OID_BUILD_CONFIG_DIGEST {
+            &mut extensions.build_config_digest
+        } else if oid == OID_BUILD_TRIGGER {
+            &mut extensions.build_trigger
+        } else if oid == OID_RUN_INVOCATION_URI {
+            &mut extensions.run_invocation_uri
+        } else if oid == OID_SOURCE_REPOSITORY_VISIBILITY {
+            &mut extensions.source_repository_visibility_at_signing
+        } else if oid == OID_GITHUB_WORKFLOW_TRIGGER {
+            &mut extensions.github_workflow_trigger
+        } else if oid == OID_GITHUB_WORKFLOW_SHA {
+            &mut extensions.github_workflow_sha
+        } else if oid == OID_GITHUB_WORKFLOW_NAME {
+            &mut extensions.github_workflow_name
+        } else if oid == OID_GITHUB_WORKFLOW_REPOSITORY {
+            &mut extensions.github_workflow_repository
+        } else if oid == OID_GITHUB_WORKFLOW_REF {
+            &mut extensions.github_workflow_ref
+        } else {
+            continue;
+        };
+        *slot = extract_string_from_extension(ext)?;
+    }
+
+    if extensions.issuer.is_none() {
+        extensions.issuer = legacy_issuer;
     }
+
+    Ok(extensions)
 }
 
-fn extract_string_from_extension(ext: &X509Extension) -> Result<Option<String>, CertificateError> {
-    // x509_parser already unwraps the OCTET STRING for us, so ext.value contains
-    // the inner bytes directly (e.g., UTF8String, IA5String, etc.)
-    let bytes = ext.value;
+fn extract_string_from_extension(ext: &CertificateExtension) -> Result<Option<String>, CertificateError> {
+    // The OCTET STRING is already unwrapped, so ext.value contains the inner
+    // bytes directly (e.g., UTF8String, IA5String, etc.)
+    let bytes = ext.value.as_slice();
 
     if bytes.is_empty() {
         return Ok(None);
@@ -85,12 +154,16 @@ fn extract_string_from_extension(ext: &X509Extension) -> Result<Option<String>,
     // Check if it starts with a known string tag
     if bytes.len() > 2 {
         let tag = bytes[0];
-        let len = bytes[1] as usize;
 
         // UTF8String (0x0C) or IA5String (0x16) or PrintableString (0x13)
-        if (tag == 0x0C || tag == 0x16 || tag == 0x13) && len + 2 <= bytes.len() {
-            if let Ok(s) = std::str::from_utf8(&bytes[2..2 + len]) {
-                return Ok(Some(s.to_string()));
+        if tag == 0x0C || tag == 0x16 || tag == 0x13 {
+            if let Some((header_len, len)) = parse_der_length(&bytes[1..]) {
+                let start = 1 + header_len;
+                if start + len <= bytes.len() {
+                    if let Ok(s) = std::str::from_utf8(&bytes[start..start + len]) {
+                        return Ok(Some(s.to_string()));
+                    }
+                }
             }
         }
     }
@@ -102,3 +175,36 @@ fn extract_string_from_extension(ext: &X509Extension) -> Result<Option<String>,
 
     Ok(None)
 }
+
+/// Parse a DER length, returning (length octets consumed, content length)
+///
+/// Values longer than 127 bytes (e.g., long build config URIs) use the long form.
+fn parse_der_length(bytes: &[u8]) -> Option<(usize, usize)> {
+    let first = *bytes.first()?;
+    if first < 0x80 {
+        return Some((1, first as usize));
+    }
+
+    let num_octets = (first & 0x7F) as usize;
+    if num_octets == 0 || num_octets > 4 || bytes.len() < 1 + num_octets {
+        return None;
+    }
+    let len = bytes[1..1 + num_octets]
+        .iter()
+        .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+    Some((1 + num_octets, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_der_length() {
+        assert_eq!(parse_der_length(&[0x05]), Some((1, 5)));
+        assert_eq!(parse_der_length(&[0x81, 0x90]), Some((2, 0x90)));
+        assert_eq!(parse_der_length(&[0x82, 0x01, 0x00]), Some((3, 256)));
+        assert_eq!(parse_der_length(&[0x80]), None);
+        assert_eq!(parse_der_length(&[0x82, 0x01]), None);
+    }
+}
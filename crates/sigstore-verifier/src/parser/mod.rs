@@ -1,5 +1,27 @@
 pub mod bundle;
+#[cfg(feature = "full")]
 pub mod certificate;
+#[cfg(feature = "full")]
+pub mod checkpoint;
+pub mod der;
+#[cfg(feature = "full")]
+pub mod diff;
+#[cfg(feature = "full")]
+pub mod gh_attestation;
+#[cfg(feature = "full")]
 pub mod identity;
+#[cfg(feature = "full")]
+pub mod payload;
+#[cfg(feature = "full")]
+pub mod rekor_body;
+#[cfg(feature = "full")]
 pub mod rfc3161;
+#[cfg(feature = "full")]
+pub mod simple_signing;
+#[cfg(feature = "full")]
 pub mod timestamp;
+#[cfg(feature = "full")]
+pub mod x509;
+
+#[cfg(feature = "full")]
+pub use diff::{diff_bundles, BundleDiff};
@@ -0,0 +1,133 @@
+//! DSSE payload parsers
+//!
+//! Verification reads the subject digest and predicate type from the in-toto
+//! `Statement` in a bundle's DSSE envelope. Envelopes with another
+//! `payloadType`, such as internal compliance statements, can still be verified
+//! by registering a `PayloadParser` that maps their payload to a `Statement`;
+//! signature, certificate and transparency log checks are unchanged.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::VerificationError;
+use crate::parser::bundle::parse_statement;
+use crate::types::dsse::Statement;
+
+pub use crate::types::dsse::IN_TOTO_PAYLOAD_TYPE;
+
+/// Turns a decoded DSSE payload into a `Statement`
+pub trait PayloadParser: Send + Sync {
+    /// Parse `payload`
+    ///
+    /// # Errors
+    ///
+    /// Any error rejects the bundle.
+    fn parse(&self, payload: &[u8]) -> Result<Statement, VerificationError>;
+}
+
+impl<F> PayloadParser for F
+where
+    F: Fn(&[u8]) -> Result<Statement, VerificationError> + Send + Sync,
+{
+    fn parse(&self, payload: &[u8]) -> Result<Statement, VerificationError> {
+        self(payload)
+    }
+}
+
+/// Parser for in-toto statements
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InTotoParser;
+
+impl PayloadParser for InTotoParser {
+    fn parse(&self, payload: &[u8]) -> Result<Statement, VerificationError> {
+        parse_statement(payload)
+    }
+}
+
+/// Payload parsers keyed by DSSE `payloadType`
+///
+/// Payload types without a registered parser are parsed as in-toto
+/// statements, so registering parsers only ever widens what is accepted.
+#[derive(Clone, Default)]
+pub struct PayloadParserRegistry {
+    parsers: HashMap<String, Arc<dyn PayloadParser>>,
+}
+
+impl std::fmt::Debug for PayloadParserRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.parsers.keys()).finish()
+    }
+}
+
+impl PayloadParserRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse payloads of type `payload_type` with `parser`, replacing any
+    /// parser registered for it
+    pub fn register(&mut self, payload_type: impl Into<String>, parser: Arc<dyn PayloadParser>) {
+        self.parsers.insert(payload_type.into(), parser);
+    }
+
+    /// Parser registered for `payload_type`
+    pub fn get(&self, payload_type: &str) -> Option<&dyn PayloadParser> {
+        self.parsers.get(payload_type).map(|parser| parser.as_ref())
+    }
+
+    /// Parse a payload with the parser registered for its type, or as an
+    /// in-toto statement if there is none
+    pub fn parse(&self, payload_type: &str, payload: &[u8]) -> Result<Statement, VerificationError> {
+        match self.get(payload_type) {
+            Some(parser) => parser.parse(payload),
+            None => InTotoParser.parse(payload),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parsers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::dsse::Subject;
+
+    const COMPLIANCE_TYPE: &str = "application/vnd.example.compliance+json";
+
+    /// `{"artifact": "<name>", "sha256": "<hex>", "control": "<id>"}`
+    fn parse_compliance(payload: &[u8]) -> Result<Statement, VerificationError> {
+        let value: serde_json::Value = serde_json::from_slice(payload)?;
+        let field = |name: &str| {
+            value[name]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| VerificationError::InvalidBundleFormat(format!("missing {}", name)))
+        };
+        Ok(Statement {
+            statement_type: IN_TOTO_PAYLOAD_TYPE.to_string(),
+            subject: vec![Subject {
+                name: field("artifact")?,
+                digest: HashMap::from([("sha256".to_string(), field("sha256")?)]),
+            }],
+            predicate_type: COMPLIANCE_TYPE.to_string(),
+            predicate: value,
+        })
+    }
+
+    #[test]
+    fn test_payload_parser_registry() {
+        let mut registry = PayloadParserRegistry::new();
+        registry.register(COMPLIANCE_TYPE, Arc::new(parse_compliance));
+
+        let payload = br#"{"artifact": "app.tar", "sha256": "ab", "control": "SC-7"}"#;
+        let statement = registry.parse(COMPLIANCE_TYPE, payload).unwrap();
+        assert_eq!(statement.get_subject_digest("sha256").as_deref(), Some("ab"));
+        assert_eq!(statement.predicate["control"], "SC-7");
+
+        // Unregistered types fall back to in-toto, which rejects this payload
+        assert!(registry.parse(IN_TOTO_PAYLOAD_TYPE, payload).is_err());
+        assert!(registry.parse(COMPLIANCE_TYPE, b"{}").is_err());
+    }
+}
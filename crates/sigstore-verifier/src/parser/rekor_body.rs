@@ -0,0 +1,414 @@
+//! Canonicalized Rekor entry bodies
+//!
+//! A transparency log entry carries the body Rekor stored for it as base64
+//! JSON (`canonicalizedBody`), whose shape depends on the entry's
+//! `kindVersion`. This module parses the bodies of the kinds Sigstore clients
+//! upload — `dsse` 0.0.1, `intoto` 0.0.2 and `hashedrekord` 0.0.1 on Rekor v1,
+//! `dsse` 0.0.2 and `hashedrekord` 0.0.2 on Rekor v2 — into typed models so
+//! callers can inspect hashes, signatures and verifiers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{DigestError, TransparencyError, VerificationError};
+use crate::parser::bundle::decode_base64;
+use crate::types::bundle::TransparencyLogEntry;
+use crate::types::digest::{Digest, DigestAlgorithm};
+
+/// Hash as Rekor records it: algorithm name and lowercase hex value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RekorHash {
+    pub algorithm: String,
+    pub value: String,
+}
+
+impl RekorHash {
+    /// Parse into a `Digest`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown algorithms, invalid hex or a wrong length.
+    pub fn to_digest(&self) -> Result<Digest, DigestError> {
+        let algorithm = DigestAlgorithm::from_name(&self.algorithm)
+            .ok_or_else(|| DigestError::UnknownAlgorithm(self.algorithm.clone()))?;
+        Digest::from_hex(algorithm, &self.value)
+    }
+}
+
+/// Hash as Rekor v2 records it: algorithm enum name (e.g. `SHA2_256`) and
+/// base64 digest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashOutput {
+    pub algorithm: String,
+    pub digest: String,
+}
+
+impl HashOutput {
+    /// Parse into a `Digest`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown algorithms, invalid base64 or a wrong length.
+    pub fn to_digest(&self) -> Result<Digest, DigestError> {
+        let algorithm = match self.algorithm.as_str() {
+            "SHA2_256" => DigestAlgorithm::Sha256,
+            "SHA2_384" => DigestAlgorithm::Sha384,
+            "SHA2_512" => DigestAlgorithm::Sha512,
+            other => return Err(DigestError::UnknownAlgorithm(other.to_string())),
+        };
+        let bytes = decode_base64(&self.digest).map_err(|e| DigestError::InvalidBase64(e.to_string()))?;
+        Digest::new(algorithm, bytes)
+    }
+}
+
+/// `dsse` 0.0.1 spec
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DsseV001 {
+    /// Hash of the canonical DSSE envelope
+    pub envelope_hash: Option<RekorHash>,
+    /// Hash of the DSSE payload
+    pub payload_hash: Option<RekorHash>,
+    pub signatures: Vec<DsseSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DsseSignature {
+    /// Base64 signature over the DSSE PAE
+    pub signature: String,
+    /// Base64 PEM of the verifying certificate or public key
+    pub verifier: String,
+}
+
+/// `dsse` 0.0.2 spec (Rekor v2)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DsseV002 {
+    /// Hash of the DSSE payload
+    pub payload_hash: HashOutput,
+    pub signatures: Vec<SignatureV002>,
+}
+
+/// Signature and verifier of a Rekor v2 entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureV002 {
+    /// Base64 signature
+    pub content: String,
+    pub verifier: VerifierV002,
+}
+
+/// Verifier of a Rekor v2 signature: a public key or an X.509 certificate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifierV002 {
+    pub public_key: Option<RawBytes>,
+    pub x509_certificate: Option<RawBytes>,
+    /// Key type and signature algorithm, e.g. `PKIX_ECDSA_P256_SHA_256`
+    pub key_details: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawBytes {
+    /// Base64 DER
+    pub raw_bytes: String,
+}
+
+/// `intoto` 0.0.2 spec
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntotoV002 {
+    pub content: IntotoContent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntotoContent {
+    /// Envelope without its payload, which Rekor does not store
+    pub envelope: Option<IntotoEnvelope>,
+    /// Hash of the canonical envelope
+    pub hash: Option<RekorHash>,
+    /// Hash of the envelope payload
+    pub payload_hash: Option<RekorHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntotoEnvelope {
+    pub payload: Option<String>,
+    pub payload_type: String,
+    pub signatures: Vec<IntotoSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntotoSignature {
+    pub keyid: Option<String>,
+    /// Base64 of the base64 signature (Rekor encodes it twice)
+    pub sig: String,
+    /// Base64 PEM of the verifying certificate or public key
+    pub public_key: String,
+}
+
+/// `hashedrekord` 0.0.1 spec
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashedRekordV001 {
+    pub data: HashedRekordData,
+    pub signature: HashedRekordSignature,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashedRekordData {
+    /// Hash of the signed artifact
+    pub hash: Option<RekorHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashedRekordSignature {
+    /// Base64 signature over the artifact
+    pub content: Option<String>,
+    pub public_key: Option<HashedRekordPublicKey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashedRekordPublicKey {
+    /// Base64 PEM of the verifying certificate or public key
+    pub content: Option<String>,
+}
+
+/// `hashedrekord` 0.0.2 spec (Rekor v2)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashedRekordV002 {
+    /// Hash of the signed artifact
+    pub data: HashOutput,
+    pub signature: SignatureV002,
+}
+
+/// Typed canonicalized body of a Rekor entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RekorBody {
+    DsseV001(DsseV001),
+    IntotoV002(IntotoV002),
+    HashedRekordV001(HashedRekordV001),
+    DsseV002(DsseV002),
+    HashedRekordV002(HashedRekordV002),
+}
+
+impl RekorBody {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RekorBody::DsseV001(_) | RekorBody::DsseV002(_) => "dsse",
+            RekorBody::IntotoV002(_) => "intoto",
+            RekorBody::HashedRekordV001(_) | RekorBody::HashedRekordV002(_) => "hashedrekord",
+        }
+    }
+
+    pub fn api_version(&self) -> &'static str {
+        match self {
+            RekorBody::DsseV001(_) => "0.0.1",
+            RekorBody::IntotoV002(_) => "0.0.2",
+            RekorBody::HashedRekordV001(_) => "0.0.1",
+            RekorBody::DsseV002(_) | RekorBody::HashedRekordV002(_) => "0.0.2",
+        }
+    }
+
+    /// Hash of the signed content of a Rekor v1 body: the DSSE payload for
+    /// `dsse` and `intoto`, the artifact for `hashedrekord`
+    ///
+    /// Returns `None` for Rekor v2 bodies; use [`RekorBody::payload_digest`].
+    pub fn payload_hash(&self) -> Option<&RekorHash> {
+        match self {
+            RekorBody::DsseV001(spec) => spec.payload_hash.as_ref(),
+            RekorBody::IntotoV002(spec) => spec.content.payload_hash.as_ref(),
+            RekorBody::HashedRekordV001(spec) => spec.data.hash.as_ref(),
+            RekorBody::DsseV002(_) | RekorBody::HashedRekordV002(_) => None,
+        }
+    }
+
+    /// Digest of the signed content for any body version
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recorded hash cannot be parsed.
+    pub fn payload_digest(&self) -> Result<Option<Digest>, DigestError> {
+        match self {
+            RekorBody::DsseV002(spec) => spec.payload_hash.to_digest().map(Some),
+            RekorBody::HashedRekordV002(spec) => spec.data.to_digest().map(Some),
+            _ => self.payload_hash().map(RekorHash::to_digest).transpose(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DsseV002Spec {
+    dsse_v002: DsseV002,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HashedRekordV002Spec {
+    hashed_rekord_v002: HashedRekordV002,
+}
+
+/// Envelope shared by all entry kinds
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBody {
+    api_version: String,
+    kind: String,
+    spec: serde_json::Value,
+}
+
+/// Parse a decoded canonicalized body
+///
+/// # Errors
+///
+/// Returns `UnsupportedEntryKind` for other kinds or versions and
+/// `InvalidEntryBody` if the JSON does not match the kind's schema.
+pub fn parse_rekor_body(body: &[u8]) -> Result<RekorBody, VerificationError> {
+    let raw: RawBody = serde_json::from_slice(body).map_err(invalid_body)?;
+    let body = match (raw.kind.as_str(), raw.api_version.as_str()) {
+        ("dsse", "0.0.1") => RekorBody::DsseV001(serde_json::from_value(raw.spec).map_err(invalid_body)?),
+        ("intoto", "0.0.2") => RekorBody::IntotoV002(serde_json::from_value(raw.spec).map_err(invalid_body)?),
+        ("hashedrekord", "0.0.1") => {
+            RekorBody::HashedRekordV001(serde_json::from_value(raw.spec).map_err(invalid_body)?)
+        }
+        ("dsse", "0.0.2") => {
+            let spec: DsseV002Spec = serde_json::from_value(raw.spec).map_err(invalid_body)?;
+            RekorBody::DsseV002(spec.dsse_v002)
+        }
+        ("hashedrekord", "0.0.2") => {
+            let spec: HashedRekordV002Spec = serde_json::from_value(raw.spec).map_err(invalid_body)?;
+            RekorBody::HashedRekordV002(spec.hashed_rekord_v002)
+        }
+        _ => {
+            return Err(TransparencyError::UnsupportedEntryKind {
+                kind: raw.kind,
+                version: raw.api_version,
+            }
+            .into())
+        }
+    };
+    Ok(body)
+}
+
+/// Decode and parse the canonicalized body of a bundle's tlog entry
+///
+/// # Errors
+///
+/// In addition to the errors of [`parse_rekor_body`], returns
+/// `EntryKindMismatch` if the entry's `kindVersion` names a different kind or
+/// version than the body.
+pub fn parse_entry_body(entry: &TransparencyLogEntry) -> Result<RekorBody, VerificationError> {
+    let body = parse_rekor_body(&decode_base64(&entry.canonicalized_body)?)?;
+    if let Some(kind_version) = &entry.kind_version {
+        if kind_version.kind != body.kind() || kind_version.version != body.api_version() {
+            return Err(TransparencyError::EntryKindMismatch {
+                declared: format!("{} {}", kind_version.kind, kind_version.version),
+                body: format!("{} {}", body.kind(), body.api_version()),
+            }
+            .into());
+        }
+    }
+    Ok(body)
+}
+
+fn invalid_body(e: serde_json::Error) -> VerificationError {
+    TransparencyError::InvalidEntryBody(e.to_string()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::bundle::parse_bundle_from_path;
+    use crate::types::bundle::KindVersion;
+    use std::path::PathBuf;
+
+    fn sample_entry() -> TransparencyLogEntry {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        let bundle = parse_bundle_from_path(&path).unwrap();
+        bundle.verification_material.tlog_entries.unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_parse_dsse_entry_body() {
+        let body = parse_entry_body(&sample_entry()).unwrap();
+        let RekorBody::DsseV001(spec) = &body else {
+            panic!("expected a dsse body, got {}", body.kind());
+        };
+        assert_eq!(spec.signatures.len(), 1);
+        let payload_hash = body.payload_hash().unwrap().to_digest().unwrap();
+        assert_eq!(
+            payload_hash.to_string(),
+            "sha256:34a54440586ec49735b8953a3406d9a8ef6186e066268e4a64cdae95cac08731"
+        );
+    }
+
+    #[test]
+    fn test_parse_entry_body_kind_mismatch() {
+        let mut entry = sample_entry();
+        entry.kind_version = Some(KindVersion {
+            kind: "intoto".to_string(),
+            version: "0.0.2".to_string(),
+        });
+        let err = parse_entry_body(&entry).unwrap_err();
+        assert!(matches!(
+            err,
+            VerificationError::Transparency(TransparencyError::EntryKindMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_intoto_body() {
+        let body = br#"{"apiVersion":"0.0.2","kind":"intoto","spec":{"content":{"envelope":{"payloadType":"application/vnd.in-toto+json","signatures":[{"publicKey":"cGVt","sig":"YzJsbg=="}]},"hash":{"algorithm":"sha256","value":"aa"},"payloadHash":{"algorithm":"sha256","value":"bb"}}}}"#;
+        let RekorBody::IntotoV002(spec) = parse_rekor_body(body).unwrap() else {
+            panic!("expected an intoto body");
+        };
+        let envelope = spec.content.envelope.unwrap();
+        assert_eq!(envelope.payload_type, "application/vnd.in-toto+json");
+        assert_eq!(envelope.signatures[0].public_key, "cGVt");
+        assert_eq!(spec.content.payload_hash.unwrap().value, "bb");
+    }
+
+    #[test]
+    fn test_parse_hashedrekord_body() {
+        let body = br#"{"apiVersion":"0.0.1","kind":"hashedrekord","spec":{"data":{"hash":{"algorithm":"sha256","value":"cc"}},"signature":{"content":"c2ln","publicKey":{"content":"cGVt"}}}}"#;
+        let body = parse_rekor_body(body).unwrap();
+        assert_eq!(body.kind(), "hashedrekord");
+        assert_eq!(body.payload_hash().unwrap().value, "cc");
+    }
+
+    #[test]
+    fn test_parse_rekor_v2_bodies() {
+        let body = br#"{"apiVersion":"0.0.2","kind":"dsse","spec":{"dsseV002":{"payloadHash":{"algorithm":"SHA2_256","digest":"NKVEQFhuxJc1uJU6NAbZqO9hhuBmJo5KZM2ulcrAhzE="},"signatures":[{"content":"c2ln","verifier":{"keyDetails":"PKIX_ECDSA_P256_SHA_256","x509Certificate":{"rawBytes":"Y2VydA=="}}}]}}}"#;
+        let body = parse_rekor_body(body).unwrap();
+        let RekorBody::DsseV002(spec) = &body else {
+            panic!("expected a dsse v0.0.2 body, got {}", body.kind());
+        };
+        assert_eq!(body.api_version(), "0.0.2");
+        assert_eq!(spec.signatures[0].verifier.x509_certificate.as_ref().unwrap().raw_bytes, "Y2VydA==");
+        assert!(body.payload_hash().is_none());
+        assert_eq!(
+            body.payload_digest().unwrap().unwrap().to_string(),
+            "sha256:34a54440586ec49735b8953a3406d9a8ef6186e066268e4a64cdae95cac08731"
+        );
+
+        let body = br#"{"apiVersion":"0.0.2","kind":"hashedrekord","spec":{"hashedRekordV002":{"data":{"algorithm":"SHA2_512","digest":"AA=="},"signature":{"content":"c2ln","verifier":{"publicKey":{"rawBytes":"cGVt"}}}}}}"#;
+        let body = parse_rekor_body(body).unwrap();
+        assert_eq!(body.kind(), "hashedrekord");
+        assert!(matches!(body.payload_digest(), Err(DigestError::InvalidLength { expected: 64, .. })));
+    }
+
+    #[test]
+    fn test_parse_unsupported_body() {
+        let body = br#"{"apiVersion":"0.0.1","kind":"rekord","spec":{}}"#;
+        let err = parse_rekor_body(body).unwrap_err();
+        assert!(matches!(
+            err,
+            VerificationError::Transparency(TransparencyError::UnsupportedEntryKind { .. })
+        ));
+    }
+}
@@ -1,10 +1,10 @@
-use chrono::{DateTime, TimeZone, Utc};
 use cms::content_info::ContentInfo;
 use cms::signed_data::SignedData;
 use der::{Decode, Encode};
 use sha2::{Digest, Sha256, Sha384};
 
 use crate::error::TimestampError;
+use crate::types::time::Timestamp;
 
 /// Hash algorithm used in message imprint
 #[derive(Debug, Clone, PartialEq)]
@@ -30,11 +30,52 @@ pub struct MessageImprint {
     pub hashed_message: Vec<u8>,
 }
 
+/// Accuracy of a TSTInfo `genTime` (RFC 3161 §2.4.2)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Accuracy {
+    pub seconds: u64,
+    pub millis: u64,
+    pub micros: u64,
+}
+
+impl Accuracy {
+    /// Total accuracy in microseconds
+    pub fn as_micros(&self) -> u64 {
+        self.seconds
+            .saturating_mul(1_000_000)
+            .saturating_add(self.millis.saturating_mul(1_000))
+            .saturating_add(self.micros)
+    }
+}
+
 /// Parsed RFC 3161 timestamp information
 #[derive(Debug, Clone)]
 pub struct TSTInfo {
-    pub gen_time: DateTime<Utc>,
+    /// TSA policy under which the token was issued (dotted OID)
+    pub policy: String,
+    pub gen_time: Timestamp,
     pub message_imprint: MessageImprint,
+    /// Time deviation around `gen_time`, if the TSA states one
+    pub accuracy: Option<Accuracy>,
+    /// Whether tokens from this TSA can be ordered by `gen_time` alone
+    pub ordering: bool,
+    /// Nonce from the request (big-endian, without leading zero bytes)
+    pub nonce: Option<Vec<u8>>,
+}
+
+impl TSTInfo {
+    /// Earliest and latest time the token may have been issued, per `accuracy`
+    ///
+    /// A token without accuracy yields `gen_time` for both bounds.
+    pub fn time_window(&self) -> (Timestamp, Timestamp) {
+        // Timestamps have second precision, so round the accuracy up
+        let accuracy = self.accuracy.map_or(0, |accuracy| accuracy.as_micros().div_ceil(1_000_000));
+        let accuracy = i64::try_from(accuracy).unwrap_or(i64::MAX);
+        (
+            self.gen_time.saturating_sub_seconds(accuracy),
+            self.gen_time.saturating_add_seconds(accuracy),
+        )
+    }
 }
 
 /// Parsed RFC 3161 timestamp token with optional embedded certificates
@@ -88,6 +129,51 @@ fn extract_timestamp_token_from_resp(der: &[u8]) -> Result<ContentInfo, Timestam
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse ContentInfo from TimeStampResp: {}", e)))
 }
 
+/// Check the status of a TimeStampResp and return its TimeStampToken
+///
+/// PKIStatusInfo ::= SEQUENCE {
+///     status       PKIStatus,
+///     statusString PKIFreeText OPTIONAL,
+///     failInfo     PKIFailureInfo OPTIONAL
+/// }
+///
+/// # Errors
+///
+/// Returns `Rfc3161Rejected` unless the status is `granted` (0) or
+/// `grantedWithMods` (1), with the TSA's status text if it sent one.
+pub fn extract_granted_timestamp_token(der: &[u8]) -> Result<Vec<u8>, TimestampError> {
+    use asn1_rs::{FromDer, Integer, Sequence, Utf8String};
+
+    let (_, resp_seq) = Sequence::from_der(der)
+        .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse TimeStampResp: {}", e)))?;
+    let (token, status_info) = Sequence::from_der(resp_seq.content.as_ref())
+        .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse PKIStatusInfo: {}", e)))?;
+    let (rem, status) = Integer::from_der(status_info.content.as_ref())
+        .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse PKIStatus: {}", e)))?;
+    let status = status
+        .as_u64()
+        .map_err(|e| TimestampError::Rfc3161Parse(format!("Invalid PKIStatus: {}", e)))?;
+
+    if status > 1 {
+        // PKIFreeText ::= SEQUENCE OF UTF8String
+        let message = Sequence::from_der(rem)
+            .ok()
+            .and_then(|(_, text)| {
+                Utf8String::from_der(text.content.as_ref())
+                    .ok()
+                    .map(|(_, text)| text.string())
+            })
+            .unwrap_or_default();
+        return Err(TimestampError::Rfc3161Rejected { status, message });
+    }
+    if token.is_empty() {
+        return Err(TimestampError::Rfc3161Parse(
+            "No TimeStampToken found in TimeStampResp".to_string(),
+        ));
+    }
+    Ok(token.to_vec())
+}
+
 /// Parse an RFC 3161 timestamp token from DER-encoded bytes
 ///
 /// This parses the CMS ContentInfo structure and extracts:
@@ -168,10 +254,13 @@ fn parse_tstinfo(signed_data: &SignedData) -> Result<TSTInfo, TimestampError> {
 ///   messageImprint MessageImprint,
 ///   serialNumber INTEGER,
 ///   genTime GeneralizedTime,
+///   accuracy Accuracy OPTIONAL,
+///   ordering BOOLEAN DEFAULT FALSE,
+///   nonce INTEGER OPTIONAL,
 ///   ...
 /// }
 fn parse_tstinfo_asn1(der: &[u8]) -> Result<TSTInfo, TimestampError> {
-    use asn1_rs::{FromDer, Integer, Sequence, Any};
+    use asn1_rs::{FromDer, Integer, Oid, Sequence, Any};
 
     let (_rem, tstinfo_seq) = Sequence::from_der(der)
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse TSTInfo sequence: {}", e)))?;
@@ -183,8 +272,8 @@ fn parse_tstinfo_asn1(der: &[u8]) -> Result<TSTInfo, TimestampError> {
     let (rem, _version) = Integer::from_der(content)
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse version: {}", e)))?;
 
-    // Skip policy (OID)
-    let (rem, _policy) = Any::from_der(rem)
+    // Parse policy (OID)
+    let (rem, policy) = Oid::from_der(rem)
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse policy: {}", e)))?;
 
     // Parse messageImprint (SEQUENCE)
@@ -199,19 +288,95 @@ fn parse_tstinfo_asn1(der: &[u8]) -> Result<TSTInfo, TimestampError> {
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse serialNumber: {}", e)))?;
 
     // Parse genTime (GeneralizedTime)
-    let (_, gen_time_obj) = Any::from_der(rem)
+    let (mut rem, gen_time_obj) = Any::from_der(rem)
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse genTime: {}", e)))?;
 
     // Use data() to get the actual content bytes without tag/length
     let gen_time = parse_generalized_time_value(gen_time_obj.data)
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse GeneralizedTime: {}", e)))?;
 
+    // Parse the optional fields, identified by their tags; tsa [0] and
+    // extensions [1] are skipped
+    let mut accuracy = None;
+    let mut ordering = false;
+    let mut nonce = None;
+    while !rem.is_empty() {
+        let (next, field) = Any::from_der(rem)
+            .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse TSTInfo field: {}", e)))?;
+        rem = next;
+        if field.class() != asn1_rs::Class::Universal {
+            continue;
+        }
+        match field.tag() {
+            asn1_rs::Tag::Sequence => accuracy = Some(parse_accuracy(field.data)?),
+            asn1_rs::Tag::Boolean => ordering = field.data.first().is_some_and(|byte| *byte != 0),
+            asn1_rs::Tag::Integer => nonce = Some(unsigned_integer_bytes(field.data).to_vec()),
+            _ => {}
+        }
+    }
+
     Ok(TSTInfo {
+        policy: policy.to_id_string(),
         gen_time,
         message_imprint,
+        accuracy,
+        ordering,
+        nonce,
     })
 }
 
+/// Parse the content of an Accuracy SEQUENCE
+///
+/// Accuracy ::= SEQUENCE {
+///   seconds INTEGER OPTIONAL,
+///   millis [0] INTEGER (1..999) OPTIONAL,
+///   micros [1] INTEGER (1..999) OPTIONAL
+/// }
+fn parse_accuracy(mut content: &[u8]) -> Result<Accuracy, TimestampError> {
+    use asn1_rs::{Any, Class, FromDer, Tag};
+
+    let mut accuracy = Accuracy::default();
+    while !content.is_empty() {
+        let (rem, field) = Any::from_der(content)
+            .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse accuracy: {}", e)))?;
+        content = rem;
+        let value = integer_to_u64(field.data)?;
+        match (field.class(), field.tag()) {
+            (Class::Universal, Tag::Integer) => accuracy.seconds = value,
+            (Class::ContextSpecific, Tag(0)) => accuracy.millis = value,
+            (Class::ContextSpecific, Tag(1)) => accuracy.micros = value,
+            _ => {
+                return Err(TimestampError::Rfc3161Parse(
+                    "Unexpected field in accuracy".to_string(),
+                ))
+            }
+        }
+    }
+    if accuracy.millis > 999 || accuracy.micros > 999 {
+        return Err(TimestampError::Rfc3161Parse(
+            "Accuracy millis and micros must be at most 999".to_string(),
+        ));
+    }
+    Ok(accuracy)
+}
+
+/// Strip the sign padding of a non-negative DER INTEGER
+fn unsigned_integer_bytes(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn integer_to_u64(bytes: &[u8]) -> Result<u64, TimestampError> {
+    if bytes.first().is_some_and(|byte| byte & 0x80 != 0) {
+        return Err(TimestampError::Rfc3161Parse("Negative accuracy".to_string()));
+    }
+    let bytes = unsigned_integer_bytes(bytes);
+    if bytes.len() > 8 {
+        return Err(TimestampError::Rfc3161Parse("Accuracy out of range".to_string()));
+    }
+    Ok(bytes.iter().fold(0, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
 /// Parse MessageImprint from Sequence object
 fn parse_message_imprint_from_sequence(seq: &asn1_rs::Sequence) -> Result<MessageImprint, String> {
     use asn1_rs::{FromDer, Sequence, OctetString};
@@ -255,11 +420,11 @@ fn parse_hash_algorithm_from_sequence(seq: &asn1_rs::Sequence) -> Result<HashAlg
     }
 }
 
-/// Parse GeneralizedTime to DateTime<Utc>
+/// Parse GeneralizedTime to Timestamp
 ///
 /// Format: YYYYMMDDHHMMSSsZ or YYYYMMDDHHMMSS.fffZ
 /// Parse GeneralizedTime from value bytes (without tag/length)
-fn parse_generalized_time_value(value_bytes: &[u8]) -> Result<DateTime<Utc>, String> {
+fn parse_generalized_time_value(value_bytes: &[u8]) -> Result<Timestamp, String> {
     // GeneralizedTime format: YYYYMMDDHHMMSS[.fff]Z (as ASCII/UTF8 string)
     let time_str = std::str::from_utf8(value_bytes)
         .map_err(|e| format!("Invalid UTF-8 in GeneralizedTime: {}", e))?;
@@ -278,7 +443,7 @@ fn parse_generalized_time_value(value_bytes: &[u8]) -> Result<DateTime<Utc>, Str
         return Err(format!("Invalid GeneralizedTime format: {}", time_str));
     }
 
-    let year: i32 = date_time_part[0..4].parse()
+    let year: i64 = date_time_part[0..4].parse()
         .map_err(|e| format!("Failed to parse year: {}", e))?;
     let month: u32 = date_time_part[4..6].parse()
         .map_err(|e| format!("Failed to parse month: {}", e))?;
@@ -291,8 +456,7 @@ fn parse_generalized_time_value(value_bytes: &[u8]) -> Result<DateTime<Utc>, Str
     let second: u32 = date_time_part[12..14].parse()
         .map_err(|e| format!("Failed to parse second: {}", e))?;
 
-    Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
-        .single()
+    Timestamp::from_ymd_hms(year, month, day, hour, minute, second)
         .ok_or_else(|| format!("Invalid date/time values: {}", time_str))
 }
 
@@ -342,4 +506,51 @@ mod tests {
         // Verify it's 48 bytes (384 bits)
         assert_eq!(hash.len(), 48);
     }
+
+    #[test]
+    fn test_parse_tstinfo_optional_fields() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13531551.sigstore.json");
+        let bundle = crate::parser::bundle::parse_bundle_from_path(&path).unwrap();
+        let timestamps = bundle.verification_material.timestamp_verification_data.unwrap();
+        let der = crate::parser::bundle::decode_base64(
+            &timestamps.rfc3161_timestamps.unwrap()[0].signed_timestamp,
+        )
+        .unwrap();
+
+        let tst_info = parse_rfc3161_timestamp(&der).unwrap().tst_info;
+        assert_eq!(tst_info.policy, "1.3.6.1.4.1.57264.2");
+        assert_eq!(
+            tst_info.accuracy,
+            Some(Accuracy {
+                seconds: 1,
+                millis: 0,
+                micros: 0
+            })
+        );
+        assert!(!tst_info.ordering);
+        assert_eq!(tst_info.nonce, None);
+
+        let (earliest, latest) = tst_info.time_window();
+        assert_eq!(latest.seconds_since(earliest), 2);
+    }
+
+    #[test]
+    fn test_extract_granted_timestamp_token() {
+        // TimeStampResp { status: granted, timeStampToken }
+        let token = [0x30, 0x00];
+        let response = [0x30, 0x07, 0x30, 0x03, 0x02, 0x01, 0x00, 0x30, 0x00];
+        assert_eq!(extract_granted_timestamp_token(&response).unwrap(), token);
+
+        // TimeStampResp { status: rejection, statusString: ["bad alg"] }
+        let response = [
+            0x30, 0x0e, 0x30, 0x0c, 0x02, 0x01, 0x02, 0x30, 0x07, 0x0c, 0x05, b'b', b'a', b'd', b' ', b'x',
+        ];
+        assert!(matches!(
+            extract_granted_timestamp_token(&response),
+            Err(TimestampError::Rfc3161Rejected { status: 2, message }) if message == "bad x"
+        ));
+    }
 }
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::error::VerificationError;
+use crate::parser::bundle::decode_base64;
+use crate::parser::certificate::parse_pem_certificate;
+use crate::profiling::{self, CycleSpan};
+use crate::types::simple_signing::{
+    CosignRekorBundle, SimpleSignature, SimpleSigningPayload, ATOMIC_SIGNATURE_TYPE, COSIGN_SIGNATURE_TYPE,
+};
+
+/// Annotation holding the base64 signature over the layer
+pub const SIGNATURE_ANNOTATION: &str = "dev.cosignproject.cosign/signature";
+/// Annotation holding the PEM signing certificate
+pub const CERTIFICATE_ANNOTATION: &str = "dev.sigstore.cosign/certificate";
+/// Annotation holding the Rekor bundle as JSON
+pub const BUNDLE_ANNOTATION: &str = "dev.sigstore.cosign/bundle";
+/// Annotation holding the RFC 3161 timestamp as JSON
+pub const RFC3161_TIMESTAMP_ANNOTATION: &str = "dev.sigstore.cosign/rfc3161timestamp";
+
+/// Parse a simple signing payload
+///
+/// # Errors
+///
+/// Returns `BundleParse` if the payload is not a simple signing document and
+/// `InvalidBundleFormat` if its `critical.type` is not a container signature.
+pub fn parse_simple_signing_payload(payload: &[u8]) -> Result<SimpleSigningPayload, VerificationError> {
+    let _span = CycleSpan::enter(profiling::JSON_PARSE);
+    let parsed: SimpleSigningPayload = serde_json::from_slice(payload)?;
+    let signature_type = parsed.critical.signature_type.as_str();
+    if signature_type != COSIGN_SIGNATURE_TYPE && signature_type != ATOMIC_SIGNATURE_TYPE {
+        return Err(VerificationError::InvalidBundleFormat(format!(
+            "Unsupported simple signing type: {}",
+            signature_type
+        )));
+    }
+    Ok(parsed)
+}
+
+#[derive(Deserialize)]
+struct Rfc3161TimestampAnnotation {
+    #[serde(rename = "SignedRFC3161Timestamp")]
+    signed_rfc3161_timestamp: String,
+}
+
+/// Assemble a `SimpleSignature` from a cosign signature layer
+///
+/// # Arguments
+///
+/// * `payload` - The layer blob
+/// * `annotations` - The layer's annotations from the signature manifest
+///
+/// # Errors
+///
+/// Returns `InvalidBundleFormat` if the signature or certificate annotation is
+/// missing, and a decoding error if an annotation is malformed.
+pub fn parse_cosign_signature_layer(
+    payload: Vec<u8>,
+    annotations: &HashMap<String, String>,
+) -> Result<SimpleSignature, VerificationError> {
+    let annotation = |name: &str| {
+        annotations
+            .get(name)
+            .ok_or_else(|| VerificationError::InvalidBundleFormat(format!("Missing {} annotation", name)))
+    };
+
+    let signature = decode_base64(annotation(SIGNATURE_ANNOTATION)?)?;
+    let certificate = parse_pem_certificate(annotation(CERTIFICATE_ANNOTATION)?)?;
+    let rekor_bundle = annotations
+        .get(BUNDLE_ANNOTATION)
+        .map(|json| serde_json::from_str::<CosignRekorBundle>(json))
+        .transpose()?;
+    let rfc3161_timestamp = annotations
+        .get(RFC3161_TIMESTAMP_ANNOTATION)
+        .map(|json| {
+            let annotation: Rfc3161TimestampAnnotation = serde_json::from_str(json)?;
+            decode_base64(&annotation.signed_rfc3161_timestamp)
+        })
+        .transpose()?;
+
+    Ok(SimpleSignature {
+        payload,
+        signature,
+        certificate,
+        rekor_bundle,
+        rfc3161_timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::*;
+
+    fn sample(name: &str) -> Vec<u8> {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/certs");
+        path.push(name);
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_simple_signing_payload() {
+        let payload = parse_simple_signing_payload(&sample("simple-signing.json")).unwrap();
+        assert_eq!(payload.critical.identity.docker_reference, "ghcr.io/automata-network/example");
+
+        let statement = payload.to_statement().unwrap();
+        assert_eq!(statement.get_subject_digest("sha256"), Some(payload.manifest_digest().unwrap().to_hex()));
+
+        let other_type = br#"{"critical":{"identity":{"docker-reference":"a"},"image":{"docker-manifest-digest":"sha256:00"},"type":"other"}}"#;
+        assert!(matches!(
+            parse_simple_signing_payload(other_type),
+            Err(VerificationError::InvalidBundleFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_cosign_signature_layer() {
+        let signature = sample("simple-signing.sig");
+        let certificate = String::from_utf8(sample("simple-signing-leaf.pem")).unwrap();
+        let mut annotations = HashMap::from([
+            (SIGNATURE_ANNOTATION.to_string(), BASE64_STANDARD.encode(&signature)),
+            (CERTIFICATE_ANNOTATION.to_string(), certificate),
+            (
+                BUNDLE_ANNOTATION.to_string(),
+                r#"{"SignedEntryTimestamp":"AA==","Payload":{"body":"e30=","integratedTime":1700000000,"logIndex":7,"logID":"0a0b"}}"#
+                    .to_string(),
+            ),
+        ]);
+
+        let layer = parse_cosign_signature_layer(sample("simple-signing.json"), &annotations).unwrap();
+        assert_eq!(layer.signature, signature);
+        let entry = layer.rekor_bundle.as_ref().unwrap().to_tlog_entry().unwrap();
+        assert_eq!(entry.integrated_time, "1700000000");
+        assert_eq!(entry.log_id.unwrap().key_id, "Cgs=");
+        assert!(layer.rfc3161_timestamp.is_none());
+
+        annotations.remove(CERTIFICATE_ANNOTATION);
+        assert!(parse_cosign_signature_layer(Vec::new(), &annotations).is_err());
+    }
+}
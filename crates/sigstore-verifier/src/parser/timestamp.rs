@@ -1,11 +1,11 @@
 use asn1_rs::{FromDer, Sequence};
-use chrono::{DateTime, Utc};
 
 use crate::error::TimestampError;
+use crate::types::time::Timestamp;
 
 #[derive(Debug, Clone)]
 pub struct Rfc3161TimestampInfo {
-    pub signing_time: DateTime<Utc>,
+    pub signing_time: Timestamp,
     pub raw_bytes: Vec<u8>,
 }
 
@@ -31,13 +31,12 @@ pub fn parse_rfc3161_timestamp(der: &[u8]) -> Result<Rfc3161TimestampInfo, Times
     ))
 }
 
-pub fn parse_integrated_time(time_str: &str) -> Result<DateTime<Utc>, TimestampError> {
+pub fn parse_integrated_time(time_str: &str) -> Result<Timestamp, TimestampError> {
     let timestamp = time_str
         .parse::<i64>()
         .map_err(|_| TimestampError::InvalidIntegratedTime)?;
 
-    DateTime::from_timestamp(timestamp, 0)
-        .ok_or(TimestampError::InvalidIntegratedTime)
+    Ok(Timestamp::from_unix(timestamp))
 }
 
 #[cfg(test)]
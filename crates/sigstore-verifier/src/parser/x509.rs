@@ -0,0 +1,324 @@
+//! Minimal X.509 certificate parser
+//!
+//! Reads only what verification uses into [`ParsedCertificate`]: serial,
+//! distinguished names, validity, SubjectPublicKeyInfo, the signature and its
+//! algorithm identifiers, and the extensions undecoded apart from subject key
+//! identifier and subject alternative names. x509-parser and asn1-rs decode
+//! every known extension up front, which is a large share of zkVM guest cycles
+//! and binary size; with the `minimal-x509` feature,
+//! `parser::certificate::parse_certificate` uses this parser instead.
+//!
+//! Names render as x509-parser renders them (`CN=..., O=...`) for the usual
+//! attribute types; other types show as their dotted OID.
+
+use crate::crypto::hash::sha256;
+use crate::crypto::signature::PublicKey;
+use crate::error::CertificateError;
+use crate::parser::der::{self, DerReader, Tlv};
+use crate::types::certificate::{CertificateExtension, ParsedCertificate, SubjectAltName};
+
+const OID_COMMON_NAME: &str = "2.5.4.3";
+const OID_SUBJECT_KEY_IDENTIFIER: &str = "2.5.29.14";
+const OID_SUBJECT_ALT_NAME: &str = "2.5.29.17";
+
+/// Context-specific tags of the optional TBSCertificate fields
+const TAG_VERSION: u8 = 0xa0;
+const TAG_ISSUER_UNIQUE_ID: u8 = 0x81;
+const TAG_SUBJECT_UNIQUE_ID: u8 = 0x82;
+const TAG_EXTENSIONS: u8 = 0xa3;
+
+/// GeneralName choices read from subject alternative names
+const TAG_RFC822_NAME: u8 = 0x81;
+const TAG_URI: u8 = 0x86;
+
+/// Parse a DER certificate into [`ParsedCertificate`]
+///
+/// # Errors
+///
+/// Returns `ParseError` if the certificate is not well-formed DER, has a
+/// malformed validity time, or repeats an extension. A public key of an
+/// unsupported type is only an error when the key is used.
+pub fn parse_certificate(der: &[u8]) -> Result<ParsedCertificate, CertificateError> {
+    parse(der).map_err(CertificateError::ParseError)
+}
+
+fn parse(der: &[u8]) -> Result<ParsedCertificate, String> {
+    let certificate = DerReader::new(der).read(der::TAG_SEQUENCE)?;
+    let mut fields = DerReader::new(certificate.content);
+    let tbs = fields.read(der::TAG_SEQUENCE)?;
+    let signature_algorithm = AlgorithmIdentifier::read(&mut fields)?;
+    let signature = bit_string(&fields.read(der::TAG_BIT_STRING)?)?;
+    fields.finish()?;
+
+    let mut tbs_fields = DerReader::new(tbs.content);
+    tbs_fields.read_optional(TAG_VERSION)?;
+    let serial = tbs_fields.read(der::TAG_INTEGER)?;
+    let tbs_signature_algorithm = AlgorithmIdentifier::read(&mut tbs_fields)?;
+    let issuer = Name::read(&mut tbs_fields)?;
+    let mut validity = DerReader::new(tbs_fields.read(der::TAG_SEQUENCE)?.content);
+    let not_before = der::parse_time(&validity.read_any()?)?;
+    let not_after = der::parse_time(&validity.read_any()?)?;
+    validity.finish()?;
+    let subject = Name::read(&mut tbs_fields)?;
+    let spki = tbs_fields.read(der::TAG_SEQUENCE)?;
+    tbs_fields.read_optional(TAG_ISSUER_UNIQUE_ID)?;
+    tbs_fields.read_optional(TAG_SUBJECT_UNIQUE_ID)?;
+    let extensions = match tbs_fields.read_optional(TAG_EXTENSIONS)? {
+        Some(wrapper) => read_extensions(wrapper.content)?,
+        None => Vec::new(),
+    };
+    tbs_fields.finish()?;
+
+    let subject_key_id = extension(&extensions, OID_SUBJECT_KEY_IDENTIFIER)
+        .and_then(|ext| DerReader::new(&ext.value).read(der::TAG_OCTET_STRING).ok())
+        .map(|key_id| hex::encode(key_id.content));
+    let subject_alt_names = extension(&extensions, OID_SUBJECT_ALT_NAME)
+        .and_then(|ext| subject_alt_names(&ext.value).ok())
+        .unwrap_or_default();
+
+    Ok(ParsedCertificate {
+        der: der.to_vec(),
+        fingerprint: sha256(der),
+        serial_number: hex::encode(serial.content),
+        subject: subject.to_string(),
+        issuer: issuer.to_string(),
+        issuer_cn: issuer.common_name(),
+        not_before,
+        not_after,
+        spki: spki.raw.to_vec(),
+        subject_key_id,
+        subject_alt_names,
+        extensions,
+        public_key: PublicKey::from_spki_der(spki.raw),
+        tbs_certificate: tbs.raw.to_vec(),
+        signature: signature.to_vec(),
+        algorithm_parameters_match: signature_algorithm.parameters == tbs_signature_algorithm.parameters,
+        signature_algorithm: signature_algorithm.oid,
+        tbs_signature_algorithm: tbs_signature_algorithm.oid,
+    })
+}
+
+/// `AlgorithmIdentifier ::= SEQUENCE { algorithm OID, parameters ANY OPTIONAL }`
+struct AlgorithmIdentifier<'a> {
+    oid: String,
+    parameters: Option<Tlv<'a>>,
+}
+
+impl<'a> AlgorithmIdentifier<'a> {
+    fn read(reader: &mut DerReader<'a>) -> Result<Self, String> {
+        let mut fields = DerReader::new(reader.read(der::TAG_SEQUENCE)?.content);
+        let oid = der::oid_to_string(fields.read(der::TAG_OID)?.content)?;
+        let parameters = if fields.is_empty() { None } else { Some(fields.read_any()?) };
+        fields.finish()?;
+        Ok(AlgorithmIdentifier { oid, parameters })
+    }
+}
+
+/// A distinguished name, as (OID, value) attributes per RDN
+struct Name {
+    rdns: Vec<Vec<(String, String)>>,
+}
+
+impl Name {
+    fn read(reader: &mut DerReader) -> Result<Self, String> {
+        let mut rdns = Vec::new();
+        let mut sets = DerReader::new(reader.read(der::TAG_SEQUENCE)?.content);
+        while !sets.is_empty() {
+            let mut attributes = DerReader::new(sets.read(der::TAG_SET)?.content);
+            let mut rdn = Vec::new();
+            while !attributes.is_empty() {
+                let mut attribute = DerReader::new(attributes.read(der::TAG_SEQUENCE)?.content);
+                let oid = der::oid_to_string(attribute.read(der::TAG_OID)?.content)?;
+                let value = attribute_value(&attribute.read_any()?)?;
+                attribute.finish()?;
+                rdn.push((oid, value));
+            }
+            rdns.push(rdn);
+        }
+        Ok(Name { rdns })
+    }
+
+    fn common_name(&self) -> Option<String> {
+        self.rdns
+            .iter()
+            .flatten()
+            .find(|(oid, _)| oid == OID_COMMON_NAME)
+            .map(|(_, value)| value.clone())
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, rdn) in self.rdns.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            for (position, (oid, value)) in rdn.iter().enumerate() {
+                if position > 0 {
+                    f.write_str(" + ")?;
+                }
+                write!(f, "{}={}", attribute_abbreviation(oid).unwrap_or(oid), value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Short names x509-parser uses when rendering names
+fn attribute_abbreviation(oid: &str) -> Option<&'static str> {
+    Some(match oid {
+        OID_COMMON_NAME => "CN",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "0.9.2342.19200300.100.1.25" => "DC",
+        "1.2.840.113549.1.9.1" => "Email",
+        _ => return None,
+    })
+}
+
+/// String form of an attribute value; values that are not strings are uppercase hex
+fn attribute_value(value: &Tlv) -> Result<String, String> {
+    match value.tag {
+        der::TAG_UTF8_STRING
+        | der::TAG_PRINTABLE_STRING
+        | der::TAG_T61_STRING
+        | der::TAG_IA5_STRING
+        | der::TAG_NUMERIC_STRING
+        | der::TAG_VISIBLE_STRING
+        | der::TAG_GENERAL_STRING
+        | der::TAG_GRAPHIC_STRING
+        | der::TAG_VIDEOTEX_STRING
+        | der::TAG_OBJECT_DESCRIPTOR => {
+            std::str::from_utf8(value.content)
+                .map(str::to_string)
+                .map_err(|_| "invalid string in name".to_string())
+        }
+        der::TAG_BMP_STRING => {
+            let pairs = value.content.chunks_exact(2);
+            if !pairs.remainder().is_empty() {
+                return Err("invalid BMPString in name".to_string());
+            }
+            char::decode_utf16(pairs.map(|pair| u16::from_be_bytes([pair[0], pair[1]])))
+                .collect::<Result<String, _>>()
+                .map_err(|_| "invalid BMPString in name".to_string())
+        }
+        _ => Ok(hex::encode_upper(value.content)),
+    }
+}
+
+/// Bytes of a BIT STRING, without the unused-bits byte
+fn bit_string<'a>(tlv: &Tlv<'a>) -> Result<&'a [u8], String> {
+    match tlv.content.split_first() {
+        Some((unused, bits)) if *unused < 8 => Ok(bits),
+        _ => Err("invalid BIT STRING".to_string()),
+    }
+}
+
+/// `Extensions ::= SEQUENCE OF SEQUENCE { extnID OID, critical BOOLEAN DEFAULT FALSE, extnValue OCTET STRING }`
+fn read_extensions(wrapper: &[u8]) -> Result<Vec<CertificateExtension>, String> {
+    let mut outer = DerReader::new(wrapper);
+    let mut entries = DerReader::new(outer.read(der::TAG_SEQUENCE)?.content);
+    outer.finish()?;
+
+    let mut extensions: Vec<CertificateExtension> = Vec::new();
+    while !entries.is_empty() {
+        let mut fields = DerReader::new(entries.read(der::TAG_SEQUENCE)?.content);
+        let oid = der::oid_to_string(fields.read(der::TAG_OID)?.content)?;
+        let critical = match fields.read_optional(der::TAG_BOOLEAN)? {
+            Some(flag) => flag.content.first().is_some_and(|byte| *byte != 0),
+            None => false,
+        };
+        let value = fields.read(der::TAG_OCTET_STRING)?.content.to_vec();
+        fields.finish()?;
+        if extensions.iter().any(|ext| ext.oid == oid) {
+            return Err(format!("duplicate extension {}", oid));
+        }
+        extensions.push(CertificateExtension { oid, critical, value });
+    }
+    Ok(extensions)
+}
+
+fn extension<'a>(extensions: &'a [CertificateExtension], oid: &str) -> Option<&'a CertificateExtension> {
+    extensions.iter().find(|ext| ext.oid == oid)
+}
+
+/// E-mail and URI entries of a `GeneralNames` sequence
+fn subject_alt_names(value: &[u8]) -> Result<Vec<SubjectAltName>, String> {
+    let mut names = DerReader::new(DerReader::new(value).read(der::TAG_SEQUENCE)?.content);
+    let mut subject_alt_names = Vec::new();
+    while !names.is_empty() {
+        let name = names.read_any()?;
+        let text = || std::str::from_utf8(name.content).map(str::to_string).map_err(|e| e.to_string());
+        match name.tag {
+            TAG_RFC822_NAME => subject_alt_names.push(SubjectAltName::Email(text()?)),
+            TAG_URI => subject_alt_names.push(SubjectAltName::Uri(text()?)),
+            _ => {}
+        }
+    }
+    Ok(subject_alt_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::certificate::{parse_certificate_with_x509_parser, parse_pem_certificates};
+
+    /// The fields x509-parser and this parser must agree on
+    fn fields(cert: &ParsedCertificate) -> impl PartialEq + std::fmt::Debug + '_ {
+        (
+            (cert.fingerprint, &cert.serial_number, &cert.subject, &cert.issuer, &cert.issuer_cn),
+            (cert.not_before, cert.not_after, &cert.spki, &cert.subject_key_id, &cert.subject_alt_names),
+            (&cert.extensions, &cert.tbs_certificate, &cert.signature),
+            (&cert.signature_algorithm, &cert.tbs_signature_algorithm, cert.algorithm_parameters_match),
+            cert.public_key.as_ref().map(PublicKey::default_algorithm).map_err(ToString::to_string),
+        )
+    }
+
+    /// Parse with x509-parser, as the default build does
+    fn reference(der: &[u8]) -> ParsedCertificate {
+        parse_certificate_with_x509_parser(der).unwrap()
+    }
+
+    #[test]
+    fn test_matches_x509_parser() {
+        let mut dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        dir.pop();
+        dir.pop();
+        dir.push("samples/certs");
+        let mut checked = 0;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("pem") {
+                continue;
+            }
+            for der in parse_pem_certificates(&std::fs::read(&path).unwrap()).unwrap() {
+                let parsed = parse_certificate(&der).unwrap();
+                let reference = reference(&der);
+                assert_eq!(fields(&parsed), fields(&reference), "{}", path.display());
+                checked += 1;
+            }
+        }
+        assert!(checked >= 10);
+    }
+
+    #[test]
+    fn test_rejects_malformed_certificates() {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/certs/email-leaf.pem");
+        let der = parse_pem_certificates(&std::fs::read(path).unwrap()).unwrap().remove(0);
+        assert!(parse_certificate(&der[..der.len() - 1]).is_err());
+        assert!(parse_certificate(&[]).is_err());
+
+        // Subject key identifier extension, once and twice
+        let extension = [0x30, 0x09, 0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04, 0x02, 0x04, 0x00];
+        let once = [&[0x30, 0x0b][..], &extension].concat();
+        assert_eq!(read_extensions(&once).unwrap().len(), 1);
+        let twice = [&[0x30, 0x16][..], &extension, &extension].concat();
+        assert!(read_extensions(&twice).unwrap_err().contains("duplicate extension 2.5.29.14"));
+    }
+}
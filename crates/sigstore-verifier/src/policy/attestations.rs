@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+
+use super::consistency::{
+    bundle_fulcio_extensions, check_consistency, ConsistencyField, Inconsistency,
+};
+use super::pattern::wildcard_match;
+use crate::report::VerificationReport;
+use crate::types::certificate::CertificateChain;
+use crate::types::digest::Digest;
+use crate::types::result::VerificationOptions;
+use crate::AttestationVerifier;
+
+/// SLSA build provenance v1
+pub const SLSA_PROVENANCE_V1: &str = "https://slsa.dev/provenance/v1";
+
+/// GitHub release attestation (`gh release` immutable releases)
+pub const RELEASE_V0_1: &str = "https://in-toto.io/attestation/release/v0.1";
+
+/// SPDX SBOM, any version (e.g. `https://spdx.dev/Document/v2.3`)
+pub const SPDX_DOCUMENT: &str = "https://spdx.dev/Document*";
+
+/// CycloneDX SBOM, any version
+pub const CYCLONEDX_BOM: &str = "https://cyclonedx.org/bom*";
+
+/// in-toto vulnerability scan result, any version
+pub const VULNERABILITY_SCAN: &str = "https://in-toto.io/attestation/vulns*";
+
+/// Predicate types that must all be attested for one subject
+///
+/// Each entry is a pattern where `*` matches any sequence of characters, so a
+/// single entry can accept several versions of a predicate type.
+///
+/// # Example
+///
+/// ```ignore
+/// let policy = RequiredAttestations::new([SLSA_PROVENANCE_V1, SPDX_DOCUMENT, VULNERABILITY_SCAN]);
+/// let report = policy.evaluate(&verifier, &digest, &bundles, &options, &trust_bundle, tsa_chain);
+/// assert!(report.satisfied, "{}", report.message());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequiredAttestations {
+    pub predicate_types: Vec<String>,
+
+    /// Signer attributes that must match across all bundles (see `check_consistency`)
+    #[serde(default)]
+    pub consistent_fields: Vec<ConsistencyField>,
+}
+
+/// Combined outcome of verifying a set of attestations for one subject
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationSetReport {
+    /// Subject every bundle was verified against
+    pub subject: Digest,
+
+    /// One report per bundle, in input order
+    pub reports: Vec<VerificationReport>,
+
+    /// Required predicate types not covered by any verified bundle
+    pub missing: Vec<String>,
+
+    /// Signer attributes that differ between the bundles
+    pub inconsistencies: Vec<Inconsistency>,
+
+    /// True if every bundle verified, no required predicate type is missing
+    /// and the bundles are consistent
+    pub satisfied: bool,
+}
+
+impl RequiredAttestations {
+    pub fn new<I, S>(predicate_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            predicate_types: predicate_types.into_iter().map(Into::into).collect(),
+            consistent_fields: Vec::new(),
+        }
+    }
+
+    /// Also require the given signer attributes to match across bundles
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let policy = RequiredAttestations::new([SLSA_PROVENANCE_V1, SPDX_DOCUMENT])
+    ///     .with_consistency(DEFAULT_CONSISTENCY_FIELDS);
+    /// ```
+    pub fn with_consistency(mut self, fields: &[ConsistencyField]) -> Self {
+        self.consistent_fields = fields.to_vec();
+        self
+    }
+
+    /// Verify a set of bundles for `subject` and check the required predicate types
+    ///
+    /// Every bundle is verified with `expected_digest` set to `subject`, so all
+    /// verified bundles agree on the subject. The set is satisfied only if every
+    /// bundle verifies, each required predicate type matches at least one of them,
+    /// and the signing certificates agree on `consistent_fields`.
+    ///
+    /// # Arguments
+    ///
+    /// * `verifier` - Verifier used for each bundle
+    /// * `subject` - Digest of the artifact the attestations must cover
+    /// * `bundles` - Name (e.g. file path) and raw JSON bytes of each bundle
+    /// * `options` - Policy applied to every bundle; `expected_digest` is replaced by `subject`
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    pub fn evaluate(
+        &self,
+        verifier: &AttestationVerifier,
+        subject: &Digest,
+        bundles: &[(String, Vec<u8>)],
+        options: &VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> AttestationSetReport {
+        let mut options = options.clone();
+        options.expected_digest = Some(subject.clone());
+
+        let reports: Vec<VerificationReport> = bundles
+            .iter()
+            .map(|(name, bundle_json)| {
+                let result = verifier.verify_bundle_bytes(
+                    bundle_json,
+                    options.clone(),
+                    trust_bundle,
+                    tsa_cert_chain,
+                );
+                VerificationReport::new(name.clone(), bundle_json, options.clone(), result)
+            })
+            .collect();
+
+        let missing = self.missing(&reports);
+
+        // Compare the certificates of verified bundles; rejected ones already fail the set
+        let signers: Vec<_> = bundles
+            .iter()
+            .zip(&reports)
+            .filter(|(_, report)| report.verified())
+            .filter_map(|((name, bundle_json), _)| {
+                bundle_fulcio_extensions(bundle_json)
+                    .ok()
+                    .map(|extensions| (name.clone(), extensions))
+            })
+            .collect();
+        let inconsistencies = check_consistency(&self.consistent_fields, &signers);
+
+        let satisfied = missing.is_empty()
+            && inconsistencies.is_empty()
+            && reports.iter().all(VerificationReport::verified);
+
+        AttestationSetReport {
+            subject: subject.clone(),
+            reports,
+            missing,
+            inconsistencies,
+            satisfied,
+        }
+    }
+
+    /// Required predicate types without a verified bundle
+    pub fn missing(&self, reports: &[VerificationReport]) -> Vec<String> {
+        self.predicate_types
+            .iter()
+            .filter(|required| {
+                !reports.iter().any(|report| {
+                    report.verified()
+                        && report
+                            .predicate_type
+                            .as_deref()
+                            .is_some_and(|actual| wildcard_match(required, actual))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl AttestationSetReport {
+    /// Human-readable summary listing missing predicate types and rejected bundles
+    pub fn message(&self) -> String {
+        if self.satisfied {
+            return format!(
+                "all {} attestations for {} verified",
+                self.reports.len(),
+                self.subject
+            );
+        }
+
+        let mut parts: Vec<String> = self
+            .missing
+            .iter()
+            .map(|predicate_type| {
+                format!("missing verified attestation of type {}", predicate_type)
+            })
+            .collect();
+        parts.extend(self.inconsistencies.iter().map(ToString::to_string));
+        parts.extend(self.reports.iter().filter_map(|report| {
+            report
+                .error
+                .as_ref()
+                .map(|error| format!("{} rejected: {}", report.bundle, error.message))
+        }));
+        parts.join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_chain() -> CertificateChain {
+        CertificateChain {
+            leaf: vec![],
+            intermediates: vec![],
+            root: vec![],
+        }
+    }
+
+    #[test]
+    fn test_no_bundles_reports_all_missing() {
+        let policy = RequiredAttestations::new([SLSA_PROVENANCE_V1, SPDX_DOCUMENT]);
+        let report = policy.evaluate(
+            &AttestationVerifier::new(),
+            &Digest::sha256([1; 32]),
+            &[],
+            &VerificationOptions::default(),
+            &empty_chain(),
+            None,
+        );
+        assert!(!report.satisfied);
+        assert_eq!(report.missing, vec![SLSA_PROVENANCE_V1, SPDX_DOCUMENT]);
+        assert!(report.message().contains("https://spdx.dev/Document*"));
+    }
+
+    #[test]
+    fn test_rejected_bundle_fails_the_set() {
+        let report = RequiredAttestations::default().evaluate(
+            &AttestationVerifier::new(),
+            &Digest::sha256([1; 32]),
+            &[("broken.json".to_string(), b"{}".to_vec())],
+            &VerificationOptions::default(),
+            &empty_chain(),
+            None,
+        );
+        assert!(report.missing.is_empty());
+        assert!(!report.satisfied);
+        assert!(report.message().starts_with("broken.json rejected"));
+    }
+}
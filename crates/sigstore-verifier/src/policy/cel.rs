@@ -0,0 +1,207 @@
+//! CEL policy hook
+//!
+//! Evaluates [CEL](https://cel.dev) expressions against a `PolicyInput`. The
+//! input's fields are exposed as top-level variables: `statement`,
+//! `subject_digest`, `signing_time` (RFC 3339), `identity`, `extensions` and
+//! `tlog` (`null` when the bundle has no transparency log entry). Every rule must
+//! evaluate to `true`; the messages of the rules that do not are returned as
+//! the denial reasons.
+//!
+//! ```ignore
+//! let policy = CelPolicy::new(vec![
+//!     CelRule::new(r#"statement.predicateType == "https://slsa.dev/provenance/v1""#),
+//!     CelRule::new(r#"extensions.runner_environment == "github-hosted""#)
+//!         .with_message("build must run on a GitHub-hosted runner"),
+//!     CelRule::new(r#"statement.predicate.buildDefinition.externalParameters.workflow.ref.startsWith("refs/tags/")"#)
+//!         .with_message("only tagged releases are accepted"),
+//! ])?;
+//! let verifier = AttestationVerifier::new().with_policy_hook(Arc::new(policy));
+//! ```
+
+use cel_interpreter::{Context, Program, Value};
+use serde::{Deserialize, Serialize};
+
+use super::hook::{PolicyDecision, PolicyHook, PolicyInput};
+use crate::error::PolicyError;
+
+/// A CEL expression that must evaluate to `true`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CelRule {
+    pub expression: String,
+
+    /// Denial reason reported when the expression is `false`
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl CelRule {
+    pub fn new(expression: impl Into<String>) -> Self {
+        CelRule {
+            expression: expression.into(),
+            message: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    fn failure_message(&self) -> String {
+        self.message
+            .clone()
+            .unwrap_or_else(|| format!("rule failed: {}", self.expression))
+    }
+}
+
+/// Policy hook evaluating a list of compiled CEL rules
+pub struct CelPolicy {
+    rules: Vec<(CelRule, Program)>,
+}
+
+impl std::fmt::Debug for CelPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules: Vec<&CelRule> = self.rules.iter().map(|(rule, _)| rule).collect();
+        f.debug_struct("CelPolicy").field("rules", &rules).finish()
+    }
+}
+
+impl CelPolicy {
+    /// Compile the rules
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError::Evaluation` naming the first rule that does not parse.
+    pub fn new(rules: Vec<CelRule>) -> Result<Self, PolicyError> {
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                let program = Program::compile(&rule.expression).map_err(|e| {
+                    PolicyError::Evaluation(format!(
+                        "invalid CEL rule `{}`: {}",
+                        rule.expression, e
+                    ))
+                })?;
+                Ok((rule, program))
+            })
+            .collect::<Result<_, PolicyError>>()?;
+        Ok(CelPolicy { rules })
+    }
+
+    /// The rules this policy evaluates, in order
+    pub fn rules(&self) -> impl Iterator<Item = &CelRule> {
+        self.rules.iter().map(|(rule, _)| rule)
+    }
+}
+
+impl PolicyHook for CelPolicy {
+    fn evaluate(&self, input: &PolicyInput) -> Result<PolicyDecision, PolicyError> {
+        let variables = serde_json::to_value(input).map_err(|e| {
+            PolicyError::Evaluation(format!("failed to serialize policy input: {}", e))
+        })?;
+
+        let mut context = Context::default();
+        if let serde_json::Value::Object(variables) = variables {
+            for (name, value) in variables {
+                context.add_variable(name.clone(), value).map_err(|e| {
+                    PolicyError::Evaluation(format!("failed to bind `{}`: {}", name, e))
+                })?;
+            }
+        }
+
+        let mut failures = Vec::new();
+        for (rule, program) in &self.rules {
+            match program.execute(&context) {
+                Ok(Value::Bool(true)) => {}
+                Ok(Value::Bool(false)) => failures.push(rule.failure_message()),
+                Ok(other) => {
+                    return Err(PolicyError::Evaluation(format!(
+                        "rule `{}` returned {:?}, expected a bool",
+                        rule.expression, other
+                    )))
+                }
+                Err(e) => {
+                    return Err(PolicyError::Evaluation(format!(
+                        "rule `{}` failed: {}",
+                        rule.expression, e
+                    )))
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(PolicyDecision::allow())
+        } else {
+            Ok(PolicyDecision::deny(failures))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::certificate::FulcioExtensions;
+    use crate::types::digest::Digest;
+    use crate::types::dsse::Statement;
+    use crate::types::time::Timestamp;
+
+    fn input() -> PolicyInput {
+        PolicyInput {
+            statement: Statement {
+                statement_type: "https://in-toto.io/Statement/v1".to_string(),
+                subject: vec![],
+                predicate_type: "https://slsa.dev/provenance/v1".to_string(),
+                predicate: serde_json::json!({ "buildDefinition": { "buildType": "gha" } }),
+            },
+            subject_digest: Digest::sha256([1; 32]),
+            signing_time: Timestamp::from_unix(1_700_000_000),
+            identity: None,
+            extensions: FulcioExtensions {
+                runner_environment: Some("self-hosted".to_string()),
+                ..Default::default()
+            },
+            tlog: None,
+        }
+    }
+
+    #[test]
+    fn test_all_rules_pass() {
+        let policy = CelPolicy::new(vec![
+            CelRule::new(r#"statement.predicateType == "https://slsa.dev/provenance/v1""#),
+            CelRule::new(r#"statement.predicate.buildDefinition.buildType == "gha""#),
+            CelRule::new("tlog == null"),
+        ])
+        .unwrap();
+        assert_eq!(policy.evaluate(&input()).unwrap(), PolicyDecision::allow());
+    }
+
+    #[test]
+    fn test_failed_rules_are_reported() {
+        let policy = CelPolicy::new(vec![
+            CelRule::new(r#"extensions.runner_environment == "github-hosted""#)
+                .with_message("build must run on a GitHub-hosted runner"),
+            CelRule::new(r#"subject_digest.startsWith("sha384:")"#),
+        ])
+        .unwrap();
+        let decision = policy.evaluate(&input()).unwrap();
+        assert!(!decision.allow);
+        assert_eq!(
+            decision.messages,
+            vec![
+                "build must run on a GitHub-hosted runner".to_string(),
+                r#"rule failed: subject_digest.startsWith("sha384:")"#.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(CelPolicy::new(vec![CelRule::new("statement.predicateType ==")]).is_err());
+
+        let non_bool = CelPolicy::new(vec![CelRule::new("statement.predicateType")]).unwrap();
+        assert!(matches!(
+            non_bool.evaluate(&input()),
+            Err(PolicyError::Evaluation(_))
+        ));
+    }
+}
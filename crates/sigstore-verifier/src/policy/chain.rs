@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CertificateError, PolicyError};
+use crate::types::digest::Digest;
+use crate::types::result::CertificateChainHashes;
+
+/// Constraints on the shape of the Fulcio certificate chain
+///
+/// The default policy matches the behavior without a policy: any depth the
+/// input limits allow, a self-signed root, and `pinned_root_digests` (if set)
+/// naming the root. Private deployments with deeper chains or roots
+/// cross-signed by a CA outside the trust bundle relax it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainPolicy {
+    /// Largest accepted number of certificates from the leaf to the root, inclusive
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// Require the last certificate of the trust bundle to be self-signed;
+    /// when unset it is trusted as an anchor without checking its signature
+    #[serde(default = "default_true")]
+    pub require_self_signed_root: bool,
+
+    /// Require `pinned_root_digests` to name the root; when unset, pinning any
+    /// CA certificate of the chain (e.g. a cross-signed intermediate) is enough
+    #[serde(default = "default_true")]
+    pub require_pinned_root: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ChainPolicy {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            require_self_signed_root: true,
+            require_pinned_root: true,
+        }
+    }
+}
+
+impl ChainPolicy {
+    /// Check the number of certificates in the chain, leaf and root included
+    pub fn check_depth(&self, depth: usize) -> Result<(), CertificateError> {
+        match self.max_depth {
+            Some(max) if depth > max => Err(CertificateError::ChainTooDeep { depth, max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that the chain is anchored in one of the `pinned` certificate digests
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnpinnedRoot` if the root must be pinned and is not, and
+    /// `UnpinnedChain` if no CA certificate of the chain is pinned.
+    pub fn check_pinned(&self, hashes: &CertificateChainHashes, pinned: &[Digest]) -> Result<(), PolicyError> {
+        let root = Digest::sha256(hashes.root);
+        if pinned.contains(&root) {
+            return Ok(());
+        }
+        if self.require_pinned_root {
+            return Err(PolicyError::UnpinnedRoot(root.to_string()));
+        }
+        if hashes
+            .intermediates
+            .iter()
+            .any(|hash| pinned.contains(&Digest::sha256(*hash)))
+        {
+            return Ok(());
+        }
+        Err(PolicyError::UnpinnedChain(root.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes() -> CertificateChainHashes {
+        CertificateChainHashes {
+            leaf: [1; 32],
+            intermediates: vec![[2; 32], [3; 32]],
+            root: [4; 32],
+        }
+    }
+
+    #[test]
+    fn test_check_depth() {
+        let policy = ChainPolicy {
+            max_depth: Some(3),
+            ..Default::default()
+        };
+        assert!(policy.check_depth(3).is_ok());
+        assert!(matches!(
+            policy.check_depth(4),
+            Err(CertificateError::ChainTooDeep { depth: 4, max: 3 })
+        ));
+        assert!(ChainPolicy::default().check_depth(10).is_ok());
+    }
+
+    #[test]
+    fn test_check_pinned() {
+        let intermediate = vec![Digest::sha256([3; 32])];
+        let root = vec![Digest::sha256([4; 32])];
+        let leaf = vec![Digest::sha256([1; 32])];
+
+        let strict = ChainPolicy::default();
+        assert!(strict.check_pinned(&hashes(), &root).is_ok());
+        assert!(matches!(
+            strict.check_pinned(&hashes(), &intermediate),
+            Err(PolicyError::UnpinnedRoot(_))
+        ));
+
+        let relaxed = ChainPolicy {
+            require_pinned_root: false,
+            ..Default::default()
+        };
+        assert!(relaxed.check_pinned(&hashes(), &intermediate).is_ok());
+        // The leaf is never an anchor
+        assert!(matches!(
+            relaxed.check_pinned(&hashes(), &leaf),
+            Err(PolicyError::UnpinnedChain(_))
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::parser::bundle::{decode_base64, parse_bundle_from_bytes};
+use crate::parser::identity::extract_fulcio_extensions;
+use crate::types::certificate::{FulcioExtensions, ParsedCertificate};
+
+/// Signer attribute that must be identical across the bundles of one artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsistencyField {
+    /// OIDC issuer
+    Issuer,
+    /// Workflow that requested the certificate (build signer URI)
+    BuildSigner,
+    /// Build signer digest (e.g. the commit of a reusable workflow)
+    BuildSignerDigest,
+    /// Source repository URI
+    SourceRepository,
+    /// Source revision (commit digest)
+    SourceRevision,
+    /// Source ref (e.g. `refs/heads/main`)
+    SourceRef,
+}
+
+/// Builder and source revision: a compromised or unrelated pipeline producing
+/// one of the attestations shows up as a mismatch on one of these
+pub const DEFAULT_CONSISTENCY_FIELDS: &[ConsistencyField] = &[
+    ConsistencyField::Issuer,
+    ConsistencyField::BuildSigner,
+    ConsistencyField::SourceRepository,
+    ConsistencyField::SourceRevision,
+];
+
+impl ConsistencyField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsistencyField::Issuer => "issuer",
+            ConsistencyField::BuildSigner => "build_signer",
+            ConsistencyField::BuildSignerDigest => "build_signer_digest",
+            ConsistencyField::SourceRepository => "source_repository",
+            ConsistencyField::SourceRevision => "source_revision",
+            ConsistencyField::SourceRef => "source_ref",
+        }
+    }
+
+    /// Value of this field, falling back to the legacy GitHub extensions
+    pub fn value<'a>(&self, extensions: &'a FulcioExtensions) -> Option<&'a str> {
+        let value = match self {
+            ConsistencyField::Issuer => &extensions.issuer,
+            ConsistencyField::BuildSigner => &extensions.build_signer_uri,
+            ConsistencyField::BuildSignerDigest => &extensions.build_signer_digest,
+            ConsistencyField::SourceRepository => {
+                if extensions.source_repository_uri.is_some() {
+                    &extensions.source_repository_uri
+                } else {
+                    &extensions.github_workflow_repository
+                }
+            }
+            ConsistencyField::SourceRevision => {
+                if extensions.source_repository_digest.is_some() {
+                    &extensions.source_repository_digest
+                } else {
+                    &extensions.github_workflow_sha
+                }
+            }
+            ConsistencyField::SourceRef => {
+                if extensions.source_repository_ref.is_some() {
+                    &extensions.source_repository_ref
+                } else {
+                    &extensions.github_workflow_ref
+                }
+            }
+        };
+        value.as_deref()
+    }
+}
+
+/// A field whose value differs between bundles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Inconsistency {
+    pub field: ConsistencyField,
+
+    /// Bundle name and its value of the field (`None` if the certificate lacks it)
+    pub values: Vec<(String, Option<String>)>,
+}
+
+impl std::fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<String> = self
+            .values
+            .iter()
+            .map(|(bundle, value)| {
+                format!("{}={}", bundle, value.as_deref().unwrap_or("<missing>"))
+            })
+            .collect();
+        write!(
+            f,
+            "{} differs across bundles: {}",
+            self.field.name(),
+            values.join(", ")
+        )
+    }
+}
+
+/// Compare signer attributes across bundles
+///
+/// A field is consistent if every bundle has the same value, or no bundle has
+/// it at all. A field present in some certificates but missing from others is
+/// reported, since the bundles cannot be shown to come from the same pipeline.
+///
+/// # Arguments
+///
+/// * `fields` - Attributes to compare, e.g. `DEFAULT_CONSISTENCY_FIELDS`
+/// * `bundles` - Bundle name and Fulcio extensions of each signing certificate
+///
+/// # Returns
+///
+/// One `Inconsistency` per field that differs, in the order of `fields`.
+pub fn check_consistency(
+    fields: &[ConsistencyField],
+    bundles: &[(String, FulcioExtensions)],
+) -> Vec<Inconsistency> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let values: Vec<(String, Option<String>)> = bundles
+                .iter()
+                .map(|(name, extensions)| {
+                    (name.clone(), field.value(extensions).map(str::to_string))
+                })
+                .collect();
+            let consistent = values.windows(2).all(|pair| pair[0].1 == pair[1].1);
+            (!consistent).then_some(Inconsistency {
+                field: *field,
+                values,
+            })
+        })
+        .collect()
+}
+
+/// Fulcio extensions of a bundle's signing certificate
+///
+/// This only parses the certificate; verify the bundle before relying on the values.
+pub fn bundle_fulcio_extensions(bundle_json: &[u8]) -> Result<FulcioExtensions, VerificationError> {
+    let bundle = parse_bundle_from_bytes(bundle_json)?;
+    let der = decode_base64(&bundle.verification_material.certificate.raw_bytes)?;
+    let cert = ParsedCertificate::from_der(&der)?;
+    Ok(extract_fulcio_extensions(&cert)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extensions(repository: &str, revision: Option<&str>) -> FulcioExtensions {
+        FulcioExtensions {
+            issuer: Some("https://token.actions.githubusercontent.com".to_string()),
+            build_signer_uri: Some(format!(
+                "{}/.github/workflows/release.yml@refs/heads/main",
+                repository
+            )),
+            source_repository_uri: Some(repository.to_string()),
+            source_repository_digest: revision.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_consistent_bundles() {
+        let bundles = vec![
+            (
+                "provenance".to_string(),
+                extensions("https://github.com/org/app", Some("abc")),
+            ),
+            (
+                "sbom".to_string(),
+                extensions("https://github.com/org/app", Some("abc")),
+            ),
+        ];
+        assert!(check_consistency(DEFAULT_CONSISTENCY_FIELDS, &bundles).is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_revision_and_builder() {
+        let bundles = vec![
+            (
+                "provenance".to_string(),
+                extensions("https://github.com/org/app", Some("abc")),
+            ),
+            (
+                "sbom".to_string(),
+                extensions("https://github.com/evil/app", Some("def")),
+            ),
+        ];
+        let inconsistencies = check_consistency(DEFAULT_CONSISTENCY_FIELDS, &bundles);
+        let fields: Vec<ConsistencyField> = inconsistencies.iter().map(|i| i.field).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ConsistencyField::BuildSigner,
+                ConsistencyField::SourceRepository,
+                ConsistencyField::SourceRevision
+            ]
+        );
+        assert_eq!(
+            inconsistencies[2].to_string(),
+            "source_revision differs across bundles: provenance=abc, sbom=def"
+        );
+    }
+
+    #[test]
+    fn test_missing_in_one_bundle_is_inconsistent() {
+        let bundles = vec![
+            (
+                "provenance".to_string(),
+                extensions("https://github.com/org/app", Some("abc")),
+            ),
+            (
+                "scan".to_string(),
+                extensions("https://github.com/org/app", None),
+            ),
+        ];
+        let inconsistencies = check_consistency(&[ConsistencyField::SourceRevision], &bundles);
+        assert_eq!(inconsistencies.len(), 1);
+        assert_eq!(inconsistencies[0].values[1], ("scan".to_string(), None));
+
+        // Missing everywhere is not a mismatch
+        assert!(check_consistency(&[ConsistencyField::SourceRef], &bundles).is_empty());
+    }
+
+    #[test]
+    fn test_legacy_extension_fallback() {
+        let legacy = FulcioExtensions {
+            github_workflow_sha: Some("abc".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ConsistencyField::SourceRevision.value(&legacy), Some("abc"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Policies on the dependencies a build resolved
+//!
+//! A SLSA v1 provenance lists the artifacts the build fetched in
+//! `buildDefinition.resolvedDependencies` (`materials` in v0.2): the source
+//! checkout, base images, downloaded tools. The verifier returns them in
+//! `VerificationResult::resolved_dependencies`, and a `DependencyPolicy` in
+//! `VerificationOptions::dependency_policy` rejects builds that pulled in
+//! anything from outside an allowlist of URIs.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::attestations::SLSA_PROVENANCE_V1;
+use super::pattern::wildcard_match;
+use crate::error::PolicyError;
+use crate::slsa::SLSA_PROVENANCE_V0_2;
+use crate::types::digest::{Digest, DigestAlgorithm};
+use crate::types::dsse::Statement;
+
+/// An artifact a build resolved, as recorded in its provenance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedDependency {
+    /// Location the artifact was fetched from, e.g.
+    /// `git+https://github.com/org/repo@refs/heads/main`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Digests in a supported algorithm; others, such as `sha1`, are dropped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub digests: Vec<Digest>,
+}
+
+impl ResolvedDependency {
+    fn from_descriptor(descriptor: &Value) -> Self {
+        let string = |value: &Value| value.as_str().filter(|s| !s.is_empty()).map(str::to_string);
+        let digests = descriptor["digest"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, hex)| {
+                let algorithm = DigestAlgorithm::from_name(name)?;
+                Digest::from_hex(algorithm, hex.as_str()?).ok()
+            })
+            .collect();
+        ResolvedDependency {
+            uri: string(&descriptor["uri"]),
+            name: string(&descriptor["name"]),
+            digests,
+        }
+    }
+}
+
+/// Dependencies a SLSA v1 or v0.2 provenance records
+///
+/// Statements of other predicate types have none.
+pub fn resolved_dependencies(statement: &Statement) -> Vec<ResolvedDependency> {
+    let descriptors = match statement.predicate_type.as_str() {
+        SLSA_PROVENANCE_V1 => &statement.predicate["buildDefinition"]["resolvedDependencies"],
+        SLSA_PROVENANCE_V0_2 => &statement.predicate["materials"],
+        _ => return Vec::new(),
+    };
+    descriptors
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(ResolvedDependency::from_descriptor)
+        .collect()
+}
+
+/// Requirements on every dependency of a build
+///
+/// # Example
+///
+/// ```ignore
+/// let options = VerificationOptions::default().with_dependency_policy(DependencyPolicy {
+///     allowed_uris: vec!["git+https://github.com/org/*".to_string()],
+///     require_digest: true,
+/// });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DependencyPolicy {
+    /// URI patterns (`*` matches any sequence of characters) every dependency
+    /// must match; a dependency without a URI matches none
+    #[serde(default)]
+    pub allowed_uris: Vec<String>,
+
+    /// Every dependency must be pinned by a digest in a supported algorithm
+    #[serde(default)]
+    pub require_digest: bool,
+}
+
+impl DependencyPolicy {
+    /// Check the dependencies of a build
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError::DependencyPolicy` naming the first dependency
+    /// that violates the policy.
+    pub fn evaluate(&self, dependencies: &[ResolvedDependency]) -> Result<(), PolicyError> {
+        for dependency in dependencies {
+            let label = dependency
+                .uri
+                .as_deref()
+                .or(dependency.name.as_deref())
+                .unwrap_or("(unnamed)");
+
+            let allowed = dependency.uri.as_deref().is_some_and(|uri| {
+                self.allowed_uris.iter().any(|pattern| wildcard_match(pattern, uri))
+            });
+            if !allowed {
+                return Err(PolicyError::DependencyPolicy(format!(
+                    "dependency {} is not from an allowed source",
+                    label
+                )));
+            }
+            if self.require_digest && dependency.digests.is_empty() {
+                return Err(PolicyError::DependencyPolicy(format!("dependency {} is not pinned by digest", label)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const COMMIT: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    fn provenance(dependencies: Value) -> Statement {
+        serde_json::from_value(json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "subject": [{ "name": "artifact", "digest": { "sha256": "01".repeat(32) } }],
+            "predicateType": SLSA_PROVENANCE_V1,
+            "predicate": { "buildDefinition": { "resolvedDependencies": dependencies } },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolved_dependencies() {
+        let statement = provenance(json!([
+            { "uri": "git+https://github.com/org/app@refs/heads/main", "digest": { "gitCommit": COMMIT } },
+            { "name": "go.mod", "digest": { "sha1": COMMIT } },
+        ]));
+        let dependencies = resolved_dependencies(&statement);
+        assert_eq!(dependencies.len(), 2);
+        assert_eq!(dependencies[0].digests[0].algorithm, DigestAlgorithm::GitCommit);
+        assert_eq!(dependencies[1].uri, None);
+        assert_eq!(dependencies[1].name.as_deref(), Some("go.mod"));
+        assert!(dependencies[1].digests.is_empty());
+    }
+
+    #[test]
+    fn test_dependency_policy() {
+        let policy = DependencyPolicy {
+            allowed_uris: vec!["git+https://github.com/org/*".to_string()],
+            require_digest: true,
+        };
+        let own = provenance(json!([{ "uri": "git+https://github.com/org/app", "digest": { "gitCommit": COMMIT } }]));
+        assert!(policy.evaluate(&resolved_dependencies(&own)).is_ok());
+
+        let foreign = provenance(json!([{ "uri": "git+https://github.com/other/app", "digest": { "gitCommit": COMMIT } }]));
+        let err = policy.evaluate(&resolved_dependencies(&foreign)).unwrap_err();
+        assert!(err.to_string().contains("github.com/other/app"), "{}", err);
+
+        let unpinned = provenance(json!([{ "uri": "git+https://github.com/org/app" }]));
+        assert!(policy.evaluate(&resolved_dependencies(&unpinned)).is_err());
+
+        let unnamed = provenance(json!([{ "name": "go.mod" }]));
+        assert!(DependencyPolicy::default().evaluate(&resolved_dependencies(&unnamed)).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! Verification policy files
+//!
+//! A policy file is a YAML document describing `VerificationOptions`, so the
+//! policy can be reviewed and versioned in git instead of being built in code.
+//! Unknown fields are rejected at every level, so a misspelled key fails to load
+//! instead of silently relaxing the policy.
+//!
+//! ```yaml
+//! version: 1
+//!
+//! # Expected subject digest (<alg>:<hex>, or bare SHA-2 hex)
+//! digest: "sha256:<hex>"
+//!
+//! # Subject name pattern; the digest is read from the first matching subject
+//! subject_name: "myapp-*-linux-amd64"
+//!
+//! # Exact OIDC issuer and subject
+//! issuer: "https://token.actions.githubusercontent.com"
+//! subject: "https://github.com/org/repo/.github/workflows/release.yml@refs/tags/v1.0.0"
+//!
+//! # Signer identity patterns (`*` matches any sequence of characters)
+//! identity:
+//!   issuers: ["https://token.actions.githubusercontent.com"]
+//!   subject: "https://github.com/org/repo/.github/workflows/release.yml@refs/tags/v*"
+//!   repository: "https://github.com/org/repo"
+//!   workflow_ref: "refs/tags/v*"
+//!
+//! # Accepted in-toto predicate types (`*` patterns)
+//! predicate_types: ["https://slsa.dev/provenance/v1"]
+//!
+//! # Required timestamp mechanism: rfc3161 or rekor (omit to accept either)
+//! timestamp: rfc3161
+//!
+//! # SHA-256 digests of the accepted Fulcio root certificates
+//! pinned_roots: ["sha256:<hex>"]
+//!
+//! # Shape of the Fulcio chain (for private deployments with deeper or cross-signed chains)
+//! chain:
+//!   max_depth: 4
+//!   require_self_signed_root: true
+//!   require_pinned_root: true
+//!
+//! # Log IDs (SHA-256 of the log public key) of the accepted transparency logs
+//! transparency_logs: ["sha256:<hex>"]
+//!
+//! # Constraints on the RFC 3161 timestamp token
+//! tsa:
+//!   policy_oids: ["1.3.6.1.4.1.57264.2"]
+//!   max_accuracy_millis: 1000
+//!   honor_accuracy: true
+//!   ordering: false
+//!   require_nonce: false
+//!
+//! # Check statements against the in-toto and SLSA v1 schemas
+//! strict_statement: true
+//!
+//! # Accept redactable statements with redacted predicate fields
+//! allow_redacted_predicate: false
+//!
+//! # Require a SLSA source provenance attestation
+//! source:
+//!   repository: "https://github.com/org/repo"
+//!   branches: ["refs/heads/main"]
+//!   required_controls: [CONTINUITY_ENFORCED, REVIEW_ENFORCED]
+//!   min_reviewers: 1
+//!
+//! # Sources the build's resolved dependencies must come from
+//! dependencies:
+//!   allowed_uris: ["git+https://github.com/org/*"]
+//!   require_digest: true
+//!
+//! # Verify as of this time: reject bundles signed after it
+//! verify_at: "2025-06-01T00:00:00Z"
+//!
+//! # Accepted certificate and DSSE signature algorithms
+//! signature_algorithms: [ecdsa_sha256, ecdsa_sha384]
+//! ```
+//!
+//! Only `version` is required; an omitted field is not checked.
+
+use std::path::Path;
+
+use crate::types::time::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use super::chain::ChainPolicy;
+use super::dependency::DependencyPolicy;
+use super::identity::IdentityPolicy;
+use super::source::SourcePolicy;
+use super::tsa::TsaPolicy;
+use crate::crypto::algorithm::SignatureAlgorithm;
+use crate::error::PolicyFileError;
+use crate::types::digest::Digest;
+use crate::types::result::{TimestampRequirement, VerificationOptions};
+
+/// Version of the policy file schema
+pub const POLICY_FILE_VERSION: u32 = 1;
+
+/// Parsed policy file (see the module documentation for the schema)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyFile {
+    /// Schema version, must be `POLICY_FILE_VERSION`
+    pub version: u32,
+
+    pub digest: Option<Digest>,
+    pub subject_name: Option<String>,
+    pub issuer: Option<String>,
+    pub subject: Option<String>,
+    pub identity: Option<IdentityPolicy>,
+    pub predicate_types: Option<Vec<String>>,
+    pub timestamp: Option<TimestampRequirement>,
+    pub pinned_roots: Option<Vec<Digest>>,
+    pub chain: Option<ChainPolicy>,
+    pub transparency_logs: Option<Vec<Digest>>,
+    pub tsa: Option<TsaPolicy>,
+    #[serde(default)]
+    pub strict_statement: bool,
+    #[serde(default)]
+    pub allow_redacted_predicate: bool,
+    pub source: Option<SourcePolicy>,
+    pub dependencies: Option<DependencyPolicy>,
+    pub verify_at: Option<Timestamp>,
+    pub signature_algorithms: Option<Vec<SignatureAlgorithm>>,
+}
+
+impl PolicyFile {
+    /// Parse a policy from YAML text
+    ///
+    /// # Errors
+    ///
+    /// Returns `Parse` for invalid YAML, unknown fields or invalid values, and
+    /// `UnsupportedVersion` if `version` is not `POLICY_FILE_VERSION`.
+    pub fn from_yaml(yaml: &str) -> Result<Self, PolicyFileError> {
+        let policy: PolicyFile =
+            serde_yaml::from_str(yaml).map_err(|e| PolicyFileError::Parse(e.to_string()))?;
+        if policy.version != POLICY_FILE_VERSION {
+            return Err(PolicyFileError::UnsupportedVersion {
+                found: policy.version,
+                expected: POLICY_FILE_VERSION,
+            });
+        }
+        Ok(policy)
+    }
+
+    /// Read and parse a policy file
+    pub fn from_path(path: &Path) -> Result<Self, PolicyFileError> {
+        Self::from_yaml(&std::fs::read_to_string(path)?)
+    }
+
+    /// The verification options this policy describes
+    pub fn into_options(self) -> VerificationOptions {
+        VerificationOptions {
+            expected_digest: self.digest,
+            expected_subject_name: self.subject_name,
+            expected_issuer: self.issuer,
+            expected_subject: self.subject,
+            identity_policy: self.identity,
+            allowed_signature_algorithms: self.signature_algorithms,
+            allowed_predicate_types: self.predicate_types,
+            required_timestamp: self.timestamp,
+            pinned_root_digests: self.pinned_roots,
+            chain_policy: self.chain,
+            allowed_log_ids: self.transparency_logs,
+            transparency_log_keys: None,
+            tsa_policy: self.tsa,
+            strict_statement: self.strict_statement,
+            allow_redacted_predicate: self.allow_redacted_predicate,
+            source_policy: self.source,
+            dependency_policy: self.dependencies,
+            verify_at: self.verify_at,
+        }
+    }
+}
+
+impl VerificationOptions {
+    /// Load verification options from a YAML policy file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Policy file following the schema in `policy::file`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not valid for the schema
+    /// (including unknown fields), or has an unsupported version.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = VerificationOptions::from_policy_file(Path::new("policy.yaml"))?;
+    /// let result = verifier.verify_bundle(bundle_path, options, &trust_bundle, tsa_chain.as_ref())?;
+    /// ```
+    pub fn from_policy_file(path: &Path) -> Result<Self, PolicyFileError> {
+        Ok(PolicyFile::from_path(path)?.into_options())
+    }
+
+    /// Parse verification options from YAML policy text
+    pub fn from_policy_yaml(yaml: &str) -> Result<Self, PolicyFileError> {
+        Ok(PolicyFile::from_yaml(yaml)?.into_options())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_POLICY: &str = r#"
+version: 1
+digest: "sha256:0101010101010101010101010101010101010101010101010101010101010101"
+issuer: "https://token.actions.githubusercontent.com"
+identity:
+  issuers: ["https://token.actions.githubusercontent.com"]
+  repository: "https://github.com/org/repo"
+  workflow_ref: "refs/tags/v*"
+predicate_types: ["https://slsa.dev/provenance/v1"]
+timestamp: rfc3161
+pinned_roots: ["sha256:0202020202020202020202020202020202020202020202020202020202020202"]
+chain:
+  max_depth: 4
+  require_pinned_root: false
+transparency_logs: ["sha256:0303030303030303030303030303030303030303030303030303030303030303"]
+tsa:
+  policy_oids: ["1.3.6.1.4.1.57264.2"]
+  honor_accuracy: true
+dependencies:
+  allowed_uris: ["git+https://github.com/org/*"]
+verify_at: "2025-06-01T00:00:00Z"
+signature_algorithms: [ecdsa_sha384]
+"#;
+
+    #[test]
+    fn test_full_policy() {
+        let options = VerificationOptions::from_policy_yaml(FULL_POLICY).unwrap();
+        assert_eq!(options.expected_digest, Some(Digest::sha256([1; 32])));
+        assert_eq!(
+            options.expected_issuer.as_deref(),
+            Some("https://token.actions.githubusercontent.com")
+        );
+        assert_eq!(options.expected_subject, None);
+
+        let identity = options.identity_policy.unwrap();
+        assert_eq!(
+            identity.repository.as_deref(),
+            Some("https://github.com/org/repo")
+        );
+        assert_eq!(identity.subject, None);
+
+        assert_eq!(
+            options.allowed_predicate_types,
+            Some(vec!["https://slsa.dev/provenance/v1".to_string()])
+        );
+        assert_eq!(
+            options.required_timestamp,
+            Some(TimestampRequirement::Rfc3161)
+        );
+        assert_eq!(
+            options.pinned_root_digests,
+            Some(vec![Digest::sha256([2; 32])])
+        );
+        let chain = options.chain_policy.unwrap();
+        assert_eq!(chain.max_depth, Some(4));
+        assert!(chain.require_self_signed_root);
+        assert!(!chain.require_pinned_root);
+        assert_eq!(
+            options.allowed_log_ids,
+            Some(vec![Digest::sha256([3; 32])])
+        );
+        assert_eq!(
+            options.allowed_signature_algorithms,
+            Some(vec![SignatureAlgorithm::EcdsaSha384])
+        );
+
+        let tsa = options.tsa_policy.unwrap();
+        assert_eq!(tsa.policy_oids, vec!["1.3.6.1.4.1.57264.2".to_string()]);
+        assert!(tsa.honor_accuracy);
+        assert_eq!(tsa.max_accuracy_millis, None);
+
+        let dependencies = options.dependency_policy.unwrap();
+        assert_eq!(dependencies.allowed_uris, vec!["git+https://github.com/org/*".to_string()]);
+        assert!(!dependencies.require_digest);
+
+        assert_eq!(options.verify_at.map(|time| time.timestamp()), Some(1_748_736_000));
+    }
+
+    #[test]
+    fn test_minimal_policy() {
+        let options = VerificationOptions::from_policy_yaml("version: 1\n").unwrap();
+        assert_eq!(
+            options.options_hash().unwrap(),
+            VerificationOptions::default().options_hash().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_rejected() {
+        let top_level = VerificationOptions::from_policy_yaml("version: 1\nissuers: [x]\n");
+        assert!(matches!(top_level, Err(PolicyFileError::Parse(ref e)) if e.contains("issuers")));
+
+        let nested = VerificationOptions::from_policy_yaml(
+            "version: 1\nidentity:\n  repo: https://github.com/org/repo\n",
+        );
+        assert!(matches!(nested, Err(PolicyFileError::Parse(ref e)) if e.contains("repo")));
+    }
+
+    #[test]
+    fn test_invalid_values_rejected() {
+        assert!(VerificationOptions::from_policy_yaml("version: 1\ntimestamp: any\n").is_err());
+        assert!(VerificationOptions::from_policy_yaml("version: 1\ndigest: \"md5:00\"\n").is_err());
+        assert!(VerificationOptions::from_policy_yaml("digest: \"sha256:00\"\n").is_err());
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        assert!(matches!(
+            VerificationOptions::from_policy_yaml("version: 2\n"),
+            Err(PolicyFileError::UnsupportedVersion {
+                found: 2,
+                expected: 1
+            })
+        ));
+    }
+}
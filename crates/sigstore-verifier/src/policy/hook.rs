@@ -0,0 +1,136 @@
+//! Custom policy evaluation hook
+//!
+//! For rules the built-in `VerificationOptions` cannot express, a `PolicyHook`
+//! attached with `AttestationVerifier::with_policy_hook` runs after every other
+//! check passes. It receives the decoded statement, the signing certificate's
+//! Fulcio extensions and the transparency log metadata, and allows or denies
+//! the bundle. The `cel` feature provides a hook evaluating CEL expressions
+//! (see `policy::cel`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::hex_encode;
+use crate::error::PolicyError;
+use crate::parser::bundle::decode_base64;
+use crate::types::bundle::TransparencyLogEntry;
+use crate::types::certificate::{FulcioExtensions, OidcIdentity};
+use crate::types::digest::Digest;
+use crate::types::dsse::Statement;
+use crate::types::time::Timestamp;
+
+/// Everything a `PolicyHook` can inspect about a verified bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyInput {
+    /// Decoded in-toto statement
+    pub statement: Statement,
+
+    /// Verified subject digest
+    pub subject_digest: Digest,
+
+    /// Signing time from the RFC 3161 timestamp or Rekor integrated time
+    pub signing_time: Timestamp,
+
+    /// OIDC identity of the signing certificate
+    pub identity: Option<OidcIdentity>,
+
+    /// All Fulcio extensions of the signing certificate
+    pub extensions: FulcioExtensions,
+
+    /// First transparency log entry, if the bundle has one
+    pub tlog: Option<TlogMetadata>,
+}
+
+/// Transparency log entry metadata exposed to policy hooks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlogMetadata {
+    /// Entry kind and version (e.g. "dsse" and "0.0.1")
+    pub kind: Option<String>,
+    pub version: Option<String>,
+
+    /// Hex-encoded log ID (SHA-256 of the log's public key)
+    pub log_id: Option<String>,
+
+    /// Entry index in the log
+    pub log_index: Option<u64>,
+
+    /// Unix time the entry was integrated into the log
+    pub integrated_time: Option<i64>,
+}
+
+impl TlogMetadata {
+    pub fn from_entry(entry: &TransparencyLogEntry) -> Self {
+        TlogMetadata {
+            kind: entry.kind_version.as_ref().map(|kv| kv.kind.clone()),
+            version: entry.kind_version.as_ref().map(|kv| kv.version.clone()),
+            log_id: entry
+                .log_id
+                .as_ref()
+                .and_then(|log_id| decode_base64(&log_id.key_id).ok())
+                .map(|bytes| hex_encode(&bytes)),
+            log_index: entry
+                .log_index
+                .as_ref()
+                .and_then(|index| index.parse().ok()),
+            integrated_time: entry.integrated_time.parse().ok(),
+        }
+    }
+}
+
+/// Outcome of a policy hook
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyDecision {
+    pub allow: bool,
+
+    /// Reasons for a denial (or notes for an allow)
+    pub messages: Vec<String>,
+}
+
+impl PolicyDecision {
+    pub fn allow() -> Self {
+        PolicyDecision {
+            allow: true,
+            messages: Vec::new(),
+        }
+    }
+
+    pub fn deny(messages: Vec<String>) -> Self {
+        PolicyDecision {
+            allow: false,
+            messages,
+        }
+    }
+}
+
+/// Custom policy evaluated after all built-in checks pass
+pub trait PolicyHook: Send + Sync {
+    /// Decide whether a verified bundle is acceptable
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError::Evaluation` if the policy itself cannot be evaluated;
+    /// the bundle is then rejected.
+    fn evaluate(&self, input: &PolicyInput) -> Result<PolicyDecision, PolicyError>;
+}
+
+impl<F> PolicyHook for F
+where
+    F: Fn(&PolicyInput) -> Result<PolicyDecision, PolicyError> + Send + Sync,
+{
+    fn evaluate(&self, input: &PolicyInput) -> Result<PolicyDecision, PolicyError> {
+        self(input)
+    }
+}
+
+/// Run `hook` and turn a denial into `PolicyError::Denied`
+pub(crate) fn enforce(hook: &dyn PolicyHook, input: &PolicyInput) -> Result<(), PolicyError> {
+    let decision = hook.evaluate(input)?;
+    if decision.allow {
+        return Ok(());
+    }
+    let reason = if decision.messages.is_empty() {
+        "denied by policy hook".to_string()
+    } else {
+        decision.messages.join("; ")
+    };
+    Err(PolicyError::Denied(reason))
+}
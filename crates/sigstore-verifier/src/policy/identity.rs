@@ -0,0 +1,401 @@
+use serde::{Deserialize, Serialize};
+
+use super::pattern::wildcard_match;
+use crate::crypto::hash::constant_time_eq;
+use crate::error::PolicyError;
+use crate::types::certificate::OidcIdentity;
+
+/// OIDC issuer for GitHub Actions workflow tokens
+pub const GITHUB_ACTIONS_ISSUER: &str = "https://token.actions.githubusercontent.com";
+
+/// OIDC issuer for gitlab.com CI job tokens
+pub const GITLAB_COM_ISSUER: &str = "https://gitlab.com";
+
+/// OIDC issuer for Google accounts, including Cloud Build service accounts
+pub const GOOGLE_ACCOUNTS_ISSUER: &str = "https://accounts.google.com";
+
+/// OIDC issuer for GitHub OAuth logins (human signers, not Actions)
+pub const GITHUB_OAUTH_ISSUER: &str = "https://github.com/login/oauth";
+
+/// OIDC issuer for Microsoft accounts
+pub const MICROSOFT_ISSUER: &str = "https://login.microsoftonline.com";
+
+/// OAuth issuers the public-good Fulcio accepts for `cosign sign` with an email identity
+pub const OAUTH_EMAIL_ISSUERS: [&str; 3] = [GOOGLE_ACCOUNTS_ISSUER, GITHUB_OAUTH_ISSUER, MICROSOFT_ISSUER];
+
+const GITHUB_URL: &str = "https://github.com";
+const GITHUB_WORKFLOWS_DIR: &str = ".github/workflows/";
+
+/// Constraints on the signer identity embedded in a Fulcio certificate
+///
+/// The issuer must equal one of `issuers`. All other fields are patterns where
+/// `*` matches any sequence of characters. An empty issuer list or a `None`
+/// field is not checked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IdentityPolicy {
+    /// Accepted OIDC issuers
+    #[serde(default)]
+    pub issuers: Vec<String>,
+
+    /// Pattern for the certificate SAN (workflow URI or email)
+    pub subject: Option<String>,
+
+    /// Pattern for the source repository extension
+    pub repository: Option<String>,
+
+    /// Pattern for the source repository ref extension (e.g., "refs/heads/main")
+    pub workflow_ref: Option<String>,
+}
+
+impl IdentityPolicy {
+    /// Policy for an attestation signed directly by a GitHub Actions workflow
+    ///
+    /// # Arguments
+    ///
+    /// * `org` - Repository owner (e.g., "actions")
+    /// * `repo` - Repository name (e.g., "attest-build-provenance")
+    /// * `workflow_path` - Workflow file, either "release.yml" or ".github/workflows/release.yml"
+    /// * `ref_pattern` - Git ref the workflow ran on (e.g., "refs/heads/main" or "refs/tags/v*")
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let policy = IdentityPolicy::github_actions(
+    ///     "actions",
+    ///     "attest-build-provenance",
+    ///     "prober.yml",
+    ///     "refs/heads/main",
+    /// );
+    /// ```
+    pub fn github_actions(org: &str, repo: &str, workflow_path: &str, ref_pattern: &str) -> Self {
+        let repository = github_repository_url(org, repo);
+        Self {
+            issuers: vec![GITHUB_ACTIONS_ISSUER.to_string()],
+            subject: Some(github_workflow_uri(&repository, workflow_path, ref_pattern)),
+            repository: Some(repository),
+            workflow_ref: Some(ref_pattern.to_string()),
+        }
+    }
+
+    /// Policy for an attestation signed by a reusable workflow called from another repository
+    ///
+    /// For reusable workflows Fulcio puts the *called* workflow in the SAN while the
+    /// source repository extensions describe the *calling* repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_org` / `workflow_repo` - Repository hosting the reusable workflow
+    /// * `workflow_path` - Reusable workflow file inside that repository
+    /// * `workflow_ref_pattern` - Ref the reusable workflow was pinned to
+    /// * `caller_repository` - Optional `owner/repo` of the caller; `None` accepts any caller
+    pub fn github_reusable_workflow(
+        workflow_org: &str,
+        workflow_repo: &str,
+        workflow_path: &str,
+        workflow_ref_pattern: &str,
+        caller_repository: Option<&str>,
+    ) -> Self {
+        let workflow_repository = github_repository_url(workflow_org, workflow_repo);
+        Self {
+            issuers: vec![GITHUB_ACTIONS_ISSUER.to_string()],
+            subject: Some(github_workflow_uri(
+                &workflow_repository,
+                workflow_path,
+                workflow_ref_pattern,
+            )),
+            repository: caller_repository.map(|caller| format!("{}/{}", GITHUB_URL, caller)),
+            workflow_ref: None,
+        }
+    }
+
+    /// Policy for an attestation signed by a GitLab CI pipeline
+    ///
+    /// For self-managed instances the issuer is the instance URL, so pass it as
+    /// `instance_url`; use `GITLAB_COM_ISSUER` for gitlab.com.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance_url` - GitLab instance URL (e.g., "https://gitlab.com")
+    /// * `project_path` - Full project path including subgroups (e.g., "group/subgroup/project")
+    /// * `ci_config_path` - Pipeline definition file (e.g., ".gitlab-ci.yml")
+    /// * `ref_pattern` - Git ref the pipeline ran on (e.g., "refs/heads/main")
+    pub fn gitlab_ci(
+        instance_url: &str,
+        project_path: &str,
+        ci_config_path: &str,
+        ref_pattern: &str,
+    ) -> Self {
+        let instance_url = instance_url.trim_end_matches('/');
+        let repository = format!("{}/{}", instance_url, project_path.trim_matches('/'));
+        Self {
+            issuers: vec![instance_url.to_string()],
+            // GitLab separates the project from the config path with `//`
+            subject: Some(format!(
+                "{}//{}@{}",
+                repository,
+                ci_config_path.trim_start_matches('/'),
+                ref_pattern
+            )),
+            repository: Some(repository),
+            workflow_ref: Some(ref_pattern.to_string()),
+        }
+    }
+
+    /// Policy for an attestation signed by a Google Cloud Build service account
+    ///
+    /// Cloud Build certificates carry no source repository extensions; the
+    /// service account email in the SAN is the only identity.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_number` - Numeric Google Cloud project number owning the build
+    pub fn google_cloud_build(project_number: &str) -> Self {
+        Self {
+            issuers: vec![GOOGLE_ACCOUNTS_ISSUER.to_string()],
+            subject: Some(format!("{}@cloudbuild.gserviceaccount.com", project_number)),
+            repository: None,
+            workflow_ref: None,
+        }
+    }
+
+    /// Policy for a bundle signed by a person with `cosign sign` and an email identity
+    ///
+    /// The email comes from the certificate's RFC822 SAN. Restricting the issuer
+    /// matters: the same address can be asserted by different OAuth providers.
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - Expected signer email (a pattern, e.g. "*@example.com")
+    /// * `issuers` - Accepted OAuth issuers, e.g. `&OAUTH_EMAIL_ISSUERS` or `&[GOOGLE_ACCOUNTS_ISSUER]`
+    pub fn email(email: &str, issuers: &[&str]) -> Self {
+        Self {
+            issuers: issuers.iter().map(|issuer| issuer.to_string()).collect(),
+            subject: Some(email.to_string()),
+            repository: None,
+            workflow_ref: None,
+        }
+    }
+
+    /// Check an extracted identity against this policy
+    ///
+    /// # Errors
+    ///
+    /// Returns the first constraint that is missing from or does not match the identity.
+    pub fn evaluate(&self, identity: &OidcIdentity) -> Result<(), PolicyError> {
+        if !self.issuers.is_empty() {
+            let actual = identity
+                .issuer
+                .as_deref()
+                .ok_or(PolicyError::MissingField("issuer"))?;
+            // Check every allowed issuer rather than stopping at the first match
+            let matched = self.issuers.iter().fold(false, |matched, issuer| {
+                matched | constant_time_eq(issuer.as_bytes(), actual.as_bytes())
+            });
+            if !matched {
+                return Err(PolicyError::Mismatch {
+                    field: "issuer",
+                    expected: self.issuers.join(" | "),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+
+        check_pattern("subject", self.subject.as_deref(), identity.subject.as_deref())?;
+        check_pattern(
+            "repository",
+            self.repository.as_deref(),
+            identity.repository.as_deref(),
+        )?;
+        check_pattern(
+            "workflow_ref",
+            self.workflow_ref.as_deref(),
+            identity.workflow_ref.as_deref(),
+        )?;
+
+        Ok(())
+    }
+}
+
+fn check_pattern(
+    field: &'static str,
+    pattern: Option<&str>,
+    actual: Option<&str>,
+) -> Result<(), PolicyError> {
+    let Some(pattern) = pattern else {
+        return Ok(());
+    };
+    let actual = actual.ok_or(PolicyError::MissingField(field))?;
+    if wildcard_match(pattern, actual) {
+        Ok(())
+    } else {
+        Err(PolicyError::Mismatch {
+            field,
+            expected: pattern.to_string(),
+            actual: actual.to_string(),
+        })
+    }
+}
+
+fn github_repository_url(org: &str, repo: &str) -> String {
+    format!("{}/{}/{}", GITHUB_URL, org, repo)
+}
+
+fn github_workflow_uri(repository_url: &str, workflow_path: &str, ref_pattern: &str) -> String {
+    let workflow_path = workflow_path.trim_start_matches('/');
+    if workflow_path.starts_with(GITHUB_WORKFLOWS_DIR) {
+        format!("{}/{}@{}", repository_url, workflow_path, ref_pattern)
+    } else {
+        format!(
+            "{}/{}{}@{}",
+            repository_url, GITHUB_WORKFLOWS_DIR, workflow_path, ref_pattern
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prober_identity() -> OidcIdentity {
+        OidcIdentity {
+            issuer: Some(GITHUB_ACTIONS_ISSUER.to_string()),
+            subject: Some(
+                "https://github.com/actions/attest-build-provenance/.github/workflows/prober.yml@refs/heads/main"
+                    .to_string(),
+            ),
+            workflow_ref: Some("refs/heads/main".to_string()),
+            repository: Some("https://github.com/actions/attest-build-provenance".to_string()),
+            event_name: Some("schedule".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_github_actions_preset_matches() {
+        let policy = IdentityPolicy::github_actions(
+            "actions",
+            "attest-build-provenance",
+            "prober.yml",
+            "refs/heads/*",
+        );
+        assert!(policy.evaluate(&prober_identity()).is_ok());
+
+        let full_path = IdentityPolicy::github_actions(
+            "actions",
+            "attest-build-provenance",
+            ".github/workflows/prober.yml",
+            "refs/heads/main",
+        );
+        assert!(full_path.evaluate(&prober_identity()).is_ok());
+    }
+
+    #[test]
+    fn test_github_actions_preset_rejects_other_ref() {
+        let policy = IdentityPolicy::github_actions(
+            "actions",
+            "attest-build-provenance",
+            "prober.yml",
+            "refs/tags/v*",
+        );
+        assert!(matches!(
+            policy.evaluate(&prober_identity()),
+            Err(PolicyError::Mismatch { field: "subject", .. })
+        ));
+    }
+
+    #[test]
+    fn test_github_reusable_workflow_preset() {
+        let mut identity = prober_identity();
+        identity.subject = Some(
+            "https://github.com/slsa-framework/slsa-github-generator/.github/workflows/generator_generic_slsa3.yml@refs/tags/v2.0.0"
+                .to_string(),
+        );
+
+        let policy = IdentityPolicy::github_reusable_workflow(
+            "slsa-framework",
+            "slsa-github-generator",
+            "generator_generic_slsa3.yml",
+            "refs/tags/v*",
+            Some("actions/attest-build-provenance"),
+        );
+        assert!(policy.evaluate(&identity).is_ok());
+
+        let other_caller = IdentityPolicy::github_reusable_workflow(
+            "slsa-framework",
+            "slsa-github-generator",
+            "generator_generic_slsa3.yml",
+            "refs/tags/v*",
+            Some("evil/repo"),
+        );
+        assert!(matches!(
+            other_caller.evaluate(&identity),
+            Err(PolicyError::Mismatch { field: "repository", .. })
+        ));
+    }
+
+    #[test]
+    fn test_gitlab_ci_preset() {
+        let identity = OidcIdentity {
+            issuer: Some(GITLAB_COM_ISSUER.to_string()),
+            subject: Some(
+                "https://gitlab.com/group/project//.gitlab-ci.yml@refs/heads/main".to_string(),
+            ),
+            workflow_ref: Some("refs/heads/main".to_string()),
+            repository: Some("https://gitlab.com/group/project".to_string()),
+            event_name: None,
+        };
+        let policy =
+            IdentityPolicy::gitlab_ci(GITLAB_COM_ISSUER, "group/project", ".gitlab-ci.yml", "refs/heads/*");
+        assert!(policy.evaluate(&identity).is_ok());
+
+        let self_managed = IdentityPolicy::gitlab_ci(
+            "https://gitlab.example.com/",
+            "group/project",
+            ".gitlab-ci.yml",
+            "refs/heads/*",
+        );
+        assert!(matches!(
+            self_managed.evaluate(&identity),
+            Err(PolicyError::Mismatch { field: "issuer", .. })
+        ));
+    }
+
+    #[test]
+    fn test_email_preset() {
+        let identity = OidcIdentity {
+            issuer: Some(GITHUB_OAUTH_ISSUER.to_string()),
+            subject: Some("alice@example.com".to_string()),
+            workflow_ref: None,
+            repository: None,
+            event_name: None,
+        };
+        assert!(IdentityPolicy::email("alice@example.com", &OAUTH_EMAIL_ISSUERS)
+            .evaluate(&identity)
+            .is_ok());
+        assert!(IdentityPolicy::email("*@example.com", &[GITHUB_OAUTH_ISSUER])
+            .evaluate(&identity)
+            .is_ok());
+        assert!(matches!(
+            IdentityPolicy::email("alice@example.com", &[GOOGLE_ACCOUNTS_ISSUER]).evaluate(&identity),
+            Err(PolicyError::Mismatch { field: "issuer", .. })
+        ));
+        assert!(matches!(
+            IdentityPolicy::email("bob@example.com", &OAUTH_EMAIL_ISSUERS).evaluate(&identity),
+            Err(PolicyError::Mismatch { field: "subject", .. })
+        ));
+    }
+
+    #[test]
+    fn test_missing_issuer() {
+        let mut identity = prober_identity();
+        identity.issuer = None;
+        let policy = IdentityPolicy {
+            issuers: vec![GITHUB_ACTIONS_ISSUER.to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            policy.evaluate(&identity),
+            Err(PolicyError::MissingField("issuer"))
+        ));
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "full")]
+pub mod attestations;
+#[cfg(feature = "cel")]
+pub mod cel;
+#[cfg(feature = "full")]
+pub mod chain;
+#[cfg(feature = "full")]
+pub mod consistency;
+#[cfg(feature = "full")]
+pub mod dependency;
+#[cfg(feature = "policy-file")]
+pub mod file;
+#[cfg(feature = "full")]
+pub mod hook;
+#[cfg(feature = "full")]
+pub mod identity;
+pub mod pattern;
+#[cfg(feature = "full")]
+pub mod source;
+#[cfg(feature = "full")]
+pub mod tsa;
@@ -0,0 +1,56 @@
+/// Match `value` against a pattern where `*` matches any (possibly empty)
+/// sequence of characters. All other characters match literally.
+///
+/// # Example
+///
+/// ```ignore
+/// assert!(wildcard_match("refs/tags/v*", "refs/tags/v1.2.0"));
+/// assert!(!wildcard_match("refs/heads/main", "refs/heads/main2"));
+/// ```
+pub fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let value = value.as_bytes();
+
+    let (mut p, mut v) = (0, 0);
+    // Position of the last `*` seen in the pattern and the value index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, v));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == value[v] {
+            p += 1;
+            v += 1;
+        } else if let Some((star, tried)) = backtrack {
+            p = star + 1;
+            v = tried + 1;
+            backtrack = Some((star, tried + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match_literal() {
+        assert!(wildcard_match("refs/heads/main", "refs/heads/main"));
+        assert!(!wildcard_match("refs/heads/main", "refs/heads/main2"));
+        assert!(!wildcard_match("refs/heads/main", "refs/heads/mai"));
+    }
+
+    #[test]
+    fn test_wildcard_match_star() {
+        assert!(wildcard_match("refs/tags/v*", "refs/tags/v1.2.0"));
+        assert!(wildcard_match("refs/tags/v*", "refs/tags/v"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a*b*c", "a-x-b-y-c"));
+        assert!(!wildcard_match("a*b*c", "a-x-c-y-b"));
+    }
+}
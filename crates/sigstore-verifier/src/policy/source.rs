@@ -0,0 +1,246 @@
+//! SLSA source track policies
+//!
+//! Source provenance attestations (SLSA source track v1) are issued by the
+//! source control system for a revision rather than by a builder for an
+//! artifact: their subject is a git commit (`gitCommit` digest) and their
+//! predicate records the repository, the branch the revision landed on, and
+//! the controls (branch protections) that were enforced when it did. A
+//! `SourcePolicy` in `VerificationOptions::source_policy` requires the verified
+//! bundle to be such an attestation and checks those claims.
+
+use serde::{Deserialize, Serialize};
+
+use super::pattern::wildcard_match;
+use crate::error::PolicyError;
+use crate::types::dsse::Statement;
+
+/// SLSA source provenance v1
+pub const SLSA_SOURCE_PROVENANCE_V1: &str = "https://slsa.dev/source_provenance/v1";
+
+/// Every commit on the branch descends from the previous one (no force pushes)
+pub const CONTINUITY_ENFORCED: &str = "CONTINUITY_ENFORCED";
+
+/// Changes need approval from a second party
+pub const REVIEW_ENFORCED: &str = "REVIEW_ENFORCED";
+
+/// Provenance is issued for every revision of the branch
+pub const PROVENANCE_AVAILABLE: &str = "PROVENANCE_AVAILABLE";
+
+/// Tags cannot be moved or deleted
+pub const TAG_HYGIENE: &str = "TAG_HYGIENE";
+
+/// A control in effect on the branch when the revision was recorded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceControl {
+    /// Control name, e.g. `CONTINUITY_ENFORCED`
+    pub name: String,
+    /// When the control started being enforced (RFC 3339)
+    #[serde(default)]
+    pub since: Option<String>,
+}
+
+/// Predicate of a source provenance attestation
+///
+/// Field names are camelCase as in the source track; the snake_case names used
+/// by earlier tooling are accepted too.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceProvenance {
+    /// Repository URI, e.g. `https://github.com/org/repo`
+    #[serde(alias = "repo_uri")]
+    pub repo_uri: String,
+    /// Branch the revision landed on, e.g. `refs/heads/main`
+    pub branch: String,
+    /// How the revision landed, e.g. `pr_merge` or `push`
+    #[serde(default, alias = "activity_type")]
+    pub activity_type: Option<String>,
+    /// Identity that landed the revision
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Previous revision of the branch
+    #[serde(default, alias = "prev_commit")]
+    pub prev_commit: Option<String>,
+    /// Controls enforced on the branch
+    #[serde(default)]
+    pub controls: Vec<SourceControl>,
+    /// Identities that approved the change, other than its author
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+}
+
+impl SourceProvenance {
+    /// Parse the predicate of a source provenance statement
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError::SourcePolicy` if the statement is not a source
+    /// provenance attestation or its predicate is malformed.
+    pub fn from_statement(statement: &Statement) -> Result<Self, PolicyError> {
+        if statement.predicate_type != SLSA_SOURCE_PROVENANCE_V1 {
+            return Err(PolicyError::SourcePolicy(format!(
+                "{} is not a source provenance attestation",
+                statement.predicate_type
+            )));
+        }
+        serde_json::from_value(statement.predicate.clone())
+            .map_err(|e| PolicyError::SourcePolicy(format!("invalid source provenance: {}", e)))
+    }
+
+    pub fn has_control(&self, name: &str) -> bool {
+        self.controls.iter().any(|control| control.name == name)
+    }
+}
+
+/// Requirements on a source provenance attestation
+///
+/// # Example
+///
+/// ```ignore
+/// let options = VerificationOptions::default().with_source_policy(SourcePolicy {
+///     repository: Some("https://github.com/org/repo".to_string()),
+///     branches: vec!["refs/heads/main".to_string()],
+///     required_controls: vec![CONTINUITY_ENFORCED.to_string(), REVIEW_ENFORCED.to_string()],
+///     min_reviewers: Some(1),
+/// });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourcePolicy {
+    /// Repository URI pattern (`*` matches any sequence of characters)
+    #[serde(default)]
+    pub repository: Option<String>,
+
+    /// Accepted branch patterns (empty accepts any)
+    #[serde(default)]
+    pub branches: Vec<String>,
+
+    /// Controls that must all be enforced, e.g. `REVIEW_ENFORCED`
+    #[serde(default)]
+    pub required_controls: Vec<String>,
+
+    /// Minimum number of distinct reviewers
+    #[serde(default)]
+    pub min_reviewers: Option<u32>,
+}
+
+impl SourcePolicy {
+    /// Check a statement's source provenance claims
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError::SourcePolicy` naming the first violated
+    /// requirement, or if the statement is not a source provenance attestation.
+    pub fn evaluate(&self, statement: &Statement) -> Result<SourceProvenance, PolicyError> {
+        let provenance = SourceProvenance::from_statement(statement)?;
+
+        if let Some(ref repository) = self.repository {
+            if !wildcard_match(repository, &provenance.repo_uri) {
+                return Err(PolicyError::SourcePolicy(format!(
+                    "repository {} does not match {}",
+                    provenance.repo_uri, repository
+                )));
+            }
+        }
+
+        if !self.branches.is_empty() && !self.branches.iter().any(|branch| wildcard_match(branch, &provenance.branch)) {
+            return Err(PolicyError::SourcePolicy(format!("branch {} is not allowed", provenance.branch)));
+        }
+
+        if let Some(control) = self
+            .required_controls
+            .iter()
+            .find(|control| !provenance.has_control(control))
+        {
+            return Err(PolicyError::SourcePolicy(format!("control {} is not enforced", control)));
+        }
+
+        if let Some(min_reviewers) = self.min_reviewers {
+            let mut reviewers: Vec<&str> = provenance.reviewers.iter().map(String::as_str).collect();
+            reviewers.sort_unstable();
+            reviewers.dedup();
+            if reviewers.len() < min_reviewers as usize {
+                return Err(PolicyError::SourcePolicy(format!(
+                    "{} reviewers, policy requires {}",
+                    reviewers.len(),
+                    min_reviewers
+                )));
+            }
+        }
+
+        Ok(provenance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::dsse::Subject;
+    use std::collections::HashMap;
+
+    fn source_statement(predicate: serde_json::Value) -> Statement {
+        Statement {
+            statement_type: "https://in-toto.io/Statement/v1".to_string(),
+            subject: vec![Subject {
+                name: "https://github.com/org/repo/commit/8f1d".to_string(),
+                digest: HashMap::from([(
+                    "gitCommit".to_string(),
+                    "8f1d2e3c4b5a69788796a5b4c3d2e1f0a9b8c7d6".to_string(),
+                )]),
+            }],
+            predicate_type: SLSA_SOURCE_PROVENANCE_V1.to_string(),
+            predicate,
+        }
+    }
+
+    #[test]
+    fn test_source_policy() {
+        let statement = source_statement(serde_json::json!({
+            "repo_uri": "https://github.com/org/repo",
+            "branch": "refs/heads/main",
+            "activity_type": "pr_merge",
+            "controls": [
+                {"name": "CONTINUITY_ENFORCED", "since": "2025-01-01T00:00:00Z"},
+                {"name": "REVIEW_ENFORCED"}
+            ],
+            "reviewers": ["alice", "bob", "alice"]
+        }));
+        let policy = SourcePolicy {
+            repository: Some("https://github.com/org/*".to_string()),
+            branches: vec!["refs/heads/main".to_string()],
+            required_controls: vec![CONTINUITY_ENFORCED.to_string(), REVIEW_ENFORCED.to_string()],
+            min_reviewers: Some(2),
+        };
+        let provenance = policy.evaluate(&statement).unwrap();
+        assert_eq!(provenance.activity_type.as_deref(), Some("pr_merge"));
+
+        let reject = |policy: SourcePolicy| match policy.evaluate(&statement) {
+            Err(PolicyError::SourcePolicy(message)) => message,
+            other => panic!("expected a source policy error, got {:?}", other),
+        };
+        assert_eq!(
+            reject(SourcePolicy {
+                branches: vec!["refs/heads/release/*".to_string()],
+                ..policy.clone()
+            }),
+            "branch refs/heads/main is not allowed"
+        );
+        assert_eq!(
+            reject(SourcePolicy {
+                required_controls: vec![TAG_HYGIENE.to_string()],
+                ..policy.clone()
+            }),
+            "control TAG_HYGIENE is not enforced"
+        );
+        assert_eq!(
+            reject(SourcePolicy {
+                min_reviewers: Some(3),
+                ..policy.clone()
+            }),
+            "2 reviewers, policy requires 3"
+        );
+
+        let mut build = statement.clone();
+        build.predicate_type = "https://slsa.dev/provenance/v1".to_string();
+        assert!(SourcePolicy::default().evaluate(&build).is_err());
+    }
+}
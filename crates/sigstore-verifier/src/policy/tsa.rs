@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::hash::constant_time_eq;
+use crate::error::PolicyError;
+use crate::parser::rfc3161::TSTInfo;
+
+/// TSA policy OID of the Sigstore timestamp authorities (public-good and GitHub)
+pub const SIGSTORE_TSA_POLICY_OID: &str = "1.3.6.1.4.1.57264.2";
+
+/// Constraints on the TSTInfo of an RFC 3161 timestamp
+///
+/// The default policy accepts every token, matching the behavior without a
+/// policy. Bundles timestamped by Rekor are not affected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TsaPolicy {
+    /// Accepted TSA policy OIDs, dotted (empty accepts any)
+    #[serde(default)]
+    pub policy_oids: Vec<String>,
+
+    /// Largest accepted `accuracy`, in milliseconds; tokens that state no
+    /// accuracy are rejected when set
+    #[serde(default)]
+    pub max_accuracy_millis: Option<u64>,
+
+    /// Require the whole `genTime ± accuracy` window, not just `genTime`, to lie
+    /// within the signing certificate's validity
+    #[serde(default)]
+    pub honor_accuracy: bool,
+
+    /// Required value of the `ordering` field (`None` accepts either)
+    #[serde(default)]
+    pub ordering: Option<bool>,
+
+    /// Reject tokens without a nonce
+    #[serde(default)]
+    pub require_nonce: bool,
+
+    /// Nonce sent in the timestamp request, as hex, when the caller requested
+    /// the token itself
+    #[serde(default)]
+    pub expected_nonce: Option<String>,
+}
+
+impl TsaPolicy {
+    /// Policy accepting only tokens issued under the Sigstore TSA policy
+    pub fn sigstore() -> Self {
+        Self {
+            policy_oids: vec![SIGSTORE_TSA_POLICY_OID.to_string()],
+            ..Default::default()
+        }
+    }
+
+    /// Check a token's policy OID, accuracy, ordering and nonce
+    ///
+    /// # Errors
+    ///
+    /// Returns the `PolicyError` of the first violated constraint.
+    pub fn evaluate(&self, tst_info: &TSTInfo) -> Result<(), PolicyError> {
+        if !self.policy_oids.is_empty() && !self.policy_oids.contains(&tst_info.policy) {
+            return Err(PolicyError::TsaPolicyNotAllowed(tst_info.policy.clone()));
+        }
+
+        if let Some(max_millis) = self.max_accuracy_millis {
+            let accuracy = tst_info
+                .accuracy
+                .ok_or_else(|| PolicyError::TimestampAccuracy("token states no accuracy".to_string()))?;
+            if accuracy.as_micros() > max_millis.saturating_mul(1_000) {
+                return Err(PolicyError::TimestampAccuracy(format!(
+                    "{}us exceeds {}ms",
+                    accuracy.as_micros(),
+                    max_millis
+                )));
+            }
+        }
+
+        if let Some(expected) = self.ordering {
+            if tst_info.ordering != expected {
+                return Err(PolicyError::TimestampOrdering { expected });
+            }
+        }
+
+        match (&tst_info.nonce, &self.expected_nonce) {
+            (None, _) if self.require_nonce || self.expected_nonce.is_some() => {
+                Err(PolicyError::TimestampNonce("token has no nonce".to_string()))
+            }
+            (Some(nonce), Some(expected)) => {
+                let expected = hex::decode(expected.trim_start_matches("0x"))
+                    .map_err(|e| PolicyError::TimestampNonce(format!("invalid expected nonce: {}", e)))?;
+                let start = expected.iter().position(|byte| *byte != 0).unwrap_or(expected.len());
+                if constant_time_eq(nonce, &expected[start..]) {
+                    Ok(())
+                } else {
+                    Err(PolicyError::TimestampNonce(format!("got {}", hex::encode(nonce))))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::rfc3161::{Accuracy, HashAlgorithm, MessageImprint};
+    use crate::types::time::Timestamp;
+
+    fn tst_info() -> TSTInfo {
+        TSTInfo {
+            policy: SIGSTORE_TSA_POLICY_OID.to_string(),
+            gen_time: Timestamp::from_ymd_hms(2025, 11, 18, 7, 58, 34).unwrap(),
+            message_imprint: MessageImprint {
+                hash_algorithm: HashAlgorithm::Sha256,
+                hashed_message: vec![0; 32],
+            },
+            accuracy: Some(Accuracy {
+                seconds: 1,
+                millis: 0,
+                micros: 0,
+            }),
+            ordering: false,
+            nonce: Some(vec![0x01, 0x02]),
+        }
+    }
+
+    #[test]
+    fn test_tsa_policy_oid_and_accuracy() {
+        let info = tst_info();
+        TsaPolicy::default().evaluate(&info).unwrap();
+        TsaPolicy::sigstore().evaluate(&info).unwrap();
+
+        let other_oid = TsaPolicy {
+            policy_oids: vec!["1.2.3.4".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            other_oid.evaluate(&info),
+            Err(PolicyError::TsaPolicyNotAllowed(_))
+        ));
+
+        let strict_accuracy = TsaPolicy {
+            max_accuracy_millis: Some(500),
+            ..Default::default()
+        };
+        assert!(matches!(
+            strict_accuracy.evaluate(&info),
+            Err(PolicyError::TimestampAccuracy(_))
+        ));
+        let mut no_accuracy = tst_info();
+        no_accuracy.accuracy = None;
+        assert!(TsaPolicy { max_accuracy_millis: Some(1_000), ..Default::default() }
+            .evaluate(&no_accuracy)
+            .is_err());
+    }
+
+    #[test]
+    fn test_tsa_policy_ordering_and_nonce() {
+        let info = tst_info();
+        let ordered = TsaPolicy {
+            ordering: Some(true),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ordered.evaluate(&info),
+            Err(PolicyError::TimestampOrdering { expected: true })
+        ));
+
+        let nonce = |expected: &str| TsaPolicy {
+            expected_nonce: Some(expected.to_string()),
+            ..Default::default()
+        };
+        nonce("000102").evaluate(&info).unwrap();
+        assert!(nonce("0103").evaluate(&info).is_err());
+
+        let mut no_nonce = tst_info();
+        no_nonce.nonce = None;
+        assert!(TsaPolicy { require_nonce: true, ..Default::default() }
+            .evaluate(&no_nonce)
+            .is_err());
+    }
+}
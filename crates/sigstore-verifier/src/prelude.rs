@@ -0,0 +1,51 @@
+//! Curated high-level API
+//!
+//! `use sigstore_verifier::prelude::*;` brings in what an application needs to
+//! verify bundles: the verifier, its options and result, the trust material
+//! types, the policy extension points and the error type.
+//!
+//! # Stability
+//!
+//! Items re-exported here follow semver: until 1.0, a breaking change to any of
+//! them (removing or renaming an item, a field, a variant or a method, or
+//! changing a signature) only happens in a minor release (`0.x` → `0.x+1`), and
+//! is listed in the changelog. Fields and variants may be added in patch
+//! releases where the type is constructed with `..Default::default()` or
+//! matched with a wildcard.
+//!
+//! The remaining public modules (`parser`, `verifier`, `crypto`, ...) expose the
+//! individual verification steps for advanced use and for the zkVM guests.
+//! They may change in any release; items hidden from the documentation are
+//! internal.
+
+pub use crate::crypto::algorithm::SignatureAlgorithm;
+pub use crate::error::{PolicyError, VerificationError};
+pub use crate::parser::payload::PayloadParser;
+pub use crate::policy::dependency::DependencyPolicy;
+pub use crate::policy::hook::{PolicyDecision, PolicyHook, PolicyInput};
+pub use crate::policy::identity::IdentityPolicy;
+pub use crate::policy::source::SourcePolicy;
+pub use crate::policy::tsa::TsaPolicy;
+pub use crate::types::certificate::{
+    CertificateChain, CertificateMetadata, FulcioInstance, OidcIdentity, ParsedCertificate,
+};
+pub use crate::types::digest::{Digest, DigestAlgorithm};
+pub use crate::types::environment::SigstoreEnvironment;
+pub use crate::types::limits::InputLimits;
+pub use crate::types::result::{
+    TimestampProof, TimestampRequirement, VerificationOptions, VerificationResult,
+};
+pub use crate::verifier::context::{CancellationToken, VerificationContext};
+pub use crate::verifier::pool::CertificatePool;
+pub use crate::verifier::step::{BuiltinStep, StepContext, VerificationStep};
+pub use crate::AttestationVerifier;
+
+pub use crate::fetcher::jsonl::parser::{
+    load_trusted_root_from_jsonl, select_certificate_authority_for, select_timestamp_authority_for,
+};
+
+#[cfg(feature = "policy-file")]
+pub use crate::policy::file::PolicyFile;
+
+#[cfg(feature = "revocation")]
+pub use crate::verifier::revocation::CrlSet;
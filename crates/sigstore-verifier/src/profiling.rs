@@ -0,0 +1,51 @@
+//! Cycle-tracker regions for profiling verification inside a zkVM guest
+//!
+//! With the `cycle-tracker` feature enabled, each [`CycleSpan`] prints the
+//! `cycle-tracker-report-start` / `cycle-tracker-report-end` markers that the SP1
+//! executor aggregates into `ExecutionReport::cycle_tracker`. Regions may nest and
+//! are reported inclusively, so e.g. `base64` cycles are also counted under
+//! `cert_chain`. Without the feature, spans compile to nothing.
+
+/// Parsing the bundle and DSSE payload JSON
+pub const JSON_PARSE: &str = "json_parse";
+
+/// Base64 decoding of bundle fields
+pub const BASE64: &str = "base64";
+
+/// Fulcio certificate chain verification
+pub const CERT_CHAIN: &str = "cert_chain";
+
+/// DSSE envelope signature verification
+pub const DSSE_SIGNATURE: &str = "dsse_signature";
+
+/// Rekor inclusion proof verification
+pub const MERKLE: &str = "merkle";
+
+/// RFC 3161 timestamp and TSA chain verification
+pub const RFC3161: &str = "rfc3161";
+
+/// Every region emitted by the verifier, in pipeline order
+pub const REGIONS: [&str; 6] = [JSON_PARSE, BASE64, CERT_CHAIN, DSSE_SIGNATURE, MERKLE, RFC3161];
+
+/// Guard that marks a cycle-tracker region until it is dropped
+#[must_use = "the region ends when the span is dropped"]
+pub struct CycleSpan {
+    #[cfg_attr(not(feature = "cycle-tracker"), allow(dead_code))]
+    name: &'static str,
+}
+
+impl CycleSpan {
+    /// Start a region named `name`
+    pub fn enter(name: &'static str) -> Self {
+        #[cfg(feature = "cycle-tracker")]
+        println!("cycle-tracker-report-start: {}", name);
+        Self { name }
+    }
+}
+
+impl Drop for CycleSpan {
+    fn drop(&mut self) {
+        #[cfg(feature = "cycle-tracker")]
+        println!("cycle-tracker-report-end: {}", self.name);
+    }
+}
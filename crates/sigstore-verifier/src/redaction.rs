@@ -0,0 +1,327 @@
+//! Redaction of predicate fields for privacy-preserving sharing
+//!
+//! `SigstoreBundle::redact` replaces selected predicate fields with a marker
+//! holding their digest, `{"_redacted": "sha256:<hex>"}`, and leaves the
+//! subjects and the signing certificate untouched. A redacted in-toto bundle
+//! no longer verifies, since its DSSE signature covers the original payload.
+//!
+//! Statements signed as [`REDACTABLE_PAYLOAD_TYPE`] survive redaction. Their
+//! signature covers the statement's [`commitment`], in which the predicate is
+//! replaced by the root of a hash tree over its JSON:
+//!
+//! - an object hashes to `SHA-256(0x01 || JCS({key: "sha256:<member digest>"}))`
+//! - an array to `SHA-256(0x02 || JCS(["sha256:<item digest>", ...]))`
+//! - any other value to `SHA-256(0x00 || JCS(value))`
+//! - a marker to the digest it holds
+//!
+//! so replacing any field by its marker leaves the commitment, and with it the
+//! signature, unchanged. Verification accepts such predicates only when
+//! `VerificationOptions::allow_redacted_predicate` is set.
+//!
+//! Digests are unsalted: a redacted value with few possible values, such as a
+//! boolean or a short enum, can be recovered by hashing candidates.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest as _, Sha256};
+
+use crate::crypto::jcs::to_canonical_vec;
+use crate::error::{RedactionError, VerificationError};
+use crate::parser::bundle::decode_base64;
+use crate::types::bundle::SigstoreBundle;
+
+/// DSSE payload type of in-toto statements signed over their commitment
+pub const REDACTABLE_PAYLOAD_TYPE: &str = "application/vnd.dev.sigstore.redactable-statement.v1+json";
+
+/// Key of the object replacing a redacted value
+pub const REDACTED_KEY: &str = "_redacted";
+
+const LEAF_TAG: u8 = 0x00;
+const OBJECT_TAG: u8 = 0x01;
+const ARRAY_TAG: u8 = 0x02;
+
+/// Predicate fields to redact
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionPolicy {
+    /// JSON Pointers (RFC 6901) into the predicate, e.g. `/buildDefinition/internalParameters`;
+    /// the empty pointer redacts the whole predicate
+    pub fields: Vec<String>,
+}
+
+impl RedactionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also redact the predicate field at `pointer`
+    pub fn with_field(mut self, pointer: impl Into<String>) -> Self {
+        self.fields.push(pointer.into());
+        self
+    }
+}
+
+impl SigstoreBundle {
+    /// Copy of the bundle with the predicate fields of `policy` redacted
+    ///
+    /// The DSSE signatures and verification material are kept as they are, so
+    /// only bundles signed as [`REDACTABLE_PAYLOAD_TYPE`] still verify. Fields
+    /// under an already redacted value are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FieldNotFound` if a pointer of `policy` names no predicate
+    /// field, `InvalidPointer` if it does not start with `/`, and
+    /// `InvalidPayload` if the payload is not a JSON statement.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let policy = RedactionPolicy::new()
+    ///     .with_field("/buildDefinition/internalParameters")
+    ///     .with_field("/runDetails/builder/builderDependencies");
+    /// let shared = bundle.redact(&policy)?;
+    /// ```
+    pub fn redact(&self, policy: &RedactionPolicy) -> Result<SigstoreBundle, VerificationError> {
+        let payload = decode_base64(&self.dsse_envelope.payload)?;
+        let redacted = redact_payload(&payload, policy)?;
+
+        let mut bundle = self.clone();
+        bundle.dsse_envelope.payload = BASE64.encode(redacted);
+        Ok(bundle)
+    }
+}
+
+/// Redact the predicate fields of `policy` in a JSON statement
+///
+/// # Errors
+///
+/// See [`SigstoreBundle::redact`].
+pub fn redact_payload(payload: &[u8], policy: &RedactionPolicy) -> Result<Vec<u8>, VerificationError> {
+    let mut statement: Value = serde_json::from_slice(payload)?;
+    let predicate = predicate_mut(&mut statement)?;
+    for pointer in &policy.fields {
+        redact_field(predicate, pointer)?;
+    }
+    Ok(serde_json::to_vec(&statement)?)
+}
+
+/// Bytes a [`REDACTABLE_PAYLOAD_TYPE`] signature covers: the canonical JSON of
+/// the statement with its predicate replaced by the marker of its digest
+///
+/// # Errors
+///
+/// Returns `InvalidPayload` if the payload is not a JSON statement, and
+/// `InvalidMarker` if a marker does not hold a SHA-256 digest.
+pub fn commitment(payload: &[u8]) -> Result<Vec<u8>, VerificationError> {
+    let mut statement: Value = serde_json::from_slice(payload)?;
+    let predicate = predicate_mut(&mut statement)?;
+    *predicate = marker(&digest(predicate)?);
+    Ok(canonical(&statement)?)
+}
+
+/// Root of the hash tree over a JSON value
+///
+/// # Errors
+///
+/// Returns `InvalidMarker` if a marker does not hold a SHA-256 digest.
+pub fn digest(value: &Value) -> Result<[u8; 32], RedactionError> {
+    if let Some(digest) = marker_digest(value)? {
+        return Ok(digest);
+    }
+    let (tag, children) = match value {
+        Value::Object(members) => {
+            let children = members
+                .iter()
+                .map(|(key, member)| Ok((key.clone(), Value::String(digest_string(&digest(member)?)))))
+                .collect::<Result<Map<_, _>, RedactionError>>()?;
+            (OBJECT_TAG, Value::Object(children))
+        }
+        Value::Array(items) => {
+            let children = items
+                .iter()
+                .map(|item| Ok(Value::String(digest_string(&digest(item)?))))
+                .collect::<Result<Vec<_>, RedactionError>>()?;
+            (ARRAY_TAG, Value::Array(children))
+        }
+        leaf => (LEAF_TAG, leaf.clone()),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update([tag]);
+    hasher.update(canonical(&children)?);
+    Ok(hasher.finalize().into())
+}
+
+/// Whether any part of `value` has been redacted
+pub fn contains_redactions(value: &Value) -> bool {
+    match value {
+        Value::Object(members) => is_marker(value) || members.values().any(contains_redactions),
+        Value::Array(items) => items.iter().any(contains_redactions),
+        _ => false,
+    }
+}
+
+fn redact_field(predicate: &mut Value, pointer: &str) -> Result<(), RedactionError> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return Err(RedactionError::InvalidPointer(pointer.to_string()));
+    }
+
+    let mut target = predicate;
+    for token in pointer.split('/').skip(1) {
+        if is_marker(target) {
+            return Ok(());
+        }
+        let token = token.replace("~1", "/").replace("~0", "~");
+        target = match target {
+            Value::Object(members) => members.get_mut(&token),
+            Value::Array(items) => token.parse::<usize>().ok().and_then(|index| items.get_mut(index)),
+            _ => None,
+        }
+        .ok_or_else(|| RedactionError::FieldNotFound(pointer.to_string()))?;
+    }
+
+    *target = marker(&digest(target)?);
+    Ok(())
+}
+
+fn predicate_mut(statement: &mut Value) -> Result<&mut Value, RedactionError> {
+    statement
+        .as_object_mut()
+        .and_then(|members| members.get_mut("predicate"))
+        .ok_or_else(|| RedactionError::InvalidPayload("statement has no predicate".to_string()))
+}
+
+fn is_marker(value: &Value) -> bool {
+    value.as_object().is_some_and(|members| members.len() == 1 && members.contains_key(REDACTED_KEY))
+}
+
+fn marker(digest: &[u8; 32]) -> Value {
+    serde_json::json!({ REDACTED_KEY: digest_string(digest) })
+}
+
+fn marker_digest(value: &Value) -> Result<Option<[u8; 32]>, RedactionError> {
+    if !is_marker(value) {
+        return Ok(None);
+    }
+    let invalid = || RedactionError::InvalidMarker(value.to_string());
+    let hex_digest = value[REDACTED_KEY]
+        .as_str()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+        .ok_or_else(invalid)?;
+    let mut digest = [0u8; 32];
+    hex::decode_to_slice(hex_digest, &mut digest).map_err(|_| invalid())?;
+    Ok(Some(digest))
+}
+
+fn digest_string(digest: &[u8; 32]) -> String {
+    format!("sha256:{}", hex::encode(digest))
+}
+
+fn canonical(value: &Value) -> Result<Vec<u8>, RedactionError> {
+    to_canonical_vec(value).map_err(|e| RedactionError::InvalidPayload(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn statement() -> Value {
+        json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "subject": [{"name": "app", "digest": {"sha256": "ab".repeat(32)}}],
+            "predicateType": "https://slsa.dev/provenance/v1",
+            "predicate": {
+                "buildDefinition": {
+                    "buildType": "https://actions.github.io/buildtypes/workflow/v1",
+                    "internalParameters": {"github": {"runner_environment": "self-hosted"}},
+                    "resolvedDependencies": [{"uri": "git+https://github.com/org/app"}, {"uri": "pkg:npm/secret@1.0.0"}],
+                },
+                "runDetails": {"builder": {"id": "https://github.com/actions/runner"}},
+            },
+        })
+    }
+
+    fn redact(statement: &Value, fields: &[&str]) -> Result<Value, VerificationError> {
+        let policy = fields.iter().fold(RedactionPolicy::new(), |policy, field| policy.with_field(*field));
+        let redacted = redact_payload(&serde_json::to_vec(statement).unwrap(), &policy)?;
+        Ok(serde_json::from_slice(&redacted).unwrap())
+    }
+
+    fn commit(statement: &Value) -> Vec<u8> {
+        commitment(&serde_json::to_vec(statement).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_redact_replaces_fields_with_markers() {
+        let original = statement();
+        let redacted = redact(
+            &original,
+            &["/buildDefinition/internalParameters", "/buildDefinition/resolvedDependencies/1"],
+        )
+        .unwrap();
+
+        let internal = &original["predicate"]["buildDefinition"]["internalParameters"];
+        assert_eq!(
+            redacted["predicate"]["buildDefinition"]["internalParameters"],
+            json!({ "_redacted": digest_string(&digest(internal).unwrap()) })
+        );
+        assert!(is_marker(&redacted["predicate"]["buildDefinition"]["resolvedDependencies"][1]));
+        assert_eq!(
+            redacted["predicate"]["buildDefinition"]["resolvedDependencies"][0],
+            original["predicate"]["buildDefinition"]["resolvedDependencies"][0]
+        );
+        assert_eq!(redacted["subject"], original["subject"]);
+        assert!(contains_redactions(&redacted["predicate"]));
+        assert!(!contains_redactions(&original["predicate"]));
+    }
+
+    #[test]
+    fn test_commitment_survives_redaction() {
+        let original = statement();
+        let expected = commit(&original);
+
+        for fields in [
+            vec!["/buildDefinition/internalParameters"],
+            vec!["/buildDefinition/resolvedDependencies/1", "/runDetails"],
+            vec!["/buildDefinition", "/buildDefinition/buildType"],
+            vec![""],
+        ] {
+            let redacted = redact(&original, &fields).unwrap();
+            assert_eq!(commit(&redacted), expected, "{:?}", fields);
+        }
+
+        let mut tampered = redact(&original, &["/runDetails"]).unwrap();
+        tampered["predicate"]["buildDefinition"]["buildType"] = json!("https://example.com/other");
+        assert_ne!(commit(&tampered), expected);
+
+        let mut tampered = statement();
+        tampered["subject"][0]["name"] = json!("other");
+        assert_ne!(commit(&tampered), expected);
+    }
+
+    #[test]
+    fn test_redact_errors() {
+        assert!(matches!(
+            redact(&statement(), &["/buildDefinition/missing"]),
+            Err(VerificationError::Redaction(RedactionError::FieldNotFound(_)))
+        ));
+        assert!(matches!(
+            redact(&statement(), &["buildDefinition"]),
+            Err(VerificationError::Redaction(RedactionError::InvalidPointer(_)))
+        ));
+        assert!(matches!(
+            redact(&json!({"subject": []}), &[]),
+            Err(VerificationError::Redaction(RedactionError::InvalidPayload(_)))
+        ));
+
+        let mut forged = statement();
+        forged["predicate"]["runDetails"] = json!({ "_redacted": "sha256:zz" });
+        assert!(matches!(
+            commitment(&serde_json::to_vec(&forged).unwrap()),
+            Err(VerificationError::Redaction(RedactionError::InvalidMarker(_)))
+        ));
+    }
+}
@@ -0,0 +1,586 @@
+//! Verification reports for CI systems
+//!
+//! A [`VerificationReport`] captures the outcome of verifying one bundle, and
+//! [`ReportFormat`] renders a set of reports as plain text, JSON, SARIF 2.1.0
+//! (for GitHub code scanning upload) or in-toto Verification Summary
+//! Attestations (SLSA VSA v1, one statement per line).
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::crypto::hash::sha256;
+use crate::error::VerificationError;
+use crate::parser::bundle::{parse_bundle_from_bytes, parse_dsse_payload};
+use crate::slsa::{self, SlsaLevelAssessment};
+use crate::trail::ProvenanceTrail;
+use crate::types::digest::Digest;
+use crate::types::dsse::Subject;
+use crate::types::result::{VerificationOptions, VerificationResult};
+use crate::types::time::Timestamp;
+
+/// Identifies this verifier in SARIF and VSA output
+pub const VERIFIER_ID: &str = "https://github.com/automata-network/automata-slsa-sigstore-verifier";
+
+/// SLSA Verification Summary Attestation predicate type
+pub const VSA_PREDICATE_TYPE: &str = "https://slsa.dev/verification_summary/v1";
+
+const IN_TOTO_STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_PASS_RULE: &str = "sigstore/verified";
+
+/// Outcome of verifying one bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Bundle location as given by the user, e.g. a file path
+    pub bundle: String,
+
+    /// SHA-256 of the bundle JSON
+    pub bundle_digest: Digest,
+
+    /// Subjects and predicate type of the attested statement, if the bundle could be parsed
+    pub subjects: Vec<Subject>,
+    pub predicate_type: Option<String>,
+
+    /// Options the bundle was verified against
+    pub options: VerificationOptions,
+
+    /// `options.verify_at` if set, otherwise the time the report was made
+    pub time_verified: Timestamp,
+
+    /// Set when verification succeeded
+    pub result: Option<VerificationResult>,
+
+    /// Set when verification failed
+    pub error: Option<ReportError>,
+
+    /// Certificate, transparency log and timestamp evidence of a verified bundle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trail: Option<ProvenanceTrail>,
+
+    /// SLSA Build level supported by the evidence, set by [`VerificationReport::assess_slsa`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slsa: Option<SlsaLevelAssessment>,
+}
+
+/// Why a bundle failed verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportError {
+    /// [`VerificationError::code`] of the failure
+    pub code: String,
+    pub message: String,
+}
+
+impl ReportError {
+    pub fn class(&self) -> FailureClass {
+        FailureClass::from_code(&self.code)
+    }
+}
+
+/// Coarse failure categories, each with its own process exit code
+///
+/// Exit code 0 means every bundle verified and 1 is reserved for errors that
+/// prevented verification from running (unreadable files, missing trust roots).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum FailureClass {
+    /// Malformed bundle JSON, base64 or structure
+    Bundle = 2,
+    Certificate = 3,
+    Signature = 4,
+    Timestamp = 5,
+    Transparency = 6,
+    /// Identity policy or expected issuer/subject not met
+    Policy = 7,
+    /// Subject digest is zero or does not match the expected digest
+    SubjectDigest = 8,
+    Other = 9,
+}
+
+impl FailureClass {
+    /// Classify a [`VerificationError::code`]
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "bundle_parse" | "base64_decode" | "invalid_bundle_format" => FailureClass::Bundle,
+            "certificate" => FailureClass::Certificate,
+            "signature" => FailureClass::Signature,
+            "timestamp" => FailureClass::Timestamp,
+            "transparency" => FailureClass::Transparency,
+            "policy" => FailureClass::Policy,
+            "zero_subject_digest" | "subject_digest_mismatch" | "subject_name_mismatch" => FailureClass::SubjectDigest,
+            _ => FailureClass::Other,
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FailureClass::Bundle => "bundle",
+            FailureClass::Certificate => "certificate",
+            FailureClass::Signature => "signature",
+            FailureClass::Timestamp => "timestamp",
+            FailureClass::Transparency => "transparency",
+            FailureClass::Policy => "policy",
+            FailureClass::SubjectDigest => "subject_digest",
+            FailureClass::Other => "other",
+        }
+    }
+}
+
+impl VerificationReport {
+    /// Build a report from a verification outcome
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle` - Bundle location shown in the report
+    /// * `bundle_json` - Raw JSON bytes of the bundle
+    /// * `options` - Options the bundle was verified against
+    /// * `result` - Outcome of the verification
+    pub fn new(
+        bundle: impl Into<String>,
+        bundle_json: &[u8],
+        options: VerificationOptions,
+        result: Result<VerificationResult, VerificationError>,
+    ) -> Self {
+        // Subjects are informational; a bundle that can't be parsed fails verification anyway
+        let parsed = parse_bundle_from_bytes(bundle_json).ok();
+        let statement = parsed
+            .as_ref()
+            .and_then(|parsed| parse_dsse_payload(&parsed.dsse_envelope).ok());
+        let trail = parsed
+            .as_ref()
+            .filter(|_| result.is_ok())
+            .and_then(|parsed| ProvenanceTrail::from_bundle(parsed).ok());
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(e) => (
+                None,
+                Some(ReportError {
+                    code: e.code().to_string(),
+                    message: e.to_string(),
+                }),
+            ),
+        };
+
+        VerificationReport {
+            bundle: bundle.into(),
+            bundle_digest: Digest::sha256(sha256(bundle_json)),
+            subjects: statement
+                .as_ref()
+                .map(|s| s.subject.clone())
+                .unwrap_or_default(),
+            predicate_type: statement.map(|s| s.predicate_type),
+            time_verified: options.verify_at.unwrap_or_else(Timestamp::now),
+            options,
+            result,
+            error,
+            trail,
+            slsa: None,
+        }
+    }
+
+    /// Assess the SLSA Build level of the bundle and record it in the report
+    ///
+    /// `bundle_json` is the bundle the report was made for; nothing is
+    /// recorded if its statement cannot be decoded. The level then appears in
+    /// the VSA's `verifiedLevels`. See [`slsa::assess`].
+    pub fn assess_slsa(&mut self, bundle_json: &[u8]) -> Option<&SlsaLevelAssessment> {
+        self.assess_slsa_with_gitlab_issuers(bundle_json, &[])
+    }
+
+    /// Assess the SLSA Build level, recognizing the given self-managed GitLab issuers
+    ///
+    /// See [`slsa::assess_with_gitlab_issuers`].
+    pub fn assess_slsa_with_gitlab_issuers(
+        &mut self,
+        bundle_json: &[u8],
+        gitlab_issuers: &[String],
+    ) -> Option<&SlsaLevelAssessment> {
+        let parsed = parse_bundle_from_bytes(bundle_json).ok()?;
+        let statement = parse_dsse_payload(&parsed.dsse_envelope).ok()?;
+        let identity = self
+            .result
+            .as_ref()
+            .and_then(|result| result.oidc_identity.clone())
+            .or_else(|| self.trail.as_ref().map(|trail| trail.certificate.identity.clone()));
+        self.slsa = Some(slsa::assess_with_gitlab_issuers(
+            &statement,
+            identity.as_ref(),
+            self,
+            gitlab_issuers,
+        ));
+        self.slsa.as_ref()
+    }
+
+    pub fn verified(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// 0 if verified, otherwise the exit code of the failure class
+    pub fn exit_code(&self) -> u8 {
+        if self.verified() {
+            return 0;
+        }
+        self.error
+            .as_ref()
+            .map_or(FailureClass::Other, ReportError::class)
+            .exit_code()
+    }
+}
+
+/// Exit code for a set of reports: 0 if all verified, else that of the first failure
+pub fn exit_code(reports: &[VerificationReport]) -> u8 {
+    reports
+        .iter()
+        .map(VerificationReport::exit_code)
+        .find(|code| *code != 0)
+        .unwrap_or(0)
+}
+
+/// Key/value outputs for a GitHub Actions step (`$GITHUB_OUTPUT`)
+///
+/// `verified` is `true` only if every report verified, and `failure_reason`
+/// names the first failure class. The remaining keys describe the first
+/// verified bundle and are empty if none verified: `subject_digest`,
+/// `repository`, `workflow_ref` and `signing_time` (RFC 3339).
+pub fn github_outputs(reports: &[VerificationReport]) -> Vec<(&'static str, String)> {
+    let verified = !reports.is_empty() && reports.iter().all(VerificationReport::verified);
+    let failure_reason = reports
+        .iter()
+        .find_map(|report| report.error.as_ref())
+        .map(|error| error.class().name().to_string())
+        .unwrap_or_default();
+    let result = reports.iter().find_map(|report| report.result.as_ref());
+    let identity = result.and_then(|result| result.oidc_identity.as_ref());
+
+    vec![
+        ("verified", verified.to_string()),
+        ("failure_reason", failure_reason),
+        (
+            "subject_digest",
+            result
+                .map(|r| r.subject_digest.to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "repository",
+            identity
+                .and_then(|i| i.repository.clone())
+                .unwrap_or_default(),
+        ),
+        (
+            "workflow_ref",
+            identity
+                .and_then(|i| i.workflow_ref.clone())
+                .unwrap_or_default(),
+        ),
+        (
+            "signing_time",
+            result
+                .map(|r| {
+                    r.signing_time.to_rfc3339()
+                })
+                .unwrap_or_default(),
+        ),
+    ]
+}
+
+/// Output format for verification reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// One line per bundle
+    #[default]
+    Text,
+    /// The reports as a JSON array
+    Json,
+    /// A SARIF 2.1.0 log with one result per bundle
+    Sarif,
+    /// One in-toto VSA statement per bundle, as JSON lines
+    Vsa,
+}
+
+impl ReportFormat {
+    /// Render `reports` in this format
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reports cannot be serialized.
+    pub fn render(&self, reports: &[VerificationReport]) -> Result<String, serde_json::Error> {
+        match self {
+            ReportFormat::Text => Ok(reports
+                .iter()
+                .map(|r| format!("{}\n", text_line(r)))
+                .collect()),
+            ReportFormat::Json => serde_json::to_string_pretty(reports),
+            ReportFormat::Sarif => serde_json::to_string_pretty(&to_sarif(reports)),
+            ReportFormat::Vsa => {
+                let mut out = String::new();
+                for report in reports {
+                    out.push_str(&serde_json::to_string(&to_vsa(report)?)?);
+                    out.push('\n');
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            "sarif" => Ok(ReportFormat::Sarif),
+            "vsa" => Ok(ReportFormat::Vsa),
+            other => Err(format!("Unknown report format: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReportFormat::Text => "text",
+            ReportFormat::Json => "json",
+            ReportFormat::Sarif => "sarif",
+            ReportFormat::Vsa => "vsa",
+        };
+        f.write_str(name)
+    }
+}
+
+fn text_line(report: &VerificationReport) -> String {
+    match (&report.result, &report.error) {
+        (Some(result), _) => format!(
+            "PASS {} subject={} signed={}",
+            report.bundle,
+            result.subject_digest,
+            result.signing_time.to_rfc3339()
+        ),
+        (None, Some(error)) => format!("FAIL {} [{}] {}", report.bundle, error.code, error.message),
+        (None, None) => format!("FAIL {}", report.bundle),
+    }
+}
+
+/// Render reports as a SARIF 2.1.0 log
+///
+/// Failed bundles become `error` results whose rule is the failure code
+/// (e.g. `sigstore/certificate`); verified bundles are recorded as `pass` results.
+pub fn to_sarif(reports: &[VerificationReport]) -> Value {
+    let mut rule_ids: Vec<String> = vec![SARIF_PASS_RULE.to_string()];
+    let mut results = Vec::with_capacity(reports.len());
+
+    for report in reports {
+        let location = json!([{
+            "physicalLocation": { "artifactLocation": { "uri": report.bundle } }
+        }]);
+        let result = match &report.error {
+            None => json!({
+                "ruleId": SARIF_PASS_RULE,
+                "kind": "pass",
+                "level": "none",
+                "message": { "text": format!("Attestation bundle {} verified", report.bundle) },
+                "locations": location,
+            }),
+            Some(error) => {
+                let rule_id = format!("sigstore/{}", error.code);
+                if !rule_ids.contains(&rule_id) {
+                    rule_ids.push(rule_id.clone());
+                }
+                json!({
+                    "ruleId": rule_id,
+                    "kind": "fail",
+                    "level": "error",
+                    "message": { "text": error.message },
+                    "locations": location,
+                })
+            }
+        };
+        results.push(result);
+    }
+
+    let rules: Vec<Value> = rule_ids
+        .iter()
+        .map(|id| {
+            let description = if id == SARIF_PASS_RULE {
+                "Sigstore attestation bundle verified".to_string()
+            } else {
+                format!(
+                    "Sigstore attestation verification failed: {}",
+                    &id["sigstore/".len()..]
+                )
+            };
+            json!({ "id": id, "shortDescription": { "text": description } })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "sigstore-verifier",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": VERIFIER_ID,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Render a report as an in-toto statement with a SLSA VSA v1 predicate
+///
+/// The subjects are those of the verified statement. The policy is identified
+/// by `VerificationOptions::options_hash`, the same hash a proof commits to.
+/// `verifiedLevels` holds the assessed SLSA Build level of a verified report
+/// (see [`VerificationReport::assess_slsa`]), and is empty otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the options cannot be serialized.
+pub fn to_vsa(report: &VerificationReport) -> Result<Value, serde_json::Error> {
+    let policy_hash = report
+        .options
+        .options_hash()
+        .map_err(|e| serde::ser::Error::custom(e.to_string()))?;
+
+    let verified_levels: Vec<&str> = report
+        .slsa
+        .as_ref()
+        .filter(|_| report.verified())
+        .map(|assessment| assessment.level.name())
+        .into_iter()
+        .collect();
+
+    Ok(json!({
+        "_type": IN_TOTO_STATEMENT_TYPE,
+        "subject": report.subjects,
+        "predicateType": VSA_PREDICATE_TYPE,
+        "predicate": {
+            "verifier": {
+                "id": VERIFIER_ID,
+                "version": { "sigstore-verifier": env!("CARGO_PKG_VERSION") },
+            },
+            "timeVerified": report.time_verified.to_rfc3339(),
+            "resourceUri": report.bundle,
+            "policy": {
+                "uri": format!("{}#verification-options", VERIFIER_ID),
+                "digest": { "sha256": hex::encode(policy_hash) },
+            },
+            "inputAttestations": [{
+                "uri": report.bundle,
+                "digest": { "sha256": report.bundle_digest.to_hex() },
+            }],
+            "verificationResult": if report.verified() { "PASSED" } else { "FAILED" },
+            "verifiedLevels": verified_levels,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SignatureError;
+
+    fn failed_report() -> VerificationReport {
+        VerificationReport::new(
+            "bundle.json",
+            b"{}",
+            VerificationOptions::default(),
+            Err(SignatureError::InvalidSignature.into()),
+        )
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let failed = failed_report();
+        assert_eq!(
+            failed.error.as_ref().unwrap().class(),
+            FailureClass::Signature
+        );
+        assert_eq!(failed.exit_code(), 4);
+        assert_eq!(exit_code(&[]), 0);
+        assert_eq!(exit_code(&[failed]), 4);
+        assert_eq!(
+            FailureClass::from_code("subject_digest_mismatch").exit_code(),
+            8
+        );
+        assert_eq!(FailureClass::from_code("audit"), FailureClass::Other);
+    }
+
+    #[test]
+    fn test_github_outputs_for_failure() {
+        let outputs = github_outputs(&[failed_report()]);
+        let get = |key: &str| outputs.iter().find(|(k, _)| *k == key).unwrap().1.clone();
+        assert_eq!(get("verified"), "false");
+        assert_eq!(get("failure_reason"), "signature");
+        assert_eq!(get("subject_digest"), "");
+    }
+
+    #[test]
+    fn test_format_names() {
+        for format in [
+            ReportFormat::Text,
+            ReportFormat::Json,
+            ReportFormat::Sarif,
+            ReportFormat::Vsa,
+        ] {
+            assert_eq!(format.to_string().parse::<ReportFormat>().unwrap(), format);
+        }
+        assert!("xml".parse::<ReportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_sarif_failure() {
+        let sarif = to_sarif(&[failed_report()]);
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let run = &sarif["runs"][0];
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "sigstore/signature");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "bundle.json"
+        );
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert!(rules.iter().any(|rule| rule["id"] == "sigstore/signature"));
+    }
+
+    #[test]
+    fn test_vsa_failure() {
+        let report = failed_report();
+        let vsa = to_vsa(&report).unwrap();
+        assert_eq!(vsa["_type"], IN_TOTO_STATEMENT_TYPE);
+        assert_eq!(vsa["predicateType"], VSA_PREDICATE_TYPE);
+        assert_eq!(vsa["predicate"]["verificationResult"], "FAILED");
+        assert_eq!(
+            vsa["predicate"]["inputAttestations"][0]["digest"]["sha256"],
+            report.bundle_digest.to_hex()
+        );
+        assert_eq!(vsa["subject"], json!([]));
+    }
+
+    #[test]
+    fn test_text_and_vsa_lines() {
+        let reports = [failed_report(), failed_report()];
+        let text = ReportFormat::Text.render(&reports).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.starts_with("FAIL bundle.json [signature]"));
+
+        let vsa = ReportFormat::Vsa.render(&reports).unwrap();
+        assert_eq!(vsa.lines().count(), 2);
+    }
+}
@@ -0,0 +1,231 @@
+//! Reproducible-build cross-checks
+//!
+//! An attestation only shows that its builder produced the artifact. When two
+//! independent builders attest to the same digest, compromising one of them is
+//! no longer enough to ship a tampered artifact: that is the claim behind
+//! reproducible builds and two-party build policies.
+//! [`AttestationVerifier::verify_reproduced`] verifies both bundles and checks
+//! that they attest to the same subject digest from different builders,
+//! by default different repositories (see [`BuilderScope`]).
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PolicyError, VerificationError};
+use crate::types::certificate::{CertificateChain, OidcIdentity};
+use crate::types::digest::Digest;
+use crate::types::result::{VerificationOptions, VerificationResult};
+use crate::AttestationVerifier;
+
+/// Two verified attestations of the same artifact by different builders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReproducedBuild {
+    /// Digest both bundles attest to
+    pub subject_digest: Digest,
+    pub first: VerificationResult,
+    pub second: VerificationResult,
+}
+
+/// What tells two builders apart in [`cross_check_with`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuilderScope {
+    /// The repository that built: `oidc_identity.repository`, or else the
+    /// signing workflow without its ref. The same workflow run at two refs,
+    /// or two workflows of one repository, are one builder.
+    #[default]
+    Repository,
+    /// The full certificate subject, so each workflow at each ref is a
+    /// builder of its own; only for builders that are isolated per ref
+    Workflow,
+}
+
+/// Signing identity of a builder: the OIDC issuer and the part of the
+/// certificate subject `scope` selects
+///
+/// For GitHub Actions the subject is the workflow that signed, including the
+/// ref it ran at, which whoever controls the repository can pick freely, so
+/// `BuilderScope::Repository` leaves it out.
+pub fn builder_identity(identity: &OidcIdentity, scope: BuilderScope) -> Option<(&str, &str)> {
+    let issuer = identity.issuer.as_deref()?;
+    let subject = identity.subject.as_deref()?;
+    let builder = match scope {
+        BuilderScope::Repository => match identity.repository.as_deref() {
+            Some(repository) => repository,
+            // Only URI subjects carry a ref; an email subject keeps its '@'
+            None if subject.contains("://") => subject.rsplit_once('@').map_or(subject, |(workflow, _)| workflow),
+            None => subject,
+        },
+        BuilderScope::Workflow => subject,
+    };
+    Some((issuer, builder))
+}
+
+/// Check that two verified results attest to the same digest from different
+/// builders, told apart by their repository
+///
+/// Used by [`AttestationVerifier::verify_reproduced`], and by callers that
+/// verified the bundles themselves, e.g. against different trust material.
+///
+/// # Errors
+///
+/// As [`cross_check_with`] with `BuilderScope::Repository`.
+pub fn cross_check(first: VerificationResult, second: VerificationResult) -> Result<ReproducedBuild, VerificationError> {
+    cross_check_with(first, second, BuilderScope::default())
+}
+
+/// Check that two verified results attest to the same digest from builders
+/// that differ in `scope`
+///
+/// # Errors
+///
+/// Returns `SubjectDigestMismatch` if the results attest to different digests,
+/// `PolicyError::MissingField` if either has no issuer or subject, and
+/// `PolicyError::SameBuilder` if both have the same builder identity.
+pub fn cross_check_with(
+    first: VerificationResult,
+    second: VerificationResult,
+    scope: BuilderScope,
+) -> Result<ReproducedBuild, VerificationError> {
+    if first.subject_digest != second.subject_digest {
+        return Err(VerificationError::SubjectDigestMismatch {
+            expected: first.subject_digest.to_string(),
+            actual: second.subject_digest.to_string(),
+        });
+    }
+
+    fn builder(result: &VerificationResult, scope: BuilderScope) -> Result<(&str, &str), PolicyError> {
+        result
+            .oidc_identity
+            .as_ref()
+            .and_then(|identity| builder_identity(identity, scope))
+            .ok_or(PolicyError::MissingField("identity"))
+    }
+    let (issuer, builder_a) = builder(&first, scope)?;
+    if (issuer, builder_a) == builder(&second, scope)? {
+        return Err(PolicyError::SameBuilder(format!("{} ({})", builder_a, issuer)).into());
+    }
+
+    Ok(ReproducedBuild {
+        subject_digest: first.subject_digest.clone(),
+        first,
+        second,
+    })
+}
+
+impl AttestationVerifier {
+    /// Verify two bundles attesting to the same artifact from independent builders
+    ///
+    /// Both bundles are verified against `options`; an `expected_digest` there
+    /// applies to both.
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle_a` - Raw JSON bytes of the first bundle
+    /// * `bundle_b` - Raw JSON bytes of the second bundle
+    /// * `options` - Verification options, applied to both bundles
+    /// * `trust_bundle` - Certificate chain (intermediates and root) for verification
+    /// * `tsa_cert_chain` - Optional TSA certificate chain for RFC 3161 timestamp verification
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of whichever bundle fails verification,
+    /// `SubjectDigestMismatch` if they attest to different digests,
+    /// `PolicyError::MissingField` if a certificate carries no issuer or
+    /// subject, and `PolicyError::SameBuilder` if both were signed by the same
+    /// builder identity.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let reproduced = verifier.verify_reproduced(&github_bundle, &gitlab_bundle, options, &trust_bundle, None)?;
+    /// println!("{} reproduced", reproduced.subject_digest);
+    /// ```
+    pub fn verify_reproduced(
+        &self,
+        bundle_a: &[u8],
+        bundle_b: &[u8],
+        options: VerificationOptions,
+        trust_bundle: &CertificateChain,
+        tsa_cert_chain: Option<&CertificateChain>,
+    ) -> Result<ReproducedBuild, VerificationError> {
+        let first = self.verify_bundle_bytes(bundle_a, options.clone(), trust_bundle, tsa_cert_chain)?;
+        let second = self.verify_bundle_bytes(bundle_b, options, trust_bundle, tsa_cert_chain)?;
+        cross_check(first, second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::result::CertificateChainHashes;
+    use crate::types::time::Timestamp;
+
+    fn identity(subject: &str, repository: Option<&str>) -> OidcIdentity {
+        OidcIdentity {
+            issuer: Some("https://token.actions.githubusercontent.com".to_string()),
+            subject: Some(subject.to_string()),
+            workflow_ref: None,
+            repository: repository.map(str::to_string),
+            event_name: None,
+        }
+    }
+
+    fn result(identity: OidcIdentity) -> VerificationResult {
+        let hashes = CertificateChainHashes {
+            leaf: [0; 32],
+            intermediates: vec![],
+            root: [0; 32],
+        };
+        VerificationResult::new(hashes, Timestamp::default(), Digest::sha256([1; 32]), Some(identity), Default::default())
+    }
+
+    #[test]
+    fn test_builder_identity() {
+        let release = "https://github.com/org/app/.github/workflows/release.yml@refs/tags/v1";
+        let mut identity = identity(release, Some("org/app"));
+        assert_eq!(
+            builder_identity(&identity, BuilderScope::Repository),
+            Some(("https://token.actions.githubusercontent.com", "org/app"))
+        );
+        assert_eq!(
+            builder_identity(&identity, BuilderScope::Workflow),
+            Some(("https://token.actions.githubusercontent.com", release))
+        );
+
+        // Without a repository, the workflow without its ref
+        identity.repository = None;
+        assert_eq!(
+            builder_identity(&identity, BuilderScope::Repository),
+            Some((
+                "https://token.actions.githubusercontent.com",
+                "https://github.com/org/app/.github/workflows/release.yml"
+            ))
+        );
+        identity.subject = Some("builder@example.com".to_string());
+        assert_eq!(
+            builder_identity(&identity, BuilderScope::Repository),
+            Some(("https://token.actions.githubusercontent.com", "builder@example.com"))
+        );
+
+        identity.issuer = None;
+        assert_eq!(builder_identity(&identity, BuilderScope::Repository), None);
+    }
+
+    #[test]
+    fn test_cross_check_same_workflow_at_two_refs() {
+        let at = |git_ref: &str| {
+            result(identity(
+                &format!("https://github.com/org/app/.github/workflows/release.yml@{}", git_ref),
+                None,
+            ))
+        };
+
+        // Whoever can push a ref can run the workflow again there
+        let err = cross_check(at("refs/tags/v1"), at("refs/heads/rebuild")).unwrap_err();
+        assert!(matches!(err, VerificationError::Policy(PolicyError::SameBuilder(_))));
+
+        cross_check_with(at("refs/tags/v1"), at("refs/heads/rebuild"), BuilderScope::Workflow).unwrap();
+
+        let other = result(identity("https://github.com/other/app/.github/workflows/release.yml@refs/tags/v1", None));
+        cross_check(at("refs/tags/v1"), other).unwrap();
+    }
+}
@@ -0,0 +1,541 @@
+//! SLSA Build level assessment
+//!
+//! A verified bundle proves who signed a statement; which SLSA Build level the
+//! artifact reaches depends on what that evidence says about the build.
+//! [`assess`] infers the highest level (L0 to L3) the verified evidence
+//! supports and records, for every requirement of the Build track, whether it
+//! was met and why:
+//!
+//! | Level | Requirement | Evidence |
+//! |-------|-------------|----------|
+//! | L1 | Provenance exists | A verified SLSA provenance statement |
+//! | L1 | Provenance is complete | Builder ID, build type and external parameters |
+//! | L2 | Hosted build platform | Signed by a CI workload identity naming the builder |
+//! | L2 | Provenance is authentic | Signature verified against the Fulcio chain |
+//! | L3 | Ephemeral credentials | Short-lived signing certificate |
+//! | L3 | Isolated signing | Provenance signed outside the user's workflow |
+//! | L3 | Hosted runner | The build did not run on a self-hosted runner |
+//!
+//! The assessment is only as strong as this mapping: for instance a GitHub
+//! workflow signing its own provenance with `actions/attest-build-provenance`
+//! reaches L2, and one calling a reusable workflow that builds and signs (as
+//! `slsa-github-generator` does) reaches L3.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::PolicyError;
+use crate::policy::attestations::SLSA_PROVENANCE_V1;
+use crate::report::VerificationReport;
+use crate::types::certificate::{CiProvider, OidcIdentity};
+use crate::types::dsse::Statement;
+
+/// SLSA provenance v0.2, still produced by older generators
+pub const SLSA_PROVENANCE_V0_2: &str = "https://slsa.dev/provenance/v0.2";
+
+/// Longest signing certificate validity considered ephemeral (Fulcio issues 10 minutes)
+pub const MAX_EPHEMERAL_VALIDITY_MINUTES: i64 = 60;
+
+const GITHUB_URL: &str = "https://github.com/";
+
+/// SLSA Build track level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SlsaBuildLevel {
+    L0,
+    L1,
+    L2,
+    L3,
+}
+
+impl SlsaBuildLevel {
+    /// Level name as used in VSA `verifiedLevels`, e.g. `SLSA_BUILD_LEVEL_2`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SlsaBuildLevel::L0 => "SLSA_BUILD_LEVEL_0",
+            SlsaBuildLevel::L1 => "SLSA_BUILD_LEVEL_1",
+            SlsaBuildLevel::L2 => "SLSA_BUILD_LEVEL_2",
+            SlsaBuildLevel::L3 => "SLSA_BUILD_LEVEL_3",
+        }
+    }
+}
+
+impl fmt::Display for SlsaBuildLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A Build track requirement checked by [`assess`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlsaRequirement {
+    ProvenanceExists,
+    ProvenanceComplete,
+    HostedBuildPlatform,
+    ProvenanceAuthentic,
+    EphemeralCredentials,
+    IsolatedSigning,
+    HostedRunner,
+}
+
+impl SlsaRequirement {
+    /// Lowest level that needs this requirement
+    pub fn level(&self) -> SlsaBuildLevel {
+        match self {
+            SlsaRequirement::ProvenanceExists | SlsaRequirement::ProvenanceComplete => SlsaBuildLevel::L1,
+            SlsaRequirement::HostedBuildPlatform | SlsaRequirement::ProvenanceAuthentic => SlsaBuildLevel::L2,
+            SlsaRequirement::EphemeralCredentials
+            | SlsaRequirement::IsolatedSigning
+            | SlsaRequirement::HostedRunner => SlsaBuildLevel::L3,
+        }
+    }
+}
+
+/// Whether one requirement was met, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequirementCheck {
+    pub requirement: SlsaRequirement,
+    pub level: SlsaBuildLevel,
+    pub met: bool,
+    pub reason: String,
+}
+
+/// The SLSA Build level the verified evidence of a bundle supports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlsaLevelAssessment {
+    /// Highest level whose requirements, and those of every lower level, are met
+    pub level: SlsaBuildLevel,
+    /// `builder.id` of the provenance, if it has one
+    pub builder_id: Option<String>,
+    /// Every requirement, lowest level first
+    pub requirements: Vec<RequirementCheck>,
+}
+
+impl SlsaLevelAssessment {
+    /// Requirements that were not met
+    pub fn unmet(&self) -> impl Iterator<Item = &RequirementCheck> + '_ {
+        self.requirements.iter().filter(|check| !check.met)
+    }
+
+    /// Check that the assessed level is at least `required`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PolicyError::SlsaLevel` listing the unmet requirements up to `required`.
+    pub fn require(&self, required: SlsaBuildLevel) -> Result<(), PolicyError> {
+        if self.level >= required {
+            return Ok(());
+        }
+        let unmet: Vec<String> = self
+            .unmet()
+            .filter(|check| check.level <= required)
+            .map(|check| check.reason.clone())
+            .collect();
+        Err(PolicyError::SlsaLevel {
+            required: required.name(),
+            achieved: self.level.name(),
+            unmet: unmet.join("; "),
+        })
+    }
+}
+
+/// Infer the SLSA Build level supported by a verified bundle
+///
+/// # Arguments
+///
+/// * `statement` - Decoded in-toto statement of the bundle
+/// * `oidc_identity` - OIDC identity of the signing certificate
+/// * `verification_report` - Report of the bundle's verification; a failed
+///   verification supports no level
+///
+/// # Example
+///
+/// ```ignore
+/// let report = VerificationReport::new(path, &bundle_json, options, result);
+/// let assessment = slsa::assess(&statement, identity.as_ref(), &report);
+/// assessment.require(SlsaBuildLevel::L2)?;
+/// ```
+pub fn assess(
+    statement: &Statement,
+    oidc_identity: Option<&OidcIdentity>,
+    verification_report: &VerificationReport,
+) -> SlsaLevelAssessment {
+    assess_with_gitlab_issuers(statement, oidc_identity, verification_report, &[])
+}
+
+/// Infer the SLSA Build level, treating `gitlab_issuers` as self-managed GitLab instances
+///
+/// Without them, a pipeline of a self-managed instance is not a known CI
+/// workload identity and reaches at most L1. See
+/// [`CiProvider::detect_with_gitlab_issuers`].
+pub fn assess_with_gitlab_issuers(
+    statement: &Statement,
+    oidc_identity: Option<&OidcIdentity>,
+    verification_report: &VerificationReport,
+    gitlab_issuers: &[String],
+) -> SlsaLevelAssessment {
+    let provenance = Provenance::from_statement(statement);
+    let builder_id = provenance.as_ref().and_then(|p| p.builder_id.clone());
+    let provider = oidc_identity.map_or(CiProvider::Unknown, |identity| {
+        identity.provider_with_gitlab_issuers(gitlab_issuers)
+    });
+
+    let requirements = vec![
+        check_exists(statement, provenance.as_ref(), verification_report),
+        check_complete(provenance.as_ref()),
+        check_hosted_platform(provider, oidc_identity, builder_id.as_deref()),
+        check_authentic(verification_report, oidc_identity),
+        check_ephemeral(verification_report, provider),
+        check_isolated(provider, oidc_identity),
+        check_hosted_runner(provider, provenance.as_ref()),
+    ];
+
+    let level = [SlsaBuildLevel::L1, SlsaBuildLevel::L2, SlsaBuildLevel::L3]
+        .into_iter()
+        .take_while(|level| {
+            requirements
+                .iter()
+                .filter(|check| check.level == *level)
+                .all(|check| check.met)
+        })
+        .last()
+        .unwrap_or(SlsaBuildLevel::L0);
+
+    SlsaLevelAssessment {
+        level,
+        builder_id,
+        requirements,
+    }
+}
+
+/// Fields of a v1 or v0.2 provenance predicate the assessment looks at
+struct Provenance {
+    builder_id: Option<String>,
+    build_type: Option<String>,
+    has_external_parameters: bool,
+    runner_environment: Option<String>,
+}
+
+impl Provenance {
+    fn from_statement(statement: &Statement) -> Option<Self> {
+        let predicate = &statement.predicate;
+        let string = |value: &Value| value.as_str().filter(|s| !s.is_empty()).map(str::to_string);
+        let non_empty = |value: &Value| match value {
+            Value::Object(map) => !map.is_empty(),
+            Value::Null => false,
+            _ => true,
+        };
+        match statement.predicate_type.as_str() {
+            SLSA_PROVENANCE_V1 => Some(Provenance {
+                builder_id: string(&predicate["runDetails"]["builder"]["id"]),
+                build_type: string(&predicate["buildDefinition"]["buildType"]),
+                has_external_parameters: non_empty(&predicate["buildDefinition"]["externalParameters"]),
+                runner_environment: string(
+                    &predicate["buildDefinition"]["internalParameters"]["github"]["runner_environment"],
+                ),
+            }),
+            SLSA_PROVENANCE_V0_2 => Some(Provenance {
+                builder_id: string(&predicate["builder"]["id"]),
+                build_type: string(&predicate["buildType"]),
+                has_external_parameters: non_empty(&predicate["invocation"]["configSource"]),
+                runner_environment: string(&predicate["invocation"]["environment"]["github_runner_environment"]),
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn check(requirement: SlsaRequirement, met: bool, reason: impl Into<String>) -> RequirementCheck {
+    RequirementCheck {
+        requirement,
+        level: requirement.level(),
+        met,
+        reason: reason.into(),
+    }
+}
+
+fn check_exists(
+    statement: &Statement,
+    provenance: Option<&Provenance>,
+    report: &VerificationReport,
+) -> RequirementCheck {
+    let requirement = SlsaRequirement::ProvenanceExists;
+    if provenance.is_none() {
+        return check(
+            requirement,
+            false,
+            format!("predicate type {} is not SLSA provenance", statement.predicate_type),
+        );
+    }
+    if !report.verified() {
+        return check(requirement, false, "provenance failed verification");
+    }
+    check(requirement, true, "verified SLSA provenance")
+}
+
+fn check_complete(provenance: Option<&Provenance>) -> RequirementCheck {
+    let requirement = SlsaRequirement::ProvenanceComplete;
+    let Some(provenance) = provenance else {
+        return check(requirement, false, "no provenance");
+    };
+    let missing: Vec<&str> = [
+        ("builder.id", provenance.builder_id.is_some()),
+        ("buildType", provenance.build_type.is_some()),
+        ("external parameters", provenance.has_external_parameters),
+    ]
+    .into_iter()
+    .filter(|(_, present)| !present)
+    .map(|(field, _)| field)
+    .collect();
+    if missing.is_empty() {
+        check(requirement, true, "provenance names the builder, build type and parameters")
+    } else {
+        check(requirement, false, format!("provenance lacks {}", missing.join(", ")))
+    }
+}
+
+fn check_hosted_platform(
+    provider: CiProvider,
+    identity: Option<&OidcIdentity>,
+    builder_id: Option<&str>,
+) -> RequirementCheck {
+    let requirement = SlsaRequirement::HostedBuildPlatform;
+    if provider == CiProvider::Unknown {
+        return check(requirement, false, "signer is not a known CI workload identity");
+    }
+    // GitHub's certificate subject is the workflow that signed, which the provenance must name as builder
+    if provider == CiProvider::GitHubActions {
+        let subject = identity.and_then(|identity| identity.subject.as_deref());
+        if builder_id.is_some() && builder_id != subject {
+            return check(
+                requirement,
+                false,
+                format!(
+                    "builder {} is not the signing workflow {}",
+                    builder_id.unwrap_or_default(),
+                    subject.unwrap_or("(none)")
+                ),
+            );
+        }
+    }
+    check(requirement, true, format!("signed by a {:?} workload identity", provider))
+}
+
+fn check_authentic(report: &VerificationReport, identity: Option<&OidcIdentity>) -> RequirementCheck {
+    let requirement = SlsaRequirement::ProvenanceAuthentic;
+    if !report.verified() {
+        return check(requirement, false, "signature was not verified");
+    }
+    if identity.is_none() {
+        return check(requirement, false, "signing certificate carries no identity");
+    }
+    check(requirement, true, "signature verified against the Fulcio chain")
+}
+
+fn check_ephemeral(report: &VerificationReport, provider: CiProvider) -> RequirementCheck {
+    let requirement = SlsaRequirement::EphemeralCredentials;
+    let metadata = report
+        .result
+        .as_ref()
+        .and_then(|result| result.leaf_certificate.as_ref())
+        .or_else(|| report.trail.as_ref().map(|trail| &trail.certificate.metadata));
+    let Some(metadata) = metadata else {
+        return check(requirement, false, "signing certificate validity unknown");
+    };
+    let validity = metadata.not_after.seconds_since(metadata.not_before);
+    if validity > MAX_EPHEMERAL_VALIDITY_MINUTES * 60 {
+        return check(
+            requirement,
+            false,
+            format!("signing certificate is valid for {} minutes", validity / 60),
+        );
+    }
+    if provider == CiProvider::Unknown {
+        return check(requirement, false, "short-lived certificate, but not issued to a build job");
+    }
+    check(
+        requirement,
+        true,
+        format!("signing certificate is valid for {} minutes", validity / 60),
+    )
+}
+
+/// `owner/repo` of a GitHub repository URL or name
+fn github_repository(value: &str) -> &str {
+    value.strip_prefix(GITHUB_URL).unwrap_or(value).trim_end_matches('/')
+}
+
+fn check_isolated(provider: CiProvider, identity: Option<&OidcIdentity>) -> RequirementCheck {
+    let requirement = SlsaRequirement::IsolatedSigning;
+    match provider {
+        // Cloud Build signs with the platform's service account, never the build steps
+        CiProvider::GoogleCloudBuild => check(requirement, true, "signed by the build platform"),
+        CiProvider::GitHubActions => {
+            let signer = identity
+                .and_then(|identity| identity.subject.as_deref())
+                .and_then(|subject| subject.split_once("/.github/workflows/"))
+                .map(|(repository, _)| github_repository(repository));
+            let caller = identity
+                .and_then(|identity| identity.repository.as_deref())
+                .map(github_repository);
+            match (signer, caller) {
+                (Some(signer), Some(caller)) if signer != caller => check(
+                    requirement,
+                    true,
+                    format!("signed by reusable workflow of {} for {}", signer, caller),
+                ),
+                (Some(_), Some(caller)) => check(
+                    requirement,
+                    false,
+                    format!("signed by a workflow of {} itself", caller),
+                ),
+                _ => check(requirement, false, "signing workflow or repository unknown"),
+            }
+        }
+        _ => check(requirement, false, "signing is not isolated from the build steps"),
+    }
+}
+
+fn check_hosted_runner(provider: CiProvider, provenance: Option<&Provenance>) -> RequirementCheck {
+    let requirement = SlsaRequirement::HostedRunner;
+    if provider == CiProvider::GoogleCloudBuild {
+        return check(requirement, true, "built on Cloud Build workers");
+    }
+    match provenance.and_then(|p| p.runner_environment.as_deref()) {
+        Some("self-hosted") => check(requirement, false, "built on a self-hosted runner"),
+        Some(environment) => check(requirement, true, format!("built on a {} runner", environment)),
+        None => check(requirement, false, "runner environment unknown"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SignatureError;
+    use crate::types::result::VerificationOptions;
+    use serde_json::json;
+
+    fn statement(builder: &str, runner: &str) -> Statement {
+        serde_json::from_value(json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "subject": [{ "name": "artifact", "digest": { "sha256": "00" } }],
+            "predicateType": SLSA_PROVENANCE_V1,
+            "predicate": {
+                "buildDefinition": {
+                    "buildType": "https://actions.github.io/buildtypes/workflow/v1",
+                    "externalParameters": { "workflow": { "path": ".github/workflows/release.yml" } },
+                    "internalParameters": { "github": { "runner_environment": runner } },
+                },
+                "runDetails": { "builder": { "id": builder } },
+            }
+        }))
+        .unwrap()
+    }
+
+    fn identity(subject: &str) -> OidcIdentity {
+        OidcIdentity {
+            issuer: Some("https://token.actions.githubusercontent.com".to_string()),
+            subject: Some(subject.to_string()),
+            workflow_ref: Some("refs/heads/main".to_string()),
+            repository: Some("https://github.com/org/app".to_string()),
+            event_name: Some("push".to_string()),
+        }
+    }
+
+    fn failed_report() -> VerificationReport {
+        VerificationReport::new(
+            "bundle.json",
+            b"{}",
+            VerificationOptions::default(),
+            Err(SignatureError::InvalidSignature.into()),
+        )
+    }
+
+    #[test]
+    fn test_failed_verification_is_level_0() {
+        let builder = "https://github.com/org/app/.github/workflows/release.yml@refs/heads/main";
+        let assessment = assess(
+            &statement(builder, "github-hosted"),
+            Some(&identity(builder)),
+            &failed_report(),
+        );
+        assert_eq!(assessment.level, SlsaBuildLevel::L0);
+        assert_eq!(assessment.builder_id.as_deref(), Some(builder));
+
+        let met: Vec<SlsaRequirement> = assessment
+            .requirements
+            .iter()
+            .filter(|check| check.met)
+            .map(|check| check.requirement)
+            .collect();
+        assert_eq!(
+            met,
+            [
+                SlsaRequirement::ProvenanceComplete,
+                SlsaRequirement::HostedBuildPlatform,
+                SlsaRequirement::HostedRunner
+            ]
+        );
+        assert!(matches!(
+            assessment.require(SlsaBuildLevel::L1),
+            Err(PolicyError::SlsaLevel { .. })
+        ));
+    }
+
+    #[test]
+    fn test_isolated_signing() {
+        let reusable = identity("https://github.com/slsa-framework/slsa-github-generator/.github/workflows/builder.yml@refs/tags/v2.0.0");
+        assert!(check_isolated(CiProvider::GitHubActions, Some(&reusable)).met);
+
+        let own = identity("https://github.com/org/app/.github/workflows/release.yml@refs/heads/main");
+        assert!(!check_isolated(CiProvider::GitHubActions, Some(&own)).met);
+        assert!(!check_isolated(CiProvider::GitLab, Some(&own)).met);
+    }
+
+    #[test]
+    fn test_builder_must_be_signer() {
+        let signer = identity("https://github.com/org/app/.github/workflows/release.yml@refs/heads/main");
+        let other = "https://github.com/org/app/.github/workflows/other.yml@refs/heads/main";
+        assert!(!check_hosted_platform(CiProvider::GitHubActions, Some(&signer), Some(other)).met);
+        assert!(check_hosted_platform(CiProvider::GitHubActions, Some(&signer), signer.subject.as_deref()).met);
+    }
+
+    #[test]
+    fn test_self_managed_gitlab_issuer() {
+        let subject = "https://gitlab.example.com/group/app//.gitlab-ci.yml@refs/heads/main";
+        let signer = OidcIdentity {
+            issuer: Some("https://gitlab.example.com".to_string()),
+            subject: Some(subject.to_string()),
+            workflow_ref: Some("refs/heads/main".to_string()),
+            repository: Some("https://gitlab.example.com/group/app".to_string()),
+            event_name: Some("push".to_string()),
+        };
+        assert_eq!(signer.provider(), CiProvider::Unknown);
+        let gitlab_issuers = ["https://gitlab.example.com/".to_string()];
+        assert_eq!(signer.provider_with_gitlab_issuers(&gitlab_issuers), CiProvider::GitLab);
+
+        let hosted_platform = |assessment: SlsaLevelAssessment| {
+            assessment
+                .requirements
+                .iter()
+                .any(|check| check.requirement == SlsaRequirement::HostedBuildPlatform && check.met)
+        };
+        let statement = statement(subject, "gitlab-hosted");
+        assert!(!hosted_platform(assess(&statement, Some(&signer), &failed_report())));
+        assert!(hosted_platform(assess_with_gitlab_issuers(
+            &statement,
+            Some(&signer),
+            &failed_report(),
+            &gitlab_issuers
+        )));
+    }
+
+    #[test]
+    fn test_level_names() {
+        assert_eq!(SlsaBuildLevel::L2.to_string(), "SLSA_BUILD_LEVEL_2");
+        assert!(SlsaBuildLevel::L3 > SlsaBuildLevel::L1);
+        assert_eq!(SlsaRequirement::HostedRunner.level(), SlsaBuildLevel::L3);
+    }
+}
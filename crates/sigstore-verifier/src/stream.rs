@@ -0,0 +1,151 @@
+//! Streaming verification of newline-delimited bundles
+//!
+//! Release pipelines dump hundreds of megabytes of attestations into a single
+//! JSONL file. [`AttestationVerifier::verify_bundle_stream`] reads such a file
+//! one line at a time and verifies each bundle as it is read, so only the
+//! current line is held in memory. Lines longer than the verifier's
+//! `max_bundle_size` are skipped without buffering them and yield
+//! `VerificationError::LimitExceeded`. Lines may be bare bundles or
+//! `gh attestation download` wrappers (see [`crate::parser::gh_attestation`]).
+
+use std::io::{BufRead, Read};
+
+use crate::error::VerificationError;
+use crate::parser::gh_attestation::parse_gh_attestation_line;
+use crate::types::certificate::CertificateChain;
+use crate::types::result::{VerificationOptions, VerificationResult};
+use crate::AttestationVerifier;
+
+/// Outcome of verifying one line of a bundle stream
+#[derive(Debug)]
+pub struct StreamedVerification {
+    /// 1-based line number in the input
+    pub line: usize,
+    pub result: Result<VerificationResult, VerificationError>,
+}
+
+/// Iterator returned by [`AttestationVerifier::verify_bundle_stream`]
+///
+/// Yields one item per non-empty line. A line that is not a valid bundle, or
+/// fails verification, yields an error and the stream carries on with the next
+/// line; a read error from the underlying reader is yielded once and ends the
+/// stream.
+pub struct BundleStream<'a, R> {
+    verifier: &'a AttestationVerifier,
+    reader: R,
+    options: VerificationOptions,
+    trust_bundle: &'a CertificateChain,
+    tsa_cert_chain: Option<&'a CertificateChain>,
+    line: usize,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl<'a, R: BufRead> BundleStream<'a, R> {
+    pub(crate) fn new(
+        verifier: &'a AttestationVerifier,
+        reader: R,
+        options: VerificationOptions,
+        trust_bundle: &'a CertificateChain,
+        tsa_cert_chain: Option<&'a CertificateChain>,
+    ) -> Self {
+        Self {
+            verifier,
+            reader,
+            options,
+            trust_bundle,
+            tsa_cert_chain,
+            line: 0,
+            buffer: Vec::new(),
+            done: false,
+        }
+    }
+
+    fn verify_line(&self) -> Option<Result<VerificationResult, VerificationError>> {
+        let line = match std::str::from_utf8(&self.buffer) {
+            Ok(line) => line,
+            Err(_) => {
+                return Some(Err(VerificationError::InvalidBundleFormat(format!(
+                    "line {}: not valid UTF-8",
+                    self.line
+                ))))
+            }
+        };
+        let attestation = match parse_gh_attestation_line(self.line, line) {
+            Ok(attestation) => attestation?,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(self.verifier.verify_bundle_bytes(
+            &attestation.bundle_json,
+            self.options.clone(),
+            self.trust_bundle,
+            self.tsa_cert_chain,
+        ))
+    }
+}
+
+/// Discard the rest of the current line without buffering it
+///
+/// Returns the number of bytes skipped, not counting the newline.
+fn skip_line<R: BufRead>(reader: &mut R) -> std::io::Result<usize> {
+    let mut skipped = 0;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(skipped);
+        }
+        match available.iter().position(|&byte| byte == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(skipped + end);
+            }
+            None => {
+                let len = available.len();
+                reader.consume(len);
+                skipped += len;
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for BundleStream<'_, R> {
+    type Item = StreamedVerification;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buffer.clear();
+            self.line += 1;
+            let max = self.verifier.input_limits.max_bundle_size;
+            match self.reader.by_ref().take(max as u64 + 1).read_until(b'\n', &mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(read) if read > max && self.buffer.last() != Some(&b'\n') => {
+                    let result = match skip_line(&mut self.reader) {
+                        Ok(skipped) => Err(VerificationError::LimitExceeded {
+                            limit: "bundle size",
+                            max,
+                            actual: read + skipped,
+                        }),
+                        Err(e) => {
+                            self.done = true;
+                            Err(e.into())
+                        }
+                    };
+                    return Some(StreamedVerification { line: self.line, result });
+                }
+                Ok(_) => {
+                    if let Some(result) = self.verify_line() {
+                        return Some(StreamedVerification { line: self.line, result });
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(StreamedVerification {
+                        line: self.line,
+                        result: Err(e.into()),
+                    });
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,261 @@
+//! Chain of custody of a bundle
+//!
+//! A verification result says *that* a bundle is trustworthy; auditors also
+//! want to see *why*. [`ProvenanceTrail`] collects the evidence a bundle
+//! carries into one serializable record: the leaf certificate and the signed
+//! certificate timestamps (SCTs) of the CT logs that saw Fulcio issue it, the
+//! Rekor entries of the signature, and the RFC 3161 timestamps. The trail is
+//! read from the bundle as is, so build it from a bundle that verified.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use x509_parser::extensions::ParsedExtension;
+
+use crate::error::VerificationError;
+use crate::parser::bundle::decode_base64;
+use crate::parser::certificate::parse_der_certificate;
+use crate::parser::identity::extract_oidc_identity;
+use crate::parser::rfc3161::{parse_rfc3161_timestamp, HashAlgorithm};
+use crate::types::bundle::{SigstoreBundle, TransparencyLogEntry};
+use crate::types::certificate::{CertificateMetadata, OidcIdentity, ParsedCertificate};
+use crate::types::digest::{Digest, DigestAlgorithm};
+use crate::types::time::Timestamp;
+use crate::verifier::timestamp::get_integrated_time;
+
+/// Evidence linking a bundle's signature to its issuance, logging and timestamping
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvenanceTrail {
+    pub certificate: CertificateIssuance,
+    /// Rekor entries, in bundle order
+    pub transparency_log: Vec<LogEntryRecord>,
+    /// RFC 3161 timestamps, in bundle order
+    pub timestamps: Vec<TimestampRecord>,
+}
+
+/// The leaf certificate and its certificate transparency evidence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CertificateIssuance {
+    /// SHA-256 of the DER certificate
+    pub fingerprint: Digest,
+    pub metadata: CertificateMetadata,
+    pub identity: OidcIdentity,
+    /// SCTs embedded by Fulcio; empty for CAs that do not log to CT
+    pub scts: Vec<SctRecord>,
+}
+
+/// A signed certificate timestamp: a CT log's promise to include the certificate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SctRecord {
+    /// SHA-256 of the CT log's public key, hex
+    pub log_id: String,
+    pub timestamp: Timestamp,
+}
+
+/// A transparency log entry of the signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntryRecord {
+    /// Leaf hash of the entry, hex; Rekor accepts it as the entry UUID
+    pub uuid: String,
+    /// SHA-256 of the log's public key, hex
+    pub log_id: Option<String>,
+    pub log_index: Option<u64>,
+    /// `None` for Rekor v2 entries, which are timestamped by a TSA instead
+    pub integrated_time: Option<Timestamp>,
+    /// Entry type, e.g. `dsse` `0.0.1`
+    pub kind: Option<String>,
+    pub version: Option<String>,
+    /// Whether the entry carries a signed entry timestamp
+    pub inclusion_promise: bool,
+    /// Size of the tree the inclusion proof is against, if there is one
+    pub tree_size: Option<u64>,
+}
+
+/// An RFC 3161 timestamp of the signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampRecord {
+    pub gen_time: Timestamp,
+    /// TSA policy OID
+    pub policy: String,
+    /// Hash of the signature the TSA timestamped
+    pub message_imprint: Digest,
+    /// Stated accuracy of `gen_time`, in microseconds
+    pub accuracy_micros: Option<u64>,
+    /// SHA-256 of each certificate embedded in the token
+    pub tsa_certificates: Vec<Digest>,
+}
+
+impl ProvenanceTrail {
+    /// Collect the trail of a bundle
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the certificate, a log entry or a timestamp is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let trail = ProvenanceTrail::from_bundle(&bundle)?;
+    /// println!("{}", serde_json::to_string_pretty(&trail)?);
+    /// ```
+    pub fn from_bundle(bundle: &SigstoreBundle) -> Result<Self, VerificationError> {
+        let material = &bundle.verification_material;
+        let certificate = certificate_issuance(&decode_base64(&material.certificate.raw_bytes)?)?;
+        let transparency_log = material
+            .tlog_entries
+            .iter()
+            .flatten()
+            .map(log_entry_record)
+            .collect::<Result<_, _>>()?;
+        let timestamps = material
+            .timestamp_verification_data
+            .as_ref()
+            .and_then(|data| data.rfc3161_timestamps.as_ref())
+            .into_iter()
+            .flatten()
+            .map(|timestamp| timestamp_record(&decode_base64(&timestamp.signed_timestamp)?))
+            .collect::<Result<_, _>>()?;
+
+        Ok(ProvenanceTrail {
+            certificate,
+            transparency_log,
+            timestamps,
+        })
+    }
+}
+
+fn certificate_issuance(cert_der: &[u8]) -> Result<CertificateIssuance, VerificationError> {
+    let cert = parse_der_certificate(cert_der)?;
+    let mut scts = Vec::new();
+    for extension in cert.tbs_certificate.extensions() {
+        if let ParsedExtension::SCT(list) = extension.parsed_extension() {
+            for sct in list {
+                scts.push(SctRecord {
+                    log_id: hex::encode(sct.id.key_id),
+                    timestamp: i64::try_from(sct.timestamp / 1000).map(Timestamp::from_unix).map_err(|_| {
+                        VerificationError::InvalidBundleFormat(format!("SCT timestamp {} out of range", sct.timestamp))
+                    })?,
+                });
+            }
+        }
+    }
+
+    let parsed = ParsedCertificate::from_der(cert_der)?;
+    Ok(CertificateIssuance {
+        fingerprint: Digest::sha256(parsed.fingerprint),
+        metadata: parsed.metadata()?,
+        identity: extract_oidc_identity(&parsed)?,
+        scts,
+    })
+}
+
+fn log_entry_record(entry: &TransparencyLogEntry) -> Result<LogEntryRecord, VerificationError> {
+    let body = decode_base64(&entry.canonicalized_body)?;
+    // RFC 6962 leaf hash
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(&body);
+
+    let log_id = entry
+        .log_id
+        .as_ref()
+        .map(|log_id| decode_base64(&log_id.key_id).map(hex::encode))
+        .transpose()?;
+    let parse_number = |name: &str, value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|_| VerificationError::InvalidBundleFormat(format!("Invalid {}: {}", name, value)))
+    };
+    let log_index = entry
+        .log_index
+        .as_deref()
+        .map(|index| parse_number("log index", index))
+        .transpose()?;
+    let tree_size = entry
+        .inclusion_proof
+        .as_ref()
+        .map(|proof| parse_number("tree size", &proof.tree_size))
+        .transpose()?;
+    let integrated_time = if entry.is_rekor_v2() {
+        None
+    } else {
+        Some(get_integrated_time(entry)?)
+    };
+
+    Ok(LogEntryRecord {
+        uuid: hex::encode(hasher.finalize()),
+        log_id,
+        log_index,
+        integrated_time,
+        kind: entry.kind_version.as_ref().map(|kv| kv.kind.clone()),
+        version: entry.kind_version.as_ref().map(|kv| kv.version.clone()),
+        inclusion_promise: entry.inclusion_promise.is_some(),
+        tree_size,
+    })
+}
+
+fn timestamp_record(token: &[u8]) -> Result<TimestampRecord, VerificationError> {
+    let timestamp = parse_rfc3161_timestamp(token)?;
+    let tst_info = &timestamp.tst_info;
+    let algorithm = match tst_info.message_imprint.hash_algorithm {
+        HashAlgorithm::Sha256 => DigestAlgorithm::Sha256,
+        HashAlgorithm::Sha384 => DigestAlgorithm::Sha384,
+    };
+
+    Ok(TimestampRecord {
+        gen_time: tst_info.gen_time,
+        policy: tst_info.policy.clone(),
+        message_imprint: Digest {
+            algorithm,
+            bytes: tst_info.message_imprint.hashed_message.clone(),
+        },
+        accuracy_micros: tst_info.accuracy.map(|accuracy| accuracy.as_micros()),
+        tsa_certificates: timestamp
+            .certificates
+            .iter()
+            .flatten()
+            .map(|cert| Digest::sha256(Sha256::digest(cert).into()))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::bundle::parse_bundle_from_path;
+
+    fn sample_bundle(name: &str) -> SigstoreBundle {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples");
+        path.push(name);
+        parse_bundle_from_path(&path).unwrap()
+    }
+
+    #[test]
+    fn test_provenance_trail() {
+        let bundle = sample_bundle("actions-attest-build-provenance-attestation-13581567.sigstore.json");
+        let trail = ProvenanceTrail::from_bundle(&bundle).unwrap();
+        assert!(trail.transparency_log.is_empty());
+        assert_eq!(trail.timestamps.len(), 1);
+        let timestamp = &trail.timestamps[0];
+        assert_eq!(timestamp.message_imprint.algorithm, DigestAlgorithm::Sha256);
+        assert!(timestamp.gen_time >= trail.certificate.metadata.not_before);
+        assert!(timestamp.gen_time <= trail.certificate.metadata.not_after);
+
+        let bundle = sample_bundle("actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        let trail = ProvenanceTrail::from_bundle(&bundle).unwrap();
+        let entry = &trail.transparency_log[0];
+        assert_eq!(entry.uuid.len(), 64);
+        assert!(entry.log_index.is_some());
+        assert!(entry.integrated_time.is_some());
+        assert!(!trail.certificate.scts.is_empty());
+        // Fulcio logs the precertificate as it issues the certificate
+        let sct_time = trail.certificate.scts[0].timestamp;
+        assert!(sct_time >= trail.certificate.metadata.not_before);
+        assert!(sct_time <= trail.certificate.metadata.not_after);
+
+        let json = serde_json::to_value(&trail).unwrap();
+        assert_eq!(serde_json::from_value::<ProvenanceTrail>(json).unwrap(), trail);
+    }
+}
@@ -1,25 +1,223 @@
+use std::fmt;
+
+use base64::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::error::ConversionError;
+
+/// Layout version of a Sigstore bundle, as named by its media type
+///
+/// v0.1 and v0.2 bundles carry the signing certificate as an
+/// `x509CertificateChain`; v0.3 carries only the leaf as `certificate` and
+/// takes the intermediates from the trusted root. v0.1 bundles require an
+/// inclusion promise on each transparency log entry, v0.2 and later an
+/// inclusion proof with its checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BundleVersion {
+    V0_1,
+    V0_2,
+    V0_3,
+}
+
+impl BundleVersion {
+    /// Media type emitted for bundles of this version
+    pub const fn media_type(self) -> &'static str {
+        match self {
+            BundleVersion::V0_1 => "application/vnd.dev.sigstore.bundle+json;version=0.1",
+            BundleVersion::V0_2 => "application/vnd.dev.sigstore.bundle+json;version=0.2",
+            BundleVersion::V0_3 => "application/vnd.dev.sigstore.bundle.v0.3+json",
+        }
+    }
+
+    /// Version named by a bundle media type
+    ///
+    /// Accepts both spellings of v0.3, `application/vnd.dev.sigstore.bundle.v0.3+json`
+    /// and `application/vnd.dev.sigstore.bundle+json;version=0.3`.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/vnd.dev.sigstore.bundle+json;version=0.1" => Some(BundleVersion::V0_1),
+            "application/vnd.dev.sigstore.bundle+json;version=0.2" => Some(BundleVersion::V0_2),
+            "application/vnd.dev.sigstore.bundle+json;version=0.3"
+            | "application/vnd.dev.sigstore.bundle.v0.3+json" => Some(BundleVersion::V0_3),
+            _ => None,
+        }
+    }
+
+    /// Short name, e.g. `v0.3`
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            BundleVersion::V0_1 => "v0.1",
+            BundleVersion::V0_2 => "v0.2",
+            BundleVersion::V0_3 => "v0.3",
+        }
+    }
+}
+
+impl fmt::Display for BundleVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct SigstoreBundle {
     pub media_type: String,
     pub verification_material: VerificationMaterial,
     pub dsse_envelope: DsseEnvelope,
 }
 
+impl SigstoreBundle {
+    /// A v0.3 bundle of `dsse_envelope`
+    pub fn new(verification_material: VerificationMaterial, dsse_envelope: DsseEnvelope) -> Self {
+        SigstoreBundle {
+            media_type: BundleVersion::V0_3.media_type().to_string(),
+            verification_material,
+            dsse_envelope,
+        }
+    }
+
+    /// Layout version of the bundle
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedMediaType` if the media type names no known version.
+    pub fn version(&self) -> Result<BundleVersion, ConversionError> {
+        BundleVersion::from_media_type(&self.media_type)
+            .ok_or_else(|| ConversionError::UnsupportedMediaType(self.media_type.clone()))
+    }
+
+    /// Convert the bundle to the layout of another version
+    ///
+    /// Moves the signing certificate between `x509CertificateChain` and
+    /// `certificate` and sets the media type of `version`; the signed content
+    /// and timestamps are unchanged, so the converted bundle verifies exactly
+    /// as the original does. Converting to v0.3 keeps only the leaf of a
+    /// certificate chain, as v0.3 verifiers take the intermediates from the
+    /// trusted root.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version to convert to; may be the bundle's own version
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedMediaType` for bundles of unknown versions,
+    /// `MissingCertificate` if the bundle has no certificate, and
+    /// `MissingInclusionProof`, `MissingCheckpoint` or `MissingInclusionPromise`
+    /// if a transparency log entry lacks what `version` requires, e.g. when
+    /// converting a v0.1 bundle without inclusion proofs to v0.2, or a bundle
+    /// with Rekor v2 entries to v0.1.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let legacy: SigstoreBundle = serde_json::from_slice(&std::fs::read("legacy.sigstore.json")?)?;
+    /// let bundle = legacy.convert_to(BundleVersion::V0_3)?;
+    /// let result = verifier.verify_bundle_bytes(&serde_json::to_vec(&bundle)?, options, &trust_bundle, None)?;
+    /// ```
+    pub fn convert_to(&self, version: BundleVersion) -> Result<SigstoreBundle, ConversionError> {
+        self.version()?;
+
+        let material = &self.verification_material;
+        let chain = material
+            .x509_certificate_chain
+            .as_ref()
+            .map(|chain| chain.certificates.as_slice())
+            .unwrap_or_default();
+        let leaf = if material.certificate.is_empty() {
+            chain.first().cloned().ok_or(ConversionError::MissingCertificate)?
+        } else {
+            material.certificate.clone()
+        };
+
+        for entry in material.tlog_entries.as_deref().unwrap_or_default() {
+            let log_index = || entry.log_index.clone().unwrap_or_default();
+            if version == BundleVersion::V0_1 {
+                if entry.inclusion_promise.is_none() {
+                    return Err(ConversionError::MissingInclusionPromise {
+                        log_index: log_index(),
+                        version: version.as_str(),
+                    });
+                }
+                continue;
+            }
+            let proof = entry.inclusion_proof.as_ref().ok_or_else(|| ConversionError::MissingInclusionProof {
+                log_index: log_index(),
+                version: version.as_str(),
+            })?;
+            if proof.checkpoint.is_none() {
+                return Err(ConversionError::MissingCheckpoint {
+                    log_index: log_index(),
+                    version: version.as_str(),
+                });
+            }
+        }
+
+        let (certificate, x509_certificate_chain) = if version == BundleVersion::V0_3 {
+            (leaf, None)
+        } else {
+            let certificates = if chain.is_empty() { vec![leaf] } else { chain.to_vec() };
+            (Certificate::default(), Some(X509CertificateChain { certificates }))
+        };
+
+        Ok(SigstoreBundle {
+            media_type: version.media_type().to_string(),
+            verification_material: VerificationMaterial {
+                timestamp_verification_data: material.timestamp_verification_data.clone(),
+                certificate,
+                x509_certificate_chain,
+                tlog_entries: material.tlog_entries.clone(),
+            },
+            dsse_envelope: self.dsse_envelope.clone(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct VerificationMaterial {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp_verification_data: Option<TimestampVerificationData>,
+    /// Leaf certificate of v0.3 bundles; empty in v0.1 and v0.2 bundles
+    #[serde(default, skip_serializing_if = "Certificate::is_empty")]
     pub certificate: Certificate,
+    /// Certificate chain, leaf first, of v0.1 and v0.2 bundles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x509_certificate_chain: Option<X509CertificateChain>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tlog_entries: Option<Vec<TransparencyLogEntry>>,
 }
 
+impl VerificationMaterial {
+    /// Material of a v0.3 bundle signed by `certificate`, without timestamps or log entries
+    pub fn new(certificate: Certificate) -> Self {
+        VerificationMaterial {
+            timestamp_verification_data: None,
+            certificate,
+            x509_certificate_chain: None,
+            tlog_entries: None,
+        }
+    }
+
+    pub fn with_tlog_entries(mut self, entries: Vec<TransparencyLogEntry>) -> Self {
+        self.tlog_entries = Some(entries);
+        self
+    }
+
+    pub fn with_rfc3161_timestamps(mut self, timestamps: Vec<Rfc3161Timestamp>) -> Self {
+        self.timestamp_verification_data = Some(TimestampVerificationData {
+            rfc3161_timestamps: Some(timestamps),
+        });
+        self
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct TimestampVerificationData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rfc3161_timestamps: Option<Vec<Rfc3161Timestamp>>,
@@ -31,24 +229,76 @@ pub struct Rfc3161Timestamp {
     pub signed_timestamp: String, // Base64-encoded
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Certificate {
     pub raw_bytes: String, // Base64-encoded DER certificate
 }
 
+impl Certificate {
+    /// A certificate of the DER bytes `der`
+    pub fn from_der(der: &[u8]) -> Self {
+        Certificate {
+            raw_bytes: BASE64_STANDARD.encode(der),
+        }
+    }
+
+    /// Whether the certificate is absent, as in v0.1 and v0.2 bundles
+    pub fn is_empty(&self) -> bool {
+        self.raw_bytes.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct X509CertificateChain {
+    pub certificates: Vec<Certificate>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct TransparencyLogEntry {
     pub log_index: Option<String>,
     pub log_id: Option<LogId>,
     pub kind_version: Option<KindVersion>,
+    /// Unset for Rekor v2 entries, which are timestamped by a TSA instead
+    #[serde(default)]
     pub integrated_time: String,
     pub inclusion_promise: Option<InclusionPromise>,
     pub inclusion_proof: Option<InclusionProof>,
     pub canonicalized_body: String, // Base64-encoded
 }
 
+impl TransparencyLogEntry {
+    /// An entry of `canonicalized_body` (base64) with every other field unset
+    pub fn new(canonicalized_body: impl Into<String>) -> Self {
+        TransparencyLogEntry {
+            log_index: None,
+            log_id: None,
+            kind_version: None,
+            integrated_time: String::new(),
+            inclusion_promise: None,
+            inclusion_proof: None,
+            canonicalized_body: canonicalized_body.into(),
+        }
+    }
+
+    /// Whether the entry comes from a Rekor v2 (tile-based) log
+    ///
+    /// Rekor v2 logs neither integrate a timestamp nor issue inclusion
+    /// promises; their entries only carry an inclusion proof and checkpoint.
+    /// They are recognized by their `kindVersion`, `dsse` or `hashedrekord`
+    /// 0.0.2, which only Rekor v2 issues, and not by the missing promise, so
+    /// stripping the promise from a Rekor v1 entry does not make it one. The
+    /// `kindVersion` is bound to the logged body by `parse_entry_body`.
+    pub fn is_rekor_v2(&self) -> bool {
+        self.kind_version
+            .as_ref()
+            .is_some_and(|kv| kv.version == "0.0.2" && matches!(kv.kind.as_str(), "dsse" | "hashedrekord"))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LogId {
@@ -70,6 +320,7 @@ pub struct InclusionPromise {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct InclusionProof {
     pub log_index: String,
     pub root_hash: String,   // Base64-encoded
@@ -78,6 +329,26 @@ pub struct InclusionProof {
     pub checkpoint: Option<Checkpoint>,
 }
 
+impl InclusionProof {
+    /// A proof without a checkpoint; hashes are base64-encoded
+    pub fn new(log_index: u64, root_hash: String, tree_size: u64, hashes: Vec<String>) -> Self {
+        InclusionProof {
+            log_index: log_index.to_string(),
+            root_hash,
+            tree_size: tree_size.to_string(),
+            hashes,
+            checkpoint: None,
+        }
+    }
+
+    pub fn with_checkpoint(mut self, envelope: impl Into<String>) -> Self {
+        self.checkpoint = Some(Checkpoint {
+            envelope: envelope.into(),
+        });
+        self
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Checkpoint {
@@ -86,13 +357,162 @@ pub struct Checkpoint {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct DsseEnvelope {
     pub payload: String,      // Base64-encoded
     pub payload_type: String,
     pub signatures: Vec<Signature>,
 }
 
+impl DsseEnvelope {
+    /// An envelope of the raw `payload`, which is base64-encoded here
+    pub fn new(payload_type: impl Into<String>, payload: &[u8], signatures: Vec<Signature>) -> Self {
+        DsseEnvelope {
+            payload: BASE64_STANDARD.encode(payload),
+            payload_type: payload_type.into(),
+            signatures,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Signature {
     pub sig: String, // Base64-encoded
 }
+
+impl Signature {
+    /// A signature of the raw bytes `signature`
+    pub fn from_bytes(signature: &[u8]) -> Self {
+        Signature {
+            sig: BASE64_STANDARD.encode(signature),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::bundle::parse_bundle_from_path;
+    use std::path::PathBuf;
+
+    fn sample_bundle() -> SigstoreBundle {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        parse_bundle_from_path(&path).unwrap()
+    }
+
+    fn to_json(bundle: &SigstoreBundle) -> serde_json::Value {
+        serde_json::to_value(bundle).unwrap()
+    }
+
+    #[test]
+    fn test_bundle_version_media_types() {
+        for version in [BundleVersion::V0_1, BundleVersion::V0_2, BundleVersion::V0_3] {
+            assert_eq!(BundleVersion::from_media_type(version.media_type()), Some(version));
+        }
+        assert_eq!(
+            BundleVersion::from_media_type("application/vnd.dev.sigstore.bundle+json;version=0.3"),
+            Some(BundleVersion::V0_3)
+        );
+        assert_eq!(BundleVersion::from_media_type("application/json"), None);
+        assert_eq!(sample_bundle().version(), Ok(BundleVersion::V0_3));
+    }
+
+    #[test]
+    fn test_is_rekor_v2_follows_kind_version() {
+        let mut entry = sample_bundle().verification_material.tlog_entries.unwrap().remove(0);
+        assert!(!entry.is_rekor_v2());
+
+        // Stripping the promise and integrated time does not turn a Rekor v1 entry into a v2 one
+        entry.inclusion_promise = None;
+        entry.integrated_time = "0".to_string();
+        assert!(!entry.is_rekor_v2());
+
+        entry.kind_version = Some(KindVersion {
+            kind: "dsse".to_string(),
+            version: "0.0.2".to_string(),
+        });
+        assert!(entry.is_rekor_v2());
+    }
+
+    #[test]
+    fn test_convert_round_trip() {
+        let bundle = sample_bundle();
+
+        for version in [BundleVersion::V0_1, BundleVersion::V0_2] {
+            let legacy = bundle.convert_to(version).unwrap();
+            let json = to_json(&legacy);
+            assert_eq!(json["mediaType"], version.media_type());
+            assert!(json["verificationMaterial"].get("certificate").is_none());
+            assert_eq!(
+                json["verificationMaterial"]["x509CertificateChain"]["certificates"][0]["rawBytes"],
+                bundle.verification_material.certificate.raw_bytes
+            );
+
+            // Legacy layouts survive a JSON round trip and convert back unchanged
+            let reparsed: SigstoreBundle = serde_json::from_value(json).unwrap();
+            assert_eq!(to_json(&reparsed.convert_to(BundleVersion::V0_3).unwrap()), to_json(&bundle));
+        }
+    }
+
+    #[test]
+    fn test_convert_keeps_only_leaf_for_v0_3() {
+        let mut legacy = sample_bundle().convert_to(BundleVersion::V0_2).unwrap();
+        let chain = legacy.verification_material.x509_certificate_chain.as_mut().unwrap();
+        let leaf = chain.certificates[0].clone();
+        chain.certificates.push(Certificate {
+            raw_bytes: "aW50ZXJtZWRpYXRl".to_string(),
+        });
+
+        let v0_1 = legacy.convert_to(BundleVersion::V0_1).unwrap();
+        assert_eq!(v0_1.verification_material.x509_certificate_chain.unwrap().certificates.len(), 2);
+
+        let v0_3 = legacy.convert_to(BundleVersion::V0_3).unwrap();
+        assert_eq!(v0_3.verification_material.certificate, leaf);
+        assert!(v0_3.verification_material.x509_certificate_chain.is_none());
+    }
+
+    #[test]
+    fn test_convert_refuses_lossy_conversions() {
+        let mut bundle = sample_bundle();
+        bundle.verification_material.tlog_entries.as_mut().unwrap()[0].inclusion_promise = None;
+        assert_eq!(
+            bundle.convert_to(BundleVersion::V0_1).unwrap_err(),
+            ConversionError::MissingInclusionPromise {
+                log_index: "707288064".to_string(),
+                version: "v0.1",
+            }
+        );
+
+        let mut bundle = sample_bundle();
+        let entry = &mut bundle.verification_material.tlog_entries.as_mut().unwrap()[0];
+        entry.inclusion_proof.as_mut().unwrap().checkpoint = None;
+        assert!(matches!(
+            bundle.convert_to(BundleVersion::V0_2),
+            Err(ConversionError::MissingCheckpoint { .. })
+        ));
+        let entry = &mut bundle.verification_material.tlog_entries.as_mut().unwrap()[0];
+        entry.inclusion_proof = None;
+        assert!(matches!(
+            bundle.convert_to(BundleVersion::V0_3),
+            Err(ConversionError::MissingInclusionProof { .. })
+        ));
+        assert!(bundle.convert_to(BundleVersion::V0_1).is_ok());
+
+        let mut bundle = sample_bundle();
+        bundle.verification_material.certificate = Certificate::default();
+        assert_eq!(
+            bundle.convert_to(BundleVersion::V0_3).unwrap_err(),
+            ConversionError::MissingCertificate
+        );
+
+        bundle.media_type = "application/json".to_string();
+        assert!(matches!(
+            bundle.convert_to(BundleVersion::V0_3),
+            Err(ConversionError::UnsupportedMediaType(_))
+        ));
+    }
+}
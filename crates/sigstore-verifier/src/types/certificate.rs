@@ -1,14 +1,43 @@
+use crate::crypto::algorithm::SignatureAlgorithm;
+use crate::crypto::signature::PublicKey;
+use crate::error::{CertificateError, SignatureError};
 use crate::parser::bundle::{decode_base64, parse_bundle_from_str};
-use crate::parser::certificate::{determine_fulcio_instance, parse_der_certificate};
+use crate::parser::certificate::{
+    determine_fulcio_instance, parse_certificate, parse_der_certificate, signature_algorithm_name,
+};
+use crate::types::time::Timestamp;
+use crate::verifier::certificate::check_signature_algorithm;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CertificateChain {
     pub leaf: Vec<u8>,               // DER-encoded
     pub intermediates: Vec<Vec<u8>>, // DER-encoded
     pub root: Vec<u8>,               // DER-encoded
 }
 
+impl CertificateChain {
+    /// DER certificates from leaf to root (an empty leaf, as in trust bundles, is skipped)
+    pub fn to_der(&self) -> Vec<Vec<u8>> {
+        std::iter::once(&self.leaf)
+            .chain(&self.intermediates)
+            .chain(std::iter::once(&self.root))
+            .filter(|der| !der.is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// Concatenated PEM `CERTIFICATE` blocks from leaf to root
+    pub fn to_pem(&self) -> String {
+        let blocks: Vec<::pem::Pem> = self
+            .to_der()
+            .into_iter()
+            .map(|der| ::pem::Pem::new("CERTIFICATE", der))
+            .collect();
+        ::pem::encode_many(&blocks)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrustBundle {
     pub chains: Vec<CertChain>,
@@ -57,10 +86,9 @@ impl FulcioInstance {
     /// # Example
     ///
     /// ```ignore
-    /// # #[cfg(feature = "fetcher")]
     /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// use sigstore_verifier::types::certificate::FulcioInstance;
-    /// use sigstore_verifier::fetcher::trust_bundle::fetch_fulcio_trust_bundle;
+    /// use sigstore_fetcher::trust_bundle::fetch_fulcio_trust_bundle;
     ///
     /// let bundle_json = std::fs::read_to_string("bundle.sigstore.json")?;
     /// let instance = FulcioInstance::from_bundle_json(&bundle_json)?;
@@ -83,6 +111,147 @@ impl FulcioInstance {
     }
 }
 
+/// Parsed fields of a certificate, for audit records
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateMetadata {
+    /// Serial number, lowercase hex
+    pub serial_number: String,
+    /// Subject key identifier extension, lowercase hex
+    pub subject_key_id: Option<String>,
+    pub not_before: Timestamp,
+    pub not_after: Timestamp,
+    /// Signature algorithm name (e.g. "ecdsa-with-SHA256"), or its OID if unsupported
+    pub signature_algorithm: String,
+    /// Common name of the issuer, e.g. "Fulcio Intermediate l2" for GitHub's Fulcio
+    pub issuer_cn: String,
+}
+
+/// A certificate parsed once into owned fields
+///
+/// Unlike x509-parser's `X509Certificate<'a>`, it does not borrow the DER, so
+/// it can be cached (see `verifier::pool`), sent between threads and kept
+/// after the bundle it came from is dropped. Chain, signature and identity
+/// verification all work on this model.
+#[derive(Debug, Clone)]
+pub struct ParsedCertificate {
+    /// The DER certificate
+    pub der: Vec<u8>,
+    /// SHA-256 of `der`
+    pub fingerprint: [u8; 32],
+    /// Serial number, lowercase hex
+    pub serial_number: String,
+    /// Subject distinguished name, RFC 4514 string
+    pub subject: String,
+    /// Issuer distinguished name, RFC 4514 string
+    pub issuer: String,
+    /// Common name of the issuer, if it has one
+    pub issuer_cn: Option<String>,
+    pub not_before: Timestamp,
+    pub not_after: Timestamp,
+    /// DER SubjectPublicKeyInfo
+    pub spki: Vec<u8>,
+    /// Subject key identifier extension, lowercase hex
+    pub subject_key_id: Option<String>,
+    /// E-mail and URI subject alternative names, in certificate order
+    pub subject_alt_names: Vec<SubjectAltName>,
+    pub extensions: Vec<CertificateExtension>,
+    /// Decoded public key, or why it cannot be used to verify signatures
+    pub(crate) public_key: Result<PublicKey, SignatureError>,
+    pub(crate) tbs_certificate: Vec<u8>,
+    pub(crate) signature: Vec<u8>,
+    /// OID of the outer `signatureAlgorithm`
+    pub(crate) signature_algorithm: String,
+    /// OID of the `signature` field inside the TBSCertificate
+    pub(crate) tbs_signature_algorithm: String,
+    /// Whether both algorithm identifiers carry the same parameters
+    pub(crate) algorithm_parameters_match: bool,
+}
+
+/// A subject alternative name the verifier reads identities from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubjectAltName {
+    Email(String),
+    Uri(String),
+}
+
+/// An X.509 extension, undecoded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateExtension {
+    /// Dotted OID, e.g. "1.3.6.1.4.1.57264.1.8"
+    pub oid: String,
+    pub critical: bool,
+    /// Extension value, without the OCTET STRING wrapper
+    pub value: Vec<u8>,
+}
+
+impl ParsedCertificate {
+    /// Parse a DER certificate; see `parser::certificate::parse_certificate`
+    pub fn from_der(der: &[u8]) -> Result<Self, CertificateError> {
+        parse_certificate(der)
+    }
+
+    /// The certificate's public key
+    ///
+    /// # Errors
+    ///
+    /// Returns the error decoding the key met, e.g. for an unsupported curve.
+    pub fn public_key(&self) -> Result<&PublicKey, SignatureError> {
+        self.public_key.as_ref().map_err(Clone::clone)
+    }
+
+    /// Common name of the issuer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issuer has no common name.
+    pub fn issuer_cn(&self) -> Result<&str, CertificateError> {
+        self.issuer_cn
+            .as_deref()
+            .ok_or_else(|| CertificateError::ParseError("Common Name not found in issuer".to_string()))
+    }
+
+    /// The extension with dotted OID `oid`, if present
+    pub fn extension(&self, oid: &str) -> Option<&CertificateExtension> {
+        self.extensions.iter().find(|ext| ext.oid == oid)
+    }
+
+    /// Audit metadata of the certificate, as `parser::certificate::extract_certificate_metadata`
+    pub fn metadata(&self) -> Result<CertificateMetadata, CertificateError> {
+        Ok(CertificateMetadata {
+            serial_number: self.serial_number.clone(),
+            subject_key_id: self.subject_key_id.clone(),
+            not_before: self.not_before,
+            not_after: self.not_after,
+            signature_algorithm: signature_algorithm_name(&self.signature_algorithm),
+            issuer_cn: self.issuer_cn()?.to_string(),
+        })
+    }
+
+    /// Verify that `issuer` signed this certificate
+    ///
+    /// The certificate's signature algorithm is checked as by
+    /// `verifier::certificate::certificate_signature_algorithm`.
+    pub fn verify_issued_by(
+        &self,
+        issuer: &ParsedCertificate,
+        allowed_algorithms: &[SignatureAlgorithm],
+    ) -> Result<(), CertificateError> {
+        let algorithm = check_signature_algorithm(
+            &self.signature_algorithm,
+            &self.tbs_signature_algorithm,
+            self.algorithm_parameters_match,
+            allowed_algorithms,
+        )?;
+
+        // Verify with the digest the certificate declares, not the issuer curve's default
+        issuer
+            .public_key()
+            .map_err(|e| CertificateError::ChainVerificationFailed(e.to_string()))?
+            .verify_prehash(&algorithm.digest(&self.tbs_certificate), &self.signature)
+            .map_err(|e| CertificateError::ChainVerificationFailed(e.to_string()))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OidcIdentity {
     pub issuer: Option<String>,
@@ -91,3 +260,116 @@ pub struct OidcIdentity {
     pub repository: Option<String>,
     pub event_name: Option<String>,
 }
+
+/// Fulcio certificate extensions (OID 1.3.6.1.4.1.57264.1.x)
+///
+/// Field names follow the Fulcio OID registry. Legacy GitHub-specific
+/// extensions (1.2 - 1.6) are kept separately since newer certificates carry
+/// both forms.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FulcioExtensions {
+    /// 1.8 (falls back to the deprecated raw-string 1.1)
+    pub issuer: Option<String>,
+    /// 1.9
+    pub build_signer_uri: Option<String>,
+    /// 1.10
+    pub build_signer_digest: Option<String>,
+    /// 1.11 (e.g., "github-hosted", "gitlab-hosted", "self-hosted")
+    pub runner_environment: Option<String>,
+    /// 1.12
+    pub source_repository_uri: Option<String>,
+    /// 1.13
+    pub source_repository_digest: Option<String>,
+    /// 1.14
+    pub source_repository_ref: Option<String>,
+    /// 1.15
+    pub source_repository_identifier: Option<String>,
+    /// 1.16
+    pub source_repository_owner_uri: Option<String>,
+    /// 1.17
+    pub source_repository_owner_identifier: Option<String>,
+    /// 1.18
+    pub build_config_uri: Option<String>,
+    /// 1.19
+    pub build_config_digest: Option<String>,
+    /// 1.20
+    pub build_trigger: Option<String>,
+    /// 1.21
+    pub run_invocation_uri: Option<String>,
+    /// 1.22
+    pub source_repository_visibility_at_signing: Option<String>,
+    /// Legacy 1.2
+    pub github_workflow_trigger: Option<String>,
+    /// Legacy 1.3
+    pub github_workflow_sha: Option<String>,
+    /// Legacy 1.4
+    pub github_workflow_name: Option<String>,
+    /// Legacy 1.5
+    pub github_workflow_repository: Option<String>,
+    /// Legacy 1.6
+    pub github_workflow_ref: Option<String>,
+}
+
+/// CI/CD platform that requested the signing certificate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CiProvider {
+    GitHubActions,
+    GitLab,
+    GoogleCloudBuild,
+    Unknown,
+}
+
+impl CiProvider {
+    /// Detect the provider from the OIDC issuer and certificate subject
+    ///
+    /// GitHub Actions and gitlab.com are recognized by issuer. Google Cloud
+    /// Build signs with a Google service account, so its issuer is shared
+    /// with every Google identity and the subject decides.
+    pub fn detect(issuer: Option<&str>, subject: Option<&str>) -> Self {
+        Self::detect_with_gitlab_issuers(issuer, subject, &[])
+    }
+
+    /// Detect the provider, also recognizing self-managed GitLab instances
+    ///
+    /// A self-managed instance issues job tokens under its own URL, which looks
+    /// like any other issuer, so the caller lists the instances it expects,
+    /// e.g. "https://gitlab.example.com". gitlab.com is always recognized.
+    pub fn detect_with_gitlab_issuers(
+        issuer: Option<&str>,
+        subject: Option<&str>,
+        gitlab_issuers: &[String],
+    ) -> Self {
+        match issuer {
+            Some(issuer) if issuer.starts_with("https://token.actions.githubusercontent.com") => {
+                CiProvider::GitHubActions
+            }
+            Some(issuer) if is_gitlab_issuer(issuer, gitlab_issuers) => CiProvider::GitLab,
+            Some("https://accounts.google.com")
+                if subject.is_some_and(|s| s.ends_with("@cloudbuild.gserviceaccount.com")) =>
+            {
+                CiProvider::GoogleCloudBuild
+            }
+            _ => CiProvider::Unknown,
+        }
+    }
+}
+
+fn is_gitlab_issuer(issuer: &str, gitlab_issuers: &[String]) -> bool {
+    let issuer = issuer.trim_end_matches('/');
+    issuer == "https://gitlab.com"
+        || gitlab_issuers
+            .iter()
+            .any(|gitlab| gitlab.trim_end_matches('/') == issuer)
+}
+
+impl OidcIdentity {
+    /// CI/CD platform this identity belongs to
+    pub fn provider(&self) -> CiProvider {
+        CiProvider::detect(self.issuer.as_deref(), self.subject.as_deref())
+    }
+
+    /// CI/CD platform this identity belongs to, recognizing the given self-managed GitLab issuers
+    pub fn provider_with_gitlab_issuers(&self, gitlab_issuers: &[String]) -> CiProvider {
+        CiProvider::detect_with_gitlab_issuers(self.issuer.as_deref(), self.subject.as_deref(), gitlab_issuers)
+    }
+}
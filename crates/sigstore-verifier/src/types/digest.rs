@@ -0,0 +1,315 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::crypto::hash::constant_time_eq;
+use crate::encoding::{hex_decode, hex_encode};
+use crate::error::DigestError;
+
+/// Hash algorithm identifier for Solidity encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum DigestAlgorithm {
+    Unknown = 0,
+    Sha256 = 1,
+    Sha384 = 2,
+    /// Git commit ID (SHA-1 object name), the subject of source attestations
+    GitCommit = 3,
+    Sha512 = 4,
+}
+
+impl DigestAlgorithm {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => DigestAlgorithm::Sha256,
+            2 => DigestAlgorithm::Sha384,
+            3 => DigestAlgorithm::GitCommit,
+            4 => DigestAlgorithm::Sha512,
+            _ => DigestAlgorithm::Unknown,
+        }
+    }
+
+    /// Look up an algorithm by the name used in in-toto subjects and `<alg>:<hex>` strings
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(DigestAlgorithm::Sha256),
+            "sha384" => Some(DigestAlgorithm::Sha384),
+            "gitCommit" => Some(DigestAlgorithm::GitCommit),
+            "sha512" => Some(DigestAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Lowercase algorithm name, e.g. `sha256`
+    pub fn name(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Unknown => "unknown",
+            DigestAlgorithm::Sha256 => "sha256",
+            DigestAlgorithm::Sha384 => "sha384",
+            DigestAlgorithm::GitCommit => "gitCommit",
+            DigestAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Digest length in bytes, or `None` for `Unknown`
+    pub fn output_len(&self) -> Option<usize> {
+        match self {
+            DigestAlgorithm::Unknown => None,
+            DigestAlgorithm::Sha256 => Some(32),
+            DigestAlgorithm::Sha384 => Some(48),
+            DigestAlgorithm::GitCommit => Some(20),
+            DigestAlgorithm::Sha512 => Some(64),
+        }
+    }
+
+    /// The SHA-2 algorithm producing digests of `len` bytes
+    ///
+    /// Only SHA-256, SHA-384 and SHA-512 are inferred; a 20-byte value could be
+    /// any SHA-1 digest and must name `gitCommit` explicitly.
+    pub fn from_output_len(len: usize) -> Option<Self> {
+        match len {
+            32 => Some(DigestAlgorithm::Sha256),
+            48 => Some(DigestAlgorithm::Sha384),
+            64 => Some(DigestAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+}
+
+/// A digest tagged with its algorithm
+///
+/// Written as `<alg>:<hex>` (e.g. `sha256:e3b0c4...`) by `Display` and serde.
+/// `FromStr` (and so serde and `TryFrom<&str>`) also accepts bare hex, and
+/// `from_bytes` raw bytes, inferring SHA-256, SHA-384 or SHA-512 from the
+/// length, so malformed input is rejected where the digest is built rather than
+/// reported as a mismatch at verification. Equality compares the bytes in
+/// constant time.
+#[derive(Clone)]
+pub struct Digest {
+    pub algorithm: DigestAlgorithm,
+    pub bytes: Vec<u8>,
+}
+
+impl Digest {
+    /// Create a digest, checking the length against the algorithm
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownAlgorithm` for `DigestAlgorithm::Unknown` and
+    /// `InvalidLength` if `bytes` is not the algorithm's output size.
+    pub fn new(algorithm: DigestAlgorithm, bytes: Vec<u8>) -> Result<Self, DigestError> {
+        let expected = algorithm
+            .output_len()
+            .ok_or_else(|| DigestError::UnknownAlgorithm(algorithm.name().to_string()))?;
+        if bytes.len() != expected {
+            return Err(DigestError::InvalidLength {
+                algorithm: algorithm.name(),
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        Ok(Digest { algorithm, bytes })
+    }
+
+    /// Create a SHA-256 digest
+    pub fn sha256(bytes: [u8; 32]) -> Self {
+        Digest {
+            algorithm: DigestAlgorithm::Sha256,
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    /// Create a digest from raw bytes, inferring the algorithm from their length
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownLength` unless `bytes` is 32, 48 or 64 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DigestError> {
+        let algorithm = DigestAlgorithm::from_output_len(bytes.len()).ok_or(DigestError::UnknownLength(bytes.len()))?;
+        Self::new(algorithm, bytes.to_vec())
+    }
+
+    /// Parse the hex value of a digest whose algorithm is known separately
+    pub fn from_hex(algorithm: DigestAlgorithm, hex: &str) -> Result<Self, DigestError> {
+        let bytes = hex_decode(hex).map_err(|e| DigestError::InvalidHex(e.to_string()))?;
+        Self::new(algorithm, bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Lowercase hex of the digest bytes, without the algorithm prefix
+    pub fn to_hex(&self) -> String {
+        hex_encode(&self.bytes)
+    }
+}
+
+impl PartialEq for Digest {
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm == other.algorithm && constant_time_eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for Digest {}
+
+impl std::hash::Hash for Digest {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.algorithm.hash(state);
+        self.bytes.hash(state);
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm.name(), self.to_hex())
+    }
+}
+
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Digest({})", self)
+    }
+}
+
+impl FromStr for Digest {
+    type Err = DigestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some((name, hex)) = s.split_once(':') else {
+            // Bare hex: the length must identify the algorithm
+            return DigestAlgorithm::from_output_len(s.len() / 2)
+                .filter(|_| s.len().is_multiple_of(2))
+                .ok_or_else(|| DigestError::MissingAlgorithm(s.to_string()))
+                .and_then(|algorithm| Self::from_hex(algorithm, s));
+        };
+        let algorithm = DigestAlgorithm::from_name(name)
+            .ok_or_else(|| DigestError::UnknownAlgorithm(name.to_string()))?;
+        Self::from_hex(algorithm, hex)
+    }
+}
+
+impl TryFrom<&str> for Digest {
+    type Error = DigestError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for Digest {
+    type Error = DigestError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<&[u8]> for Digest {
+    type Error = DigestError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<[u8; 32]> for Digest {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self::sha256(bytes)
+    }
+}
+
+impl Serialize for Digest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Digest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EMPTY_SHA256: &str =
+        "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn test_digest_round_trip() {
+        let digest: Digest = EMPTY_SHA256.parse().unwrap();
+        assert_eq!(digest.algorithm, DigestAlgorithm::Sha256);
+        assert_eq!(digest.bytes.len(), 32);
+        assert_eq!(digest.to_string(), EMPTY_SHA256);
+
+        // Uppercase hex is accepted but always written back lowercase
+        let upper: Digest = EMPTY_SHA256.to_uppercase().replace("SHA256", "sha256").parse().unwrap();
+        assert_eq!(upper, digest);
+        assert_eq!(upper.to_string(), EMPTY_SHA256);
+    }
+
+    #[test]
+    fn test_digest_parse_errors() {
+        assert!(matches!(
+            "e3b0c442".parse::<Digest>(),
+            Err(DigestError::MissingAlgorithm(_))
+        ));
+        assert!(matches!(
+            "md5:d41d8cd98f00b204e9800998ecf8427e".parse::<Digest>(),
+            Err(DigestError::UnknownAlgorithm(name)) if name == "md5"
+        ));
+        assert!(matches!(
+            "sha256:abcd".parse::<Digest>(),
+            Err(DigestError::InvalidLength { expected: 32, actual: 2, .. })
+        ));
+        assert!(matches!("sha384:zz".parse::<Digest>(), Err(DigestError::InvalidHex(_))));
+    }
+
+    #[test]
+    fn test_digest_inferred_algorithm() {
+        let bare: Digest = EMPTY_SHA256.trim_start_matches("sha256:").parse().unwrap();
+        assert_eq!(bare, EMPTY_SHA256.parse().unwrap());
+        assert_eq!(Digest::try_from(format!(" {} ", EMPTY_SHA256)).unwrap(), bare);
+        assert_eq!(Digest::from_bytes(&[7; 48]).unwrap().algorithm, DigestAlgorithm::Sha384);
+        assert_eq!(Digest::try_from(&[7u8; 64][..]).unwrap().to_string(), format!("sha512:{}", "07".repeat(64)));
+        assert_eq!(Digest::from([1; 32]), Digest::sha256([1; 32]));
+
+        // A SHA-1 sized value is not guessed to be a git commit
+        assert!(matches!(
+            "8f1d2e3c4b5a69788796a5b4c3d2e1f0a9b8c7d6".parse::<Digest>(),
+            Err(DigestError::MissingAlgorithm(_))
+        ));
+        assert!(matches!(Digest::from_bytes(&[0; 20]), Err(DigestError::UnknownLength(20))));
+        assert!(matches!(
+            format!("{}z", &EMPTY_SHA256[7..70]).parse::<Digest>(),
+            Err(DigestError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn test_digest_equality_includes_algorithm() {
+        let sha256 = Digest::new(DigestAlgorithm::Sha256, vec![1; 32]).unwrap();
+        let tagged_unknown = Digest {
+            algorithm: DigestAlgorithm::Unknown,
+            bytes: vec![1; 32],
+        };
+        assert_ne!(sha256, tagged_unknown);
+        assert_ne!(sha256, Digest::sha256([2; 32]));
+        assert_eq!(sha256, Digest::sha256([1; 32]));
+    }
+
+    #[test]
+    fn test_digest_serde() {
+        let digest: Digest = EMPTY_SHA256.parse().unwrap();
+        let json = serde_json::to_string(&digest).unwrap();
+        assert_eq!(json, format!("\"{}\"", EMPTY_SHA256));
+        assert_eq!(serde_json::from_str::<Digest>(&json).unwrap(), digest);
+        assert!(serde_json::from_str::<Digest>("\"sha256:00\"").is_err());
+    }
+}
@@ -1,7 +1,49 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// DSSE payload type of in-toto statements
+pub const IN_TOTO_PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+/// `_type` of in-toto v1 statements
+pub const STATEMENT_V1: &str = "https://in-toto.io/Statement/v1";
+
+/// `_type` of in-toto v0.1 statements, still produced by older SLSA v0.2 provenance generators
+pub const STATEMENT_V0_1: &str = "https://in-toto.io/Statement/v0.1";
+
+/// Layout version of an in-toto statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementVersion {
+    V0_1,
+    V1,
+}
+
+impl StatementVersion {
+    /// Version named by a statement's `_type`, if it is a known one
+    pub fn from_type(statement_type: &str) -> Option<Self> {
+        match statement_type {
+            STATEMENT_V1 => Some(StatementVersion::V1),
+            STATEMENT_V0_1 => Some(StatementVersion::V0_1),
+            _ => None,
+        }
+    }
+
+    pub fn type_uri(&self) -> &'static str {
+        match self {
+            StatementVersion::V0_1 => STATEMENT_V0_1,
+            StatementVersion::V1 => STATEMENT_V1,
+        }
+    }
+}
+
+/// An in-toto statement, in the same form whatever its version
+///
+/// Statements are deserialized according to their `_type`. v0.1 statements
+/// may omit `predicate`, which then reads as `null`; v1 statements, and
+/// statements of unknown type, need both `predicateType` and `predicate`.
+/// Policies therefore see one layout, and `version` tells them which one the
+/// statement was written in.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawStatement")]
 pub struct Statement {
     #[serde(rename = "_type")]
     pub statement_type: String,
@@ -17,10 +59,70 @@ pub struct Subject {
     pub digest: HashMap<String, String>,
 }
 
+/// Statement as written, before its `_type` decides which fields are required
+#[derive(Deserialize)]
+struct RawStatement {
+    #[serde(rename = "_type")]
+    statement_type: String,
+    subject: Vec<Subject>,
+    #[serde(rename = "predicateType")]
+    predicate_type: String,
+    predicate: Option<serde_json::Value>,
+}
+
+impl TryFrom<RawStatement> for Statement {
+    type Error = String;
+
+    fn try_from(raw: RawStatement) -> Result<Self, Self::Error> {
+        let predicate = match (StatementVersion::from_type(&raw.statement_type), raw.predicate) {
+            (_, Some(predicate)) => predicate,
+            (Some(StatementVersion::V0_1), None) => serde_json::Value::Null,
+            (_, None) => return Err("missing field `predicate`".to_string()),
+        };
+        Ok(Statement {
+            statement_type: raw.statement_type,
+            subject: raw.subject,
+            predicate_type: raw.predicate_type,
+            predicate,
+        })
+    }
+}
+
 impl Statement {
+    /// Layout version named by `_type`, `None` for an unknown type
+    pub fn version(&self) -> Option<StatementVersion> {
+        StatementVersion::from_type(&self.statement_type)
+    }
+
     pub fn get_subject_digest(&self, algorithm: &str) -> Option<String> {
         self.subject
             .first()
             .and_then(|s| s.digest.get(algorithm).cloned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statement_versions() {
+        let v0_1: Statement = serde_json::from_str(
+            r#"{"_type":"https://in-toto.io/Statement/v0.1","subject":[{"name":"a","digest":{"sha256":"00"}}],"predicateType":"https://slsa.dev/provenance/v0.2"}"#,
+        )
+        .unwrap();
+        assert_eq!(v0_1.version(), Some(StatementVersion::V0_1));
+        assert!(v0_1.predicate.is_null());
+        assert_eq!(v0_1.get_subject_digest("sha256").as_deref(), Some("00"));
+
+        let v1 = r#"{"_type":"https://in-toto.io/Statement/v1","subject":[{"name":"a","digest":{"sha256":"00"}}],"predicateType":"https://slsa.dev/provenance/v1"}"#;
+        let err = serde_json::from_str::<Statement>(v1).unwrap_err();
+        assert!(err.to_string().contains("missing field `predicate`"));
+
+        let other: Statement = serde_json::from_str(
+            r#"{"_type":"https://example.com/Statement","subject":[],"predicateType":"p","predicate":{}}"#,
+        )
+        .unwrap();
+        assert_eq!(other.version(), None);
+    }
+}
@@ -0,0 +1,223 @@
+//! Sigstore deployments
+//!
+//! A `SigstoreEnvironment` names the Sigstore deployment a bundle was signed
+//! against and the endpoints that come with it: the TUF repository serving its
+//! trusted root, its Rekor log, and one `SigningAuthority` per Fulcio instance
+//! with the CNs of that instance's intermediates and its timestamp authority.
+//! Production covers the public-good instance and GitHub's; staging covers the
+//! public-good staging instance (`*.sigstage.dev`).
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CertificateError;
+use crate::parser::bundle::{decode_base64, parse_bundle_from_str};
+use crate::types::certificate::ParsedCertificate;
+
+/// A Fulcio instance and the TSA paired with it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningAuthority {
+    /// Short name, e.g. `public-good` or `github`
+    pub name: String,
+    /// Fulcio base URL, matched against TrustedRoot certificate authority URIs
+    pub fulcio_url: String,
+    /// Common names of the intermediates issuing this instance's leaf certificates
+    pub issuer_cns: Vec<String>,
+    /// Timestamp authority base URL, matched against TrustedRoot TSA URIs
+    pub tsa_url: Option<String>,
+}
+
+impl SigningAuthority {
+    /// Host name of `fulcio_url`
+    pub fn fulcio_domain(&self) -> &str {
+        domain(&self.fulcio_url)
+    }
+
+    /// Host name of `tsa_url`
+    pub fn tsa_domain(&self) -> Option<&str> {
+        self.tsa_url.as_deref().map(domain)
+    }
+
+    /// URL of the Fulcio trust bundle API
+    pub fn trust_bundle_url(&self) -> String {
+        format!("{}/api/v2/trustBundle", self.fulcio_url.trim_end_matches('/'))
+    }
+}
+
+fn domain(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+/// Endpoints of a Sigstore deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentConfig {
+    pub name: String,
+    /// TUF repository distributing the deployment's trusted root
+    pub tuf_url: Option<String>,
+    /// Rekor transparency log base URL
+    pub rekor_url: String,
+    pub authorities: Vec<SigningAuthority>,
+}
+
+/// Sigstore deployment a bundle was signed against
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SigstoreEnvironment {
+    /// Public-good Sigstore and GitHub's Fulcio instance
+    #[default]
+    Production,
+    /// Public-good Sigstore staging (`*.sigstage.dev`)
+    Staging,
+    /// Private or self-hosted deployment
+    Custom(Box<EnvironmentConfig>),
+}
+
+impl SigstoreEnvironment {
+    pub fn name(&self) -> &str {
+        match self {
+            SigstoreEnvironment::Production => "production",
+            SigstoreEnvironment::Staging => "staging",
+            SigstoreEnvironment::Custom(config) => &config.name,
+        }
+    }
+
+    /// Endpoints of the deployment
+    pub fn config(&self) -> EnvironmentConfig {
+        match self {
+            SigstoreEnvironment::Production => EnvironmentConfig {
+                name: "production".to_string(),
+                tuf_url: Some("https://tuf-repo-cdn.sigstore.dev".to_string()),
+                rekor_url: "https://rekor.sigstore.dev".to_string(),
+                authorities: vec![
+                    SigningAuthority {
+                        name: "public-good".to_string(),
+                        fulcio_url: "https://fulcio.sigstore.dev".to_string(),
+                        issuer_cns: vec!["sigstore-intermediate".to_string()],
+                        tsa_url: Some("https://timestamp.sigstore.dev".to_string()),
+                    },
+                    SigningAuthority {
+                        name: "github".to_string(),
+                        fulcio_url: "https://fulcio.githubapp.com".to_string(),
+                        issuer_cns: vec!["Fulcio Intermediate l2".to_string()],
+                        tsa_url: Some("https://timestamp.githubapp.com".to_string()),
+                    },
+                ],
+            },
+            SigstoreEnvironment::Staging => EnvironmentConfig {
+                name: "staging".to_string(),
+                tuf_url: Some("https://tuf-repo-cdn.sigstage.dev".to_string()),
+                rekor_url: "https://rekor.sigstage.dev".to_string(),
+                authorities: vec![SigningAuthority {
+                    name: "public-good".to_string(),
+                    fulcio_url: "https://fulcio.sigstage.dev".to_string(),
+                    issuer_cns: vec!["sigstore-intermediate".to_string()],
+                    tsa_url: Some("https://timestamp.sigstage.dev".to_string()),
+                }],
+            },
+            SigstoreEnvironment::Custom(config) => (**config).clone(),
+        }
+    }
+
+    /// Signing authority whose intermediates have the common name `issuer_cn`
+    pub fn authority_for_issuer(&self, issuer_cn: &str) -> Option<SigningAuthority> {
+        self.config()
+            .authorities
+            .into_iter()
+            .find(|authority| authority.issuer_cns.iter().any(|cn| cn == issuer_cn))
+    }
+
+    /// Signing authority that issued a leaf certificate
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownIssuer` if no authority of the deployment has the
+    /// certificate's issuer CN.
+    pub fn authority_for_certificate(&self, cert: &ParsedCertificate) -> Result<SigningAuthority, CertificateError> {
+        let issuer_cn = cert.issuer_cn()?;
+        self.authority_for_issuer(issuer_cn).ok_or_else(|| {
+            CertificateError::UnknownIssuer(format!("{} (environment {})", issuer_cn, self.name()))
+        })
+    }
+
+    /// Signing authority that issued a bundle's leaf certificate
+    pub fn authority_for_bundle_json(&self, bundle_json: &str) -> Result<SigningAuthority, String> {
+        let bundle = parse_bundle_from_str(bundle_json)
+            .map_err(|e| format!("Failed to parse bundle: {}", e))?;
+        let leaf_der = decode_base64(&bundle.verification_material.certificate.raw_bytes)
+            .map_err(|e| format!("Failed to decode certificate: {}", e))?;
+        let leaf_cert = ParsedCertificate::from_der(&leaf_der)
+            .map_err(|e| format!("Failed to parse certificate: {}", e))?;
+        self.authority_for_certificate(&leaf_cert)
+            .map_err(|e| format!("Failed to determine signing authority: {}", e))
+    }
+}
+
+impl fmt::Display for SigstoreEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for SigstoreEnvironment {
+    type Err = String;
+
+    /// Parse `production` or `staging`; custom deployments are built from an
+    /// `EnvironmentConfig`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "production" => Ok(SigstoreEnvironment::Production),
+            "staging" => Ok(SigstoreEnvironment::Staging),
+            other => Err(format!(
+                "Unknown Sigstore environment '{}' (expected production or staging)",
+                other
+            )),
+        }
+    }
+}
+
+impl From<EnvironmentConfig> for SigstoreEnvironment {
+    fn from(config: EnvironmentConfig) -> Self {
+        SigstoreEnvironment::Custom(Box::new(config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_authorities() {
+        let production = SigstoreEnvironment::Production;
+        let github = production.authority_for_issuer("Fulcio Intermediate l2").unwrap();
+        assert_eq!(github.fulcio_domain(), "fulcio.githubapp.com");
+        assert_eq!(github.tsa_domain(), Some("timestamp.githubapp.com"));
+        assert_eq!(
+            github.trust_bundle_url(),
+            "https://fulcio.githubapp.com/api/v2/trustBundle"
+        );
+
+        let staging = SigstoreEnvironment::Staging;
+        let public_good = staging.authority_for_issuer("sigstore-intermediate").unwrap();
+        assert_eq!(public_good.fulcio_domain(), "fulcio.sigstage.dev");
+        assert_eq!(public_good.tsa_domain(), Some("timestamp.sigstage.dev"));
+        assert!(staging.authority_for_issuer("Fulcio Intermediate l2").is_none());
+    }
+
+    #[test]
+    fn test_environment_names() {
+        assert_eq!("staging".parse::<SigstoreEnvironment>().unwrap(), SigstoreEnvironment::Staging);
+        assert!("qa".parse::<SigstoreEnvironment>().is_err());
+
+        let custom: SigstoreEnvironment = EnvironmentConfig {
+            name: "internal".to_string(),
+            tuf_url: None,
+            rekor_url: "https://rekor.internal.example".to_string(),
+            authorities: vec![],
+        }
+        .into();
+        assert_eq!(custom.to_string(), "internal");
+        assert_eq!(custom.config().rekor_url, "https://rekor.internal.example");
+    }
+}
@@ -0,0 +1,134 @@
+//! Size limits on untrusted inputs
+//!
+//! Bundles, their base64 fields and trust bundles all come from outside the
+//! verifier, and in a zkVM guest every byte parsed or decoded costs proving
+//! cycles. [`InputLimits`] bounds them so an oversized or maliciously nested
+//! input is rejected with `VerificationError::LimitExceeded` before it is
+//! decoded, instead of exhausting host memory or a paid proving budget. The
+//! defaults are far above what any Sigstore bundle needs.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::types::bundle::SigstoreBundle;
+
+/// Default maximum size of a bundle's JSON, in bytes
+pub const DEFAULT_MAX_BUNDLE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Default maximum length of a single base64 field, in characters
+pub const DEFAULT_MAX_BASE64_FIELD_SIZE: usize = 12 * 1024 * 1024;
+
+/// Default maximum number of transparency log entries in a bundle
+pub const DEFAULT_MAX_TLOG_ENTRIES: usize = 16;
+
+/// Default maximum number of hashes in an inclusion proof, enough for a tree of 2^64 leaves
+pub const DEFAULT_MAX_PROOF_HASHES: usize = 64;
+
+/// Default maximum number of certificates in a chain, leaf and root included
+pub const DEFAULT_MAX_CHAIN_LENGTH: usize = 8;
+
+/// Upper bounds on the size of untrusted inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputLimits {
+    /// Size of the bundle JSON, in bytes
+    pub max_bundle_size: usize,
+    /// Length of each base64 field (payload, signature, certificate, timestamp,
+    /// log entry body and proof hashes), in characters
+    pub max_base64_field_size: usize,
+    /// Number of entries in `tlogEntries`
+    pub max_tlog_entries: usize,
+    /// Number of hashes in an inclusion proof
+    pub max_proof_hashes: usize,
+    /// Number of certificates in a Fulcio or TSA chain, leaf and root included
+    pub max_chain_length: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            max_bundle_size: DEFAULT_MAX_BUNDLE_SIZE,
+            max_base64_field_size: DEFAULT_MAX_BASE64_FIELD_SIZE,
+            max_tlog_entries: DEFAULT_MAX_TLOG_ENTRIES,
+            max_proof_hashes: DEFAULT_MAX_PROOF_HASHES,
+            max_chain_length: DEFAULT_MAX_CHAIN_LENGTH,
+        }
+    }
+}
+
+impl InputLimits {
+    /// Check the size of a bundle's JSON before it is parsed
+    pub fn check_bundle_size(&self, size: usize) -> Result<(), VerificationError> {
+        check("bundle size", self.max_bundle_size, size)
+    }
+
+    /// Check the length of a base64 field before it is decoded
+    pub fn check_base64_field(&self, field: &'static str, encoded: &str) -> Result<(), VerificationError> {
+        check(field, self.max_base64_field_size, encoded.len())
+    }
+
+    /// Check the number of transparency log entries
+    pub fn check_tlog_entries(&self, count: usize) -> Result<(), VerificationError> {
+        check("tlog entries", self.max_tlog_entries, count)
+    }
+
+    /// Check the number of hashes in an inclusion proof
+    pub fn check_proof_hashes(&self, count: usize) -> Result<(), VerificationError> {
+        check("inclusion proof hashes", self.max_proof_hashes, count)
+    }
+
+    /// Check the length of a certificate chain, leaf and root included
+    pub fn check_chain_length(&self, length: usize) -> Result<(), VerificationError> {
+        check("certificate chain length", self.max_chain_length, length)
+    }
+
+    /// Check every count and base64 field of a parsed bundle
+    ///
+    /// Run right after parsing, so that no later step decodes an oversized field.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LimitExceeded` naming the first field over its limit.
+    pub fn check_bundle(&self, bundle: &SigstoreBundle) -> Result<(), VerificationError> {
+        let envelope = &bundle.dsse_envelope;
+        self.check_base64_field("DSSE payload", &envelope.payload)?;
+        for signature in &envelope.signatures {
+            self.check_base64_field("DSSE signature", &signature.sig)?;
+        }
+
+        let material = &bundle.verification_material;
+        self.check_base64_field("certificate", &material.certificate.raw_bytes)?;
+        let timestamps = material
+            .timestamp_verification_data
+            .as_ref()
+            .and_then(|data| data.rfc3161_timestamps.as_deref())
+            .unwrap_or_default();
+        for timestamp in timestamps {
+            self.check_base64_field("RFC 3161 timestamp", &timestamp.signed_timestamp)?;
+        }
+
+        let tlog_entries = material.tlog_entries.as_deref().unwrap_or_default();
+        self.check_tlog_entries(tlog_entries.len())?;
+        for entry in tlog_entries {
+            self.check_base64_field("tlog entry body", &entry.canonicalized_body)?;
+            if let Some(ref promise) = entry.inclusion_promise {
+                self.check_base64_field("signed entry timestamp", &promise.signed_entry_timestamp)?;
+            }
+            if let Some(ref proof) = entry.inclusion_proof {
+                self.check_proof_hashes(proof.hashes.len())?;
+                self.check_base64_field("inclusion proof root hash", &proof.root_hash)?;
+                for hash in &proof.hashes {
+                    self.check_base64_field("inclusion proof hash", hash)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check(limit: &'static str, max: usize, actual: usize) -> Result<(), VerificationError> {
+    if actual > max {
+        return Err(VerificationError::LimitExceeded { limit, max, actual });
+    }
+    Ok(())
+}
@@ -1,4 +1,13 @@
 pub mod bundle;
+#[cfg(feature = "full")]
 pub mod certificate;
+pub mod digest;
 pub mod dsse;
+#[cfg(feature = "full")]
+pub mod environment;
+pub mod limits;
+#[cfg(feature = "full")]
 pub mod result;
+#[cfg(feature = "full")]
+pub mod simple_signing;
+pub mod time;
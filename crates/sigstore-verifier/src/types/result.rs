@@ -1,6 +1,17 @@
-use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use super::certificate::OidcIdentity;
+use super::certificate::{CertificateChain, CertificateMetadata, OidcIdentity};
+pub use super::digest::{Digest, DigestAlgorithm};
+use crate::crypto::algorithm::{SignatureAlgorithm, DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS};
+use crate::crypto::hash::sha256;
+use crate::crypto::jcs::{to_canonical_vec, JcsError};
+use crate::error::{DigestError, VerificationError};
+use crate::policy::chain::ChainPolicy;
+use crate::policy::dependency::{DependencyPolicy, ResolvedDependency};
+use crate::policy::identity::IdentityPolicy;
+use crate::policy::source::SourcePolicy;
+use crate::policy::tsa::TsaPolicy;
+use crate::types::time::Timestamp;
+use crate::verifier::checkpoint::LogVerifier;
 use alloy_sol_types::{sol, SolValue};
 
 // =============================================================================
@@ -75,25 +86,6 @@ sol! {
     }
 }
 
-/// Hash algorithm identifier for Solidity encoding
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[repr(u8)]
-pub enum DigestAlgorithm {
-    Unknown = 0,
-    Sha256 = 1,
-    Sha384 = 2,
-}
-
-impl DigestAlgorithm {
-    pub fn from_u8(value: u8) -> Self {
-        match value {
-            1 => DigestAlgorithm::Sha256,
-            2 => DigestAlgorithm::Sha384,
-            _ => DigestAlgorithm::Unknown,
-        }
-    }
-}
-
 /// Timestamp proof type identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
@@ -146,14 +138,36 @@ impl Default for TimestampProof {
     }
 }
 
+/// Outcome of a successful verification
+///
+/// `#[non_exhaustive]` so fields can be added in minor releases: outside this
+/// crate, build one with [`VerificationResult::new`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct VerificationResult {
     pub certificate_hashes: CertificateChainHashes,
-    pub signing_time: DateTime<Utc>,
-    pub subject_digest: Vec<u8>,
-    pub subject_digest_algorithm: DigestAlgorithm,
+    pub signing_time: Timestamp,
+    pub subject_digest: Digest,
+    /// Name of the subject the digest was read from (not part of the zkVM
+    /// output, so `None` after `from_slice`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_name: Option<String>,
     pub oidc_identity: Option<OidcIdentity>,
     pub timestamp_proof: TimestampProof,
+    /// Serial, key ID, validity and issuer of the leaf certificate (not part of
+    /// the zkVM output, so `None` after `from_slice`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_certificate: Option<CertificateMetadata>,
+    /// Verified Fulcio chain, kept with `AttestationVerifier::with_certificate_chains`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certificate_chain: Option<CertificateChain>,
+    /// Verified TSA chain of the RFC 3161 timestamp, kept likewise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tsa_certificate_chain: Option<CertificateChain>,
+    /// Dependencies recorded by a SLSA provenance (not part of the zkVM
+    /// output, so empty after `from_slice`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolved_dependencies: Vec<ResolvedDependency>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -169,19 +183,388 @@ impl CertificateChainHashes {
     }
 }
 
+/// What a bundle must satisfy beyond a valid signature and chain
+///
+/// Fields are public for reading and assignment, but the struct is
+/// `#[non_exhaustive]` so new options are not breaking changes: outside this
+/// crate, start from `VerificationOptions::default()` and set fields with the
+/// `with_*` methods.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct VerificationOptions {
     /// Optional expected digest to verify against the subject digest in the attestation
-    pub expected_digest: Option<Vec<u8>>,
+    pub expected_digest: Option<Digest>,
+
+    /// Subject name pattern (`*` matches any sequence of characters), e.g.
+    /// `pkg:npm/left-pad@*`; the digest is then read from the first subject
+    /// whose name matches (`None` reads the first subject)
+    #[serde(default)]
+    pub expected_subject_name: Option<String>,
 
     /// Optional expected OIDC issuer (e.g., "https://token.actions.githubusercontent.com")
     pub expected_issuer: Option<String>,
 
     /// Optional expected OIDC subject (e.g., "repo:owner/repo:ref:refs/heads/main")
     pub expected_subject: Option<String>,
+
+    /// Optional identity policy evaluated against the certificate's OIDC identity
+    pub identity_policy: Option<IdentityPolicy>,
+
+    /// Signature algorithms accepted for certificates and the DSSE signature
+    /// (`None` uses `DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS`)
+    pub allowed_signature_algorithms: Option<Vec<SignatureAlgorithm>>,
+
+    /// Accepted in-toto predicate types, as `*` patterns (`None` accepts any)
+    #[serde(default)]
+    pub allowed_predicate_types: Option<Vec<String>>,
+
+    /// Timestamp mechanism the bundle must use (`None` accepts either)
+    #[serde(default)]
+    pub required_timestamp: Option<TimestampRequirement>,
+
+    /// SHA-256 digests of the accepted Fulcio root certificates (`None` accepts
+    /// any root of the provided trust bundle)
+    #[serde(default)]
+    pub pinned_root_digests: Option<Vec<Digest>>,
+
+    /// Maximum depth of the Fulcio chain, and whether its root must be
+    /// self-signed and pinned (`None` requires a self-signed root, and a pinned
+    /// one if `pinned_root_digests` is set)
+    #[serde(default)]
+    pub chain_policy: Option<ChainPolicy>,
+
+    /// Log IDs (SHA-256 of the log public key) of the accepted transparency
+    /// logs (`None` accepts any log)
+    #[serde(default)]
+    pub allowed_log_ids: Option<Vec<Digest>>,
+
+    /// Public keys of the trusted transparency logs, used to verify Rekor v1
+    /// signed entry timestamps and Rekor v2 checkpoints; an entry from a log
+    /// not listed here is rejected (`None` leaves Rekor v1 promises unchecked
+    /// and rejects Rekor v2 entries)
+    #[serde(default)]
+    pub transparency_log_keys: Option<Vec<TransparencyLogKey>>,
+
+    /// Constraints on the RFC 3161 timestamp token (`None` accepts any token
+    /// from the trusted TSA)
+    #[serde(default)]
+    pub tsa_policy: Option<TsaPolicy>,
+
+    /// Check in-toto payloads against the Statement (and SLSA v1 provenance)
+    /// schema, reporting every violation with its path
+    #[serde(default)]
+    pub strict_statement: bool,
+
+    /// Accept redactable statements whose predicate has redacted fields (see
+    /// `redaction`); their subjects are always intact
+    #[serde(default)]
+    pub allow_redacted_predicate: bool,
+
+    /// Require a SLSA source provenance attestation and check its repository,
+    /// branch, controls and reviewers (`None` accepts any predicate)
+    #[serde(default)]
+    pub source_policy: Option<SourcePolicy>,
+
+    /// Require every dependency a SLSA provenance resolved to come from an
+    /// allowed URI (`None` accepts any dependency)
+    #[serde(default)]
+    pub dependency_policy: Option<DependencyPolicy>,
+
+    /// Verify as of this time instead of leaving the reference time open: the
+    /// bundle must have been signed at or before it (`None` accepts any signing
+    /// time). Certificate validity is always judged at the signing time, so
+    /// expired certificates never matter either way.
+    #[serde(default)]
+    pub verify_at: Option<Timestamp>,
+}
+
+/// Timestamp mechanism required by `VerificationOptions::required_timestamp`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampRequirement {
+    /// RFC 3161 signed timestamp from a timestamp authority
+    Rfc3161,
+    /// Rekor transparency log integrated time
+    Rekor,
+}
+
+impl TimestampRequirement {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimestampRequirement::Rfc3161 => "rfc3161",
+            TimestampRequirement::Rekor => "rekor",
+        }
+    }
+}
+
+/// Public key of a trusted transparency log, as listed in a TrustedRoot's `tlogs`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransparencyLogKey {
+    /// Log origin, the signer name of its checkpoints (e.g. `log2025-1.rekor.sigstore.dev`)
+    pub origin: String,
+    /// Base64 DER SubjectPublicKeyInfo (`publicKey.rawBytes`)
+    pub public_key: String,
+}
+
+impl TransparencyLogKey {
+    pub fn new(origin: impl Into<String>, public_key: impl Into<String>) -> Self {
+        TransparencyLogKey {
+            origin: origin.into(),
+            public_key: public_key.into(),
+        }
+    }
+
+    /// Log ID: SHA-256 of the DER public key for ECDSA keys, and the
+    /// `signed-note` key ID of the origin and key for Ed25519 keys (Rekor v2)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the public key cannot be parsed.
+    pub fn log_id(&self) -> Result<Digest, VerificationError> {
+        Ok(Digest::sha256(LogVerifier::from_base64(&self.origin, &self.public_key)?.log_id()))
+    }
+}
+
+impl VerificationOptions {
+    /// Require the subject digest to equal `digest`
+    ///
+    /// Accepts `"sha256:<hex>"` or bare hex (`&str` or `String`) and raw bytes
+    /// (`&[u8]`), as well as an already parsed `Digest`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error if `digest` is malformed or its algorithm
+    /// cannot be inferred.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = VerificationOptions::default().with_expected_digest(hex_digest_from_ci.as_str())?;
+    /// ```
+    pub fn with_expected_digest<D>(mut self, digest: D) -> Result<Self, DigestError>
+    where
+        D: TryInto<Digest>,
+        DigestError: From<D::Error>,
+    {
+        self.expected_digest = Some(digest.try_into()?);
+        Ok(self)
+    }
+
+    /// Read the digest from the first subject whose name matches `pattern`
+    pub fn with_expected_subject_name(mut self, pattern: impl Into<String>) -> Self {
+        self.expected_subject_name = Some(pattern.into());
+        self
+    }
+
+    /// Require the certificate's OIDC issuer to equal `issuer`
+    pub fn with_expected_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.expected_issuer = Some(issuer.into());
+        self
+    }
+
+    /// Require the certificate's OIDC subject to equal `subject`
+    pub fn with_expected_subject(mut self, subject: impl Into<String>) -> Self {
+        self.expected_subject = Some(subject.into());
+        self
+    }
+
+    pub fn with_identity_policy(mut self, policy: IdentityPolicy) -> Self {
+        self.identity_policy = Some(policy);
+        self
+    }
+
+    pub fn with_allowed_signature_algorithms(mut self, algorithms: Vec<SignatureAlgorithm>) -> Self {
+        self.allowed_signature_algorithms = Some(algorithms);
+        self
+    }
+
+    /// Accept only predicate types matching one of `patterns`
+    pub fn with_allowed_predicate_types<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_predicate_types = Some(patterns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_required_timestamp(mut self, requirement: TimestampRequirement) -> Self {
+        self.required_timestamp = Some(requirement);
+        self
+    }
+
+    pub fn with_pinned_root_digests(mut self, digests: Vec<Digest>) -> Self {
+        self.pinned_root_digests = Some(digests);
+        self
+    }
+
+    pub fn with_chain_policy(mut self, policy: ChainPolicy) -> Self {
+        self.chain_policy = Some(policy);
+        self
+    }
+
+    pub fn with_allowed_log_ids(mut self, log_ids: Vec<Digest>) -> Self {
+        self.allowed_log_ids = Some(log_ids);
+        self
+    }
+
+    pub fn with_transparency_log_keys(mut self, keys: Vec<TransparencyLogKey>) -> Self {
+        self.transparency_log_keys = Some(keys);
+        self
+    }
+
+    pub fn with_tsa_policy(mut self, policy: TsaPolicy) -> Self {
+        self.tsa_policy = Some(policy);
+        self
+    }
+
+    pub fn with_strict_statement(mut self, strict: bool) -> Self {
+        self.strict_statement = strict;
+        self
+    }
+
+    pub fn with_allow_redacted_predicate(mut self, allow: bool) -> Self {
+        self.allow_redacted_predicate = allow;
+        self
+    }
+
+    pub fn with_source_policy(mut self, policy: SourcePolicy) -> Self {
+        self.source_policy = Some(policy);
+        self
+    }
+
+    pub fn with_dependency_policy(mut self, policy: DependencyPolicy) -> Self {
+        self.dependency_policy = Some(policy);
+        self
+    }
+
+    /// Verify as of `time`; see the `verify_at` field
+    pub fn with_verify_at(mut self, time: Timestamp) -> Self {
+        self.verify_at = Some(time);
+        self
+    }
+
+    /// The effective signature algorithm allowlist
+    pub fn signature_algorithms(&self) -> &[SignatureAlgorithm] {
+        self.allowed_signature_algorithms
+            .as_deref()
+            .unwrap_or(DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS)
+    }
+
+    /// Canonical, versioned serialization of the options
+    ///
+    /// RFC 8785 canonical JSON (members sorted by key) tagged with
+    /// `VERIFICATION_OPTIONS_VERSION`. Every field is written, `null` when unset,
+    /// and the signature algorithm allowlist is resolved to its effective value,
+    /// so options that verify identically serialize identically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the options cannot be serialized to JSON.
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, JcsError> {
+        to_canonical_vec(&CanonicalOptions {
+            version: VERIFICATION_OPTIONS_VERSION,
+            expected_digest: self.expected_digest.as_ref(),
+            expected_subject_name: self.expected_subject_name.as_deref(),
+            expected_issuer: self.expected_issuer.as_deref(),
+            expected_subject: self.expected_subject.as_deref(),
+            identity_policy: self.identity_policy.as_ref(),
+            allowed_signature_algorithms: self.signature_algorithms(),
+            allowed_predicate_types: self.allowed_predicate_types.as_deref(),
+            required_timestamp: self.required_timestamp,
+            pinned_root_digests: self.pinned_root_digests.as_deref(),
+            chain_policy: self.chain_policy.as_ref().filter(|policy| **policy != ChainPolicy::default()),
+            allowed_log_ids: self.allowed_log_ids.as_deref(),
+            transparency_log_keys: self.transparency_log_keys.as_deref(),
+            tsa_policy: self.tsa_policy.as_ref(),
+            strict_statement: self.strict_statement,
+            allow_redacted_predicate: self.allow_redacted_predicate,
+            source_policy: self.source_policy.as_ref(),
+            dependency_policy: self.dependency_policy.as_ref(),
+            verify_at: self.verify_at.map(|time| time.timestamp()),
+        })
+    }
+
+    /// SHA-256 of `canonical_bytes()`, committed by the guest next to the result
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the options cannot be serialized to JSON.
+    pub fn options_hash(&self) -> Result<[u8; 32], JcsError> {
+        Ok(sha256(&self.canonical_bytes()?))
+    }
+}
+
+/// Version of the `VerificationOptions::canonical_bytes` format
+///
+/// Bump when a field is added or its meaning changes, so hashes of old and new
+/// options never collide.
+pub const VERIFICATION_OPTIONS_VERSION: u32 = 12;
+
+#[derive(Serialize)]
+struct CanonicalOptions<'a> {
+    version: u32,
+    expected_digest: Option<&'a Digest>,
+    expected_subject_name: Option<&'a str>,
+    expected_issuer: Option<&'a str>,
+    expected_subject: Option<&'a str>,
+    identity_policy: Option<&'a IdentityPolicy>,
+    allowed_signature_algorithms: &'a [SignatureAlgorithm],
+    allowed_predicate_types: Option<&'a [String]>,
+    required_timestamp: Option<TimestampRequirement>,
+    pinned_root_digests: Option<&'a [Digest]>,
+    /// `null` for the default policy, which verifies the same as no policy
+    chain_policy: Option<&'a ChainPolicy>,
+    allowed_log_ids: Option<&'a [Digest]>,
+    transparency_log_keys: Option<&'a [TransparencyLogKey]>,
+    tsa_policy: Option<&'a TsaPolicy>,
+    strict_statement: bool,
+    allow_redacted_predicate: bool,
+    source_policy: Option<&'a SourcePolicy>,
+    dependency_policy: Option<&'a DependencyPolicy>,
+    /// Unix seconds, the precision the signing time is compared at
+    verify_at: Option<i64>,
 }
 
 impl VerificationResult {
+    /// Build a result from the fields committed by the zkVM output
+    ///
+    /// The remaining fields (subject name, leaf certificate metadata, retained
+    /// chains and resolved dependencies) start empty, as after `from_slice`.
+    pub fn new(
+        certificate_hashes: CertificateChainHashes,
+        signing_time: Timestamp,
+        subject_digest: Digest,
+        oidc_identity: Option<OidcIdentity>,
+        timestamp_proof: TimestampProof,
+    ) -> Self {
+        VerificationResult {
+            certificate_hashes,
+            signing_time,
+            subject_digest,
+            subject_name: None,
+            oidc_identity,
+            timestamp_proof,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
+        }
+    }
+
+    /// Verified Fulcio chain (leaf, intermediates, root)
+    ///
+    /// Only retained when verifying with `AttestationVerifier::with_certificate_chains`;
+    /// export it with `CertificateChain::to_pem` or `to_der`.
+    pub fn certificate_chain(&self) -> Option<&CertificateChain> {
+        self.certificate_chain.as_ref()
+    }
+
+    /// Verified TSA chain of the RFC 3161 timestamp, retained like `certificate_chain`
+    pub fn tsa_certificate_chain(&self) -> Option<&CertificateChain> {
+        self.tsa_certificate_chain.as_ref()
+    }
+
     /// Serialize the VerificationResult into a Solidity-compatible byte array
     ///
     /// See the module-level documentation for the complete binary format specification.
@@ -256,8 +639,8 @@ impl VerificationResult {
         // Create the Solidity-compatible struct
         let encoded_struct = VerificationResultEncoded {
             certificateHashes: cert_hashes,
-            subjectDigest: self.subject_digest.clone().into(),
-            subjectDigestAlgorithm: self.subject_digest_algorithm as u8,
+            subjectDigest: self.subject_digest.bytes.clone().into(),
+            subjectDigestAlgorithm: self.subject_digest.algorithm as u8,
             oidcIssuer: issuer,
             oidcSubject: subject,
             oidcWorkflowRef: workflow_ref,
@@ -391,22 +774,25 @@ impl VerificationResult {
             }
         };
 
-        // Convert timestamp to DateTime<Utc>
-        let signing_time = DateTime::from_timestamp(timestamp as i64, 0)
-            .ok_or_else(|| format!("Invalid timestamp: {}", timestamp))?;
+        // Convert timestamp to Timestamp
+        let signing_time = i64::try_from(timestamp)
+            .map(Timestamp::from_unix)
+            .map_err(|_| format!("Invalid timestamp: {}", timestamp))?;
 
-        Ok(VerificationResult {
-            certificate_hashes: CertificateChainHashes {
+        Ok(VerificationResult::new(
+            CertificateChainHashes {
                 leaf,
                 intermediates,
                 root,
             },
             signing_time,
-            subject_digest: decoded.subjectDigest.to_vec(),
-            subject_digest_algorithm: DigestAlgorithm::from_u8(decoded.subjectDigestAlgorithm),
+            Digest {
+                algorithm: DigestAlgorithm::from_u8(decoded.subjectDigestAlgorithm),
+                bytes: decoded.subjectDigest.to_vec(),
+            },
             oidc_identity,
             timestamp_proof,
-        })
+        ))
     }
 }
 
@@ -423,9 +809,11 @@ mod tests {
                 intermediates: vec![[2u8; 32], [3u8; 32]],
                 root: [4u8; 32],
             },
-            signing_time: DateTime::from_timestamp(1700000000, 0).unwrap(),
-            subject_digest: vec![5u8; 32],
-            subject_digest_algorithm: DigestAlgorithm::Sha256,
+            signing_time: Timestamp::from_unix(1700000000),
+            subject_digest: Digest {
+                algorithm: DigestAlgorithm::Sha256,
+                bytes: vec![5u8; 32],
+            },
             oidc_identity: Some(OidcIdentity {
                 issuer: Some("https://token.actions.githubusercontent.com".to_string()),
                 subject: Some("repo:owner/repo:ref:refs/heads/main".to_string()),
@@ -442,6 +830,11 @@ mod tests {
                 message_imprint_algorithm: DigestAlgorithm::Sha256,
                 message_imprint: vec![13u8; 32],
             },
+            subject_name: None,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
         };
 
         let encoded = original.as_slice();
@@ -453,7 +846,7 @@ mod tests {
         assert_eq!(original.certificate_hashes.root, decoded.certificate_hashes.root);
         assert_eq!(original.signing_time.timestamp(), decoded.signing_time.timestamp());
         assert_eq!(original.subject_digest, decoded.subject_digest);
-        assert_eq!(original.subject_digest_algorithm, decoded.subject_digest_algorithm);
+        assert_eq!(original.subject_digest.algorithm, decoded.subject_digest.algorithm);
         assert_eq!(original.oidc_identity, decoded.oidc_identity);
 
         // Verify RFC 3161 timestamp proof
@@ -481,15 +874,22 @@ mod tests {
                 intermediates: vec![],
                 root: [2u8; 32],
             },
-            signing_time: DateTime::from_timestamp(1700000000, 0).unwrap(),
-            subject_digest: vec![3u8; 32],
-            subject_digest_algorithm: DigestAlgorithm::Sha256,
+            signing_time: Timestamp::from_unix(1700000000),
+            subject_digest: Digest {
+                algorithm: DigestAlgorithm::Sha256,
+                bytes: vec![3u8; 32],
+            },
             oidc_identity: None,
             timestamp_proof: TimestampProof::Rekor {
                 log_id: [20u8; 32],
                 log_index: 12345678,
                 entry_index: 87654321,
             },
+            subject_name: None,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
         };
 
         let encoded = original.as_slice();
@@ -518,11 +918,18 @@ mod tests {
                 intermediates: vec![],
                 root: [20u8; 32],
             },
-            signing_time: DateTime::from_timestamp(1600000000, 0).unwrap(),
-            subject_digest: vec![30u8; 32],
-            subject_digest_algorithm: DigestAlgorithm::Sha384,
+            signing_time: Timestamp::from_unix(1600000000),
+            subject_digest: Digest {
+                algorithm: DigestAlgorithm::Sha384,
+                bytes: vec![30u8; 32],
+            },
             oidc_identity: None,
             timestamp_proof: TimestampProof::None,
+            subject_name: None,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
         };
 
         let encoded = original.as_slice();
@@ -532,7 +939,7 @@ mod tests {
         assert_eq!(original.certificate_hashes.intermediates.len(), 0);
         assert_eq!(decoded.certificate_hashes.intermediates.len(), 0);
         assert_eq!(original.certificate_hashes.root, decoded.certificate_hashes.root);
-        assert_eq!(original.subject_digest_algorithm, decoded.subject_digest_algorithm);
+        assert_eq!(original.subject_digest.algorithm, decoded.subject_digest.algorithm);
         assert!(matches!(decoded.timestamp_proof, TimestampProof::None));
     }
 
@@ -545,9 +952,11 @@ mod tests {
                 intermediates: vec![[101u8; 32]],
                 root: [102u8; 32],
             },
-            signing_time: DateTime::from_timestamp(1650000000, 0).unwrap(),
-            subject_digest: vec![103u8; 32],
-            subject_digest_algorithm: DigestAlgorithm::Sha256,
+            signing_time: Timestamp::from_unix(1650000000),
+            subject_digest: Digest {
+                algorithm: DigestAlgorithm::Sha256,
+                bytes: vec![103u8; 32],
+            },
             oidc_identity: Some(OidcIdentity {
                 issuer: Some("https://example.com".to_string()),
                 subject: Some("test-subject".to_string()),
@@ -556,6 +965,11 @@ mod tests {
                 event_name: None,
             }),
             timestamp_proof: TimestampProof::None,
+            subject_name: None,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
         };
 
         let encoded = original.as_slice();
@@ -592,15 +1006,22 @@ mod tests {
                 intermediates: vec![],
                 root: [2u8; 32],
             },
-            signing_time: DateTime::from_timestamp(1700000000, 0).unwrap(),
-            subject_digest: vec![3u8; 32],
-            subject_digest_algorithm: DigestAlgorithm::Sha256,
+            signing_time: Timestamp::from_unix(1700000000),
+            subject_digest: Digest {
+                algorithm: DigestAlgorithm::Sha256,
+                bytes: vec![3u8; 32],
+            },
             oidc_identity: None,
             timestamp_proof: TimestampProof::Rekor {
                 log_id: [4u8; 32],
                 log_index: 999,
                 entry_index: 1000,
             },
+            subject_name: None,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
         };
 
         let encoded = original.as_slice();
@@ -626,11 +1047,18 @@ mod tests {
                 intermediates: vec![[22u8; 32], [33u8; 32], [44u8; 32]],
                 root: [55u8; 32],
             },
-            signing_time: DateTime::from_timestamp(1700000000, 0).unwrap(),
-            subject_digest: vec![66u8; 32],
-            subject_digest_algorithm: DigestAlgorithm::Sha256,
+            signing_time: Timestamp::from_unix(1700000000),
+            subject_digest: Digest {
+                algorithm: DigestAlgorithm::Sha256,
+                bytes: vec![66u8; 32],
+            },
             oidc_identity: None,
             timestamp_proof: TimestampProof::None,
+            subject_name: None,
+            leaf_certificate: None,
+            certificate_chain: None,
+            tsa_certificate_chain: None,
+            resolved_dependencies: Vec::new(),
         };
 
         let encoded = original.as_slice();
@@ -648,6 +1076,8 @@ mod tests {
         assert_eq!(DigestAlgorithm::from_u8(0), DigestAlgorithm::Unknown);
         assert_eq!(DigestAlgorithm::from_u8(1), DigestAlgorithm::Sha256);
         assert_eq!(DigestAlgorithm::from_u8(2), DigestAlgorithm::Sha384);
+        assert_eq!(DigestAlgorithm::from_u8(3), DigestAlgorithm::GitCommit);
+        assert_eq!(DigestAlgorithm::from_u8(4), DigestAlgorithm::Sha512);
         assert_eq!(DigestAlgorithm::from_u8(255), DigestAlgorithm::Unknown);
     }
 
@@ -659,4 +1089,50 @@ mod tests {
         assert_eq!(TimestampProofType::from_u8(2), TimestampProofType::Rekor);
         assert_eq!(TimestampProofType::from_u8(255), TimestampProofType::None);
     }
+
+    #[test]
+    fn test_options_canonical_bytes() {
+        let canonical = VerificationOptions::default().canonical_bytes().unwrap();
+        assert_eq!(
+            String::from_utf8(canonical).unwrap(),
+            concat!(
+                r#"{"allow_redacted_predicate":false,"allowed_log_ids":null,"allowed_predicate_types":null,"#,
+                r#""allowed_signature_algorithms":["ecdsa_sha256","ecdsa_sha384","ecdsa_sha512"],"chain_policy":null,"#,
+                r#""dependency_policy":null,"#,
+                r#""expected_digest":null,"expected_issuer":null,"expected_subject":null,"expected_subject_name":null,"#,
+                r#""identity_policy":null,"pinned_root_digests":null,"required_timestamp":null,"#,
+                r#""source_policy":null,"strict_statement":false,"transparency_log_keys":null,"tsa_policy":null,"#,
+                r#""verify_at":null,"version":12}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_options_hash_resolves_defaults() {
+        let default = VerificationOptions::default();
+        let explicit = VerificationOptions {
+            allowed_signature_algorithms: Some(DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS.to_vec()),
+            ..Default::default()
+        };
+        assert_eq!(default.options_hash().unwrap(), explicit.options_hash().unwrap());
+
+        let narrowed = VerificationOptions {
+            allowed_signature_algorithms: Some(vec![SignatureAlgorithm::EcdsaSha256]),
+            ..Default::default()
+        };
+        assert_ne!(default.options_hash().unwrap(), narrowed.options_hash().unwrap());
+    }
+
+    #[test]
+    fn test_with_expected_digest() {
+        let hex = "01".repeat(32);
+        let options = VerificationOptions::default().with_expected_digest(hex.as_str()).unwrap();
+        assert_eq!(options.expected_digest, Some(Digest::sha256([1; 32])));
+        let options = VerificationOptions::default().with_expected_digest(&[1u8; 32][..]).unwrap();
+        assert_eq!(options.expected_digest, Some(Digest::sha256([1; 32])));
+        assert!(matches!(
+            VerificationOptions::default().with_expected_digest("sha256:0101"),
+            Err(DigestError::InvalidLength { .. })
+        ));
+    }
 }
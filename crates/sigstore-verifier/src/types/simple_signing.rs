@@ -0,0 +1,190 @@
+//! Cosign "simple signing" signatures
+//!
+//! Before in-toto attestations, cosign signed a container image by signing a
+//! small JSON document naming the image's manifest digest. Registries still
+//! hold these signatures as layers of `sha256-<digest>.sig` tags: the layer is
+//! the payload and its annotations carry the signature, the Fulcio
+//! certificate, and the Rekor or TSA material timestamping it.
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::types::bundle::{
+    Certificate, InclusionPromise, KindVersion, LogId, Rfc3161Timestamp, TimestampVerificationData,
+    TransparencyLogEntry, VerificationMaterial,
+};
+use crate::types::digest::Digest;
+use crate::types::dsse::{Statement, Subject, STATEMENT_V1};
+
+/// Media type of a simple signing payload
+pub const SIMPLE_SIGNING_MEDIA_TYPE: &str = "application/vnd.dev.cosign.simplesigning.v1+json";
+
+/// `critical.type` of cosign image signatures
+pub const COSIGN_SIGNATURE_TYPE: &str = "cosign container image signature";
+
+/// `critical.type` of containers/image signatures, also produced by cosign
+pub const ATOMIC_SIGNATURE_TYPE: &str = "atomic container signature";
+
+/// A simple signing payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimpleSigningPayload {
+    pub critical: SimpleSigningCritical,
+    /// Free-form annotations added at signing time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimpleSigningCritical {
+    pub identity: SimpleSigningIdentity,
+    pub image: SimpleSigningImage,
+    #[serde(rename = "type")]
+    pub signature_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimpleSigningIdentity {
+    /// Repository the image was signed under, e.g. `ghcr.io/org/image`
+    #[serde(rename = "docker-reference")]
+    pub docker_reference: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimpleSigningImage {
+    /// Signed manifest digest, `<alg>:<hex>`
+    #[serde(rename = "docker-manifest-digest")]
+    pub docker_manifest_digest: String,
+}
+
+impl SimpleSigningPayload {
+    /// The signed manifest digest
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidBundleFormat` if `docker-manifest-digest` is not `<alg>:<hex>`.
+    pub fn manifest_digest(&self) -> Result<Digest, VerificationError> {
+        self.critical
+            .image
+            .docker_manifest_digest
+            .parse()
+            .map_err(|e| VerificationError::InvalidBundleFormat(format!("Invalid docker-manifest-digest: {}", e)))
+    }
+
+    /// View the payload as an in-toto statement
+    ///
+    /// The statement has the image as its only subject and the payload itself
+    /// as predicate, with [`SIMPLE_SIGNING_MEDIA_TYPE`] as predicate type, so
+    /// subject digest checks, predicate type allow-lists and policy hooks apply
+    /// to simple signing payloads unchanged.
+    pub fn to_statement(&self) -> Result<Statement, VerificationError> {
+        let digest = self.manifest_digest()?;
+        Ok(Statement {
+            statement_type: STATEMENT_V1.to_string(),
+            subject: vec![Subject {
+                name: self.critical.identity.docker_reference.clone(),
+                digest: HashMap::from([(digest.algorithm.name().to_string(), digest.to_hex())]),
+            }],
+            predicate_type: SIMPLE_SIGNING_MEDIA_TYPE.to_string(),
+            predicate: serde_json::to_value(self)?,
+        })
+    }
+}
+
+/// A cosign signature layer with its annotations decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleSignature {
+    /// Raw simple signing payload (the layer blob)
+    pub payload: Vec<u8>,
+    /// Signature over `payload`
+    pub signature: Vec<u8>,
+    /// DER-encoded signing certificate
+    pub certificate: Vec<u8>,
+    /// Rekor entry the signature was uploaded to
+    pub rekor_bundle: Option<CosignRekorBundle>,
+    /// DER-encoded RFC 3161 timestamp over `signature`
+    pub rfc3161_timestamp: Option<Vec<u8>>,
+}
+
+impl SimpleSignature {
+    /// Express the certificate and timestamp material as a bundle's `verificationMaterial`
+    pub fn to_verification_material(&self) -> Result<VerificationMaterial, VerificationError> {
+        let tlog_entries = self
+            .rekor_bundle
+            .as_ref()
+            .map(|bundle| bundle.to_tlog_entry().map(|entry| vec![entry]))
+            .transpose()?;
+        let timestamp_verification_data = self.rfc3161_timestamp.as_ref().map(|token| TimestampVerificationData {
+            rfc3161_timestamps: Some(vec![Rfc3161Timestamp {
+                signed_timestamp: BASE64.encode(token),
+            }]),
+        });
+
+        Ok(VerificationMaterial {
+            timestamp_verification_data,
+            certificate: Certificate {
+                raw_bytes: BASE64.encode(&self.certificate),
+            },
+            x509_certificate_chain: None,
+            tlog_entries,
+        })
+    }
+}
+
+/// Rekor bundle of the `dev.sigstore.cosign/bundle` annotation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CosignRekorBundle {
+    /// Base64-encoded Signed Entry Timestamp
+    pub signed_entry_timestamp: String,
+    pub payload: CosignRekorPayload,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CosignRekorPayload {
+    /// Base64-encoded canonicalized entry body
+    pub body: String,
+    pub integrated_time: i64,
+    pub log_index: i64,
+    /// Hex-encoded log ID
+    #[serde(rename = "logID")]
+    pub log_id: String,
+}
+
+impl CosignRekorBundle {
+    /// Convert to the transparency log entry a Sigstore bundle would carry
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidBundleFormat` if the log ID is not hex.
+    pub fn to_tlog_entry(&self) -> Result<TransparencyLogEntry, VerificationError> {
+        let log_id = hex::decode(&self.payload.log_id)
+            .map_err(|e| VerificationError::InvalidBundleFormat(format!("Invalid Rekor log ID: {}", e)))?;
+        let kind_version = serde_json::from_slice::<serde_json::Value>(&BASE64.decode(&self.payload.body)?)
+            .ok()
+            .and_then(|body| {
+                Some(KindVersion {
+                    kind: body.get("kind")?.as_str()?.to_string(),
+                    version: body.get("apiVersion")?.as_str()?.to_string(),
+                })
+            });
+
+        Ok(TransparencyLogEntry {
+            log_index: Some(self.payload.log_index.to_string()),
+            log_id: Some(LogId {
+                key_id: BASE64.encode(log_id),
+            }),
+            kind_version,
+            integrated_time: self.payload.integrated_time.to_string(),
+            inclusion_promise: Some(InclusionPromise {
+                signed_entry_timestamp: self.signed_entry_timestamp.clone(),
+            }),
+            inclusion_proof: None,
+            canonicalized_body: self.payload.body.clone(),
+        })
+    }
+}
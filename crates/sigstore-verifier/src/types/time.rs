@@ -0,0 +1,315 @@
+//! Unix timestamps without a calendar library
+//!
+//! Verification only compares instants at second precision, so the
+//! verification paths use [`Timestamp`], a count of seconds since the Unix
+//! epoch, with the RFC 3339 parsing and formatting that bundles, trusted roots
+//! and policy files need. This keeps chrono out of zkVM guests; hosts working
+//! with chrono enable the `std-time` feature for conversions from and to
+//! `DateTime<Utc>`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::TimeError;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Seconds since the Unix epoch (UTC)
+///
+/// Serializes as an RFC 3339 string, e.g. `2025-06-01T00:00:00Z`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub const UNIX_EPOCH: Timestamp = Timestamp(0);
+    pub const MIN: Timestamp = Timestamp(i64::MIN);
+    pub const MAX: Timestamp = Timestamp(i64::MAX);
+
+    pub const fn from_unix(seconds: i64) -> Self {
+        Timestamp(seconds)
+    }
+
+    /// Seconds since the Unix epoch
+    pub const fn timestamp(self) -> i64 {
+        self.0
+    }
+
+    /// Time of a UTC calendar date, for years 0 to 9999
+    ///
+    /// Returns `None` if a field is out of range, e.g. February 30th.
+    pub fn from_ymd_hms(year: i64, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<Self> {
+        if !(0..=9999).contains(&year)
+            || !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        Some(Timestamp(
+            days * SECONDS_PER_DAY + i64::from(hour) * 3600 + i64::from(minute) * 60 + i64::from(second),
+        ))
+    }
+
+    /// Parse an RFC 3339 date-time, e.g. `2025-06-01T00:00:00Z` or
+    /// `2025-06-01T02:00:00.5+02:00`
+    ///
+    /// Fractional seconds are truncated and a leap second is read as the
+    /// second before it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidFormat` if `s` is not an RFC 3339 date-time.
+    pub fn parse_rfc3339(s: &str) -> Result<Self, TimeError> {
+        let invalid = || TimeError::InvalidFormat(s.to_string());
+        let bytes = s.as_bytes();
+        if bytes.len() < 20
+            || bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || !matches!(bytes[10], b'T' | b't' | b' ')
+            || bytes[13] != b':'
+            || bytes[16] != b':'
+        {
+            return Err(invalid());
+        }
+        let field = |range: std::ops::Range<usize>| -> Result<u32, TimeError> {
+            let digits = s.get(range).ok_or_else(invalid)?;
+            if !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            digits.parse().map_err(|_| invalid())
+        };
+
+        let second = field(17..19)?;
+        if second > 60 {
+            return Err(invalid());
+        }
+        let time = Timestamp::from_ymd_hms(
+            i64::from(field(0..4)?),
+            field(5..7)?,
+            field(8..10)?,
+            field(11..13)?,
+            field(14..16)?,
+            second.min(59),
+        )
+        .ok_or_else(invalid)?;
+
+        let mut rest = &s[19..];
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return Err(invalid());
+            }
+            rest = &fraction[digits..];
+        }
+        let offset = match rest {
+            "Z" | "z" => 0,
+            _ if rest.len() == 6 && matches!(rest.as_bytes()[0], b'+' | b'-') && rest.as_bytes()[3] == b':' => {
+                let (hours, minutes) = (field(s.len() - 5..s.len() - 3)?, field(s.len() - 2..s.len())?);
+                if hours > 23 || minutes > 59 {
+                    return Err(invalid());
+                }
+                let offset = i64::from(hours * 3600 + minutes * 60);
+                if rest.starts_with('-') {
+                    -offset
+                } else {
+                    offset
+                }
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(Timestamp(time.0 - offset))
+    }
+
+    /// RFC 3339 form in UTC, e.g. `2025-06-01T00:00:00Z`
+    pub fn to_rfc3339(self) -> String {
+        let days = self.0.div_euclid(SECONDS_PER_DAY);
+        let seconds = self.0.rem_euclid(SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+
+    pub fn checked_add_seconds(self, seconds: i64) -> Option<Self> {
+        self.0.checked_add(seconds).map(Timestamp)
+    }
+
+    pub fn saturating_add_seconds(self, seconds: i64) -> Self {
+        Timestamp(self.0.saturating_add(seconds))
+    }
+
+    pub fn saturating_sub_seconds(self, seconds: i64) -> Self {
+        Timestamp(self.0.saturating_sub(seconds))
+    }
+
+    /// Seconds from `earlier` to `self`, negative if `earlier` is later
+    pub fn seconds_since(self, earlier: Timestamp) -> i64 {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// The current time from the system clock
+    ///
+    /// Not available in zkVM guests, which have no clock; they take the time
+    /// as input instead.
+    #[cfg(not(target_os = "zkvm"))]
+    pub fn now() -> Self {
+        let seconds = match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+            Ok(elapsed) => i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX),
+            Err(before) => i64::try_from(before.duration().as_secs()).map_or(i64::MIN, |seconds| -seconds),
+        };
+        Timestamp(seconds)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_rfc3339())
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = TimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timestamp::parse_rfc3339(s)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "std-time")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp(time.timestamp())
+    }
+}
+
+#[cfg(feature = "std-time")]
+impl Timestamp {
+    /// The time as a chrono `DateTime`, or `None` outside chrono's range
+    pub fn to_datetime(self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.0, 0)
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date (Howard Hinnant's `days_from_civil`)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`]
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc3339_round_trip() {
+        for (text, seconds) in [
+            ("1970-01-01T00:00:00Z", 0),
+            ("2024-02-29T12:34:56Z", 1_709_210_096),
+            ("2025-11-18T07:58:34Z", 1_763_452_714),
+            ("1969-12-31T23:59:59Z", -1),
+            ("9999-12-31T23:59:59Z", 253_402_300_799),
+        ] {
+            let time = Timestamp::parse_rfc3339(text).unwrap();
+            assert_eq!(time.timestamp(), seconds, "{}", text);
+            assert_eq!(time.to_rfc3339(), text);
+        }
+    }
+
+    #[test]
+    fn test_parse_rfc3339_variants() {
+        let expected = Timestamp::from_unix(1_748_736_000);
+        for text in [
+            "2025-06-01T00:00:00Z",
+            "2025-06-01t00:00:00z",
+            "2025-06-01 00:00:00Z",
+            "2025-06-01T00:00:00.999999Z",
+            "2025-06-01T02:00:00+02:00",
+            "2025-05-31T19:30:00-04:30",
+        ] {
+            assert_eq!(Timestamp::parse_rfc3339(text), Ok(expected), "{}", text);
+        }
+        assert_eq!(
+            Timestamp::parse_rfc3339("2016-12-31T23:59:60Z").unwrap().to_rfc3339(),
+            "2016-12-31T23:59:59Z"
+        );
+
+        for text in [
+            "",
+            "2025-06-01",
+            "2025-06-01T00:00:00",
+            "2025-02-29T00:00:00Z",
+            "2025-06-01T24:00:00Z",
+            "2025-06-01T00:00:00.Z",
+            "2025-06-01T00:00:00+0200",
+            "2025-06-01T00:00:00+24:00",
+            "+025-06-01T00:00:00Z",
+        ] {
+            assert!(Timestamp::parse_rfc3339(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let time = Timestamp::from_unix(1_700_000_000);
+        let json = serde_json::to_string(&time).unwrap();
+        assert_eq!(json, r#""2023-11-14T22:13:20Z""#);
+        assert_eq!(serde_json::from_str::<Timestamp>(&json).unwrap(), time);
+    }
+}
@@ -1,13 +1,16 @@
+use std::sync::Arc;
+
 use x509_parser::prelude::*;
 
-use crate::crypto::hash::sha256;
-use crate::crypto::signature::PublicKey;
+use crate::crypto::algorithm::{AlgorithmLookup, SignatureAlgorithm, DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS};
 use crate::error::CertificateError;
 use crate::parser::bundle::decode_base64;
-use crate::parser::certificate::parse_der_certificate;
+use crate::policy::chain::ChainPolicy;
 use crate::types::bundle::SigstoreBundle;
-use crate::types::certificate::CertificateChain;
+use crate::types::certificate::{CertificateChain, ParsedCertificate};
 use crate::types::result::CertificateChainHashes;
+use crate::verifier::context::VerificationContext;
+use crate::verifier::pool::CertificatePool;
 
 /// Verify the certificate chain using provided trust bundle
 ///
@@ -27,71 +30,222 @@ pub fn verify_certificate_chain(
     let leaf_der = decode_base64(&bundle.verification_material.certificate.raw_bytes)
         .map_err(|e| CertificateError::ParseError(e.to_string()))?;
 
+    verify_certificate_chain_der(leaf_der, trust_bundle, DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS)
+}
+
+/// Verify the certificate chain using provided trust bundle, under a chain policy
+///
+/// # Arguments
+///
+/// * `bundle` - The Sigstore bundle containing the leaf certificate
+/// * `trust_bundle` - The trust bundle (intermediates and root) for verification
+/// * `policy` - Maximum depth and whether the root must be self-signed
+///
+/// # Errors
+///
+/// Returns `ChainTooDeep` if the chain is longer than the policy allows and
+/// `SelfSignedVerificationFailed` if a required self-signature does not verify.
+pub fn verify_certificate_chain_with_policy(
+    bundle: &SigstoreBundle,
+    trust_bundle: &CertificateChain,
+    policy: &ChainPolicy,
+) -> Result<(CertificateChain, CertificateChainHashes), CertificateError> {
+    let leaf_der = decode_base64(&bundle.verification_material.certificate.raw_bytes)
+        .map_err(|e| CertificateError::ParseError(e.to_string()))?;
+
+    verify_chain(
+        leaf_der,
+        trust_bundle,
+        DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS,
+        policy,
+        None,
+        &VerificationContext::default(),
+    )
+}
+
+/// Verify the certificate chain for an already decoded leaf certificate
+///
+/// # Arguments
+///
+/// * `leaf_der` - DER-encoded leaf certificate
+/// * `trust_bundle` - The trust bundle (intermediates and root) for verification
+/// * `allowed_algorithms` - Signature algorithms certificates in the chain may use
+///
+/// # Returns
+///
+/// Returns the complete certificate chain and SHA256 hashes of all certificates
+pub fn verify_certificate_chain_der(
+    leaf_der: Vec<u8>,
+    trust_bundle: &CertificateChain,
+    allowed_algorithms: &[SignatureAlgorithm],
+) -> Result<(CertificateChain, CertificateChainHashes), CertificateError> {
+    verify_chain(
+        leaf_der,
+        trust_bundle,
+        allowed_algorithms,
+        &ChainPolicy::default(),
+        None,
+        &VerificationContext::default(),
+    )
+}
+
+/// Verify the certificate chain, taking the trust bundle's certificates from `pool`
+///
+/// Same as [`verify_certificate_chain_der`], but the intermediates and root
+/// are parsed once and then reused from the pool (see `verifier::pool`).
+pub fn verify_certificate_chain_der_pooled(
+    leaf_der: Vec<u8>,
+    trust_bundle: &CertificateChain,
+    allowed_algorithms: &[SignatureAlgorithm],
+    pool: &CertificatePool,
+) -> Result<(CertificateChain, CertificateChainHashes), CertificateError> {
+    verify_chain(
+        leaf_der,
+        trust_bundle,
+        allowed_algorithms,
+        &ChainPolicy::default(),
+        Some(pool),
+        &VerificationContext::default(),
+    )
+}
+
+/// Verify the certificate chain under `policy` with an optional pool, checking `context` between links
+pub(crate) fn verify_chain(
+    leaf_der: Vec<u8>,
+    trust_bundle: &CertificateChain,
+    allowed_algorithms: &[SignatureAlgorithm],
+    policy: &ChainPolicy,
+    pool: Option<&CertificatePool>,
+    context: &VerificationContext,
+) -> Result<(CertificateChain, CertificateChainHashes), CertificateError> {
     // Create complete chain with leaf from bundle
     let chain = CertificateChain {
-        leaf: leaf_der.clone(),
+        leaf: leaf_der,
         intermediates: trust_bundle.intermediates.clone(),
         root: trust_bundle.root.clone(),
     };
 
+    policy.check_depth(chain.intermediates.len() + 2)?;
+
     // Parse all certificates
-    let leaf_x509 = parse_der_certificate(&chain.leaf)?;
-    let mut intermediate_x509 = Vec::new();
-    for der in &chain.intermediates {
-        intermediate_x509.push(parse_der_certificate(der)?);
-    }
-    let root_x509 = parse_der_certificate(&chain.root)?;
+    let leaf = ParsedCertificate::from_der(&chain.leaf)?;
+    let (intermediates, root) = parse_trust_certificates(&chain, pool)?;
+    let first_issuer = intermediates.first().ok_or_else(|| {
+        CertificateError::ChainVerificationFailed("Trust bundle has no intermediate certificate".to_string())
+    })?;
 
     // Verify certificate signatures
     // 1. Verify leaf signed by first intermediate
-    verify_cert_signature(&leaf_x509, &intermediate_x509[0])?;
+    leaf.verify_issued_by(first_issuer, allowed_algorithms)?;
 
-    // 2. Verify intermediate chain
-    for i in 0..intermediate_x509.len() - 1 {
-        verify_cert_signature(&intermediate_x509[i], &intermediate_x509[i + 1])?;
-    }
-
-    // 3. Verify last intermediate signed by root
-    if let Some(last_intermediate) = intermediate_x509.last() {
-        verify_cert_signature(last_intermediate, &root_x509)?;
-    }
-
-    // 4. Verify root is self-signed
-    verify_cert_signature(&root_x509, &root_x509)?;
+    // 2-4. Verify the intermediate chain, the last intermediate against the root and the root itself
+    verify_trust_links(
+        &intermediates,
+        &root,
+        allowed_algorithms,
+        policy.require_self_signed_root,
+        context,
+    )?;
 
     // Compute SHA256 hashes of all certificates
-    let leaf_hash = sha256(&chain.leaf);
-    let intermediate_hashes: Vec<[u8; 32]> = chain
-        .intermediates
-        .iter()
-        .map(|der| sha256(der))
-        .collect();
-    let root_hash = sha256(&chain.root);
-
     let hashes = CertificateChainHashes {
-        leaf: leaf_hash,
-        intermediates: intermediate_hashes,
-        root: root_hash,
+        leaf: leaf.fingerprint,
+        intermediates: intermediates.iter().map(|cert| cert.fingerprint).collect(),
+        root: root.fingerprint,
     };
 
     Ok((chain, hashes))
 }
 
-fn verify_cert_signature(
-    cert: &X509Certificate,
-    issuer: &X509Certificate,
+/// Parse the intermediates and root of a chain, through `pool` if there is one
+fn parse_trust_certificates(
+    chain: &CertificateChain,
+    pool: Option<&CertificatePool>,
+) -> Result<(Vec<Arc<ParsedCertificate>>, Arc<ParsedCertificate>), CertificateError> {
+    let parse = |der: &[u8]| match pool {
+        Some(pool) => pool.get_or_parse(der),
+        None => ParsedCertificate::from_der(der).map(Arc::new),
+    };
+    let intermediates = chain
+        .intermediates
+        .iter()
+        .map(|der| parse(der))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((intermediates, parse(&chain.root)?))
+}
+
+/// Verify each intermediate against the next, the last against the root, and
+/// the root against itself if `self_signed_root` is set
+fn verify_trust_links(
+    intermediates: &[Arc<ParsedCertificate>],
+    root: &ParsedCertificate,
+    allowed_algorithms: &[SignatureAlgorithm],
+    self_signed_root: bool,
+    context: &VerificationContext,
 ) -> Result<(), CertificateError> {
-    let public_key = PublicKey::from_certificate(issuer)
-        .map_err(|e| CertificateError::ChainVerificationFailed(e.to_string()))?;
+    for pair in intermediates.windows(2) {
+        context.check("certificate_chain")?;
+        pair[0].verify_issued_by(&pair[1], allowed_algorithms)?;
+    }
+    context.check("certificate_chain")?;
+    if let Some(last_intermediate) = intermediates.last() {
+        last_intermediate.verify_issued_by(root, allowed_algorithms)?;
+    }
+    if !self_signed_root {
+        return Ok(());
+    }
+    root.verify_issued_by(root, allowed_algorithms).map_err(|e| match e {
+        CertificateError::ChainVerificationFailed(_) => CertificateError::SelfSignedVerificationFailed,
+        e => e,
+    })
+}
 
-    let signature = &cert.signature_value.data;
-    let tbs_certificate = cert.tbs_certificate.as_ref();
+/// Resolve and check the signature algorithm declared by a certificate
+///
+/// The outer `signatureAlgorithm` must equal the `signature` field inside the
+/// TBSCertificate (RFC 5280 §4.1.1.2), must not be MD5 or SHA-1 based, and must
+/// be in `allowed_algorithms`.
+pub fn certificate_signature_algorithm(
+    cert: &X509Certificate,
+    allowed_algorithms: &[SignatureAlgorithm],
+) -> Result<SignatureAlgorithm, CertificateError> {
+    check_signature_algorithm(
+        &cert.signature_algorithm.algorithm.to_id_string(),
+        &cert.tbs_certificate.signature.algorithm.to_id_string(),
+        cert.signature_algorithm.parameters == cert.tbs_certificate.signature.parameters,
+        allowed_algorithms,
+    )
+}
 
-    public_key
-        .verify_signature(tbs_certificate, signature)
-        .map_err(|e| CertificateError::ChainVerificationFailed(e.to_string()))?;
+/// [`certificate_signature_algorithm`] on the already extracted algorithm identifiers
+pub(crate) fn check_signature_algorithm(
+    outer: &str,
+    tbs: &str,
+    parameters_match: bool,
+    allowed_algorithms: &[SignatureAlgorithm],
+) -> Result<SignatureAlgorithm, CertificateError> {
+    if outer != tbs || !parameters_match {
+        return Err(CertificateError::SignatureAlgorithmMismatch {
+            outer: outer.to_string(),
+            tbs: tbs.to_string(),
+        });
+    }
 
-    Ok(())
+    let algorithm = match SignatureAlgorithm::from_oid(outer) {
+        AlgorithmLookup::Supported(algorithm) => algorithm,
+        AlgorithmLookup::Weak(name) => {
+            return Err(CertificateError::WeakSignatureAlgorithm(name.to_string()))
+        }
+        AlgorithmLookup::Unsupported => {
+            return Err(CertificateError::UnsupportedSignatureAlgorithm(outer.to_string()))
+        }
+    };
+
+    if !allowed_algorithms.contains(&algorithm) {
+        return Err(CertificateError::DisallowedSignatureAlgorithm(algorithm.name().to_string()));
+    }
+
+    Ok(algorithm)
 }
 
 /// Verify TSA certificate chain with EKU validation
@@ -102,47 +256,52 @@ fn verify_cert_signature(
 /// # Arguments
 ///
 /// * `tsa_chain` - The TSA certificate chain (leaf, intermediates, root)
+/// * `allowed_algorithms` - Signature algorithms certificates in the chain may use
 ///
 /// # Returns
 ///
 /// Returns Ok(()) if verification succeeds
 pub fn verify_tsa_certificate_chain(
     tsa_chain: &CertificateChain,
+    allowed_algorithms: &[SignatureAlgorithm],
+) -> Result<(), CertificateError> {
+    verify_tsa_chain(tsa_chain, allowed_algorithms, true, None, &VerificationContext::default())
+}
+
+/// Verify TSA certificate chain with EKU validation, taking the intermediates and root from `pool`
+pub fn verify_tsa_certificate_chain_pooled(
+    tsa_chain: &CertificateChain,
+    allowed_algorithms: &[SignatureAlgorithm],
+    pool: &CertificatePool,
+) -> Result<(), CertificateError> {
+    verify_tsa_chain(tsa_chain, allowed_algorithms, true, Some(pool), &VerificationContext::default())
+}
+
+/// Verify the TSA chain with an optional pool, checking `context` between links
+///
+/// `self_signed_root` is unset for roots taken from a `RootStore`, which are
+/// trust anchors whatever their self-signature uses (often SHA-1).
+pub(crate) fn verify_tsa_chain(
+    tsa_chain: &CertificateChain,
+    allowed_algorithms: &[SignatureAlgorithm],
+    self_signed_root: bool,
+    pool: Option<&CertificatePool>,
+    context: &VerificationContext,
 ) -> Result<(), CertificateError> {
     // Parse all certificates
-    let leaf_x509 = parse_der_certificate(&tsa_chain.leaf)?;
-    let mut intermediate_x509 = Vec::new();
-    for der in &tsa_chain.intermediates {
-        intermediate_x509.push(parse_der_certificate(der)?);
-    }
-    let root_x509 = parse_der_certificate(&tsa_chain.root)?;
+    let leaf = ParsedCertificate::from_der(&tsa_chain.leaf)?;
+    let (intermediates, root) = parse_trust_certificates(tsa_chain, pool)?;
 
     // Verify TSA leaf certificate EKU
-    verify_tsa_certificate_eku(&leaf_x509)?;
+    verify_tsa_certificate_eku(&leaf)?;
 
     // Verify certificate signatures
-    // 1. Verify leaf signed by first intermediate
-    if !intermediate_x509.is_empty() {
-        verify_cert_signature(&leaf_x509, &intermediate_x509[0])?;
-    } else {
-        // No intermediates - verify leaf signed by root
-        verify_cert_signature(&leaf_x509, &root_x509)?;
-    }
-
-    // 2. Verify intermediate chain
-    for i in 0..intermediate_x509.len().saturating_sub(1) {
-        verify_cert_signature(&intermediate_x509[i], &intermediate_x509[i + 1])?;
-    }
-
-    // 3. Verify last intermediate signed by root (if intermediates exist)
-    if let Some(last_intermediate) = intermediate_x509.last() {
-        verify_cert_signature(last_intermediate, &root_x509)?;
-    }
+    // 1. Verify leaf signed by first intermediate, or by the root if there are none
+    let issuer = intermediates.first().unwrap_or(&root);
+    leaf.verify_issued_by(issuer, allowed_algorithms)?;
 
-    // 4. Verify root is self-signed
-    verify_cert_signature(&root_x509, &root_x509)?;
-
-    Ok(())
+    // 2-4. Verify the intermediate chain, the last intermediate against the root and the root itself
+    verify_trust_links(&intermediates, &root, allowed_algorithms, self_signed_root, context)
 }
 
 /// Verify TSA certificate Extended Key Usage (EKU)
@@ -158,16 +317,13 @@ pub fn verify_tsa_certificate_chain(
 /// # Returns
 ///
 /// Returns Ok(()) if the certificate has correct EKU for timestamping
-pub fn verify_tsa_certificate_eku(cert: &X509Certificate) -> Result<(), CertificateError> {
+pub fn verify_tsa_certificate_eku(cert: &ParsedCertificate) -> Result<(), CertificateError> {
     // TimeStamping EKU OID: 1.3.6.1.5.5.7.3.8
     const TIME_STAMPING_OID: &str = "1.3.6.1.5.5.7.3.8";
 
     // Find Extended Key Usage extension
     let eku_ext = cert
-        .tbs_certificate
-        .extensions()
-        .iter()
-        .find(|ext| ext.oid == x509_parser::oid_registry::OID_X509_EXT_EXTENDED_KEY_USAGE)
+        .extension(&x509_parser::oid_registry::OID_X509_EXT_EXTENDED_KEY_USAGE.to_id_string())
         .ok_or_else(|| {
             CertificateError::ChainVerificationFailed(
                 "TSA certificate missing Extended Key Usage extension".to_string(),
@@ -181,24 +337,10 @@ pub fn verify_tsa_certificate_eku(cert: &X509Certificate) -> Result<(), Certific
         ));
     }
 
-    // Parse Extended Key Usage extension
-    let _eku = match eku_ext.parsed_extension() {
-        x509_parser::extensions::ParsedExtension::ExtendedKeyUsage(eku) => eku,
-        _ => {
-            return Err(CertificateError::ChainVerificationFailed(
-                "Failed to parse Extended Key Usage extension".to_string(),
-            ))
-        }
-    };
-
-    // Verify it contains time stamping OID
-    // x509_parser ExtendedKeyUsage has fields: any (bool), server_auth (bool), client_auth (bool), etc.
-    // For time stamping, we need to check the raw extension value
-
     // Parse the extension value as a sequence of OIDs
     use ::asn1_rs::{FromDer, Sequence, Oid};
 
-    let (_, oid_seq) = Sequence::from_der(eku_ext.value)
+    let (_, oid_seq) = Sequence::from_der(&eku_ext.value)
         .map_err(|e| CertificateError::ChainVerificationFailed(format!("Failed to parse EKU value: {}", e)))?;
 
     // Parse OIDs from the sequence
@@ -0,0 +1,241 @@
+use ed25519_dalek::{Signature as Ed25519Signature, VerifyingKey as Ed25519VerifyingKey};
+use x509_parser::prelude::*;
+
+use crate::crypto::hash::{constant_time_eq, sha256};
+use crate::crypto::signature::PublicKey;
+use crate::error::{SignatureError, TransparencyError, VerificationError};
+use crate::parser::bundle::decode_base64;
+use crate::parser::checkpoint::{parse_checkpoint, LogCheckpoint};
+use crate::types::bundle::InclusionProof;
+
+/// id-Ed25519 (RFC 8410)
+pub const OID_ED25519: &str = "1.3.101.112";
+
+/// Signed-note signature type of Ed25519 keys
+const NOTE_TYPE_ED25519: u8 = 0x01;
+
+#[derive(Debug, Clone)]
+enum LogKey {
+    Ecdsa(PublicKey),
+    Ed25519(Ed25519VerifyingKey),
+}
+
+/// Public key of a transparency log, for verifying its checkpoint signatures
+///
+/// Rekor v1 signs checkpoints with ECDSA P-256 and Rekor v2 with Ed25519. The
+/// log ID is SHA-256 over the DER public key for ECDSA keys and over
+/// `name || '\n' || 0x01 || key` for Ed25519 keys (C2SP `signed-note`); its
+/// first four bytes are the key hint that selects the matching signature line.
+#[derive(Debug, Clone)]
+pub struct LogVerifier {
+    name: String,
+    key: LogKey,
+    log_id: [u8; 32],
+}
+
+impl LogVerifier {
+    /// Build a verifier from the log's DER SubjectPublicKeyInfo
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Signer name used in the checkpoint's signature lines
+    /// * `spki_der` - DER public key, e.g. `publicKey.rawBytes` of a TrustedRoot tlog
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key cannot be parsed or uses an unsupported algorithm.
+    pub fn from_spki_der(name: &str, spki_der: &[u8]) -> Result<Self, SignatureError> {
+        let (_, spki) = SubjectPublicKeyInfo::from_der(spki_der)
+            .map_err(|e| SignatureError::PublicKeyParse(e.to_string()))?;
+
+        let (key, log_id) = if spki.algorithm.algorithm.to_id_string() == OID_ED25519 {
+            let bytes: [u8; 32] = spki
+                .subject_public_key
+                .data
+                .as_ref()
+                .try_into()
+                .map_err(|_| SignatureError::PublicKeyParse("Ed25519 key is not 32 bytes".to_string()))?;
+            let key = Ed25519VerifyingKey::from_bytes(&bytes)
+                .map_err(|e| SignatureError::PublicKeyParse(e.to_string()))?;
+
+            let mut key_id = Vec::with_capacity(name.len() + 2 + bytes.len());
+            key_id.extend_from_slice(name.as_bytes());
+            key_id.push(b'\n');
+            key_id.push(NOTE_TYPE_ED25519);
+            key_id.extend_from_slice(&bytes);
+            (LogKey::Ed25519(key), sha256(&key_id))
+        } else {
+            (LogKey::Ecdsa(PublicKey::from_spki(&spki)?), sha256(spki_der))
+        };
+
+        Ok(LogVerifier {
+            name: name.to_string(),
+            key,
+            log_id,
+        })
+    }
+
+    /// Build a verifier from a base64 DER public key
+    pub fn from_base64(name: &str, spki_base64: &str) -> Result<Self, SignatureError> {
+        let der = decode_base64(spki_base64).map_err(|e| SignatureError::PublicKeyParse(e.to_string()))?;
+        Self::from_spki_der(name, &der)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Log ID, as in a TrustedRoot's `logId.keyId` and an entry's `logId`
+    pub fn log_id(&self) -> [u8; 32] {
+        self.log_id
+    }
+
+    pub fn key_hint(&self) -> [u8; 4] {
+        self.log_id[..4].try_into().unwrap()
+    }
+}
+
+/// Verify a checkpoint's signature from `verifier`
+///
+/// # Errors
+///
+/// Returns `MissingCheckpointSignature` if no signature line matches the
+/// verifier's name and key hint, and `CheckpointSignatureInvalid` if the
+/// matching signature does not verify over the checkpoint body.
+pub fn verify_checkpoint_signature(
+    checkpoint: &LogCheckpoint,
+    verifier: &LogVerifier,
+) -> Result<(), VerificationError> {
+    let signature = checkpoint
+        .signatures
+        .iter()
+        .find(|sig| sig.name == verifier.name && sig.key_hint == verifier.key_hint())
+        .ok_or_else(|| TransparencyError::MissingCheckpointSignature(verifier.name.clone()))?;
+
+    let body = checkpoint.body.as_bytes();
+    let valid = match &verifier.key {
+        LogKey::Ecdsa(key) => key.verify_signature(body, &signature.signature).is_ok(),
+        LogKey::Ed25519(key) => Ed25519Signature::from_slice(&signature.signature)
+            .map(|sig| key.verify_strict(body, &sig).is_ok())
+            .unwrap_or(false),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(TransparencyError::CheckpointSignatureInvalid.into())
+    }
+}
+
+/// Check that an inclusion proof's tree size and root hash are the ones its
+/// checkpoint commits to
+///
+/// # Returns
+///
+/// The parsed checkpoint, or `None` if the proof carries none
+///
+/// # Errors
+///
+/// Returns `InvalidCheckpoint` for a malformed checkpoint and
+/// `CheckpointMismatch` if it commits to a different tree.
+pub fn verify_checkpoint_matches_proof(
+    proof: &InclusionProof,
+) -> Result<Option<LogCheckpoint>, VerificationError> {
+    let Some(envelope) = &proof.checkpoint else {
+        return Ok(None);
+    };
+    let checkpoint = parse_checkpoint(&envelope.envelope)?;
+
+    if proof.tree_size.parse::<u64>().ok() != Some(checkpoint.tree_size) {
+        return Err(TransparencyError::CheckpointMismatch(format!(
+            "tree size {} != {}",
+            proof.tree_size, checkpoint.tree_size
+        ))
+        .into());
+    }
+    let root_hash = decode_base64(&proof.root_hash).map_err(|_| TransparencyError::InvalidEntryHash)?;
+    if !constant_time_eq(&root_hash, &checkpoint.root_hash) {
+        return Err(TransparencyError::CheckpointMismatch("root hash".to_string()).into());
+    }
+
+    Ok(Some(checkpoint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::bundle::parse_bundle_from_path;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const REKOR_V1_KEY: &str = "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE2G2Y+2tabdTV5BcGiBIx0a9fAFwrkBbmLSGtks4L3qX6yYY0zufBnhC8Ur/iy55GhWP/9A/bY2LhC30M9+RYtw==";
+
+    /// DER SubjectPublicKeyInfo prefix of an Ed25519 key
+    const ED25519_SPKI_PREFIX: [u8; 12] = [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+
+    fn sample_proof() -> InclusionProof {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        let bundle = parse_bundle_from_path(&path).unwrap();
+        let mut entries = bundle.verification_material.tlog_entries.unwrap();
+        entries.remove(0).inclusion_proof.unwrap()
+    }
+
+    #[test]
+    fn test_verify_rekor_v1_checkpoint() {
+        let checkpoint = verify_checkpoint_matches_proof(&sample_proof()).unwrap().unwrap();
+        let verifier = LogVerifier::from_base64("rekor.sigstore.dev", REKOR_V1_KEY).unwrap();
+        verify_checkpoint_signature(&checkpoint, &verifier).unwrap();
+
+        let mut tampered = checkpoint.clone();
+        tampered.body = tampered.body.replace("585383803", "585383804");
+        assert!(matches!(
+            verify_checkpoint_signature(&tampered, &verifier),
+            Err(VerificationError::Transparency(TransparencyError::CheckpointSignatureInvalid))
+        ));
+
+        let other = LogVerifier::from_base64("rekor.example.com", REKOR_V1_KEY).unwrap();
+        assert!(matches!(
+            verify_checkpoint_signature(&checkpoint, &other),
+            Err(VerificationError::Transparency(TransparencyError::MissingCheckpointSignature(_)))
+        ));
+    }
+
+    #[test]
+    fn test_checkpoint_proof_mismatch() {
+        let mut proof = sample_proof();
+        proof.tree_size = "585383804".to_string();
+        assert!(matches!(
+            verify_checkpoint_matches_proof(&proof),
+            Err(VerificationError::Transparency(TransparencyError::CheckpointMismatch(_)))
+        ));
+    }
+
+    #[test]
+    fn test_verify_ed25519_checkpoint() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let spki = [ED25519_SPKI_PREFIX.as_slice(), signing_key.verifying_key().as_bytes()].concat();
+        let verifier = LogVerifier::from_spki_der("log.example.com", &spki).unwrap();
+
+        let body = format!("log.example.com\n3\n{}\n", BASE64.encode([1; 32]));
+        let signature = signing_key.sign(body.as_bytes());
+        let note = format!(
+            "{}\n\u{2014} log.example.com {}\n",
+            body,
+            BASE64.encode([verifier.key_hint().as_slice(), &signature.to_bytes()].concat())
+        );
+
+        let checkpoint = parse_checkpoint(&note).unwrap();
+        assert_eq!(checkpoint.tree_size, 3);
+        verify_checkpoint_signature(&checkpoint, &verifier).unwrap();
+
+        let other = LogVerifier::from_spki_der(
+            "log.example.com",
+            &[ED25519_SPKI_PREFIX.as_slice(), SigningKey::from_bytes(&[8; 32]).verifying_key().as_bytes()].concat(),
+        )
+        .unwrap();
+        assert!(verify_checkpoint_signature(&checkpoint, &other).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Deadlines and cancellation for a verification
+//!
+//! A bundle with a pathological certificate chain or inclusion proof can keep
+//! a request thread busy long after its caller gave up. A
+//! [`VerificationContext`] attached with `AttestationVerifier::with_context`
+//! carries an optional deadline and [`CancellationToken`]; the verifier checks
+//! it between built-in steps and inside the loops over chain links and proof
+//! hashes, and stops with `VerificationError::Interrupted` once the deadline
+//! has passed or the token was cancelled.
+//!
+//! Checks are cooperative: a single signature verification is never
+//! interrupted, only the step after it. Without a deadline the clock is never
+//! read, so a default context is free in zkVM guests.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::Interrupted;
+
+/// Shared flag for cancelling verifications from another thread
+///
+/// Clones share the flag, so a request handler can keep one clone and cancel
+/// every verification holding another.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every verification using this token (or a clone of it)
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Deadline and cancellation token checked while verifying
+#[derive(Debug, Clone, Default)]
+pub struct VerificationContext {
+    deadline: Option<Instant>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl VerificationContext {
+    /// A context that never interrupts verification
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop verifying once `deadline` has passed
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop verifying once `timeout` has elapsed from now
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let context = VerificationContext::new().with_timeout(Duration::from_secs(2));
+    /// let result = verifier.clone().with_context(context).verify_bundle_bytes(&json, options, &trust, tsa)?;
+    /// ```
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Stop verifying once `token` is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Check whether verification may continue
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - Where verification is, reported in the error
+    ///
+    /// # Errors
+    ///
+    /// Returns `Interrupted::Cancelled` if the token was cancelled and
+    /// `Interrupted::DeadlineExceeded` if the deadline has passed.
+    pub fn check(&self, stage: &'static str) -> Result<(), Interrupted> {
+        if self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            return Err(Interrupted::Cancelled(stage));
+        }
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(Interrupted::DeadlineExceeded(stage));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_check() {
+        let token = CancellationToken::new();
+        let context = VerificationContext::new().with_cancellation(token.clone());
+        context.check("subject").unwrap();
+        token.cancel();
+        assert_eq!(context.check("subject"), Err(Interrupted::Cancelled("subject")));
+
+        let context = VerificationContext::new().with_deadline(Instant::now());
+        assert_eq!(context.check("merkle"), Err(Interrupted::DeadlineExceeded("merkle")));
+        VerificationContext::new().with_timeout(Duration::from_secs(60)).check("merkle").unwrap();
+    }
+}
@@ -1,6 +1,24 @@
+#[cfg(feature = "full")]
 pub mod certificate;
+#[cfg(feature = "full")]
+pub mod checkpoint;
+#[cfg(feature = "full")]
+pub mod context;
+#[cfg(feature = "full")]
+pub mod pool;
+#[cfg(feature = "revocation")]
+pub mod revocation;
+#[cfg(feature = "full")]
 pub mod rfc3161;
+#[cfg(feature = "full")]
+pub mod root_store;
 pub mod signature;
+#[cfg(feature = "full")]
+pub mod statement;
+#[cfg(feature = "full")]
+pub mod step;
 pub mod subject;
+#[cfg(feature = "full")]
 pub mod timestamp;
+#[cfg(feature = "full")]
 pub mod transparency;
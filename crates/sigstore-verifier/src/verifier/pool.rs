@@ -0,0 +1,208 @@
+//! Pool of parsed trust bundle certificates
+//!
+//! Every bundle is checked against the same few Fulcio and TSA intermediates
+//! and roots, and a service verifying many bundles would otherwise parse them
+//! (and decode their public keys) again for each one. A [`CertificatePool`]
+//! keeps the owned [`ParsedCertificate`] of each certificate, keyed by the
+//! SHA-256 of its DER, and evicts the least recently used entry once it is
+//! full. Share one pool between verifiers with
+//! `AttestationVerifier::with_certificate_pool`.
+//!
+//! Only parsing is cached: chain signatures are still verified for every
+//! bundle, and leaf certificates, which differ per bundle, are never pooled.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::crypto::hash::sha256;
+use crate::error::CertificateError;
+use crate::types::certificate::ParsedCertificate;
+
+/// Number of certificates a default pool holds
+///
+/// Comfortably more than the intermediates and roots of every Fulcio and TSA
+/// instance in the public-good and GitHub trusted roots.
+pub const DEFAULT_POOL_CAPACITY: usize = 64;
+
+/// Hit and miss counts of a pool, to size it and measure its effect
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Certificates currently held
+    pub len: usize,
+}
+
+/// Least recently used cache of parsed certificates, safe to share between threads
+#[derive(Debug)]
+pub struct CertificatePool {
+    capacity: usize,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    entries: HashMap<[u8; 32], PoolEntry>,
+    /// Incremented on every lookup; an entry's `last_used` is the tick it was last returned at
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug)]
+struct PoolEntry {
+    certificate: Arc<ParsedCertificate>,
+    last_used: u64,
+}
+
+impl Default for CertificatePool {
+    fn default() -> Self {
+        Self::new(DEFAULT_POOL_CAPACITY)
+    }
+}
+
+impl CertificatePool {
+    /// Create a pool holding at most `capacity` certificates (at least one)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(PoolState::default()),
+        }
+    }
+
+    /// Return the parsed form of `der`, parsing and pooling it on a miss
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the certificate cannot be parsed; nothing is pooled then.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let pool = CertificatePool::default();
+    /// let root = pool.get_or_parse(&trust_bundle.root)?;
+    /// println!("{} valid until {}", root.subject, root.not_after);
+    /// ```
+    pub fn get_or_parse(&self, der: &[u8]) -> Result<Arc<ParsedCertificate>, CertificateError> {
+        let fingerprint = sha256(der);
+        {
+            let mut state = self.lock();
+            state.tick += 1;
+            let tick = state.tick;
+            if let Some(entry) = state.entries.get_mut(&fingerprint) {
+                entry.last_used = tick;
+                let certificate = entry.certificate.clone();
+                state.hits += 1;
+                return Ok(certificate);
+            }
+            state.misses += 1;
+        }
+
+        // Parse without holding the lock; a concurrent miss on the same
+        // certificate parses it twice and keeps one
+        let certificate = Arc::new(ParsedCertificate::from_der(der)?);
+        let mut state = self.lock();
+        if !state.entries.contains_key(&fingerprint) && state.entries.len() >= self.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+        let last_used = state.tick;
+        state.entries.insert(
+            fingerprint,
+            PoolEntry {
+                certificate: certificate.clone(),
+                last_used,
+            },
+        );
+        Ok(certificate)
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        let state = self.lock();
+        PoolStats {
+            hits: state.hits,
+            misses: state.misses,
+            len: state.entries.len(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every pooled certificate, e.g. after the trust bundle was rotated
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        // The state is consistent after every statement, so a panic elsewhere cannot corrupt it
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::algorithm::SignatureAlgorithm;
+    use crate::crypto::signature::PublicKey;
+    use crate::parser::certificate::parse_pem_certificate;
+
+    fn sample_cert(name: &str) -> Vec<u8> {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/certs");
+        path.push(name);
+        parse_pem_certificate(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_parsed_certificate() {
+        let ca = ParsedCertificate::from_der(&sample_cert("revocation-ca.pem")).unwrap();
+        let intermediate = ParsedCertificate::from_der(&sample_cert("revocation-intermediate.pem")).unwrap();
+        assert_eq!(intermediate.issuer, ca.subject);
+        assert!(intermediate.not_before < intermediate.not_after);
+        assert!(PublicKey::from_spki_der(&ca.spki).is_ok());
+
+        intermediate.verify_issued_by(&ca, &[SignatureAlgorithm::EcdsaSha256]).unwrap();
+        assert!(ca.verify_issued_by(&intermediate, &[SignatureAlgorithm::EcdsaSha256]).is_err());
+        assert!(matches!(
+            intermediate.verify_issued_by(&ca, &[SignatureAlgorithm::EcdsaSha384]),
+            Err(CertificateError::DisallowedSignatureAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn test_pool_evicts_least_recently_used() {
+        let ca = sample_cert("revocation-ca.pem");
+        let intermediate = sample_cert("revocation-intermediate.pem");
+        let leaf = sample_cert("revocation-revoked.pem");
+        let pool = CertificatePool::new(2);
+
+        let first = pool.get_or_parse(&ca).unwrap();
+        pool.get_or_parse(&intermediate).unwrap();
+        assert!(Arc::ptr_eq(&first, &pool.get_or_parse(&ca).unwrap()));
+        // The intermediate is now the least recently used
+        pool.get_or_parse(&leaf).unwrap();
+        assert_eq!(pool.stats(), PoolStats { hits: 1, misses: 3, len: 2 });
+        pool.get_or_parse(&ca).unwrap();
+        pool.get_or_parse(&intermediate).unwrap();
+        assert_eq!(pool.stats(), PoolStats { hits: 2, misses: 4, len: 2 });
+
+        assert!(pool.get_or_parse(b"not a certificate").is_err());
+        assert_eq!(pool.len(), 2);
+        pool.clear();
+        assert!(pool.is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+//! Certificate revocation checking
+//!
+//! Fulcio leaf certificates are valid for minutes and never revoked, but Fulcio
+//! intermediates, TSA certificates and private CA chains live for years. A
+//! `CrlSet` holds the CRLs published for those chains — supplied up front for
+//! offline verification, or downloaded from the certificates' CRL distribution
+//! points with `sigstore_fetcher::crl` — and `check_chain_revocation` rejects a chain
+//! containing a revoked certificate.
+//!
+//! The check is best-effort: a certificate whose issuer has no CRL in the set
+//! passes. OCSP is not supported.
+
+use x509_parser::extensions::{DistributionPointName, GeneralName, ParsedExtension};
+use x509_parser::prelude::*;
+use x509_parser::revocation_list::CertificateRevocationList;
+
+use crate::crypto::algorithm::{AlgorithmLookup, SignatureAlgorithm};
+use crate::crypto::signature::PublicKey;
+use crate::error::CertificateError;
+use crate::parser::certificate::parse_der_certificate;
+use crate::types::certificate::CertificateChain;
+
+/// PEM label of a certificate revocation list
+const PEM_CRL_TAG: &str = "X509 CRL";
+
+/// Certificate revocation lists to check chains against
+#[derive(Debug, Clone, Default)]
+pub struct CrlSet {
+    crls: Vec<Vec<u8>>,
+}
+
+impl CrlSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a DER-encoded CRL
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidCrl` if `der` is not a CRL.
+    pub fn add_der(&mut self, der: Vec<u8>) -> Result<(), CertificateError> {
+        parse_crl(&der)?;
+        self.crls.push(der);
+        Ok(())
+    }
+
+    /// Add every `X509 CRL` block of a PEM document
+    ///
+    /// # Returns
+    ///
+    /// The number of CRLs added
+    pub fn add_pem(&mut self, pem: &str) -> Result<usize, CertificateError> {
+        let blocks = ::pem::parse_many(pem.as_bytes())
+            .map_err(|e| CertificateError::InvalidCrl(e.to_string()))?;
+        let mut added = 0;
+        for block in blocks.into_iter().filter(|block| block.tag() == PEM_CRL_TAG) {
+            self.add_der(block.into_contents())?;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Add a CRL in PEM or DER form
+    pub fn add_bytes(&mut self, bytes: &[u8]) -> Result<(), CertificateError> {
+        match std::str::from_utf8(bytes) {
+            Ok(text) if text.trim_start().starts_with("-----BEGIN") => self.add_pem(text).map(|_| ()),
+            _ => self.add_der(bytes.to_vec()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.crls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.crls.is_empty()
+    }
+}
+
+fn parse_crl(der: &[u8]) -> Result<CertificateRevocationList<'_>, CertificateError> {
+    CertificateRevocationList::from_der(der)
+        .map(|(_, crl)| crl)
+        .map_err(|e| CertificateError::InvalidCrl(e.to_string()))
+}
+
+/// URIs of a certificate's CRL distribution points
+pub fn crl_distribution_points(cert: &X509Certificate) -> Vec<String> {
+    let mut uris = Vec::new();
+    for ext in cert.tbs_certificate.extensions() {
+        let ParsedExtension::CRLDistributionPoints(points) = ext.parsed_extension() else {
+            continue;
+        };
+        for point in points.iter() {
+            if let Some(DistributionPointName::FullName(names)) = &point.distribution_point {
+                for name in names {
+                    if let GeneralName::URI(uri) = name {
+                        uris.push(uri.to_string());
+                    }
+                }
+            }
+        }
+    }
+    uris
+}
+
+/// Check that no certificate of a chain has been revoked by its issuer
+///
+/// Each certificate is checked against the CRLs in `crls` issued by the next
+/// certificate of the chain (the root is not checked). A CRL is trusted only
+/// if its signature verifies with that issuer's key.
+///
+/// # Errors
+///
+/// Returns `Revoked` if a certificate's serial number is listed, and
+/// `InvalidCrl` if a CRL naming the issuer is malformed or not signed by it.
+pub fn check_chain_revocation(chain: &CertificateChain, crls: &CrlSet) -> Result<(), CertificateError> {
+    if crls.is_empty() {
+        return Ok(());
+    }
+
+    let mut certs = Vec::with_capacity(chain.intermediates.len() + 2);
+    if !chain.leaf.is_empty() {
+        certs.push(parse_der_certificate(&chain.leaf)?);
+    }
+    for der in &chain.intermediates {
+        certs.push(parse_der_certificate(der)?);
+    }
+    certs.push(parse_der_certificate(&chain.root)?);
+
+    for pair in certs.windows(2) {
+        check_certificate_revocation(&pair[0], &pair[1], crls)?;
+    }
+    Ok(())
+}
+
+/// Check one certificate against the CRLs published by its issuer
+pub fn check_certificate_revocation(
+    cert: &X509Certificate,
+    issuer: &X509Certificate,
+    crls: &CrlSet,
+) -> Result<(), CertificateError> {
+    for der in &crls.crls {
+        let crl = parse_crl(der)?;
+        if crl.issuer() != issuer.subject() {
+            continue;
+        }
+        verify_crl_signature(&crl, issuer)?;
+
+        if crl
+            .iter_revoked_certificates()
+            .any(|revoked| revoked.raw_serial() == cert.raw_serial())
+        {
+            return Err(CertificateError::Revoked(format!(
+                "{} (serial {})",
+                cert.subject(),
+                cert.raw_serial_as_string()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn verify_crl_signature(crl: &CertificateRevocationList, issuer: &X509Certificate) -> Result<(), CertificateError> {
+    let oid = crl.signature_algorithm.algorithm.to_id_string();
+    if oid != crl.tbs_cert_list.signature.algorithm.to_id_string() {
+        return Err(CertificateError::InvalidCrl(
+            "signature algorithm does not match TBSCertList algorithm".to_string(),
+        ));
+    }
+    let algorithm = match SignatureAlgorithm::from_oid(&oid) {
+        AlgorithmLookup::Supported(algorithm) => algorithm,
+        AlgorithmLookup::Weak(name) => return Err(CertificateError::WeakSignatureAlgorithm(name.to_string())),
+        AlgorithmLookup::Unsupported => return Err(CertificateError::UnsupportedSignatureAlgorithm(oid)),
+    };
+
+    let public_key =
+        PublicKey::from_certificate(issuer).map_err(|e| CertificateError::InvalidCrl(e.to_string()))?;
+    public_key
+        .verify_prehash(
+            &algorithm.digest(crl.tbs_cert_list.as_ref()),
+            &crl.signature_value.data,
+        )
+        .map_err(|_| CertificateError::InvalidCrl(format!("CRL not signed by {}", issuer.subject())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::certificate::parse_pem_certificate;
+
+    fn sample(name: &str) -> String {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/certs");
+        path.push(name);
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    fn chain(intermediate: &str) -> CertificateChain {
+        CertificateChain {
+            leaf: Vec::new(),
+            intermediates: vec![parse_pem_certificate(&sample(intermediate)).unwrap()],
+            root: parse_pem_certificate(&sample("revocation-ca.pem")).unwrap(),
+        }
+    }
+
+    fn crls() -> CrlSet {
+        let mut crls = CrlSet::new();
+        assert_eq!(crls.add_pem(&sample("revocation-ca.crl")).unwrap(), 1);
+        crls
+    }
+
+    #[test]
+    fn test_check_chain_revocation() {
+        let crls = crls();
+        check_chain_revocation(&chain("revocation-intermediate.pem"), &crls).unwrap();
+        assert!(matches!(
+            check_chain_revocation(&chain("revocation-revoked.pem"), &crls),
+            Err(CertificateError::Revoked(_))
+        ));
+
+        // Without a CRL from the issuer the check passes
+        check_chain_revocation(&chain("revocation-revoked.pem"), &CrlSet::new()).unwrap();
+    }
+
+    #[test]
+    fn test_crl_signature() {
+        let crl_der = ::pem::parse(sample("revocation-ca.crl")).unwrap().into_contents();
+        let crl = parse_crl(&crl_der).unwrap();
+
+        let ca_der = parse_pem_certificate(&sample("revocation-ca.pem")).unwrap();
+        verify_crl_signature(&crl, &parse_der_certificate(&ca_der).unwrap()).unwrap();
+
+        let other_der = parse_pem_certificate(&sample("p256-ca.pem")).unwrap();
+        assert!(matches!(
+            verify_crl_signature(&crl, &parse_der_certificate(&other_der).unwrap()),
+            Err(CertificateError::InvalidCrl(_))
+        ));
+    }
+
+    #[test]
+    fn test_crl_distribution_points() {
+        let der = parse_pem_certificate(&sample("revocation-intermediate.pem")).unwrap();
+        let cert = parse_der_certificate(&der).unwrap();
+        assert_eq!(
+            crl_distribution_points(&cert),
+            vec!["http://crl.example.com/revocation-ca.crl".to_string()]
+        );
+        assert!(CrlSet::new().add_bytes(b"not a crl").is_err());
+    }
+}
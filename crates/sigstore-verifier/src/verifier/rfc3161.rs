@@ -1,11 +1,11 @@
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use chrono::{DateTime, Utc};
 
+use crate::crypto::hash::constant_time_eq;
 use crate::error::TimestampError;
-use crate::parser::certificate::parse_der_certificate;
 use crate::parser::rfc3161::{parse_rfc3161_timestamp, MessageImprint, Rfc3161Timestamp};
 use crate::types::bundle::SigstoreBundle;
-use crate::types::certificate::CertificateChain;
+use crate::types::certificate::{CertificateChain, ParsedCertificate};
+use crate::types::time::Timestamp;
 
 /// Verify RFC 3161 timestamp token
 ///
@@ -28,7 +28,7 @@ pub fn verify_rfc3161_timestamp(
     bundle: &SigstoreBundle,
     signature_b64: &str,
     tsa_chain: &CertificateChain,
-) -> Result<DateTime<Utc>, TimestampError> {
+) -> Result<Timestamp, TimestampError> {
     // Extract RFC 3161 timestamp from bundle
     let rfc3161_timestamps = bundle
         .verification_material
@@ -57,8 +57,27 @@ pub fn verify_rfc3161_timestamp(
         .decode(signature_b64)
         .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to decode signature base64: {}", e)))?;
 
+    verify_rfc3161_token(&parsed_timestamp, &signature_bytes, tsa_chain)
+}
+
+/// Verify an already parsed RFC 3161 timestamp token against decoded DSSE signature bytes
+///
+/// # Arguments
+///
+/// * `parsed_timestamp` - The parsed RFC 3161 timestamp token
+/// * `signature_bytes` - Decoded DSSE signature bytes
+/// * `tsa_chain` - TSA certificate chain for verification
+///
+/// # Returns
+///
+/// The signing time from the timestamp token on success
+pub fn verify_rfc3161_token(
+    parsed_timestamp: &Rfc3161Timestamp,
+    signature_bytes: &[u8],
+    tsa_chain: &CertificateChain,
+) -> Result<Timestamp, TimestampError> {
     // Verify message imprint matches the signature
-    verify_message_imprint(&signature_bytes, &parsed_timestamp.tst_info.message_imprint)?;
+    verify_message_imprint(signature_bytes, &parsed_timestamp.tst_info.message_imprint)?;
 
     // Verify PKCS#7 signature on the timestamp token
     // Use the signed_data we already extracted during parsing
@@ -79,7 +98,7 @@ fn verify_message_imprint(
     let computed_hash = message_imprint.hash_algorithm.hash(signature_bytes);
 
     // Compare with the expected hash from the timestamp
-    if computed_hash != message_imprint.hashed_message {
+    if !constant_time_eq(&computed_hash, &message_imprint.hashed_message) {
         return Err(TimestampError::MessageImprintMismatch {
             expected: hex::encode(&message_imprint.hashed_message),
             actual: hex::encode(&computed_hash),
@@ -98,7 +117,7 @@ fn verify_message_imprint(
 ///
 /// * `signed_data_bytes` - DER-encoded SignedData (already extracted from ContentInfo)
 /// * `tsa_chain` - TSA certificate chain for verification
-fn verify_pkcs7_signature(
+pub fn verify_pkcs7_signature(
     signed_data_bytes: &[u8],
     tsa_chain: &CertificateChain,
 ) -> Result<(), TimestampError> {
@@ -149,18 +168,14 @@ fn verify_pkcs7_signature(
     };
 
     // Parse the TSA leaf certificate from the chain
-    let tsa_leaf_cert = parse_der_certificate(&tsa_chain.leaf)
+    let tsa_leaf_cert = ParsedCertificate::from_der(&tsa_chain.leaf)
         .map_err(|e| TimestampError::InvalidTSACertificate(format!("Failed to parse TSA leaf certificate: {}", e)))?;
 
-    // Extract public key from certificate
-    let public_key_info = tsa_leaf_cert.public_key();
-    let public_key_der = public_key_info.raw;
-
     // Verify the signature using the digest algorithm and signature algorithm from signer info
     verify_cms_signature(
         &signed_content_bytes,
         &signer_info.signature.as_bytes(),
-        public_key_der,
+        &tsa_leaf_cert.spki,
         &signer_info.digest_alg,
         &signer_info.signature_algorithm,
     )?;
@@ -270,46 +285,22 @@ fn verify_ecdsa_signature(
     signature: &[u8],
     public_key_der: &[u8],
 ) -> Result<(), TimestampError> {
-    use ecdsa::signature::hazmat::PrehashVerifier;
-    use p256::ecdsa::{Signature as P256Signature, VerifyingKey as P256VerifyingKey};
-    use p256::pkcs8::DecodePublicKey as P256DecodePublicKey;
-    use p384::ecdsa::{Signature as P384Signature, VerifyingKey as P384VerifyingKey};
-
-    // Try P-256 first, then P-384 based on digest length
-    match digest.len() {
-        32 => {
-            // P-256 / SHA-256
-            let verifying_key = P256VerifyingKey::from_public_key_der(public_key_der)
-                .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse P-256 public key: {}", e)))?;
-
-            let sig = P256Signature::from_der(signature)
-                .map_err(|e| TimestampError::Rfc3161Parse(format!("Invalid ECDSA signature: {}", e)))?;
-
-            verifying_key
-                .verify_prehash(digest, &sig)
-                .map_err(|_| TimestampError::Rfc3161SignatureInvalid)?;
-        }
-        48 => {
-            // P-384 / SHA-384
-            let verifying_key = P384VerifyingKey::from_public_key_der(public_key_der)
-                .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse P-384 public key: {}", e)))?;
-
-            let sig = P384Signature::from_der(signature)
-                .map_err(|e| TimestampError::Rfc3161Parse(format!("Invalid ECDSA signature: {}", e)))?;
-
-            verifying_key
-                .verify_prehash(digest, &sig)
-                .map_err(|_| TimestampError::Rfc3161SignatureInvalid)?;
-        }
-        _ => {
-            return Err(TimestampError::UnsupportedHashAlgorithm(format!(
-                "Unexpected digest length: {}",
-                digest.len()
-            )))
-        }
-    }
-
-    Ok(())
+    use crate::crypto::signature::PublicKey;
+    use crate::error::SignatureError;
+    use x509_parser::prelude::{FromDer, SubjectPublicKeyInfo};
+
+    // Select the curve from the key's SPKI; the digest algorithm is independent of it
+    let (_, spki) = SubjectPublicKeyInfo::from_der(public_key_der)
+        .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse ECDSA public key: {}", e)))?;
+    let public_key = PublicKey::from_spki(&spki)
+        .map_err(|e| TimestampError::Rfc3161Parse(format!("Failed to parse ECDSA public key: {}", e)))?;
+
+    public_key
+        .verify_prehash(digest, signature)
+        .map_err(|e| match e {
+            SignatureError::InvalidSignature => TimestampError::Rfc3161SignatureInvalid,
+            e => TimestampError::Rfc3161Parse(format!("Invalid ECDSA signature: {}", e)),
+        })
 }
 
 /// Detect or validate TSA certificate chain
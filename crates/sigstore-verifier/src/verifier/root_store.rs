@@ -0,0 +1,154 @@
+//! Trust anchors for RFC 3161 timestamp chains
+//!
+//! Sigstore TSAs are trusted through the chain the caller takes from the
+//! trusted root. Timestamp authorities on the public web PKI (DigiCert,
+//! Sectigo, FreeTSA, ...) instead embed their signing certificate and
+//! intermediates in the token and chain up to a root of a browser or OS trust
+//! store. With a [`RootStore`] set through
+//! `AttestationVerifier::with_tsa_root_store`, the verifier completes the
+//! token's embedded certificates with the store root that issued the last of
+//! them, and only accepts a chain ending in a store root.
+//!
+//! With the `webpki` feature, [`RootStore::webpki`] holds the Mozilla root
+//! program certificates (`webpki-root-certs`) and [`RootStore::native`] the
+//! roots of the operating system's trust store.
+//!
+//! Web PKI chains are mostly RSA signed; verifying them requires adding the
+//! `RsaSha*` algorithms to `VerificationOptions::allowed_signature_algorithms`.
+
+use crate::crypto::algorithm::SignatureAlgorithm;
+use crate::error::CertificateError;
+use crate::types::certificate::{CertificateChain, ParsedCertificate};
+
+/// Root certificates TSA chains may end in
+#[derive(Debug, Clone, Default)]
+pub struct RootStore {
+    roots: Vec<ParsedCertificate>,
+}
+
+impl RootStore {
+    /// Store of the given DER root certificates
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a certificate cannot be parsed.
+    pub fn from_der<I, C>(roots: I) -> Result<Self, CertificateError>
+    where
+        I: IntoIterator<Item = C>,
+        C: AsRef<[u8]>,
+    {
+        let roots = roots
+            .into_iter()
+            .map(|der| ParsedCertificate::from_der(der.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { roots })
+    }
+
+    /// Mozilla's root program certificates, as shipped by `webpki-root-certs`
+    ///
+    /// Roots the verifier cannot parse or verify with (e.g. unsupported key
+    /// types) are left out.
+    #[cfg(feature = "webpki")]
+    pub fn webpki() -> Self {
+        Self::from_der_lossy(webpki_root_certs::TLS_SERVER_ROOT_CERTS.iter().map(|der| der.as_ref()))
+    }
+
+    /// Root certificates of the operating system's trust store
+    ///
+    /// Like [`webpki`](Self::webpki), unusable roots are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trust store cannot be read at all.
+    #[cfg(feature = "webpki")]
+    pub fn native() -> Result<Self, CertificateError> {
+        let result = rustls_native_certs::load_native_certs();
+        if result.certs.is_empty() {
+            if let Some(error) = result.errors.first() {
+                return Err(CertificateError::TrustBundleFetch(error.to_string()));
+            }
+        }
+        Ok(Self::from_der_lossy(result.certs.iter().map(|der| der.as_ref())))
+    }
+
+    #[cfg(feature = "webpki")]
+    fn from_der_lossy<'a>(roots: impl Iterator<Item = &'a [u8]>) -> Self {
+        Self {
+            roots: roots
+                .filter_map(|der| ParsedCertificate::from_der(der).ok())
+                .filter(|root| root.public_key().is_ok())
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Build a chain from a leaf-first certificate list and the store root that issued its last certificate
+    ///
+    /// A trailing certificate that is itself in the store is taken as the
+    /// root; any other self-signed certificate in the list is not trusted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownIssuer` if no store root issued the last certificate.
+    pub fn complete_chain(
+        &self,
+        mut certificates: Vec<Vec<u8>>,
+        allowed_algorithms: &[SignatureAlgorithm],
+    ) -> Result<CertificateChain, CertificateError> {
+        let last = certificates
+            .last()
+            .ok_or_else(|| CertificateError::ParseError("Certificate chain is empty".to_string()))?;
+        if certificates.len() > 1 && self.roots.iter().any(|root| root.der == *last) {
+            let root = certificates.pop().unwrap_or_default();
+            return Ok(chain_with_root(certificates, root));
+        }
+
+        let last = ParsedCertificate::from_der(last)?;
+        let root = self
+            .roots
+            .iter()
+            .filter(|root| root.subject == last.issuer)
+            .find(|root| last.verify_issued_by(root, allowed_algorithms).is_ok())
+            .ok_or_else(|| CertificateError::UnknownIssuer(last.issuer.clone()))?;
+        Ok(chain_with_root(certificates, root.der.clone()))
+    }
+}
+
+fn chain_with_root(mut certificates: Vec<Vec<u8>>, root: Vec<u8>) -> CertificateChain {
+    let leaf = certificates.remove(0);
+    CertificateChain {
+        leaf,
+        intermediates: certificates,
+        root,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_chain() {
+        let store = RootStore::default();
+        assert!(store.is_empty());
+        assert!(matches!(
+            store.complete_chain(Vec::new(), &[]),
+            Err(CertificateError::ParseError(_))
+        ));
+    }
+
+    #[cfg(feature = "webpki")]
+    #[test]
+    fn test_webpki_roots() {
+        // Only roots with unusual key types (e.g. P-521) are left out
+        let store = RootStore::webpki();
+        assert!(store.len() > 100);
+    }
+}
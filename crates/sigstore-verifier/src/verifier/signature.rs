@@ -1,12 +1,15 @@
 use crate::crypto::signature::PublicKey;
-use crate::error::VerificationError;
+use crate::error::{SignatureError, VerificationError};
 use crate::parser::bundle::decode_base64;
-use crate::parser::certificate::parse_der_certificate;
+#[cfg(feature = "full")]
+use crate::redaction::{commitment, REDACTABLE_PAYLOAD_TYPE};
 use crate::types::bundle::DsseEnvelope;
-use crate::types::certificate::CertificateChain;
+#[cfg(feature = "full")]
+use crate::types::certificate::{CertificateChain, ParsedCertificate};
 
 const DSSE_PREFIX: &[u8] = b"DSSEv1";
 
+#[cfg(feature = "full")]
 pub fn verify_dsse_signature(
     envelope: &DsseEnvelope,
     chain: &CertificateChain,
@@ -17,26 +20,120 @@ pub fn verify_dsse_signature(
         ));
     }
 
+    let payload = decode_base64(&envelope.payload)?;
+
+    // Verify the first signature (bundles typically have one signature)
+    let signature_bytes = decode_base64(&envelope.signatures[0].sig)?;
+
+    verify_dsse_signature_bytes(&envelope.payload_type, &payload, &signature_bytes, chain)
+}
+
+/// Verify a DSSE signature over an already decoded payload
+///
+/// # Arguments
+///
+/// * `payload_type` - The envelope payload type
+/// * `payload` - Decoded envelope payload
+/// * `signature_bytes` - Decoded signature
+/// * `chain` - Certificate chain whose leaf holds the signing key
+#[cfg(feature = "full")]
+pub fn verify_dsse_signature_bytes(
+    payload_type: &str,
+    payload: &[u8],
+    signature_bytes: &[u8],
+    chain: &CertificateChain,
+) -> Result<(), VerificationError> {
     // Parse leaf certificate to extract public key
-    let leaf_cert = parse_der_certificate(&chain.leaf)
+    let leaf_cert = ParsedCertificate::from_der(&chain.leaf)
         .map_err(|e| VerificationError::InvalidBundleFormat(e.to_string()))?;
-    let public_key = PublicKey::from_certificate(&leaf_cert)?;
+    let public_key = leaf_cert.public_key()?;
 
-    // DSSE signature is over: "DSSEv1" || len(payloadType) || payloadType || len(payload) || payload
-    let pae = create_pae(&envelope.payload_type, &envelope.payload)?;
+    let pae = signed_message(payload_type, payload)?;
 
-    // Verify the first signature (bundles typically have one signature)
-    let signature_bytes = decode_base64(&envelope.signatures[0].sig)?;
+    public_key
+        .verify_signature(&pae, signature_bytes)
+        .map_err(|e| e.into())
+}
+
+/// Verify a DSSE envelope signed with a pinned public key rather than a
+/// certificate
+///
+/// The envelope is accepted if any of its signatures verifies over the PAE of
+/// its payload.
+///
+/// # Errors
+///
+/// Returns `InvalidBundleFormat` if the envelope has no signatures and
+/// `InvalidSignature` if none of them verifies.
+pub fn verify_dsse_signature_with_key(
+    envelope: &DsseEnvelope,
+    public_key: &PublicKey,
+) -> Result<(), VerificationError> {
+    if envelope.signatures.is_empty() {
+        return Err(VerificationError::InvalidBundleFormat(
+            "No signatures in envelope".to_string(),
+        ));
+    }
+
+    let payload = decode_base64(&envelope.payload)?;
+    let pae = build_pae(&envelope.payload_type, &payload);
+    for signature in &envelope.signatures {
+        let signature_bytes = decode_base64(&signature.sig)?;
+        if public_key.verify_signature(&pae, &signature_bytes).is_ok() {
+            return Ok(());
+        }
+    }
+    Err(SignatureError::InvalidSignature.into())
+}
+
+/// Verify a signature made directly over a payload, as cosign signs simple
+/// signing payloads
+///
+/// # Arguments
+///
+/// * `payload` - The signed bytes
+/// * `signature_bytes` - DER-encoded signature
+/// * `chain` - Certificate chain whose leaf holds the signing key
+#[cfg(feature = "full")]
+pub fn verify_message_signature(
+    payload: &[u8],
+    signature_bytes: &[u8],
+    chain: &CertificateChain,
+) -> Result<(), VerificationError> {
+    let leaf_cert = ParsedCertificate::from_der(&chain.leaf)
+        .map_err(|e| VerificationError::InvalidBundleFormat(e.to_string()))?;
+    let public_key = leaf_cert.public_key()?;
 
     public_key
-        .verify_signature(&pae, &signature_bytes)
+        .verify_signature(payload, signature_bytes)
         .map_err(|e| e.into())
 }
 
+#[cfg(test)]
 fn create_pae(payload_type: &str, payload_b64: &str) -> Result<Vec<u8>, VerificationError> {
     // Decode base64 payload
     let payload = decode_base64(payload_b64)?;
+    Ok(build_pae(payload_type, &payload))
+}
+
+/// Bytes a DSSE signature over `payload` covers
+///
+/// The PAE of the payload, or of its commitment for
+/// [`REDACTABLE_PAYLOAD_TYPE`] payloads, whose predicate fields may be redacted.
+///
+/// # Errors
+///
+/// Returns `Redaction` if a redactable payload is not a JSON statement.
+#[cfg(feature = "full")]
+pub fn signed_message(payload_type: &str, payload: &[u8]) -> Result<Vec<u8>, VerificationError> {
+    if payload_type == REDACTABLE_PAYLOAD_TYPE {
+        return Ok(build_pae(payload_type, &commitment(payload)?));
+    }
+    Ok(build_pae(payload_type, payload))
+}
 
+/// DSSE pre-authentication encoding of a payload
+pub fn build_pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
     // PAE = "DSSEv1" || len(payloadType) || payloadType || len(payload) || payload
     let mut pae = Vec::new();
 
@@ -59,9 +156,9 @@ fn create_pae(payload_type: &str, payload_b64: &str) -> Result<Vec<u8>, Verifica
     pae.push(b' ');
 
     // Add payload
-    pae.extend_from_slice(&payload);
+    pae.extend_from_slice(payload);
 
-    Ok(pae)
+    pae
 }
 
 #[cfg(test)]
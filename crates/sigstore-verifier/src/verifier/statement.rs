@@ -0,0 +1,272 @@
+//! In-toto statement schema validation
+//!
+//! Parsing a statement with serde stops at the first problem and reports it
+//! without a path ("missing field `predicateType`"). With
+//! `VerificationOptions::strict_statement` the decoded payload is first checked
+//! against the in-toto Statement schema, and the SLSA v1 provenance schema for
+//! SLSA predicates, and every violation is reported with its path, e.g.
+//! `predicate.runDetails.builder.id missing`.
+
+use serde_json::{Map, Value};
+
+use crate::error::VerificationError;
+use crate::policy::attestations::SLSA_PROVENANCE_V1;
+use crate::types::dsse::{STATEMENT_V0_1, STATEMENT_V1};
+use crate::types::time::Timestamp;
+
+/// Accepted values of a statement's `_type`
+pub const STATEMENT_TYPES: &[&str] = &[STATEMENT_V1, STATEMENT_V0_1];
+
+/// Check a decoded DSSE payload against the statement schema
+///
+/// # Errors
+///
+/// Returns `BundleParse` if the payload is not JSON and `StatementSchema` with
+/// every violation found otherwise.
+pub fn validate_statement_json(payload: &[u8]) -> Result<(), VerificationError> {
+    let value: Value = serde_json::from_slice(payload)?;
+    let errors = statement_schema_errors(&value);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(VerificationError::StatementSchema(errors))
+    }
+}
+
+/// Every schema violation of a statement, as `<path> <problem>`
+pub fn statement_schema_errors(statement: &Value) -> Vec<String> {
+    let mut schema = Schema::default();
+    let Some(statement) = schema.object(Some(statement), "statement", true) else {
+        return schema.errors;
+    };
+
+    let statement_type = schema.string(statement.get("_type"), "_type", true);
+    if let Some(statement_type) = statement_type {
+        if !STATEMENT_TYPES.contains(&statement_type) {
+            schema.error("_type", &format!("is not a supported statement type ({})", statement_type));
+        }
+    }
+
+    if let Some(subjects) = schema.non_empty_array(statement.get("subject"), "subject") {
+        for (i, subject) in subjects.iter().enumerate() {
+            let path = format!("subject[{}]", i);
+            if let Some(subject) = schema.object(Some(subject), &path, true) {
+                schema.string(subject.get("name"), &format!("{}.name", path), true);
+                schema.digest_set(subject.get("digest"), &format!("{}.digest", path), true);
+            }
+        }
+    }
+
+    let predicate_type = schema.string(statement.get("predicateType"), "predicateType", true);
+    // v0.1 statements may omit the predicate
+    let predicate_required = statement_type != Some(STATEMENT_V0_1);
+    let predicate = schema.object(statement.get("predicate"), "predicate", predicate_required);
+    if let (Some(SLSA_PROVENANCE_V1), Some(predicate)) = (predicate_type, predicate) {
+        slsa_v1_errors(&mut schema, predicate);
+    }
+
+    schema.errors
+}
+
+/// Check a SLSA v1 provenance predicate
+fn slsa_v1_errors(schema: &mut Schema, predicate: &Map<String, Value>) {
+    let path = "predicate.buildDefinition";
+    if let Some(definition) = schema.object(predicate.get("buildDefinition"), path, true) {
+        schema.string(definition.get("buildType"), &format!("{}.buildType", path), true);
+        schema.object(definition.get("externalParameters"), &format!("{}.externalParameters", path), true);
+        schema.object(definition.get("internalParameters"), &format!("{}.internalParameters", path), false);
+        schema.resource_descriptors(definition.get("resolvedDependencies"), &format!("{}.resolvedDependencies", path));
+    }
+
+    let path = "predicate.runDetails";
+    if let Some(run_details) = schema.object(predicate.get("runDetails"), path, true) {
+        let builder_path = format!("{}.builder", path);
+        if let Some(builder) = schema.object(run_details.get("builder"), &builder_path, true) {
+            schema.string(builder.get("id"), &format!("{}.id", builder_path), true);
+            schema.object(builder.get("version"), &format!("{}.version", builder_path), false);
+            schema.resource_descriptors(
+                builder.get("builderDependencies"),
+                &format!("{}.builderDependencies", builder_path),
+            );
+        }
+
+        let metadata_path = format!("{}.metadata", path);
+        if let Some(metadata) = schema.object(run_details.get("metadata"), &metadata_path, false) {
+            schema.string(metadata.get("invocationId"), &format!("{}.invocationId", metadata_path), false);
+            for field in ["startedOn", "finishedOn"] {
+                let field_path = format!("{}.{}", metadata_path, field);
+                if let Some(time) = schema.string(metadata.get(field), &field_path, false) {
+                    if Timestamp::parse_rfc3339(time).is_err() {
+                        schema.error(&field_path, "is not an RFC 3339 timestamp");
+                    }
+                }
+            }
+        }
+
+        schema.resource_descriptors(run_details.get("byproducts"), &format!("{}.byproducts", path));
+    }
+}
+
+/// Accumulates violations while walking a JSON document
+#[derive(Default)]
+struct Schema {
+    errors: Vec<String>,
+}
+
+impl Schema {
+    fn error(&mut self, path: &str, problem: &str) {
+        self.errors.push(format!("{} {}", path, problem));
+    }
+
+    /// `value` as an object, recording a violation if it is missing (and
+    /// `required`) or not an object
+    fn object<'a>(&mut self, value: Option<&'a Value>, path: &str, required: bool) -> Option<&'a Map<String, Value>> {
+        match value {
+            Some(Value::Object(object)) => Some(object),
+            None if !required => None,
+            None => {
+                self.error(path, "missing");
+                None
+            }
+            Some(_) => {
+                self.error(path, "must be an object");
+                None
+            }
+        }
+    }
+
+    fn string<'a>(&mut self, value: Option<&'a Value>, path: &str, required: bool) -> Option<&'a str> {
+        match value {
+            Some(Value::String(string)) => Some(string),
+            None if !required => None,
+            None => {
+                self.error(path, "missing");
+                None
+            }
+            Some(_) => {
+                self.error(path, "must be a string");
+                None
+            }
+        }
+    }
+
+    fn non_empty_array<'a>(&mut self, value: Option<&'a Value>, path: &str) -> Option<&'a [Value]> {
+        match value {
+            Some(Value::Array(array)) if !array.is_empty() => Some(array),
+            None => {
+                self.error(path, "missing");
+                None
+            }
+            Some(_) => {
+                self.error(path, "must be a non-empty array");
+                None
+            }
+        }
+    }
+
+    /// A DigestSet: algorithm names mapped to lowercase hex digests
+    fn digest_set(&mut self, value: Option<&Value>, path: &str, required: bool) {
+        let Some(digests) = self.object(value, path, required) else {
+            return;
+        };
+        if required && digests.is_empty() {
+            self.error(path, "must contain at least one digest");
+        }
+        for (algorithm, digest) in digests {
+            let digest_path = format!("{}.{}", path, algorithm);
+            if let Some(digest) = self.string(Some(digest), &digest_path, true) {
+                let is_hex = !digest.is_empty()
+                    && digest.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'));
+                if !is_hex {
+                    self.error(&digest_path, "must be a lowercase hex digest");
+                }
+            }
+        }
+    }
+
+    /// An optional list of ResourceDescriptors, each naming a resource by at
+    /// least one of `uri`, `digest`, `name` or `content`
+    fn resource_descriptors(&mut self, value: Option<&Value>, path: &str) {
+        let Some(value) = value else {
+            return;
+        };
+        let Value::Array(descriptors) = value else {
+            self.error(path, "must be an array");
+            return;
+        };
+        for (i, descriptor) in descriptors.iter().enumerate() {
+            let path = format!("{}[{}]", path, i);
+            let Some(descriptor) = self.object(Some(descriptor), &path, true) else {
+                continue;
+            };
+            if !["uri", "digest", "name", "content"]
+                .iter()
+                .any(|field| descriptor.contains_key(*field))
+            {
+                self.error(&path, "must have a uri, digest, name or content");
+            }
+            self.string(descriptor.get("uri"), &format!("{}.uri", path), false);
+            self.string(descriptor.get("name"), &format!("{}.name", path), false);
+            self.digest_set(descriptor.get("digest"), &format!("{}.digest", path), false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::bundle::{decode_base64, parse_bundle_from_path};
+
+    fn sample_payload() -> Value {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples/actions-attest-build-provenance-attestation-13581567.sigstore.json");
+        let bundle = parse_bundle_from_path(&path).unwrap();
+        serde_json::from_slice(&decode_base64(&bundle.dsse_envelope.payload).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_sample_statement_is_valid() {
+        let statement = sample_payload();
+        assert_eq!(statement["predicateType"], SLSA_PROVENANCE_V1);
+        assert_eq!(statement_schema_errors(&statement), Vec::<String>::new());
+        validate_statement_json(statement.to_string().as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_statement_schema_errors() {
+        let mut statement = sample_payload();
+        statement["_type"] = Value::from("https://example.com/Statement");
+        statement["subject"][0]["digest"]["sha256"] = Value::from("ABC");
+        statement["predicate"]["runDetails"]["builder"]
+            .as_object_mut()
+            .unwrap()
+            .remove("id");
+        statement["predicate"]["buildDefinition"]["externalParameters"] = Value::from("none");
+
+        assert_eq!(
+            statement_schema_errors(&statement),
+            vec![
+                "_type is not a supported statement type (https://example.com/Statement)",
+                "subject[0].digest.sha256 must be a lowercase hex digest",
+                "predicate.buildDefinition.externalParameters must be an object",
+                "predicate.runDetails.builder.id missing",
+            ]
+        );
+
+        assert_eq!(
+            statement_schema_errors(&serde_json::json!({"subject": []})),
+            vec![
+                "_type missing",
+                "subject must be a non-empty array",
+                "predicateType missing",
+                "predicate missing",
+            ]
+        );
+        assert!(matches!(
+            validate_statement_json(b"{}"),
+            Err(VerificationError::StatementSchema(errors)) if errors.len() == 4
+        ));
+    }
+}
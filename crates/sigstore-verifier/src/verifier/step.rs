@@ -0,0 +1,84 @@
+//! Custom verification steps
+//!
+//! Verification runs a fixed sequence of built-in steps (see
+//! [`BuiltinStep`]). A `VerificationStep` attached with
+//! `AttestationVerifier::with_step` runs right after one of them and sees
+//! everything verified so far, so organization-specific checks, such as
+//! validating a private certificate extension, can reject a bundle before the
+//! remaining steps without forking the verifier. Steps attached after the same
+//! built-in step run in the order they were added, and all of them run before
+//! the policy hook.
+
+use crate::error::VerificationError;
+use crate::types::certificate::{CertificateChain, CertificateMetadata, OidcIdentity};
+use crate::types::digest::Digest;
+use crate::types::dsse::Statement;
+use crate::types::result::{TimestampProof, VerificationOptions};
+use crate::types::time::Timestamp;
+
+/// Built-in verification steps, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BuiltinStep {
+    /// Payload parsed, subject digest and predicate type checked
+    Subject,
+    /// Fulcio chain verified, pinned roots and certificate validity checked
+    CertificateChain,
+    /// DSSE (or simple signing) signature verified
+    Signature,
+    /// RFC 3161 timestamp or Rekor entry verified
+    Timestamp,
+    /// Expected issuer, subject and identity policy checked
+    Identity,
+}
+
+impl BuiltinStep {
+    /// Snake-case name of the step, e.g. `certificate_chain`
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinStep::Subject => "subject",
+            BuiltinStep::CertificateChain => "certificate_chain",
+            BuiltinStep::Signature => "signature",
+            BuiltinStep::Timestamp => "timestamp",
+            BuiltinStep::Identity => "identity",
+        }
+    }
+}
+
+/// What a custom step can inspect, filled in as verification progresses
+///
+/// Fields produced by a later built-in step are `None`.
+#[derive(Debug, Clone, Copy)]
+pub struct StepContext<'a> {
+    /// The built-in step that just completed
+    pub after: BuiltinStep,
+    pub options: &'a VerificationOptions,
+    pub statement: &'a Statement,
+    pub subject_digest: &'a Digest,
+    /// Signing time from the RFC 3161 timestamp or Rekor integrated time, from
+    /// `CertificateChain` on (its source is only verified at `Timestamp`)
+    pub signing_time: Option<Timestamp>,
+    /// Verified Fulcio chain, from `CertificateChain` on
+    pub certificate_chain: Option<&'a CertificateChain>,
+    pub leaf_certificate: Option<&'a CertificateMetadata>,
+    pub timestamp_proof: Option<&'a TimestampProof>,
+    pub identity: Option<&'a OidcIdentity>,
+}
+
+/// A check inserted into the verification pipeline
+pub trait VerificationStep: Send + Sync {
+    /// Accept or reject the bundle verified so far
+    ///
+    /// # Errors
+    ///
+    /// Any error rejects the bundle; `PolicyError::Denied` is the usual choice.
+    fn check(&self, context: &StepContext<'_>) -> Result<(), VerificationError>;
+}
+
+impl<F> VerificationStep for F
+where
+    F: Fn(&StepContext<'_>) -> Result<(), VerificationError> + Send + Sync,
+{
+    fn check(&self, context: &StepContext<'_>) -> Result<(), VerificationError> {
+        self(context)
+    }
+}
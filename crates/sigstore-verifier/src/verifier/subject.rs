@@ -1,33 +1,102 @@
-use crate::crypto::hash::hex_decode;
 use crate::error::VerificationError;
-use crate::types::dsse::Statement;
+use crate::policy::pattern::wildcard_match;
+use crate::types::digest::{Digest, DigestAlgorithm};
+use crate::types::dsse::{Statement, Subject};
 
+/// Extract the first subject's digest and check it against the expected digest
+///
+/// The subject digest is read for the expected digest's algorithm. When no
+/// digest is expected, SHA-256 is read, or the first of SHA-384, SHA-512 and
+/// `gitCommit` (source attestations) the subject carries if it has no SHA-256
+/// digest.
+///
+/// # Errors
+///
+/// Returns `InvalidBundleFormat` if the subject has no digest for the algorithm
+/// or it is malformed, `ZeroSubjectDigest` if it is all zeros, and
+/// `SubjectDigestMismatch` if it differs from `expected_digest`.
 pub fn verify_subject_digest(
     statement: &Statement,
-    expected_digest: Option<&[u8]>,
-) -> Result<Vec<u8>, VerificationError> {
-    // Get SHA256 digest from subject
-    let digest_hex = statement
-        .get_subject_digest("sha256")
-        .ok_or_else(|| {
-            VerificationError::InvalidBundleFormat("No sha256 digest in subject".to_string())
-        })?;
-
-    // Decode hex digest
-    let digest = hex_decode(&digest_hex)
-        .map_err(|e| VerificationError::InvalidBundleFormat(format!("Invalid digest hex: {}", e)))?;
+    expected_digest: Option<&Digest>,
+) -> Result<Digest, VerificationError> {
+    verify_subject(statement, expected_digest, None).map(|(digest, _)| digest)
+}
+
+/// Select the attested subject and check its digest
+///
+/// With a name pattern (`*` matches any sequence of characters), the subject
+/// is the first whose name matches it; otherwise it is the first subject. Its
+/// digest is then read and checked as by [`verify_subject_digest`].
+///
+/// # Returns
+///
+/// The subject's digest and name
+///
+/// # Errors
+///
+/// Returns `SubjectNameMismatch` if no subject name matches `name_pattern`,
+/// and the errors of [`verify_subject_digest`] for the selected subject.
+///
+/// # Example
+///
+/// ```ignore
+/// let (digest, name) = verify_subject(&statement, None, Some("pkg:npm/left-pad@*"))?;
+/// ```
+pub fn verify_subject(
+    statement: &Statement,
+    expected_digest: Option<&Digest>,
+    name_pattern: Option<&str>,
+) -> Result<(Digest, String), VerificationError> {
+    let subject = match name_pattern {
+        Some(pattern) => statement
+            .subject
+            .iter()
+            .find(|subject| wildcard_match(pattern, &subject.name))
+            .ok_or_else(|| VerificationError::SubjectNameMismatch {
+                pattern: pattern.to_string(),
+                names: statement.subject.iter().map(|subject| subject.name.clone()).collect(),
+            })?,
+        None => statement
+            .subject
+            .first()
+            .ok_or_else(|| VerificationError::InvalidBundleFormat("Statement has no subject".to_string()))?,
+    };
+    let digest = verify_digest_of(subject, expected_digest)?;
+    Ok((digest, subject.name.clone()))
+}
+
+fn verify_digest_of(subject: &Subject, expected_digest: Option<&Digest>) -> Result<Digest, VerificationError> {
+    let algorithm = match expected_digest {
+        Some(expected) => expected.algorithm,
+        None => [
+            DigestAlgorithm::Sha256,
+            DigestAlgorithm::Sha384,
+            DigestAlgorithm::Sha512,
+            DigestAlgorithm::GitCommit,
+        ]
+        .into_iter()
+        .find(|algorithm| subject.digest.contains_key(algorithm.name()))
+        .unwrap_or(DigestAlgorithm::Sha256),
+    };
+
+    let digest_hex = subject.digest.get(algorithm.name()).ok_or_else(|| {
+        VerificationError::InvalidBundleFormat(format!("No {} digest in subject", algorithm.name()))
+    })?;
+
+    let digest = Digest::from_hex(algorithm, digest_hex)
+        .map_err(|e| VerificationError::InvalidBundleFormat(format!("Invalid subject digest: {}", e)))?;
 
     // Check digest is not all zeros
-    if digest.iter().all(|&b| b == 0) {
+    if digest.bytes.iter().all(|&b| b == 0) {
         return Err(VerificationError::ZeroSubjectDigest);
     }
 
     // If expected digest provided, verify it matches
     if let Some(expected) = expected_digest {
-        if digest != expected {
+        if digest != *expected {
             return Err(VerificationError::SubjectDigestMismatch {
-                expected: hex::encode(expected),
-                actual: digest_hex,
+                expected: expected.to_string(),
+                actual: digest.to_string(),
             });
         }
     }
@@ -61,7 +130,7 @@ mod tests {
 
         let result = verify_subject_digest(&statement, None);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().len(), 32);
+        assert_eq!(result.unwrap().bytes.len(), 32);
     }
 
     #[test]
@@ -104,11 +173,50 @@ mod tests {
             predicate: serde_json::Value::Null,
         };
 
-        let expected = vec![0u8; 32];
+        let expected = Digest::sha256([0u8; 32]);
         let result = verify_subject_digest(&statement, Some(&expected));
         assert!(matches!(
             result,
             Err(VerificationError::SubjectDigestMismatch { .. })
         ));
     }
+
+    #[test]
+    fn test_verify_subject_name_pattern() {
+        let subject = |name: &str, hex: &str| Subject {
+            name: name.to_string(),
+            digest: HashMap::from([("sha256".to_string(), hex.to_string())]),
+        };
+        let statement = Statement {
+            statement_type: "test".to_string(),
+            subject: vec![
+                subject("myapp-1.2.0-darwin-arm64", &"11".repeat(32)),
+                subject("myapp-1.2.0-linux-amd64", &"22".repeat(32)),
+            ],
+            predicate_type: "test".to_string(),
+            predicate: serde_json::Value::Null,
+        };
+
+        let (digest, name) = verify_subject(&statement, None, Some("myapp-*-linux-amd64")).unwrap();
+        assert_eq!(name, "myapp-1.2.0-linux-amd64");
+        assert_eq!(digest, Digest::sha256([0x22; 32]));
+
+        // The expected digest is checked against the matched subject, not the first one
+        let expected = Digest::sha256([0x11; 32]);
+        assert!(matches!(
+            verify_subject(&statement, Some(&expected), Some("myapp-*-linux-amd64")),
+            Err(VerificationError::SubjectDigestMismatch { .. })
+        ));
+
+        match verify_subject(&statement, None, Some("myapp-*-windows-*")) {
+            Err(VerificationError::SubjectNameMismatch { pattern, names }) => {
+                assert_eq!(pattern, "myapp-*-windows-*");
+                assert_eq!(names.len(), 2);
+            }
+            other => panic!("Expected SubjectNameMismatch, got {:?}", other),
+        }
+
+        let (_, name) = verify_subject(&statement, None, None).unwrap();
+        assert_eq!(name, "myapp-1.2.0-darwin-arm64");
+    }
 }
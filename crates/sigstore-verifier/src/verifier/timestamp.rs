@@ -1,14 +1,19 @@
+use std::path::Path;
+
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use chrono::{DateTime, Utc};
-use x509_parser::prelude::*;
 
-use crate::error::{CertificateError, TimestampError};
-use crate::parser::rfc3161::parse_rfc3161_timestamp;
+use crate::crypto::hash::constant_time_eq;
+use crate::error::{CertificateError, TimestampError, VerificationError};
+use crate::parser::certificate::{certs_to_chain, parse_pem_certificates};
+use crate::parser::rfc3161::{extract_granted_timestamp_token, parse_rfc3161_timestamp, MessageImprint, Rfc3161Timestamp};
 use crate::parser::timestamp::parse_integrated_time;
 use crate::types::bundle::{SigstoreBundle, TransparencyLogEntry};
+use crate::types::certificate::{CertificateChain, ParsedCertificate};
+use crate::types::time::Timestamp;
+use crate::verifier::rfc3161::verify_pkcs7_signature;
 
 /// Extract signing time from RFC 3161 timestamp
-pub fn get_rfc3161_time(bundle: &SigstoreBundle) -> Result<DateTime<Utc>, TimestampError> {
+pub fn get_rfc3161_time(bundle: &SigstoreBundle) -> Result<Timestamp, TimestampError> {
     let rfc3161_timestamps = bundle
         .verification_material
         .timestamp_verification_data
@@ -34,30 +39,137 @@ pub fn get_rfc3161_time(bundle: &SigstoreBundle) -> Result<DateTime<Utc>, Timest
     Ok(parsed_timestamp.tst_info.gen_time)
 }
 
-pub fn get_integrated_time(entry: &TransparencyLogEntry) -> Result<DateTime<Utc>, TimestampError> {
+pub fn get_integrated_time(entry: &TransparencyLogEntry) -> Result<Timestamp, TimestampError> {
     parse_integrated_time(&entry.integrated_time)
 }
 
 pub fn verify_signing_time_in_validity(
-    signing_time: &DateTime<Utc>,
-    cert: &X509Certificate,
+    signing_time: &Timestamp,
+    cert: &ParsedCertificate,
 ) -> Result<(), CertificateError> {
-    let validity = cert.validity();
-    let not_before = validity.not_before.timestamp();
-    let not_after = validity.not_after.timestamp();
+    // Validity is at second precision; compare whole seconds
     let signing_timestamp = signing_time.timestamp();
 
-    if signing_timestamp < not_before || signing_timestamp > not_after {
+    if signing_timestamp < cert.not_before.timestamp() || signing_timestamp > cert.not_after.timestamp() {
         return Err(CertificateError::SigningTimeOutsideValidity {
             signing_time: signing_time.to_rfc3339(),
-            not_before: validity.not_before.to_string(),
-            not_after: validity.not_after.to_string(),
+            not_before: cert.not_before.to_rfc3339(),
+            not_after: cert.not_after.to_rfc3339(),
         });
     }
 
     Ok(())
 }
 
+/// TSA certificate chains and stored timestamp responses for offline RFC 3161 verification
+///
+/// Set through `AttestationVerifier::with_tsa_material`, it fills in what a
+/// verification would otherwise need from the network or the caller:
+///
+/// - a bundle without an RFC 3161 timestamp (or Rekor integrated time) is
+///   verified with the stored token whose message imprint covers its
+///   signature, as if the bundle carried it
+/// - when no TSA chain is passed to `verify_*`, the stored chain that signed
+///   the token is used
+///
+/// Either way the token and chain get the full RFC 3161 verification.
+///
+/// # Example
+///
+/// ```ignore
+/// let material = TsaMaterial::from_dir(Path::new("tsa"))?;
+/// let verifier = AttestationVerifier::new().with_tsa_material(Arc::new(material));
+/// let result = verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, None)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TsaMaterial {
+    chains: Vec<CertificateChain>,
+    tokens: Vec<(MessageImprint, Vec<u8>)>,
+}
+
+impl TsaMaterial {
+    /// Empty material
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the chains and responses of a directory, e.g. one filled by
+    /// `sigstore_fetcher::tsa::TsaCache`
+    ///
+    /// `*.pem` and `*.crt` files are TSA chains (leaf first, root last, as
+    /// served by a TSA's `certchain` endpoint) and `*.tsr` files DER-encoded
+    /// TimeStampResps. Other files are ignored; files are read in name order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read, or a chain or
+    /// response in it cannot be parsed.
+    pub fn from_dir(path: &Path) -> Result<Self, VerificationError> {
+        let mut paths = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
+
+        let mut material = Self::new();
+        for path in paths {
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some("pem" | "crt") => {
+                    let certs = parse_pem_certificates(&std::fs::read(&path)?)?;
+                    material.add_chain(certs_to_chain(certs)?);
+                }
+                Some("tsr") => material.add_response(&std::fs::read(&path)?)?,
+                _ => {}
+            }
+        }
+        Ok(material)
+    }
+
+    /// Add a TSA chain to pick from when the caller passes none
+    pub fn add_chain(&mut self, chain: CertificateChain) {
+        self.chains.push(chain);
+    }
+
+    /// Add a DER-encoded TimeStampResp to replay for the signature it covers
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response was not granted or its token cannot be parsed.
+    pub fn add_response(&mut self, response: &[u8]) -> Result<(), TimestampError> {
+        let token = extract_granted_timestamp_token(response)?;
+        let imprint = parse_rfc3161_timestamp(&token)?.tst_info.message_imprint;
+        self.tokens.push((imprint, token));
+        Ok(())
+    }
+
+    /// The TSA chains
+    pub fn chains(&self) -> &[CertificateChain] {
+        &self.chains
+    }
+
+    /// Number of stored timestamp tokens
+    pub fn token_count(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// DER-encoded token of the first stored response covering `signature`
+    pub fn token_for(&self, signature: &[u8]) -> Option<&[u8]> {
+        self.tokens
+            .iter()
+            .find(|(imprint, _)| constant_time_eq(&imprint.hash_algorithm.hash(signature), &imprint.hashed_message))
+            .map(|(_, token)| token.as_slice())
+    }
+
+    /// First chain whose leaf signed `timestamp`
+    ///
+    /// Only the token signature is checked here; the chain itself is verified
+    /// with the rest of the timestamp.
+    pub fn chain_for(&self, timestamp: &Rfc3161Timestamp) -> Option<&CertificateChain> {
+        self.chains
+            .iter()
+            .find(|chain| verify_pkcs7_signature(&timestamp.signed_data, chain).is_ok())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,14 +1,30 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Serialize;
+
+use crate::crypto::hash::{constant_time_eq, hash_reader};
+use crate::crypto::jcs::to_canonical_vec;
 use crate::crypto::merkle::{compute_leaf_hash, verify_inclusion_proof};
-use crate::error::{TransparencyError, VerificationError};
+use crate::crypto::signature::PublicKey;
+use crate::error::{DigestError, TransparencyError, VerificationError};
 use crate::parser::bundle::decode_base64;
-use crate::types::bundle::SigstoreBundle;
+use crate::parser::certificate::parse_pem_certificate;
+use crate::parser::rekor_body::{parse_entry_body, IntotoV002, RekorBody, SignatureV002};
+use crate::types::bundle::{SigstoreBundle, TransparencyLogEntry};
+use crate::types::digest::{Digest, DigestAlgorithm};
+use crate::types::limits::InputLimits;
+use crate::types::result::TransparencyLogKey;
+use crate::verifier::checkpoint::{verify_checkpoint_matches_proof, verify_checkpoint_signature, LogVerifier};
+use crate::verifier::context::VerificationContext;
 
 /// Verify the Rekor transparency log inclusion proof
 ///
 /// This verification ensures that:
 /// 1. The bundle contains transparency log entries
 /// 2. The inclusion proof is valid (Merkle tree verification)
-/// 3. The entry was properly logged in Rekor
+/// 3. The proof's checkpoint, if present, commits to the proven tree
+/// 4. The entry was properly logged in Rekor
+///
+/// Rekor v2 entries have no inclusion promise, so their inclusion proof is required.
 ///
 /// This provides protection against backdating attacks and ensures the signature
 /// was publicly logged in an immutable transparency log.
@@ -18,15 +34,53 @@ pub fn verify_transparency_log(bundle: &SigstoreBundle) -> Result<(), Verificati
         .tlog_entries
         .as_ref()
         .ok_or(TransparencyError::NoRekorEntry)?;
+    verify_tlog_entries(tlog_entries)
+}
+
+/// Verify the first of a set of transparency log entries, as [`verify_transparency_log`]
+/// does for a bundle's `tlogEntries`
+pub fn verify_tlog_entries(tlog_entries: &[TransparencyLogEntry]) -> Result<(), VerificationError> {
+    verify_tlog_entries_with_limits(tlog_entries, &InputLimits::default())
+}
+
+/// Verify transparency log entries as [`verify_tlog_entries`] does, with
+/// their number, proof length and base64 fields bounded by `limits`
+///
+/// # Errors
+///
+/// Returns `LimitExceeded` before decoding anything if an input exceeds
+/// `limits`, and the errors of [`verify_tlog_entries`].
+pub fn verify_tlog_entries_with_limits(
+    tlog_entries: &[TransparencyLogEntry],
+    limits: &InputLimits,
+) -> Result<(), VerificationError> {
+    verify_tlog_entries_in(tlog_entries, limits, &VerificationContext::default())
+}
 
+/// Verify transparency log entries within `limits`, checking `context` while
+/// decoding the inclusion proof
+pub(crate) fn verify_tlog_entries_in(
+    tlog_entries: &[TransparencyLogEntry],
+    limits: &InputLimits,
+    context: &VerificationContext,
+) -> Result<(), VerificationError> {
     if tlog_entries.is_empty() {
         return Err(TransparencyError::NoRekorEntry.into());
     }
+    limits.check_tlog_entries(tlog_entries.len())?;
 
     let entry = &tlog_entries[0];
 
+    if entry.is_rekor_v2() && entry.inclusion_proof.is_none() {
+        return Err(TransparencyError::MissingInclusionProof.into());
+    }
+
     // Verify inclusion proof if present
     if let Some(ref inclusion_proof) = entry.inclusion_proof {
+        limits.check_proof_hashes(inclusion_proof.hashes.len())?;
+        limits.check_base64_field("tlog entry body", &entry.canonicalized_body)?;
+        verify_checkpoint_matches_proof(inclusion_proof)?;
+
         let log_index = inclusion_proof
             .log_index
             .parse::<u64>()
@@ -37,11 +91,14 @@ pub fn verify_transparency_log(bundle: &SigstoreBundle) -> Result<(), Verificati
             .parse::<u64>()
             .map_err(|_| TransparencyError::InvalidEntryHash)?;
 
+        limits.check_base64_field("inclusion proof root hash", &inclusion_proof.root_hash)?;
         let root_hash = decode_base64(&inclusion_proof.root_hash)
             .map_err(|_| TransparencyError::InvalidEntryHash)?;
 
         let mut proof_hashes = Vec::new();
         for hash_b64 in &inclusion_proof.hashes {
+            context.check("merkle")?;
+            limits.check_base64_field("inclusion proof hash", hash_b64)?;
             let hash = decode_base64(hash_b64)
                 .map_err(|_| TransparencyError::InvalidEntryHash)?;
             proof_hashes.push(hash);
@@ -53,24 +110,392 @@ pub fn verify_transparency_log(bundle: &SigstoreBundle) -> Result<(), Verificati
         let leaf_hash = compute_leaf_hash(&canonicalized_body);
 
         // Verify inclusion proof
+        context.check("merkle")?;
         verify_inclusion_proof(&leaf_hash, log_index, tree_size, &proof_hashes, &root_hash)?;
     }
 
-    // Verify signed entry timestamp if present
+    // The signature itself needs the log key (see verify_signed_entry_timestamp)
     if let Some(ref inclusion_promise) = entry.inclusion_promise {
-        // TODO: Verify the signed entry timestamp signature
-        // This requires fetching the Rekor public key and verifying the signature
-        // For now, we just check it exists
-        let _set_bytes = decode_base64(&inclusion_promise.signed_entry_timestamp)
+        decode_base64(&inclusion_promise.signed_entry_timestamp)
             .map_err(|_| TransparencyError::SignedEntryTimestampInvalid)?;
     }
 
     Ok(())
 }
 
+/// Log ID of a transparency log entry
+///
+/// A log ID is the SHA-256 of the log's public key, carried base64-encoded in
+/// `logId.keyId`.
+///
+/// # Errors
+///
+/// Returns `MissingLogId` if the entry has no log ID and `InvalidLogId` if it
+/// is not a base64 SHA-256 digest.
+pub fn entry_log_id(entry: &TransparencyLogEntry) -> Result<Digest, VerificationError> {
+    let log_id = entry.log_id.as_ref().ok_or(TransparencyError::MissingLogId)?;
+    let bytes = decode_base64(&log_id.key_id)
+        .map_err(|e| TransparencyError::InvalidLogId(e.to_string()))?;
+    Digest::new(DigestAlgorithm::Sha256, bytes)
+        .map_err(|e| TransparencyError::InvalidLogId(e.to_string()).into())
+}
+
+/// Key of the log an entry comes from
+///
+/// # Errors
+///
+/// Returns `UnknownLog` if no key in `keys` has the entry's log ID, and the
+/// errors of [`entry_log_id`].
+pub fn entry_log_key<'a>(
+    entry: &TransparencyLogEntry,
+    keys: &'a [TransparencyLogKey],
+) -> Result<&'a TransparencyLogKey, VerificationError> {
+    let log_id = entry_log_id(entry)?;
+    for key in keys {
+        if key.log_id()? == log_id {
+            return Ok(key);
+        }
+    }
+    Err(TransparencyError::UnknownLog(log_id.to_string()).into())
+}
+
+/// Verify that a Rekor v2 entry's checkpoint is signed by its log
+///
+/// The inclusion proof only shows that the entry is in the tree the
+/// checkpoint names; the checkpoint signature shows that the log, and not the
+/// bundle's author, built that tree.
+///
+/// # Arguments
+///
+/// * `entry` - Entry whose inclusion proof carries the checkpoint
+/// * `keys` - Keys of the trusted logs
+///
+/// # Errors
+///
+/// Returns `UnknownLog` if the entry's log is not in `keys`,
+/// `MissingCheckpoint` if the proof has no checkpoint, the errors of
+/// [`verify_checkpoint_matches_proof`], and `CheckpointMismatch`,
+/// `MissingCheckpointSignature` or `CheckpointSignatureInvalid` if the
+/// checkpoint is not the log's.
+pub fn verify_entry_checkpoint(entry: &TransparencyLogEntry, keys: &[TransparencyLogKey]) -> Result<(), VerificationError> {
+    let key = entry_log_key(entry, keys)?;
+    let proof = entry.inclusion_proof.as_ref().ok_or(TransparencyError::MissingInclusionProof)?;
+    let checkpoint = verify_checkpoint_matches_proof(proof)?.ok_or(TransparencyError::MissingCheckpoint)?;
+    if checkpoint.origin != key.origin {
+        return Err(TransparencyError::CheckpointMismatch(format!(
+            "origin {} != {}",
+            checkpoint.origin, key.origin
+        ))
+        .into());
+    }
+    let verifier = LogVerifier::from_base64(&key.origin, &key.public_key)?;
+    verify_checkpoint_signature(&checkpoint, &verifier)
+}
+
+/// Verify that a Rekor v1 entry's signed entry timestamp is signed by its log
+///
+/// The SET is the log's promise that it integrated the entry at
+/// `integratedTime`, which the verifier takes as the signing time.
+///
+/// # Arguments
+///
+/// * `entry` - Entry whose inclusion promise carries the SET
+/// * `keys` - Keys of the trusted logs
+///
+/// # Errors
+///
+/// Returns `UnknownLog` if the entry's log is not in `keys`,
+/// `MissingInclusionPromise` if the entry has no promise, and
+/// `SignedEntryTimestampInvalid` if the SET does not verify.
+pub fn verify_signed_entry_timestamp(
+    entry: &TransparencyLogEntry,
+    keys: &[TransparencyLogKey],
+) -> Result<(), VerificationError> {
+    let key = entry_log_key(entry, keys)?;
+    let promise = entry.inclusion_promise.as_ref().ok_or(TransparencyError::MissingInclusionPromise)?;
+    let set = decode_base64(&promise.signed_entry_timestamp)
+        .map_err(|_| TransparencyError::SignedEntryTimestampInvalid)?;
+    let public_key = PublicKey::from_spki_der(&decode_base64(&key.public_key)?)?;
+    public_key
+        .verify_signature(&signed_entry_timestamp_payload(entry)?, &set)
+        .map_err(|_| TransparencyError::SignedEntryTimestampInvalid.into())
+}
+
+/// Check that every transparency log entry of a bundle comes from an allowed log
+///
+/// # Arguments
+///
+/// * `bundle` - Bundle whose `tlogEntries` are checked
+/// * `allowed` - Log IDs of the accepted logs
+///
+/// # Errors
+///
+/// Returns `UnknownLog` for an entry from a log not in `allowed`, and the
+/// errors of [`entry_log_id`] for an entry without a valid log ID.
+pub fn verify_log_ids(bundle: &SigstoreBundle, allowed: &[Digest]) -> Result<(), VerificationError> {
+    verify_entry_log_ids(bundle.verification_material.tlog_entries.as_deref().unwrap_or_default(), allowed)
+}
+
+/// Check that every entry comes from an allowed log, as [`verify_log_ids`]
+/// does for a bundle's `tlogEntries`
+pub fn verify_entry_log_ids(tlog_entries: &[TransparencyLogEntry], allowed: &[Digest]) -> Result<(), VerificationError> {
+    for entry in tlog_entries {
+        let log_id = entry_log_id(entry)?;
+        if !allowed.contains(&log_id) {
+            return Err(TransparencyError::UnknownLog(log_id.to_string()).into());
+        }
+    }
+    Ok(())
+}
+
+/// The decoded signed content and signing certificate of a bundle
+#[derive(Debug, Clone, Copy)]
+pub struct SignedEnvelope<'a> {
+    /// DSSE payload type, `None` for a signature over the payload itself
+    /// (a cosign simple signing payload)
+    pub payload_type: Option<&'a str>,
+    pub payload: &'a [u8],
+    /// Signature over the DSSE PAE, or over the payload itself
+    pub signature: &'a [u8],
+    /// DER-encoded signing certificate
+    pub certificate: &'a [u8],
+}
+
+/// Check that a log entry's body commits to the content the bundle carries
+///
+/// The inclusion proof covers the entry body, not the bundle, so a body for
+/// another attestation could otherwise be paired with the bundle. Every body
+/// must record the hash of the bundle's payload and the bundle's signature and
+/// certificate:
+///
+/// - `dsse` 0.0.1 and 0.0.2 and `intoto` 0.0.2 bodies for DSSE envelopes
+///   (`intoto` with [`verify_intoto_entry`])
+/// - `hashedrekord` 0.0.1 and 0.0.2 bodies for signatures over the payload
+///
+/// The `dsse` 0.0.1 envelope hash is not checked: Rekor computes it over the
+/// envelope JSON as the client uploaded it, which the bundle does not keep.
+/// A Rekor v2 body must come with the matching `kindVersion`, which selects
+/// the Rekor v2 verification path.
+///
+/// # Errors
+///
+/// Returns the errors of `parse_entry_body`, including `UnsupportedEntryKind`
+/// for any other kind or version, and `EntryBodyMismatch` if the body does not
+/// match `envelope`.
+pub fn verify_entry_body(entry: &TransparencyLogEntry, envelope: &SignedEnvelope<'_>) -> Result<(), VerificationError> {
+    let mismatch = |field: &str| TransparencyError::EntryBodyMismatch(field.to_string());
+    let body = parse_entry_body(entry)?;
+    // Without a kindVersion, a Rekor v2 body would be taken for a Rekor v1 entry
+    if matches!(body, RekorBody::DsseV002(_) | RekorBody::HashedRekordV002(_)) != entry.is_rekor_v2() {
+        return Err(TransparencyError::EntryKindMismatch {
+            declared: "no kindVersion".to_string(),
+            body: format!("{} {}", body.kind(), body.api_version()),
+        }
+        .into());
+    }
+    match (&body, envelope.payload_type) {
+        (RekorBody::IntotoV002(spec), Some(_)) => verify_intoto_entry(spec, envelope),
+        (RekorBody::DsseV001(spec), Some(_)) => {
+            let payload_hash = spec.payload_hash.as_ref().ok_or_else(|| mismatch("no payloadHash"))?;
+            check_hash(payload_hash.to_digest(), envelope.payload, "payloadHash")?;
+            let signed = spec.signatures.iter().any(|signature| {
+                decode_base64(&signature.signature).is_ok_and(|sig| constant_time_eq(&sig, envelope.signature))
+                    && pem_certificate_matches(&signature.verifier, envelope.certificate)
+            });
+            if !signed {
+                return Err(mismatch("signature").into());
+            }
+            Ok(())
+        }
+        (RekorBody::DsseV002(spec), Some(_)) => {
+            check_hash(spec.payload_hash.to_digest(), envelope.payload, "payloadHash")?;
+            if !spec.signatures.iter().any(|signature| signature_v002_matches(signature, envelope)) {
+                return Err(mismatch("signature").into());
+            }
+            Ok(())
+        }
+        (RekorBody::HashedRekordV001(spec), None) => {
+            let hash = spec.data.hash.as_ref().ok_or_else(|| mismatch("no data hash"))?;
+            check_hash(hash.to_digest(), envelope.payload, "data hash")?;
+            let signature = spec.signature.content.as_deref().ok_or_else(|| mismatch("no signature"))?;
+            if !decode_base64(signature).is_ok_and(|sig| constant_time_eq(&sig, envelope.signature)) {
+                return Err(mismatch("signature").into());
+            }
+            let public_key = spec
+                .signature
+                .public_key
+                .as_ref()
+                .and_then(|key| key.content.as_deref())
+                .ok_or_else(|| mismatch("no publicKey"))?;
+            if !pem_certificate_matches(public_key, envelope.certificate) {
+                return Err(mismatch("publicKey").into());
+            }
+            Ok(())
+        }
+        (RekorBody::HashedRekordV002(spec), None) => {
+            check_hash(spec.data.to_digest(), envelope.payload, "data hash")?;
+            if !signature_v002_matches(&spec.signature, envelope) {
+                return Err(mismatch("signature").into());
+            }
+            Ok(())
+        }
+        (body, Some(_)) => Err(mismatch(&format!("{} entry for a DSSE envelope", body.kind())).into()),
+        (body, None) => Err(mismatch(&format!("{} entry for a bundle without a DSSE envelope", body.kind())).into()),
+    }
+}
+
+/// Whether a Rekor v2 signature is the bundle's signature, made with the bundle's certificate
+fn signature_v002_matches(signature: &SignatureV002, envelope: &SignedEnvelope<'_>) -> bool {
+    decode_base64(&signature.content).is_ok_and(|sig| constant_time_eq(&sig, envelope.signature))
+        && signature
+            .verifier
+            .x509_certificate
+            .as_ref()
+            .and_then(|certificate| decode_base64(&certificate.raw_bytes).ok())
+            .is_some_and(|der| der == envelope.certificate)
+}
+
+/// Whether base64 PEM text holds the certificate `der`
+fn pem_certificate_matches(base64_pem: &str, der: &[u8]) -> bool {
+    decode_base64(base64_pem)
+        .ok()
+        .and_then(|pem| parse_pem_certificate(std::str::from_utf8(&pem).ok()?).ok())
+        .is_some_and(|certificate| certificate == der)
+}
+
+/// Envelope as Rekor serializes it to compute an `intoto` 0.0.2 envelope hash
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IntotoEnvelopeJson<'a> {
+    payload_type: &'a str,
+    payload: String,
+    signatures: Vec<IntotoSignatureJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct IntotoSignatureJson<'a> {
+    keyid: &'a str,
+    sig: String,
+}
+
+/// Check an `intoto` 0.0.2 body against a bundle's envelope
+///
+/// These bodies record the payload hash and the envelope hash separately,
+/// and store the envelope without its payload. The check requires:
+///
+/// - `payloadHash` to be the hash of the bundle's payload
+/// - the envelope's payload type, signature and public key to be the bundle's
+/// - `hash` to be the hash of the bundle's envelope, serialized as Rekor does
+///   (`{"payloadType", "payload", "signatures": [{"keyid", "sig"}]}`)
+///
+/// # Errors
+///
+/// Returns `EntryBodyMismatch` naming the first field that differs, or that
+/// the body lacks.
+pub fn verify_intoto_entry(spec: &IntotoV002, envelope: &SignedEnvelope<'_>) -> Result<(), VerificationError> {
+    let mismatch = |field: &str| TransparencyError::EntryBodyMismatch(field.to_string());
+    let content = &spec.content;
+
+    let payload_hash = content.payload_hash.as_ref().ok_or_else(|| mismatch("no payloadHash"))?;
+    check_hash(payload_hash.to_digest(), envelope.payload, "payloadHash")?;
+
+    let payload_type = envelope.payload_type.ok_or_else(|| mismatch("intoto entry for a bundle without a DSSE envelope"))?;
+    let entry_envelope = content.envelope.as_ref().ok_or_else(|| mismatch("no envelope"))?;
+    if entry_envelope.payload_type != payload_type {
+        return Err(mismatch("payloadType").into());
+    }
+    let [entry_signature] = entry_envelope.signatures.as_slice() else {
+        return Err(mismatch("signature count").into());
+    };
+    // Rekor stores the base64 signature base64 encoded again
+    let signature = decode_base64(&entry_signature.sig)
+        .ok()
+        .and_then(|sig| decode_base64(std::str::from_utf8(&sig).ok()?).ok())
+        .ok_or_else(|| mismatch("signature encoding"))?;
+    if !constant_time_eq(&signature, envelope.signature) {
+        return Err(mismatch("signature").into());
+    }
+    if !pem_certificate_matches(&entry_signature.public_key, envelope.certificate) {
+        return Err(mismatch("publicKey").into());
+    }
+
+    let envelope_hash = content.hash.as_ref().ok_or_else(|| mismatch("no envelope hash"))?;
+    let serialized = serde_json::to_vec(&IntotoEnvelopeJson {
+        payload_type,
+        payload: BASE64.encode(envelope.payload),
+        signatures: vec![IntotoSignatureJson {
+            keyid: entry_signature.keyid.as_deref().unwrap_or_default(),
+            sig: BASE64.encode(envelope.signature),
+        }],
+    })
+    .map_err(|e| TransparencyError::InvalidEntryBody(e.to_string()))?;
+    check_hash(envelope_hash.to_digest(), &serialized, "envelope hash")
+}
+
+/// Check that `data` hashes to a digest recorded in an entry body
+///
+/// # Errors
+///
+/// Returns `InvalidEntryBody` if the recorded digest cannot be parsed and
+/// `EntryBodyMismatch` naming `field` if it differs.
+fn check_hash(expected: Result<Digest, DigestError>, data: &[u8], field: &str) -> Result<(), VerificationError> {
+    let expected = expected.map_err(|e| TransparencyError::InvalidEntryBody(e.to_string()))?;
+    let actual = hash_reader(expected.algorithm, data)?;
+    if !constant_time_eq(&actual.bytes, &expected.bytes) {
+        return Err(TransparencyError::EntryBodyMismatch(field.to_string()).into());
+    }
+    Ok(())
+}
+
+/// Reconstruct the payload Rekor signs to produce a Signed Entry Timestamp
+///
+/// The SET is a signature over the RFC 8785 canonical JSON of the entry's
+/// `body`, `integratedTime`, `logID` (hex) and `logIndex`.
+///
+/// # Arguments
+///
+/// * `entry` - A transparency log entry with `logIndex` and `logId` set
+///
+/// # Returns
+///
+/// The canonical JSON bytes covered by the SET signature
+///
+/// # Errors
+///
+/// Returns `SignedEntryTimestampInvalid` if a required field is missing or malformed.
+pub fn signed_entry_timestamp_payload(
+    entry: &TransparencyLogEntry,
+) -> Result<Vec<u8>, VerificationError> {
+    let log_index = entry
+        .log_index
+        .as_deref()
+        .and_then(|index| index.parse::<u64>().ok())
+        .ok_or(TransparencyError::SignedEntryTimestampInvalid)?;
+    let integrated_time = entry
+        .integrated_time
+        .parse::<i64>()
+        .map_err(|_| TransparencyError::SignedEntryTimestampInvalid)?;
+    let log_id = entry
+        .log_id
+        .as_ref()
+        .ok_or(TransparencyError::SignedEntryTimestampInvalid)?;
+    let key_id = decode_base64(&log_id.key_id)
+        .map_err(|_| TransparencyError::SignedEntryTimestampInvalid)?;
+
+    let payload = serde_json::json!({
+        "body": entry.canonicalized_body,
+        "integratedTime": integrated_time,
+        "logID": hex::encode(key_id),
+        "logIndex": log_index,
+    });
+
+    to_canonical_vec(&payload).map_err(|_| TransparencyError::SignedEntryTimestampInvalid.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::rekor_body::RekorHash;
     use crate::types::bundle::{Certificate, DsseEnvelope, VerificationMaterial};
 
     #[test]
@@ -82,6 +507,7 @@ mod tests {
                 certificate: Certificate {
                     raw_bytes: String::new(),
                 },
+                x509_certificate_chain: None,
                 tlog_entries: None,
             },
             dsse_envelope: DsseEnvelope {
@@ -97,4 +523,346 @@ mod tests {
             Err(VerificationError::Transparency(TransparencyError::NoRekorEntry))
         ));
     }
+
+    fn parse_sample(name: &str) -> SigstoreBundle {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples");
+        path.push(name);
+        crate::parser::bundle::parse_bundle_from_path(&path).unwrap()
+    }
+
+    fn rekor_bundle() -> SigstoreBundle {
+        parse_sample("actions-attest-build-provenance-attestation-13532655.sigstore.json")
+    }
+
+    #[test]
+    fn test_verify_log_ids() {
+        let bundle = rekor_bundle();
+        let entry = &bundle.verification_material.tlog_entries.as_ref().unwrap()[0];
+        let log_id = entry_log_id(entry).unwrap();
+        assert_eq!(
+            log_id.to_hex(),
+            "c0d23d6ad406973f9559f3ba2d1ca01f84147d8ffc5b8445c224f98b9591801d"
+        );
+
+        verify_log_ids(&bundle, &[Digest::sha256([0; 32]), log_id]).unwrap();
+
+        let err = verify_log_ids(&bundle, &[Digest::sha256([0; 32])]).unwrap_err();
+        assert!(matches!(
+            err,
+            VerificationError::Transparency(TransparencyError::UnknownLog(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_signed_entry_timestamp() {
+        // rekor.sigstore.dev, whose log ID the sample's entry carries
+        let keys = [TransparencyLogKey::new(
+            "rekor.sigstore.dev",
+            "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE2G2Y+2tabdTV5BcGiBIx0a9fAFwrkBbmLSGtks4L3qX6yYY0zufBnhC8Ur/iy55GhWP/9A/bY2LhC30M9+RYtw==",
+        )];
+        let bundle = rekor_bundle();
+        let entry = &bundle.verification_material.tlog_entries.as_ref().unwrap()[0];
+        verify_signed_entry_timestamp(entry, &keys).unwrap();
+
+        let mut backdated = entry.clone();
+        backdated.integrated_time = "1".to_string();
+        assert!(matches!(
+            verify_signed_entry_timestamp(&backdated, &keys),
+            Err(VerificationError::Transparency(TransparencyError::SignedEntryTimestampInvalid))
+        ));
+
+        let mut unpromised = entry.clone();
+        unpromised.inclusion_promise = None;
+        assert!(matches!(
+            verify_signed_entry_timestamp(&unpromised, &keys),
+            Err(VerificationError::Transparency(TransparencyError::MissingInclusionPromise))
+        ));
+
+        let other_log = [TransparencyLogKey::new(
+            "log2025-1.rekor.sigstore.dev",
+            "MCowBQYDK2VwAyEAt8rlp1knGwjfbcXAYPYAkn0XiLz1x8O4t0YkEhie244=",
+        )];
+        assert!(matches!(
+            verify_signed_entry_timestamp(entry, &other_log),
+            Err(VerificationError::Transparency(TransparencyError::UnknownLog(_)))
+        ));
+    }
+
+    #[test]
+    fn test_verify_log_ids_missing_log_id() {
+        let mut bundle = rekor_bundle();
+        bundle.verification_material.tlog_entries.as_mut().unwrap()[0].log_id = None;
+        let err = verify_log_ids(&bundle, &[]).unwrap_err();
+        assert!(matches!(
+            err,
+            VerificationError::Transparency(TransparencyError::MissingLogId)
+        ));
+    }
+
+    #[test]
+    fn test_verify_tlog_entries_limits() {
+        let bundle = rekor_bundle();
+        let entries = bundle.verification_material.tlog_entries.as_ref().unwrap();
+        verify_tlog_entries_with_limits(entries, &InputLimits::default()).unwrap();
+
+        let limits = InputLimits { max_proof_hashes: 2, ..InputLimits::default() };
+        assert!(matches!(
+            verify_tlog_entries_with_limits(entries, &limits),
+            Err(VerificationError::LimitExceeded { limit: "inclusion proof hashes", max: 2, .. })
+        ));
+
+        let duplicated = vec![entries[0].clone(); 3];
+        let limits = InputLimits { max_tlog_entries: 2, ..InputLimits::default() };
+        assert!(matches!(
+            verify_tlog_entries_with_limits(&duplicated, &limits),
+            Err(VerificationError::LimitExceeded { limit: "tlog entries", max: 2, actual: 3 })
+        ));
+    }
+
+    /// An `intoto` 0.0.2 body for the sample bundle's envelope, as Rekor would store it
+    fn intoto_body(bundle: &SigstoreBundle) -> IntotoV002 {
+        use crate::parser::rekor_body::{IntotoContent, IntotoEnvelope, IntotoSignature};
+
+        let envelope = &bundle.dsse_envelope;
+        let payload = decode_base64(&envelope.payload).unwrap();
+        let pem = pem::encode(&pem::Pem::new(
+            "CERTIFICATE",
+            decode_base64(&bundle.verification_material.certificate.raw_bytes).unwrap(),
+        ));
+        let serialized = format!(
+            r#"{{"payloadType":"{}","payload":"{}","signatures":[{{"keyid":"","sig":"{}"}}]}}"#,
+            envelope.payload_type, envelope.payload, envelope.signatures[0].sig
+        );
+        let sha256 = |data: &[u8]| RekorHash {
+            algorithm: "sha256".to_string(),
+            value: hex::encode(crate::crypto::hash::sha256(data)),
+        };
+        IntotoV002 {
+            content: IntotoContent {
+                envelope: Some(IntotoEnvelope {
+                    payload: None,
+                    payload_type: envelope.payload_type.clone(),
+                    signatures: vec![IntotoSignature {
+                        keyid: None,
+                        sig: BASE64.encode(&envelope.signatures[0].sig),
+                        public_key: BASE64.encode(pem),
+                    }],
+                }),
+                hash: Some(sha256(serialized.as_bytes())),
+                payload_hash: Some(sha256(&payload)),
+            },
+        }
+    }
+
+    /// Decoded payload, signature and certificate of a bundle
+    fn decoded_fields(bundle: &SigstoreBundle) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        (
+            decode_base64(&bundle.dsse_envelope.payload).unwrap(),
+            decode_base64(&bundle.dsse_envelope.signatures[0].sig).unwrap(),
+            decode_base64(&bundle.verification_material.certificate.raw_bytes).unwrap(),
+        )
+    }
+
+    fn body_mismatch(result: Result<(), VerificationError>) -> String {
+        match result {
+            Err(VerificationError::Transparency(TransparencyError::EntryBodyMismatch(field))) => field,
+            other => panic!("expected a mismatch, got {:?}", other),
+        }
+    }
+
+    /// An entry of the sample bundle with its body replaced
+    fn entry_with_body(bundle: &SigstoreBundle, body: serde_json::Value) -> TransparencyLogEntry {
+        let mut entry = bundle.verification_material.tlog_entries.as_ref().unwrap()[0].clone();
+        entry.kind_version = Some(crate::types::bundle::KindVersion {
+            kind: body["kind"].as_str().unwrap().to_string(),
+            version: body["apiVersion"].as_str().unwrap().to_string(),
+        });
+        entry.canonicalized_body = BASE64.encode(serde_json::to_vec(&body).unwrap());
+        entry
+    }
+
+    #[test]
+    fn test_verify_intoto_entry() {
+        let bundle = rekor_bundle();
+        let (payload, signature, certificate) = decoded_fields(&bundle);
+        let envelope = SignedEnvelope {
+            payload_type: Some(&bundle.dsse_envelope.payload_type),
+            payload: &payload,
+            signature: &signature,
+            certificate: &certificate,
+        };
+        let spec = intoto_body(&bundle);
+        verify_intoto_entry(&spec, &envelope).unwrap();
+
+        let mismatch = |spec: &IntotoV002, envelope: &SignedEnvelope<'_>| body_mismatch(verify_intoto_entry(spec, envelope));
+
+        // The log committed to another attestation
+        let other_payload = b"{}".to_vec();
+        assert_eq!(mismatch(&spec, &SignedEnvelope { payload: &other_payload, ..envelope }), "payloadHash");
+        let other_signature = vec![0u8; signature.len()];
+        assert_eq!(mismatch(&spec, &SignedEnvelope { signature: &other_signature, ..envelope }), "signature");
+        assert_eq!(
+            mismatch(&spec, &SignedEnvelope { payload_type: Some("application/json"), ..envelope }),
+            "payloadType"
+        );
+
+        let mut tampered = spec.clone();
+        tampered.content.hash.as_mut().unwrap().value = hex::encode([0u8; 32]);
+        assert_eq!(mismatch(&tampered, &envelope), "envelope hash");
+
+        let mut tampered = spec.clone();
+        tampered.content.payload_hash = None;
+        assert_eq!(mismatch(&tampered, &envelope), "no payloadHash");
+    }
+
+    #[test]
+    fn test_verify_entry_body() {
+        let bundle = rekor_bundle();
+        let entry = &bundle.verification_material.tlog_entries.as_ref().unwrap()[0];
+        let (payload, signature, certificate) = decoded_fields(&bundle);
+        let envelope = SignedEnvelope {
+            payload_type: Some(&bundle.dsse_envelope.payload_type),
+            payload: &payload,
+            signature: &signature,
+            certificate: &certificate,
+        };
+
+        // The sample's dsse 0.0.1 body commits to its own envelope only
+        verify_entry_body(entry, &envelope).unwrap();
+        let other_payload = b"{}".to_vec();
+        assert_eq!(
+            body_mismatch(verify_entry_body(entry, &SignedEnvelope { payload: &other_payload, ..envelope })),
+            "payloadHash"
+        );
+        let other_signature = vec![0u8; signature.len()];
+        assert_eq!(
+            body_mismatch(verify_entry_body(entry, &SignedEnvelope { signature: &other_signature, ..envelope })),
+            "signature"
+        );
+        body_mismatch(verify_entry_body(entry, &SignedEnvelope { payload_type: None, ..envelope }));
+
+        let intoto = entry_with_body(
+            &bundle,
+            serde_json::json!({ "apiVersion": "0.0.2", "kind": "intoto", "spec": intoto_body(&bundle) }),
+        );
+        verify_entry_body(&intoto, &envelope).unwrap();
+        body_mismatch(verify_entry_body(&intoto, &SignedEnvelope { payload_type: None, ..envelope }));
+
+        let unknown = entry_with_body(&bundle, serde_json::json!({ "apiVersion": "0.0.1", "kind": "rekord", "spec": {} }));
+        assert!(matches!(
+            verify_entry_body(&unknown, &envelope),
+            Err(VerificationError::Transparency(TransparencyError::UnsupportedEntryKind { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_verify_entry_body_rekor_v2() {
+        let bundle = rekor_bundle();
+        let (payload, signature, certificate) = decoded_fields(&bundle);
+        let envelope = SignedEnvelope {
+            payload_type: Some(&bundle.dsse_envelope.payload_type),
+            payload: &payload,
+            signature: &signature,
+            certificate: &certificate,
+        };
+        let signature_v002 = serde_json::json!({
+            "content": BASE64.encode(&signature),
+            "verifier": {
+                "keyDetails": "PKIX_ECDSA_P256_SHA_256",
+                "x509Certificate": { "rawBytes": BASE64.encode(&certificate) },
+            },
+        });
+
+        let dsse = entry_with_body(
+            &bundle,
+            serde_json::json!({
+                "apiVersion": "0.0.2",
+                "kind": "dsse",
+                "spec": { "dsseV002": {
+                    "payloadHash": { "algorithm": "SHA2_256", "digest": BASE64.encode(crate::crypto::hash::sha256(&payload)) },
+                    "signatures": [signature_v002],
+                }},
+            }),
+        );
+        verify_entry_body(&dsse, &envelope).unwrap();
+
+        // A body from a different envelope
+        let other_payload = b"{}".to_vec();
+        assert_eq!(
+            body_mismatch(verify_entry_body(&dsse, &SignedEnvelope { payload: &other_payload, ..envelope })),
+            "payloadHash"
+        );
+        let (_, _, other_certificate) = decoded_fields(&parse_sample("actions-attest-build-provenance-attestation-13531551.sigstore.json"));
+        assert_eq!(
+            body_mismatch(verify_entry_body(&dsse, &SignedEnvelope { certificate: &other_certificate, ..envelope })),
+            "signature"
+        );
+
+        // hashedrekord bodies record a signature over the payload itself
+        let message = SignedEnvelope { payload_type: None, ..envelope };
+        let hashed_rekord = entry_with_body(
+            &bundle,
+            serde_json::json!({
+                "apiVersion": "0.0.2",
+                "kind": "hashedrekord",
+                "spec": { "hashedRekordV002": {
+                    "data": { "algorithm": "SHA2_256", "digest": BASE64.encode(crate::crypto::hash::sha256(&payload)) },
+                    "signature": signature_v002,
+                }},
+            }),
+        );
+        verify_entry_body(&hashed_rekord, &message).unwrap();
+        assert_eq!(
+            body_mismatch(verify_entry_body(&hashed_rekord, &SignedEnvelope { payload: &other_payload, ..message })),
+            "data hash"
+        );
+        body_mismatch(verify_entry_body(&hashed_rekord, &envelope));
+        body_mismatch(verify_entry_body(&dsse, &message));
+
+        // A Rekor v2 body without its kindVersion would pass as a Rekor v1 entry
+        let undeclared = TransparencyLogEntry { kind_version: None, ..dsse };
+        assert!(matches!(
+            verify_entry_body(&undeclared, &envelope),
+            Err(VerificationError::Transparency(TransparencyError::EntryKindMismatch { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_verify_entry_body_hashedrekord_v001() {
+        let bundle = rekor_bundle();
+        let (payload, signature, certificate) = decoded_fields(&bundle);
+        let message = SignedEnvelope {
+            payload_type: None,
+            payload: &payload,
+            signature: &signature,
+            certificate: &certificate,
+        };
+        let pem = pem::encode(&pem::Pem::new("CERTIFICATE", certificate.clone()));
+        let entry = entry_with_body(
+            &bundle,
+            serde_json::json!({
+                "apiVersion": "0.0.1",
+                "kind": "hashedrekord",
+                "spec": {
+                    "data": { "hash": { "algorithm": "sha256", "value": hex::encode(crate::crypto::hash::sha256(&payload)) } },
+                    "signature": { "content": BASE64.encode(&signature), "publicKey": { "content": BASE64.encode(pem) } },
+                },
+            }),
+        );
+        verify_entry_body(&entry, &message).unwrap();
+
+        let other_signature = vec![0u8; signature.len()];
+        assert_eq!(
+            body_mismatch(verify_entry_body(&entry, &SignedEnvelope { signature: &other_signature, ..message })),
+            "signature"
+        );
+        let other_certificate = vec![0u8; 4];
+        assert_eq!(
+            body_mismatch(verify_entry_body(&entry, &SignedEnvelope { certificate: &other_certificate, ..message })),
+            "publicKey"
+        );
+    }
 }
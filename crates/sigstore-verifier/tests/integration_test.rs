@@ -1,51 +1,8 @@
-use sigstore_verifier::types::certificate::FulcioInstance;
+use sigstore_verifier::types::certificate::{CertificateChain, FulcioInstance};
 use sigstore_verifier::types::result::VerificationOptions;
 use sigstore_verifier::AttestationVerifier;
 use std::path::PathBuf;
 
-#[test]
-#[cfg(feature = "fetcher")]
-fn test_verify_rekor_bundle() {
-    use sigstore_verifier::fetcher::trust_bundle::fetch_fulcio_trust_bundle;
-
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.pop();
-    path.pop();
-    path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
-
-    // Auto-detect Fulcio instance from bundle
-    let bundle_json = std::fs::read_to_string(&path).expect("Failed to read bundle");
-    let instance =
-        FulcioInstance::from_bundle_json(&bundle_json).expect("Failed to detect Fulcio instance");
-
-    // Fetch trust bundle for detected instance
-    // In production, the client should fetch and cache this
-    let trust_bundle = fetch_fulcio_trust_bundle(&instance).expect("Failed to fetch trust bundle");
-
-    let verifier = AttestationVerifier::new();
-    let options = VerificationOptions {
-        expected_digest: None,
-        expected_issuer: None,
-        expected_subject: None,
-    };
-
-    let result = verifier.verify_bundle(&path, options, &trust_bundle, None);
-    assert!(result.is_ok(), "Verification failed: {:?}", result.err());
-
-    if let Ok(verification_result) = result {
-        println!("Verification succeeded!");
-        println!(
-            "Leaf hash: {}",
-            hex::encode(&verification_result.certificate_hashes.leaf)
-        );
-        println!(
-            "Root hash: {}",
-            hex::encode(&verification_result.certificate_hashes.root)
-        );
-        println!("Signing time: {}", verification_result.signing_time);
-    }
-}
-
 #[test]
 fn test_verify_rfc3161_bundle() {
     use sigstore_verifier::fetcher::jsonl::parser::{
@@ -80,11 +37,7 @@ fn test_verify_rfc3161_bundle() {
     let timestamp = extract_bundle_timestamp(&bundle).expect("Failed to extract timestamp");
 
     let verifier = AttestationVerifier::new();
-    let options = VerificationOptions {
-        expected_digest: None,
-        expected_issuer: None,
-        expected_subject: None,
-    };
+    let options = VerificationOptions::default();
 
     let fulcio_chain = select_certificate_authority(&trust_roots, &fulcio_instance, timestamp)
         .expect("Failed to select certificate authority");
@@ -106,4 +59,1153 @@ fn test_verify_rfc3161_bundle() {
         );
         println!("Signing time: {}", verification_result.signing_time);
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_verify_rfc3161_bundle_with_github_actions_policy() {
+    use sigstore_verifier::policy::identity::IdentityPolicy;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+
+    let matching = VerificationOptions::default().with_identity_policy(IdentityPolicy::github_actions(
+        "actions",
+        "attest-build-provenance",
+        "prober.yml",
+        "refs/heads/main",
+    ));
+    let result = verifier.verify_bundle_bytes(&bundle_json, matching, &fulcio_chain, Some(&tsa_chain));
+    assert!(result.is_ok(), "Verification failed: {:?}", result.err());
+
+    let other_repo = VerificationOptions::default().with_identity_policy(IdentityPolicy::github_actions(
+        "actions",
+        "other-repo",
+        "prober.yml",
+        "refs/heads/main",
+    ));
+    let result = verifier.verify_bundle_bytes(&bundle_json, other_repo, &fulcio_chain, Some(&tsa_chain));
+    assert!(result.is_err(), "Policy for another repository should be rejected");
+
+    // The GitHub Fulcio intermediate signs leaves with ecdsa-with-SHA384
+    let sha256_only = VerificationOptions::default().with_allowed_signature_algorithms(vec![
+        sigstore_verifier::crypto::algorithm::SignatureAlgorithm::EcdsaSha256,
+    ]);
+    let result = verifier.verify_bundle_bytes(&bundle_json, sha256_only, &fulcio_chain, Some(&tsa_chain));
+    assert!(
+        matches!(
+            result,
+            Err(sigstore_verifier::error::VerificationError::Certificate(
+                sigstore_verifier::error::CertificateError::DisallowedSignatureAlgorithm(_)
+            ))
+        ),
+        "Disallowed certificate signature algorithm should be rejected: {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_signed_entry_timestamp_payload() {
+    use base64::prelude::*;
+    use sigstore_verifier::crypto::signature::PublicKey;
+    use sigstore_verifier::fetcher::jsonl::parser::load_trusted_root_from_jsonl;
+    use sigstore_verifier::parser::bundle::parse_bundle_from_path;
+    use sigstore_verifier::verifier::transparency::signed_entry_timestamp_payload;
+    use x509_parser::prelude::{FromDer, SubjectPublicKeyInfo};
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    let mut trusted_root_path = path.clone();
+    path.push("samples/actions-attest-build-provenance-attestation-13532655.sigstore.json");
+    trusted_root_path.push("samples/trusted_root.jsonl");
+
+    let bundle = parse_bundle_from_path(&path).expect("Failed to parse bundle");
+    let entry = &bundle.verification_material.tlog_entries.as_ref().unwrap()[0];
+    let payload = signed_entry_timestamp_payload(entry).expect("Failed to build SET payload");
+
+    // The canonical payload must verify against the Rekor key that signed the SET
+    let jsonl = std::fs::read_to_string(&trusted_root_path).expect("Failed to read trusted root");
+    let trusted_roots = load_trusted_root_from_jsonl(&jsonl).expect("Failed to parse trusted root");
+    let key_id = &entry.log_id.as_ref().unwrap().key_id;
+    let rekor_key = trusted_roots
+        .iter()
+        .flat_map(|root| root.tlogs.iter())
+        .find(|tlog| tlog.log_id.as_ref().is_some_and(|id| &id.key_id == key_id))
+        .and_then(|tlog| tlog.public_key.as_ref())
+        .and_then(|key| key.raw_bytes.as_ref())
+        .expect("Rekor key not found in trusted root");
+    let spki_der = BASE64_STANDARD.decode(rekor_key).unwrap();
+    let (_, spki) = SubjectPublicKeyInfo::from_der(&spki_der).unwrap();
+    let key = PublicKey::from_spki(&spki).unwrap();
+
+    let set = BASE64_STANDARD
+        .decode(&entry.inclusion_promise.as_ref().unwrap().signed_entry_timestamp)
+        .unwrap();
+    key.verify_signature(&payload, &set)
+        .expect("SET should verify over the canonical payload");
+
+    let mut tampered = entry.clone();
+    tampered.integrated_time = "0".to_string();
+    let tampered_payload = signed_entry_timestamp_payload(&tampered).unwrap();
+    assert!(key.verify_signature(&tampered_payload, &set).is_err());
+}
+
+/// Read the RFC 3161 sample bundle and select its Fulcio and TSA chains from trusted_root.jsonl
+fn load_rfc3161_sample() -> (Vec<u8>, CertificateChain, CertificateChain) {
+    use sigstore_verifier::fetcher::jsonl::parser::{
+        load_trusted_root_from_jsonl, select_certificate_authority, select_timestamp_authority,
+    };
+    use sigstore_verifier::parser::bundle::{extract_bundle_timestamp, parse_bundle_from_bytes};
+
+    let mut samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    samples.pop();
+    samples.pop();
+    samples.push("samples");
+
+    let bundle_json =
+        std::fs::read(samples.join("actions-attest-build-provenance-attestation-13581567.sigstore.json"))
+            .expect("Failed to read bundle");
+    let trusted_root_content = std::fs::read_to_string(samples.join("trusted_root.jsonl"))
+        .expect("Failed to read trusted root file");
+    let trust_roots = load_trusted_root_from_jsonl(&trusted_root_content)
+        .expect("Failed to parse trusted root JSONL");
+
+    let fulcio_instance = FulcioInstance::from_bundle_json(std::str::from_utf8(&bundle_json).unwrap())
+        .expect("Failed to detect Fulcio instance");
+    let bundle = parse_bundle_from_bytes(&bundle_json).expect("Failed to parse bundle");
+    let timestamp = extract_bundle_timestamp(&bundle).expect("Failed to extract timestamp");
+    let fulcio_chain = select_certificate_authority(&trust_roots, &fulcio_instance, timestamp)
+        .expect("Failed to select certificate authority");
+    let tsa_chain = select_timestamp_authority(&trust_roots, &fulcio_instance, timestamp)
+        .expect("Failed to select timestamp authority");
+
+    (bundle_json, fulcio_chain, tsa_chain)
+}
+
+#[test]
+fn test_verify_with_audit_sink() {
+    use sigstore_verifier::audit::{verify_chain, AuditSink, JsonlAuditSink};
+    use sigstore_verifier::types::digest::Digest;
+    use std::sync::Arc;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+
+    let sink = Arc::new(JsonlAuditSink::hash_chained(Vec::new()));
+    let verifier = AttestationVerifier::new().with_audit_sink(sink.clone() as Arc<dyn AuditSink>);
+
+    verifier
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification failed");
+    let mismatch = VerificationOptions::default()
+        .with_expected_digest(Digest::sha256([0x11; 32]))
+        .unwrap();
+    assert!(verifier
+        .verify_bundle_bytes(&bundle_json, mismatch, &fulcio_chain, Some(&tsa_chain))
+        .is_err());
+
+    drop(verifier);
+    let log = Arc::try_unwrap(sink).ok().unwrap().into_inner();
+    let head = verify_chain(log.as_slice()).expect("Audit chain should verify");
+    assert_eq!(head.records, 2);
+
+    let lines: Vec<serde_json::Value> = log
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).unwrap())
+        .collect();
+    assert_eq!(lines[0]["event"]["outcome"]["decision"], "verified");
+    assert_eq!(lines[1]["event"]["outcome"]["decision"], "rejected");
+    assert_eq!(lines[1]["event"]["outcome"]["code"], "subject_digest_mismatch");
+    assert_eq!(lines[0]["event"]["bundle_digest"], lines[1]["event"]["bundle_digest"]);
+}
+
+#[test]
+fn test_required_attestations() {
+    use sigstore_verifier::policy::attestations::{
+        RequiredAttestations, SLSA_PROVENANCE_V1, SPDX_DOCUMENT,
+    };
+    use sigstore_verifier::types::digest::Digest;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let bundles = vec![("provenance.json".to_string(), bundle_json)];
+    let subject: Digest = "sha256:658913cfebe8a49165264e2b5e54ad99b3bdbfbc8cd281b3cfaa949a21588f18"
+        .parse()
+        .unwrap();
+    let verifier = AttestationVerifier::new();
+    let options = VerificationOptions::default();
+
+    let provenance_only = RequiredAttestations::new([SLSA_PROVENANCE_V1]).evaluate(
+        &verifier,
+        &subject,
+        &bundles,
+        &options,
+        &fulcio_chain,
+        Some(&tsa_chain),
+    );
+    assert!(provenance_only.satisfied, "{}", provenance_only.message());
+
+    // The provenance alone does not satisfy a release gate that also wants an SBOM
+    let with_sbom = RequiredAttestations::new([SLSA_PROVENANCE_V1, SPDX_DOCUMENT]).evaluate(
+        &verifier,
+        &subject,
+        &bundles,
+        &options,
+        &fulcio_chain,
+        Some(&tsa_chain),
+    );
+    assert!(!with_sbom.satisfied);
+    assert_eq!(with_sbom.missing, vec![SPDX_DOCUMENT]);
+
+    // A bundle for a different subject fails the set and leaves its type uncovered
+    let other_subject = RequiredAttestations::new([SLSA_PROVENANCE_V1]).evaluate(
+        &verifier,
+        &Digest::sha256([0x11; 32]),
+        &bundles,
+        &options,
+        &fulcio_chain,
+        Some(&tsa_chain),
+    );
+    assert!(!other_subject.satisfied);
+    assert_eq!(other_subject.missing, vec![SLSA_PROVENANCE_V1]);
+}
+
+#[test]
+fn test_cross_bundle_consistency() {
+    use sigstore_verifier::policy::consistency::{
+        bundle_fulcio_extensions, check_consistency, ConsistencyField, DEFAULT_CONSISTENCY_FIELDS,
+    };
+
+    let mut samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    samples.pop();
+    samples.pop();
+    samples.push("samples");
+
+    let signer = |run: &str| {
+        let name = format!("actions-attest-build-provenance-attestation-{}.sigstore.json", run);
+        let bundle_json = std::fs::read(samples.join(&name)).expect("Failed to read bundle");
+        (name, bundle_fulcio_extensions(&bundle_json).expect("Failed to read Fulcio extensions"))
+    };
+
+    // Two prober runs at the same commit agree on builder and source revision
+    let same_commit = vec![signer("13531551"), signer("13532655")];
+    assert!(check_consistency(DEFAULT_CONSISTENCY_FIELDS, &same_commit).is_empty());
+
+    // A run at another commit shares the builder but not the source revision
+    let other_commit = vec![signer("13531551"), signer("13581567")];
+    let inconsistencies = check_consistency(DEFAULT_CONSISTENCY_FIELDS, &other_commit);
+    assert_eq!(inconsistencies.len(), 1);
+    assert_eq!(inconsistencies[0].field, ConsistencyField::SourceRevision);
+}
+
+#[test]
+#[cfg(feature = "policy-file")]
+fn test_verify_with_policy_file() {
+    use sigstore_verifier::error::{PolicyError, VerificationError};
+    use sigstore_verifier::types::digest::Digest;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let pinned_root = Digest::sha256(sigstore_verifier::crypto::hash::sha256(&fulcio_chain.root));
+
+    let policy = format!(
+        r#"
+version: 1
+identity:
+  issuers: ["https://token.actions.githubusercontent.com"]
+  repository: "https://github.com/actions/attest-build-provenance"
+predicate_types: ["https://slsa.dev/provenance/*"]
+timestamp: rfc3161
+pinned_roots: ["{}"]
+"#,
+        pinned_root
+    );
+    let options = VerificationOptions::from_policy_yaml(&policy).expect("Failed to load policy");
+    let result = verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain));
+    assert!(result.is_ok(), "Verification failed: {:?}", result.err());
+
+    let rekor_only = VerificationOptions::from_policy_yaml("version: 1\ntimestamp: rekor\n").unwrap();
+    let result = verifier.verify_bundle_bytes(&bundle_json, rekor_only, &fulcio_chain, Some(&tsa_chain));
+    assert!(matches!(
+        result,
+        Err(VerificationError::Policy(PolicyError::TimestampMechanism { .. }))
+    ));
+
+    let sbom_only =
+        VerificationOptions::from_policy_yaml("version: 1\npredicate_types: [\"https://spdx.dev/Document*\"]\n")
+            .unwrap();
+    let result = verifier.verify_bundle_bytes(&bundle_json, sbom_only, &fulcio_chain, Some(&tsa_chain));
+    assert!(matches!(
+        result,
+        Err(VerificationError::Policy(PolicyError::PredicateTypeNotAllowed(_)))
+    ));
+
+    let other_root = format!("version: 1\npinned_roots: [\"{}\"]\n", Digest::sha256([0x42; 32]));
+    let options = VerificationOptions::from_policy_yaml(&other_root).unwrap();
+    let result = verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain));
+    assert!(matches!(
+        result,
+        Err(VerificationError::Policy(PolicyError::UnpinnedRoot(_)))
+    ));
+}
+
+#[test]
+fn test_verify_with_policy_hook() {
+    use sigstore_verifier::error::{PolicyError, VerificationError};
+    use sigstore_verifier::policy::hook::{PolicyDecision, PolicyInput};
+    use std::sync::Arc;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+
+    let same_repository = AttestationVerifier::new().with_policy_hook(Arc::new(|input: &PolicyInput| {
+        assert_eq!(input.statement.predicate_type, "https://slsa.dev/provenance/v1");
+        let decision = match input.extensions.source_repository_uri.as_deref() {
+            Some("https://github.com/actions/attest-build-provenance") => PolicyDecision::allow(),
+            _ => PolicyDecision::deny(vec!["unexpected source repository".to_string()]),
+        };
+        Ok(decision)
+    }));
+    let result = same_repository.verify_bundle_bytes(
+        &bundle_json,
+        VerificationOptions::default(),
+        &fulcio_chain,
+        Some(&tsa_chain),
+    );
+    assert!(result.is_ok(), "Verification failed: {:?}", result.err());
+
+    let deny_all = AttestationVerifier::new().with_policy_hook(Arc::new(|_: &PolicyInput| {
+        Ok(PolicyDecision::deny(vec!["frozen".to_string(), "no releases".to_string()]))
+    }));
+    let result = deny_all.verify_bundle_bytes(
+        &bundle_json,
+        VerificationOptions::default(),
+        &fulcio_chain,
+        Some(&tsa_chain),
+    );
+    match result {
+        Err(VerificationError::Policy(PolicyError::Denied(reason))) => {
+            assert_eq!(reason, "frozen; no releases")
+        }
+        other => panic!("Expected a policy denial, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_verify_with_tsa_policy() {
+    use sigstore_verifier::error::{CertificateError, PolicyError, VerificationError};
+    use sigstore_verifier::policy::tsa::TsaPolicy;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let verify = |tsa_policy: TsaPolicy| {
+        let options = VerificationOptions::default().with_tsa_policy(tsa_policy);
+        verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain))
+    };
+
+    // The GitHub TSA issues tokens under the Sigstore policy with 1s accuracy
+    verify(TsaPolicy {
+        max_accuracy_millis: Some(1_000),
+        ..TsaPolicy::sigstore()
+    })
+    .expect("Verification failed");
+
+    // genTime is 1s after the leaf's notBefore, so genTime - accuracy is not
+    // provably within the certificate's validity
+    assert!(matches!(
+        verify(TsaPolicy {
+            honor_accuracy: true,
+            ..Default::default()
+        }),
+        Err(VerificationError::Certificate(CertificateError::SigningTimeOutsideValidity { .. }))
+    ));
+
+    let other_oid = TsaPolicy {
+        policy_oids: vec!["1.2.3.4".to_string()],
+        ..Default::default()
+    };
+    assert!(matches!(
+        verify(other_oid),
+        Err(VerificationError::Policy(PolicyError::TsaPolicyNotAllowed(_)))
+    ));
+    assert!(matches!(
+        verify(TsaPolicy {
+            require_nonce: true,
+            ..Default::default()
+        }),
+        Err(VerificationError::Policy(PolicyError::TimestampNonce(_)))
+    ));
+}
+
+#[test]
+fn test_verify_with_certificate_chains() {
+    use sigstore_verifier::crypto::hash::sha256;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+
+    let result = AttestationVerifier::new()
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification failed");
+    assert!(result.certificate_chain().is_none());
+    assert!(!serde_json::to_string(&result).unwrap().contains("certificate_chain"));
+
+    let result = AttestationVerifier::new()
+        .with_certificate_chains()
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification failed");
+    let chain = result.certificate_chain().expect("Fulcio chain should be retained");
+    assert_eq!(sha256(&chain.leaf), result.certificate_hashes.leaf);
+    assert_eq!(chain.root, fulcio_chain.root);
+
+    let der = chain.to_der();
+    assert_eq!(der.len(), 2 + chain.intermediates.len());
+    let pem = chain.to_pem();
+    assert_eq!(pem.matches("-----BEGIN CERTIFICATE-----").count(), der.len());
+    assert_eq!(pem::parse_many(&pem).unwrap()[0].contents(), der[0].as_slice());
+
+    assert!(result.tsa_certificate_chain().is_some());
+
+    let leaf = result.leaf_certificate.as_ref().expect("leaf metadata");
+    assert!(leaf.not_before <= result.signing_time && result.signing_time <= leaf.not_after);
+    assert!(!leaf.serial_number.is_empty());
+    assert_eq!(leaf.signature_algorithm, "ecdsa-with-SHA384");
+}
+
+#[test]
+fn test_verify_with_payload_parser() {
+    use sigstore_verifier::error::VerificationError;
+    use sigstore_verifier::parser::bundle::parse_statement;
+    use sigstore_verifier::parser::payload::IN_TOTO_PAYLOAD_TYPE;
+    use std::sync::Arc;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let options = VerificationOptions::default().with_allowed_predicate_types(vec!["https://example.com/compliance/*"]);
+
+    // The registered parser decides the statement the options are checked against
+    let verifier = AttestationVerifier::new().with_payload_parser(
+        IN_TOTO_PAYLOAD_TYPE,
+        Arc::new(|payload: &[u8]| {
+            let mut statement = parse_statement(payload)?;
+            statement.predicate_type = "https://example.com/compliance/v1".to_string();
+            Ok(statement)
+        }),
+    );
+    verifier
+        .verify_bundle_bytes(&bundle_json, options.clone(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification with custom payload parser failed");
+
+    assert!(AttestationVerifier::new()
+        .verify_bundle_bytes(&bundle_json, options.clone(), &fulcio_chain, Some(&tsa_chain))
+        .is_err());
+
+    let rejecting = AttestationVerifier::new().with_payload_parser(
+        IN_TOTO_PAYLOAD_TYPE,
+        Arc::new(|_: &[u8]| Err(VerificationError::InvalidBundleFormat("unsupported statement".to_string()))),
+    );
+    assert!(matches!(
+        rejecting.verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::InvalidBundleFormat(_))
+    ));
+}
+
+#[test]
+fn test_verify_strict_statement() {
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let options = VerificationOptions::default().with_strict_statement(true);
+    AttestationVerifier::new()
+        .verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain))
+        .expect("Sample statement should match the schema");
+}
+
+#[test]
+fn test_verify_at() {
+    use sigstore_verifier::error::{TimestampError, VerificationError};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let signing_time = verifier
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Sample should verify")
+        .signing_time;
+
+    // Long after the leaf certificate expired, the bundle still verifies as of any later time
+    let options = VerificationOptions::default().with_verify_at(signing_time.saturating_add_seconds(3650 * 86_400));
+    verifier
+        .verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain))
+        .expect("Bundle signed before the verification time should verify");
+
+    let options = VerificationOptions::default().with_verify_at(signing_time.saturating_sub_seconds(1));
+    assert!(matches!(
+        verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Timestamp(TimestampError::SignedAfterVerificationTime { .. }))
+    ));
+}
+
+#[test]
+fn test_verify_chain_policy() {
+    use sigstore_verifier::error::{CertificateError, PolicyError, VerificationError};
+    use sigstore_verifier::policy::chain::ChainPolicy;
+    use sigstore_verifier::types::digest::Digest;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let depth = fulcio_chain.intermediates.len() + 2;
+    let verify = |options: VerificationOptions| {
+        verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain))
+    };
+
+    let exact = VerificationOptions::default().with_chain_policy(ChainPolicy {
+        max_depth: Some(depth),
+        ..Default::default()
+    });
+    let result = verify(exact).expect("Chain within the depth limit should verify");
+
+    let shallow = VerificationOptions::default().with_chain_policy(ChainPolicy {
+        max_depth: Some(depth - 1),
+        ..Default::default()
+    });
+    assert!(matches!(
+        verify(shallow),
+        Err(VerificationError::Certificate(CertificateError::ChainTooDeep { .. }))
+    ));
+
+    // Pinning an intermediate only anchors the chain when the root need not be pinned
+    let intermediate = Digest::sha256(result.certificate_hashes.intermediates[0]);
+    let pinned_intermediate = |require_pinned_root| {
+        VerificationOptions::default()
+            .with_pinned_root_digests(vec![intermediate.clone()])
+            .with_chain_policy(ChainPolicy {
+                require_pinned_root,
+                ..Default::default()
+            })
+    };
+    assert!(matches!(
+        verify(pinned_intermediate(true)),
+        Err(VerificationError::Policy(PolicyError::UnpinnedRoot(_)))
+    ));
+    verify(pinned_intermediate(false)).expect("Pinned intermediate should anchor the chain");
+}
+
+#[test]
+fn test_verify_with_tsa_root_store() {
+    use sigstore_verifier::error::{CertificateError, VerificationError};
+    use sigstore_verifier::verifier::root_store::RootStore;
+    use std::sync::Arc;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+
+    // The TSA leaf and intermediates are completed with the store's root
+    let store = RootStore::from_der([&tsa_chain.root]).unwrap();
+    let verifier = AttestationVerifier::new().with_tsa_root_store(Arc::new(store));
+    verifier
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("TSA chain should end in the store root");
+
+    // A store without the root rejects the chain
+    let store = RootStore::from_der(&fulcio_chain.intermediates).unwrap();
+    let verifier = AttestationVerifier::new().with_tsa_root_store(Arc::new(store));
+    let err = verifier
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .unwrap_err();
+    assert!(matches!(err, VerificationError::Certificate(CertificateError::UnknownIssuer(_))));
+}
+
+#[test]
+fn test_verify_subject_name() {
+    use sigstore_verifier::error::VerificationError;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+
+    let options = VerificationOptions::default().with_expected_subject_name("art*");
+    let result = verifier
+        .verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain))
+        .expect("Sample subject should match the pattern");
+    assert_eq!(result.subject_name.as_deref(), Some("artifact"));
+
+    let options = VerificationOptions::default().with_expected_subject_name("pkg:npm/left-pad@*");
+    let err = verifier
+        .verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain))
+        .unwrap_err();
+    assert!(matches!(err, VerificationError::SubjectNameMismatch { .. }));
+    assert_eq!(err.code(), "subject_name_mismatch");
+}
+
+#[test]
+fn test_assess_slsa_level() {
+    use sigstore_verifier::report::{to_vsa, VerificationReport};
+    use sigstore_verifier::slsa::{SlsaBuildLevel, SlsaRequirement};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let options = VerificationOptions::default();
+    let result = AttestationVerifier::new().verify_bundle_bytes(
+        &bundle_json,
+        options.clone(),
+        &fulcio_chain,
+        Some(&tsa_chain),
+    );
+    let mut report = VerificationReport::new("sample.json", &bundle_json, options, result);
+
+    // attest-build-provenance signs from the workflow it attests, on a GitHub-hosted runner
+    let assessment = report.assess_slsa(&bundle_json).expect("Sample statement should decode").clone();
+    assert_eq!(assessment.level, SlsaBuildLevel::L2);
+    let unmet: Vec<SlsaRequirement> = assessment.unmet().map(|check| check.requirement).collect();
+    assert_eq!(unmet, [SlsaRequirement::IsolatedSigning]);
+    assert!(assessment.require(SlsaBuildLevel::L2).is_ok());
+    assert!(assessment.require(SlsaBuildLevel::L3).is_err());
+
+    let vsa = to_vsa(&report).unwrap();
+    assert_eq!(vsa["predicate"]["verifiedLevels"], serde_json::json!(["SLSA_BUILD_LEVEL_2"]));
+}
+
+#[test]
+fn test_dependency_policy() {
+    use sigstore_verifier::error::{PolicyError, VerificationError};
+    use sigstore_verifier::policy::dependency::DependencyPolicy;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let verify = |allowed: &str| {
+        let options = VerificationOptions::default().with_dependency_policy(DependencyPolicy {
+            allowed_uris: vec![allowed.to_string()],
+            require_digest: true,
+        });
+        verifier.verify_bundle_bytes(&bundle_json, options, &fulcio_chain, Some(&tsa_chain))
+    };
+
+    let result = verify("git+https://github.com/actions/*").expect("Sample dependencies should be allowed");
+    let dependency = &result.resolved_dependencies[0];
+    assert_eq!(
+        dependency.uri.as_deref(),
+        Some("git+https://github.com/actions/attest-build-provenance@refs/heads/main")
+    );
+    assert_eq!(dependency.digests[0].to_string(), "gitCommit:268464dd14307f17143ddd8784cca15db03db05f");
+
+    let err = verify("git+https://github.com/org/*").unwrap_err();
+    assert!(matches!(err, VerificationError::Policy(PolicyError::DependencyPolicy(_))));
+}
+
+#[test]
+fn test_verify_build_tree() {
+    use sigstore_verifier::build_tree::{BuildStatus, BundleMap, ReferenceKind};
+    use sigstore_verifier::types::digest::{Digest, DigestAlgorithm};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let builder = "https://github.com/actions/attest-build-provenance/.github/workflows/prober.yml@refs/heads/main";
+    let commit = Digest::from_hex(DigestAlgorithm::GitCommit, "268464dd14307f17143ddd8784cca15db03db05f").unwrap();
+
+    // The sample stands in for its builder's and source's attestations
+    let mut source = BundleMap::new();
+    source
+        .insert_uri(builder, bundle_json.clone())
+        .insert_digest(commit, bundle_json.clone());
+
+    let tree = verifier
+        .verify_build_tree(&bundle_json, VerificationOptions::default(), &source, 2, &fulcio_chain, Some(&tsa_chain))
+        .expect("Root bundle should verify");
+    assert!(tree.result().is_some());
+    assert_eq!(tree.node_count(), 3);
+
+    let [builder_node, source_node] = &tree.children[..] else {
+        panic!("Expected the builder and one dependency, got {:?}", tree.children);
+    };
+    assert_eq!(builder_node.reference.as_ref().unwrap().kind, ReferenceKind::Builder);
+    // The builder's bundle attests to the root artifact again
+    assert!(matches!(builder_node.status, BuildStatus::Cycle));
+    // ... and the dependency's bundle does not attest to the commit
+    match &source_node.status {
+        BuildStatus::Failed(error) => assert!(error.message.contains("gitCommit"), "{}", error.message),
+        status => panic!("Expected a failed dependency, got {:?}", status),
+    }
+    assert!(!tree.all_verified());
+
+    let tree = verifier
+        .verify_build_tree(&bundle_json, VerificationOptions::default(), &source, 0, &fulcio_chain, Some(&tsa_chain))
+        .unwrap();
+    assert!(tree.children.iter().all(|node| matches!(node.status, BuildStatus::DepthLimit)));
+}
+
+#[test]
+fn test_verify_reproduced() {
+    use sigstore_verifier::error::{PolicyError, VerificationError};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let verify = |other: &[u8]| {
+        verifier.verify_reproduced(&bundle_json, other, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+    };
+
+    // The same bundle twice matches on digest but not on independence
+    let err = verify(&bundle_json).unwrap_err();
+    match err {
+        VerificationError::Policy(PolicyError::SameBuilder(builder)) => {
+            assert!(builder.starts_with("https://github.com/actions/attest-build-provenance "), "{}", builder)
+        }
+        err => panic!("Expected a same-builder error, got {:?}", err),
+    }
+
+    // Another prober run attests to a different artifact
+    let mut samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    samples.pop();
+    samples.pop();
+    samples.push("samples");
+    let other = std::fs::read(samples.join("actions-attest-build-provenance-attestation-13739985.sigstore.json"))
+        .expect("Failed to read bundle");
+    let err = verify(&other).unwrap_err();
+    assert_eq!(err.code(), "subject_digest_mismatch");
+}
+
+#[test]
+fn test_verify_with_custom_steps() {
+    use sigstore_verifier::error::{PolicyError, VerificationError};
+    use sigstore_verifier::verifier::step::{BuiltinStep, StepContext};
+    use std::sync::{Arc, Mutex};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+
+    // Steps run after their built-in step, in insertion order, with the data verified so far
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let record = |seen: Arc<Mutex<Vec<BuiltinStep>>>| {
+        Arc::new(move |context: &StepContext| {
+            match context.after {
+                BuiltinStep::Subject => assert!(context.certificate_chain.is_none()),
+                BuiltinStep::Timestamp => assert!(context.timestamp_proof.is_some()),
+                BuiltinStep::Identity => assert!(context.identity.is_some()),
+                _ => assert!(context.leaf_certificate.is_some()),
+            }
+            seen.lock().unwrap().push(context.after);
+            Ok(())
+        })
+    };
+    let verifier = AttestationVerifier::new()
+        .with_step(BuiltinStep::Identity, record(seen.clone()))
+        .with_step(BuiltinStep::Subject, record(seen.clone()))
+        .with_step(BuiltinStep::Timestamp, record(seen.clone()))
+        .with_step(BuiltinStep::CertificateChain, record(seen.clone()));
+    verifier
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification with custom steps failed");
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            BuiltinStep::Subject,
+            BuiltinStep::CertificateChain,
+            BuiltinStep::Timestamp,
+            BuiltinStep::Identity
+        ]
+    );
+
+    let rejecting = AttestationVerifier::new().with_step(
+        BuiltinStep::CertificateChain,
+        Arc::new(|context: &StepContext| {
+            let leaf = context.leaf_certificate.expect("leaf metadata");
+            Err(PolicyError::Denied(format!("issuer {} not allowed", leaf.issuer_cn)).into())
+        }),
+    );
+    assert!(matches!(
+        rejecting.verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Policy(PolicyError::Denied(_)))
+    ));
+}
+
+#[test]
+fn test_verify_bundle_stream() {
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let bundle: serde_json::Value = serde_json::from_slice(&bundle_json).unwrap();
+    let wrapped = serde_json::json!({ "bundle": bundle, "repository_id": 1 });
+    let jsonl = format!("{}\n\n{{\"bundle\": 1}}\n{}", bundle, wrapped);
+
+    let verifier = AttestationVerifier::new();
+    let results: Vec<_> = verifier
+        .verify_bundle_stream(jsonl.as_bytes(), VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .collect();
+    let lines: Vec<_> = results.iter().map(|verified| (verified.line, verified.result.is_ok())).collect();
+    assert_eq!(lines, vec![(1, true), (3, false), (4, true)]);
+    assert!(results[1].result.as_ref().unwrap_err().to_string().contains("line 3"));
+}
+
+#[test]
+fn test_verify_bundle_stream_line_limit() {
+    use sigstore_verifier::error::VerificationError;
+    use sigstore_verifier::types::limits::InputLimits;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let bundle = serde_json::from_slice::<serde_json::Value>(&bundle_json).unwrap().to_string();
+    let max = bundle.len();
+    let oversized = "x".repeat(3 * max);
+    let jsonl = format!("{}\n{}\n{}", bundle, oversized, bundle);
+
+    // A reader that returns one byte at a time, so the line must be skipped across many reads
+    let reader = std::io::BufReader::with_capacity(1, jsonl.as_bytes());
+    let limits = InputLimits { max_bundle_size: max, ..InputLimits::default() };
+    let verifier = AttestationVerifier::new().with_input_limits(limits);
+    let results: Vec<_> = verifier
+        .verify_bundle_stream(reader, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .collect();
+    let lines: Vec<_> = results.iter().map(|verified| (verified.line, verified.result.is_ok())).collect();
+    assert_eq!(lines, vec![(1, true), (2, false), (3, true)]);
+    match &results[1].result {
+        Err(VerificationError::LimitExceeded { max: limit, actual, .. }) => {
+            assert_eq!(*limit, max);
+            assert_eq!(*actual, oversized.len());
+        }
+        other => panic!("expected LimitExceeded, got {:?}", other.as_ref().map(|_| ())),
+    }
+}
+
+#[test]
+fn test_verify_artifact() {
+    use sigstore_verifier::crypto::hash::{hex_encode, sha256};
+    use sigstore_verifier::error::VerificationError;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verifier = AttestationVerifier::new();
+    let artifact = b"not the attested artifact";
+    match verifier.verify_artifact(
+        &artifact[..],
+        &bundle_json,
+        VerificationOptions::default(),
+        &fulcio_chain,
+        Some(&tsa_chain),
+    ) {
+        Err(VerificationError::SubjectDigestMismatch { expected, .. }) => {
+            assert_eq!(expected, format!("sha256:{}", hex_encode(&sha256(artifact))));
+        }
+        other => panic!("expected a subject digest mismatch, got {:?}", other.map(|_| ())),
+    }
+
+    let missing = std::path::Path::new("does-not-exist.tar.gz");
+    assert!(matches!(
+        verifier.verify_artifact_file(missing, &bundle_json, VerificationOptions::default(), &fulcio_chain, None),
+        Err(VerificationError::Io(_))
+    ));
+}
+
+#[test]
+fn test_verify_dsse_envelope() {
+    use sigstore_verifier::error::{TimestampError, VerificationError};
+    use sigstore_verifier::parser::bundle::{decode_base64, parse_bundle_from_bytes};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let bundle = parse_bundle_from_bytes(&bundle_json).expect("Failed to parse bundle");
+    let cert_der = decode_base64(&bundle.verification_material.certificate.raw_bytes).unwrap();
+    let timestamp = decode_base64(
+        &bundle.verification_material.timestamp_verification_data.unwrap().rfc3161_timestamps.unwrap()[0]
+            .signed_timestamp,
+    )
+    .unwrap();
+
+    let verifier = AttestationVerifier::new();
+    let expected = verifier
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification failed");
+    let result = verifier
+        .verify_dsse_envelope(
+            &bundle.dsse_envelope,
+            &cert_der,
+            Some(&timestamp),
+            VerificationOptions::default(),
+            &fulcio_chain,
+            Some(&tsa_chain),
+        )
+        .expect("Envelope verification failed");
+    assert_eq!(result.as_slice(), expected.as_slice());
+
+    assert!(matches!(
+        verifier.verify_dsse_envelope(
+            &bundle.dsse_envelope,
+            &cert_der,
+            None,
+            VerificationOptions::default(),
+            &fulcio_chain,
+            None,
+        ),
+        Err(VerificationError::Timestamp(TimestampError::NoTimestamp))
+    ));
+}
+
+#[test]
+fn test_verify_with_certificate_pool() {
+    use sigstore_verifier::verifier::pool::CertificatePool;
+    use std::sync::Arc;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let expected = AttestationVerifier::new()
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification failed");
+
+    let pool = Arc::new(CertificatePool::default());
+    let verifier = AttestationVerifier::new().with_certificate_pool(pool.clone());
+    let verify = || {
+        let result = verifier
+            .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+            .expect("Pooled verification failed");
+        assert_eq!(result.as_slice(), expected.as_slice());
+    };
+    verify();
+    let first = pool.stats();
+    assert!(first.misses > 0);
+    assert_eq!(first.misses as usize, first.len);
+
+    // The second verification takes every trust certificate from the pool
+    verify();
+    let second = pool.stats();
+    assert_eq!(second.misses, first.misses);
+    assert_eq!(second.hits - first.hits, first.hits + first.misses);
+}
+
+#[test]
+fn test_verify_with_context() {
+    use sigstore_verifier::error::{Interrupted, VerificationError};
+    use sigstore_verifier::verifier::context::{CancellationToken, VerificationContext};
+    use sigstore_verifier::verifier::step::{BuiltinStep, StepContext};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let verify = |verifier: AttestationVerifier| {
+        verifier.verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+    };
+
+    let context = VerificationContext::new().with_timeout(Duration::from_secs(600));
+    verify(AttestationVerifier::new().with_context(context)).expect("Verification within the deadline failed");
+
+    let context = VerificationContext::new().with_deadline(Instant::now());
+    assert!(matches!(
+        verify(AttestationVerifier::new().with_context(context)),
+        Err(VerificationError::Interrupted(Interrupted::DeadlineExceeded("start")))
+    ));
+
+    // Cancelled while verifying: the chain loop notices before the next step
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    let verifier = AttestationVerifier::new()
+        .with_context(VerificationContext::new().with_cancellation(token))
+        .with_step(
+            BuiltinStep::Subject,
+            Arc::new(move |_: &StepContext| {
+                cancel.cancel();
+                Ok(())
+            }),
+        );
+    let err = verify(verifier).unwrap_err();
+    assert!(matches!(
+        err,
+        VerificationError::Interrupted(Interrupted::Cancelled("certificate_chain"))
+    ));
+    assert_eq!(err.code(), "interrupted");
+}
+
+#[test]
+fn test_verify_with_tsa_material() {
+    use sigstore_verifier::parser::bundle::{decode_base64, parse_bundle_from_bytes};
+    use sigstore_verifier::verifier::timestamp::TsaMaterial;
+    use std::sync::Arc;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let bundle = parse_bundle_from_bytes(&bundle_json).expect("Failed to parse bundle");
+    let cert_der = decode_base64(&bundle.verification_material.certificate.raw_bytes).unwrap();
+    let token = decode_base64(
+        &bundle.verification_material.timestamp_verification_data.unwrap().rfc3161_timestamps.unwrap()[0]
+            .signed_timestamp,
+    )
+    .unwrap();
+    let expected = AttestationVerifier::new()
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification failed");
+
+    // TimeStampResp ::= SEQUENCE { status PKIStatusInfo (granted), timeStampToken }
+    let mut content = vec![0x30, 0x03, 0x02, 0x01, 0x00];
+    content.extend_from_slice(&token);
+    let mut response = vec![0x30, 0x82];
+    response.extend_from_slice(&(content.len() as u16).to_be_bytes());
+    response.extend_from_slice(&content);
+
+    let chain_pem = pem::encode_many(
+        &std::iter::once(&tsa_chain.leaf)
+            .chain(&tsa_chain.intermediates)
+            .chain(std::iter::once(&tsa_chain.root))
+            .map(|der| pem::Pem::new("CERTIFICATE", der.clone()))
+            .collect::<Vec<_>>(),
+    );
+    let dir = std::env::temp_dir().join(format!("sigstore-tsa-material-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("timestamp.githubapp.com.pem"), chain_pem).unwrap();
+    std::fs::write(dir.join("13581567.tsr"), &response).unwrap();
+    std::fs::write(dir.join("notes.txt"), "not TSA material").unwrap();
+    let material = TsaMaterial::from_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let material = material.expect("Failed to load TSA material");
+    assert_eq!(material.chains().len(), 1);
+    assert_eq!(material.token_count(), 1);
+    assert!(material.token_for(b"another signature").is_none());
+
+    let verifier = AttestationVerifier::new().with_tsa_material(Arc::new(material));
+
+    // The bundle's own timestamp, verified with the stored chain
+    let result = verifier
+        .verify_bundle_bytes(&bundle_json, VerificationOptions::default(), &fulcio_chain, None)
+        .expect("Verification with the stored chain failed");
+    assert_eq!(result.as_slice(), expected.as_slice());
+
+    // No timestamp in the envelope: the stored response over its signature is replayed
+    let result = verifier
+        .verify_dsse_envelope(
+            &bundle.dsse_envelope,
+            &cert_der,
+            None,
+            VerificationOptions::default(),
+            &fulcio_chain,
+            None,
+        )
+        .expect("Verification with the replayed response failed");
+    assert_eq!(result.as_slice(), expected.as_slice());
+}
+
+#[test]
+fn test_verify_redacted_bundle() {
+    use sigstore_verifier::error::{RedactionError, VerificationError};
+    use sigstore_verifier::parser::bundle::parse_bundle_from_bytes;
+    use sigstore_verifier::redaction::{RedactionPolicy, REDACTABLE_PAYLOAD_TYPE};
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let bundle = parse_bundle_from_bytes(&bundle_json).expect("Failed to parse bundle");
+    let redacted = bundle
+        .redact(&RedactionPolicy::new().with_field("/buildDefinition/internalParameters"))
+        .expect("Redaction failed");
+    let verifier = AttestationVerifier::new();
+
+    // The signature of an in-toto payload covers the redacted fields
+    let redacted_json = serde_json::to_vec(&redacted).unwrap();
+    assert!(matches!(
+        verifier.verify_bundle_bytes(&redacted_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Signature(_))
+    ));
+
+    // Redacted predicates of redactable statements must be allowed explicitly
+    let mut redactable = redacted;
+    redactable.dsse_envelope.payload_type = REDACTABLE_PAYLOAD_TYPE.to_string();
+    let redactable_json = serde_json::to_vec(&redactable).unwrap();
+    assert!(matches!(
+        verifier.verify_bundle_bytes(&redactable_json, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Redaction(RedactionError::RedactedPredicate))
+    ));
+    let options = VerificationOptions::default().with_allow_redacted_predicate(true);
+    assert!(matches!(
+        verifier.verify_bundle_bytes(&redactable_json, options, &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Signature(_))
+    ));
+}
+
+/// Add a Rekor v2 `dsse` 0.0.2 entry for `envelope_bundle`'s envelope to the
+/// RFC 3161 sample, in a one-entry log whose checkpoint `signing_key` signs
+fn with_rekor_v2_entry(bundle_json: &[u8], envelope_bundle: &[u8], signing_key: &ed25519_dalek::SigningKey) -> Vec<u8> {
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use ed25519_dalek::Signer;
+    use sigstore_verifier::crypto::hash::sha256;
+    use sigstore_verifier::verifier::checkpoint::LogVerifier;
+
+    let source: serde_json::Value = serde_json::from_slice(envelope_bundle).unwrap();
+    let payload = BASE64.decode(source["dsseEnvelope"]["payload"].as_str().unwrap()).unwrap();
+    let body = serde_json::to_vec(&serde_json::json!({
+        "apiVersion": "0.0.2",
+        "kind": "dsse",
+        "spec": { "dsseV002": {
+            "payloadHash": { "algorithm": "SHA2_256", "digest": BASE64.encode(sha256(&payload)) },
+            "signatures": [{
+                "content": source["dsseEnvelope"]["signatures"][0]["sig"],
+                "verifier": {
+                    "keyDetails": "PKIX_ECDSA_P256_SHA_256",
+                    "x509Certificate": { "rawBytes": source["verificationMaterial"]["certificate"]["rawBytes"] },
+                },
+            }],
+        }},
+    }))
+    .unwrap();
+
+    // A one-entry tree's root is the leaf hash
+    let root = sha256(&[[0u8].as_slice(), &body].concat());
+    let spki = rekor_v2_spki(&signing_key.verifying_key());
+    let verifier = LogVerifier::from_spki_der(REKOR_V2_ORIGIN, &spki).unwrap();
+    let note_body = format!("{}\n1\n{}\n", REKOR_V2_ORIGIN, BASE64.encode(root));
+    let signature = signing_key.sign(note_body.as_bytes());
+    let note = format!(
+        "{}\n\u{2014} {} {}\n",
+        note_body,
+        REKOR_V2_ORIGIN,
+        BASE64.encode([verifier.key_hint().as_slice(), &signature.to_bytes()].concat())
+    );
+
+    let mut bundle: serde_json::Value = serde_json::from_slice(bundle_json).unwrap();
+    bundle["verificationMaterial"]["tlogEntries"] = serde_json::json!([{
+        "logIndex": "0",
+        "logId": { "keyId": BASE64.encode(verifier.log_id()) },
+        "kindVersion": { "kind": "dsse", "version": "0.0.2" },
+        "integratedTime": "0",
+        "inclusionProof": {
+            "logIndex": "0",
+            "rootHash": BASE64.encode(root),
+            "treeSize": "1",
+            "hashes": [],
+            "checkpoint": { "envelope": note },
+        },
+        "canonicalizedBody": BASE64.encode(&body),
+    }]);
+    serde_json::to_vec(&bundle).unwrap()
+}
+
+const REKOR_V2_ORIGIN: &str = "log2025-1.rekor.example.com";
+
+fn rekor_v2_spki(key: &ed25519_dalek::VerifyingKey) -> Vec<u8> {
+    [[0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00].as_slice(), key.as_bytes()].concat()
+}
+
+#[test]
+fn test_verify_rekor_v2_entry() {
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use ed25519_dalek::SigningKey;
+    use sigstore_verifier::error::{TransparencyError, VerificationError};
+    use sigstore_verifier::types::result::TransparencyLogKey;
+
+    let (bundle_json, fulcio_chain, tsa_chain) = load_rfc3161_sample();
+    let log_key = SigningKey::from_bytes(&[7; 32]);
+    let keys = vec![TransparencyLogKey::new(
+        REKOR_V2_ORIGIN,
+        BASE64.encode(rekor_v2_spki(&log_key.verifying_key())),
+    )];
+    let options = || VerificationOptions::default().with_transparency_log_keys(keys.clone());
+    let verifier = AttestationVerifier::new();
+
+    let logged = with_rekor_v2_entry(&bundle_json, &bundle_json, &log_key);
+    verifier
+        .verify_bundle_bytes(&logged, options(), &fulcio_chain, Some(&tsa_chain))
+        .expect("Verification of the Rekor v2 entry failed");
+
+    // Without log keys, nothing ties the checkpoint to a trusted log
+    assert!(matches!(
+        verifier.verify_bundle_bytes(&logged, VerificationOptions::default(), &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Transparency(TransparencyError::MissingLogKeys))
+    ));
+
+    // A checkpoint signed by another key, under the trusted log's ID
+    let forged = with_rekor_v2_entry(&bundle_json, &bundle_json, &SigningKey::from_bytes(&[8; 32]));
+    let mut forged: serde_json::Value = serde_json::from_slice(&forged).unwrap();
+    forged["verificationMaterial"]["tlogEntries"][0]["logId"] =
+        serde_json::from_slice::<serde_json::Value>(&logged).unwrap()["verificationMaterial"]["tlogEntries"][0]["logId"].clone();
+    assert!(matches!(
+        verifier.verify_bundle_bytes(&serde_json::to_vec(&forged).unwrap(), options(), &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Transparency(_))
+    ));
+
+    // A validly logged body for a different envelope
+    let mut samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    samples.pop();
+    samples.pop();
+    let other = std::fs::read(samples.join("samples/actions-attest-build-provenance-attestation-13531551.sigstore.json")).unwrap();
+    let other_entry = with_rekor_v2_entry(&bundle_json, &other, &log_key);
+    assert!(matches!(
+        verifier.verify_bundle_bytes(&other_entry, options(), &fulcio_chain, Some(&tsa_chain)),
+        Err(VerificationError::Transparency(TransparencyError::EntryBodyMismatch(_)))
+    ));
+}
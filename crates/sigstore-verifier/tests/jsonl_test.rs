@@ -1,9 +1,9 @@
-#![cfg(feature = "fetcher")]
-
 use sigstore_verifier::fetcher::jsonl::parser::{
-    load_trusted_root_from_jsonl, select_certificate_authority, select_timestamp_authority,
+    load_trusted_root_from_jsonl, select_certificate_authority, select_certificate_authority_for,
+    select_timestamp_authority, select_timestamp_authority_for,
 };
 use sigstore_verifier::types::certificate::FulcioInstance;
+use sigstore_verifier::types::environment::SigstoreEnvironment;
 use std::fs;
 use std::path::PathBuf;
 
@@ -211,3 +211,74 @@ fn test_certificate_chain_structure() {
     assert!(chain.root.len() > 100, "Root certificate too small");
     assert_eq!(chain.root[0], 0x30, "Root not a valid DER certificate");
 }
+
+#[test]
+fn test_select_authorities_for_environment() {
+    let content = get_sample_trusted_root();
+    let roots = load_trusted_root_from_jsonl(&content).expect("Failed to parse JSONL");
+    let timestamp = 1720000000;
+
+    // Production authorities select the same chains as the Fulcio instances
+    let github = SigstoreEnvironment::Production
+        .authority_for_issuer("Fulcio Intermediate l2")
+        .unwrap();
+    assert_eq!(
+        select_certificate_authority_for(&roots, &github, timestamp).unwrap(),
+        select_certificate_authority(&roots, &FulcioInstance::GitHub, timestamp).unwrap()
+    );
+    assert_eq!(
+        select_timestamp_authority_for(&roots, &github, timestamp).unwrap(),
+        select_timestamp_authority(&roots, &FulcioInstance::GitHub, timestamp).unwrap()
+    );
+
+    // The production trusted root has no staging authorities
+    let staging = SigstoreEnvironment::Staging
+        .authority_for_issuer("sigstore-intermediate")
+        .unwrap();
+    assert!(select_certificate_authority_for(&roots, &staging, timestamp).is_err());
+    assert!(select_timestamp_authority_for(&roots, &staging, timestamp).is_err());
+}
+
+#[test]
+fn test_trusted_root_diff() {
+    use sigstore_verifier::fetcher::jsonl::diff::TrustRootChangeKind;
+    use sigstore_verifier::fetcher::jsonl::types::{TrustedRootSet, ValidityPeriod};
+
+    let old = TrustedRootSet::from_jsonl(&get_sample_trusted_root()).expect("Failed to parse JSONL");
+    assert!(TrustedRootSet::diff(&old, &old.clone()).is_empty());
+
+    // GitHub rotates by ending the current CA and adding a new one for the same URI
+    let mut new = old.clone();
+    let github = &mut new.roots[1];
+    let mut rotated = github.certificate_authorities[0].clone();
+    rotated.cert_chain.certificates.truncate(1);
+    github.certificate_authorities.last_mut().unwrap().valid_for = ValidityPeriod {
+        start: Some("2025-11-13T00:00:00Z".to_string()),
+        end: Some("2026-06-01T00:00:00Z".to_string()),
+    };
+    github.certificate_authorities.push(rotated);
+    // A transparency log disappears
+    new.roots[0].tlogs.pop();
+
+    let diff = TrustedRootSet::diff(&old, &new);
+    let kinds: Vec<TrustRootChangeKind> =
+        diff.certificate_authorities.iter().map(|change| change.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![TrustRootChangeKind::ValidityChanged, TrustRootChangeKind::Rotated]
+    );
+    assert!(diff.certificate_authorities.iter().all(|change| change.uri == "fulcio.githubapp.com"));
+    assert!(diff.timestamp_authorities.is_empty());
+    assert_eq!(diff.tlogs.len(), 1);
+    assert_eq!(diff.tlogs[0].kind, TrustRootChangeKind::Removed);
+    assert_eq!(diff.tlogs[0].uri, "https://log2025-1.rekor.sigstore.dev");
+    assert!(diff.has_removals());
+    assert_eq!(diff.changes().count(), 3);
+
+    // Starting from nothing, everything is new
+    let initial = TrustedRootSet::diff(&TrustedRootSet::default(), &old);
+    assert!(initial
+        .changes()
+        .all(|(_, change)| change.kind == TrustRootChangeKind::Added));
+    assert!(!initial.has_removals());
+}
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 use sigstore_verifier::parser::bundle::{parse_bundle_from_path, parse_dsse_payload};
+use sigstore_verifier::types::certificate::ParsedCertificate;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
 
@@ -55,4 +56,96 @@ fn test_rekor_bundle_parser() {
     let statement =
         parse_dsse_payload(&bundle.dsse_envelope).expect("Failed to parse DSSE payload");
     println!("Extracted DSSE statement: {:?}", statement);
-}
\ No newline at end of file
+}
+fn load_sample_cert(name: &str) -> Vec<u8> {
+    use sigstore_verifier::parser::certificate::parse_pem_certificate;
+
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("samples/certs");
+    path.push(name);
+    let pem = std::fs::read_to_string(&path).expect("Failed to read certificate");
+    parse_pem_certificate(&pem).expect("Failed to decode PEM certificate")
+}
+
+#[test]
+fn test_gitlab_ci_identity() {
+    use sigstore_verifier::parser::identity::{extract_fulcio_extensions, extract_oidc_identity};
+    use sigstore_verifier::policy::identity::{IdentityPolicy, GITLAB_COM_ISSUER};
+    use sigstore_verifier::types::certificate::CiProvider;
+
+    let der = load_sample_cert("gitlab-ci-leaf.pem");
+    let cert = ParsedCertificate::from_der(&der).expect("Failed to parse certificate");
+
+    let identity = extract_oidc_identity(&cert).expect("Failed to extract identity");
+    assert_eq!(identity.provider(), CiProvider::GitLab);
+    assert_eq!(
+        identity.repository.as_deref(),
+        Some("https://gitlab.com/example-group/example-project")
+    );
+
+    let extensions = extract_fulcio_extensions(&cert).expect("Failed to extract extensions");
+    assert_eq!(extensions.runner_environment.as_deref(), Some("gitlab-hosted"));
+    assert_eq!(extensions.build_trigger.as_deref(), Some("push"));
+    assert_eq!(
+        extensions.run_invocation_uri.as_deref(),
+        Some("https://gitlab.com/example-group/example-project/-/jobs/123456")
+    );
+
+    let policy = IdentityPolicy::gitlab_ci(
+        GITLAB_COM_ISSUER,
+        "example-group/example-project",
+        ".gitlab-ci.yml",
+        "refs/heads/main",
+    );
+    assert!(policy.evaluate(&identity).is_ok());
+}
+
+#[test]
+fn test_google_cloud_build_identity() {
+    use sigstore_verifier::parser::identity::{extract_fulcio_extensions, extract_oidc_identity};
+    use sigstore_verifier::policy::identity::IdentityPolicy;
+    use sigstore_verifier::types::certificate::CiProvider;
+
+    let der = load_sample_cert("cloud-build-leaf.pem");
+    let cert = ParsedCertificate::from_der(&der).expect("Failed to parse certificate");
+
+    let identity = extract_oidc_identity(&cert).expect("Failed to extract identity");
+    assert_eq!(identity.provider(), CiProvider::GoogleCloudBuild);
+    assert_eq!(
+        identity.subject.as_deref(),
+        Some("123456789012@cloudbuild.gserviceaccount.com")
+    );
+
+    let extensions = extract_fulcio_extensions(&cert).expect("Failed to extract extensions");
+    assert_eq!(extensions.issuer.as_deref(), Some("https://accounts.google.com"));
+    assert!(extensions.source_repository_uri.is_none());
+
+    assert!(IdentityPolicy::google_cloud_build("123456789012").evaluate(&identity).is_ok());
+    assert!(IdentityPolicy::google_cloud_build("999").evaluate(&identity).is_err());
+}
+
+#[test]
+fn test_email_identity() {
+    use sigstore_verifier::parser::identity::extract_oidc_identity;
+    use sigstore_verifier::policy::identity::{
+        IdentityPolicy, GOOGLE_ACCOUNTS_ISSUER, OAUTH_EMAIL_ISSUERS,
+    };
+    use sigstore_verifier::types::certificate::CiProvider;
+
+    let der = load_sample_cert("email-leaf.pem");
+    let cert = ParsedCertificate::from_der(&der).expect("Failed to parse certificate");
+
+    let identity = extract_oidc_identity(&cert).expect("Failed to extract identity");
+    assert_eq!(identity.subject.as_deref(), Some("alice@example.com"));
+    assert_eq!(identity.issuer.as_deref(), Some("https://github.com/login/oauth"));
+    assert_eq!(identity.provider(), CiProvider::Unknown);
+
+    assert!(IdentityPolicy::email("alice@example.com", &OAUTH_EMAIL_ISSUERS)
+        .evaluate(&identity)
+        .is_ok());
+    assert!(IdentityPolicy::email("alice@example.com", &[GOOGLE_ACCOUNTS_ISSUER])
+        .evaluate(&identity)
+        .is_err());
+}
@@ -0,0 +1,206 @@
+use sigstore_verifier::crypto::signature::PublicKey;
+use sigstore_verifier::parser::certificate::{parse_der_certificate, parse_pem_certificate};
+use sigstore_verifier::types::certificate::CertificateChain;
+use sigstore_verifier::verifier::signature::verify_dsse_signature_bytes;
+use std::path::PathBuf;
+
+const PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+const PAYLOAD: &[u8] = b"{}";
+
+fn sample_path(name: &str) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("samples/certs");
+    path.push(name);
+    path
+}
+
+fn load_cert(name: &str) -> Vec<u8> {
+    let pem = std::fs::read_to_string(sample_path(name)).expect("Failed to read certificate");
+    parse_pem_certificate(&pem).expect("Failed to decode PEM certificate")
+}
+
+/// Check the CA signature on the leaf and the leaf signature over the DSSE PAE for `curve`
+fn check_curve(curve: &str) {
+    let ca_der = load_cert(&format!("{}-ca.pem", curve));
+    let leaf_der = load_cert(&format!("{}-leaf.pem", curve));
+    let ca = parse_der_certificate(&ca_der).expect("Failed to parse CA certificate");
+    let leaf = parse_der_certificate(&leaf_der).expect("Failed to parse leaf certificate");
+
+    // Certificate signature: CA key over the leaf TBSCertificate
+    let ca_key = PublicKey::from_certificate(&ca).expect("Failed to load CA public key");
+    ca_key
+        .verify_signature(leaf.tbs_certificate.as_ref(), &leaf.signature_value.data)
+        .expect("Leaf certificate signature should verify");
+    assert!(
+        PublicKey::from_certificate(&leaf)
+            .unwrap()
+            .verify_signature(leaf.tbs_certificate.as_ref(), &leaf.signature_value.data)
+            .is_err(),
+        "Leaf certificate is not self-signed"
+    );
+
+    // DSSE signature: leaf key over PAE(PAYLOAD_TYPE, PAYLOAD)
+    let signature = std::fs::read(sample_path(&format!("{}-message.sig", curve)))
+        .expect("Failed to read signature");
+    let chain = CertificateChain {
+        leaf: leaf_der,
+        intermediates: vec![],
+        root: ca_der,
+    };
+    verify_dsse_signature_bytes(PAYLOAD_TYPE, PAYLOAD, &signature, &chain)
+        .expect("DSSE signature should verify");
+    assert!(
+        verify_dsse_signature_bytes(PAYLOAD_TYPE, b"[]", &signature, &chain).is_err(),
+        "DSSE signature over a different payload should be rejected"
+    );
+}
+
+#[test]
+fn test_p256_signatures() {
+    let leaf = load_cert("p256-leaf.pem");
+    let cert = parse_der_certificate(&leaf).unwrap();
+    assert!(matches!(PublicKey::from_certificate(&cert), Ok(PublicKey::P256(_))));
+    check_curve("p256");
+}
+
+#[test]
+fn test_p384_signatures() {
+    let leaf = load_cert("p384-leaf.pem");
+    let cert = parse_der_certificate(&leaf).unwrap();
+    assert!(matches!(PublicKey::from_certificate(&cert), Ok(PublicKey::P384(_))));
+    check_curve("p384");
+}
+
+#[test]
+fn test_secp256k1_signatures() {
+    let leaf = load_cert("secp256k1-leaf.pem");
+    let cert = parse_der_certificate(&leaf).unwrap();
+    assert!(matches!(PublicKey::from_certificate(&cert), Ok(PublicKey::Secp256k1(_))));
+    check_curve("secp256k1");
+}
+
+#[test]
+fn test_prehash_uses_key_curve_not_digest_length() {
+    use sha2::{Digest, Sha256};
+
+    // A P-384 key verifying a SHA-256 digest (as CMS SignedData allows) must still use P-384
+    let message = std::fs::read(sample_path("message.txt")).expect("Failed to read message");
+    let signature =
+        std::fs::read(sample_path("p384-message-sha256.sig")).expect("Failed to read signature");
+    let leaf = load_cert("p384-leaf.pem");
+    let key = PublicKey::from_certificate(&parse_der_certificate(&leaf).unwrap()).unwrap();
+
+    key.verify_prehash(&Sha256::digest(&message), &signature)
+        .expect("P-384 signature over a SHA-256 digest should verify");
+    assert!(key.verify_prehash(&Sha256::digest(b"other"), &signature).is_err());
+}
+
+#[test]
+fn test_certificate_signature_algorithm_allowlist() {
+    use sigstore_verifier::crypto::algorithm::{
+        SignatureAlgorithm, DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS,
+    };
+    use sigstore_verifier::error::CertificateError;
+    use sigstore_verifier::verifier::certificate::certificate_signature_algorithm;
+
+    let p384_leaf = load_cert("p384-leaf.pem");
+    let p384_leaf = parse_der_certificate(&p384_leaf).unwrap();
+    assert_eq!(
+        certificate_signature_algorithm(&p384_leaf, DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS).unwrap(),
+        SignatureAlgorithm::EcdsaSha384
+    );
+    assert!(matches!(
+        certificate_signature_algorithm(&p384_leaf, &[SignatureAlgorithm::EcdsaSha256]),
+        Err(CertificateError::DisallowedSignatureAlgorithm(_))
+    ));
+
+    // SHA-1 is rejected even if nothing else is configured
+    let sha1_leaf = load_cert("p256-sha1-leaf.pem");
+    let sha1_leaf = parse_der_certificate(&sha1_leaf).unwrap();
+    assert!(matches!(
+        certificate_signature_algorithm(&sha1_leaf, DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS),
+        Err(CertificateError::WeakSignatureAlgorithm(name)) if name == "ecdsa-with-SHA1"
+    ));
+}
+
+#[test]
+fn test_verify_simple_signing() {
+    use std::collections::HashMap;
+
+    use base64::prelude::*;
+    use sigstore_verifier::crypto::hash::sha256;
+    use sigstore_verifier::error::VerificationError;
+    use sigstore_verifier::parser::simple_signing::{
+        parse_cosign_signature_layer, BUNDLE_ANNOTATION, CERTIFICATE_ANNOTATION, SIGNATURE_ANNOTATION,
+    };
+    use sigstore_verifier::types::digest::Digest;
+    use sigstore_verifier::types::result::VerificationOptions;
+    use sigstore_verifier::AttestationVerifier;
+
+    let payload = std::fs::read(sample_path("simple-signing.json")).unwrap();
+    let signature = std::fs::read(sample_path("simple-signing.sig")).unwrap();
+    let leaf_der = load_cert("simple-signing-leaf.pem");
+    let not_before = parse_der_certificate(&leaf_der).unwrap().validity().not_before.timestamp();
+    let leaf_pem = std::fs::read_to_string(sample_path("simple-signing-leaf.pem")).unwrap();
+    // The logged hashedrekord body must commit to this payload, signature and certificate
+    let body = serde_json::json!({
+        "apiVersion": "0.0.1",
+        "kind": "hashedrekord",
+        "spec": {
+            "data": { "hash": { "algorithm": "sha256", "value": hex::encode(sha256(&payload)) } },
+            "signature": {
+                "content": BASE64_STANDARD.encode(&signature),
+                "publicKey": { "content": BASE64_STANDARD.encode(&leaf_pem) },
+            },
+        },
+    });
+    let rekor_bundle = serde_json::json!({
+        "SignedEntryTimestamp": "AA==",
+        "Payload": {
+            "body": BASE64_STANDARD.encode(body.to_string()),
+            "integratedTime": not_before + 60,
+            "logIndex": 1,
+            "logID": "00".repeat(32),
+        },
+    });
+    let annotations = HashMap::from([
+        (SIGNATURE_ANNOTATION.to_string(), BASE64_STANDARD.encode(&signature)),
+        (CERTIFICATE_ANNOTATION.to_string(), leaf_pem),
+        (BUNDLE_ANNOTATION.to_string(), rekor_bundle.to_string()),
+    ]);
+    let layer = parse_cosign_signature_layer(payload, &annotations).unwrap();
+
+    let trust_bundle = CertificateChain {
+        leaf: vec![],
+        intermediates: vec![load_cert("simple-signing-intermediate.pem")],
+        root: load_cert("simple-signing-root.pem"),
+    };
+    let manifest_digest: Digest = "sha256:6e2a2e1a5e3b4d1d8f7c0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d"
+        .parse()
+        .unwrap();
+
+    let verifier = AttestationVerifier::new();
+    let result = verifier
+        .verify_simple_signing(&layer, &manifest_digest, VerificationOptions::default(), &trust_bundle, None)
+        .expect("Simple signing verification failed");
+    assert_eq!(result.subject_digest, manifest_digest);
+    assert_eq!(result.signing_time.timestamp(), not_before + 60);
+
+    let other_digest = Digest::sha256([1; 32]);
+    assert!(matches!(
+        verifier.verify_simple_signing(&layer, &other_digest, VerificationOptions::default(), &trust_bundle, None),
+        Err(VerificationError::SubjectDigestMismatch { .. })
+    ));
+
+    let mut tampered = layer.clone();
+    tampered.payload = String::from_utf8(layer.payload.clone())
+        .unwrap()
+        .replace("\"creator\":\"cosign\"", "\"creator\":\"other\"")
+        .into_bytes();
+    assert!(matches!(
+        verifier.verify_simple_signing(&tampered, &manifest_digest, VerificationOptions::default(), &trust_bundle, None),
+        Err(VerificationError::Signature(_))
+    ));
+}
@@ -0,0 +1,198 @@
+//! Host-side helpers for batch proofs
+//!
+//! A batch proof commits only the Merkle root of the per-entry results (see
+//! [`crate::guest::BatchVerificationOutput`]). The host keeps the results and
+//! hands out an inclusion proof for each entry, so a consumer can check one
+//! artifact against the proven root without the rest of the batch.
+
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::merkle::{compute_leaf_hash, verify_inclusion_proof, MerkleTree};
+use sigstore_verifier::AttestationVerifier;
+
+use crate::guest::{batch_output, decode_batch_output, verify_batch, with_output_version};
+use crate::types::BatchProverInput;
+
+/// Per-entry results of a batch and the Merkle tree committed by the guest
+#[derive(Debug, Clone)]
+pub struct BatchResults {
+    results: Vec<Vec<u8>>,
+    tree: MerkleTree,
+}
+
+impl BatchResults {
+    /// Build the results tree from per-entry `VerifiedOutput::as_slice()` bytes
+    pub fn new(results: Vec<Vec<u8>>) -> Self {
+        let tree = MerkleTree::from_leaves(&results);
+        BatchResults { results, tree }
+    }
+
+    /// Reproduce the guest's per-entry results by verifying the batch natively
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any entry fails verification (the guest would fail too).
+    pub fn from_input(batch: &BatchProverInput) -> Result<Self, String> {
+        let verifier = AttestationVerifier::new();
+        Ok(Self::new(verify_batch(&verifier, batch)?))
+    }
+
+    /// Merkle root committed as `resultsRoot`
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    /// Per-entry results, in input order
+    pub fn results(&self) -> &[Vec<u8>] {
+        &self.results
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The public output the guest commits for this batch, version header included
+    pub fn public_output(&self) -> Vec<u8> {
+        with_output_version(&batch_output(&self.results))
+    }
+
+    /// Inclusion proof for the entry at `index`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range.
+    pub fn inclusion_proof(&self, index: usize) -> Result<BatchInclusionProof, String> {
+        let result = self
+            .results
+            .get(index)
+            .ok_or_else(|| format!("Batch entry {} out of range", index))?;
+        let proof = self
+            .tree
+            .inclusion_proof(index as u64, self.tree.size())
+            .map_err(|e| e.to_string())?;
+
+        Ok(BatchInclusionProof {
+            index: index as u64,
+            result_count: self.tree.size(),
+            result: result.clone(),
+            proof,
+        })
+    }
+}
+
+/// Proof that one entry's result is part of a proven batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchInclusionProof {
+    /// Position of the entry in the batch
+    pub index: u64,
+
+    /// Number of entries in the batch (`resultCount`)
+    pub result_count: u64,
+
+    /// The entry's `VerifiedOutput::as_slice()` bytes
+    pub result: Vec<u8>,
+
+    /// RFC 6962 audit path from the entry's leaf to `resultsRoot`
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl BatchInclusionProof {
+    /// Check the proof against a batch root and size
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result is not included at `index` under `root`.
+    pub fn verify(&self, root: &[u8; 32], result_count: u64) -> Result<(), String> {
+        if result_count != self.result_count {
+            return Err(format!(
+                "Batch size mismatch: proof is for {} entries, output has {}",
+                self.result_count, result_count
+            ));
+        }
+        let proof: Vec<Vec<u8>> = self.proof.iter().map(|hash| hash.to_vec()).collect();
+        verify_inclusion_proof(
+            &compute_leaf_hash(&self.result),
+            self.index,
+            result_count,
+            &proof,
+            root,
+        )
+        .map_err(|e| format!("Batch entry {}: {}", self.index, e))
+    }
+
+    /// Check the proof against the ABI-encoded public output of a batch proof
+    pub fn verify_output(&self, public_output: &[u8]) -> Result<(), String> {
+        let output = decode_batch_output(public_output)?;
+        self.verify(&output.resultsRoot.0, output.resultCount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entry results `[i; 3]` for `i` in `0..count`
+    fn results(count: u8) -> BatchResults {
+        BatchResults::new((0..count).map(|i| vec![i; 3]).collect())
+    }
+
+    #[test]
+    fn test_results_root_known_answers() {
+        // RFC 6962 roots computed independently of `MerkleTree`
+        let vectors = [
+            (1, "df3f619804a92fdb4057192dc43dd748ea778adc52bc498ce80524c014b81119"),
+            (2, "a2a90e58478f28cbf45e91ea2993b4cb319aa72bd34bd34dcf4310ed167ce29f"),
+            (3, "a11a0ca433fdaca055a3a289c457b3f619c8e32fc773bbebf78fed3268bc4c87"),
+            (4, "178cbab53f812b6688ce963e2abe63cc3ca7f6b4afa1f58db651f0f3dcb8f7cb"),
+            (8, "c88ad3f5c0edf360dee2299dc572423a0ba995aeeb0c0821ff1e4413d42505c1"),
+        ];
+        for (count, root) in vectors {
+            let batch = results(count);
+            assert_eq!(hex::encode(batch.root()), root, "{} leaves", count);
+
+            let output = batch.public_output();
+            for index in 0..batch.len() {
+                let proof = batch.inclusion_proof(index).unwrap();
+                proof.verify_output(&output).unwrap();
+            }
+            assert!(batch.inclusion_proof(batch.len()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_batch_output_requires_version_header() {
+        let batch = results(2);
+        let proof = batch.inclusion_proof(0).unwrap();
+        let unversioned = batch_output(batch.results());
+        assert!(proof.verify_output(&unversioned).is_err());
+        // A root starting with two zero bytes reads as a version 0 output
+        let mut zero_prefixed = unversioned;
+        zero_prefixed[..2].fill(0);
+        assert_eq!(
+            proof.verify_output(&zero_prefixed).unwrap_err(),
+            "Batch output has no version header"
+        );
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejections() {
+        let batch = results(3);
+        let mut proof = batch.inclusion_proof(1).unwrap();
+        assert!(proof.verify(&batch.root(), 4).unwrap_err().starts_with("Batch size mismatch"));
+        assert!(proof.verify(&results(4).root(), 3).is_err());
+
+        proof.result = vec![9; 3];
+        assert!(proof.verify(&batch.root(), 3).unwrap_err().starts_with("Batch entry 1: "));
+    }
+
+    #[test]
+    fn test_empty_batch_rejected() {
+        assert_eq!(
+            BatchResults::from_input(&BatchProverInput::new()).unwrap_err(),
+            "Batch has no entries"
+        );
+    }
+}
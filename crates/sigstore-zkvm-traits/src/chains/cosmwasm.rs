@@ -0,0 +1,68 @@
+//! CosmWasm verification messages
+//!
+//! CosmWasm contracts receive JSON messages with binary fields as base64
+//! (`cosmwasm_std::Binary`). [`ExecuteMsg`] is the message of a verifier
+//! contract that checks a Groth16 proof against the program identifier and
+//! decodes the public values; its JSON form is
+//!
+//! ```text
+//! {"verify_attestation": {"zkvm": "sp1", "program_id": "0x...", "proof": "<base64>", "public_values": "<base64>"}}
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! let msg = ExecuteMsg::from_artifact(&artifact)?;
+//! let json = serde_json::to_string(&msg)?;
+//! // wasmd tx wasm execute <contract> "$json" ...
+//! ```
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::proof_and_public_values;
+use crate::codec::{decode_output, PublicValues};
+use crate::utils::ProofArtifact;
+
+/// Execute message of a CosmWasm attestation verifier contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    VerifyAttestation {
+        /// zkVM that produced the proof (e.g. "sp1", "risc0")
+        zkvm: String,
+        /// Program identifier, `0x`-prefixed hex
+        program_id: String,
+        /// Proof bytes, base64
+        proof: String,
+        /// Committed public values, base64
+        public_values: String,
+    },
+}
+
+impl ExecuteMsg {
+    /// Build the message from a proof artifact
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact has no proof or its hex fields do not decode.
+    pub fn from_artifact(artifact: &ProofArtifact) -> Result<Self, String> {
+        let (proof, public_values) = proof_and_public_values(artifact)?;
+        let base64 = base64::engine::general_purpose::STANDARD;
+        Ok(ExecuteMsg::VerifyAttestation {
+            zkvm: artifact.zkvm.clone(),
+            program_id: format!("0x{}", artifact.program_id.trim_start_matches("0x")),
+            proof: base64.encode(proof),
+            public_values: base64.encode(public_values),
+        })
+    }
+
+    /// Decode the committed public values
+    pub fn public_values(&self) -> Result<PublicValues, String> {
+        let ExecuteMsg::VerifyAttestation { public_values, .. } = self;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(public_values)
+            .map_err(|e| format!("Public values are not valid base64: {}", e))?;
+        decode_output(&bytes).map(|(_, values)| values)
+    }
+}
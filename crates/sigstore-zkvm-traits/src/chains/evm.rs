@@ -0,0 +1,106 @@
+//! EVM call data for the `SigstoreAttestationVerifier` contract
+//!
+//! The reference contract in `contracts/` verifies a proof with
+//! `verifyAndAttestWithZKProof(bytes output, ZkCoProcessorType zkCoProcessor, bytes proofBytes)`,
+//! where `output` is the artifact's journal and `proofBytes` its proof. The
+//! builders here turn a [`ProofArtifact`] into that call, ready to sign and send
+//! with any Ethereum client.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let artifact: ProofArtifact = serde_json::from_slice(&std::fs::read("proof.json")?)?;
+//! let call = artifact.to_verify_and_attest_calldata(registry)?;
+//! let tx = TransactionRequest::default().to(call.to).input(call.data.into());
+//! ```
+
+use alloy_primitives::{Address, Bytes};
+use alloy_sol_types::{sol, SolCall};
+use serde::{Deserialize, Serialize};
+
+use super::proof_and_public_values;
+use crate::utils::ProofArtifact;
+
+sol! {
+    /// Entry point of `ISigstoreAttestationVerifier`; `zkCoProcessor` is the
+    /// Solidity `ZkCoProcessorType` enum, which the ABI encodes as `uint8`
+    function verifyAndAttestWithZKProof(bytes output, uint8 zkCoProcessor, bytes proofBytes);
+}
+
+/// Selector of `verifyAndAttestWithZKProof(bytes,uint8,bytes)`
+pub const VERIFY_AND_ATTEST_SELECTOR: [u8; 4] = verifyAndAttestWithZKProofCall::SELECTOR;
+
+/// `ZkCoProcessorType` of `ISigstoreAttestationVerifier.sol`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ZkCoProcessorType {
+    RiscZero = 1,
+    Succinct = 2,
+    Pico = 3,
+}
+
+impl ZkCoProcessorType {
+    /// Co-processor of a `ProofArtifact::zkvm` name ("risc0", "sp1" or "pico")
+    pub fn from_zkvm(zkvm: &str) -> Option<Self> {
+        match zkvm {
+            "risc0" => Some(ZkCoProcessorType::RiscZero),
+            "sp1" => Some(ZkCoProcessorType::Succinct),
+            "pico" => Some(ZkCoProcessorType::Pico),
+            _ => None,
+        }
+    }
+}
+
+/// A contract call: the address to send the transaction to and its input data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvmCall {
+    pub to: Address,
+    pub data: Bytes,
+}
+
+impl ProofArtifact {
+    /// Typed `verifyAndAttestWithZKProof` arguments of the artifact
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact is from an unknown zkVM, has no proof,
+    /// or its hex fields do not decode.
+    pub fn verify_and_attest_call(&self) -> Result<verifyAndAttestWithZKProofCall, String> {
+        let zk_co_processor = ZkCoProcessorType::from_zkvm(&self.zkvm)
+            .ok_or_else(|| format!("No EVM co-processor type for zkVM {}", self.zkvm))?;
+        let (proof, output) = proof_and_public_values(self)?;
+        Ok(verifyAndAttestWithZKProofCall {
+            output: output.into(),
+            zkCoProcessor: zk_co_processor as u8,
+            proofBytes: proof.into(),
+        })
+    }
+
+    /// Call data for a contract taking the `verifyAndAttestWithZKProof` arguments
+    ///
+    /// `function_selector` lets integrators call their own contract, e.g. a
+    /// registry whose `register(bytes,uint8,bytes)` forwards to the verifier;
+    /// pass [`VERIFY_AND_ATTEST_SELECTOR`] to call the verifier itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_address` - Contract to call
+    /// * `function_selector` - Selector of a function with parameters `(bytes, uint8, bytes)`
+    ///
+    /// # Errors
+    ///
+    /// See [`ProofArtifact::verify_and_attest_call`].
+    pub fn to_evm_calldata(&self, registry_address: Address, function_selector: [u8; 4]) -> Result<EvmCall, String> {
+        let mut data = self.verify_and_attest_call()?.abi_encode();
+        data[..4].copy_from_slice(&function_selector);
+        Ok(EvmCall {
+            to: registry_address,
+            data: data.into(),
+        })
+    }
+
+    /// Call data for `verifyAndAttestWithZKProof` on a `SigstoreAttestationVerifier`
+    pub fn to_verify_and_attest_calldata(&self, verifier_address: Address) -> Result<EvmCall, String> {
+        self.to_evm_calldata(verifier_address, VERIFY_AND_ATTEST_SELECTOR)
+    }
+}
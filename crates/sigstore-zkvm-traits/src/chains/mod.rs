@@ -0,0 +1,29 @@
+//! Helpers for verifying proofs on chain
+//!
+//! Each submodule turns a [`crate::utils::ProofArtifact`] into the message a
+//! verifier on that chain expects. Verifiers that decode the public values
+//! on-chain should be proven with the matching [`crate::codec::OutputCodecId`]
+//! (raw for the EVM `SigstoreAttestationVerifier`, Borsh for Solana, SSZ or ABI
+//! for CosmWasm).
+
+pub mod cosmwasm;
+pub mod evm;
+pub mod solana;
+
+use crate::utils::ProofArtifact;
+
+/// Decode a `0x`-prefixed hex field of an artifact
+fn artifact_bytes(value: &str, field: &str) -> Result<Vec<u8>, String> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| format!("Artifact {} is not valid hex: {}", field, e))
+}
+
+/// Proof and public values of an artifact, rejecting artifacts without a proof
+fn proof_and_public_values(artifact: &ProofArtifact) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let proof = artifact_bytes(&artifact.proof, "proof")?;
+    if proof.is_empty() {
+        return Err("Artifact has no proof (was it generated in DEV_MODE?)".to_string());
+    }
+    let public_values = artifact_bytes(&artifact.journal, "journal")?;
+    Ok((proof, public_values))
+}
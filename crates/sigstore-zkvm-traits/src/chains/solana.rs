@@ -0,0 +1,63 @@
+//! Solana verification through the `sp1-solana` Groth16 verifier
+//!
+//! The `sp1-solana` crate verifies SP1 Groth16 proofs inside a Solana program
+//! with `sp1_solana::verify_proof(proof, public_inputs, vkey_hash, GROTH16_VK_BYTES)`.
+//! Its example program takes the Borsh-encoded [`SP1Groth16Proof`] as
+//! instruction data and the vkey hash as a program constant, which is the
+//! artifact's `program_id`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let artifact: ProofArtifact = serde_json::from_slice(&std::fs::read("proof.json")?)?;
+//! let proof = SP1Groth16Proof::from_artifact(&artifact)?;
+//! let instruction = Instruction::new_with_bytes(program_id, &proof.instruction_data()?, vec![]);
+//! ```
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::proof_and_public_values;
+use crate::codec::{decode_output, PublicValues};
+use crate::utils::ProofArtifact;
+
+/// Instruction data of the `sp1-solana` example verifier program
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SP1Groth16Proof {
+    /// Groth16 proof bytes, prefixed with the 4-byte verifier hash
+    pub proof: Vec<u8>,
+
+    /// Committed public values, in the codec the proof was generated with
+    pub sp1_public_inputs: Vec<u8>,
+}
+
+impl SP1Groth16Proof {
+    /// Build the instruction payload from an SP1 Groth16 artifact
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact is not from SP1, has no proof, or its
+    /// hex fields do not decode.
+    pub fn from_artifact(artifact: &ProofArtifact) -> Result<Self, String> {
+        if artifact.zkvm != "sp1" {
+            return Err(format!(
+                "sp1-solana verifies SP1 proofs, artifact is from {}",
+                artifact.zkvm
+            ));
+        }
+        let (proof, sp1_public_inputs) = proof_and_public_values(artifact)?;
+        Ok(SP1Groth16Proof {
+            proof,
+            sp1_public_inputs,
+        })
+    }
+
+    /// Borsh-encoded instruction data
+    pub fn instruction_data(&self) -> Result<Vec<u8>, String> {
+        borsh::to_vec(self).map_err(|e| format!("Failed to Borsh encode proof: {}", e))
+    }
+
+    /// Decode the committed public values
+    pub fn public_values(&self) -> Result<PublicValues, String> {
+        decode_output(&self.sp1_public_inputs).map(|(_, values)| values)
+    }
+}
@@ -0,0 +1,833 @@
+//! Pluggable encodings of the public output
+//!
+//! The guest commits its output in the encoding chosen by
+//! `ProverInput::output_codec`, so one guest binary serves verifiers on
+//! different chains:
+//!
+//! | Codec   | ID | Committed bytes                                   | Target          |
+//! |---------|----|---------------------------------------------------|-----------------|
+//! | `raw`   | 0  | `VerifiedOutput::as_slice()`                      | EVM (existing)  |
+//! | `abi`   | 1  | `0x01` \|\| ABI-encoded `PublicValuesAbi`         | EVM             |
+//! | `borsh` | 2  | `0x02` \|\| Borsh-encoded [`PublicValues`]        | Solana          |
+//! | `ssz`   | 3  | `0x03` \|\| SSZ-encoded [`PublicValues`]          | Cosmos, beacon  |
+//!
+//! The codec ID is always the first byte after the version header (see
+//! [`crate::guest`]). For `raw` it is the high byte of the big-endian signing
+//! time, which is zero, so raw outputs are unchanged and existing parsers keep
+//! working.
+
+use alloy_sol_types::{sol, SolValue};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::types::certificate::OidcIdentity;
+use sigstore_verifier::types::digest::{Digest, DigestAlgorithm};
+use sigstore_verifier::types::result::{
+    CertificateChainHashes, TimestampProof, TimestampProofType, VerificationResult,
+};
+use sigstore_verifier::types::time::Timestamp;
+
+use crate::guest::{split_output_version, ProverOutput, VerifiedOutput, FAILED_OUTPUT_HEADER};
+use crate::reproduced::REPRODUCED_BUILD_OUTPUT_HEADER;
+use crate::tee::ATTESTED_BUILD_OUTPUT_HEADER;
+
+sol! {
+    /// ABI layout of [`PublicValues`], for `OutputCodecId::Abi`
+    #[derive(Debug, PartialEq)]
+    struct PublicValuesAbi {
+        uint64 signingTime;
+        uint8 timestampProofType;
+        bytes32[] certificateHashes;
+        bytes subjectDigest;
+        uint8 subjectDigestAlgorithm;
+        string oidcIssuer;
+        string oidcSubject;
+        string oidcWorkflowRef;
+        string oidcRepository;
+        string oidcEventName;
+        bytes32[] tsaChainHashes;
+        uint8 messageImprintAlgorithm;
+        bytes messageImprint;
+        bytes32 rekorLogId;
+        uint64 rekorLogIndex;
+        uint64 rekorEntryIndex;
+        bytes32 optionsHash;
+        uint64 referenceTime;
+        uint64 signingAge;
+    }
+}
+
+/// Identifier of an output encoding, committed as the first byte of the output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum OutputCodecId {
+    /// `VerifiedOutput::as_slice()`, parsed by the on-chain `VerificationResultParser`
+    #[default]
+    Raw = 0,
+    /// Solidity ABI encoding of `PublicValuesAbi`
+    Abi = 1,
+    /// Borsh encoding of [`PublicValues`]
+    Borsh = 2,
+    /// SSZ encoding of [`PublicValues`]
+    Ssz = 3,
+}
+
+impl OutputCodecId {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(OutputCodecId::Raw),
+            1 => Some(OutputCodecId::Abi),
+            2 => Some(OutputCodecId::Borsh),
+            3 => Some(OutputCodecId::Ssz),
+            _ => None,
+        }
+    }
+
+    /// Name used in configuration and on the command line (e.g. "borsh")
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputCodecId::Raw => "raw",
+            OutputCodecId::Abi => "abi",
+            OutputCodecId::Borsh => "borsh",
+            OutputCodecId::Ssz => "ssz",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputCodecId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for OutputCodecId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(OutputCodecId::Raw),
+            "abi" => Ok(OutputCodecId::Abi),
+            "borsh" => Ok(OutputCodecId::Borsh),
+            "ssz" => Ok(OutputCodecId::Ssz),
+            _ => Err(format!(
+                "Unknown output codec '{}', expected raw, abi, borsh or ssz",
+                s
+            )),
+        }
+    }
+}
+
+/// Flat form of a single-bundle public output, shared by all codecs
+///
+/// Fields follow the raw layout: the `VerificationResult` fields (with the
+/// same empty-string and zero defaults as `VerificationResult::as_slice()`)
+/// followed by the `VerifiedOutput` trailer. The field order is the encoding
+/// order for every codec.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize,
+)]
+pub struct PublicValues {
+    /// Signing time (Unix seconds)
+    pub signing_time: u64,
+    /// `TimestampProofType` as u8
+    pub timestamp_proof_type: u8,
+    /// Signing certificate chain hashes: [leaf, ...intermediates, root]
+    pub certificate_hashes: Vec<[u8; 32]>,
+    pub subject_digest: Vec<u8>,
+    /// `DigestAlgorithm` as u8
+    pub subject_digest_algorithm: u8,
+    pub oidc_issuer: String,
+    pub oidc_subject: String,
+    pub oidc_workflow_ref: String,
+    pub oidc_repository: String,
+    pub oidc_event_name: String,
+    /// TSA chain hashes (RFC 3161 only): [leaf, ...intermediates, root]
+    pub tsa_chain_hashes: Vec<[u8; 32]>,
+    /// `DigestAlgorithm` of the message imprint as u8 (RFC 3161 only)
+    pub message_imprint_algorithm: u8,
+    /// RFC 3161 message imprint (RFC 3161 only)
+    pub message_imprint: Vec<u8>,
+    /// Rekor log ID (Rekor only)
+    pub rekor_log_id: [u8; 32],
+    /// Rekor tree leaf index (Rekor only)
+    pub rekor_log_index: u64,
+    /// Rekor entry index (Rekor only)
+    pub rekor_entry_index: u64,
+    /// Hash of the canonical verification options
+    pub options_hash: [u8; 32],
+    /// Unix time the signing age is measured against, 0 if none was supplied
+    pub reference_time: u64,
+    /// Seconds between the signing time and `reference_time`
+    pub signing_age: u64,
+}
+
+impl PublicValues {
+    /// Flatten a `VerifiedOutput`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output's result cannot be decoded.
+    pub fn from_output(output: &VerifiedOutput) -> Result<Self, String> {
+        let result = output.verification_result()?;
+
+        let mut certificate_hashes = vec![result.certificate_hashes.leaf];
+        certificate_hashes.extend_from_slice(&result.certificate_hashes.intermediates);
+        certificate_hashes.push(result.certificate_hashes.root);
+
+        let oidc = result.oidc_identity.unwrap_or(OidcIdentity {
+            issuer: None,
+            subject: None,
+            workflow_ref: None,
+            repository: None,
+            event_name: None,
+        });
+
+        let mut values = PublicValues {
+            signing_time: result.signing_time.timestamp() as u64,
+            timestamp_proof_type: TimestampProofType::None as u8,
+            certificate_hashes,
+            subject_digest: result.subject_digest.bytes,
+            subject_digest_algorithm: result.subject_digest.algorithm as u8,
+            oidc_issuer: oidc.issuer.unwrap_or_default(),
+            oidc_subject: oidc.subject.unwrap_or_default(),
+            oidc_workflow_ref: oidc.workflow_ref.unwrap_or_default(),
+            oidc_repository: oidc.repository.unwrap_or_default(),
+            oidc_event_name: oidc.event_name.unwrap_or_default(),
+            options_hash: output.options_hash,
+            reference_time: output.reference_time,
+            signing_age: output.signing_age,
+            ..Default::default()
+        };
+
+        match result.timestamp_proof {
+            TimestampProof::None => {}
+            TimestampProof::Rfc3161 {
+                tsa_chain_hashes,
+                message_imprint_algorithm,
+                message_imprint,
+            } => {
+                values.timestamp_proof_type = TimestampProofType::Rfc3161 as u8;
+                values.tsa_chain_hashes.push(tsa_chain_hashes.leaf);
+                values
+                    .tsa_chain_hashes
+                    .extend_from_slice(&tsa_chain_hashes.intermediates);
+                values.tsa_chain_hashes.push(tsa_chain_hashes.root);
+                values.message_imprint_algorithm = message_imprint_algorithm as u8;
+                values.message_imprint = message_imprint;
+            }
+            TimestampProof::Rekor {
+                log_id,
+                log_index,
+                entry_index,
+            } => {
+                values.timestamp_proof_type = TimestampProofType::Rekor as u8;
+                values.rekor_log_id = log_id;
+                values.rekor_log_index = log_index;
+                values.rekor_entry_index = entry_index;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Rebuild the `VerificationResult`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a certificate chain has fewer than two hashes or
+    /// the signing time is out of range.
+    pub fn verification_result(&self) -> Result<VerificationResult, String> {
+        let oidc_fields = [
+            &self.oidc_issuer,
+            &self.oidc_subject,
+            &self.oidc_workflow_ref,
+            &self.oidc_repository,
+            &self.oidc_event_name,
+        ];
+        let oidc_identity = if oidc_fields.iter().all(|field| field.is_empty()) {
+            None
+        } else {
+            Some(OidcIdentity {
+                issuer: non_empty(&self.oidc_issuer),
+                subject: non_empty(&self.oidc_subject),
+                workflow_ref: non_empty(&self.oidc_workflow_ref),
+                repository: non_empty(&self.oidc_repository),
+                event_name: non_empty(&self.oidc_event_name),
+            })
+        };
+
+        let timestamp_proof = match TimestampProofType::from_u8(self.timestamp_proof_type) {
+            TimestampProofType::None => TimestampProof::None,
+            TimestampProofType::Rfc3161 => TimestampProof::Rfc3161 {
+                tsa_chain_hashes: chain_hashes(&self.tsa_chain_hashes, "TSA")?,
+                message_imprint_algorithm: DigestAlgorithm::from_u8(self.message_imprint_algorithm),
+                message_imprint: self.message_imprint.clone(),
+            },
+            TimestampProofType::Rekor => TimestampProof::Rekor {
+                log_id: self.rekor_log_id,
+                log_index: self.rekor_log_index,
+                entry_index: self.rekor_entry_index,
+            },
+        };
+
+        let signing_time = i64::try_from(self.signing_time)
+            .map(Timestamp::from_unix)
+            .map_err(|_| format!("Invalid timestamp: {}", self.signing_time))?;
+
+        Ok(VerificationResult::new(
+            chain_hashes(&self.certificate_hashes, "Certificate")?,
+            signing_time,
+            Digest {
+                algorithm: DigestAlgorithm::from_u8(self.subject_digest_algorithm),
+                bytes: self.subject_digest.clone(),
+            },
+            oidc_identity,
+            timestamp_proof,
+        ))
+    }
+
+    /// Rebuild the raw `VerifiedOutput`
+    pub fn to_output(&self) -> Result<VerifiedOutput, String> {
+        Ok(VerifiedOutput {
+            result: self.verification_result()?.as_slice(),
+            options_hash: self.options_hash,
+            reference_time: self.reference_time,
+            signing_age: self.signing_age,
+        })
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn chain_hashes(hashes: &[[u8; 32]], name: &str) -> Result<CertificateChainHashes, String> {
+    match hashes {
+        [leaf, intermediates @ .., root] => Ok(CertificateChainHashes {
+            leaf: *leaf,
+            intermediates: intermediates.to_vec(),
+            root: *root,
+        }),
+        _ => Err(format!(
+            "{} chain hashes must have at least 2 elements (leaf and root), got {}",
+            name,
+            hashes.len()
+        )),
+    }
+}
+
+/// Encoding of [`PublicValues`] for one target chain
+///
+/// Implementations encode the values only; [`encode_output`] prefixes the
+/// codec ID and [`decode_output`] strips it.
+pub trait OutputCodec {
+    /// ID committed in front of the encoded values
+    const ID: OutputCodecId;
+
+    fn encode(values: &PublicValues) -> Result<Vec<u8>, String>;
+
+    /// # Errors
+    ///
+    /// Returns an error if `data` is not a complete encoding of `PublicValues`.
+    fn decode(data: &[u8]) -> Result<PublicValues, String>;
+}
+
+/// `VerifiedOutput::as_slice()` layout
+pub struct RawCodec;
+
+impl OutputCodec for RawCodec {
+    const ID: OutputCodecId = OutputCodecId::Raw;
+
+    fn encode(values: &PublicValues) -> Result<Vec<u8>, String> {
+        Ok(values.to_output()?.as_slice())
+    }
+
+    fn decode(data: &[u8]) -> Result<PublicValues, String> {
+        PublicValues::from_output(&VerifiedOutput::from_body(data)?)
+    }
+}
+
+/// Solidity ABI encoding of `PublicValuesAbi`
+pub struct AbiCodec;
+
+impl OutputCodec for AbiCodec {
+    const ID: OutputCodecId = OutputCodecId::Abi;
+
+    fn encode(values: &PublicValues) -> Result<Vec<u8>, String> {
+        Ok(PublicValuesAbi {
+            signingTime: values.signing_time,
+            timestampProofType: values.timestamp_proof_type,
+            certificateHashes: values.certificate_hashes.iter().map(|h| (*h).into()).collect(),
+            subjectDigest: values.subject_digest.clone().into(),
+            subjectDigestAlgorithm: values.subject_digest_algorithm,
+            oidcIssuer: values.oidc_issuer.clone(),
+            oidcSubject: values.oidc_subject.clone(),
+            oidcWorkflowRef: values.oidc_workflow_ref.clone(),
+            oidcRepository: values.oidc_repository.clone(),
+            oidcEventName: values.oidc_event_name.clone(),
+            tsaChainHashes: values.tsa_chain_hashes.iter().map(|h| (*h).into()).collect(),
+            messageImprintAlgorithm: values.message_imprint_algorithm,
+            messageImprint: values.message_imprint.clone().into(),
+            rekorLogId: values.rekor_log_id.into(),
+            rekorLogIndex: values.rekor_log_index,
+            rekorEntryIndex: values.rekor_entry_index,
+            optionsHash: values.options_hash.into(),
+            referenceTime: values.reference_time,
+            signingAge: values.signing_age,
+        }
+        .abi_encode())
+    }
+
+    fn decode(data: &[u8]) -> Result<PublicValues, String> {
+        let decoded = PublicValuesAbi::abi_decode(data)
+            .map_err(|e| format!("Failed to ABI decode public values: {}", e))?;
+        Ok(PublicValues {
+            signing_time: decoded.signingTime,
+            timestamp_proof_type: decoded.timestampProofType,
+            certificate_hashes: decoded.certificateHashes.iter().map(|h| h.0).collect(),
+            subject_digest: decoded.subjectDigest.to_vec(),
+            subject_digest_algorithm: decoded.subjectDigestAlgorithm,
+            oidc_issuer: decoded.oidcIssuer,
+            oidc_subject: decoded.oidcSubject,
+            oidc_workflow_ref: decoded.oidcWorkflowRef,
+            oidc_repository: decoded.oidcRepository,
+            oidc_event_name: decoded.oidcEventName,
+            tsa_chain_hashes: decoded.tsaChainHashes.iter().map(|h| h.0).collect(),
+            message_imprint_algorithm: decoded.messageImprintAlgorithm,
+            message_imprint: decoded.messageImprint.to_vec(),
+            rekor_log_id: decoded.rekorLogId.0,
+            rekor_log_index: decoded.rekorLogIndex,
+            rekor_entry_index: decoded.rekorEntryIndex,
+            options_hash: decoded.optionsHash.0,
+            reference_time: decoded.referenceTime,
+            signing_age: decoded.signingAge,
+        })
+    }
+}
+
+/// Borsh encoding of [`PublicValues`] (Solana programs can decode it with `borsh`)
+pub struct BorshCodec;
+
+impl OutputCodec for BorshCodec {
+    const ID: OutputCodecId = OutputCodecId::Borsh;
+
+    fn encode(values: &PublicValues) -> Result<Vec<u8>, String> {
+        borsh::to_vec(values).map_err(|e| format!("Failed to Borsh encode public values: {}", e))
+    }
+
+    fn decode(data: &[u8]) -> Result<PublicValues, String> {
+        borsh::from_slice(data).map_err(|e| format!("Failed to Borsh decode public values: {}", e))
+    }
+}
+
+/// SSZ encoding of [`PublicValues`] as a container
+///
+/// Integers are little-endian, `[u8; 32]` is `Bytes32`, `Vec<[u8; 32]>` is
+/// `List[Bytes32]` and byte vectors and strings are `List[uint8]`. Variable-size
+/// fields are replaced by 4-byte offsets in the fixed part, as usual for SSZ
+/// containers. The container is small and fixed, so it is encoded here rather
+/// than through an SSZ crate.
+pub struct SszCodec;
+
+/// Size of the fixed part: 10 fixed-size fields (107 bytes) and 9 offsets
+const SSZ_FIXED_LEN: usize = 107 + 9 * 4;
+
+impl OutputCodec for SszCodec {
+    const ID: OutputCodecId = OutputCodecId::Ssz;
+
+    fn encode(values: &PublicValues) -> Result<Vec<u8>, String> {
+        let mut writer = SszWriter::default();
+        writer.fixed(&values.signing_time.to_le_bytes());
+        writer.fixed(&[values.timestamp_proof_type]);
+        writer.variable(values.certificate_hashes.concat());
+        writer.variable(values.subject_digest.clone());
+        writer.fixed(&[values.subject_digest_algorithm]);
+        writer.variable(values.oidc_issuer.as_bytes().to_vec());
+        writer.variable(values.oidc_subject.as_bytes().to_vec());
+        writer.variable(values.oidc_workflow_ref.as_bytes().to_vec());
+        writer.variable(values.oidc_repository.as_bytes().to_vec());
+        writer.variable(values.oidc_event_name.as_bytes().to_vec());
+        writer.variable(values.tsa_chain_hashes.concat());
+        writer.fixed(&[values.message_imprint_algorithm]);
+        writer.variable(values.message_imprint.clone());
+        writer.fixed(&values.rekor_log_id);
+        writer.fixed(&values.rekor_log_index.to_le_bytes());
+        writer.fixed(&values.rekor_entry_index.to_le_bytes());
+        writer.fixed(&values.options_hash);
+        writer.fixed(&values.reference_time.to_le_bytes());
+        writer.fixed(&values.signing_age.to_le_bytes());
+        writer.finish()
+    }
+
+    fn decode(data: &[u8]) -> Result<PublicValues, String> {
+        let mut reader = SszReader::new(data)?;
+        let signing_time = reader.u64()?;
+        let timestamp_proof_type = reader.u8()?;
+        let certificate_hashes = reader.offset()?;
+        let subject_digest = reader.offset()?;
+        let subject_digest_algorithm = reader.u8()?;
+        let oidc_issuer = reader.offset()?;
+        let oidc_subject = reader.offset()?;
+        let oidc_workflow_ref = reader.offset()?;
+        let oidc_repository = reader.offset()?;
+        let oidc_event_name = reader.offset()?;
+        let tsa_chain_hashes = reader.offset()?;
+        let message_imprint_algorithm = reader.u8()?;
+        let message_imprint = reader.offset()?;
+        let rekor_log_id = reader.bytes32()?;
+        let rekor_log_index = reader.u64()?;
+        let rekor_entry_index = reader.u64()?;
+        let options_hash = reader.bytes32()?;
+        let reference_time = reader.u64()?;
+        let signing_age = reader.u64()?;
+        let parts = reader.variable_parts()?;
+
+        Ok(PublicValues {
+            signing_time,
+            timestamp_proof_type,
+            certificate_hashes: ssz_bytes32_list(parts[certificate_hashes])?,
+            subject_digest: parts[subject_digest].to_vec(),
+            subject_digest_algorithm,
+            oidc_issuer: ssz_string(parts[oidc_issuer])?,
+            oidc_subject: ssz_string(parts[oidc_subject])?,
+            oidc_workflow_ref: ssz_string(parts[oidc_workflow_ref])?,
+            oidc_repository: ssz_string(parts[oidc_repository])?,
+            oidc_event_name: ssz_string(parts[oidc_event_name])?,
+            tsa_chain_hashes: ssz_bytes32_list(parts[tsa_chain_hashes])?,
+            message_imprint_algorithm,
+            message_imprint: parts[message_imprint].to_vec(),
+            rekor_log_id,
+            rekor_log_index,
+            rekor_entry_index,
+            options_hash,
+            reference_time,
+            signing_age,
+        })
+    }
+}
+
+/// Builder of an SSZ container: fixed part with offsets, then the variable parts
+#[derive(Default)]
+struct SszWriter {
+    fixed: Vec<u8>,
+    variable: Vec<u8>,
+}
+
+impl SszWriter {
+    fn fixed(&mut self, bytes: &[u8]) {
+        self.fixed.extend_from_slice(bytes);
+    }
+
+    fn variable(&mut self, bytes: Vec<u8>) {
+        let offset = (SSZ_FIXED_LEN + self.variable.len()) as u32;
+        self.fixed.extend_from_slice(&offset.to_le_bytes());
+        self.variable.extend_from_slice(&bytes);
+    }
+
+    fn finish(mut self) -> Result<Vec<u8>, String> {
+        if self.fixed.len() != SSZ_FIXED_LEN {
+            return Err(format!(
+                "SSZ fixed part is {} bytes, expected {}",
+                self.fixed.len(),
+                SSZ_FIXED_LEN
+            ));
+        }
+        self.fixed.extend_from_slice(&self.variable);
+        Ok(self.fixed)
+    }
+}
+
+/// Reader of an SSZ container; `offset()` returns the index of the variable part
+struct SszReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    offsets: Vec<usize>,
+}
+
+impl<'a> SszReader<'a> {
+    fn new(data: &'a [u8]) -> Result<Self, String> {
+        if data.len() < SSZ_FIXED_LEN {
+            return Err(format!(
+                "SSZ data too short: expected at least {} bytes, got {}",
+                SSZ_FIXED_LEN,
+                data.len()
+            ));
+        }
+        Ok(SszReader {
+            data,
+            position: 0,
+            offsets: Vec::new(),
+        })
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + N)
+            .ok_or("SSZ fixed part truncated")?;
+        self.position += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn bytes32(&mut self) -> Result<[u8; 32], String> {
+        self.take()
+    }
+
+    fn offset(&mut self) -> Result<usize, String> {
+        let offset = u32::from_le_bytes(self.take()?) as usize;
+        self.offsets.push(offset);
+        Ok(self.offsets.len() - 1)
+    }
+
+    /// Split the variable part at the collected offsets
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the first offset does not follow the fixed part or
+    /// the offsets decrease or point past the end.
+    fn variable_parts(self) -> Result<Vec<&'a [u8]>, String> {
+        if self.offsets.first() != Some(&SSZ_FIXED_LEN) {
+            return Err("SSZ first offset does not follow the fixed part".to_string());
+        }
+        let mut ends = self.offsets[1..].to_vec();
+        ends.push(self.data.len());
+        self.offsets
+            .iter()
+            .zip(ends)
+            .map(|(&start, end)| {
+                if start > end || end > self.data.len() {
+                    return Err(format!("SSZ offset {} out of order", start));
+                }
+                Ok(&self.data[start..end])
+            })
+            .collect()
+    }
+}
+
+fn ssz_bytes32_list(part: &[u8]) -> Result<Vec<[u8; 32]>, String> {
+    if !part.len().is_multiple_of(32) {
+        return Err(format!(
+            "SSZ Bytes32 list length {} is not a multiple of 32",
+            part.len()
+        ));
+    }
+    Ok(part
+        .chunks_exact(32)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect())
+}
+
+fn ssz_string(part: &[u8]) -> Result<String, String> {
+    String::from_utf8(part.to_vec()).map_err(|e| format!("SSZ string is not UTF-8: {}", e))
+}
+
+/// Encode `output` with `codec`, prefixed by the codec ID
+///
+/// `OutputCodecId::Raw` returns `output.as_slice()` unchanged.
+pub fn encode_output(codec: OutputCodecId, output: &VerifiedOutput) -> Result<Vec<u8>, String> {
+    if codec == OutputCodecId::Raw {
+        return Ok(output.as_slice());
+    }
+    let values = PublicValues::from_output(output)?;
+    let encoded = match codec {
+        OutputCodecId::Raw => unreachable!("raw outputs are returned above"),
+        OutputCodecId::Abi => AbiCodec::encode(&values)?,
+        OutputCodecId::Borsh => BorshCodec::encode(&values)?,
+        OutputCodecId::Ssz => SszCodec::encode(&values)?,
+    };
+    let mut bytes = Vec::with_capacity(1 + encoded.len());
+    bytes.push(codec as u8);
+    bytes.extend_from_slice(&encoded);
+    Ok(bytes)
+}
+
+/// Decode a committed single-bundle output in any codec
+///
+/// The version header is skipped; a version 0 output is a bare result in the
+/// raw layout (see [`crate::guest`]).
+///
+/// # Returns
+///
+/// The codec named by the first byte, and the decoded values.
+///
+/// # Errors
+///
+/// Returns an error if the codec ID is unknown, the bytes do not decode, or
+/// the output is a committed verification failure.
+pub fn decode_output(data: &[u8]) -> Result<(OutputCodecId, PublicValues), String> {
+    let data = match split_output_version(data)? {
+        // Version 0 guests committed the bare result, in the raw layout
+        (0, _) => {
+            let values = PublicValues::from_output(&VerifiedOutput::from_slice(data)?)?;
+            return Ok((OutputCodecId::Raw, values));
+        }
+        (_, body) => body,
+    };
+    if data.starts_with(&FAILED_OUTPUT_HEADER)
+        || data.starts_with(&ATTESTED_BUILD_OUTPUT_HEADER)
+        || data.starts_with(&REPRODUCED_BUILD_OUTPUT_HEADER)
+    {
+        return Err(ProverOutput::from_body(data)?.into_verified().unwrap_err());
+    }
+    let (&id, encoded) = data.split_first().ok_or("Output is empty")?;
+    let codec = OutputCodecId::from_u8(id).ok_or_else(|| format!("Unknown output codec ID {}", id))?;
+    let values = match codec {
+        OutputCodecId::Raw => RawCodec::decode(data)?,
+        OutputCodecId::Abi => AbiCodec::decode(encoded)?,
+        OutputCodecId::Borsh => BorshCodec::decode(encoded)?,
+        OutputCodecId::Ssz => SszCodec::decode(encoded)?,
+    };
+    Ok((codec, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guest::with_output_version;
+    use sigstore_verifier::types::result::VerificationOptions;
+
+    const CODECS: [OutputCodecId; 4] = [
+        OutputCodecId::Raw,
+        OutputCodecId::Abi,
+        OutputCodecId::Borsh,
+        OutputCodecId::Ssz,
+    ];
+
+    fn output(timestamp_proof: TimestampProof) -> VerifiedOutput {
+        let result = VerificationResult::new(
+            CertificateChainHashes {
+                leaf: [1; 32],
+                intermediates: vec![[2; 32]],
+                root: [3; 32],
+            },
+            Timestamp::from_unix(1_700_000_000),
+            Digest::sha256([4; 32]),
+            Some(OidcIdentity {
+                issuer: Some("https://token.actions.githubusercontent.com".to_string()),
+                subject: Some("https://github.com/org/app/.github/workflows/release.yml@refs/tags/v1".to_string()),
+                workflow_ref: Some("org/app/.github/workflows/release.yml@refs/tags/v1".to_string()),
+                repository: Some("org/app".to_string()),
+                event_name: None,
+            }),
+            timestamp_proof,
+        );
+        VerifiedOutput::new(&result, &VerificationOptions::default(), Some(1_700_000_600)).unwrap()
+    }
+
+    fn outputs() -> Vec<VerifiedOutput> {
+        vec![
+            output(TimestampProof::Rfc3161 {
+                tsa_chain_hashes: CertificateChainHashes {
+                    leaf: [5; 32],
+                    intermediates: vec![],
+                    root: [6; 32],
+                },
+                message_imprint_algorithm: DigestAlgorithm::Sha256,
+                message_imprint: vec![7; 32],
+            }),
+            output(TimestampProof::Rekor {
+                log_id: [8; 32],
+                log_index: 9,
+                entry_index: 10,
+            }),
+            output(TimestampProof::None),
+        ]
+    }
+
+    /// Output as the guest commits it: version header, then the encoding
+    fn committed(codec: OutputCodecId, output: &VerifiedOutput) -> Vec<u8> {
+        with_output_version(&encode_output(codec, output).unwrap())
+    }
+
+    #[test]
+    fn test_codec_round_trips() {
+        for output in outputs() {
+            let values = PublicValues::from_output(&output).unwrap();
+            assert_eq!(values.to_output().unwrap(), output);
+
+            for codec in CODECS {
+                let (decoded_codec, decoded) = decode_output(&committed(codec, &output)).unwrap();
+                assert_eq!(decoded_codec, codec);
+                assert_eq!(decoded, values, "{} round trip", codec);
+            }
+        }
+        assert_eq!(encode_output(OutputCodecId::Raw, &outputs()[0]).unwrap(), outputs()[0].as_slice());
+    }
+
+    #[test]
+    fn test_decode_version_0_output() {
+        // Version 0 guests committed the bare result, with no trailer
+        let output = &outputs()[0];
+        let (codec, values) = decode_output(&output.result).unwrap();
+        assert_eq!(codec, OutputCodecId::Raw);
+        assert_eq!(values.verification_result().unwrap().as_slice(), output.result);
+        assert_eq!(values.options_hash, [0; 32]);
+        assert_eq!((values.reference_time, values.signing_age), (0, 0));
+
+        // Today's encoding without its header is neither version
+        assert!(decode_output(&encode_output(OutputCodecId::Borsh, output).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_decode_truncated_output() {
+        let output = &outputs()[0];
+        for codec in CODECS {
+            let committed = committed(codec, output);
+            for len in [1, 3, committed.len() / 2] {
+                assert!(decode_output(&committed[..len]).is_err(), "{} truncated to {} bytes", codec, len);
+            }
+        }
+
+        // The last SSZ variable part runs to the end of the data, so dropping its
+        // final byte leaves a well-formed container with a shorter message imprint
+        for codec in CODECS {
+            let committed = committed(codec, output);
+            let decoded = decode_output(&committed[..committed.len() - 1]);
+            match codec {
+                OutputCodecId::Ssz => assert_ne!(decoded.unwrap().1.message_imprint, vec![7; 32]),
+                _ => assert!(decoded.is_err(), "{} missing its last byte", codec),
+            }
+        }
+        assert!(decode_output(&[]).is_err());
+    }
+
+    #[test]
+    fn test_decode_ssz_offsets_out_of_range() {
+        let committed = committed(OutputCodecId::Ssz, &outputs()[0]);
+        // Version header, codec ID, signing time and proof type precede the first offset
+        let first_offset = 2 + 1 + 8 + 1;
+
+        let mut past_end = committed.clone();
+        past_end[first_offset + 4..first_offset + 8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decode_output(&past_end).is_err());
+
+        let mut decreasing = committed.clone();
+        decreasing[first_offset + 4..first_offset + 8].copy_from_slice(&(SSZ_FIXED_LEN as u32 - 1).to_le_bytes());
+        assert!(decode_output(&decreasing).is_err());
+
+        let mut detached = committed;
+        detached[first_offset..first_offset + 4].copy_from_slice(&(SSZ_FIXED_LEN as u32 + 1).to_le_bytes());
+        assert!(decode_output(&detached).is_err());
+    }
+
+    #[test]
+    fn test_unknown_codec() {
+        let mut committed = committed(OutputCodecId::Borsh, &outputs()[0]);
+        committed[2] = 4;
+        assert_eq!(decode_output(&committed).unwrap_err(), "Unknown output codec ID 4");
+
+        assert_eq!(OutputCodecId::from_u8(4), None);
+        assert!("cbor".parse::<OutputCodecId>().is_err());
+        for codec in CODECS {
+            assert_eq!(OutputCodecId::from_u8(codec as u8), Some(codec));
+            assert_eq!(codec.name().parse::<OutputCodecId>(), Ok(codec));
+        }
+    }
+}
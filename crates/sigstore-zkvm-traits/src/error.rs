@@ -15,6 +15,25 @@ pub enum ZkVmError {
     /// Error from the underlying zkVM implementation
     ZkVmImplementationError(String),
 
+    /// Estimated proving cost exceeds the configured cap
+    CostLimitExceeded(String),
+
+    /// Transient failure reaching a proving network; the request can be retried
+    NetworkError(String),
+
+    /// The guest program failed while verifying the input
+    GuestExecutionError {
+        /// `VerificationError::code()` of the failure, if the guest reported one
+        code: Option<String>,
+        message: String,
+    },
+
+    /// The input could not be encoded for the guest
+    InputEncodingError(String),
+
+    /// The proving network refused the request for lack of funds or quota
+    QuotaExceeded(String),
+
     /// Generic error
     Other(String),
 }
@@ -26,6 +45,17 @@ impl fmt::Display for ZkVmError {
             ZkVmError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
             ZkVmError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
             ZkVmError::ZkVmImplementationError(msg) => write!(f, "zkVM implementation error: {}", msg),
+            ZkVmError::CostLimitExceeded(msg) => write!(f, "Cost limit exceeded: {}", msg),
+            ZkVmError::NetworkError(msg) => write!(f, "Prover network error: {}", msg),
+            ZkVmError::GuestExecutionError {
+                code: Some(code),
+                message,
+            } => write!(f, "Guest execution failed ({}): {}", code, message),
+            ZkVmError::GuestExecutionError { code: None, message } => {
+                write!(f, "Guest execution failed: {}", message)
+            }
+            ZkVmError::InputEncodingError(msg) => write!(f, "Input encoding error: {}", msg),
+            ZkVmError::QuotaExceeded(msg) => write!(f, "Prover quota exceeded: {}", msg),
             ZkVmError::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -33,6 +63,57 @@ impl fmt::Display for ZkVmError {
 
 impl std::error::Error for ZkVmError {}
 
+/// Substrings of prover network errors caused by the requester's account
+/// rather than the network, matched case-insensitively
+const QUOTA_ERROR_MARKERS: &[&str] = &[
+    "insufficient balance",
+    "insufficient funds",
+    "quota",
+    "rate limit",
+    "payment required",
+];
+
+impl ZkVmError {
+    /// Classify an error returned by a proving network client
+    ///
+    /// The SDKs report account problems and transport failures through the same
+    /// error type, so the message decides: balance, quota and rate-limit
+    /// failures become [`ZkVmError::QuotaExceeded`], anything else
+    /// [`ZkVmError::NetworkError`].
+    pub fn from_network(message: String) -> Self {
+        let lower = message.to_lowercase();
+        if QUOTA_ERROR_MARKERS.iter().any(|marker| lower.contains(marker)) {
+            ZkVmError::QuotaExceeded(message)
+        } else {
+            ZkVmError::NetworkError(message)
+        }
+    }
+
+    /// Whether retrying the same request may succeed
+    ///
+    /// Only transient network failures are retryable. A guest failure, bad
+    /// input or exhausted quota fails again until something changes.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ZkVmError::NetworkError(_))
+    }
+
+    /// Stable machine-readable code for the error category, e.g. for exit statuses or metrics
+    pub fn code(&self) -> &'static str {
+        match self {
+            ZkVmError::ProofGenerationError(_) => "proof_generation",
+            ZkVmError::SerializationError(_) => "serialization",
+            ZkVmError::InvalidInput(_) => "invalid_input",
+            ZkVmError::ZkVmImplementationError(_) => "zkvm_implementation",
+            ZkVmError::CostLimitExceeded(_) => "cost_limit_exceeded",
+            ZkVmError::NetworkError(_) => "network",
+            ZkVmError::GuestExecutionError { .. } => "guest_execution",
+            ZkVmError::InputEncodingError(_) => "input_encoding",
+            ZkVmError::QuotaExceeded(_) => "quota_exceeded",
+            ZkVmError::Other(_) => "other",
+        }
+    }
+}
+
 impl From<anyhow::Error> for ZkVmError {
     fn from(err: anyhow::Error) -> Self {
         ZkVmError::Other(err.to_string())
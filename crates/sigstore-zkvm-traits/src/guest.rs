@@ -0,0 +1,652 @@
+//! Guest-side verification shared by the zkVM guest programs
+//!
+//! Each guest reads the encoded input, calls [`verify_input`] and commits the
+//! returned bytes as its public output.
+//!
+//! The output starts with the big-endian `u16` [`OUTPUT_VERSION`]. Guests built
+//! before the header existed committed the bare `VerificationResult::as_slice()`,
+//! with no options hash, reference time or signing age; that is version 0. It
+//! starts with the two high bytes of the signing time, both zero, while the
+//! header is `0x00` followed by a nonzero byte, so [`split_output_version`]
+//! tells them apart and consumers keep decoding old proofs after a guest
+//! upgrade. Layout versions stay below 256 to keep the header's first byte zero.
+
+use alloy_sol_types::{sol, SolValue};
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::hash::sha256;
+use sigstore_verifier::crypto::merkle::MerkleTree;
+use sigstore_verifier::error::VerificationError;
+use sigstore_verifier::types::result::{VerificationOptions, VerificationResult};
+use sigstore_verifier::AttestationVerifier;
+
+use crate::codec::encode_output;
+use crate::reproduced::{ReproducedBuildOutput, REPRODUCED_BUILD_OUTPUT_HEADER};
+use crate::tee::{AttestedBuildOutput, ATTESTED_BUILD_OUTPUT_HEADER};
+use crate::types::{is_batch_input, BatchProverInput, ProverInput};
+
+sol! {
+    /// Public output of a batch
+    ///
+    /// `resultsRoot` is the RFC 6962 Merkle root whose leaves are the entries'
+    /// `VerifiedOutput::as_slice()` bytes, in input order.
+    #[derive(Debug, PartialEq)]
+    struct BatchVerificationOutput {
+        bytes32 resultsRoot;
+        uint64 resultCount;
+    }
+
+    /// ABI layout of a committed verification failure, after `FAILED_OUTPUT_HEADER`
+    #[derive(Debug, PartialEq)]
+    struct VerificationFailure {
+        string step;
+        string code;
+        bytes32 bundleHash;
+        bytes32 optionsHash;
+    }
+}
+
+/// Header of a committed verification failure
+///
+/// It takes the place of the signing time and timestamp proof type of the raw
+/// layout. The first byte tells it apart from every output codec, and the
+/// proof type 0xff is rejected by the on-chain `VerificationResultParser`, so a
+/// failure can never be read as a verified result.
+pub const FAILED_OUTPUT_HEADER: [u8; 9] = [0xff; 9];
+
+/// Version of the committed output layout, written in front of every output
+pub const OUTPUT_VERSION: u16 = 1;
+
+/// Length of the trailer following the result: options hash, reference time, signing age
+const OUTPUT_TRAILER_LEN: usize = 32 + 8 + 8;
+
+/// Public output of a single-bundle proof
+///
+/// Layout: `VerificationResult::as_slice()` || `options_hash` (32 bytes) ||
+/// `reference_time` (8 bytes, big-endian) || `signing_age` (8 bytes, big-endian),
+/// where `options_hash` is `VerificationOptions::options_hash()` of the options
+/// the bundle was verified under. The result comes first so parsers of the
+/// result layout (such as the on-chain `VerificationResultParser`) read it unchanged.
+///
+/// `reference_time` is the host-supplied `ProverInput::reference_time` and
+/// `signing_age` is `max(0, reference_time - signing_time)` computed in the
+/// guest, so a contract can require "signed at most N seconds before
+/// `reference_time`" and only has to check `reference_time` against the block
+/// timestamp. Both are 0 when no reference time was supplied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedOutput {
+    /// `VerificationResult::as_slice()` bytes
+    pub result: Vec<u8>,
+
+    /// Hash of the canonical verification options
+    pub options_hash: [u8; 32],
+
+    /// Unix time the signing age is measured against, 0 if none was supplied
+    pub reference_time: u64,
+
+    /// Seconds between the signing time and `reference_time`, 0 if signed after it
+    pub signing_age: u64,
+}
+
+impl VerifiedOutput {
+    /// Build the output for `result` verified under `options`
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - Verification result to commit
+    /// * `options` - Options the bundle was verified under
+    /// * `reference_time` - Optional Unix time to measure the signing age against
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the options cannot be canonicalized.
+    pub fn new(
+        result: &VerificationResult,
+        options: &VerificationOptions,
+        reference_time: Option<u64>,
+    ) -> Result<Self, String> {
+        let (reference_time, signing_age) = match reference_time {
+            Some(reference_time) => {
+                let signing_time = result.signing_time.timestamp().max(0) as u64;
+                (reference_time, reference_time.saturating_sub(signing_time))
+            }
+            None => (0, 0),
+        };
+        Ok(VerifiedOutput {
+            result: result.as_slice(),
+            options_hash: options.options_hash().map_err(|e| e.to_string())?,
+            reference_time,
+            signing_age,
+        })
+    }
+
+    /// Serialize to the committed byte layout
+    pub fn as_slice(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.result.len() + OUTPUT_TRAILER_LEN);
+        bytes.extend_from_slice(&self.result);
+        bytes.extend_from_slice(&self.options_hash);
+        bytes.extend_from_slice(&self.reference_time.to_be_bytes());
+        bytes.extend_from_slice(&self.signing_age.to_be_bytes());
+        bytes
+    }
+
+    /// Split committed bytes into the result and the trailer fields
+    ///
+    /// A version 0 output is a bare `VerificationResult`; its trailer fields
+    /// are zero, as no options hash or reference time was committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is too short to hold the trailer, has no
+    /// or an unsupported version header, or is a committed verification
+    /// failure (see [`ProverOutput`]).
+    pub fn from_slice(data: &[u8]) -> Result<Self, String> {
+        match split_output_version(data)? {
+            (0, result) => Self::from_result(result),
+            (_, body) => Self::from_body(body),
+        }
+    }
+
+    /// Wrap a bare `VerificationResult::as_slice()`, as committed by version 0 guests
+    fn from_result(result: &[u8]) -> Result<Self, String> {
+        VerificationResult::from_slice(result)?;
+        Ok(VerifiedOutput {
+            result: result.to_vec(),
+            options_hash: [0; 32],
+            reference_time: 0,
+            signing_age: 0,
+        })
+    }
+
+    /// Parse the [`VerifiedOutput::as_slice`] layout, without a version header
+    ///
+    /// This is the layout embedded in combined claims and batch leaves.
+    pub(crate) fn from_body(data: &[u8]) -> Result<Self, String> {
+        if data.starts_with(&FAILED_OUTPUT_HEADER) {
+            return ProverOutput::from_body(data)?.into_verified();
+        }
+        if data.len() < OUTPUT_TRAILER_LEN {
+            return Err(format!(
+                "Output too short: expected at least {} bytes, got {}",
+                OUTPUT_TRAILER_LEN,
+                data.len()
+            ));
+        }
+        let (result, trailer) = data.split_at(data.len() - OUTPUT_TRAILER_LEN);
+        let (options_hash, times) = trailer.split_at(32);
+        let (reference_time, signing_age) = times.split_at(8);
+        Ok(VerifiedOutput {
+            result: result.to_vec(),
+            options_hash: options_hash.try_into().unwrap(),
+            reference_time: u64::from_be_bytes(reference_time.try_into().unwrap()),
+            signing_age: u64::from_be_bytes(signing_age.try_into().unwrap()),
+        })
+    }
+
+    /// Check that the bundle was signed at most `max_age` seconds before the reference time
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no reference time was committed or the signing age exceeds `max_age`.
+    pub fn check_signing_age(&self, max_age: u64) -> Result<(), String> {
+        if self.reference_time == 0 {
+            return Err("Proof does not commit a reference time".to_string());
+        }
+        if self.signing_age > max_age {
+            return Err(format!(
+                "Attestation signed {}s before the reference time, maximum is {}s",
+                self.signing_age, max_age
+            ));
+        }
+        Ok(())
+    }
+
+    /// Decode the committed `VerificationResult`
+    pub fn verification_result(&self) -> Result<VerificationResult, String> {
+        VerificationResult::from_slice(&self.result)
+    }
+
+    /// Check that the proof was generated under `options`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the committed hash differs from `options.options_hash()`.
+    pub fn check_options(&self, options: &VerificationOptions) -> Result<(), String> {
+        let expected = options.options_hash().map_err(|e| e.to_string())?;
+        if expected != self.options_hash {
+            return Err(format!(
+                "Options hash mismatch: proof committed 0x{}, expected 0x{}",
+                hex::encode(self.options_hash),
+                hex::encode(expected)
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Public output of a single-bundle guest execution
+///
+/// A failed verification normally aborts the guest, so there is nothing to
+/// prove. With `ProverInput::with_prove_failures` the guest commits
+/// [`ProverOutput::Failed`] instead, which proves that the bundle does not
+/// verify under the options, e.g. to show on-chain that an artifact's
+/// attestation was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProverOutput {
+    Verified(VerifiedOutput),
+    Failed {
+        /// Verification step that rejected the bundle, e.g. `certificate_chain`
+        step: String,
+        /// `VerificationError::code()` of the failure
+        code: String,
+        /// SHA-256 of the bundle JSON
+        bundle_hash: [u8; 32],
+        /// Hash of the canonical verification options
+        options_hash: [u8; 32],
+    },
+}
+
+impl ProverOutput {
+    /// Build the failure output for `bundle_json` rejected with `error`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the options cannot be canonicalized.
+    pub fn failed(
+        bundle_json: &[u8],
+        options: &VerificationOptions,
+        error: &VerificationError,
+    ) -> Result<Self, String> {
+        Ok(ProverOutput::Failed {
+            step: failed_step(error).to_string(),
+            code: error.code().to_string(),
+            bundle_hash: sha256(bundle_json),
+            options_hash: options.options_hash().map_err(|e| e.to_string())?,
+        })
+    }
+
+    /// Serialize to the committed byte layout
+    ///
+    /// A verified output is `VerifiedOutput::as_slice()`; a failure is
+    /// `FAILED_OUTPUT_HEADER` || ABI-encoded `VerificationFailure`.
+    pub fn as_slice(&self) -> Vec<u8> {
+        match self {
+            ProverOutput::Verified(output) => output.as_slice(),
+            ProverOutput::Failed {
+                step,
+                code,
+                bundle_hash,
+                options_hash,
+            } => {
+                let encoded = VerificationFailure {
+                    step: step.clone(),
+                    code: code.clone(),
+                    bundleHash: (*bundle_hash).into(),
+                    optionsHash: (*options_hash).into(),
+                }
+                .abi_encode();
+                let mut bytes = Vec::with_capacity(FAILED_OUTPUT_HEADER.len() + encoded.len());
+                bytes.extend_from_slice(&FAILED_OUTPUT_HEADER);
+                bytes.extend_from_slice(&encoded);
+                bytes
+            }
+        }
+    }
+
+    /// Parse committed raw-codec bytes, verified or failed
+    ///
+    /// A version 0 output is a bare `VerificationResult` (see
+    /// [`VerifiedOutput::from_slice`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a valid output of either kind.
+    pub fn from_slice(data: &[u8]) -> Result<Self, String> {
+        match split_output_version(data)? {
+            (0, result) => VerifiedOutput::from_result(result).map(ProverOutput::Verified),
+            (_, body) => Self::from_body(body),
+        }
+    }
+
+    /// Parse a raw-codec output after its version header
+    pub(crate) fn from_body(data: &[u8]) -> Result<Self, String> {
+        if data.starts_with(&ATTESTED_BUILD_OUTPUT_HEADER) {
+            return Err("Output is an attested build claim, decode it with tee::AttestedBuildOutput".to_string());
+        }
+        if data.starts_with(&REPRODUCED_BUILD_OUTPUT_HEADER) {
+            return Err("Output is a reproduced build claim, decode it with reproduced::ReproducedBuildOutput".to_string());
+        }
+        let Some(encoded) = data.strip_prefix(&FAILED_OUTPUT_HEADER) else {
+            return VerifiedOutput::from_body(data).map(ProverOutput::Verified);
+        };
+        let failure = VerificationFailure::abi_decode(encoded)
+            .map_err(|e| format!("Failed to ABI decode verification failure: {}", e))?;
+        Ok(ProverOutput::Failed {
+            step: failure.step,
+            code: failure.code,
+            bundle_hash: failure.bundleHash.into(),
+            options_hash: failure.optionsHash.into(),
+        })
+    }
+
+    /// The verified output, or an error describing the committed failure
+    pub fn into_verified(self) -> Result<VerifiedOutput, String> {
+        match self {
+            ProverOutput::Verified(output) => Ok(output),
+            ProverOutput::Failed { step, code, .. } => Err(format!(
+                "Guest committed a verification failure at step {}: {}",
+                step, code
+            )),
+        }
+    }
+}
+
+/// Prefix an output with the [`OUTPUT_VERSION`] header
+pub fn with_output_version(output: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(2 + output.len());
+    bytes.extend_from_slice(&OUTPUT_VERSION.to_be_bytes());
+    bytes.extend_from_slice(output);
+    bytes
+}
+
+/// Split a committed single-bundle output into its layout version and body
+///
+/// # Returns
+///
+/// The version and the bytes after the header, or version 0 and `data`
+/// unchanged for a bare `VerificationResult` committed before the header existed.
+///
+/// # Errors
+///
+/// Returns an error if `data` is shorter than the header, starts with neither
+/// a header nor a bare result, or the version is newer than [`OUTPUT_VERSION`].
+pub fn split_output_version(data: &[u8]) -> Result<(u16, &[u8]), String> {
+    match data {
+        [] | [_] => Err(format!(
+            "Output too short: {} bytes, the version header alone is 2",
+            data.len()
+        )),
+        [0, 0, ..] => Ok((0, data)),
+        [0, version, body @ ..] if *version != 0 => {
+            let version = u16::from(*version);
+            if version > OUTPUT_VERSION {
+                return Err(format!(
+                    "Unsupported output version {}, this build decodes up to {}",
+                    version, OUTPUT_VERSION
+                ));
+            }
+            Ok((version, body))
+        }
+        _ => Err(format!(
+            "Output has no version header: starts with {:#04x}",
+            data[0]
+        )),
+    }
+}
+
+/// Layout version of a committed output, single-bundle or batch
+///
+/// # Errors
+///
+/// Returns an error if the output has no version header and is not a bare
+/// result, or the version is newer than [`OUTPUT_VERSION`].
+pub fn output_version(data: &[u8]) -> Result<u16, String> {
+    split_output_version(data).map(|(version, _)| version)
+}
+
+/// Name of the verification step an error comes from, matching `BuiltinStep`
+/// where there is one
+fn failed_step(error: &VerificationError) -> &'static str {
+    match error {
+        VerificationError::BundleParse(_)
+        | VerificationError::Base64Decode(_)
+        | VerificationError::InvalidBundleFormat(_)
+        | VerificationError::LimitExceeded { .. } => "bundle",
+        VerificationError::ZeroSubjectDigest
+        | VerificationError::SubjectDigestMismatch { .. }
+        | VerificationError::SubjectNameMismatch { .. }
+        | VerificationError::StatementSchema(_) => "subject",
+        VerificationError::Certificate(_) => "certificate_chain",
+        VerificationError::Signature(_) => "signature",
+        VerificationError::Timestamp(_) | VerificationError::Transparency(_) => "timestamp",
+        VerificationError::Policy(_) => "policy",
+        _ => "other",
+    }
+}
+
+/// Verify encoded guest input and return the bytes to commit
+///
+/// Every output starts with the [`OUTPUT_VERSION`] header.
+/// Single-bundle input commits `VerifiedOutput::as_slice()`,
+/// or its encoding in the input's `output_codec` (see [`crate::codec`]). If the
+/// bundle fails verification and the input sets `prove_failures`, it commits
+/// [`ProverOutput::Failed`] in the raw layout instead, whatever the codec.
+/// Input with TEE evidence commits an [`AttestedBuildOutput`]; a quote that
+/// does not verify aborts the guest. Input with a reproduced bundle commits a
+/// [`ReproducedBuildOutput`]; a second bundle that does not verify, or does not
+/// reproduce the first, aborts the guest.
+/// Batch input commits the ABI-encoded `BatchVerificationOutput`; per-entry
+/// results are proven against its root with [`crate::batch::BatchResults`].
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be decoded or a bundle fails
+/// verification without `prove_failures`.
+pub fn verify_input(input_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let verifier = AttestationVerifier::new();
+
+    if !is_batch_input(input_bytes) {
+        let input = ProverInput::parse_input(input_bytes)?;
+        return verify_one(&verifier, input).map(|output| with_output_version(&output));
+    }
+
+    let batch = BatchProverInput::parse_input(input_bytes)?;
+    let results = verify_batch(&verifier, &batch)?;
+    Ok(with_output_version(&batch_output(&results)))
+}
+
+/// Verify every entry of a batch and return the per-entry outputs, in input order
+///
+/// # Errors
+///
+/// Returns an error if the batch is empty, since the root of an empty results
+/// tree would prove nothing, or naming the first entry that references missing
+/// trust material or fails verification.
+pub fn verify_batch(
+    verifier: &AttestationVerifier,
+    batch: &BatchProverInput,
+) -> Result<Vec<Vec<u8>>, String> {
+    if batch.entries.is_empty() {
+        return Err("Batch has no entries".to_string());
+    }
+    let mut results = Vec::with_capacity(batch.entries.len());
+    for (index, entry) in batch.entries.iter().enumerate() {
+        let trust_bundle = batch
+            .trust_bundles
+            .get(entry.trust_bundle_index as usize)
+            .ok_or_else(|| format!("Entry {}: trust bundle index out of range", index))?;
+        let tsa_cert_chain = match entry.tsa_cert_chain_index {
+            Some(tsa_index) => Some(
+                batch
+                    .tsa_cert_chains
+                    .get(tsa_index as usize)
+                    .ok_or_else(|| format!("Entry {}: TSA chain index out of range", index))?,
+            ),
+            None => None,
+        };
+
+        let result = verifier
+            .verify_bundle_bytes(
+                &entry.bundle_json,
+                entry.verification_options.clone(),
+                trust_bundle,
+                tsa_cert_chain,
+            )
+            .map_err(|e| format!("Entry {}: {}", index, e))?;
+        let output = VerifiedOutput::new(&result, &entry.verification_options, entry.reference_time)
+            .map_err(|e| format!("Entry {}: {}", index, e))?;
+        results.push(output.as_slice());
+    }
+    Ok(results)
+}
+
+/// ABI-encode the `BatchVerificationOutput` committing to `results`
+pub fn batch_output(results: &[Vec<u8>]) -> Vec<u8> {
+    let tree = MerkleTree::from_leaves(results);
+    BatchVerificationOutput {
+        resultsRoot: tree.root().into(),
+        resultCount: tree.size(),
+    }
+    .abi_encode()
+}
+
+/// Decode the committed public output of a batch
+///
+/// Batch guests have always written the version header, so an output without
+/// one is rejected.
+pub fn decode_batch_output(output: &[u8]) -> Result<BatchVerificationOutput, String> {
+    let output = match split_output_version(output)? {
+        (0, _) => return Err("Batch output has no version header".to_string()),
+        (_, body) => body,
+    };
+    BatchVerificationOutput::abi_decode(output)
+        .map_err(|e| format!("Failed to ABI decode batch output: {}", e))
+}
+
+fn verify_one(verifier: &AttestationVerifier, input: ProverInput) -> Result<Vec<u8>, String> {
+    let result = verifier.verify_bundle_bytes(
+        &input.bundle_json,
+        input.verification_options.clone(),
+        &input.trust_bundle,
+        input.tsa_cert_chain.as_ref(),
+    );
+    single_output(verifier, &input, result)
+}
+
+fn single_output(
+    verifier: &AttestationVerifier,
+    input: &ProverInput,
+    result: Result<VerificationResult, VerificationError>,
+) -> Result<Vec<u8>, String> {
+    match result {
+        Ok(result) => {
+            if let Some(bundle_json) = &input.reproduced_bundle_json {
+                return reproduced_output(verifier, input, result, bundle_json);
+            }
+            let output = VerifiedOutput::new(&result, &input.verification_options, input.reference_time)?;
+            match &input.tee_evidence {
+                Some(evidence) => {
+                    AttestedBuildOutput::verify(output, &result.subject_digest, evidence).map(|claim| claim.as_slice())
+                }
+                None => encode_output(input.output_codec, &output),
+            }
+        }
+        Err(e) if input.prove_failures => {
+            Ok(ProverOutput::failed(&input.bundle_json, &input.verification_options, &e)?.as_slice())
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn reproduced_output(
+    verifier: &AttestationVerifier,
+    input: &ProverInput,
+    first: VerificationResult,
+    bundle_json: &[u8],
+) -> Result<Vec<u8>, String> {
+    if input.tee_evidence.is_some() {
+        return Err("TEE evidence cannot be combined with a reproduced bundle".to_string());
+    }
+    let second = verifier
+        .verify_bundle_bytes(
+            bundle_json,
+            input.verification_options.clone(),
+            &input.trust_bundle,
+            input.tsa_cert_chain.as_ref(),
+        )
+        .map_err(|e| format!("Reproduced bundle: {}", e))?;
+    ReproducedBuildOutput::verify(first, second, &input.verification_options, input.reference_time)
+        .map(|claim| claim.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_output_version() {
+        let body = [0xab; OUTPUT_TRAILER_LEN];
+        let versioned = with_output_version(&body);
+        assert_eq!(split_output_version(&versioned).unwrap(), (OUTPUT_VERSION, &body[..]));
+        assert_eq!(output_version(&versioned).unwrap(), OUTPUT_VERSION);
+
+        // Outputs from before the header start with the high bytes of the signing time
+        let unversioned = [[0; 8].as_slice(), &body].concat();
+        assert_eq!(split_output_version(&unversioned).unwrap(), (0, &unversioned[..]));
+
+        // Anything else is neither a header nor a bare result
+        assert!(split_output_version(&FAILED_OUTPUT_HEADER)
+            .unwrap_err()
+            .starts_with("Output has no version header"));
+    }
+
+    /// Journal of the released SP1 guest, which committed the bare `VerificationResult`
+    fn version_0_journal() -> Vec<u8> {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("contracts/test/fixtures/sp1-github.json");
+        let artifact: serde_json::Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        hex::decode(artifact["journal"].as_str().unwrap().trim_start_matches("0x")).unwrap()
+    }
+
+    #[test]
+    fn test_decode_version_0_journal() {
+        let journal = version_0_journal();
+        assert_eq!(output_version(&journal).unwrap(), 0);
+
+        let output = VerifiedOutput::from_slice(&journal).unwrap();
+        assert_eq!(output.result, journal);
+        assert_eq!(output.options_hash, [0; 32]);
+        assert_eq!((output.reference_time, output.signing_age), (0, 0));
+        let result = output.verification_result().unwrap();
+        assert_eq!(result.as_slice(), journal);
+        assert_eq!(result.signing_time.timestamp(), 0x691d2ac5);
+        assert!(output.check_signing_age(u64::MAX).is_err());
+
+        assert_eq!(ProverOutput::from_slice(&journal).unwrap(), ProverOutput::Verified(output.clone()));
+        let (codec, values) = crate::codec::decode_output(&journal).unwrap();
+        assert_eq!(codec, crate::codec::OutputCodecId::Raw);
+        assert_eq!(values.to_output().unwrap(), output);
+
+        // The same result under the current layout keeps its trailer
+        let current = VerifiedOutput {
+            options_hash: [7; 32],
+            reference_time: 0x691d2ac5 + 60,
+            signing_age: 60,
+            ..output
+        };
+        assert_eq!(VerifiedOutput::from_slice(&with_output_version(&current.as_slice())).unwrap(), current);
+    }
+
+    #[test]
+    fn test_split_unknown_output_version() {
+        let next = [0, OUTPUT_VERSION as u8 + 1, 0xab];
+        assert_eq!(
+            split_output_version(&next).unwrap_err(),
+            format!(
+                "Unsupported output version {}, this build decodes up to {}",
+                OUTPUT_VERSION + 1,
+                OUTPUT_VERSION
+            )
+        );
+        assert!(output_version(&next).is_err());
+    }
+
+    #[test]
+    fn test_split_output_shorter_than_header() {
+        for data in [&[][..], &[0], &[1]] {
+            assert!(split_output_version(data).unwrap_err().starts_with("Output too short"));
+        }
+        assert_eq!(split_output_version(&[0, 1]).unwrap(), (1, &[][..]));
+    }
+}
@@ -41,7 +41,17 @@
 //! let (public_output, proof_bytes) = prover.prove(&config, &input).await?;
 //! ```
 
+pub mod batch;
+pub mod chains;
+pub mod codec;
 pub mod error;
+pub mod guest;
+pub mod notify;
+pub mod operator;
+pub mod registry;
+pub mod reproduced;
+pub mod secret;
+pub mod tee;
 pub mod traits;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,174 @@
+//! Proof completion notifications
+//!
+//! Hosts report each finished network proof to a [`ProofNotifier`], so the
+//! pipeline that consumes proofs can run separately from the one that submits
+//! them. Any `Fn(&ProofNotification) -> Result<()>` closure is a notifier; with
+//! the `webhook` feature, [`WebhookNotifier`] POSTs the notification as JSON.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::hash::sha256;
+
+use crate::codec::decode_output;
+
+/// Outcome of a proof request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofStatus {
+    Proved,
+    Failed,
+}
+
+/// Metadata of a finished proof request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofNotification {
+    /// zkVM that produced the proof (e.g. "sp1", "risc0")
+    pub zkvm: String,
+    pub program_id: String,
+    pub circuit_version: String,
+
+    /// Bundle the proof is for (e.g. its file path)
+    pub bundle: String,
+
+    /// SHA-256 of the bundle file, as `sha256:<hex>`
+    pub bundle_digest: String,
+
+    pub status: ProofStatus,
+
+    /// Hash of the verification options committed by the proof (proved only)
+    pub options_hash: Option<String>,
+
+    /// Hex-encoded public output (proved only)
+    pub journal: Option<String>,
+
+    /// Failure reason (failed only)
+    pub error: Option<String>,
+}
+
+impl ProofNotification {
+    /// Notification for a completed proof
+    ///
+    /// # Arguments
+    ///
+    /// * `zkvm` - zkVM that produced the proof
+    /// * `program_id` - Program identifier of the guest
+    /// * `circuit_version` - zkVM circuit version
+    /// * `bundle` - Name of the bundle (e.g. its file path)
+    /// * `bundle_json` - Raw bundle bytes, for the digest
+    /// * `journal` - Public output of the proof
+    pub fn proved(
+        zkvm: &str,
+        program_id: &str,
+        circuit_version: &str,
+        bundle: &str,
+        bundle_json: &[u8],
+        journal: &[u8],
+    ) -> Self {
+        let options_hash = decode_output(journal)
+            .ok()
+            .map(|(_, values)| format!("0x{}", hex::encode(values.options_hash)));
+        ProofNotification {
+            zkvm: zkvm.to_string(),
+            program_id: program_id.to_string(),
+            circuit_version: circuit_version.to_string(),
+            bundle: bundle.to_string(),
+            bundle_digest: bundle_digest(bundle_json),
+            status: ProofStatus::Proved,
+            options_hash,
+            journal: Some(format!("0x{}", hex::encode(journal))),
+            error: None,
+        }
+    }
+
+    /// Notification for a failed proof request
+    pub fn failed(
+        zkvm: &str,
+        program_id: &str,
+        circuit_version: &str,
+        bundle: &str,
+        bundle_json: &[u8],
+        error: &str,
+    ) -> Self {
+        ProofNotification {
+            zkvm: zkvm.to_string(),
+            program_id: program_id.to_string(),
+            circuit_version: circuit_version.to_string(),
+            bundle: bundle.to_string(),
+            bundle_digest: bundle_digest(bundle_json),
+            status: ProofStatus::Failed,
+            options_hash: None,
+            journal: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+fn bundle_digest(bundle_json: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(sha256(bundle_json)))
+}
+
+/// Receiver of proof completion notifications
+#[async_trait]
+pub trait ProofNotifier: Send + Sync {
+    /// Deliver a notification
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification could not be delivered. Hosts
+    /// report it without failing the proof.
+    async fn notify(&self, notification: &ProofNotification) -> Result<()>;
+}
+
+#[async_trait]
+impl<F> ProofNotifier for F
+where
+    F: Fn(&ProofNotification) -> Result<()> + Send + Sync,
+{
+    async fn notify(&self, notification: &ProofNotification) -> Result<()> {
+        self(notification)
+    }
+}
+
+/// Notifier that POSTs each notification as JSON to a URL
+#[cfg(feature = "webhook")]
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    url: String,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "webhook")]
+impl WebhookNotifier {
+    /// Request timeout for a single notification
+    pub const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(Self::TIMEOUT).build()?;
+        Ok(WebhookNotifier {
+            url: url.into(),
+            client,
+        })
+    }
+}
+
+#[cfg(feature = "webhook")]
+#[async_trait]
+impl ProofNotifier for WebhookNotifier {
+    async fn notify(&self, notification: &ProofNotification) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .json(notification)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Send `notification`, printing a warning instead of failing if it cannot be delivered
+pub async fn notify_or_warn(notifier: &dyn ProofNotifier, notification: &ProofNotification) {
+    if let Err(e) = notifier.notify(notification).await {
+        println!("⚠ Failed to send proof notification: {:#}", e);
+    }
+}
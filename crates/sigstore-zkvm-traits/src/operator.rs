@@ -0,0 +1,265 @@
+//! Operator signatures on proof artifacts
+//!
+//! A proof is verified on-chain, but a relayer forwarding artifacts from
+//! several prover services wants to know which service produced one before
+//! paying to submit it. A host holding an operator key signs the artifact's
+//! metadata ([`ProofMetadata`]: program, bundle digest, journal digest, time)
+//! and embeds the signature in the artifact; the relayer checks it against the
+//! operator keys it trusts with [`ProofArtifact::verify_operator_signature`].
+//!
+//! The signature is a base64 DER ECDSA P-256 signature over the RFC 8785
+//! canonical JSON of the metadata, as returned by
+//! [`ProofMetadata::canonical_bytes`].
+
+use std::fmt;
+
+use base64::prelude::*;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use p256::pkcs8::EncodePublicKey;
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::hash::sha256;
+use sigstore_verifier::crypto::jcs::to_canonical_vec;
+use sigstore_verifier::crypto::signature::PublicKey;
+
+use crate::secret::SecretKey;
+use crate::utils::ProofArtifact;
+
+/// What the operator vouches for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    pub zkvm: String,
+    pub program_id: String,
+    pub circuit_version: String,
+    /// SHA-256 of the bundle JSON the proof is for, hex
+    pub bundle_digest: String,
+    /// SHA-256 of the journal bytes, hex; ties the signature to this proof
+    pub journal_digest: String,
+    /// Unix time the metadata was signed
+    pub signed_at: u64,
+}
+
+impl ProofMetadata {
+    /// RFC 8785 canonical JSON of the metadata, the message the operator signs
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, String> {
+        to_canonical_vec(self).map_err(|e| format!("Failed to canonicalize proof metadata: {}", e))
+    }
+}
+
+/// Signed metadata embedded in a `ProofArtifact`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorSignature {
+    pub metadata: ProofMetadata,
+    /// Base64 DER SubjectPublicKeyInfo of the operator key
+    pub public_key: String,
+    /// Base64 DER ECDSA signature over `metadata.canonical_bytes()`
+    pub signature: String,
+}
+
+/// P-256 key a prover operator signs artifact metadata with
+#[derive(Clone)]
+pub struct OperatorKey(SigningKey);
+
+impl fmt::Debug for OperatorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OperatorKey(<redacted>)")
+    }
+}
+
+impl OperatorKey {
+    /// Load the key from a hex-encoded P-256 scalar
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not a valid P-256 scalar. The message never includes the key.
+    pub fn from_secret(key: &SecretKey) -> Result<Self, String> {
+        let bytes = key.decode_hex()?;
+        SigningKey::from_slice(&bytes)
+            .map(OperatorKey)
+            .map_err(|_| "Operator key is not a valid P-256 private key".to_string())
+    }
+
+    /// DER SubjectPublicKeyInfo of the key, for relayers to pin
+    pub fn public_key_der(&self) -> Result<Vec<u8>, String> {
+        self.0
+            .verifying_key()
+            .to_public_key_der()
+            .map(|der| der.as_bytes().to_vec())
+            .map_err(|e| format!("Failed to encode operator public key: {}", e))
+    }
+}
+
+impl ProofArtifact {
+    /// Sign the artifact's metadata with the operator key and embed the signature
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The operator key
+    /// * `bundle_json` - The bundle the proof is for
+    /// * `signed_at` - Unix time to record as the signing time
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// artifact.sign_metadata(&OperatorKey::from_secret(&operator_key)?, &input.bundle_json, now)?;
+    /// write_proof_artifact(output_path, &artifact)?;
+    /// ```
+    pub fn sign_metadata(&mut self, key: &OperatorKey, bundle_json: &[u8], signed_at: u64) -> Result<(), String> {
+        let metadata = ProofMetadata {
+            zkvm: self.zkvm.clone(),
+            program_id: self.program_id.clone(),
+            circuit_version: self.circuit_version.clone(),
+            bundle_digest: hex::encode(sha256(bundle_json)),
+            journal_digest: hex::encode(sha256(&self.journal_bytes()?)),
+            signed_at,
+        };
+        let signature: Signature = key.0.sign(&metadata.canonical_bytes()?);
+        self.operator_signature = Some(OperatorSignature {
+            metadata,
+            public_key: BASE64_STANDARD.encode(key.public_key_der()?),
+            signature: BASE64_STANDARD.encode(signature.to_der().as_bytes()),
+        });
+        Ok(())
+    }
+
+    /// Check the operator signature against the operator keys the caller trusts
+    ///
+    /// The signature must be by one of `trusted_keys` and the signed metadata
+    /// must describe this artifact: same zkVM, program, circuit and journal.
+    ///
+    /// # Arguments
+    ///
+    /// * `trusted_keys` - DER SubjectPublicKeyInfo of each trusted operator key
+    ///
+    /// # Returns
+    ///
+    /// The signed metadata, whose `bundle_digest` the caller can compare with
+    /// the bundle it expects
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact is unsigned, signed by an untrusted
+    /// key, the signature does not verify, or the metadata does not match.
+    pub fn verify_operator_signature(&self, trusted_keys: &[Vec<u8>]) -> Result<&ProofMetadata, String> {
+        let signed = self
+            .operator_signature
+            .as_ref()
+            .ok_or("Proof artifact has no operator signature")?;
+        let key_der = BASE64_STANDARD
+            .decode(&signed.public_key)
+            .map_err(|e| format!("Invalid operator public key encoding: {}", e))?;
+        if !trusted_keys.contains(&key_der) {
+            return Err("Proof artifact is signed by an untrusted operator key".to_string());
+        }
+        let signature = BASE64_STANDARD
+            .decode(&signed.signature)
+            .map_err(|e| format!("Invalid operator signature encoding: {}", e))?;
+        PublicKey::from_spki_der(&key_der)
+            .map_err(|e| format!("Invalid operator public key: {}", e))?
+            .verify_signature(&signed.metadata.canonical_bytes()?, &signature)
+            .map_err(|e| format!("Operator signature verification failed: {}", e))?;
+
+        let metadata = &signed.metadata;
+        if metadata.zkvm != self.zkvm
+            || metadata.program_id != self.program_id
+            || metadata.circuit_version != self.circuit_version
+            || metadata.journal_digest != hex::encode(sha256(&self.journal_bytes()?))
+        {
+            return Err("Operator signature is for a different proof".to_string());
+        }
+        Ok(metadata)
+    }
+
+    fn journal_bytes(&self) -> Result<Vec<u8>, String> {
+        hex::decode(self.journal.trim_start_matches("0x")).map_err(|e| format!("Invalid journal hex: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLE: &[u8] = br#"{"mediaType":"application/vnd.dev.sigstore.bundle.v0.3+json"}"#;
+
+    fn operator_key(seed: u8) -> OperatorKey {
+        OperatorKey::from_secret(&SecretKey::new(hex::encode([seed; 32]))).unwrap()
+    }
+
+    fn signed_artifact(key: &OperatorKey) -> ProofArtifact {
+        let mut artifact = ProofArtifact {
+            zkvm: "sp1".to_string(),
+            program_id: "0x0081d7".to_string(),
+            circuit_version: "v5.0.0".to_string(),
+            journal: "0x0001aabb".to_string(),
+            proof: "0x".to_string(),
+            operator_signature: None,
+        };
+        artifact.sign_metadata(key, BUNDLE, 1_700_000_000).unwrap();
+        artifact
+    }
+
+    #[test]
+    fn test_verify_operator_signature() {
+        let key = operator_key(1);
+        let trusted = vec![operator_key(2).public_key_der().unwrap(), key.public_key_der().unwrap()];
+        let artifact = signed_artifact(&key);
+
+        let metadata = artifact.verify_operator_signature(&trusted).unwrap();
+        assert_eq!(metadata.bundle_digest, hex::encode(sha256(BUNDLE)));
+        assert_eq!(metadata.journal_digest, hex::encode(sha256(&[0x00, 0x01, 0xaa, 0xbb])));
+        assert_eq!(metadata.signed_at, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_operator_signature_rejections() {
+        let key = operator_key(1);
+        let trusted = vec![key.public_key_der().unwrap()];
+
+        let mut unsigned = signed_artifact(&key);
+        unsigned.operator_signature = None;
+        assert_eq!(
+            unsigned.verify_operator_signature(&trusted).unwrap_err(),
+            "Proof artifact has no operator signature"
+        );
+
+        assert_eq!(
+            signed_artifact(&operator_key(2))
+                .verify_operator_signature(&trusted)
+                .unwrap_err(),
+            "Proof artifact is signed by an untrusted operator key"
+        );
+
+        let mut tampered = signed_artifact(&key);
+        tampered.operator_signature.as_mut().unwrap().metadata.signed_at += 1;
+        assert!(tampered
+            .verify_operator_signature(&trusted)
+            .unwrap_err()
+            .starts_with("Operator signature verification failed"));
+    }
+
+    #[test]
+    fn test_operator_signature_binds_the_proof() {
+        let key = operator_key(1);
+        let trusted = vec![key.public_key_der().unwrap()];
+
+        let mut other_journal = signed_artifact(&key);
+        other_journal.journal = "0x0001aabc".to_string();
+        let mut other_program = signed_artifact(&key);
+        other_program.program_id = "0x0081d8".to_string();
+        let mut other_circuit = signed_artifact(&key);
+        other_circuit.circuit_version = "v4.0.0".to_string();
+        for artifact in [other_journal, other_program, other_circuit] {
+            assert_eq!(
+                artifact.verify_operator_signature(&trusted).unwrap_err(),
+                "Operator signature is for a different proof"
+            );
+        }
+    }
+
+    #[test]
+    fn test_operator_key_rejects_invalid_scalar() {
+        let error = OperatorKey::from_secret(&SecretKey::new(hex::encode([0u8; 32]))).unwrap_err();
+        assert_eq!(error, "Operator key is not a valid P-256 private key");
+        assert_eq!(format!("{:?}", operator_key(1)), "OperatorKey(<redacted>)");
+    }
+}
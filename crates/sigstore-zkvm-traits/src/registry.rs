@@ -0,0 +1,275 @@
+//! Registry of released guest programs
+//!
+//! A proof is only as trustworthy as the guest program it was generated for,
+//! and the program identifier alone (SP1 vkey hash, RISC Zero ImageID, Pico
+//! vkey digest) does not say which release it belongs to. The registry is a
+//! JSON manifest, signed by the release maintainers, mapping each released
+//! guest version to its program identifiers per zkVM and to the output layout
+//! versions (see [`crate::guest::OUTPUT_VERSION`]) it commits. Consumers load
+//! it with a pinned public key and check proof artifacts against it with
+//! [`ProgramRegistry::verify_program_identifier`].
+//!
+//! Manifest format:
+//!
+//! ```json
+//! {
+//!   "registry": {
+//!     "releases": [
+//!       {
+//!         "version": "0.2.0",
+//!         "output_versions": [1],
+//!         "programs": [
+//!           {"zkvm": "sp1", "program_id": "0x0081d7...", "circuit_version": "v5.0.0"}
+//!         ]
+//!       }
+//!     ]
+//!   },
+//!   "signature": "MEUCIQ..."
+//! }
+//! ```
+//!
+//! `signature` is a base64 DER ECDSA signature (P-256, P-384 or secp256k1,
+//! with the curve's standard digest) over the RFC 8785 canonical JSON of
+//! `registry`, as returned by [`ProgramRegistry::canonical_bytes`].
+
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::jcs::to_canonical_vec;
+use sigstore_verifier::crypto::signature::PublicKey;
+
+use crate::guest::output_version;
+use crate::utils::ProofArtifact;
+
+/// A guest program identifier on one zkVM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredProgram {
+    /// zkVM name as in `ProofArtifact::zkvm`, e.g. "sp1"
+    pub zkvm: String,
+    /// Hex program identifier, with or without `0x`
+    pub program_id: String,
+    /// zkVM circuit version the identifier was derived with
+    pub circuit_version: String,
+}
+
+impl RegisteredProgram {
+    fn matches(&self, zkvm: &str, program_id: &str) -> bool {
+        self.zkvm == zkvm && normalize_program_id(&self.program_id) == normalize_program_id(program_id)
+    }
+}
+
+/// A released guest version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestRelease {
+    /// Release version, e.g. "0.2.0"
+    pub version: String,
+    /// Output layout versions the release commits
+    pub output_versions: Vec<u16>,
+    pub programs: Vec<RegisteredProgram>,
+}
+
+/// Released guest programs, as found in a verified manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramRegistry {
+    pub releases: Vec<GuestRelease>,
+}
+
+/// A registry manifest with the maintainers' signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedRegistry {
+    pub registry: ProgramRegistry,
+    /// Base64 DER ECDSA signature over `registry.canonical_bytes()`
+    pub signature: String,
+}
+
+impl SignedRegistry {
+    /// Parse a manifest without checking its signature
+    pub fn from_json(json: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(json).map_err(|e| format!("Invalid registry manifest: {}", e))
+    }
+
+    /// Check the signature and return the registry
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key_der` - DER SubjectPublicKeyInfo of the maintainers' key
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key cannot be parsed or the signature does not
+    /// match the registry.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let manifest = SignedRegistry::from_json(&std::fs::read("registry.json")?)?;
+    /// let registry = manifest.verify(&maintainers_key_der)?;
+    /// let release = registry.verify_program_identifier(&artifact)?;
+    /// ```
+    pub fn verify(self, public_key_der: &[u8]) -> Result<ProgramRegistry, String> {
+        let public_key = PublicKey::from_spki_der(public_key_der)
+            .map_err(|e| format!("Invalid registry public key: {}", e))?;
+        let signature = BASE64_STANDARD
+            .decode(self.signature.trim())
+            .map_err(|e| format!("Invalid registry signature encoding: {}", e))?;
+        public_key
+            .verify_signature(&self.registry.canonical_bytes()?, &signature)
+            .map_err(|e| format!("Registry signature verification failed: {}", e))?;
+        Ok(self.registry)
+    }
+}
+
+impl ProgramRegistry {
+    /// RFC 8785 canonical JSON of the registry, the message the manifest signs
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, String> {
+        to_canonical_vec(self).map_err(|e| format!("Failed to canonicalize registry: {}", e))
+    }
+
+    /// Find the release a program identifier belongs to
+    pub fn release_for(&self, zkvm: &str, program_id: &str) -> Option<(&GuestRelease, &RegisteredProgram)> {
+        self.releases.iter().find_map(|release| {
+            release
+                .programs
+                .iter()
+                .find(|program| program.matches(zkvm, program_id))
+                .map(|program| (release, program))
+        })
+    }
+
+    /// Check that an artifact was proven by a registered guest program
+    ///
+    /// The artifact's program identifier must belong to a release, its circuit
+    /// version must be the one the identifier was registered with, and its
+    /// journal must use an output version the release commits.
+    ///
+    /// # Returns
+    ///
+    /// The release the program belongs to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first check that fails.
+    pub fn verify_program_identifier(&self, artifact: &ProofArtifact) -> Result<&GuestRelease, String> {
+        let (release, program) = self
+            .release_for(&artifact.zkvm, &artifact.program_id)
+            .ok_or_else(|| {
+                format!(
+                    "{} program {} is not a registered release",
+                    artifact.zkvm, artifact.program_id
+                )
+            })?;
+        if program.circuit_version != artifact.circuit_version {
+            return Err(format!(
+                "Program {} of release {} is registered for circuit {}, artifact uses {}",
+                artifact.program_id, release.version, program.circuit_version, artifact.circuit_version
+            ));
+        }
+
+        let journal = hex::decode(artifact.journal.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid journal hex: {}", e))?;
+        let version = output_version(&journal)?;
+        if !release.output_versions.contains(&version) {
+            return Err(format!(
+                "Release {} does not commit output version {}",
+                release.version, version
+            ));
+        }
+        Ok(release)
+    }
+}
+
+fn normalize_program_id(program_id: &str) -> String {
+    program_id.trim().trim_start_matches("0x").to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::signature::Signer;
+    use p256::ecdsa::{DerSignature, SigningKey};
+    use p256::pkcs8::EncodePublicKey;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_slice(&[seed; 32]).unwrap()
+    }
+
+    fn public_key_der(key: &SigningKey) -> Vec<u8> {
+        key.verifying_key().to_public_key_der().unwrap().into_vec()
+    }
+
+    fn registry() -> ProgramRegistry {
+        ProgramRegistry {
+            releases: vec![GuestRelease {
+                version: "0.2.0".to_string(),
+                output_versions: vec![1],
+                programs: vec![RegisteredProgram {
+                    zkvm: "sp1".to_string(),
+                    program_id: "0x00ABCDEF".to_string(),
+                    circuit_version: "v5.0.0".to_string(),
+                }],
+            }],
+        }
+    }
+
+    fn signed(registry: ProgramRegistry, key: &SigningKey) -> Vec<u8> {
+        let signature: DerSignature = key.sign(&registry.canonical_bytes().unwrap());
+        serde_json::to_vec(&SignedRegistry {
+            registry,
+            signature: BASE64_STANDARD.encode(signature.as_bytes()),
+        })
+        .unwrap()
+    }
+
+    fn artifact(program_id: &str, circuit_version: &str, journal: &[u8]) -> ProofArtifact {
+        ProofArtifact {
+            zkvm: "sp1".to_string(),
+            program_id: program_id.to_string(),
+            circuit_version: circuit_version.to_string(),
+            journal: format!("0x{}", hex::encode(journal)),
+            proof: String::new(),
+            operator_signature: None,
+        }
+    }
+
+    #[test]
+    fn test_verify_signed_registry() {
+        let manifest = SignedRegistry::from_json(&signed(registry(), &key(1))).unwrap();
+        let verified = manifest.verify(&public_key_der(&key(1))).unwrap();
+        assert_eq!(verified, registry());
+
+        let journal = [0, 1, 0xaa];
+        let release = verified.verify_program_identifier(&artifact("00abcdef", "v5.0.0", &journal)).unwrap();
+        assert_eq!(release.version, "0.2.0");
+        assert!(verified
+            .verify_program_identifier(&artifact("0x00abcdef", "v4.0.0", &journal))
+            .unwrap_err()
+            .contains("is registered for circuit v5.0.0"));
+        assert!(verified
+            .verify_program_identifier(&artifact("0x0012", "v5.0.0", &journal))
+            .unwrap_err()
+            .ends_with("is not a registered release"));
+    }
+
+    #[test]
+    fn test_verify_tampered_registry() {
+        let mut manifest = SignedRegistry::from_json(&signed(registry(), &key(1))).unwrap();
+        manifest.registry.releases[0].programs[0].program_id = "0x0012".to_string();
+        assert!(manifest
+            .verify(&public_key_der(&key(1)))
+            .unwrap_err()
+            .starts_with("Registry signature verification failed"));
+    }
+
+    #[test]
+    fn test_verify_registry_wrong_key() {
+        let manifest = SignedRegistry::from_json(&signed(registry(), &key(1))).unwrap();
+        assert!(manifest
+            .clone()
+            .verify(&public_key_der(&key(2)))
+            .unwrap_err()
+            .starts_with("Registry signature verification failed"));
+        assert!(manifest
+            .verify(b"not a key")
+            .unwrap_err()
+            .starts_with("Invalid registry public key"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Reproduced-build claims
+//!
+//! A single proof shows that one builder attested to an artifact. With
+//! `ProverInput::with_reproduced_bundle` the guest also verifies a second
+//! bundle, from an independent builder, and commits the combined claim
+//! "artifact D was attested by builders A and B" as a [`ReproducedBuildOutput`],
+//! so a registry or contract can require two-party builds with one proof.
+//!
+//! Both bundles are verified under the input's options and trust material; the
+//! claim is checked with `sigstore_verifier::reproduced::cross_check`.
+
+use alloy_sol_types::{sol, SolValue};
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::reproduced::cross_check;
+use sigstore_verifier::types::result::{VerificationOptions, VerificationResult};
+
+use crate::guest::{split_output_version, VerifiedOutput};
+
+sol! {
+    /// ABI layout of a reproduced-build claim, after `REPRODUCED_BUILD_OUTPUT_HEADER`
+    #[derive(Debug, PartialEq)]
+    struct ReproducedBuild {
+        bytes firstOutput;
+        bytes secondOutput;
+    }
+}
+
+/// Header of a committed reproduced-build claim
+///
+/// Like `guest::FAILED_OUTPUT_HEADER`, it takes the place of the signing time
+/// and timestamp proof type of the raw layout, so the claim can never be read
+/// as a plain verified result.
+pub const REPRODUCED_BUILD_OUTPUT_HEADER: [u8; 9] = [0xfd; 9];
+
+/// Public output of a reproduced-build proof
+///
+/// Each side carries its own signer identity, signing time and options hash;
+/// the guest only commits the claim if both attest to the same subject digest
+/// and their OIDC issuer and subject differ.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReproducedBuildOutput {
+    pub first: VerifiedOutput,
+    pub second: VerifiedOutput,
+}
+
+impl ReproducedBuildOutput {
+    /// Cross-check two verified results and build the claim
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - Result of the input's bundle
+    /// * `second` - Result of the reproduced bundle
+    /// * `options` - Options both bundles were verified under
+    /// * `reference_time` - Optional Unix time to measure the signing ages against
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the digests differ, a builder identity is missing,
+    /// or both bundles come from the same builder.
+    pub fn verify(
+        first: VerificationResult,
+        second: VerificationResult,
+        options: &VerificationOptions,
+        reference_time: Option<u64>,
+    ) -> Result<Self, String> {
+        let reproduced = cross_check(first, second).map_err(|e| e.to_string())?;
+        Ok(Self {
+            first: VerifiedOutput::new(&reproduced.first, options, reference_time)?,
+            second: VerifiedOutput::new(&reproduced.second, options, reference_time)?,
+        })
+    }
+
+    /// Serialize to the committed byte layout:
+    /// `REPRODUCED_BUILD_OUTPUT_HEADER` || ABI-encoded `ReproducedBuild`
+    pub fn as_slice(&self) -> Vec<u8> {
+        let encoded = ReproducedBuild {
+            firstOutput: self.first.as_slice().into(),
+            secondOutput: self.second.as_slice().into(),
+        }
+        .abi_encode();
+        let mut bytes = Vec::with_capacity(REPRODUCED_BUILD_OUTPUT_HEADER.len() + encoded.len());
+        bytes.extend_from_slice(&REPRODUCED_BUILD_OUTPUT_HEADER);
+        bytes.extend_from_slice(&encoded);
+        bytes
+    }
+
+    /// Parse committed bytes, version header included
+    pub fn from_slice(data: &[u8]) -> Result<Self, String> {
+        let (_, data) = split_output_version(data)?;
+        let encoded = data
+            .strip_prefix(&REPRODUCED_BUILD_OUTPUT_HEADER)
+            .ok_or("Output is not a reproduced build claim")?;
+        let claim = ReproducedBuild::abi_decode(encoded)
+            .map_err(|e| format!("Failed to ABI decode reproduced build claim: {}", e))?;
+        Ok(Self {
+            first: VerifiedOutput::from_body(&claim.firstOutput)?,
+            second: VerifiedOutput::from_body(&claim.secondOutput)?,
+        })
+    }
+}
+
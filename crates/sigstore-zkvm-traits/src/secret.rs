@@ -0,0 +1,72 @@
+//! Key material handling for prover hosts
+//!
+//! Network and wallet keys are held in [`SecretKey`], which zeroizes its
+//! buffer on drop and never prints the key in `Debug` output. Hosts read keys
+//! from CLI arguments or the environment today; other key providers should
+//! hand keys over as a `SecretKey` as well.
+
+use std::fmt;
+use std::str::FromStr;
+
+use zeroize::{Zeroize, Zeroizing};
+
+/// A private key string that is wiped from memory when dropped
+#[derive(Clone, Default)]
+pub struct SecretKey(Zeroizing<String>);
+
+impl SecretKey {
+    pub fn new(key: String) -> Self {
+        SecretKey(Zeroizing::new(key))
+    }
+
+    /// Borrow the key, e.g. to pass it to an SDK that takes `&str`
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Decode a hex key (with or without a `0x` prefix) into zeroizing bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not valid hex. The message never includes the key.
+    pub fn decode_hex(&self) -> Result<Zeroizing<Vec<u8>>, String> {
+        let hex = self.0.strip_prefix("0x").unwrap_or(&self.0);
+        let mut bytes = Zeroizing::new(vec![0u8; hex.len() / 2]);
+        if !hex.len().is_multiple_of(2) {
+            return Err("Private key has an odd number of hex digits".to_string());
+        }
+        hex::decode_to_slice(hex, &mut bytes)
+            .map_err(|_| "Private key is not valid hex".to_string())?;
+        Ok(bytes)
+    }
+}
+
+impl From<String> for SecretKey {
+    fn from(key: String) -> Self {
+        SecretKey::new(key)
+    }
+}
+
+impl FromStr for SecretKey {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(SecretKey::new(s.to_string()))
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
@@ -0,0 +1,541 @@
+//! Combined TEE + SLSA claims
+//!
+//! A Sigstore bundle proves that an artifact was built by a workflow of
+//! repository X; an Intel DCAP quote proves that a runner with measurement R
+//! ran inside an SGX enclave or TDX trust domain. When the runner puts the
+//! artifact's digest at the start of the quote's report data, the guest can
+//! verify both and commit the combined claim "artifact D was built by attested
+//! runner R from repository X" as an [`AttestedBuildOutput`].
+//!
+//! The guest checks the quote's signature, the Quoting Enclave (QE) report
+//! and its binding to the attestation key, and the PCK certificate chain up to
+//! the pinned Intel SGX root CA. It does not evaluate the platform's TCB
+//! status, the QE identity or PCK revocation, which need Intel's collateral;
+//! the committed `quote_hash` lets a consumer check those with the Automata
+//! DCAP attestation contracts, which take the same quote.
+
+use alloy_sol_types::{sol, SolValue};
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::algorithm::DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS;
+use sigstore_verifier::crypto::hash::sha256;
+use sigstore_verifier::parser::certificate::{parse_der_certificate, parse_pem_certificates};
+use sigstore_verifier::types::certificate::CertificateChain;
+use sigstore_verifier::types::digest::Digest;
+use sigstore_verifier::verifier::certificate::verify_certificate_chain_der;
+
+use crate::guest::{split_output_version, VerifiedOutput};
+
+sol! {
+    /// ABI layout of a combined TEE + SLSA claim, after `ATTESTED_BUILD_OUTPUT_HEADER`
+    #[derive(Debug, PartialEq)]
+    struct AttestedBuild {
+        bytes verifiedOutput;
+        uint32 teeType;
+        bytes runnerMeasurement;
+        bytes32 runnerSigner;
+        bytes reportData;
+        bytes32 quoteHash;
+        bytes32 pckRootHash;
+    }
+}
+
+/// Header of a committed combined claim
+///
+/// Like `guest::FAILED_OUTPUT_HEADER`, it takes the place of the signing time
+/// and timestamp proof type of the raw layout, so the claim can never be read
+/// as a plain verified result.
+pub const ATTESTED_BUILD_OUTPUT_HEADER: [u8; 9] = [0xfe; 9];
+
+/// `tee_type` of an SGX quote
+pub const TEE_TYPE_SGX: u32 = 0x0000_0000;
+
+/// `tee_type` of a TDX quote
+pub const TEE_TYPE_TDX: u32 = 0x0000_0081;
+
+/// Attestation key type ECDSA-256-with-P-256
+const ATTESTATION_KEY_TYPE_ECDSA_P256: u16 = 2;
+
+/// Certification data holding the PCK certificate chain as PEM
+const CERT_DATA_PCK_CHAIN: u16 = 5;
+
+/// Certification data holding the QE report (quote version 4)
+const CERT_DATA_QE_REPORT: u16 = 6;
+
+const QUOTE_HEADER_LEN: usize = 48;
+const SGX_REPORT_LEN: usize = 384;
+const TDX_REPORT_LEN: usize = 584;
+const SGX_REPORT_DATA_OFFSET: usize = 320;
+const ECDSA_P256_LEN: usize = 64;
+
+/// TEE evidence of the runner that built the artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeeEvidence {
+    /// DCAP quote (version 3 or 4) whose report data starts with the artifact digest
+    pub quote: Vec<u8>,
+    /// DER Intel SGX root CA certificate the quote's PCK chain must end in
+    pub pck_root: Vec<u8>,
+}
+
+/// Kind of trusted execution environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TeeType {
+    Sgx,
+    Tdx,
+}
+
+impl TeeType {
+    /// The quote header's `tee_type` value
+    pub fn id(self) -> u32 {
+        match self {
+            TeeType::Sgx => TEE_TYPE_SGX,
+            TeeType::Tdx => TEE_TYPE_TDX,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            TEE_TYPE_SGX => Some(TeeType::Sgx),
+            TEE_TYPE_TDX => Some(TeeType::Tdx),
+            _ => None,
+        }
+    }
+
+    fn report_len(self) -> usize {
+        match self {
+            TeeType::Sgx => SGX_REPORT_LEN,
+            TeeType::Tdx => TDX_REPORT_LEN,
+        }
+    }
+}
+
+/// Identity of an attested runner, read from a verified quote
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeeRunner {
+    pub tee_type: TeeType,
+    /// MRENCLAVE (SGX, 32 bytes) or MRTD (TDX, 48 bytes)
+    pub measurement: Vec<u8>,
+    /// MRSIGNER (SGX); zero for TDX
+    pub signer: [u8; 32],
+    /// The 64 bytes of report data the runner put in the quote
+    pub report_data: Vec<u8>,
+}
+
+impl TeeRunner {
+    /// Check that the report data starts with the artifact digest
+    ///
+    /// This is what ties the runner to the artifact the bundle attests to.
+    pub fn check_subject(&self, subject: &Digest) -> Result<(), String> {
+        if subject.bytes.is_empty() || !self.report_data.starts_with(&subject.bytes) {
+            return Err(format!(
+                "Quote report data does not start with the subject digest {}",
+                subject
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Public output of a combined TEE + SLSA proof
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestedBuildOutput {
+    /// Output of the bundle verification: subject digest, signer identity, options hash
+    pub verified: VerifiedOutput,
+    pub runner: TeeRunner,
+    /// SHA-256 of the quote
+    pub quote_hash: [u8; 32],
+    /// SHA-256 of the DER root CA the PCK chain was verified against
+    pub pck_root_hash: [u8; 32],
+}
+
+impl AttestedBuildOutput {
+    /// Verify the runner's quote and bind it to a verified bundle
+    ///
+    /// # Arguments
+    ///
+    /// * `verified` - Output of the bundle verification
+    /// * `subject` - The bundle's subject digest, which the quote's report data must start with
+    /// * `evidence` - Quote of the runner and the pinned Intel root CA
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quote does not verify or does not name the subject.
+    pub fn verify(verified: VerifiedOutput, subject: &Digest, evidence: &TeeEvidence) -> Result<Self, String> {
+        let runner = verify_dcap_quote(&evidence.quote, &evidence.pck_root)?;
+        runner.check_subject(subject)?;
+        Ok(Self {
+            verified,
+            runner,
+            quote_hash: sha256(&evidence.quote),
+            pck_root_hash: sha256(&evidence.pck_root),
+        })
+    }
+
+    /// Serialize to the committed byte layout:
+    /// `ATTESTED_BUILD_OUTPUT_HEADER` || ABI-encoded `AttestedBuild`
+    pub fn as_slice(&self) -> Vec<u8> {
+        let encoded = AttestedBuild {
+            verifiedOutput: self.verified.as_slice().into(),
+            teeType: self.runner.tee_type.id(),
+            runnerMeasurement: self.runner.measurement.clone().into(),
+            runnerSigner: self.runner.signer.into(),
+            reportData: self.runner.report_data.clone().into(),
+            quoteHash: self.quote_hash.into(),
+            pckRootHash: self.pck_root_hash.into(),
+        }
+        .abi_encode();
+        let mut bytes = Vec::with_capacity(ATTESTED_BUILD_OUTPUT_HEADER.len() + encoded.len());
+        bytes.extend_from_slice(&ATTESTED_BUILD_OUTPUT_HEADER);
+        bytes.extend_from_slice(&encoded);
+        bytes
+    }
+
+    /// Parse committed bytes, version header included
+    pub fn from_slice(data: &[u8]) -> Result<Self, String> {
+        let (_, data) = split_output_version(data)?;
+        let encoded = data
+            .strip_prefix(&ATTESTED_BUILD_OUTPUT_HEADER)
+            .ok_or("Output is not an attested build claim")?;
+        let claim = AttestedBuild::abi_decode(encoded)
+            .map_err(|e| format!("Failed to ABI decode attested build claim: {}", e))?;
+        let tee_type = TeeType::from_id(claim.teeType)
+            .ok_or_else(|| format!("Unknown TEE type {:#x}", claim.teeType))?;
+        Ok(Self {
+            verified: VerifiedOutput::from_body(&claim.verifiedOutput)?,
+            runner: TeeRunner {
+                tee_type,
+                measurement: claim.runnerMeasurement.to_vec(),
+                signer: claim.runnerSigner.into(),
+                report_data: claim.reportData.to_vec(),
+            },
+            quote_hash: claim.quoteHash.into(),
+            pck_root_hash: claim.pckRootHash.into(),
+        })
+    }
+}
+
+/// Verify a DCAP quote against a pinned Intel SGX root CA
+///
+/// Checks, in order: the PCK certificate chain embedded in the quote up to
+/// `pck_root`, the QE report signature by the PCK key, the QE report's binding
+/// of the attestation key, and the quote signature by the attestation key.
+///
+/// # Arguments
+///
+/// * `quote` - Quote version 3 (SGX) or 4 (SGX or TDX) with an ECDSA P-256
+///   attestation key and an embedded PCK certificate chain
+/// * `pck_root` - DER Intel SGX root CA certificate
+///
+/// # Returns
+///
+/// The runner identity from the quote's report body
+///
+/// # Errors
+///
+/// Returns an error if the quote is malformed or any check fails.
+pub fn verify_dcap_quote(quote: &[u8], pck_root: &[u8]) -> Result<TeeRunner, String> {
+    let quote = DcapQuote::parse(quote)?;
+
+    let certs = parse_pem_certificates(quote.pck_chain).map_err(|e| format!("Invalid PCK certificate chain: {}", e))?;
+    let (pck_leaf, rest) = certs.split_first().ok_or("Empty PCK certificate chain")?;
+    // The chain may end in its own copy of the root; only the pinned one is trusted
+    let intermediates: Vec<Vec<u8>> = rest.iter().filter(|der| der.as_slice() != pck_root).cloned().collect();
+    if intermediates.is_empty() {
+        return Err("PCK certificate chain has no intermediate CA".to_string());
+    }
+    let trust = CertificateChain {
+        leaf: Vec::new(),
+        intermediates,
+        root: pck_root.to_vec(),
+    };
+    verify_certificate_chain_der(pck_leaf.clone(), &trust, DEFAULT_ALLOWED_SIGNATURE_ALGORITHMS)
+        .map_err(|e| format!("PCK certificate chain verification failed: {}", e))?;
+
+    let pck_cert = parse_der_certificate(pck_leaf).map_err(|e| format!("Invalid PCK certificate: {}", e))?;
+    let pck_key = VerifyingKey::from_sec1_bytes(&pck_cert.public_key().subject_public_key.data)
+        .map_err(|e| format!("PCK certificate key is not P-256: {}", e))?;
+    verify_raw_signature(&pck_key, quote.qe_report, quote.qe_report_signature, "QE report")?;
+
+    let mut bound = Vec::with_capacity(ECDSA_P256_LEN + quote.qe_auth_data.len());
+    bound.extend_from_slice(quote.attestation_key);
+    bound.extend_from_slice(quote.qe_auth_data);
+    let qe_report_data = &quote.qe_report[SGX_REPORT_DATA_OFFSET..];
+    if qe_report_data[..32] != sha256(&bound) || qe_report_data[32..].iter().any(|byte| *byte != 0) {
+        return Err("QE report does not bind the attestation key".to_string());
+    }
+
+    let mut sec1 = [0u8; 1 + ECDSA_P256_LEN];
+    sec1[0] = 0x04;
+    sec1[1..].copy_from_slice(quote.attestation_key);
+    let attestation_key =
+        VerifyingKey::from_sec1_bytes(&sec1).map_err(|e| format!("Invalid attestation key: {}", e))?;
+    verify_raw_signature(&attestation_key, quote.signed, quote.signature, "Quote")?;
+
+    Ok(quote.runner())
+}
+
+fn verify_raw_signature(key: &VerifyingKey, message: &[u8], signature: &[u8], what: &str) -> Result<(), String> {
+    let signature = Signature::from_slice(signature).map_err(|e| format!("Invalid {} signature: {}", what, e))?;
+    key.verify(message, &signature)
+        .map_err(|_| format!("{} signature verification failed", what))
+}
+
+/// The parts of a DCAP quote the guest checks
+struct DcapQuote<'a> {
+    tee_type: TeeType,
+    /// Header and report body, the message of the quote signature
+    signed: &'a [u8],
+    body: &'a [u8],
+    signature: &'a [u8],
+    attestation_key: &'a [u8],
+    qe_report: &'a [u8],
+    qe_report_signature: &'a [u8],
+    qe_auth_data: &'a [u8],
+    pck_chain: &'a [u8],
+}
+
+impl<'a> DcapQuote<'a> {
+    fn parse(quote: &'a [u8]) -> Result<Self, String> {
+        let mut reader = Reader::new(quote);
+        let version = reader.u16("version")?;
+        let key_type = reader.u16("attestation key type")?;
+        let tee_id = reader.u32("TEE type")?;
+        reader.take(QUOTE_HEADER_LEN - 8, "header")?;
+        if version != 3 && version != 4 {
+            return Err(format!("Unsupported quote version {}", version));
+        }
+        if key_type != ATTESTATION_KEY_TYPE_ECDSA_P256 {
+            return Err(format!("Unsupported attestation key type {}", key_type));
+        }
+        let tee_type = match TeeType::from_id(tee_id) {
+            Some(TeeType::Tdx) if version == 3 => return Err("Quote version 3 cannot hold a TDX report".to_string()),
+            Some(tee_type) => tee_type,
+            None => return Err(format!("Unknown TEE type {:#x}", tee_id)),
+        };
+        let body = reader.take(tee_type.report_len(), "report body")?;
+        let signed = &quote[..reader.offset];
+
+        let signature_len = reader.u32("signature data length")? as usize;
+        let mut signature_data = Reader::new(reader.take(signature_len, "signature data")?);
+        let signature = signature_data.take(ECDSA_P256_LEN, "quote signature")?;
+        let attestation_key = signature_data.take(ECDSA_P256_LEN, "attestation key")?;
+        // Version 4 wraps the QE report in certification data of its own
+        let mut qe = if version == 4 {
+            let (cert_type, data) = signature_data.certification_data()?;
+            if cert_type != CERT_DATA_QE_REPORT {
+                return Err(format!("Expected QE report certification data, got type {}", cert_type));
+            }
+            Reader::new(data)
+        } else {
+            signature_data
+        };
+        let qe_report = qe.take(SGX_REPORT_LEN, "QE report")?;
+        let qe_report_signature = qe.take(ECDSA_P256_LEN, "QE report signature")?;
+        let qe_auth_len = qe.u16("QE authentication data length")? as usize;
+        let qe_auth_data = qe.take(qe_auth_len, "QE authentication data")?;
+        let (cert_type, pck_chain) = qe.certification_data()?;
+        if cert_type != CERT_DATA_PCK_CHAIN {
+            return Err(format!(
+                "Quote does not embed its PCK certificate chain (certification data type {})",
+                cert_type
+            ));
+        }
+
+        Ok(Self {
+            tee_type,
+            signed,
+            body,
+            signature,
+            attestation_key,
+            qe_report,
+            qe_report_signature,
+            qe_auth_data,
+            pck_chain,
+        })
+    }
+
+    fn runner(&self) -> TeeRunner {
+        let (measurement, signer, report_data) = match self.tee_type {
+            TeeType::Sgx => (&self.body[80..112], &self.body[128..160], &self.body[320..384]),
+            TeeType::Tdx => (&self.body[136..184], &[0u8; 32][..], &self.body[520..584]),
+        };
+        let mut signer_bytes = [0u8; 32];
+        signer_bytes.copy_from_slice(signer);
+        TeeRunner {
+            tee_type: self.tee_type,
+            measurement: measurement.to_vec(),
+            signer: signer_bytes,
+            report_data: report_data.to_vec(),
+        }
+    }
+}
+
+/// Little-endian cursor over quote bytes
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize, what: &str) -> Result<&'a [u8], String> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| format!("Quote truncated in {}", what))?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self, what: &str) -> Result<u16, String> {
+        let bytes = self.take(2, what)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self, what: &str) -> Result<u32, String> {
+        let bytes = self.take(4, what)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Certification data: type (2 bytes) || size (4 bytes) || data
+    fn certification_data(&mut self) -> Result<(u16, &'a [u8]), String> {
+        let cert_type = self.u16("certification data type")?;
+        let size = self.u32("certification data size")? as usize;
+        Ok((cert_type, self.take(size, "certification data")?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use p256::ecdsa::signature::Signer;
+    use p256::ecdsa::SigningKey;
+    use p256::pkcs8::DecodePrivateKey;
+
+    const SUBJECT: [u8; 32] = [0x5a; 32];
+
+    fn sample(name: &str) -> String {
+        let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push(format!("samples/certs/{}", name));
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    fn pck_root() -> Vec<u8> {
+        parse_pem_certificates(sample("pck-root.pem").as_bytes()).unwrap().remove(0)
+    }
+
+    fn pck_key() -> SigningKey {
+        let pem: String = sample("pck-leaf.key").lines().filter(|line| !line.starts_with("-----")).collect();
+        SigningKey::from_pkcs8_der(&STANDARD.decode(pem).unwrap()).unwrap()
+    }
+
+    fn sign(key: &SigningKey, message: &[u8]) -> Vec<u8> {
+        let signature: Signature = key.sign(message);
+        signature.to_bytes().to_vec()
+    }
+
+    /// Version 3 SGX quote with the given report data, signed through the fixture PCK chain
+    fn sgx_quote(report_data: &[u8; 64], qe_auth_data: &[u8]) -> Vec<u8> {
+        let mut header = [0u8; QUOTE_HEADER_LEN];
+        header[..2].copy_from_slice(&3u16.to_le_bytes());
+        header[2..4].copy_from_slice(&ATTESTATION_KEY_TYPE_ECDSA_P256.to_le_bytes());
+        header[4..8].copy_from_slice(&TEE_TYPE_SGX.to_le_bytes());
+        let mut body = [0u8; SGX_REPORT_LEN];
+        body[80..112].fill(0x11);
+        body[128..160].fill(0x22);
+        body[SGX_REPORT_DATA_OFFSET..].copy_from_slice(report_data);
+        let signed = [header.as_slice(), &body].concat();
+
+        let attestation = SigningKey::from_slice(&[7; 32]).unwrap();
+        let attestation_key = attestation.verifying_key().to_encoded_point(false).as_bytes()[1..].to_vec();
+        let mut qe_report = [0u8; SGX_REPORT_LEN];
+        qe_report[SGX_REPORT_DATA_OFFSET..SGX_REPORT_DATA_OFFSET + 32]
+            .copy_from_slice(&sha256(&[attestation_key.as_slice(), qe_auth_data].concat()));
+        let pck_chain = [sample("pck-leaf.pem"), sample("pck-intermediate.pem"), sample("pck-root.pem")].concat();
+
+        let mut signature_data = Vec::new();
+        signature_data.extend_from_slice(&sign(&attestation, &signed));
+        signature_data.extend_from_slice(&attestation_key);
+        signature_data.extend_from_slice(&qe_report);
+        signature_data.extend_from_slice(&sign(&pck_key(), &qe_report));
+        signature_data.extend_from_slice(&(qe_auth_data.len() as u16).to_le_bytes());
+        signature_data.extend_from_slice(qe_auth_data);
+        signature_data.extend_from_slice(&CERT_DATA_PCK_CHAIN.to_le_bytes());
+        signature_data.extend_from_slice(&(pck_chain.len() as u32).to_le_bytes());
+        signature_data.extend_from_slice(pck_chain.as_bytes());
+
+        let mut quote = signed;
+        quote.extend_from_slice(&(signature_data.len() as u32).to_le_bytes());
+        quote.extend_from_slice(&signature_data);
+        quote
+    }
+
+    fn report_data(subject: &[u8; 32]) -> [u8; 64] {
+        let mut report_data = [0u8; 64];
+        report_data[..32].copy_from_slice(subject);
+        report_data
+    }
+
+    #[test]
+    fn test_verify_dcap_quote() {
+        let quote = sgx_quote(&report_data(&SUBJECT), b"qe auth");
+        let runner = verify_dcap_quote(&quote, &pck_root()).unwrap();
+        assert_eq!(runner.tee_type, TeeType::Sgx);
+        assert_eq!(runner.measurement, vec![0x11; 32]);
+        assert_eq!(runner.signer, [0x22; 32]);
+        assert_eq!(runner.report_data, report_data(&SUBJECT).to_vec());
+        runner.check_subject(&Digest::sha256(SUBJECT)).unwrap();
+
+        // A chain that does not lead to the pinned root
+        let other_root = parse_pem_certificates(sample("tls-root.pem").as_bytes()).unwrap().remove(0);
+        assert!(verify_dcap_quote(&quote, &other_root)
+            .unwrap_err()
+            .starts_with("PCK certificate chain verification failed"));
+
+        // A report body changed after signing
+        let mut tampered = quote.clone();
+        tampered[QUOTE_HEADER_LEN + 80] ^= 1;
+        assert_eq!(
+            verify_dcap_quote(&tampered, &pck_root()).unwrap_err(),
+            "Quote signature verification failed"
+        );
+    }
+
+    #[test]
+    fn test_verify_truncated_quote() {
+        let quote = sgx_quote(&report_data(&SUBJECT), b"qe auth");
+        let signature_data = QUOTE_HEADER_LEN + SGX_REPORT_LEN + 4;
+        for len in [0, 7, QUOTE_HEADER_LEN + 1, signature_data, signature_data + 200, quote.len() - 1] {
+            let error = verify_dcap_quote(&quote[..len], &pck_root()).unwrap_err();
+            assert!(error.starts_with("Quote truncated in "), "{} bytes: {}", len, error);
+        }
+    }
+
+    #[test]
+    fn test_report_data_binding() {
+        let quote = sgx_quote(&report_data(&[0x6b; 32]), b"qe auth");
+        let runner = verify_dcap_quote(&quote, &pck_root()).unwrap();
+        assert!(runner
+            .check_subject(&Digest::sha256(SUBJECT))
+            .unwrap_err()
+            .starts_with("Quote report data does not start with the subject digest"));
+
+        // The QE report must bind the attestation key together with the authentication data
+        let mut unbound = quote;
+        let qe_auth = unbound.windows(7).position(|window| window == b"qe auth").unwrap();
+        unbound[qe_auth] ^= 1;
+        assert_eq!(
+            verify_dcap_quote(&unbound, &pck_root()).unwrap_err(),
+            "QE report does not bind the attestation key"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use std::any::Any;
 use crate::{error::ZkVmError, types::ProverInput};
 
 /// Trait for zkVM provers that generate proofs of sigstore verification
@@ -67,3 +68,102 @@ pub trait ZkVmProver: Sized {
     /// A static reference to the ELF binary bytes
     fn elf(&self) -> &'static [u8];
 }
+
+/// Type-erased configuration of a [`DynProver`]
+///
+/// Wraps the `Config` of the prover it is passed to; a config of another
+/// prover type is rejected by [`DynProver::prove`].
+pub struct DynConfig(Box<dyn Any + Send + Sync>);
+
+impl DynConfig {
+    /// Erase `config`, the `Config` of a [`ZkVmProver`]
+    pub fn new<C: Any + Send + Sync>(config: C) -> Self {
+        DynConfig(Box::new(config))
+    }
+
+    /// The wrapped config, if it is a `C`
+    pub fn downcast_ref<C: Any>(&self) -> Option<&C> {
+        self.0.downcast_ref()
+    }
+}
+
+impl std::fmt::Debug for DynConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynConfig").finish_non_exhaustive()
+    }
+}
+
+/// Object-safe form of [`ZkVmProver`]
+///
+/// [`ZkVmProver`] has an associated `Config` and a static `circuit_version`,
+/// so it cannot be used as `dyn ZkVmProver`. Every `ZkVmProver` whose config
+/// is `Send + Sync` implements `DynProver`, which takes its config as a
+/// [`DynConfig`], so a service can pick a backend at runtime and hold it as
+/// `Box<dyn DynProver>`.
+///
+/// # Example
+///
+/// ```ignore
+/// let prover: Box<dyn DynProver> = match backend {
+///     "sp1" => Box::new(Sp1Prover::new()?),
+///     _ => Box::new(Risc0Prover::new()?),
+/// };
+/// let config = DynConfig::new(sp1_config);
+/// let (public_output, proof_bytes) = prover.prove_dyn(&config, &input).await?;
+/// ```
+#[async_trait]
+pub trait DynProver: Send + Sync {
+    /// Generate a proof, see [`ZkVmProver::prove`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZkVmError::InvalidInput`] if `config` does not hold this
+    /// prover's `Config`, or any error of the underlying prover.
+    async fn prove_dyn(
+        &self,
+        config: &DynConfig,
+        input: &ProverInput,
+    ) -> Result<(Vec<u8>, Vec<u8>), ZkVmError>;
+
+    /// See [`ZkVmProver::program_identifier`]
+    fn program_identifier_dyn(&self) -> Result<String, ZkVmError>;
+
+    /// See [`ZkVmProver::circuit_version`]
+    fn circuit_version_dyn(&self) -> String;
+
+    /// See [`ZkVmProver::elf`]
+    fn elf_dyn(&self) -> &'static [u8];
+}
+
+#[async_trait]
+impl<P> DynProver for P
+where
+    P: ZkVmProver + Send + Sync,
+    P::Config: Any + Send + Sync,
+{
+    async fn prove_dyn(
+        &self,
+        config: &DynConfig,
+        input: &ProverInput,
+    ) -> Result<(Vec<u8>, Vec<u8>), ZkVmError> {
+        let config = config.downcast_ref::<P::Config>().ok_or_else(|| {
+            ZkVmError::InvalidInput(format!(
+                "Config is not a {}",
+                std::any::type_name::<P::Config>()
+            ))
+        })?;
+        self.prove(config, input).await
+    }
+
+    fn program_identifier_dyn(&self) -> Result<String, ZkVmError> {
+        self.program_identifier()
+    }
+
+    fn circuit_version_dyn(&self) -> String {
+        P::circuit_version()
+    }
+
+    fn elf_dyn(&self) -> &'static [u8] {
+        self.elf()
+    }
+}
@@ -1,6 +1,39 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sigstore_verifier::types::result::VerificationOptions;
 use sigstore_verifier::types::certificate::CertificateChain;
+use sigstore_verifier::types::limits::InputLimits;
+
+use crate::codec::OutputCodecId;
+use crate::tee::TeeEvidence;
+
+/// Version of the encoded input header written by `encode_input()`
+pub const INPUT_FORMAT_VERSION: u8 = 5;
+
+/// Header flag: the bincode payload is zstd-compressed
+pub const INPUT_FLAG_ZSTD: u8 = 0b0000_0001;
+
+/// Header flag: the payload is a `BatchProverInput` rather than a `ProverInput`
+pub const INPUT_FLAG_BATCH: u8 = 0b0000_0010;
+
+/// Header flags selecting the payload type; at most one may be set
+const INPUT_KIND_FLAGS: u8 = INPUT_FLAG_BATCH;
+
+/// Length of the encoded input header: [version (1 byte)] || [flags (1 byte)]
+const INPUT_HEADER_LEN: usize = 2;
+
+/// Room allowed per certificate of a chain in a decompressed payload
+const MAX_CERTIFICATE_SIZE: usize = 64 * 1024;
+
+/// Room allowed for the options, TEE evidence and encoding overhead in a decompressed payload
+const INPUT_OVERHEAD: usize = 1024 * 1024;
+
+/// Host-side options controlling how a `ProverInput` is encoded for the guest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InputEncoding {
+    /// Compress the payload with zstd (requires the `compression` feature)
+    pub compress: bool,
+}
 
 /// Input data for the zkVM prover
 ///
@@ -19,6 +52,22 @@ pub struct ProverInput {
 
     /// Optional TSA certificate chain in PEM format for RFC3161 timestamp verification
     pub tsa_cert_chain: Option<CertificateChain>,
+
+    /// Optional Unix time the guest measures the signing age against (see `VerifiedOutput`)
+    pub reference_time: Option<u64>,
+
+    /// Encoding of the committed output (see `crate::codec`)
+    pub output_codec: OutputCodecId,
+
+    /// Commit `ProverOutput::Failed` instead of aborting if verification fails
+    pub prove_failures: bool,
+
+    /// DCAP quote of the build runner, to commit a combined TEE + SLSA claim
+    pub tee_evidence: Option<TeeEvidence>,
+
+    /// Bundle of an independent builder attesting to the same artifact, to
+    /// commit a reproduced-build claim
+    pub reproduced_bundle_json: Option<Vec<u8>>,
 }
 
 impl ProverInput {
@@ -34,24 +83,436 @@ impl ProverInput {
             verification_options,
             trust_bundle,
             tsa_cert_chain,
+            reference_time: None,
+            output_codec: OutputCodecId::Raw,
+            prove_failures: false,
+            tee_evidence: None,
+            reproduced_bundle_json: None,
         }
     }
 
+    /// Commit the signing age relative to `reference_time` (Unix seconds)
+    ///
+    /// The guest commits `reference_time` and `max(0, reference_time - signing_time)`
+    /// in its output, so a contract can bound the attestation's age at proof time
+    /// by checking `reference_time` against the block timestamp.
+    pub fn with_reference_time(mut self, reference_time: u64) -> Self {
+        self.reference_time = Some(reference_time);
+        self
+    }
+
+    /// Commit the output in `codec` instead of the raw `VerifiedOutput` layout
+    ///
+    /// Applies to single-bundle input; batch entries always commit raw outputs
+    /// as the leaves of the batch's Merkle tree.
+    pub fn with_output_codec(mut self, codec: OutputCodecId) -> Self {
+        self.output_codec = codec;
+        self
+    }
+
+    /// Prove verification failures instead of aborting the guest
+    ///
+    /// A bundle that fails verification then commits `ProverOutput::Failed`
+    /// with the failing step and error code, so the proof shows that the
+    /// bundle was rejected. Applies to single-bundle input; a failing batch
+    /// entry still aborts the batch.
+    pub fn with_prove_failures(mut self) -> Self {
+        self.prove_failures = true;
+        self
+    }
+
+    /// Verify the build runner's TEE quote along with the bundle
+    ///
+    /// The guest then commits a `tee::AttestedBuildOutput` instead of the
+    /// verified output, whatever the output codec: the bundle's result plus
+    /// the identity of the runner whose quote names the bundle's subject.
+    /// Applies to single-bundle input.
+    pub fn with_tee_evidence(mut self, evidence: TeeEvidence) -> Self {
+        self.tee_evidence = Some(evidence);
+        self
+    }
+
+    /// Verify a second builder's bundle for the same artifact along with the bundle
+    ///
+    /// The guest verifies both under the input's options and trust material
+    /// and commits a `reproduced::ReproducedBuildOutput` instead of the
+    /// verified output, whatever the output codec, if they attest to the same
+    /// subject digest from different signing identities. Applies to
+    /// single-bundle input without TEE evidence; a failing second bundle or
+    /// cross-check aborts the guest even with `prove_failures`.
+    pub fn with_reproduced_bundle(mut self, bundle_json: Vec<u8>) -> Self {
+        self.reproduced_bundle_json = Some(bundle_json);
+        self
+    }
+
     /// Encode the ProverInput to bytes for host-to-guest communication
     ///
     /// This method serializes the ProverInput using bincode for efficient
     /// binary encoding to be passed from the host to the guest program.
+    /// The payload is prefixed with a 2-byte header: [version] || [flags].
     pub fn encode_input(&self) -> Result<Vec<u8>, String> {
-        bincode::serialize(self)
-            .map_err(|e| format!("Failed to serialize ProverInput: {}", e))
+        encode_with_header(self, 0, "ProverInput")
+    }
+
+    /// Encode the ProverInput with a zstd-compressed payload
+    ///
+    /// Bundles and trust material are mostly base64 and PEM text, which compresses
+    /// well. This shrinks uploads to network provers at the cost of extra guest
+    /// cycles for decompression, which have not been measured yet, so measure
+    /// both before enabling it by default.
+    #[cfg(feature = "compression")]
+    pub fn encode_input_compressed(&self) -> Result<Vec<u8>, String> {
+        encode_with_header(self, INPUT_FLAG_ZSTD, "ProverInput")
+    }
+
+    /// Encode the ProverInput according to the given host-side options
+    pub fn encode_input_with(&self, encoding: InputEncoding) -> Result<Vec<u8>, String> {
+        let zstd = if encoding.compress { INPUT_FLAG_ZSTD } else { 0 };
+        encode_with_header(self, zstd, "ProverInput")
     }
 
     /// Parse ProverInput from bytes in the guest program
     ///
-    /// This method deserializes the ProverInput from the bincode format
-    /// created by encode_input().
+    /// This method deserializes the ProverInput from the format created by
+    /// encode_input() or encode_input_compressed(). Compressed input requires
+    /// the `compression` feature and is decompressed within the bound of
+    /// `InputLimits::default()` (see `max_decompressed_size`).
     pub fn parse_input(bytes: &[u8]) -> Result<Self, String> {
-        bincode::deserialize(bytes)
-            .map_err(|e| format!("Failed to deserialize ProverInput: {}", e))
+        Self::parse_input_with_limits(bytes, &InputLimits::default())
+    }
+
+    /// Parse ProverInput from bytes, decompressing within the bound of `limits`
+    pub fn parse_input_with_limits(bytes: &[u8], limits: &InputLimits) -> Result<Self, String> {
+        let (flags, payload) = decode_header(bytes)?;
+        expect_kind(flags, 0, "ProverInput")?;
+        decode_payload(flags, payload, "ProverInput", limits)
+    }
+}
+
+/// Reference to a shared trust bundle and optional TSA chain in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchEntry {
+    /// Sigstore attestation bundle in JSON format
+    pub bundle_json: Vec<u8>,
+
+    /// Options for verification (expected digest, issuer, subject, etc.)
+    pub verification_options: VerificationOptions,
+
+    /// Index into `BatchProverInput::trust_bundles`
+    pub trust_bundle_index: u32,
+
+    /// Optional index into `BatchProverInput::tsa_cert_chains`
+    pub tsa_cert_chain_index: Option<u32>,
+
+    /// Optional Unix time the signing age is measured against
+    pub reference_time: Option<u64>,
+}
+
+/// Input for proving several bundles in one guest execution
+///
+/// Bundles signed by the same Fulcio instance share a trust bundle, so each
+/// distinct certificate chain is stored once and entries refer to it by index.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchProverInput {
+    /// Distinct Fulcio certificate chains referenced by the entries
+    pub trust_bundles: Vec<CertificateChain>,
+
+    /// Distinct TSA certificate chains referenced by the entries
+    pub tsa_cert_chains: Vec<CertificateChain>,
+
+    /// Bundles to verify, in output order
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchProverInput {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single-bundle input to the batch, reusing already stored trust material
+    ///
+    /// # Returns
+    ///
+    /// The index of the new entry
+    pub fn push(&mut self, input: ProverInput) -> usize {
+        let trust_bundle_index = intern_chain(&mut self.trust_bundles, input.trust_bundle);
+        let tsa_cert_chain_index = input
+            .tsa_cert_chain
+            .map(|chain| intern_chain(&mut self.tsa_cert_chains, chain));
+
+        self.entries.push(BatchEntry {
+            bundle_json: input.bundle_json,
+            verification_options: input.verification_options,
+            trust_bundle_index,
+            tsa_cert_chain_index,
+            reference_time: input.reference_time,
+        });
+        self.entries.len() - 1
+    }
+
+    /// Expand the entry at `index` back into a standalone ProverInput
+    pub fn entry_input(&self, index: usize) -> Result<ProverInput, String> {
+        let entry = self
+            .entries
+            .get(index)
+            .ok_or_else(|| format!("Batch entry {} out of range", index))?;
+
+        let trust_bundle = self
+            .trust_bundles
+            .get(entry.trust_bundle_index as usize)
+            .ok_or_else(|| format!("Entry {}: trust bundle index {} out of range", index, entry.trust_bundle_index))?
+            .clone();
+
+        let tsa_cert_chain = match entry.tsa_cert_chain_index {
+            Some(tsa_index) => Some(
+                self.tsa_cert_chains
+                    .get(tsa_index as usize)
+                    .ok_or_else(|| format!("Entry {}: TSA chain index {} out of range", index, tsa_index))?
+                    .clone(),
+            ),
+            None => None,
+        };
+
+        let mut input = ProverInput::new(
+            entry.bundle_json.clone(),
+            entry.verification_options.clone(),
+            trust_bundle,
+            tsa_cert_chain,
+        );
+        input.reference_time = entry.reference_time;
+        Ok(input)
+    }
+
+    /// Encode the batch to bytes for host-to-guest communication
+    pub fn encode_input(&self) -> Result<Vec<u8>, String> {
+        encode_with_header(self, INPUT_FLAG_BATCH, "BatchProverInput")
+    }
+
+    /// Encode the batch with a zstd-compressed payload
+    #[cfg(feature = "compression")]
+    pub fn encode_input_compressed(&self) -> Result<Vec<u8>, String> {
+        encode_with_header(self, INPUT_FLAG_BATCH | INPUT_FLAG_ZSTD, "BatchProverInput")
+    }
+
+    /// Parse a BatchProverInput from bytes in the guest program
+    ///
+    /// A compressed batch is decompressed within the same bound as a single
+    /// input, `max_decompressed_size(&InputLimits::default())`.
+    pub fn parse_input(bytes: &[u8]) -> Result<Self, String> {
+        Self::parse_input_with_limits(bytes, &InputLimits::default())
+    }
+
+    /// Parse a BatchProverInput from bytes, decompressing within the bound of `limits`
+    pub fn parse_input_with_limits(bytes: &[u8], limits: &InputLimits) -> Result<Self, String> {
+        let (flags, payload) = decode_header(bytes)?;
+        expect_kind(flags, INPUT_FLAG_BATCH, "BatchProverInput")?;
+        decode_payload(flags, payload, "BatchProverInput", limits)
+    }
+}
+
+impl FromIterator<ProverInput> for BatchProverInput {
+    fn from_iter<I: IntoIterator<Item = ProverInput>>(iter: I) -> Self {
+        let mut batch = BatchProverInput::new();
+        for input in iter {
+            batch.push(input);
+        }
+        batch
+    }
+}
+
+/// Return the index of `chain` in `chains`, appending it if not already present
+fn intern_chain(chains: &mut Vec<CertificateChain>, chain: CertificateChain) -> u32 {
+    let index = match chains.iter().position(|existing| *existing == chain) {
+        Some(index) => index,
+        None => {
+            chains.push(chain);
+            chains.len() - 1
+        }
+    };
+    index as u32
+}
+
+/// Check whether encoded input bytes hold a batch (see `INPUT_FLAG_BATCH`)
+pub fn is_batch_input(bytes: &[u8]) -> bool {
+    bytes.len() >= INPUT_HEADER_LEN && bytes[1] & INPUT_FLAG_BATCH != 0
+}
+
+fn expect_kind(flags: u8, kind: u8, name: &str) -> Result<(), String> {
+    let actual = flags & INPUT_KIND_FLAGS;
+    if actual != kind {
+        return Err(format!(
+            "Input kind flags {:#04x} do not describe a {}",
+            actual, name
+        ));
+    }
+    Ok(())
+}
+
+fn encode_with_header<T: Serialize>(value: &T, flags: u8, name: &str) -> Result<Vec<u8>, String> {
+    let payload = bincode::serialize(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    let payload = if flags & INPUT_FLAG_ZSTD != 0 {
+        compress(&payload)?
+    } else {
+        payload
+    };
+
+    let mut bytes = Vec::with_capacity(INPUT_HEADER_LEN + payload.len());
+    bytes.push(INPUT_FORMAT_VERSION);
+    bytes.push(flags);
+    bytes.extend_from_slice(&payload);
+    Ok(bytes)
+}
+
+fn decode_header(bytes: &[u8]) -> Result<(u8, &[u8]), String> {
+    if bytes.len() < INPUT_HEADER_LEN {
+        return Err(format!(
+            "Input too short: expected at least {} bytes, got {}",
+            INPUT_HEADER_LEN,
+            bytes.len()
+        ));
+    }
+
+    let (version, flags) = (bytes[0], bytes[1]);
+    if version != INPUT_FORMAT_VERSION {
+        return Err(format!("Unsupported input format version: {}", version));
+    }
+    if flags & !(INPUT_FLAG_ZSTD | INPUT_KIND_FLAGS) != 0 {
+        return Err(format!("Unknown input flags: {:#04x}", flags));
+    }
+
+    Ok((flags, &bytes[INPUT_HEADER_LEN..]))
+}
+
+/// Largest decompressed payload accepted under `limits`
+///
+/// Room for the bundle and a reproduced bundle of `max_bundle_size` each, a
+/// Fulcio and a TSA chain of `max_chain_length` certificates, and the rest of
+/// the input. A compressed payload that inflates past it is rejected before it
+/// exhausts guest memory.
+pub fn max_decompressed_size(limits: &InputLimits) -> usize {
+    limits
+        .max_bundle_size
+        .saturating_mul(2)
+        .saturating_add(limits.max_chain_length.saturating_mul(2 * MAX_CERTIFICATE_SIZE))
+        .saturating_add(INPUT_OVERHEAD)
+}
+
+fn decode_payload<T: DeserializeOwned>(
+    flags: u8,
+    payload: &[u8],
+    name: &str,
+    limits: &InputLimits,
+) -> Result<T, String> {
+    if flags & INPUT_FLAG_ZSTD != 0 {
+        let decompressed = decompress(payload, max_decompressed_size(limits))?;
+        return bincode::deserialize(&decompressed)
+            .map_err(|e| format!("Failed to deserialize {}: {}", name, e));
+    }
+
+    bincode::deserialize(payload).map_err(|e| format!("Failed to deserialize {}: {}", name, e))
+}
+
+#[cfg(feature = "compression")]
+fn compress(payload: &[u8]) -> Result<Vec<u8>, String> {
+    Ok(ruzstd::encoding::compress_to_vec(
+        payload,
+        ruzstd::encoding::CompressionLevel::Fastest,
+    ))
+}
+
+#[cfg(not(feature = "compression"))]
+fn compress(_payload: &[u8]) -> Result<Vec<u8>, String> {
+    Err("zstd compression requires the `compression` feature".to_string())
+}
+
+#[cfg(feature = "compression")]
+fn decompress(payload: &[u8], max_size: usize) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let decoder = ruzstd::decoding::StreamingDecoder::new(payload)
+        .map_err(|e| format!("Failed to read zstd frame: {}", e))?;
+    // Read one byte past the bound to tell a payload of exactly `max_size` from a larger one
+    let mut decompressed = Vec::new();
+    decoder
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| format!("Failed to decompress input: {}", e))?;
+    if decompressed.len() > max_size {
+        return Err(format!("Decompressed input exceeds {} bytes", max_size));
+    }
+    Ok(decompressed)
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress(_payload: &[u8], _max_size: usize) -> Result<Vec<u8>, String> {
+    Err("Input is zstd-compressed but the `compression` feature is disabled".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(bundle_json: Vec<u8>) -> ProverInput {
+        let trust_bundle = CertificateChain {
+            leaf: Vec::new(),
+            intermediates: vec![vec![0x30; 600]],
+            root: vec![0x30; 500],
+        };
+        ProverInput::new(bundle_json, VerificationOptions::default(), trust_bundle, None).with_reference_time(1_700_000_000)
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn test_compressed_input_round_trip() {
+        let bundle_json = br#"{"mediaType":"application/vnd.dev.sigstore.bundle.v0.3+json"}"#.repeat(100);
+        let input = input(bundle_json.clone());
+        let plain = input.encode_input().unwrap();
+        let compressed = input.encode_input_compressed().unwrap();
+        assert_eq!(compressed[1], INPUT_FLAG_ZSTD);
+        assert!(compressed.len() < plain.len() / 4);
+
+        let parsed = ProverInput::parse_input(&compressed).unwrap();
+        assert_eq!(parsed.bundle_json, bundle_json);
+        assert_eq!(parsed.trust_bundle, input.trust_bundle);
+        assert_eq!(parsed.reference_time, Some(1_700_000_000));
+
+        let batch: BatchProverInput = [input.clone(), input].into_iter().collect();
+        let parsed = BatchProverInput::parse_input(&batch.encode_input_compressed().unwrap()).unwrap();
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.trust_bundles.len(), 1);
+        assert_eq!(parsed.entry_input(1).unwrap().bundle_json, bundle_json);
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn test_decompression_is_bounded_by_limits() {
+        let limits = InputLimits {
+            max_bundle_size: 1024,
+            max_chain_length: 1,
+            ..InputLimits::default()
+        };
+        let max_size = max_decompressed_size(&limits);
+
+        // A few kilobytes that inflate past the bound
+        let compressed = input(vec![0; max_size]).encode_input_compressed().unwrap();
+        assert!(compressed.len() < 64 * 1024);
+        assert_eq!(
+            ProverInput::parse_input_with_limits(&compressed, &limits).unwrap_err(),
+            format!("Decompressed input exceeds {} bytes", max_size)
+        );
+        assert!(ProverInput::parse_input(&compressed).is_ok());
+    }
+
+    #[test]
+    #[cfg(not(feature = "compression"))]
+    fn test_compressed_input_requires_feature() {
+        let mut encoded = input(Vec::new()).encode_input().unwrap();
+        encoded[1] |= INPUT_FLAG_ZSTD;
+        assert_eq!(
+            ProverInput::parse_input(&encoded).unwrap_err(),
+            "Input is zstd-compressed but the `compression` feature is disabled"
+        );
     }
 }
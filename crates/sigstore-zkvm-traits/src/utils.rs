@@ -7,9 +7,13 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use sigstore_verifier::report::{github_outputs, ReportFormat, VerificationReport};
 use sigstore_verifier::types::result::{DigestAlgorithm, TimestampProof, VerificationResult};
 use std::fs;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::operator::{OperatorKey, OperatorSignature};
 
 /// Proof artifact structure for serialization
 ///
@@ -19,6 +23,8 @@ use std::path::Path;
 /// - circuit_version: The version of the zkVM circuit used
 /// - journal: Hex-encoded public output/journal from the guest program
 /// - proof: Hex-encoded proof bytes (e.g., Groth16 proof, Merkle proof)
+/// - operator_signature: Optional signature of the prover operator over the
+///   artifact's metadata (see `crate::operator`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProofArtifact {
     pub zkvm: String,
@@ -26,6 +32,25 @@ pub struct ProofArtifact {
     pub circuit_version: String,
     pub journal: String,
     pub proof: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_signature: Option<OperatorSignature>,
+}
+
+/// Sign a proof artifact's metadata with the operator key, timestamped now
+///
+/// # Arguments
+///
+/// * `artifact` - The artifact to embed the signature in
+/// * `operator_key` - Operator key, see [`OperatorKey::from_secret`]
+/// * `bundle_json` - The bundle the proof is for
+pub fn sign_proof_artifact(artifact: &mut ProofArtifact, operator_key: &OperatorKey, bundle_json: &[u8]) -> Result<()> {
+    let signed_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("System clock is before the Unix epoch")?
+        .as_secs();
+    artifact
+        .sign_metadata(operator_key, bundle_json, signed_at)
+        .map_err(|e| anyhow::anyhow!("Failed to sign proof artifact: {}", e))
 }
 
 /// Write a proof artifact to a JSON file
@@ -51,6 +76,7 @@ pub struct ProofArtifact {
 ///     circuit_version: "1.0.0".to_string(),
 ///     journal: hex::encode(&journal_bytes),
 ///     proof: hex::encode(&proof_bytes),
+///     operator_signature: None,
 /// };
 /// write_proof_artifact(Path::new("output/proof.json"), &artifact)?;
 /// ```
@@ -73,6 +99,74 @@ pub fn write_proof_artifact(output_path: &Path, artifact: &ProofArtifact) -> Res
     Ok(())
 }
 
+/// Render verification reports and print them or write them to a file
+///
+/// # Arguments
+///
+/// * `reports` - Reports to render
+/// * `format` - Output format (text, json, sarif or vsa)
+/// * `output_path` - File to write; prints to stdout if `None`
+///
+/// # Example
+///
+/// ```ignore
+/// let report = verify_local(bundle_path, trusted_root_path, options)?;
+/// write_report(&[report], ReportFormat::Sarif, Some(Path::new("results.sarif")))?;
+/// ```
+pub fn write_report(
+    reports: &[VerificationReport],
+    format: ReportFormat,
+    output_path: Option<&Path>,
+) -> Result<()> {
+    let rendered = format
+        .render(reports)
+        .context("Failed to render verification report")?;
+
+    match output_path {
+        Some(output_path) => {
+            if let Some(parent) = output_path.parent() {
+                fs::create_dir_all(parent)
+                    .context(format!("Failed to create directory: {}", parent.display()))?;
+            }
+            fs::write(output_path, rendered)
+                .context(format!("Failed to write report to: {}", output_path.display()))?;
+            println!("✓ {} report written to: {}", format, output_path.display());
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+/// Append verification outputs to the file named by `$GITHUB_OUTPUT`
+///
+/// Writes the `key=value` pairs from [`github_outputs`] so later steps of a
+/// GitHub Actions job can read them as `steps.<id>.outputs.<key>`.
+///
+/// # Errors
+///
+/// Returns an error if `GITHUB_OUTPUT` is not set or the file cannot be written.
+pub fn write_github_output(reports: &[VerificationReport]) -> Result<()> {
+    let output_path = std::env::var_os("GITHUB_OUTPUT")
+        .context("GITHUB_OUTPUT is not set; --github-output only works inside GitHub Actions")?;
+
+    let mut lines = String::new();
+    for (key, value) in github_outputs(reports) {
+        // Values are single-line (digests, URIs, timestamps); drop stray newlines
+        // rather than let them inject extra outputs
+        let value = value.replace(['\r', '\n'], " ");
+        lines.push_str(&format!("{}={}\n", key, value));
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&output_path)
+        .context("Failed to open $GITHUB_OUTPUT")?;
+    std::io::Write::write_all(&mut file, lines.as_bytes())
+        .context("Failed to write $GITHUB_OUTPUT")?;
+    Ok(())
+}
+
 /// Display verification result in a readable format
 ///
 /// Prints the verification result with formatted output including:
@@ -89,16 +183,12 @@ pub fn write_proof_artifact(output_path: &Path, artifact: &ProofArtifact) -> Res
 /// # Example
 ///
 /// ```ignore
-/// let result = VerificationResult::from_slice(&journal)?;
+/// let result = VerifiedOutput::from_slice(&journal)?.verification_result()?;
 /// display_verification_result(&result);
 /// ```
 pub fn display_verification_result(result: &VerificationResult) {
     println!("\n=== Verification Result ===");
-    println!(
-        "Subject digest: {} ({})",
-        hex::encode(&result.subject_digest),
-        format_digest_algorithm(&result.subject_digest_algorithm)
-    );
+    println!("Subject digest: {}", result.subject_digest);
     println!("Signing time:   {}", result.signing_time);
 
     println!("\nCertificate Hashes:");
@@ -172,6 +262,8 @@ fn format_digest_algorithm(alg: &DigestAlgorithm) -> &'static str {
         DigestAlgorithm::Unknown => "Unknown",
         DigestAlgorithm::Sha256 => "SHA-256",
         DigestAlgorithm::Sha384 => "SHA-384",
+        DigestAlgorithm::GitCommit => "Git commit",
+        DigestAlgorithm::Sha512 => "SHA-512",
     }
 }
 
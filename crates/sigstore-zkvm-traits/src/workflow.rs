@@ -3,14 +3,21 @@
 //! This module provides utilities to prepare input data for zkVM guest programs
 //! that verify Sigstore attestation bundles.
 
-use crate::types::ProverInput;
+use crate::tee::TeeEvidence;
+use crate::types::{BatchProverInput, ProverInput};
 use anyhow::{Context, Result};
 use sigstore_verifier::fetcher::jsonl::parser::{
-    load_trusted_root_from_jsonl, select_certificate_authority, select_timestamp_authority,
+    load_trusted_root_from_jsonl, select_certificate_authority_for, select_timestamp_authority_for,
+    select_transparency_log_keys, select_transparency_logs, verify_bundle_transparency_logs,
 };
-use sigstore_verifier::parser::bundle::{extract_bundle_timestamp, parse_bundle_from_path};
-use sigstore_verifier::types::certificate::FulcioInstance;
+use sigstore_verifier::fetcher::jsonl::types::TrustedRoot;
+use sigstore_verifier::parser::bundle::{extract_bundle_timestamp, parse_bundle_from_bytes};
+use sigstore_verifier::parser::certificate::parse_pem_certificate;
+use sigstore_verifier::parser::gh_attestation::parse_gh_attestations;
+use sigstore_verifier::report::VerificationReport;
+use sigstore_verifier::types::environment::SigstoreEnvironment;
 use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_verifier::AttestationVerifier;
 use std::fs;
 use std::path::Path;
 
@@ -30,7 +37,8 @@ use std::path::Path;
 ///
 /// Returns a `ProverInput` containing:
 /// - The attestation bundle JSON
-/// - Verification options
+/// - Verification options, whose `allowed_log_ids` and `transparency_log_keys`
+///   default to the trusted root's Rekor logs valid at the signing time
 /// - Fulcio certificate chain
 /// - TSA certificate chain (if available)
 ///
@@ -41,6 +49,7 @@ use std::path::Path;
 /// - The trusted root file cannot be read or parsed
 /// - The Fulcio instance cannot be auto-detected from the bundle
 /// - The appropriate certificate chains cannot be selected based on the bundle timestamp
+/// - A transparency log entry was logged by a log missing from the trusted root
 ///
 /// # Example
 ///
@@ -51,11 +60,7 @@ use std::path::Path;
 ///
 /// let bundle_path = Path::new("samples/attestation.sigstore.json");
 /// let trusted_root_path = Path::new("samples/trusted_root.jsonl");
-/// let options = VerificationOptions {
-///     expected_digest: None,
-///     expected_issuer: None,
-///     expected_subject: None,
-/// };
+/// let options = VerificationOptions::default();
 ///
 /// let prover_input = prepare_guest_input_local(
 ///     bundle_path,
@@ -67,38 +72,114 @@ pub fn prepare_guest_input_local(
     bundle_path: &Path,
     trusted_root_path: &Path,
     options: VerificationOptions,
+) -> Result<ProverInput> {
+    prepare_guest_input_for_environment(
+        bundle_path,
+        trusted_root_path,
+        &SigstoreEnvironment::Production,
+        options,
+    )
+}
+
+/// Prepare zkVM guest input from local files for a given Sigstore deployment
+///
+/// Like [`prepare_guest_input_local`], but the Fulcio and TSA chains are
+/// selected among the signing authorities of `environment` (e.g. staging)
+/// instead of the production ones.
+///
+/// # Arguments
+///
+/// * `bundle_path` - Path to the Sigstore attestation bundle JSON file
+/// * `trusted_root_path` - Path to the trusted root JSONL file of `environment`
+/// * `environment` - Sigstore deployment the bundle was signed against
+/// * `options` - Verification options (expected digest, issuer, subject, etc.)
+///
+/// # Errors
+///
+/// As [`prepare_guest_input_local`]; the bundle's certificate must be issued
+/// by a signing authority of `environment`.
+///
+/// # Example
+///
+/// ```ignore
+/// let prover_input = prepare_guest_input_for_environment(
+///     Path::new("staging.sigstore.json"),
+///     Path::new("staging_trusted_root.jsonl"),
+///     &SigstoreEnvironment::Staging,
+///     options,
+/// )?;
+/// ```
+pub fn prepare_guest_input_for_environment(
+    bundle_path: &Path,
+    trusted_root_path: &Path,
+    environment: &SigstoreEnvironment,
+    options: VerificationOptions,
 ) -> Result<ProverInput> {
     // Read the attestation bundle
     let bundle_json = fs::read(bundle_path)
         .context(format!("Failed to read bundle from: {}", bundle_path.display()))?;
 
-    // Auto-detect Fulcio instance from bundle
-    let bundle_json_str = String::from_utf8(bundle_json.clone())
-        .context("Failed to parse bundle as UTF-8")?;
-    let fulcio_instance = FulcioInstance::from_bundle_json(&bundle_json_str)
-        .map_err(|e| anyhow::anyhow!("Failed to detect Fulcio instance from bundle: {}", e))?;
-
     // Load trusted roots for Fulcio and TSA
+    let trust_roots = load_trusted_roots(trusted_root_path)?;
+
+    prepare_guest_input_from_json(bundle_json, &trust_roots, environment, options)
+}
+
+fn load_trusted_roots(trusted_root_path: &Path) -> Result<Vec<TrustedRoot>> {
     let trusted_root_content = fs::read_to_string(trusted_root_path)
         .context(format!("Failed to read trusted root from: {}", trusted_root_path.display()))?;
-    let trust_roots = load_trusted_root_from_jsonl(&trusted_root_content)
-        .context("Failed to parse trusted root JSONL")?;
+    load_trusted_root_from_jsonl(&trusted_root_content).context("Failed to parse trusted root JSONL")
+}
+
+/// Prepare zkVM guest input from bundle JSON and already loaded trusted roots
+fn prepare_guest_input_from_json(
+    bundle_json: Vec<u8>,
+    trust_roots: &[TrustedRoot],
+    environment: &SigstoreEnvironment,
+    options: VerificationOptions,
+) -> Result<ProverInput> {
+    // Auto-detect the signing authority (Fulcio instance) from bundle
+    let bundle_json_str = String::from_utf8(bundle_json.clone())
+        .context("Failed to parse bundle as UTF-8")?;
+    let authority = environment
+        .authority_for_bundle_json(&bundle_json_str)
+        .map_err(|e| anyhow::anyhow!("Failed to detect Fulcio instance from bundle: {}", e))?;
 
     // Parse the Sigstore bundle
-    let bundle = parse_bundle_from_path(bundle_path)
+    let bundle = parse_bundle_from_bytes(&bundle_json)
         .context("Failed to parse Sigstore bundle")?;
 
     // Extract timestamp from the bundle
     let timestamp = extract_bundle_timestamp(&bundle)
         .context("Failed to extract timestamp from bundle")?;
 
-    // Select the appropriate certificate chains based on Fulcio instance and timestamp
-    let fulcio_chain = select_certificate_authority(&trust_roots, &fulcio_instance, timestamp)
+    // Select the appropriate certificate chains based on signing authority and timestamp
+    let fulcio_chain = select_certificate_authority_for(trust_roots, &authority, timestamp)
         .context("Failed to select Fulcio certificate authority")?;
 
-    let tsa_chain = select_timestamp_authority(&trust_roots, &fulcio_instance, timestamp)
+    let tsa_chain = select_timestamp_authority_for(trust_roots, &authority, timestamp)
         .context("Failed to select TSA certificate authority")?;
 
+    // Reject entries from transparency logs the trusted root does not know
+    verify_bundle_transparency_logs(trust_roots, &bundle, timestamp)
+        .context("Bundle was logged by an untrusted transparency log")?;
+
+    // Have the guest enforce the trusted root's logs too, so they are
+    // committed through the options hash rather than only checked here
+    let mut options = options;
+    if options.allowed_log_ids.is_none() {
+        options.allowed_log_ids = Some(
+            select_transparency_logs(trust_roots, timestamp)
+                .context("Failed to select transparency logs")?,
+        );
+    }
+    if options.transparency_log_keys.is_none() {
+        options.transparency_log_keys = Some(
+            select_transparency_log_keys(trust_roots, timestamp)
+                .context("Failed to select transparency log keys")?,
+        );
+    }
+
     // Create the ProverInput with properly selected certificate chains
     Ok(ProverInput::new(
         bundle_json,
@@ -107,3 +188,190 @@ pub fn prepare_guest_input_local(
         Some(tsa_chain),
     ))
 }
+
+/// Load the TEE evidence of a build runner from local files
+///
+/// # Arguments
+///
+/// * `quote_path` - Binary DCAP quote of the runner
+/// * `pck_root_path` - PEM Intel SGX root CA certificate the quote must chain to
+///
+/// # Example
+///
+/// ```ignore
+/// let input = prepare_guest_input_local(bundle_path, trusted_root_path, options)?
+///     .with_tee_evidence(load_tee_evidence(Path::new("runner.quote"), Path::new("intel_root.pem"))?);
+/// ```
+pub fn load_tee_evidence(quote_path: &Path, pck_root_path: &Path) -> Result<TeeEvidence> {
+    let quote = fs::read(quote_path).context(format!("Failed to read TEE quote from: {}", quote_path.display()))?;
+    let pck_root_pem = fs::read_to_string(pck_root_path)
+        .context(format!("Failed to read PCK root CA from: {}", pck_root_path.display()))?;
+    let pck_root = parse_pem_certificate(&pck_root_pem).context("Failed to parse PCK root CA")?;
+    Ok(TeeEvidence { quote, pck_root })
+}
+
+/// Prepare a batch guest input from several local bundles
+///
+/// Each bundle is prepared with [`prepare_guest_input_local`]; certificate chains
+/// shared between bundles are stored once in the resulting `BatchProverInput`.
+///
+/// # Arguments
+///
+/// * `bundle_paths` - Paths to the Sigstore attestation bundle JSON files, in output order
+/// * `trusted_root_path` - Path to the trusted root JSONL file
+/// * `options` - Verification options applied to every bundle
+pub fn prepare_batch_input_local(
+    bundle_paths: &[&Path],
+    trusted_root_path: &Path,
+    options: VerificationOptions,
+) -> Result<BatchProverInput> {
+    let mut batch = BatchProverInput::new();
+    for bundle_path in bundle_paths {
+        batch.push(prepare_guest_input_local(bundle_path, trusted_root_path, options.clone())?);
+    }
+    Ok(batch)
+}
+
+/// Verify a local bundle natively, without a zkVM, and report the outcome
+///
+/// Trust material is selected as in [`prepare_guest_input_local`], then the
+/// bundle is verified exactly as the guest would.
+///
+/// # Returns
+///
+/// A `VerificationReport` for the bundle. A bundle that fails verification
+/// still yields a report; its `error` holds the failure.
+///
+/// # Errors
+///
+/// Returns an error only if the inputs cannot be prepared (unreadable files,
+/// no matching trust root).
+pub fn verify_local(
+    bundle_path: &Path,
+    trusted_root_path: &Path,
+    options: VerificationOptions,
+) -> Result<VerificationReport> {
+    verify_local_for_environment(
+        bundle_path,
+        trusted_root_path,
+        &SigstoreEnvironment::Production,
+        options,
+    )
+}
+
+/// Verify a local bundle natively for a given Sigstore deployment
+///
+/// Like [`verify_local`], with trust material selected as in
+/// [`prepare_guest_input_for_environment`].
+pub fn verify_local_for_environment(
+    bundle_path: &Path,
+    trusted_root_path: &Path,
+    environment: &SigstoreEnvironment,
+    options: VerificationOptions,
+) -> Result<VerificationReport> {
+    let input =
+        prepare_guest_input_for_environment(bundle_path, trusted_root_path, environment, options)?;
+    let result = AttestationVerifier::new().verify_bundle_bytes(
+        &input.bundle_json,
+        input.verification_options.clone(),
+        &input.trust_bundle,
+        input.tsa_cert_chain.as_ref(),
+    );
+
+    Ok(VerificationReport::new(
+        bundle_path.display().to_string(),
+        &input.bundle_json,
+        input.verification_options,
+        result,
+    ))
+}
+
+/// Verify every attestation of a `gh attestation download` JSONL file
+///
+/// Each line holds a bundle, bare or wrapped in `{"bundle": ...}` metadata
+/// (see `sigstore_verifier::parser::gh_attestation`), and is verified like
+/// [`verify_local_for_environment`]. Reports are named `<path>:<line>`.
+///
+/// # Errors
+///
+/// Returns an error if the file or a line cannot be parsed, or if trust
+/// material cannot be selected for a bundle.
+pub fn verify_local_jsonl_for_environment(
+    jsonl_path: &Path,
+    trusted_root_path: &Path,
+    environment: &SigstoreEnvironment,
+    options: VerificationOptions,
+) -> Result<Vec<VerificationReport>> {
+    let jsonl = fs::read_to_string(jsonl_path)
+        .context(format!("Failed to read attestations from: {}", jsonl_path.display()))?;
+    let attestations = parse_gh_attestations(&jsonl).context("Failed to parse attestations JSONL")?;
+    let trust_roots = load_trusted_roots(trusted_root_path)?;
+
+    let mut reports = Vec::with_capacity(attestations.len());
+    for attestation in attestations {
+        let name = format!("{}:{}", jsonl_path.display(), attestation.line);
+        let input = prepare_guest_input_from_json(attestation.bundle_json, &trust_roots, environment, options.clone())
+            .context(format!("Failed to prepare {}", name))?;
+        let result = AttestationVerifier::new().verify_bundle_bytes(
+            &input.bundle_json,
+            input.verification_options.clone(),
+            &input.trust_bundle,
+            input.tsa_cert_chain.as_ref(),
+        );
+        reports.push(VerificationReport::new(name, &input.bundle_json, input.verification_options, result));
+    }
+    Ok(reports)
+}
+
+/// Verify a bundle file, or every line of a `.jsonl` attestations file
+///
+/// Dispatches on the extension to [`verify_local_jsonl_for_environment`] or
+/// [`verify_local_for_environment`].
+pub fn verify_local_file_for_environment(
+    path: &Path,
+    trusted_root_path: &Path,
+    environment: &SigstoreEnvironment,
+    options: VerificationOptions,
+) -> Result<Vec<VerificationReport>> {
+    if path.extension().is_some_and(|extension| extension == "jsonl") {
+        verify_local_jsonl_for_environment(path, trusted_root_path, environment, options)
+    } else {
+        Ok(vec![verify_local_for_environment(path, trusted_root_path, environment, options)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn samples() -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.pop();
+        path.pop();
+        path.push("samples");
+        path
+    }
+
+    #[test]
+    fn test_prepare_guest_input_commits_trusted_logs() {
+        let bundle = samples().join("actions-attest-build-provenance-attestation-13532655.sigstore.json");
+        let trusted_root = samples().join("trusted_root.jsonl");
+
+        let input = prepare_guest_input_local(&bundle, &trusted_root, VerificationOptions::default()).unwrap();
+        let options = &input.verification_options;
+        let log_ids = options.allowed_log_ids.as_ref().unwrap();
+        let keys = options.transparency_log_keys.as_ref().unwrap();
+        assert_eq!(keys.len(), log_ids.len());
+        assert!(keys.iter().all(|key| log_ids.contains(&key.log_id().unwrap())));
+
+        // The guest checks the entry's SET against the committed log key
+        let report = verify_local(&bundle, &trusted_root, VerificationOptions::default()).unwrap();
+        assert!(report.error.is_none(), "{:?}", report.error);
+
+        // Logs chosen by the caller are kept
+        let pinned = VerificationOptions::default().with_allowed_log_ids(vec![]);
+        let input = prepare_guest_input_local(&bundle, &trusted_root, pinned).unwrap();
+        assert_eq!(input.verification_options.allowed_log_ids, Some(vec![]));
+    }
+}
@@ -2,7 +2,11 @@
 //!
 //! Defines all CLI commands, subcommands, and arguments using clap.
 
+use alloy_primitives::Address;
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use sigstore_zkvm_traits::secret::SecretKey;
+use sigstore_verifier::report::ReportFormat;
+use sigstore_verifier::types::environment::SigstoreEnvironment;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -22,10 +26,23 @@ pub struct Cli {
 pub enum Commands {
     /// Display the SP1 program verifying key hash
     #[command(name = "verifying-key")]
-    VerifyingKey,
+    VerifyingKey(VerifyingKeyArgs),
 
     /// Generate a proof of attestation verification
     Prove(ProveArgs),
+
+    /// Generate proofs for every bundle in a directory concurrently
+    #[command(name = "prove-batch")]
+    ProveBatch(ProveBatchArgs),
+
+    /// Execute the guest program without proving and report its cycle count
+    Execute(ExecuteArgs),
+
+    /// Verify bundles natively (no proof) and write a report for CI
+    Verify(VerifyArgs),
+
+    /// Set up the proving key and store it in the key cache (e.g. when building CI images)
+    Setup(SetupArgs),
 }
 
 #[derive(Args, Debug)]
@@ -38,10 +55,87 @@ pub struct ProveArgs {
     #[arg(long = "trust-roots", value_name = "PATH", required = true)]
     pub trust_roots_path: PathBuf,
 
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Unix time to measure the attestation's signing age against; the guest
+    /// commits it and the age so contracts can enforce a maximum age
+    #[arg(long = "reference-time", value_name = "UNIX_SECONDS")]
+    pub reference_time: Option<u64>,
+
+    /// DCAP quote of the TEE runner that built the artifact; the guest verifies it
+    /// and commits a combined TEE + SLSA claim (requires --tee-root)
+    #[arg(long = "tee-quote", value_name = "PATH", requires = "tee_root_path")]
+    pub tee_quote_path: Option<PathBuf>,
+
+    /// Intel SGX root CA certificate (PEM) the quote's PCK chain must end in
+    #[arg(long = "tee-root", value_name = "PATH", requires = "tee_quote_path")]
+    pub tee_root_path: Option<PathBuf>,
+
+    /// Bundle of an independent builder for the same artifact; the guest verifies
+    /// both and commits a reproduced-build claim
+    #[arg(long = "reproduced-bundle", value_name = "PATH", conflicts_with = "tee_quote_path")]
+    pub reproduced_bundle_path: Option<PathBuf>,
+
     /// Path to write the proof artifact JSON file
     #[arg(long = "output", value_name = "PATH")]
     pub output_path: Option<PathBuf>,
 
+    /// Workspace directory keeping per-bundle proving state; re-runs reuse a
+    /// valid proof and resume a submitted network request
+    #[arg(long = "workspace", value_name = "DIR")]
+    pub workspace_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub proving: ProvingArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct ProveBatchArgs {
+    /// Directory of Sigstore attestation bundle JSON files (*.json)
+    #[arg(long = "bundles", value_name = "DIR", required = true)]
+    pub bundles_dir: PathBuf,
+
+    /// Path to the trusted root JSONL file
+    #[arg(long = "trust-roots", value_name = "PATH", required = true)]
+    pub trust_roots_path: PathBuf,
+
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Unix time to measure the attestations' signing age against
+    #[arg(long = "reference-time", value_name = "UNIX_SECONDS")]
+    pub reference_time: Option<u64>,
+
+    /// Directory to write one proof artifact per bundle and manifest.json
+    #[arg(long = "output-dir", value_name = "DIR", required = true)]
+    pub output_dir: PathBuf,
+
+    /// Workspace directory keeping per-bundle proving state (default: <output-dir>/workspace)
+    #[arg(long = "workspace", value_name = "DIR")]
+    pub workspace_dir: Option<PathBuf>,
+
+    /// Number of bundles proven at the same time
+    #[arg(long = "concurrency", value_name = "N", default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    pub concurrency: u16,
+
+    #[command(flatten)]
+    pub proving: ProvingArgs,
+}
+
+/// Network proving options shared by `prove` and `prove-batch`
+#[derive(Args, Debug)]
+pub struct ProvingArgs {
     /// SP1 network private key (hex-encoded)
     #[arg(
         long = "network-private-key",
@@ -49,7 +143,7 @@ pub struct ProveArgs {
         value_name = "WALLET_KEY",
         hide_env_values = true
     )]
-    pub private_key: String,
+    pub private_key: SecretKey,
 
     /// Proving mode
     #[arg(
@@ -59,6 +153,154 @@ pub struct ProveArgs {
         value_name = "MODE"
     )]
     pub mode: ProvingMode,
+
+    /// Where proofs are generated; `dev` (or DEV_MODE / SP1_DEV_MODE set) executes the guest without proving
+    #[arg(long = "prover", value_enum, default_value = "network", value_name = "PROVER")]
+    pub prover_type: ProverType,
+
+    /// SP1 network RPC endpoint (default: the SDK's endpoint for mainnet)
+    #[arg(long = "rpc-url", env = "SP1_NETWORK_RPC_URL", value_name = "URL")]
+    pub rpc_url: Option<String>,
+
+    /// Compress the guest input with zstd before upload (smaller upload, more guest cycles)
+    #[arg(long = "compress-input")]
+    pub compress_input: bool,
+
+    /// Highest auction bid per prover gas unit, in PROVE base units
+    #[arg(long = "max-price-per-pgu", value_name = "PRICE")]
+    pub max_price_per_pgu: Option<u64>,
+
+    /// Fail before submitting if the estimated cost exceeds this cap, in PROVE base units
+    #[arg(long = "max-cost", value_name = "AMOUNT", requires = "max_price_per_pgu")]
+    pub max_cost: Option<u128>,
+
+    /// Prover address allowed to bid on the request (repeat for several provers)
+    #[arg(long = "whitelist", value_name = "ADDRESS")]
+    pub whitelist: Vec<Address>,
+
+    /// Directory of cached proving keys (default: $SP1_PK_CACHE_DIR or ~/.cache/sigstore-verifier/sp1)
+    #[arg(long = "pk-cache-dir", value_name = "DIR")]
+    pub pk_cache_dir: Option<PathBuf>,
+
+    /// Run setup without reading or writing the proving key cache
+    #[arg(long = "no-pk-cache", conflicts_with = "pk_cache_dir")]
+    pub no_pk_cache: bool,
+
+    /// URL to POST proof metadata (program id, bundle digest, status) to when a proof completes or fails
+    #[arg(long = "notify-url", value_name = "URL")]
+    pub notify_url: Option<String>,
+
+    /// Operator key (hex P-256 private key) to sign the artifact metadata with, so relayers can authenticate this prover
+    #[arg(long = "operator-key", env = "PROVER_OPERATOR_KEY", value_name = "KEY", hide_env_values = true)]
+    pub operator_key: Option<SecretKey>,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyingKeyArgs {
+    /// Print the verifying key record (vk hash, circuit version, ELF hash) as JSON
+    #[arg(long = "json")]
+    pub json: bool,
+
+    /// Path to write the verifying key record JSON file, e.g. for the program registry
+    #[arg(long = "output", value_name = "PATH")]
+    pub output_path: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct SetupArgs {
+    /// Directory of cached proving keys (default: $SP1_PK_CACHE_DIR or ~/.cache/sigstore-verifier/sp1)
+    #[arg(long = "pk-cache-dir", value_name = "DIR")]
+    pub pk_cache_dir: Option<PathBuf>,
+
+    /// Run setup again even if a valid key is already cached
+    #[arg(long = "force")]
+    pub force: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Path to a Sigstore attestation bundle JSON file, or a `gh attestation download`
+    /// .jsonl file with one bundle per line (repeat for several files)
+    #[arg(long = "bundle", value_name = "PATH", required = true)]
+    pub bundle_paths: Vec<PathBuf>,
+
+    /// Path to the trusted root JSONL file
+    #[arg(long = "trust-roots", value_name = "PATH", required = true)]
+    pub trust_roots_path: PathBuf,
+
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Report format: text, json, sarif (GitHub code scanning) or vsa (in-toto VSA)
+    #[arg(long = "report-format", value_name = "FORMAT", default_value = "text")]
+    pub report_format: ReportFormat,
+
+    /// Path to write the report (printed to stdout if omitted)
+    #[arg(long = "report-output", value_name = "PATH")]
+    pub report_output: Option<PathBuf>,
+
+    /// Append verified, failure_reason, subject_digest, repository, workflow_ref
+    /// and signing_time to $GITHUB_OUTPUT
+    #[arg(long = "github-output")]
+    pub github_output: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ExecuteArgs {
+    /// Path to the Sigstore attestation bundle JSON file
+    #[arg(long = "bundle", value_name = "PATH", required = true)]
+    pub bundle_path: PathBuf,
+
+    /// Path to the trusted root JSONL file
+    #[arg(long = "trust-roots", value_name = "PATH", required = true)]
+    pub trust_roots_path: PathBuf,
+
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Unix time to measure the attestation's signing age against; the guest
+    /// commits it and the age so contracts can enforce a maximum age
+    #[arg(long = "reference-time", value_name = "UNIX_SECONDS")]
+    pub reference_time: Option<u64>,
+
+    /// Compress the guest input with zstd, to measure the decompression cost
+    #[arg(long = "compress-input")]
+    pub compress_input: bool,
+
+    /// Print a per-step cycle breakdown and write it as a JSON report
+    #[arg(long = "profile")]
+    pub profile: bool,
+
+    /// Path to write the JSON profile report
+    #[arg(
+        long = "profile-output",
+        value_name = "PATH",
+        default_value = "sp1-profile.json",
+        requires = "profile"
+    )]
+    pub profile_output: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProverType {
+    /// Prove on the SP1 network
+    #[default]
+    #[value(name = "network")]
+    Network,
+
+    /// Execute the guest locally and return an empty proof
+    #[value(name = "dev")]
+    Dev,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -2,13 +2,85 @@
 //!
 //! Defines configuration structures for different proving strategies and modes.
 
-use crate::cli::{ProveArgs, ProvingMode};
+use crate::cli::{ProverType, ProvingArgs, ProvingMode};
+use crate::workspace::BundleDir;
+use alloy_primitives::Address;
+use sigstore_zkvm_traits::secret::SecretKey;
+use sigstore_zkvm_traits::types::InputEncoding;
+pub use sp1_sdk::network::NetworkMode;
 
 /// SP1 prover configuration
+///
+/// The prover is built from the config alone and never reads or sets process
+/// environment variables, so provers with different keys, endpoints or
+/// networks can run side by side in one process.
 #[derive(Debug, Clone)]
 pub struct Sp1Config {
     pub proving_mode: ProvingMode,
-    pub private_key: String
+    pub prover_type: ProverType,
+    pub private_key: SecretKey,
+
+    /// SP1 network RPC endpoint; `None` uses the SDK's endpoint for `network_mode`
+    pub rpc_url: Option<String>,
+    pub network_mode: NetworkMode,
+    pub input_encoding: InputEncoding,
+    pub auction: AuctionConfig,
+
+    /// Workspace directory of the bundle being proven; the network request ID
+    /// is recorded there, and a recorded request is resumed instead of resubmitted
+    pub workspace: Option<BundleDir>,
+}
+
+/// SP1 network auction parameters
+///
+/// Unset fields fall back to the network defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuctionConfig {
+    /// Highest price bid per prover gas unit (PGU), in PROVE base units
+    pub max_price_per_pgu: Option<u64>,
+
+    /// Upper bound on the total cost, in PROVE base units
+    ///
+    /// Before submitting, the guest is executed to measure its gas and the
+    /// request fails if `gas * max_price_per_pgu` exceeds this cap. The request's
+    /// gas limit is then set to the measured gas, so the auction cannot settle
+    /// above the cap.
+    pub max_cost: Option<u128>,
+
+    /// Provers allowed to bid on the request (empty allows any prover)
+    pub whitelist: Vec<Address>,
+}
+
+impl AuctionConfig {
+    /// Check the worst-case cost of a request using `gas` prover gas units
+    ///
+    /// # Returns
+    ///
+    /// The worst-case cost, or `None` if no price cap is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_cost` is set without `max_price_per_pgu`, or
+    /// the worst-case cost exceeds `max_cost`.
+    pub fn check_cost(&self, gas: u64) -> Result<Option<u128>, String> {
+        let Some(price) = self.max_price_per_pgu else {
+            if self.max_cost.is_some() {
+                return Err("A cost cap requires a maximum price per PGU".to_string());
+            }
+            return Ok(None);
+        };
+
+        let cost = gas as u128 * price as u128;
+        if let Some(max_cost) = self.max_cost {
+            if cost > max_cost {
+                return Err(format!(
+                    "Estimated cost {} ({} PGU at {} per PGU) exceeds the cap of {}",
+                    cost, gas, price, max_cost
+                ));
+            }
+        }
+        Ok(Some(cost))
+    }
 }
 
 impl Sp1Config {
@@ -16,15 +88,29 @@ impl Sp1Config {
     ///
     /// # Arguments
     ///
-    /// * `args` - The prove command arguments
+    /// * `args` - The network proving arguments of `prove` or `prove-batch`
     ///
     /// # Returns
     ///
-    /// Returns a Sp1Config with the appropriate strategy and parameters.
-    pub fn from_cli_args(args: &ProveArgs) -> Self {
+    /// Returns a Sp1Config with the appropriate strategy and parameters. The
+    /// legacy `DEV_MODE` / `SP1_DEV_MODE` variables select the dev prover.
+    pub fn from_cli_args(args: &ProvingArgs) -> Self {
+        let dev_mode = std::env::var_os("DEV_MODE").is_some() || std::env::var_os("SP1_DEV_MODE").is_some();
         Sp1Config {
             proving_mode: args.mode,
+            prover_type: if dev_mode { ProverType::Dev } else { args.prover_type },
             private_key: args.private_key.clone(),
+            rpc_url: args.rpc_url.clone(),
+            network_mode: NetworkMode::Mainnet,
+            input_encoding: InputEncoding {
+                compress: args.compress_input,
+            },
+            auction: AuctionConfig {
+                max_price_per_pgu: args.max_price_per_pgu,
+                max_cost: args.max_cost,
+                whitelist: args.whitelist.clone(),
+            },
+            workspace: None,
         }
     }
 }
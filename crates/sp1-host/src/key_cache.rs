@@ -0,0 +1,119 @@
+//! On-disk cache for the SP1 proving key
+//!
+//! `setup` derives the proving key from the guest ELF, which takes minutes.
+//! The key only depends on the ELF and the SP1 circuit version, so it is
+//! stored under `<dir>/<circuit version>/<sha256(elf)>.pk` and reused by later
+//! runs. Each file is `sha256(payload)` || `payload` (the bincode-encoded key);
+//! a file whose checksum does not match is discarded and the key is set up again.
+
+use anyhow::{bail, Context, Result};
+use sigstore_verifier::crypto::hash::sha256;
+use sp1_sdk::SP1ProvingKey;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the default cache directory
+pub const CACHE_DIR_ENV: &str = "SP1_PK_CACHE_DIR";
+
+/// Directory of cached proving keys
+#[derive(Debug, Clone)]
+pub struct KeyCache {
+    dir: PathBuf,
+}
+
+impl KeyCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        KeyCache { dir: dir.into() }
+    }
+
+    /// `$SP1_PK_CACHE_DIR`, else `$XDG_CACHE_HOME/sigstore-verifier/sp1`, else
+    /// `$HOME/.cache/sigstore-verifier/sp1`
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = std::env::var_os(CACHE_DIR_ENV) {
+            return PathBuf::from(dir);
+        }
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .unwrap_or_else(std::env::temp_dir);
+        base.join("sigstore-verifier").join("sp1")
+    }
+
+    /// Path of the cached key for `elf`
+    pub fn path(&self, elf: &[u8]) -> PathBuf {
+        self.dir
+            .join(sp1_sdk::SP1_CIRCUIT_VERSION)
+            .join(format!("{}.pk", hex::encode(sha256(elf))))
+    }
+
+    /// Load the cached key for `elf`
+    ///
+    /// # Returns
+    ///
+    /// `None` if no key is cached for this ELF and circuit version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, fails its checksum or
+    /// cannot be decoded.
+    pub fn load(&self, elf: &[u8]) -> Result<Option<SP1ProvingKey>> {
+        let path = self.path(elf);
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).context(format!("Failed to read proving key: {}", path.display()))
+            }
+        };
+
+        if bytes.len() < 32 {
+            bail!("Cached proving key is truncated: {}", path.display());
+        }
+        let (checksum, payload) = bytes.split_at(32);
+        if sha256(payload) != checksum {
+            bail!("Cached proving key failed its checksum: {}", path.display());
+        }
+
+        let pk = bincode::deserialize(payload)
+            .context(format!("Failed to decode proving key: {}", path.display()))?;
+        Ok(Some(pk))
+    }
+
+    /// Store the key for `elf`, replacing any cached key atomically
+    pub fn store(&self, elf: &[u8], pk: &SP1ProvingKey) -> Result<PathBuf> {
+        let path = self.path(elf);
+        let parent = path.parent().expect("cache path has a parent");
+        std::fs::create_dir_all(parent)
+            .context(format!("Failed to create cache directory: {}", parent.display()))?;
+
+        let payload = bincode::serialize(pk).context("Failed to encode proving key")?;
+        let mut bytes = Vec::with_capacity(32 + payload.len());
+        bytes.extend_from_slice(&sha256(&payload));
+        bytes.extend_from_slice(&payload);
+
+        let tmp_path = path.with_extension(format!("pk.{}.tmp", std::process::id()));
+        std::fs::write(&tmp_path, &bytes)
+            .context(format!("Failed to write proving key: {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, &path)
+            .context(format!("Failed to write proving key: {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Return the cached key for `elf`, running `setup` and caching its key on a miss
+    ///
+    /// A corrupt cache entry is reported and replaced. Failing to write the
+    /// cache is reported but does not fail the call.
+    pub fn get_or_setup(&self, elf: &[u8], setup: impl FnOnce() -> SP1ProvingKey) -> SP1ProvingKey {
+        match self.load(elf) {
+            Ok(Some(pk)) => return pk,
+            Ok(None) => {}
+            Err(e) => println!("⚠ Ignoring cached proving key: {:#}", e),
+        }
+
+        println!("🔧 Setting up proving key (cached for later runs)...");
+        let pk = setup();
+        if let Err(e) = self.store(elf, &pk) {
+            println!("⚠ Failed to cache proving key: {:#}", e);
+        }
+        pk
+    }
+}
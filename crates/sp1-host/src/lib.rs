@@ -0,0 +1,13 @@
+//! SP1 zkVM host library for Sigstore attestation verification
+//!
+//! The `sp1-host` binary is a thin CLI over these modules; other hosts (such
+//! as `zk-host`) use the prover and configuration types directly.
+
+pub mod cli;
+pub mod config;
+pub mod key_cache;
+pub mod pipeline;
+pub mod profile;
+pub mod prover;
+pub mod proving;
+pub mod workspace;
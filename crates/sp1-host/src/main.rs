@@ -3,19 +3,28 @@
 //! This CLI tool generates zero-knowledge proofs of Sigstore attestation bundle
 //! verification using SP1 zkVM.
 
-mod cli;
-mod config;
-mod prover;
-mod proving;
+use std::path::Path;
 
 use anyhow::{Context, Result};
 use clap::Parser;
-use sigstore_verifier::types::result::{VerificationOptions, VerificationResult};
+use sigstore_verifier::report::exit_code;
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_zkvm_traits::guest::VerifiedOutput;
+use sigstore_zkvm_traits::notify::{notify_or_warn, ProofNotification, WebhookNotifier};
+use sigstore_zkvm_traits::operator::OperatorKey;
 use sigstore_zkvm_traits::traits::ZkVmProver;
+use sigstore_zkvm_traits::types::InputEncoding;
 use sigstore_zkvm_traits::utils::{
-    display_proof_result, display_verification_result, write_proof_artifact, ProofArtifact,
+    display_proof_result, display_verification_result, write_github_output, write_proof_artifact,
+    sign_proof_artifact, write_report, ProofArtifact,
 };
-use sigstore_zkvm_traits::workflow::prepare_guest_input_local;
+use sigstore_zkvm_traits::workflow::{
+    load_tee_evidence, prepare_guest_input_for_environment, verify_local_file_for_environment,
+};
+use sp1_sdk::HashableKey;
+
+use sp1_host::key_cache::KeyCache;
+use sp1_host::workspace::Workspace;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -23,15 +32,27 @@ async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
     // Parse CLI arguments
-    let cli = crate::cli::Cli::parse();
+    let cli = sp1_host::cli::Cli::parse();
 
     match cli.command {
-        crate::cli::Commands::VerifyingKey => {
-            handle_verifying_key()?;
+        sp1_host::cli::Commands::VerifyingKey(args) => {
+            handle_verifying_key(args)?;
         }
-        crate::cli::Commands::Prove(args) => {
+        sp1_host::cli::Commands::Prove(args) => {
             handle_prove(args).await?;
         }
+        sp1_host::cli::Commands::ProveBatch(args) => {
+            handle_prove_batch(args).await?;
+        }
+        sp1_host::cli::Commands::Verify(args) => {
+            handle_verify(args)?;
+        }
+        sp1_host::cli::Commands::Execute(args) => {
+            handle_execute(args)?;
+        }
+        sp1_host::cli::Commands::Setup(args) => {
+            handle_setup(args)?;
+        }
     }
 
     Ok(())
@@ -39,28 +60,171 @@ async fn main() -> Result<()> {
 
 /// Handle the verifying-key command
 ///
-/// Displays the SP1 verifying key hash of the guest program.
-fn handle_verifying_key() -> Result<()> {
+/// Displays the SP1 verifying key hash of the guest program, or its full
+/// record as JSON, and optionally writes the record to a file.
+fn handle_verifying_key(args: sp1_host::cli::VerifyingKeyArgs) -> Result<()> {
     // Create prover to get verifying key
     let prover =
-        crate::prover::Sp1Prover::new().context("Failed to create SP1 prover")?;
+        sp1_host::prover::Sp1Prover::new().context("Failed to create SP1 prover")?;
 
-    let vk_hash = prover
-        .program_identifier()
+    let record = prover
+        .verifying_key_record()
         .context("Failed to get program identifier")?;
+    let json = serde_json::to_string_pretty(&record)?;
+
+    if let Some(ref output_path) = args.output_path {
+        std::fs::write(output_path, format!("{}\n", json))
+            .with_context(|| format!("Failed to write {}", output_path.display()))?;
+    }
+
+    if args.json {
+        println!("{}", json);
+    } else {
+        println!("Verifying Key Hash: {}", record.program_id);
+        println!("Circuit Version:    {}", record.circuit_version);
+        println!("ELF SHA-256:        {}", record.elf_sha256);
+        if let Some(ref output_path) = args.output_path {
+            println!("Record written to:  {}", output_path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the setup command
+///
+/// Sets up the guest program's proving key and stores it in the key cache, so
+/// later `prove` runs skip setup.
+fn handle_setup(args: sp1_host::cli::SetupArgs) -> Result<()> {
+    let cache = KeyCache::new(args.pk_cache_dir.unwrap_or_else(KeyCache::default_dir));
+    let elf = sugstore_sp1_methods::SP1_SIGSTORE_ELF;
+    let path = cache.path(elf);
+
+    if !args.force {
+        match cache.load(elf) {
+            Ok(Some(pk)) => {
+                println!("✓ Proving key already cached: {}", path.display());
+                println!("Verifying Key Hash: {}", pk.vk.bytes32());
+                return Ok(());
+            }
+            Ok(None) => {}
+            Err(e) => println!("⚠ Replacing cached proving key: {:#}", e),
+        }
+    }
+
+    println!("🔧 Setting up proving key...");
+    let pk = sugstore_sp1_methods::pk(elf);
+    let path = cache.store(elf, &pk)?;
+    println!("✓ Proving key cached: {}", path.display());
+    println!("Verifying Key Hash: {}", pk.vk.bytes32());
+    Ok(())
+}
+
+/// Handle the execute command
+///
+/// Runs the guest program without proving, optionally reporting the cycles
+/// spent in each verification step.
+fn handle_execute(args: sp1_host::cli::ExecuteArgs) -> Result<()> {
+    println!("SP1 Sigstore Guest Execution");
+    println!("============================\n");
 
-    let circuit_version = crate::prover::Sp1Prover::circuit_version();
+    println!("📦 Preparing guest input...");
+    println!("   Bundle:       {}", args.bundle_path.display());
+    println!("   Trusted Root: {}", args.trust_roots_path.display());
 
-    println!("Verifying Key Hash: {}", vk_hash);
-    println!("Circuit Version:    {}", circuit_version);
+    let verification_options = load_verification_options(args.policy_path.as_deref())?;
+
+    let prover_input = prepare_guest_input_for_environment(
+        &args.bundle_path,
+        &args.trust_roots_path,
+        &args.environment,
+        verification_options,
+    )
+    .context("Failed to prepare guest input")?;
+    let prover_input = match args.reference_time {
+        Some(reference_time) => prover_input.with_reference_time(reference_time),
+        None => prover_input,
+    };
+
+    println!("✓ Guest input prepared\n");
+
+    println!("⚙️  Executing guest program...");
+    let prover =
+        sp1_host::prover::Sp1Prover::new().context("Failed to create SP1 prover")?;
+    let (public_values, report) = prover
+        .execute(
+            &prover_input,
+            InputEncoding {
+                compress: args.compress_input,
+            },
+        )
+        .context("Failed to execute guest program")?;
+
+    println!("✓ Executed in {} cycles\n", report.total_instruction_count());
+
+    let prover_output = VerifiedOutput::from_slice(&public_values)
+        .map_err(|e| anyhow::anyhow!("Failed to decode prover output from public values: {}", e))?;
+    let verification_result = prover_output
+        .verification_result()
+        .map_err(|e| anyhow::anyhow!("Failed to decode verification result: {}", e))?;
+    display_verification_result(&verification_result);
+    println!("Options hash:   0x{}", hex::encode(prover_output.options_hash));
+    if prover_output.reference_time != 0 {
+        println!("Reference time: {}", prover_output.reference_time);
+        println!("Signing age:    {}s", prover_output.signing_age);
+    }
+
+    if args.profile {
+        println!("\n📊 Cycle profile");
+        let profile = sp1_host::profile::ProfileReport::from_execution_report(
+            &report,
+            sp1_host::prover::Sp1Prover::circuit_version(),
+        );
+        profile.display();
+        println!();
+        profile.write(&args.profile_output)?;
+    }
 
     Ok(())
 }
 
+/// Handle the verify command
+///
+/// Verifies bundles natively, without generating a proof, and renders a
+/// report for CI systems. If any bundle fails, the process exits with the
+/// code of the first failure's class (see `FailureClass`).
+fn handle_verify(args: sp1_host::cli::VerifyArgs) -> Result<()> {
+    let options = load_verification_options(args.policy_path.as_deref())?;
+    let mut reports = Vec::with_capacity(args.bundle_paths.len());
+    for bundle_path in &args.bundle_paths {
+        let bundle_reports = verify_local_file_for_environment(
+            bundle_path,
+            &args.trust_roots_path,
+            &args.environment,
+            options.clone(),
+        )
+        .context(format!("Failed to verify bundle: {}", bundle_path.display()))?;
+        reports.extend(bundle_reports);
+    }
+
+    write_report(&reports, args.report_format, args.report_output.as_deref())?;
+    if args.github_output {
+        write_github_output(&reports)?;
+    }
+
+    let code = exit_code(&reports);
+    if code != 0 {
+        let failed = reports.iter().filter(|report| !report.verified()).count();
+        eprintln!("❌ {} of {} bundles failed verification", failed, reports.len());
+        std::process::exit(code.into());
+    }
+    Ok(())
+}
+
 /// Handle the prove command
 ///
 /// Generates a proof of Sigstore attestation verification.
-async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
+async fn handle_prove(args: sp1_host::cli::ProveArgs) -> Result<()> {
     println!("SP1 Sigstore Proof Generation");
     println!("==============================\n");
 
@@ -69,36 +233,113 @@ async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
     println!("   Bundle:       {}", args.bundle_path.display());
     println!("   Trusted Root: {}", args.trust_roots_path.display());
 
-    let verification_options = VerificationOptions {
-        expected_digest: None,
-        expected_issuer: None,
-        expected_subject: None,
-    };
+    let verification_options = load_verification_options(args.policy_path.as_deref())?;
+    let operator_key = args
+        .proving
+        .operator_key
+        .as_ref()
+        .map(OperatorKey::from_secret)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?;
 
-    let prover_input = prepare_guest_input_local(
+    let prover_input = prepare_guest_input_for_environment(
         &args.bundle_path,
         &args.trust_roots_path,
+        &args.environment,
         verification_options,
     )
     .context("Failed to prepare guest input")?;
+    let prover_input = match args.reference_time {
+        Some(reference_time) => prover_input.with_reference_time(reference_time),
+        None => prover_input,
+    };
+    let prover_input = match (&args.tee_quote_path, &args.tee_root_path) {
+        (Some(quote_path), Some(root_path)) => prover_input.with_tee_evidence(
+            load_tee_evidence(quote_path, root_path).context("Failed to load TEE evidence")?,
+        ),
+        _ => prover_input,
+    };
+    let prover_input = match &args.reproduced_bundle_path {
+        Some(path) => prover_input.with_reproduced_bundle(
+            std::fs::read(path).with_context(|| format!("Failed to read reproduced bundle {}", path.display()))?,
+        ),
+        None => prover_input,
+    };
 
     println!("✓ Guest input prepared\n");
 
     // Step 2: Create prover
     println!("🔧 Initializing SP1 prover...");
-    let prover =
-        crate::prover::Sp1Prover::new().context("Failed to create SP1 prover")?;
+    let prover = create_prover(&args.proving)?;
     println!("✓ Prover initialized\n");
 
     // Step 3: Build config
-    let config = crate::config::Sp1Config::from_cli_args(&args);
+    let mut config = sp1_host::config::Sp1Config::from_cli_args(&args.proving);
+    let program_id = prover.program_identifier()?;
+
+    // With a workspace, reuse a valid proof or resume a submitted request
+    if let Some(ref workspace_dir) = args.workspace_dir {
+        let bundle_dir = Workspace::new(workspace_dir).bundle_dir(&args.bundle_path)?;
+        if let Some(artifact) = bundle_dir.valid_artifact(
+            &program_id,
+            &sp1_host::prover::Sp1Prover::circuit_version(),
+            &prover_input.verification_options,
+            prover_input.reference_time,
+        ) {
+            println!("✓ Reusing proof from workspace, nothing to prove");
+            if let Some(ref output_path) = args.output_path {
+                write_proof_artifact(output_path, &artifact)
+                    .context("Failed to write proof artifact")?;
+            }
+            return Ok(());
+        }
+
+        let input_bytes = prover_input
+            .encode_input_with(config.input_encoding)
+            .map_err(|e| anyhow::anyhow!("Failed to encode guest input: {}", e))?;
+        if bundle_dir.prepare(&input_bytes)? {
+            println!("⏳ Found a submitted request in the workspace, resuming it\n");
+        }
+        config.workspace = Some(bundle_dir);
+    }
 
     // Step 4: Generate proof
     println!("⚙️  Generating proof...");
-    let (public_values, proof) = prover
-        .prove(&config, &prover_input)
-        .await
-        .context("Failed to generate proof")?;
+    let notifier = create_notifier(&args.proving)?;
+    let bundle_name = args.bundle_path.display().to_string();
+    let circuit_version = sp1_host::prover::Sp1Prover::circuit_version();
+    let (public_values, proof) = match prover.prove(&config, &prover_input).await {
+        Ok(proved) => proved,
+        Err(e) => {
+            if let Some(ref bundle_dir) = config.workspace {
+                bundle_dir.fail(e.to_string())?;
+            }
+            if let Some(ref notifier) = notifier {
+                let notification = ProofNotification::failed(
+                    "sp1",
+                    &program_id,
+                    &circuit_version,
+                    &bundle_name,
+                    &prover_input.bundle_json,
+                    &e.to_string(),
+                );
+                notify_or_warn(notifier, &notification).await;
+            }
+            return Err(e).context("Failed to generate proof");
+        }
+    };
+
+    if let Some(ref notifier) = notifier {
+        let notification = ProofNotification::proved(
+            "sp1",
+            &program_id,
+            &circuit_version,
+            &bundle_name,
+            &prover_input.bundle_json,
+            &public_values,
+        );
+        notify_or_warn(notifier, &notification).await;
+    }
 
     println!("✓ Proof generated successfully\n");
 
@@ -107,27 +348,36 @@ async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
 
     // Step 6: Decode and display verification result
     println!("\n🔍 Decoding verification result...");
-    let verification_result = VerificationResult::from_slice(&public_values).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to decode verification result from public values: {}",
-            e
-        )
-    })?;
+    let prover_output = VerifiedOutput::from_slice(&public_values)
+        .map_err(|e| anyhow::anyhow!("Failed to decode prover output from public values: {}", e))?;
+    let verification_result = prover_output
+        .verification_result()
+        .map_err(|e| anyhow::anyhow!("Failed to decode verification result: {}", e))?;
 
     display_verification_result(&verification_result);
+    println!("Options hash:   0x{}", hex::encode(prover_output.options_hash));
+    if prover_output.reference_time != 0 {
+        println!("Reference time: {}", prover_output.reference_time);
+        println!("Signing age:    {}s", prover_output.signing_age);
+    }
 
-    // Step 7: Write artifact if output path provided
+    // Step 7: Write artifact to the workspace and the output path
+    let mut artifact = ProofArtifact {
+        zkvm: "sp1".to_string(),
+        program_id,
+        circuit_version,
+        journal: format!("0x{}", hex::encode(&public_values)),
+        proof: format!("0x{}", hex::encode(&proof)),
+        operator_signature: None,
+    };
+    if let Some(ref operator_key) = operator_key {
+        sign_proof_artifact(&mut artifact, operator_key, &prover_input.bundle_json)?;
+    }
+    if let Some(ref bundle_dir) = config.workspace {
+        bundle_dir.complete(&artifact)?;
+    }
     if let Some(ref output_path) = args.output_path {
         println!("\n💾 Writing proof artifact...");
-
-        let artifact = ProofArtifact {
-            zkvm: "sp1".to_string(),
-            program_id: prover.program_identifier()?,
-            circuit_version: crate::prover::Sp1Prover::circuit_version(),
-            journal: format!("0x{}", hex::encode(&public_values)),
-            proof: format!("0x{}", hex::encode(&proof)),
-        };
-
         write_proof_artifact(output_path, &artifact)
             .context("Failed to write proof artifact")?;
     }
@@ -136,3 +386,92 @@ async fn handle_prove(args: crate::cli::ProveArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// Verification options from the `--policy` file, or the defaults if none was given
+fn load_verification_options(policy_path: Option<&Path>) -> Result<VerificationOptions> {
+    match policy_path {
+        Some(path) => VerificationOptions::from_policy_file(path)
+            .context(format!("Failed to load policy file: {}", path.display())),
+        None => Ok(VerificationOptions::default()),
+    }
+}
+
+/// Create the prover, with the proving key cache unless `--no-pk-cache` is set
+fn create_prover(args: &sp1_host::cli::ProvingArgs) -> Result<sp1_host::prover::Sp1Prover> {
+    let prover = sp1_host::prover::Sp1Prover::new().context("Failed to create SP1 prover")?;
+    if args.no_pk_cache {
+        return Ok(prover);
+    }
+    let cache_dir = args.pk_cache_dir.clone().unwrap_or_else(KeyCache::default_dir);
+    Ok(prover.with_key_cache(KeyCache::new(cache_dir)))
+}
+
+/// Webhook notifier for `--notify-url`, if given
+fn create_notifier(args: &sp1_host::cli::ProvingArgs) -> Result<Option<WebhookNotifier>> {
+    args.notify_url
+        .as_deref()
+        .map(WebhookNotifier::new)
+        .transpose()
+        .context("Failed to create webhook notifier")
+}
+
+/// Handle the prove-batch command
+///
+/// Proves every bundle in a directory concurrently, writes one artifact per
+/// bundle and a manifest, and fails if any bundle could not be proven.
+async fn handle_prove_batch(args: sp1_host::cli::ProveBatchArgs) -> Result<()> {
+    println!("SP1 Sigstore Batch Proof Generation");
+    println!("===================================\n");
+
+    let bundles = sp1_host::pipeline::find_bundles(&args.bundles_dir)?;
+    if bundles.is_empty() {
+        anyhow::bail!("No *.json bundles found in {}", args.bundles_dir.display());
+    }
+    std::fs::create_dir_all(&args.output_dir)
+        .context(format!("Failed to create directory: {}", args.output_dir.display()))?;
+
+    println!("📦 {} bundles, {} at a time\n", bundles.len(), args.concurrency);
+
+    let prover = create_prover(&args.proving)?;
+    // Set up the proving key once, before the workers race to fill the cache
+    prover.proving_key();
+
+    let settings = sp1_host::pipeline::BatchSettings {
+        trust_roots_path: args.trust_roots_path.clone(),
+        environment: args.environment.clone(),
+        options: load_verification_options(args.policy_path.as_deref())?,
+        reference_time: args.reference_time,
+        output_dir: args.output_dir.clone(),
+        workspace: Workspace::new(
+            args.workspace_dir
+                .clone()
+                .unwrap_or_else(|| args.output_dir.join("workspace")),
+        ),
+        notifier: create_notifier(&args.proving)?,
+        operator_key: args
+            .proving
+            .operator_key
+            .as_ref()
+            .map(OperatorKey::from_secret)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?,
+        concurrency: args.concurrency.into(),
+    };
+    let manifest = sp1_host::pipeline::run_batch(
+        std::sync::Arc::new(prover),
+        std::sync::Arc::new(sp1_host::config::Sp1Config::from_cli_args(&args.proving)),
+        bundles,
+        std::sync::Arc::new(settings),
+    )
+    .await?;
+
+    println!();
+    manifest.display();
+    let manifest_path = manifest.write(&args.output_dir)?;
+    println!("\n💾 Manifest written to {}", manifest_path.display());
+
+    if manifest.failed > 0 {
+        anyhow::bail!("{} of {} bundles failed", manifest.failed, manifest.entries.len());
+    }
+    Ok(())
+}
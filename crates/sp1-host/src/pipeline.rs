@@ -0,0 +1,412 @@
+//! Concurrent proof pipeline for `prove-batch`
+//!
+//! Each bundle goes through input preparation, cost estimation and network
+//! submission (both in `Sp1Prover::prove`), and artifact collection. Up to
+//! `concurrency` bundles are in flight at once; a failed bundle is recorded in
+//! the manifest without stopping the others.
+//!
+//! Proving state is kept in a `Workspace`, so re-running a batch reuses valid
+//! proofs, resumes submitted requests and only proves the remaining bundles.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_verifier::types::environment::SigstoreEnvironment;
+use sigstore_zkvm_traits::guest::VerifiedOutput;
+use sigstore_zkvm_traits::notify::{notify_or_warn, ProofNotification, WebhookNotifier};
+use sigstore_zkvm_traits::traits::ZkVmProver;
+use sigstore_zkvm_traits::operator::OperatorKey;
+use sigstore_zkvm_traits::utils::{sign_proof_artifact, write_proof_artifact, ProofArtifact};
+use sigstore_zkvm_traits::workflow::prepare_guest_input_for_environment;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::runtime::Handle;
+use tokio::task::JoinSet;
+
+use crate::config::Sp1Config;
+use crate::prover::Sp1Prover;
+use crate::workspace::Workspace;
+
+/// File name of the batch manifest in the output directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Pipeline stage of one bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Queued,
+    Preparing,
+    Proving,
+    Writing,
+    Proved,
+    Reused,
+    Failed,
+}
+
+impl Stage {
+    fn label(self) -> &'static str {
+        match self {
+            Stage::Queued => "queued",
+            Stage::Preparing => "preparing input",
+            Stage::Proving => "proving",
+            Stage::Writing => "writing artifact",
+            Stage::Proved => "✓ proved",
+            Stage::Reused => "✓ reused",
+            Stage::Failed => "❌ failed",
+        }
+    }
+}
+
+/// Settings shared by every bundle of a batch
+#[derive(Debug, Clone)]
+pub struct BatchSettings {
+    pub trust_roots_path: PathBuf,
+    pub environment: SigstoreEnvironment,
+    pub options: VerificationOptions,
+    pub reference_time: Option<u64>,
+    pub output_dir: PathBuf,
+    pub workspace: Workspace,
+    pub notifier: Option<WebhookNotifier>,
+    /// Key to sign each artifact's metadata with (see `--operator-key`)
+    pub operator_key: Option<OperatorKey>,
+    pub concurrency: usize,
+}
+
+/// Outcome of one bundle, as recorded in the manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestEntry {
+    pub bundle: String,
+    pub status: Stage,
+    pub artifact: Option<String>,
+    pub options_hash: Option<String>,
+    pub error: Option<String>,
+    pub duration_secs: f64,
+}
+
+/// Summary of a batch, written to `manifest.json`
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchManifest {
+    pub zkvm: String,
+    pub program_id: String,
+    pub circuit_version: String,
+
+    /// Bundles proven in this run
+    pub proved: usize,
+
+    /// Bundles whose proof from an earlier run was reused
+    pub reused: usize,
+
+    pub failed: usize,
+
+    /// One entry per bundle, in file name order
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl BatchManifest {
+    /// Print a summary table of the batch
+    pub fn display(&self) {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.bundle.len())
+            .max()
+            .unwrap_or(0)
+            .max("Bundle".len());
+
+        println!("{:<width$}  {:<10}  {:>8}", "Bundle", "Status", "Time (s)");
+        for entry in &self.entries {
+            println!(
+                "{:<width$}  {:<10}  {:>8.1}",
+                entry.bundle,
+                entry.status.label(),
+                entry.duration_secs
+            );
+            if let Some(ref error) = entry.error {
+                println!("{:<width$}  {}", "", error);
+            }
+        }
+        println!(
+            "\n{} proved, {} reused, {} failed",
+            self.proved, self.reused, self.failed
+        );
+    }
+
+    /// Write the manifest as pretty-printed JSON to `<dir>/manifest.json`
+    pub fn write(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(MANIFEST_FILE);
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize manifest")?;
+        std::fs::write(&path, json)
+            .context(format!("Failed to write manifest: {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Stage of every bundle, printed as it changes
+struct Progress {
+    names: Vec<String>,
+    stages: Mutex<Vec<Stage>>,
+}
+
+impl Progress {
+    fn new(names: Vec<String>) -> Self {
+        let stages = Mutex::new(vec![Stage::Queued; names.len()]);
+        Progress { names, stages }
+    }
+
+    fn set(&self, index: usize, stage: Stage) {
+        let mut stages = self.stages.lock().unwrap();
+        stages[index] = stage;
+        let done = stages
+            .iter()
+            .filter(|stage| matches!(stage, Stage::Proved | Stage::Reused | Stage::Failed))
+            .count();
+        println!(
+            "[{:>3}/{}] {:<40} {}",
+            done,
+            stages.len(),
+            self.names[index],
+            stage.label()
+        );
+    }
+}
+
+/// List the `*.json` bundles in `dir`, sorted by file name
+pub fn find_bundles(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut bundles = Vec::new();
+    for entry in
+        std::fs::read_dir(dir).context(format!("Failed to read directory: {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            bundles.push(path);
+        }
+    }
+    bundles.sort();
+    Ok(bundles)
+}
+
+/// Artifact path for `bundle_path`: `<output_dir>/<file stem>.proof.json`
+fn artifact_path(output_dir: &Path, bundle_path: &Path) -> PathBuf {
+    let stem = bundle_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "bundle".to_string());
+    output_dir.join(format!("{}.proof.json", stem))
+}
+
+/// Prove every bundle, at most `settings.concurrency` at a time
+///
+/// # Arguments
+///
+/// * `prover` - Prover shared by all bundles
+/// * `config` - Network proving configuration shared by all bundles
+/// * `bundles` - Bundle files to prove
+/// * `settings` - Trust roots, options and output directory
+///
+/// # Returns
+///
+/// The manifest of the batch; failed bundles are recorded in it rather than
+/// returned as errors.
+pub async fn run_batch(
+    prover: Arc<Sp1Prover>,
+    config: Arc<Sp1Config>,
+    bundles: Vec<PathBuf>,
+    settings: Arc<BatchSettings>,
+) -> Result<BatchManifest> {
+    let program_id = prover.program_identifier()?;
+    let names: Vec<String> = bundles
+        .iter()
+        .map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string())
+        })
+        .collect();
+    let progress = Arc::new(Progress::new(names));
+
+    let mut entries: Vec<Option<ManifestEntry>> = vec![None; bundles.len()];
+    let mut pending = bundles.into_iter().enumerate();
+    let mut running = JoinSet::new();
+
+    loop {
+        // Keep the pipeline full, then wait for the next bundle to finish
+        while running.len() < settings.concurrency {
+            let Some((index, bundle_path)) = pending.next() else {
+                break;
+            };
+            let (prover, config, settings, progress, program_id) = (
+                prover.clone(),
+                config.clone(),
+                settings.clone(),
+                progress.clone(),
+                program_id.clone(),
+            );
+            let handle = Handle::current();
+            running.spawn_blocking(move || {
+                let entry = prove_one(
+                    &handle,
+                    &prover,
+                    &config,
+                    &settings,
+                    &program_id,
+                    &bundle_path,
+                    index,
+                    &progress,
+                );
+                (index, entry)
+            });
+        }
+
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let (index, entry) = joined.context("Batch worker panicked")?;
+        entries[index] = Some(entry);
+    }
+
+    let entries: Vec<ManifestEntry> = entries.into_iter().flatten().collect();
+    let count = |stage: Stage| entries.iter().filter(|entry| entry.status == stage).count();
+    let (proved, reused, failed) = (count(Stage::Proved), count(Stage::Reused), count(Stage::Failed));
+    Ok(BatchManifest {
+        zkvm: "sp1".to_string(),
+        program_id,
+        circuit_version: Sp1Prover::circuit_version(),
+        proved,
+        reused,
+        failed,
+        entries,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_one(
+    handle: &Handle,
+    prover: &Sp1Prover,
+    config: &Sp1Config,
+    settings: &BatchSettings,
+    program_id: &str,
+    bundle_path: &Path,
+    index: usize,
+    progress: &Progress,
+) -> ManifestEntry {
+    let started = Instant::now();
+    let artifact_path = artifact_path(&settings.output_dir, bundle_path);
+
+    let outcome = (|| -> Result<(Stage, VerifiedOutput)> {
+        progress.set(index, Stage::Preparing);
+        let bundle_dir = settings.workspace.bundle_dir(bundle_path)?;
+        let mut input = prepare_guest_input_for_environment(
+            bundle_path,
+            &settings.trust_roots_path,
+            &settings.environment,
+            settings.options.clone(),
+        )
+        .context("Failed to prepare guest input")?;
+        if let Some(reference_time) = settings.reference_time {
+            input = input.with_reference_time(reference_time);
+        }
+
+        if let Some(artifact) = bundle_dir.valid_artifact(
+            program_id,
+            &Sp1Prover::circuit_version(),
+            &input.verification_options,
+            input.reference_time,
+        ) {
+            write_proof_artifact(&artifact_path, &artifact)?;
+            let journal = hex::decode(artifact.journal.trim_start_matches("0x"))?;
+            let output = VerifiedOutput::from_slice(&journal)
+                .map_err(|e| anyhow::anyhow!("Failed to decode prover output: {}", e))?;
+            return Ok((Stage::Reused, output));
+        }
+
+        let input_bytes = input
+            .encode_input_with(config.input_encoding)
+            .map_err(|e| anyhow::anyhow!("Failed to encode guest input: {}", e))?;
+        bundle_dir.prepare(&input_bytes)?;
+        let mut config = config.clone();
+        config.workspace = Some(bundle_dir.clone());
+
+        progress.set(index, Stage::Proving);
+        let (public_values, proof) = match handle.block_on(prover.prove(&config, &input)) {
+            Ok(proved) => proved,
+            Err(e) => {
+                bundle_dir.fail(e.to_string())?;
+                return Err(e).context("Failed to generate proof");
+            }
+        };
+        let output = VerifiedOutput::from_slice(&public_values)
+            .map_err(|e| anyhow::anyhow!("Failed to decode prover output: {}", e))?;
+
+        progress.set(index, Stage::Writing);
+        let mut artifact = ProofArtifact {
+            zkvm: "sp1".to_string(),
+            program_id: program_id.to_string(),
+            circuit_version: Sp1Prover::circuit_version(),
+            journal: format!("0x{}", hex::encode(&public_values)),
+            proof: format!("0x{}", hex::encode(&proof)),
+            operator_signature: None,
+        };
+        if let Some(ref operator_key) = settings.operator_key {
+            sign_proof_artifact(&mut artifact, operator_key, &input.bundle_json)?;
+        }
+        bundle_dir.complete(&artifact)?;
+        write_proof_artifact(&artifact_path, &artifact)?;
+        Ok((Stage::Proved, output))
+    })();
+
+    let bundle = bundle_path.display().to_string();
+    let duration_secs = started.elapsed().as_secs_f64();
+
+    // Reused proofs were already reported by the run that produced them
+    if let Some(ref notifier) = settings.notifier {
+        let bundle_json = std::fs::read(bundle_path).unwrap_or_default();
+        let circuit_version = Sp1Prover::circuit_version();
+        let notification = match outcome {
+            Ok((Stage::Reused, _)) => None,
+            Ok((_, ref output)) => Some(ProofNotification::proved(
+                "sp1",
+                program_id,
+                &circuit_version,
+                &bundle,
+                &bundle_json,
+                &output.as_slice(),
+            )),
+            Err(ref e) => Some(ProofNotification::failed(
+                "sp1",
+                program_id,
+                &circuit_version,
+                &bundle,
+                &bundle_json,
+                &format!("{:#}", e),
+            )),
+        };
+        if let Some(notification) = notification {
+            handle.block_on(notify_or_warn(notifier, &notification));
+        }
+    }
+    match outcome {
+        Ok((stage, output)) => {
+            progress.set(index, stage);
+            ManifestEntry {
+                bundle,
+                status: stage,
+                artifact: Some(artifact_path.display().to_string()),
+                options_hash: Some(format!("0x{}", hex::encode(output.options_hash))),
+                error: None,
+                duration_secs,
+            }
+        }
+        Err(e) => {
+            progress.set(index, Stage::Failed);
+            ManifestEntry {
+                bundle,
+                status: Stage::Failed,
+                artifact: None,
+                options_hash: None,
+                error: Some(format!("{:#}", e)),
+                duration_secs,
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Cycle profile reports for the SP1 guest program
+//!
+//! Turns the cycle-tracker regions of an SP1 `ExecutionReport` into a
+//! machine-readable breakdown of where verification spends its cycles.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sp1_sdk::ExecutionReport;
+use std::path::Path;
+
+/// Cycle profile of a single guest execution
+#[derive(Debug, Serialize)]
+pub struct ProfileReport {
+    /// SP1 circuit version the guest was executed with
+    pub circuit_version: String,
+
+    /// Total instructions executed by the guest
+    pub total_cycles: u64,
+
+    /// Total syscalls (including precompiles) made by the guest
+    pub total_syscalls: u64,
+
+    /// Number of distinct memory addresses touched by the guest
+    pub touched_memory_addresses: u64,
+
+    /// Tracked regions, most expensive first
+    pub regions: Vec<RegionProfile>,
+}
+
+/// Cycles spent in one cycle-tracker region
+#[derive(Debug, Serialize)]
+pub struct RegionProfile {
+    /// Region name (see `sigstore_verifier::profiling`)
+    pub name: String,
+
+    /// Cycles spent in the region, summed over all invocations and including nested regions
+    pub cycles: u64,
+
+    /// Number of times the region was entered
+    pub invocations: u64,
+
+    /// Share of `total_cycles`, in percent
+    pub percent: f64,
+}
+
+impl ProfileReport {
+    /// Build a report from an SP1 execution report
+    pub fn from_execution_report(report: &ExecutionReport, circuit_version: String) -> Self {
+        let total_cycles = report.total_instruction_count();
+
+        let mut regions: Vec<RegionProfile> = report
+            .cycle_tracker
+            .iter()
+            .map(|(name, &cycles)| RegionProfile {
+                name: name.clone(),
+                cycles,
+                invocations: report.invocation_tracker.get(name).copied().unwrap_or(0),
+                percent: if total_cycles == 0 {
+                    0.0
+                } else {
+                    cycles as f64 * 100.0 / total_cycles as f64
+                },
+            })
+            .collect();
+        regions.sort_by(|a, b| b.cycles.cmp(&a.cycles).then_with(|| a.name.cmp(&b.name)));
+
+        ProfileReport {
+            circuit_version,
+            total_cycles,
+            total_syscalls: report.total_syscall_count(),
+            touched_memory_addresses: report.touched_memory_addresses,
+            regions,
+        }
+    }
+
+    /// Print the breakdown as a table
+    pub fn display(&self) {
+        println!("Total cycles:             {}", self.total_cycles);
+        println!("Total syscalls:           {}", self.total_syscalls);
+        println!("Touched memory addresses: {}", self.touched_memory_addresses);
+        println!();
+        println!("{:<20} {:>14} {:>8} {:>12}", "Region", "Cycles", "%", "Invocations");
+        for region in &self.regions {
+            println!(
+                "{:<20} {:>14} {:>7.2}% {:>12}",
+                region.name, region.cycles, region.percent, region.invocations
+            );
+        }
+    }
+
+    /// Write the report as pretty-printed JSON
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize profile report")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write profile report to: {}", path.display()))?;
+        println!("✓ Profile report written to: {}", path.display());
+        Ok(())
+    }
+}
@@ -3,17 +3,108 @@
 //! Implements the ZkVmProver trait for SP1, providing proof generation
 //! capabilities for Sigstore attestation verification.
 
+use crate::cli::ProverType;
 use crate::config::Sp1Config;
+use crate::key_cache::KeyCache;
 use crate::proving::network::prove_with_network;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::hash::sha256;
 use sigstore_zkvm_traits::error::ZkVmError;
+use sigstore_zkvm_traits::guest::OUTPUT_VERSION;
 use sigstore_zkvm_traits::traits::ZkVmProver;
-use sigstore_zkvm_traits::types::ProverInput;
-use sp1_sdk::{EnvProver, HashableKey, Prover, ProverClient, SP1Stdin};
-use sugstore_sp1_methods::{vk, SP1_SIGSTORE_ELF};
+use sigstore_zkvm_traits::types::{InputEncoding, ProverInput};
+use sp1_sdk::{
+    ExecutionReport, HashableKey, NetworkProver, Prover, ProverClient, SP1ProvingKey, SP1Stdin,
+};
+use sugstore_sp1_methods::{pk, SP1_SIGSTORE_ELF};
+
+/// Identity of the guest program, for the program registry and deployment scripts
+///
+/// `zkvm`, `program_id` and `circuit_version` are the fields of a
+/// `registry::RegisteredProgram`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyingKeyRecord {
+    pub zkvm: String,
+    /// Verifying key hash (bytes32), the on-chain program identifier
+    pub program_id: String,
+    /// SP1 circuit version the key was set up with
+    pub circuit_version: String,
+    /// SHA-256 of the guest ELF, hex
+    pub elf_sha256: String,
+    /// Size of the guest ELF in bytes
+    pub elf_size: usize,
+    /// Output layout version the guest commits
+    pub output_version: u16,
+    /// Version of the sp1-host build that embeds the ELF
+    pub host_version: String,
+}
 
 pub struct Sp1Prover {
     elf: &'static [u8],
+    key_cache: Option<KeyCache>,
+}
+
+impl Sp1Prover {
+    /// Reuse proving keys cached in `cache` instead of running setup on every call
+    pub fn with_key_cache(mut self, cache: KeyCache) -> Self {
+        self.key_cache = Some(cache);
+        self
+    }
+
+    /// Proving key of the guest program, from the key cache if one is configured
+    pub fn proving_key(&self) -> SP1ProvingKey {
+        match &self.key_cache {
+            Some(cache) => cache.get_or_setup(self.elf, || pk(self.elf)),
+            None => pk(self.elf),
+        }
+    }
+
+    /// Describe the guest program: verifying key hash, circuit version and ELF hash
+    pub fn verifying_key_record(&self) -> Result<VerifyingKeyRecord, ZkVmError> {
+        Ok(VerifyingKeyRecord {
+            zkvm: "sp1".to_string(),
+            program_id: self.program_identifier()?,
+            circuit_version: Self::circuit_version(),
+            elf_sha256: hex::encode(sha256(self.elf)),
+            elf_size: self.elf.len(),
+            output_version: OUTPUT_VERSION,
+            host_version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    /// Execute the guest program locally without generating a proof
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input data containing the bundle and verification parameters
+    /// * `encoding` - How to encode the input for the guest
+    ///
+    /// # Returns
+    ///
+    /// The committed public values and the SP1 execution report, whose
+    /// `cycle_tracker` holds the per-step regions emitted by the verifier.
+    pub fn execute(
+        &self,
+        input: &ProverInput,
+        encoding: InputEncoding,
+    ) -> Result<(Vec<u8>, ExecutionReport), ZkVmError> {
+        let input_bytes = input
+            .encode_input_with(encoding)
+            .map_err(|e| ZkVmError::InputEncodingError(format!("Failed to encode ProverInput: {}", e)))?;
+
+        let mut stdin = SP1Stdin::new();
+        stdin.write_vec(input_bytes);
+
+        let client = ProverClient::builder().cpu().build();
+        let (public_values, report) = client.execute(self.elf, &stdin).run().map_err(|e| {
+            ZkVmError::GuestExecutionError {
+                code: None,
+                message: format!("Failed to execute guest program: {}", e),
+            }
+        })?;
+        Ok((public_values.to_vec(), report))
+    }
 }
 
 #[async_trait]
@@ -23,6 +114,7 @@ impl ZkVmProver for Sp1Prover {
     fn new() -> Result<Self, ZkVmError> {
         Ok(Sp1Prover {
             elf: SP1_SIGSTORE_ELF,
+            key_cache: None,
         })
     }
 
@@ -33,12 +125,12 @@ impl ZkVmProver for Sp1Prover {
     ) -> Result<(Vec<u8>, Vec<u8>), ZkVmError> {
         // Serialize input to bytes
         let input_bytes = input
-            .encode_input()
-            .map_err(|e| ZkVmError::InvalidInput(format!("Failed to encode ProverInput: {}", e)))?;
+            .encode_input_with(config.input_encoding)
+            .map_err(|e| ZkVmError::InputEncodingError(format!("Failed to encode ProverInput: {}", e)))?;
 
         // Log verifying key hash
-        let vk = vk(self.elf);
-        let vk_hash = vk.bytes32();
+        let pk = self.proving_key();
+        let vk_hash = pk.vk.bytes32();
         println!("Verifying Key Hash: {}", vk_hash);
         println!("SP1 Version: {}", Self::circuit_version());
 
@@ -46,35 +138,64 @@ impl ZkVmProver for Sp1Prover {
         let mut stdin = SP1Stdin::new();
         stdin.write_vec(input_bytes.clone());
 
-        // Check for DEV_MODE
-        if std::env::var("DEV_MODE").is_ok() || std::env::var("SP1_DEV_MODE").is_ok() {
-            println!("⚠ Running in DEV_MODE - no proof will be generated");
-            let client = EnvProver::new();
+        if config.prover_type == ProverType::Dev {
+            println!("⚠ Running in dev mode - no proof will be generated");
+            let client = ProverClient::builder().cpu().build();
             let (public_values, _) = client.execute(self.elf, &stdin).run().map_err(|e| {
-                ZkVmError::ProofGenerationError(format!("Failed to execute guest program: {}", e))
+                ZkVmError::GuestExecutionError {
+                    code: None,
+                    message: format!("Failed to execute guest program: {}", e),
+                }
             })?;
             return Ok((public_values.to_vec(), vec![]));
         }
 
-        // Set up SP1 environment variables
-        std::env::set_var("SP1_PROVER", "network");
-
-        // Get private key from config or environment
-        let sp1_network_key = config.private_key.as_str();
-        std::env::set_var("NETWORK_PRIVATE_KEY", sp1_network_key);
+        let client = network_client(config);
 
-        let client = ProverClient::builder()
-            .network_for(sp1_sdk::network::NetworkMode::Mainnet)
-            .build();
+        // With a price cap, measure the gas up front so an over-budget request
+        // fails here instead of settling at whatever the auction reaches. A
+        // resumed request was already checked when it was submitted.
+        let resuming = config
+            .workspace
+            .as_ref()
+            .is_some_and(|dir| dir.request_id().is_some());
+        let gas_limit = match config.auction.max_price_per_pgu {
+            Some(_) if !resuming => {
+                let (_, report) = client
+                    .execute(self.elf, &stdin)
+                    .calculate_gas(true)
+                    .run()
+                    .map_err(|e| {
+                        ZkVmError::GuestExecutionError {
+                            code: None,
+                            message: format!("Failed to execute guest program: {}", e),
+                        }
+                    })?;
+                let gas = report.gas.ok_or_else(|| {
+                    ZkVmError::ProofGenerationError("Execution report has no gas estimate".to_string())
+                })?;
+                if let Some(cost) = config.auction.check_cost(gas).map_err(ZkVmError::CostLimitExceeded)? {
+                    println!("Estimated gas: {} PGU, worst-case cost: {}", gas, cost);
+                }
+                Some(gas)
+            }
+            _ => None,
+        };
 
-        // Get proving key for proof generation
-        let (pk, _) = client.setup(self.elf);
-        prove_with_network(&client, &pk, stdin, config.proving_mode).await
+        prove_with_network(
+            &client,
+            &pk,
+            stdin,
+            config.proving_mode,
+            &config.auction,
+            gas_limit,
+            config.workspace.as_ref(),
+        )
+        .await
     }
 
     fn program_identifier(&self) -> Result<String, ZkVmError> {
-        let vk = vk(self.elf);
-        Ok(format!("{}", vk.bytes32()))
+        Ok(format!("{}", self.proving_key().vk.bytes32()))
     }
 
     fn circuit_version() -> String {
@@ -85,3 +206,18 @@ impl ZkVmProver for Sp1Prover {
         self.elf
     }
 }
+
+/// Build a network client from the config alone
+///
+/// The key and endpoint are handed to the SDK directly rather than through
+/// `NETWORK_PRIVATE_KEY` / `NETWORK_RPC_URL`, so they don't linger in the
+/// process environment and don't leak into other provers.
+fn network_client(config: &Sp1Config) -> NetworkProver {
+    let builder = ProverClient::builder()
+        .network_for(config.network_mode)
+        .private_key(config.private_key.expose());
+    match &config.rpc_url {
+        Some(rpc_url) => builder.rpc_url(rpc_url).build(),
+        None => builder.build(),
+    }
+}
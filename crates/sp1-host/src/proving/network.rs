@@ -3,18 +3,27 @@
 //! Provides functionality to generate proofs using the SP1 proving network.
 
 use crate::cli::ProvingMode;
+use alloy_primitives::B256;
+use crate::config::AuctionConfig;
+use crate::workspace::BundleDir;
 use sigstore_zkvm_traits::error::ZkVmError;
-use sp1_sdk::{NetworkProver, SP1ProvingKey, SP1Stdin, network::FulfillmentStrategy};
+use sp1_sdk::{
+    NetworkProver, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin,
+    network::FulfillmentStrategy,
+};
 
 /// Generate a proof using the SP1 proving network
 ///
 /// # Arguments
 ///
 /// * `client` - SP1 prover client
-/// * `elf` - Guest program ELF (for execute in Mock mode)
 /// * `pk` - SP1 proving key
 /// * `stdin` - Input data for the guest program (consumed)
-/// * `mode` - Proving mode (Mock, Compressed, Groth16, Plonk)
+/// * `mode` - Proving mode (Compressed, Groth16, Plonk)
+/// * `auction` - Auction bid cap and prover whitelist
+/// * `gas_limit` - Optional gas limit for the request, in prover gas units
+/// * `workspace` - Optional bundle workspace; a request recorded there is
+///   resumed, and a new request is recorded before waiting on it
 ///
 /// # Returns
 ///
@@ -31,53 +40,68 @@ pub async fn prove_with_network(
     client: &NetworkProver,
     pk: &SP1ProvingKey,
     stdin: SP1Stdin,
-    mode: ProvingMode
+    mode: ProvingMode,
+    auction: &AuctionConfig,
+    gas_limit: Option<u64>,
+    workspace: Option<&BundleDir>,
 ) -> Result<(Vec<u8>, Vec<u8>), ZkVmError> {
     println!("🔗 Connecting to SP1 network...");
+
+    if let Some(request_id) = workspace.and_then(BundleDir::request_id) {
+        println!("⏳ Resuming proof request {}...", request_id);
+        return wait_for_proof(client, request_id).await;
+    }
+
     println!("🚀 Submitting proof request to SP1 network...");
 
-    match mode {
-        ProvingMode::Compressed => {
-            println!("🔐 Generating Compressed proof...");
-            // Note: This uses local proving. Replace with network proving when SP1 network SDK is available
-            let proof = client
-                .prove(pk, &stdin)
-                .compressed()
-                .strategy(FulfillmentStrategy::Auction)
-                .run()
-                .map_err(|e| {
-                    ZkVmError::ProofGenerationError(format!("Failed to generate compressed proof: {}", e))
-                })?;
-            println!("✓ Compressed proof generated successfully!");
-            Ok((proof.public_values.to_vec(), proof.bytes()))
-        }
-        ProvingMode::Groth16 => {
-            println!("🔐 Generating Groth16 proof...");
-            // Note: This uses local proving. Replace with network proving when SP1 network SDK is available
-            let proof = client
-                .prove(pk, &stdin)
-                .groth16()
-                .strategy(FulfillmentStrategy::Auction)
-                .run()
-                .map_err(|e| {
-                    ZkVmError::ProofGenerationError(format!("Failed to generate Groth16 proof: {}", e))
-                })?;
-            println!("✓ Groth16 proof generated successfully!");
-            Ok((proof.public_values.to_vec(), proof.bytes()))
-        }
-        ProvingMode::Plonk => {
-            println!("🔐 Generating Plonk proof...");
-            // Note: This uses local proving. Replace with network proving when SP1 network SDK is available
-            let proof = client
-                .prove(pk, &stdin)
-                .plonk()
-                .strategy(FulfillmentStrategy::Auction)
-                .run()
-                .map_err(|e| {
-                    ZkVmError::ProofGenerationError(format!("Failed to generate Plonk proof: {}", e))
-                })?;
-            println!("✓ Plonk proof generated successfully!");
-            Ok((proof.public_values.to_vec(), proof.bytes()))
-        }
+    let builder = client.prove(pk, &stdin);
+    let (builder, label) = match mode {
+        ProvingMode::Compressed => (builder.compressed(), "Compressed"),
+        ProvingMode::Groth16 => (builder.groth16(), "Groth16"),
+        ProvingMode::Plonk => (builder.plonk(), "Plonk"),
+    };
+
+    let mut builder = builder.strategy(FulfillmentStrategy::Auction);
+    if let Some(price) = auction.max_price_per_pgu {
+        println!("   Max price per PGU: {}", price);
+        builder = builder.max_price_per_pgu(price);
+    }
+    if !auction.whitelist.is_empty() {
+        println!("   Whitelisted provers: {}", auction.whitelist.len());
+        builder = builder.whitelist(Some(auction.whitelist.clone()));
+    }
+    if let Some(gas_limit) = gas_limit {
+        builder = builder.gas_limit(gas_limit);
     }
+
+    println!("🔐 Generating {} proof...", label);
+    let Some(workspace) = workspace else {
+        let proof = builder.run().map_err(|e| {
+            ZkVmError::from_network(format!("Failed to generate {} proof: {}", label, e))
+        })?;
+        println!("✓ {} proof generated successfully!", label);
+        return Ok((proof.public_values.to_vec(), proof.bytes()));
+    };
+
+    // Record the request before waiting, so an interrupted run can resume it
+    let request_id = builder.request_async().await.map_err(|e| {
+        ZkVmError::from_network(format!("Failed to submit {} proof request: {}", label, e))
+    })?;
+    workspace.record_request(request_id).map_err(|e| {
+        ZkVmError::Other(format!("Failed to record request {}: {:#}", request_id, e))
+    })?;
+    println!("   Request ID: {}", request_id);
+    wait_for_proof(client, request_id).await
+}
+
+/// Wait for a submitted request and return (public_values, proof_bytes)
+async fn wait_for_proof(
+    client: &NetworkProver,
+    request_id: B256,
+) -> Result<(Vec<u8>, Vec<u8>), ZkVmError> {
+    let proof: SP1ProofWithPublicValues = client.wait_proof(request_id, None).await.map_err(|e| {
+        ZkVmError::from_network(format!("Failed to fetch proof {}: {}", request_id, e))
+    })?;
+    println!("✓ Proof {} fulfilled!", request_id);
+    Ok((proof.public_values.to_vec(), proof.bytes()))
 }
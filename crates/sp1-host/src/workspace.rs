@@ -0,0 +1,202 @@
+//! Resumable proving workspace
+//!
+//! A workspace keeps the state of each bundle's proof in its own directory,
+//! named after the SHA-256 of the bundle file:
+//!
+//! ```text
+//! <workspace>/<sha256(bundle)>/
+//!   input.bin      encoded guest input
+//!   request_id     SP1 network request ID, once submitted
+//!   status.json    current state and last error
+//!   artifact.json  proof artifact, once proven
+//! ```
+//!
+//! Re-running `prove` or `prove-batch` against the same workspace reuses
+//! artifacts that are still valid for the current program and options, waits
+//! on requests that were submitted but not collected, and proves the rest
+//! again. A request is only resumed if its stored input matches the current one.
+
+use alloy_primitives::B256;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sigstore_verifier::crypto::hash::sha256;
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_zkvm_traits::guest::VerifiedOutput;
+use sigstore_zkvm_traits::utils::{write_proof_artifact, ProofArtifact};
+use std::path::{Path, PathBuf};
+
+/// Proof state of one bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleState {
+    /// Input written, not yet submitted
+    Prepared,
+    /// Request submitted to the network, proof not yet collected
+    Submitted,
+    /// Artifact written
+    Proved,
+    /// Last attempt failed
+    Failed,
+}
+
+/// Contents of `status.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleStatus {
+    /// Bundle file the directory was created for
+    pub bundle: String,
+    pub state: BundleState,
+    pub error: Option<String>,
+}
+
+/// Root directory of a workspace
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Workspace { root: root.into() }
+    }
+
+    /// Directory of the bundle at `bundle_path`, keyed by the hash of its contents
+    pub fn bundle_dir(&self, bundle_path: &Path) -> Result<BundleDir> {
+        let bundle = std::fs::read(bundle_path)
+            .context(format!("Failed to read bundle: {}", bundle_path.display()))?;
+        let dir = self.root.join(hex::encode(sha256(&bundle)));
+        std::fs::create_dir_all(&dir)
+            .context(format!("Failed to create directory: {}", dir.display()))?;
+        Ok(BundleDir {
+            dir,
+            bundle: bundle_path.display().to_string(),
+        })
+    }
+}
+
+/// Per-bundle workspace directory
+#[derive(Debug, Clone)]
+pub struct BundleDir {
+    dir: PathBuf,
+    bundle: String,
+}
+
+impl BundleDir {
+    fn input_path(&self) -> PathBuf {
+        self.dir.join("input.bin")
+    }
+
+    fn request_id_path(&self) -> PathBuf {
+        self.dir.join("request_id")
+    }
+
+    pub fn artifact_path(&self) -> PathBuf {
+        self.dir.join("artifact.json")
+    }
+
+    fn status_path(&self) -> PathBuf {
+        self.dir.join("status.json")
+    }
+
+    /// Record the bundle's current state
+    fn set_state(&self, state: BundleState, error: Option<String>) -> Result<()> {
+        let status = BundleStatus {
+            bundle: self.bundle.clone(),
+            state,
+            error,
+        };
+        let json = serde_json::to_string_pretty(&status).context("Failed to serialize status")?;
+        std::fs::write(self.status_path(), json).context(format!(
+            "Failed to write status: {}",
+            self.status_path().display()
+        ))
+    }
+
+    /// Store the encoded guest input, discarding a request made for a different input
+    ///
+    /// # Returns
+    ///
+    /// `true` if a previously submitted request for the same input can be resumed.
+    pub fn prepare(&self, input_bytes: &[u8]) -> Result<bool> {
+        let same_input = std::fs::read(self.input_path())
+            .map(|stored| stored == input_bytes)
+            .unwrap_or(false);
+        let resumable = same_input && self.request_id_path().exists();
+
+        if !same_input {
+            remove_if_exists(&self.request_id_path())?;
+            std::fs::write(self.input_path(), input_bytes).context(format!(
+                "Failed to write input: {}",
+                self.input_path().display()
+            ))?;
+        }
+        if !resumable {
+            self.set_state(BundleState::Prepared, None)?;
+        }
+        Ok(resumable)
+    }
+
+    /// Return the stored artifact if it is still valid
+    ///
+    /// An artifact is valid if it holds a proof from `program_id` and
+    /// `circuit_version`, and its output commits `options` and `reference_time`.
+    pub fn valid_artifact(
+        &self,
+        program_id: &str,
+        circuit_version: &str,
+        options: &VerificationOptions,
+        reference_time: Option<u64>,
+    ) -> Option<ProofArtifact> {
+        let json = std::fs::read(self.artifact_path()).ok()?;
+        let artifact: ProofArtifact = serde_json::from_slice(&json).ok()?;
+        if artifact.program_id != program_id
+            || artifact.circuit_version != circuit_version
+            || artifact.proof.trim_start_matches("0x").is_empty()
+        {
+            return None;
+        }
+
+        let journal = hex::decode(artifact.journal.trim_start_matches("0x")).ok()?;
+        let output = VerifiedOutput::from_slice(&journal).ok()?;
+        output.check_options(options).ok()?;
+        if output.reference_time != reference_time.unwrap_or(0) {
+            return None;
+        }
+        Some(artifact)
+    }
+
+    /// Network request ID recorded by a previous run
+    pub fn request_id(&self) -> Option<B256> {
+        let id = std::fs::read_to_string(self.request_id_path()).ok()?;
+        id.trim().parse().ok()
+    }
+
+    /// Record a submitted network request so a later run can resume it
+    pub fn record_request(&self, request_id: B256) -> Result<()> {
+        std::fs::write(self.request_id_path(), format!("{}\n", request_id)).context(format!(
+            "Failed to write request ID: {}",
+            self.request_id_path().display()
+        ))?;
+        self.set_state(BundleState::Submitted, None)
+    }
+
+    /// Mark the bundle failed; its request is dropped so the next run proves it again
+    pub fn fail(&self, error: String) -> Result<()> {
+        remove_if_exists(&self.request_id_path())?;
+        self.set_state(BundleState::Failed, Some(error))
+    }
+
+    /// Write the artifact, mark the bundle proved and drop its request ID
+    pub fn complete(&self, artifact: &ProofArtifact) -> Result<()> {
+        write_proof_artifact(&self.artifact_path(), artifact)?;
+        remove_if_exists(&self.request_id_path())?;
+        self.set_state(BundleState::Proved, None)
+    }
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).context(format!("Failed to remove {}", path.display())),
+    }
+}
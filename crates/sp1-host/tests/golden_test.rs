@@ -0,0 +1,117 @@
+//! Golden tests: native and SP1 guest verification must agree
+//!
+//! Every sample bundle is verified natively with `AttestationVerifier` and by
+//! executing the SP1 guest on the same `ProverInput`, and the committed
+//! `ProverOutput`s must be byte-identical. A difference means the guest-core
+//! logic drifted from the host verifier (e.g. a zkVM-only code path such as
+//! the bounded base64 decoder), so the proof would attest to a different
+//! decision than `sp1-host verify` reports.
+
+use std::path::{Path, PathBuf};
+
+use sigstore_verifier::types::digest::Digest;
+use sigstore_verifier::types::environment::SigstoreEnvironment;
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_verifier::AttestationVerifier;
+use sigstore_zkvm_traits::guest::{with_output_version, ProverOutput, VerifiedOutput};
+use sigstore_zkvm_traits::traits::ZkVmProver;
+use sigstore_zkvm_traits::types::{InputEncoding, ProverInput};
+use sigstore_zkvm_traits::workflow::prepare_guest_input_for_environment;
+use sp1_host::prover::Sp1Prover;
+
+/// Fixed so the committed signing age does not depend on when the test runs
+const REFERENCE_TIME: u64 = 1_750_000_000;
+
+fn samples_dir() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("samples");
+    path
+}
+
+/// Every `*.sigstore.json` bundle in `samples/`, in a stable order
+fn sample_bundles() -> Vec<PathBuf> {
+    let mut bundles: Vec<PathBuf> = std::fs::read_dir(samples_dir())
+        .expect("Failed to read samples directory")
+        .map(|entry| entry.expect("Failed to read samples directory").path())
+        .filter(|path| path.to_string_lossy().ends_with(".sigstore.json"))
+        .collect();
+    bundles.sort();
+    assert!(!bundles.is_empty(), "No sample bundles found");
+    bundles
+}
+
+fn prepare_input(bundle: &Path, options: VerificationOptions) -> ProverInput {
+    prepare_guest_input_for_environment(
+        bundle,
+        &samples_dir().join("trusted_root.jsonl"),
+        &SigstoreEnvironment::Production,
+        options,
+    )
+    .expect("Failed to prepare guest input")
+    .with_reference_time(REFERENCE_TIME)
+}
+
+/// The output the guest should commit for `input`, computed natively
+fn native_output(input: &ProverInput) -> Vec<u8> {
+    let result = AttestationVerifier::new().verify_bundle_bytes(
+        &input.bundle_json,
+        input.verification_options.clone(),
+        &input.trust_bundle,
+        input.tsa_cert_chain.as_ref(),
+    );
+    let output = match result {
+        Ok(result) => ProverOutput::Verified(
+            VerifiedOutput::new(&result, &input.verification_options, input.reference_time)
+                .expect("Failed to build verified output"),
+        ),
+        Err(e) => {
+            assert!(input.prove_failures, "Native verification failed: {}", e);
+            ProverOutput::failed(&input.bundle_json, &input.verification_options, &e)
+                .expect("Failed to build failure output")
+        }
+    };
+    with_output_version(&output.as_slice())
+}
+
+fn assert_guest_matches_native(bundle: &Path, input: &ProverInput, encoding: InputEncoding) {
+    let expected = native_output(input);
+    let prover = Sp1Prover::new().expect("Failed to create SP1 prover");
+    let (public_values, _) = prover
+        .execute(input, encoding)
+        .unwrap_or_else(|e| panic!("{}: guest execution failed: {}", bundle.display(), e));
+
+    assert_eq!(
+        ProverOutput::from_slice(&public_values).expect("Failed to decode guest output"),
+        ProverOutput::from_slice(&expected).expect("Failed to decode native output"),
+        "{}: guest and native outputs differ ({:?})",
+        bundle.display(),
+        encoding
+    );
+    assert_eq!(
+        public_values,
+        expected,
+        "{}: committed bytes differ",
+        bundle.display()
+    );
+}
+
+#[test]
+fn test_guest_matches_native_verified() {
+    for bundle in sample_bundles() {
+        let input = prepare_input(&bundle, VerificationOptions::default());
+        assert_guest_matches_native(&bundle, &input, InputEncoding::default());
+    }
+}
+
+#[test]
+fn test_guest_matches_native_failed() {
+    // A digest no sample matches: both sides must reject at the same step with the same code
+    let mut options = VerificationOptions::default();
+    options.expected_digest = Some(Digest::sha256([0x42; 32]));
+    for bundle in sample_bundles() {
+        let input = prepare_input(&bundle, options.clone()).with_prove_failures();
+        assert_guest_matches_native(&bundle, &input, InputEncoding::default());
+    }
+}
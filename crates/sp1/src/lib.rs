@@ -1,15 +1,17 @@
-use sp1_sdk::{include_elf, EnvProver, SP1ProvingKey, SP1VerifyingKey};
+use sp1_sdk::{include_elf, Prover, ProverClient, SP1ProvingKey, SP1VerifyingKey};
 
 pub const SP1_SIGSTORE_ELF: &[u8] = include_elf!("sigstore-sp1-program");
 
 pub fn vk(elf: &[u8]) -> SP1VerifyingKey {
-    let env_prover = EnvProver::new();
-    let (_, vk) = env_prover.setup(elf);
+    // Setup is deterministic, so the local CPU prover gives the same keys as
+    // the network without depending on SP1_PROVER
+    let client = ProverClient::builder().cpu().build();
+    let (_, vk) = client.setup(elf);
     vk
 }
 
 pub fn pk(elf: &[u8]) -> SP1ProvingKey {
-    let env_prover = EnvProver::new();
-    let (pk, _) = env_prover.setup(elf);
+    let client = ProverClient::builder().cpu().build();
+    let (pk, _) = client.setup(elf);
     pk
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+//! Runtime backend selection
+//!
+//! The selected backend's prover is held as a [`DynProver`] trait object,
+//! together with its type-erased proving configuration.
+
+use crate::cli::{Backend, ProveArgs};
+use anyhow::{Context, Result};
+use risc0_host::config::{BoundlessConfig, ProvingStrategy, Risc0Config};
+use risc0_host::prover::Risc0Prover;
+use sigstore_zkvm_traits::error::ZkVmError;
+use sigstore_zkvm_traits::traits::{DynConfig, DynProver, ZkVmProver};
+use sigstore_zkvm_traits::types::{InputEncoding, ProverInput};
+use sp1_host::config::{AuctionConfig, NetworkMode, Sp1Config};
+use sp1_host::key_cache::KeyCache;
+use sp1_host::prover::Sp1Prover;
+
+/// Prover of the selected backend, with its proving configuration
+pub struct BackendProver {
+    backend: Backend,
+    prover: Box<dyn DynProver>,
+    config: Option<DynConfig>,
+}
+
+impl BackendProver {
+    /// Create the prover of `backend` without a proving configuration
+    ///
+    /// Such a prover can report its program identifier, but not prove.
+    pub fn new(backend: Backend) -> Result<Self, ZkVmError> {
+        let prover: Box<dyn DynProver> = match backend {
+            Backend::Sp1 => {
+                Box::new(Sp1Prover::new()?.with_key_cache(KeyCache::new(KeyCache::default_dir())))
+            }
+            Backend::Risc0 => Box::new(Risc0Prover::new()?),
+        };
+        Ok(BackendProver {
+            backend,
+            prover,
+            config: None,
+        })
+    }
+
+    /// Create the prover of `args.backend`, configured from the `prove` arguments
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an option the backend requires is missing.
+    pub fn from_prove_args(args: &ProveArgs) -> Result<Self> {
+        let input_encoding = InputEncoding {
+            compress: args.compress_input,
+        };
+
+        match args.backend {
+            Backend::Sp1 => {
+                let private_key = args.sp1.private_key.clone().context(
+                    "The SP1 backend requires --sp1-network-private-key or SP1_NETWORK_PRIVATE_KEY",
+                )?;
+                let cache_dir = args
+                    .sp1
+                    .pk_cache_dir
+                    .clone()
+                    .unwrap_or_else(KeyCache::default_dir);
+                let prover = Sp1Prover::new()?.with_key_cache(KeyCache::new(cache_dir));
+                let config = Sp1Config {
+                    proving_mode: args.sp1.mode,
+                    prover_type: Default::default(),
+                    private_key,
+                    rpc_url: args.sp1.rpc_url.clone(),
+                    network_mode: NetworkMode::Mainnet,
+                    input_encoding,
+                    auction: AuctionConfig::default(),
+                    workspace: None,
+                };
+                Ok(BackendProver {
+                    backend: Backend::Sp1,
+                    prover: Box::new(prover),
+                    config: Some(DynConfig::new(config)),
+                })
+            }
+            Backend::Risc0 => {
+                let boundless = match &args.risc0.rpc_url {
+                    Some(rpc_url) => Some(BoundlessConfig {
+                        rpc_url: rpc_url.clone(),
+                        private_key: args.risc0.private_key.clone().context(
+                            "Boundless proving requires --boundless-private-key or BOUNDLESS_PRIVATE_KEY",
+                        )?,
+                        program_url: args.risc0.program_url.clone(),
+                        proof_type: args.risc0.proof_type,
+                        min_price: args.risc0.min_price,
+                        max_price: args.risc0.max_price,
+                        timeout: args.risc0.timeout,
+                        ramp_up_period: args.risc0.ramp_up_period,
+                    }),
+                    None => None,
+                };
+                let config = Risc0Config {
+                    proving_strategy: match boundless {
+                        Some(_) => ProvingStrategy::Boundless,
+                        None => ProvingStrategy::Local,
+                    },
+                    boundless,
+                    input_encoding,
+                };
+                Ok(BackendProver {
+                    backend: Backend::Risc0,
+                    prover: Box::new(Risc0Prover::new()?),
+                    config: Some(DynConfig::new(config)),
+                })
+            }
+        }
+    }
+
+    /// Backend name, as recorded in proof artifacts (e.g. "sp1")
+    pub fn zkvm(&self) -> &'static str {
+        match self.backend {
+            Backend::Sp1 => "sp1",
+            Backend::Risc0 => "risc0",
+        }
+    }
+
+    /// Program identifier as a `0x`-prefixed hex string
+    pub fn program_identifier(&self) -> Result<String, ZkVmError> {
+        let id = self.prover.program_identifier_dyn()?;
+        Ok(format!("0x{}", id.trim_start_matches("0x")))
+    }
+
+    pub fn circuit_version(&self) -> String {
+        self.prover.circuit_version_dyn()
+    }
+
+    /// Generate a proof with the backend's configuration
+    ///
+    /// # Returns
+    ///
+    /// The public output and the proof bytes, as returned by [`DynProver::prove_dyn`].
+    pub async fn prove(&self, input: &ProverInput) -> Result<(Vec<u8>, Vec<u8>), ZkVmError> {
+        let config = self.config.as_ref().ok_or_else(|| {
+            ZkVmError::InvalidInput(format!(
+                "No proving configuration for the {} backend",
+                self.zkvm()
+            ))
+        })?;
+        self.prover.prove_dyn(config, input).await
+    }
+}
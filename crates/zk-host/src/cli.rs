@@ -0,0 +1,269 @@
+//! Command-line interface definitions for zk-host
+//!
+//! Defines all CLI commands, subcommands, and arguments using clap. Options
+//! that only apply to one backend are prefixed with its name.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use risc0_host::cli::BoundlessProofType;
+use sigstore_verifier::report::ReportFormat;
+use sigstore_verifier::types::environment::SigstoreEnvironment;
+use sigstore_zkvm_traits::codec::OutputCodecId;
+use sigstore_zkvm_traits::secret::SecretKey;
+use sp1_host::cli::ProvingMode;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "zk-host",
+    author,
+    version,
+    about = "zkVM host program for Sigstore attestation verification",
+    long_about = "Generate zero-knowledge proofs of Sigstore attestation bundle verification using SP1 or RISC0, selected with --backend"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Display the program identifier of the backend's guest (SP1 verifying key hash or RISC0 ImageID)
+    #[command(name = "program-id")]
+    ProgramId(ProgramIdArgs),
+
+    /// Generate a proof of attestation verification
+    Prove(ProveArgs),
+
+    /// Verify bundles natively (no proof) and write a report for CI
+    Verify(VerifyArgs),
+
+    /// Inspect bundles without verifying them
+    #[command(subcommand)]
+    Inspect(InspectCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum InspectCommand {
+    /// Report differences in certificates, identities, subjects, predicates
+    /// and tlog entries between two bundles; exits with 1 if they differ
+    Diff(DiffArgs),
+}
+
+/// zkVM backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    /// SP1, proving on the SP1 network
+    #[value(name = "sp1")]
+    Sp1,
+
+    /// RISC0, proving on Boundless
+    #[value(name = "risc0")]
+    Risc0,
+}
+
+#[derive(Args, Debug)]
+pub struct ProgramIdArgs {
+    /// zkVM backend
+    #[arg(long = "backend", value_enum, value_name = "BACKEND", required = true)]
+    pub backend: Backend,
+}
+
+#[derive(Args, Debug)]
+pub struct ProveArgs {
+    /// zkVM backend
+    #[arg(long = "backend", value_enum, value_name = "BACKEND", required = true)]
+    pub backend: Backend,
+
+    /// Path to the Sigstore attestation bundle JSON file
+    #[arg(long = "bundle", value_name = "PATH", required = true)]
+    pub bundle_path: PathBuf,
+
+    /// Path to the trusted root JSONL file
+    #[arg(long = "trust-roots", value_name = "PATH", required = true)]
+    pub trust_roots_path: PathBuf,
+
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Unix time to measure the attestation's signing age against; the guest
+    /// commits it and the age so contracts can enforce a maximum age
+    #[arg(long = "reference-time", value_name = "UNIX_SECONDS")]
+    pub reference_time: Option<u64>,
+
+    /// DCAP quote of the TEE runner that built the artifact; the guest verifies it
+    /// and commits a combined TEE + SLSA claim (requires --tee-root)
+    #[arg(long = "tee-quote", value_name = "PATH", requires = "tee_root_path")]
+    pub tee_quote_path: Option<PathBuf>,
+
+    /// Intel SGX root CA certificate (PEM) the quote's PCK chain must end in
+    #[arg(long = "tee-root", value_name = "PATH", requires = "tee_quote_path")]
+    pub tee_root_path: Option<PathBuf>,
+
+    /// Bundle of an independent builder for the same artifact; the guest verifies
+    /// both and commits a reproduced-build claim
+    #[arg(long = "reproduced-bundle", value_name = "PATH", conflicts_with = "tee_quote_path")]
+    pub reproduced_bundle_path: Option<PathBuf>,
+
+    /// Operator key (hex P-256 private key) to sign the artifact metadata with, so relayers can authenticate this prover
+    #[arg(long = "operator-key", env = "PROVER_OPERATOR_KEY", value_name = "KEY", hide_env_values = true)]
+    pub operator_key: Option<SecretKey>,
+
+    /// Path to write the proof artifact JSON file
+    #[arg(long = "output", value_name = "PATH")]
+    pub output_path: Option<PathBuf>,
+
+    /// Encoding of the committed output: raw (EVM parser layout), abi, borsh (Solana) or ssz
+    #[arg(long = "output-codec", value_name = "CODEC", default_value = "raw")]
+    pub output_codec: OutputCodecId,
+
+    /// Compress the guest input with zstd before upload (smaller upload, more guest cycles)
+    #[arg(long = "compress-input")]
+    pub compress_input: bool,
+
+    /// URL to POST proof metadata (program id, bundle digest, status) to when a proof completes or fails
+    #[arg(long = "notify-url", value_name = "URL")]
+    pub notify_url: Option<String>,
+
+    #[command(flatten)]
+    pub sp1: Sp1Args,
+
+    #[command(flatten)]
+    pub risc0: Risc0Args,
+}
+
+/// Options of the SP1 backend
+#[derive(Args, Debug)]
+pub struct Sp1Args {
+    /// SP1 network private key (hex-encoded), required with `--backend sp1`
+    #[arg(
+        long = "sp1-network-private-key",
+        env = "SP1_NETWORK_PRIVATE_KEY",
+        value_name = "WALLET_KEY",
+        hide_env_values = true
+    )]
+    pub private_key: Option<SecretKey>,
+
+    /// SP1 proving mode
+    #[arg(
+        long = "sp1-mode",
+        value_enum,
+        default_value = "groth16",
+        value_name = "MODE"
+    )]
+    pub mode: ProvingMode,
+
+    /// SP1 network RPC endpoint (default: the SDK's endpoint for mainnet)
+    #[arg(long = "sp1-rpc-url", env = "SP1_NETWORK_RPC_URL", value_name = "URL")]
+    pub rpc_url: Option<String>,
+
+    /// Directory of cached SP1 proving keys (default: $SP1_PK_CACHE_DIR or ~/.cache/sigstore-verifier/sp1)
+    #[arg(long = "sp1-pk-cache-dir", value_name = "DIR")]
+    pub pk_cache_dir: Option<PathBuf>,
+}
+
+/// Options of the RISC0 backend; without `--boundless-rpc-url` the proof is generated locally
+#[derive(Args, Debug)]
+pub struct Risc0Args {
+    /// Boundless RPC URL
+    #[arg(
+        long = "boundless-rpc-url",
+        env = "BOUNDLESS_RPC_URL",
+        value_name = "URL"
+    )]
+    pub rpc_url: Option<String>,
+
+    /// Boundless private key (hex-encoded), required with `--boundless-rpc-url`
+    #[arg(
+        long = "boundless-private-key",
+        env = "BOUNDLESS_PRIVATE_KEY",
+        value_name = "WALLET_KEY",
+        hide_env_values = true
+    )]
+    pub private_key: Option<SecretKey>,
+
+    /// Program URL (optional, uses embedded ELF if not provided)
+    #[arg(
+        long = "boundless-program-url",
+        env = "BOUNDLESS_PROGRAM_URL",
+        value_name = "URL"
+    )]
+    pub program_url: Option<String>,
+
+    /// Boundless proof type
+    #[arg(
+        long = "boundless-proof-type",
+        value_enum,
+        default_value = "groth16",
+        value_name = "TYPE"
+    )]
+    pub proof_type: BoundlessProofType,
+
+    /// Minimum price for proof generation
+    #[arg(long = "boundless-min-price", value_name = "WEI")]
+    pub min_price: Option<u128>,
+
+    /// Maximum price for proof generation
+    #[arg(long = "boundless-max-price", value_name = "WEI")]
+    pub max_price: Option<u128>,
+
+    /// Timeout in seconds
+    #[arg(long = "boundless-timeout", value_name = "SECONDS")]
+    pub timeout: Option<u32>,
+
+    /// Ramp-up period in seconds
+    #[arg(long = "boundless-ramp-up-period", value_name = "SECONDS")]
+    pub ramp_up_period: Option<u32>,
+}
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Path to the first bundle JSON file
+    #[arg(long = "left", value_name = "PATH", required = true)]
+    pub left_path: PathBuf,
+
+    /// Path to the second bundle JSON file
+    #[arg(long = "right", value_name = "PATH", required = true)]
+    pub right_path: PathBuf,
+
+    /// Print the differences as JSON
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Path to a Sigstore attestation bundle JSON file, or a `gh attestation download`
+    /// .jsonl file with one bundle per line (repeat for several files)
+    #[arg(long = "bundle", value_name = "PATH", required = true)]
+    pub bundle_paths: Vec<PathBuf>,
+
+    /// Path to the trusted root JSONL file
+    #[arg(long = "trust-roots", value_name = "PATH", required = true)]
+    pub trust_roots_path: PathBuf,
+
+    /// Sigstore deployment the bundle was signed against: production or staging
+    #[arg(long = "sigstore-env", value_name = "ENV", default_value = "production")]
+    pub environment: SigstoreEnvironment,
+
+    /// YAML policy file with the verification options (see sigstore_verifier::policy::file)
+    #[arg(long = "policy", value_name = "PATH")]
+    pub policy_path: Option<PathBuf>,
+
+    /// Report format: text, json, sarif (GitHub code scanning) or vsa (in-toto VSA)
+    #[arg(long = "report-format", value_name = "FORMAT", default_value = "text")]
+    pub report_format: ReportFormat,
+
+    /// Path to write the report (printed to stdout if omitted)
+    #[arg(long = "report-output", value_name = "PATH")]
+    pub report_output: Option<PathBuf>,
+
+    /// Append verified, failure_reason, subject_digest, repository, workflow_ref
+    /// and signing_time to $GITHUB_OUTPUT
+    #[arg(long = "github-output")]
+    pub github_output: bool,
+}
@@ -0,0 +1,286 @@
+//! Unified zkVM host program for Sigstore attestation verification
+//!
+//! This CLI tool generates zero-knowledge proofs of Sigstore attestation bundle
+//! verification with the SP1 or RISC0 zkVM, selected with `--backend`. Input
+//! preparation, the proof artifact and native verification are shared by
+//! both backends.
+
+mod backend;
+mod cli;
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use sigstore_verifier::parser::bundle::parse_bundle_from_path;
+use sigstore_verifier::parser::diff_bundles;
+use sigstore_verifier::report::exit_code;
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_zkvm_traits::codec::decode_output;
+use sigstore_zkvm_traits::notify::{notify_or_warn, ProofNotification, WebhookNotifier};
+use sigstore_zkvm_traits::operator::OperatorKey;
+use sigstore_zkvm_traits::utils::{
+    display_proof_result, display_verification_result, write_github_output, write_proof_artifact,
+    sign_proof_artifact, write_report, ProofArtifact,
+};
+use sigstore_zkvm_traits::workflow::{
+    load_tee_evidence, prepare_guest_input_for_environment, verify_local_file_for_environment,
+};
+
+use crate::backend::BackendProver;
+use crate::cli::{Cli, Commands, DiffArgs, InspectCommand, ProgramIdArgs, ProveArgs, VerifyArgs};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Load .env file if present (ignore errors if file doesn't exist)
+    dotenvy::dotenv().ok();
+
+    // Parse CLI arguments
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::ProgramId(args) => {
+            handle_program_id(args)?;
+        }
+        Commands::Prove(args) => {
+            handle_prove(args).await?;
+        }
+        Commands::Verify(args) => {
+            handle_verify(args)?;
+        }
+        Commands::Inspect(InspectCommand::Diff(args)) => {
+            handle_diff(args)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the program-id command
+///
+/// Displays the program identifier of the selected backend's guest.
+fn handle_program_id(args: ProgramIdArgs) -> Result<()> {
+    let prover = BackendProver::new(args.backend).context("Failed to create prover")?;
+
+    let program_id = prover
+        .program_identifier()
+        .context("Failed to get program identifier")?;
+
+    println!("Backend:         {}", prover.zkvm());
+    println!("Program ID:      {}", program_id);
+    println!("Circuit Version: {}", prover.circuit_version());
+
+    Ok(())
+}
+
+/// Handle the verify command
+///
+/// Verifies bundles natively, without generating a proof, and renders a
+/// report for CI systems. If any bundle fails, the process exits with the
+/// code of the first failure's class (see `FailureClass`).
+fn handle_verify(args: VerifyArgs) -> Result<()> {
+    let options = load_verification_options(args.policy_path.as_deref())?;
+    let mut reports = Vec::with_capacity(args.bundle_paths.len());
+    for bundle_path in &args.bundle_paths {
+        let bundle_reports = verify_local_file_for_environment(
+            bundle_path,
+            &args.trust_roots_path,
+            &args.environment,
+            options.clone(),
+        )
+        .context(format!("Failed to verify bundle: {}", bundle_path.display()))?;
+        reports.extend(bundle_reports);
+    }
+
+    write_report(&reports, args.report_format, args.report_output.as_deref())?;
+    if args.github_output {
+        write_github_output(&reports)?;
+    }
+
+    let code = exit_code(&reports);
+    if code != 0 {
+        let failed = reports.iter().filter(|report| !report.verified()).count();
+        eprintln!(
+            "❌ {} of {} bundles failed verification",
+            failed,
+            reports.len()
+        );
+        std::process::exit(code.into());
+    }
+    Ok(())
+}
+
+/// Handle the inspect diff command
+///
+/// Prints the differences between two bundles and exits with 1 if there are
+/// any, like `diff`.
+fn handle_diff(args: DiffArgs) -> Result<()> {
+    let left = parse_bundle_from_path(&args.left_path)
+        .context(format!("Failed to parse bundle: {}", args.left_path.display()))?;
+    let right = parse_bundle_from_path(&args.right_path)
+        .context(format!("Failed to parse bundle: {}", args.right_path.display()))?;
+    let diff = diff_bundles(&left, &right).context("Failed to compare bundles")?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print!("{}", diff);
+    }
+
+    if !diff.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Handle the prove command
+///
+/// Generates a proof of Sigstore attestation verification with the selected backend.
+async fn handle_prove(args: ProveArgs) -> Result<()> {
+    println!("Sigstore Proof Generation");
+    println!("=========================\n");
+
+    // Step 1: Prepare guest input
+    println!("📦 Preparing guest input...");
+    println!("   Bundle:       {}", args.bundle_path.display());
+    println!("   Trusted Root: {}", args.trust_roots_path.display());
+
+    let verification_options = load_verification_options(args.policy_path.as_deref())?;
+    let operator_key = args
+        .operator_key
+        .as_ref()
+        .map(OperatorKey::from_secret)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    let prover_input = prepare_guest_input_for_environment(
+        &args.bundle_path,
+        &args.trust_roots_path,
+        &args.environment,
+        verification_options,
+    )
+    .context("Failed to prepare guest input")?;
+    let prover_input = match args.reference_time {
+        Some(reference_time) => prover_input.with_reference_time(reference_time),
+        None => prover_input,
+    }
+    .with_output_codec(args.output_codec);
+    let prover_input = match (&args.tee_quote_path, &args.tee_root_path) {
+        (Some(quote_path), Some(root_path)) => prover_input.with_tee_evidence(
+            load_tee_evidence(quote_path, root_path).context("Failed to load TEE evidence")?,
+        ),
+        _ => prover_input,
+    };
+    let prover_input = match &args.reproduced_bundle_path {
+        Some(path) => prover_input.with_reproduced_bundle(
+            std::fs::read(path).with_context(|| format!("Failed to read reproduced bundle {}", path.display()))?,
+        ),
+        None => prover_input,
+    };
+
+    println!("✓ Guest input prepared\n");
+
+    // Step 2: Create prover
+    let prover = BackendProver::from_prove_args(&args)?;
+    println!("🔧 Initializing {} prover...", prover.zkvm());
+    let program_id = prover
+        .program_identifier()
+        .context("Failed to get program identifier")?;
+    let circuit_version = prover.circuit_version();
+    println!("✓ Prover initialized\n");
+
+    // Step 3: Generate proof
+    println!("⚙️  Generating proof...");
+    let notifier = args
+        .notify_url
+        .as_deref()
+        .map(WebhookNotifier::new)
+        .transpose()
+        .context("Failed to create webhook notifier")?;
+    let bundle_name = args.bundle_path.display().to_string();
+    let (journal, proof) = match prover.prove(&prover_input).await {
+        Ok(proved) => proved,
+        Err(e) => {
+            if let Some(ref notifier) = notifier {
+                let notification = ProofNotification::failed(
+                    prover.zkvm(),
+                    &program_id,
+                    &circuit_version,
+                    &bundle_name,
+                    &prover_input.bundle_json,
+                    &e.to_string(),
+                );
+                notify_or_warn(notifier, &notification).await;
+            }
+            return Err(e).context("Failed to generate proof");
+        }
+    };
+
+    if let Some(ref notifier) = notifier {
+        let notification = ProofNotification::proved(
+            prover.zkvm(),
+            &program_id,
+            &circuit_version,
+            &bundle_name,
+            &prover_input.bundle_json,
+            &journal,
+        );
+        notify_or_warn(notifier, &notification).await;
+    }
+
+    println!("✓ Proof generated successfully\n");
+
+    // Step 4: Display proof result
+    display_proof_result(&journal, &proof);
+
+    // Step 5: Decode and display verification result
+    println!("\n🔍 Decoding verification result...");
+    let (codec, public_values) = decode_output(&journal)
+        .map_err(|e| anyhow::anyhow!("Failed to decode public values from journal: {}", e))?;
+    let verification_result = public_values
+        .verification_result()
+        .map_err(|e| anyhow::anyhow!("Failed to decode verification result: {}", e))?;
+
+    display_verification_result(&verification_result);
+    println!("Output codec:   {}", codec);
+    println!(
+        "Options hash:   0x{}",
+        hex::encode(public_values.options_hash)
+    );
+    if public_values.reference_time != 0 {
+        println!("Reference time: {}", public_values.reference_time);
+        println!("Signing age:    {}s", public_values.signing_age);
+    }
+
+    // Step 6: Write artifact if output path provided
+    if let Some(ref output_path) = args.output_path {
+        println!("\n💾 Writing proof artifact...");
+
+        let mut artifact = ProofArtifact {
+            zkvm: prover.zkvm().to_string(),
+            program_id,
+            circuit_version,
+            journal: format!("0x{}", hex::encode(&journal)),
+            proof: format!("0x{}", hex::encode(&proof)),
+            operator_signature: None,
+        };
+        if let Some(ref operator_key) = operator_key {
+            sign_proof_artifact(&mut artifact, operator_key, &prover_input.bundle_json)?;
+        }
+
+        write_proof_artifact(output_path, &artifact).context("Failed to write proof artifact")?;
+    }
+
+    println!("\n✅ Success!");
+
+    Ok(())
+}
+
+/// Verification options from the `--policy` file, or the defaults if none was given
+fn load_verification_options(policy_path: Option<&Path>) -> Result<VerificationOptions> {
+    match policy_path {
+        Some(path) => VerificationOptions::from_policy_file(path)
+            .context(format!("Failed to load policy file: {}", path.display())),
+        None => Ok(VerificationOptions::default()),
+    }
+}
@@ -0,0 +1,186 @@
+//! End-to-end pipeline over all crates: sign → bundle → verify → prove → submit
+//!
+//! Each stage is a plain function so the tests in `tests/` and the
+//! `sigstore-e2e` binary run the same code:
+//!
+//! 1. [`sign`] signs an in-toto statement with a Fulcio certificate and logs it
+//!    in Rekor (`sigstore-fetcher`); without an OIDC token the pipeline starts
+//!    from a sample bundle instead.
+//! 2. [`Pipeline::prepare_input`] selects the trust material for the bundle and
+//!    [`Pipeline::verify`] verifies it natively (`sigstore-verifier`).
+//! 3. [`Pipeline::execute`] runs the SP1 guest in execute mode, which commits
+//!    the same output a proof would without generating one (`sp1-host`).
+//! 4. [`decode`] reads the committed output back and [`check_matches`]
+//!    compares it with the native result (`sigstore-zkvm-traits`).
+//! 5. [`dry_run`] sends the `verifyAndAttestWithZKProof` call of a proof
+//!    artifact to a node with `eth_call`, e.g. anvil with the contracts of
+//!    `contracts/` deployed, without submitting a transaction.
+
+use std::path::{Path, PathBuf};
+
+use alloy_primitives::Address;
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use sigstore_fetcher::rekor::{RekorClient, PUBLIC_GOOD_REKOR_URL};
+use sigstore_fetcher::signer::{sign_bundle, EphemeralSigner, FulcioClient, IdentityToken, PUBLIC_GOOD_FULCIO_URL};
+use sigstore_verifier::types::result::VerificationOptions;
+use sigstore_verifier::AttestationVerifier;
+use sigstore_zkvm_traits::chains::evm::EvmCall;
+use sigstore_zkvm_traits::codec::{decode_output, PublicValues};
+use sigstore_zkvm_traits::guest::VerifiedOutput;
+use sigstore_zkvm_traits::traits::ZkVmProver;
+use sigstore_zkvm_traits::types::{InputEncoding, ProverInput};
+use sigstore_zkvm_traits::utils::ProofArtifact;
+use sigstore_zkvm_traits::workflow::prepare_guest_input_local;
+use sp1_host::prover::Sp1Prover;
+
+/// The repository's `samples/` directory
+pub fn samples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../samples")
+}
+
+/// The repository's `contracts/test/fixtures/` directory, which holds proof artifacts
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../contracts/test/fixtures")
+}
+
+/// Sign an in-toto statement with the public-good Fulcio and Rekor instances
+///
+/// # Arguments
+///
+/// * `token` - OIDC identity token, e.g. `IdentityToken::from_github_actions()`
+/// * `statement_json` - The in-toto statement to sign
+///
+/// # Returns
+///
+/// The bundle JSON
+pub fn sign(token: &IdentityToken, statement_json: &[u8]) -> Result<Vec<u8>> {
+    let signer = EphemeralSigner::new(&FulcioClient::new(PUBLIC_GOOD_FULCIO_URL), token)
+        .context("Failed to obtain a signing certificate")?;
+    let bundle = sign_bundle(
+        &signer,
+        &RekorClient::new(PUBLIC_GOOD_REKOR_URL),
+        "application/vnd.in-toto+json",
+        statement_json,
+    )
+    .context("Failed to sign the statement")?;
+    serde_json::to_vec(&bundle).context("Failed to serialize bundle")
+}
+
+/// A bundle and the trust material to verify and prove it with
+pub struct Pipeline {
+    pub bundle_path: PathBuf,
+    pub trusted_root_path: PathBuf,
+    pub options: VerificationOptions,
+}
+
+impl Pipeline {
+    /// Pipeline over a bundle of `samples/`, e.g. `sample("13581567")`
+    pub fn sample(run: &str) -> Self {
+        let samples = samples_dir();
+        Pipeline {
+            bundle_path: samples.join(format!("actions-attest-build-provenance-attestation-{}.sigstore.json", run)),
+            trusted_root_path: samples.join("trusted_root.jsonl"),
+            options: VerificationOptions::default(),
+        }
+    }
+
+    /// Read the bundle and select its Fulcio and TSA chains from the trusted root
+    pub fn prepare_input(&self) -> Result<ProverInput> {
+        prepare_guest_input_local(&self.bundle_path, &self.trusted_root_path, self.options.clone())
+    }
+
+    /// Verify the input natively, as the guest will
+    ///
+    /// # Returns
+    ///
+    /// The output the guest should commit
+    pub fn verify(&self, input: &ProverInput) -> Result<VerifiedOutput> {
+        let result = AttestationVerifier::new()
+            .verify_bundle_bytes(
+                &input.bundle_json,
+                input.verification_options.clone(),
+                &input.trust_bundle,
+                input.tsa_cert_chain.as_ref(),
+            )
+            .context("Bundle verification failed")?;
+        VerifiedOutput::new(&result, &input.verification_options, input.reference_time).map_err(|e| anyhow!(e))
+    }
+
+    /// Run the SP1 guest on the input in execute mode
+    ///
+    /// # Returns
+    ///
+    /// The committed public values
+    pub fn execute(&self, input: &ProverInput) -> Result<Vec<u8>> {
+        let prover = Sp1Prover::new().context("Failed to create SP1 prover")?;
+        let (public_values, _) = prover
+            .execute(input, InputEncoding::default())
+            .context("Guest execution failed")?;
+        Ok(public_values)
+    }
+}
+
+/// Decode a committed output in any codec
+pub fn decode(public_values: &[u8]) -> Result<PublicValues> {
+    decode_output(public_values)
+        .map(|(_, values)| values)
+        .map_err(|e| anyhow!("Failed to decode guest output: {}", e))
+}
+
+/// Check that the guest committed the natively verified output
+pub fn check_matches(public_values: &[u8], native: &VerifiedOutput) -> Result<()> {
+    let committed = decode(public_values)?;
+    let expected = PublicValues::from_output(native).map_err(|e| anyhow!(e))?;
+    if committed != expected {
+        bail!("Guest output differs from the native result: {:?} != {:?}", committed, expected);
+    }
+    Ok(())
+}
+
+/// Read a proof artifact written by a host's `prove` command
+pub fn load_artifact(path: &Path) -> Result<ProofArtifact> {
+    let json = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_slice(&json).with_context(|| format!("Invalid proof artifact {}", path.display()))
+}
+
+/// The `verifyAndAttestWithZKProof` call of a proof artifact
+pub fn submission(artifact: &ProofArtifact, verifier: Address) -> Result<EvmCall> {
+    artifact
+        .to_verify_and_attest_calldata(verifier)
+        .map_err(|e| anyhow!("Failed to build call data: {}", e))
+}
+
+/// Execute `call` with `eth_call` on the node at `rpc_url`
+///
+/// Nothing is submitted: the node runs the call against its latest state and
+/// discards the changes.
+///
+/// # Returns
+///
+/// The call's return data
+///
+/// # Errors
+///
+/// Returns an error if the node is unreachable or the call reverts.
+pub fn dry_run(rpc_url: &str, call: &EvmCall) -> Result<Vec<u8>> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_call",
+        "params": [{ "to": call.to, "data": call.data }, "latest"],
+    });
+    let response: Value = reqwest::blocking::Client::new()
+        .post(rpc_url)
+        .json(&request)
+        .send()
+        .with_context(|| format!("Failed to reach {}", rpc_url))?
+        .json()
+        .context("Invalid JSON-RPC response")?;
+
+    if let Some(error) = response.get("error") {
+        bail!("eth_call reverted: {}", error);
+    }
+    let result = response["result"].as_str().ok_or_else(|| anyhow!("JSON-RPC response has no result"))?;
+    hex::decode(result.trim_start_matches("0x")).context("eth_call result is not hex")
+}
@@ -0,0 +1,92 @@
+//! Run the end-to-end pipeline on a sample bundle
+//!
+//! ```text
+//! cargo run -p sigstore-e2e
+//! cargo run -p sigstore-e2e -- --rpc-url http://localhost:8545 --verifier 0x...
+//! ```
+
+use std::path::PathBuf;
+
+use alloy_primitives::Address;
+use anyhow::{Context, Result};
+use clap::Parser;
+use sigstore_e2e::{
+    check_matches, decode, dry_run, fixtures_dir, load_artifact, sign, submission, Pipeline,
+};
+use sigstore_fetcher::signer::IdentityToken;
+
+#[derive(Parser, Debug)]
+#[command(name = "sigstore-e2e", about = "Sign, verify, prove and submit a Sigstore attestation end to end")]
+struct Cli {
+    /// In-toto statement to sign first; needs a GitHub Actions OIDC token
+    /// (`permissions: id-token: write`)
+    #[arg(long = "statement", value_name = "PATH", conflicts_with = "bundle_path")]
+    statement_path: Option<PathBuf>,
+
+    /// Bundle to verify and prove [default: a sample bundle]
+    #[arg(long = "bundle", value_name = "PATH")]
+    bundle_path: Option<PathBuf>,
+
+    /// Trusted root JSONL [default: samples/trusted_root.jsonl]
+    #[arg(long = "trust-roots", value_name = "PATH")]
+    trust_roots_path: Option<PathBuf>,
+
+    /// Proof artifact to submit [default: the SP1 fixture of contracts/test/fixtures]
+    #[arg(long = "artifact", value_name = "PATH")]
+    artifact_path: Option<PathBuf>,
+
+    /// JSON-RPC endpoint to dry-run the submission on, e.g. anvil
+    #[arg(long = "rpc-url", value_name = "URL", requires = "verifier")]
+    rpc_url: Option<String>,
+
+    /// Address of the deployed SigstoreAttestationVerifier
+    #[arg(long = "verifier", value_name = "ADDRESS")]
+    verifier: Option<Address>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut pipeline = Pipeline::sample("13581567");
+    if let Some(path) = cli.trust_roots_path {
+        pipeline.trusted_root_path = path;
+    }
+
+    if let Some(statement_path) = &cli.statement_path {
+        println!("✍️  Signing {}...", statement_path.display());
+        let statement = std::fs::read(statement_path).context("Failed to read statement")?;
+        let token = IdentityToken::from_github_actions().context("Failed to get an OIDC token")?;
+        let bundle_path = std::env::temp_dir().join("sigstore-e2e.sigstore.json");
+        std::fs::write(&bundle_path, sign(&token, &statement)?).context("Failed to write bundle")?;
+        println!("✓ Bundle written to {}\n", bundle_path.display());
+        pipeline.bundle_path = bundle_path;
+    } else if let Some(path) = cli.bundle_path {
+        pipeline.bundle_path = path;
+    }
+
+    println!("🔍 Verifying {}...", pipeline.bundle_path.display());
+    let input = pipeline.prepare_input()?;
+    let native = pipeline.verify(&input)?;
+    println!("✓ Verified natively\n");
+
+    println!("⚙️  Executing the SP1 guest...");
+    let public_values = pipeline.execute(&input)?;
+    check_matches(&public_values, &native)?;
+    let values = decode(&public_values)?;
+    println!("✓ Guest committed {} for {}\n", hex::encode(&values.subject_digest), values.oidc_subject);
+
+    let artifact_path = cli.artifact_path.unwrap_or_else(|| fixtures_dir().join("sp1-github.json"));
+    let artifact = load_artifact(&artifact_path)?;
+    let call = submission(&artifact, cli.verifier.unwrap_or_default())?;
+    match &cli.rpc_url {
+        Some(rpc_url) => {
+            println!("📨 Dry-running {} on {}...", artifact_path.display(), rpc_url);
+            let returned = dry_run(rpc_url, &call)?;
+            println!("✓ verifyAndAttestWithZKProof returned {} bytes", returned.len());
+        }
+        None => {
+            println!("📨 Call data for {} (pass --rpc-url to dry-run it):", artifact_path.display());
+            println!("{}", call.data);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! The pipeline stages against the repository's samples and contract fixtures
+
+use alloy_primitives::Address;
+use sigstore_e2e::{check_matches, decode, dry_run, fixtures_dir, load_artifact, submission, Pipeline};
+use sigstore_zkvm_traits::chains::evm::EvmCall;
+
+#[test]
+fn test_verify_execute_decode() {
+    let pipeline = Pipeline::sample("13581567");
+    let input = pipeline.prepare_input().expect("Failed to prepare input");
+    let native = pipeline.verify(&input).expect("Sample should verify");
+
+    let public_values = pipeline.execute(&input).expect("Guest execution failed");
+    check_matches(&public_values, &native).expect("Guest and native outputs differ");
+    assert_eq!(
+        hex::encode(decode(&public_values).unwrap().subject_digest),
+        "658913cfebe8a49165264e2b5e54ad99b3bdbfbc8cd281b3cfaa949a21588f18"
+    );
+}
+
+#[test]
+fn test_submission_matches_fixture() {
+    for name in ["sp1-github", "boundless-public"] {
+        let artifact = load_artifact(&fixtures_dir().join(format!("{}.json", name))).unwrap();
+        let expected: EvmCall =
+            serde_json::from_slice(&std::fs::read(fixtures_dir().join(format!("{}.evm.json", name))).unwrap())
+                .unwrap();
+        assert_eq!(submission(&artifact, Address::ZERO).unwrap(), expected, "{}", name);
+    }
+}
+
+#[test]
+#[ignore] // Requires anvil with the contracts deployed, see README.md
+fn test_dry_run_on_anvil() {
+    let rpc_url = std::env::var("E2E_RPC_URL").unwrap_or_else(|_| "http://localhost:8545".to_string());
+    let verifier: Address = std::env::var("E2E_VERIFIER")
+        .expect("E2E_VERIFIER must name the deployed SigstoreAttestationVerifier")
+        .parse()
+        .unwrap();
+
+    let artifact = load_artifact(&fixtures_dir().join("sp1-github.json")).unwrap();
+    let returned = dry_run(&rpc_url, &submission(&artifact, verifier).unwrap()).expect("Submission reverted");
+    assert!(!returned.is_empty());
+}
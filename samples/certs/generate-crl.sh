@@ -0,0 +1,57 @@
+#!/bin/bash
+# Generate a CA with two intermediates and a CRL revoking one of them, for the
+# revocation tests.
+#
+# Both intermediates point at http://crl.example.com/revocation-ca.crl through
+# their CRL distribution points extension.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+dir=$(mktemp -d)
+trap 'rm -rf "$dir"' EXIT
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/ca.key"
+openssl req -x509 -new -key "$dir/ca.key" -sha256 -days 3650 \
+  -subj "/O=sigstore.dev/CN=revocation-ca" \
+  -addext "basicConstraints=critical,CA:TRUE" \
+  -addext "keyUsage=critical,keyCertSign,cRLSign" \
+  -out revocation-ca.pem
+
+mkdir -p "$dir/db/new"
+touch "$dir/db/index.txt"
+echo 1000 > "$dir/db/serial"
+echo 1000 > "$dir/db/crlnumber"
+cat > "$dir/ca.cnf" <<CNF
+[ca]
+default_ca = ca_default
+[ca_default]
+dir = $dir/db
+database = \$dir/index.txt
+new_certs_dir = \$dir/new
+serial = \$dir/serial
+crlnumber = \$dir/crlnumber
+certificate = $PWD/revocation-ca.pem
+private_key = $dir/ca.key
+default_md = sha256
+default_days = 3650
+default_crl_days = 3650
+policy = policy
+unique_subject = no
+[policy]
+organizationName = supplied
+commonName = supplied
+[intermediate]
+basicConstraints = critical,CA:TRUE
+keyUsage = critical,keyCertSign
+crlDistributionPoints = URI:http://crl.example.com/revocation-ca.crl
+CNF
+
+for name in revocation-intermediate revocation-revoked; do
+  openssl ecparam -name prime256v1 -genkey -noout -out "$dir/$name.key"
+  openssl req -new -key "$dir/$name.key" -subj "/O=sigstore.dev/CN=$name" -out "$dir/$name.csr"
+  openssl ca -batch -config "$dir/ca.cnf" -extensions intermediate -notext \
+    -in "$dir/$name.csr" -out "$name.pem" 2>/dev/null
+done
+
+openssl ca -config "$dir/ca.cnf" -revoke revocation-revoked.pem 2>/dev/null
+openssl ca -config "$dir/ca.cnf" -gencrl -out revocation-ca.crl 2>/dev/null
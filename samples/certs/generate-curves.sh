@@ -0,0 +1,49 @@
+#!/bin/bash
+# Generate a CA and leaf certificate per supported ECDSA curve, plus a signature
+# by each leaf key over message.txt, for the signature verification tests.
+#
+# P-256 and secp256k1 sign with SHA-256, P-384 with SHA-384. The P-384 leaf also
+# signs with SHA-256 (p384-message-sha256.sig), as CMS signers may pick any digest.
+# p256-sha1-leaf.pem is the P-256 leaf re-issued with ecdsa-with-SHA1.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+printf 'DSSEv1 28 application/vnd.in-toto+json 2 {}' > message.txt
+
+gen() {
+  local curve="$1" digest="$2" name="$3"
+  local dir
+  dir=$(mktemp -d)
+
+  openssl ecparam -name "$curve" -genkey -noout -out "$dir/ca.key"
+  openssl req -x509 -new -key "$dir/ca.key" -"$digest" -days 3650 \
+    -subj "/O=sigstore.dev/CN=$name-ca" \
+    -addext "basicConstraints=critical,CA:TRUE" \
+    -addext "keyUsage=critical,keyCertSign" \
+    -out "$name-ca.pem"
+
+  openssl ecparam -name "$curve" -genkey -noout -out "$dir/leaf.key"
+  openssl req -new -key "$dir/leaf.key" -subj "/O=sigstore.dev/CN=$name-leaf" -out "$dir/leaf.csr"
+  printf 'keyUsage=critical,digitalSignature\nextendedKeyUsage=codeSigning\n' > "$dir/leaf.ext"
+  openssl x509 -req -in "$dir/leaf.csr" -CA "$name-ca.pem" -CAkey "$dir/ca.key" \
+    -CAserial "$dir/ca.srl" -CAcreateserial \
+    -"$digest" -days 3650 -extfile "$dir/leaf.ext" -out "$name-leaf.pem" 2>/dev/null
+
+  # Same leaf key, but signed by the CA with SHA-1 to exercise weak algorithm rejection
+  if [ "$name" = p256 ]; then
+    openssl x509 -req -in "$dir/leaf.csr" -CA "$name-ca.pem" -CAkey "$dir/ca.key" \
+      -CAserial "$dir/ca.srl" -sha1 -days 3650 -extfile "$dir/leaf.ext" \
+      -out "$name-sha1-leaf.pem" 2>/dev/null
+  fi
+
+  openssl dgst -"$digest" -sign "$dir/leaf.key" -out "$name-message.sig" message.txt
+  if [ "$digest" != sha256 ]; then
+    openssl dgst -sha256 -sign "$dir/leaf.key" -out "$name-message-sha256.sig" message.txt
+  fi
+
+  rm -rf "$dir"
+}
+
+gen prime256v1 sha256 p256
+gen secp384r1 sha384 p384
+gen secp256k1 sha256 secp256k1
@@ -0,0 +1,34 @@
+#!/bin/bash
+# Generate a root, intermediate and PCK leaf certificate, and the leaf's
+# PKCS#8 key, for the DCAP quote tests. The chain mirrors Intel's layout; the
+# tests build quotes signed by the leaf key.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+dir=$(mktemp -d)
+trap 'rm -rf "$dir"' EXIT
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/root.key"
+openssl req -x509 -new -key "$dir/root.key" -sha256 -days 3650 \
+  -subj "/O=sigstore.dev/CN=pck-root" \
+  -addext "basicConstraints=critical,CA:TRUE" \
+  -addext "keyUsage=critical,keyCertSign,cRLSign" \
+  -out pck-root.pem
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/intermediate.key"
+openssl req -new -key "$dir/intermediate.key" -subj "/O=sigstore.dev/CN=pck-platform-ca" \
+  -out "$dir/intermediate.csr"
+printf 'basicConstraints=critical,CA:TRUE,pathlen:0\nkeyUsage=critical,keyCertSign,cRLSign\n' \
+  > "$dir/intermediate.ext"
+openssl x509 -req -in "$dir/intermediate.csr" -CA pck-root.pem -CAkey "$dir/root.key" \
+  -CAserial "$dir/root.srl" -CAcreateserial -sha256 -days 3650 \
+  -extfile "$dir/intermediate.ext" -out pck-intermediate.pem 2>/dev/null
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/leaf.key"
+openssl pkcs8 -topk8 -nocrypt -in "$dir/leaf.key" -out pck-leaf.key
+openssl req -new -key "$dir/leaf.key" -subj "/O=sigstore.dev/CN=pck-leaf" -out "$dir/leaf.csr"
+printf 'basicConstraints=critical,CA:FALSE\nkeyUsage=critical,digitalSignature,nonRepudiation\n' \
+  > "$dir/leaf.ext"
+openssl x509 -req -in "$dir/leaf.csr" -CA pck-intermediate.pem -CAkey "$dir/intermediate.key" \
+  -CAserial "$dir/intermediate.srl" -CAcreateserial -sha256 -days 3650 \
+  -extfile "$dir/leaf.ext" -out pck-leaf.pem 2>/dev/null
@@ -0,0 +1,37 @@
+#!/bin/bash
+# Generate a root, intermediate and leaf certificate and a cosign "simple
+# signing" payload signed by the leaf key, for the simple signing tests.
+#
+# simple-signing.sig is the DER ECDSA signature over simple-signing.json, as
+# carried base64-encoded in the dev.cosignproject.cosign/signature annotation.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+dir=$(mktemp -d)
+trap 'rm -rf "$dir"' EXIT
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/root.key"
+openssl req -x509 -new -key "$dir/root.key" -sha256 -days 3650 \
+  -subj "/O=sigstore.dev/CN=simple-signing-root" \
+  -addext "basicConstraints=critical,CA:TRUE" \
+  -addext "keyUsage=critical,keyCertSign" \
+  -out simple-signing-root.pem
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/intermediate.key"
+openssl req -new -key "$dir/intermediate.key" -subj "/O=sigstore.dev/CN=simple-signing-intermediate" \
+  -out "$dir/intermediate.csr"
+printf 'basicConstraints=critical,CA:TRUE,pathlen:0\nkeyUsage=critical,keyCertSign\n' > "$dir/intermediate.ext"
+openssl x509 -req -in "$dir/intermediate.csr" -CA simple-signing-root.pem -CAkey "$dir/root.key" \
+  -CAserial "$dir/root.srl" -CAcreateserial -sha256 -days 3650 \
+  -extfile "$dir/intermediate.ext" -out simple-signing-intermediate.pem 2>/dev/null
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/leaf.key"
+openssl req -new -key "$dir/leaf.key" -subj "/O=sigstore.dev/CN=simple-signing-leaf" -out "$dir/leaf.csr"
+printf 'keyUsage=critical,digitalSignature\nextendedKeyUsage=codeSigning\n' > "$dir/leaf.ext"
+openssl x509 -req -in "$dir/leaf.csr" -CA simple-signing-intermediate.pem -CAkey "$dir/intermediate.key" \
+  -CAserial "$dir/intermediate.srl" -CAcreateserial -sha256 -days 3650 \
+  -extfile "$dir/leaf.ext" -out simple-signing-leaf.pem 2>/dev/null
+
+printf '%s' '{"critical":{"identity":{"docker-reference":"ghcr.io/automata-network/example"},"image":{"docker-manifest-digest":"sha256:6e2a2e1a5e3b4d1d8f7c0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d"},"type":"cosign container image signature"},"optional":{"creator":"cosign"}}' \
+  > simple-signing.json
+openssl dgst -sha256 -sign "$dir/leaf.key" -out simple-signing.sig simple-signing.json
@@ -0,0 +1,37 @@
+#!/bin/bash
+# Generate a root, intermediate and server certificate for rekor.example.com,
+# and an unrelated CA certificate, for the TLS key pinning tests.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+dir=$(mktemp -d)
+trap 'rm -rf "$dir"' EXIT
+
+ca() {
+  local name="$1"
+  openssl ecparam -name prime256v1 -genkey -noout -out "$dir/$name.key"
+  openssl req -x509 -new -key "$dir/$name.key" -sha256 -days 3650 \
+    -subj "/O=sigstore.dev/CN=$name" \
+    -addext "basicConstraints=critical,CA:TRUE" \
+    -addext "keyUsage=critical,keyCertSign" \
+    -out "$name.pem"
+}
+
+ca tls-root
+ca tls-unused
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/intermediate.key"
+openssl req -new -key "$dir/intermediate.key" -subj "/O=sigstore.dev/CN=tls-intermediate" \
+  -out "$dir/intermediate.csr"
+printf 'basicConstraints=critical,CA:TRUE,pathlen:0\nkeyUsage=critical,keyCertSign\n' > "$dir/intermediate.ext"
+openssl x509 -req -in "$dir/intermediate.csr" -CA tls-root.pem -CAkey "$dir/tls-root.key" \
+  -CAserial "$dir/root.srl" -CAcreateserial -sha256 -days 3650 \
+  -extfile "$dir/intermediate.ext" -out tls-intermediate.pem 2>/dev/null
+
+openssl ecparam -name prime256v1 -genkey -noout -out "$dir/leaf.key"
+openssl req -new -key "$dir/leaf.key" -subj "/O=sigstore.dev/CN=rekor.example.com" -out "$dir/leaf.csr"
+printf 'keyUsage=critical,digitalSignature\nextendedKeyUsage=serverAuth\nsubjectAltName=DNS:rekor.example.com\n' \
+  > "$dir/leaf.ext"
+openssl x509 -req -in "$dir/leaf.csr" -CA tls-intermediate.pem -CAkey "$dir/intermediate.key" \
+  -CAserial "$dir/intermediate.srl" -CAcreateserial -sha256 -days 3650 \
+  -extfile "$dir/leaf.ext" -out tls-leaf.pem 2>/dev/null
@@ -0,0 +1,57 @@
+#!/bin/bash
+# Generate synthetic Fulcio-style leaf certificates used by the identity parser tests.
+#
+# These certificates are self-signed and carry the SAN and Fulcio OIDC extensions
+# (OID 1.3.6.1.4.1.57264.1.x) that Fulcio issues for each CI provider. They are
+# only meant for extension parsing tests, not for chain verification.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+gen() {
+  local name="$1" san="$2"; shift 2
+  local cnf
+  cnf=$(mktemp)
+  {
+    echo "[req]"
+    echo "distinguished_name = dn"
+    echo "x509_extensions = ext"
+    echo "prompt = no"
+    echo "[dn]"
+    echo "O = sigstore.dev"
+    echo "CN = sigstore"
+    echo "[ext]"
+    echo "subjectAltName = critical, $san"
+    for line in "$@"; do echo "$line"; done
+  } > "$cnf"
+  openssl req -x509 -new -newkey ec -pkeyopt ec_paramgen_curve:prime256v1 -nodes \
+    -keyout /dev/null -days 3650 -config "$cnf" -out "$name.pem" 2>/dev/null
+  rm -f "$cnf"
+}
+
+GITLAB_PROJECT="https://gitlab.com/example-group/example-project"
+GITLAB_CONFIG="$GITLAB_PROJECT//.gitlab-ci.yml@refs/heads/main"
+
+gen gitlab-ci-leaf "URI:$GITLAB_CONFIG" \
+  "1.3.6.1.4.1.57264.1.8 = ASN1:UTF8String:https://gitlab.com" \
+  "1.3.6.1.4.1.57264.1.9 = ASN1:UTF8String:$GITLAB_CONFIG" \
+  "1.3.6.1.4.1.57264.1.10 = ASN1:UTF8String:9f7a53e9d1b2c3a4f5e6d7c8b9a0f1e2d3c4b5a6" \
+  "1.3.6.1.4.1.57264.1.11 = ASN1:UTF8String:gitlab-hosted" \
+  "1.3.6.1.4.1.57264.1.12 = ASN1:UTF8String:$GITLAB_PROJECT" \
+  "1.3.6.1.4.1.57264.1.13 = ASN1:UTF8String:9f7a53e9d1b2c3a4f5e6d7c8b9a0f1e2d3c4b5a6" \
+  "1.3.6.1.4.1.57264.1.14 = ASN1:UTF8String:refs/heads/main" \
+  "1.3.6.1.4.1.57264.1.15 = ASN1:UTF8String:42424242" \
+  "1.3.6.1.4.1.57264.1.16 = ASN1:UTF8String:https://gitlab.com/example-group" \
+  "1.3.6.1.4.1.57264.1.17 = ASN1:UTF8String:4242" \
+  "1.3.6.1.4.1.57264.1.18 = ASN1:UTF8String:$GITLAB_CONFIG" \
+  "1.3.6.1.4.1.57264.1.19 = ASN1:UTF8String:9f7a53e9d1b2c3a4f5e6d7c8b9a0f1e2d3c4b5a6" \
+  "1.3.6.1.4.1.57264.1.20 = ASN1:UTF8String:push" \
+  "1.3.6.1.4.1.57264.1.21 = ASN1:UTF8String:$GITLAB_PROJECT/-/jobs/123456" \
+  "1.3.6.1.4.1.57264.1.22 = ASN1:UTF8String:public"
+
+gen cloud-build-leaf "email:123456789012@cloudbuild.gserviceaccount.com" \
+  "1.3.6.1.4.1.57264.1.1 = DER:$(printf 'https://accounts.google.com' | xxd -p | tr -d '\n')" \
+  "1.3.6.1.4.1.57264.1.8 = ASN1:UTF8String:https://accounts.google.com"
+
+gen email-leaf "email:alice@example.com" \
+  "1.3.6.1.4.1.57264.1.1 = DER:$(printf 'https://github.com/login/oauth' | xxd -p | tr -d '\n')" \
+  "1.3.6.1.4.1.57264.1.8 = ASN1:UTF8String:https://github.com/login/oauth"